@@ -0,0 +1,10 @@
+#version 430 core
+
+layout (location = 0) in vec4 vout_color;
+
+layout (location = 0) out vec4 fout_frag_color;
+
+void main()
+{
+    fout_frag_color = vout_color;
+}
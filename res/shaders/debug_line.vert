@@ -0,0 +1,15 @@
+#version 430 core
+
+layout (location = 0) in vec3 vin_position;
+layout (location = 1) in vec4 vin_color;
+
+// Active camera's projection * view, set once per flush.
+layout (location = 0) uniform mat4 u_view_projection;
+
+layout (location = 0) out vec4 vout_color;
+
+void main()
+{
+    vout_color = vin_color;
+    gl_Position = u_view_projection * vec4(vin_position, 1.0);
+}
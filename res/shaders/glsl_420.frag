@@ -8,25 +8,17 @@ struct Frag_data_s
     vec3 wireframe_distances;
 };
 
+// Must match the std140 layout packed by the engine (see graphics::light::EnumLight::pack).
 struct Dir_light_s
 {
-    bool enabled;
-    vec3 direction;
-    vec3 ambient;
-    vec3 diffuse;
-    vec3 specular;
+    vec4 direction;    // xyz: direction the light travels in.
+    vec4 color;        // rgb: color, a: intensity.
 };
 
 struct Point_light_s
 {
-    bool enabled;
-    vec3 position;
-    vec3 ambient;
-    vec3 diffuse;
-    vec3 specular;
-    float constant;
-    float linear;
-    float quadratic;
+    vec4 position;     // xyz: world position, w: range.
+    vec4 color;        // rgb: color, a: intensity.
 };
 
 layout (location = 0) flat in uint vout_entity_ID;
@@ -55,15 +47,20 @@ layout (std140, binding = 10) uniform ubo_material
     float shininess;
 } Ubo_material;
 
-layout (std140, binding = 11) uniform ubo_dir_light
+layout (std140, binding = 11) uniform ubo_lights
 {
-    Dir_light_s properties[255];
-} Ubo_dir_light;
+    ivec4 counts;                  // x: directional lights, y: point lights.
+    Dir_light_s directional[4];
+    Point_light_s point[16];
+} Ubo_lights;
 
-layout (std140, binding = 12) uniform ubo_point_light
+layout (std140, binding = 12) uniform ubo_shadow
 {
-    Point_light_s properties[255];
-} Ubo_point_light;
+    mat4 light_space;              // Projection * view of the shadow casting directional light.
+    ivec4 enabled;                 // x: whether a shadow map has been rendered, y: whether this primitive receives shadows.
+} Ubo_shadow;
+
+layout (binding = 15) uniform sampler2D s_shadow_map;
 
 layout (location = 0) out vec4 fout_color;
 layout (location = 1) out uint fout_entity_ID;
@@ -125,14 +122,34 @@ vec3 extract_texture()
     return texture_color;
 }
 
-vec3 calculate_dir_light(Dir_light_s light, vec3 normal, vec3 view_dir)
+// Hard shadow test against the shadow map: 1.0 if the fragment is occluded from the light, 0.0 otherwise.
+float calculate_shadow(vec3 normal, vec3 light_dir)
+{
+    if (Ubo_shadow.enabled.x == 0 || Ubo_shadow.enabled.y == 0) return 0.0;
+
+    vec4 light_space_pos = Ubo_shadow.light_space * vec4(vout_frag_pos, 1.0);
+    vec3 projected = (light_space_pos.xyz / light_space_pos.w) * 0.5 + 0.5;
+
+    // Outside of the light's frustum, nothing can occlude it.
+    if (projected.z > 1.0 || any(lessThan(projected.xy, vec2(0.0))) || any(greaterThan(projected.xy, vec2(1.0))))
+    {
+        return 0.0;
+    }
+
+    float bias = max(0.005 * (1.0 - dot(normal, light_dir)), 0.0005);
+    float closest_depth = texture(s_shadow_map, projected.xy).r;
+    return (projected.z - bias) > closest_depth ? 1.0 : 0.0;
+}
+
+vec3 calculate_dir_light(Dir_light_s light, vec3 normal, vec3 view_dir, float shadow)
 {
-    if (!light.enabled) return vec3(0.0);
+    vec3 light_dir = normalize(-light.direction.xyz);
+    vec3 light_color = light.color.rgb * light.color.a;
 
-    float diff = max(dot(vout_vertex_data.vout_normal, light.direction), 0.0);  // Diffuse shading
+    float diff = max(dot(normal, light_dir), 0.0);  // Diffuse shading
 
     // Specular shading
-    vec3 reflect_dir = reflect(-light.direction, vout_vertex_data.vout_normal);
+    vec3 reflect_dir = reflect(-light_dir, normal);
     const float pi = 3.14159265;
     const float shininess = 16.0;
     const float energy_conservation = (2.0 + shininess) / (2.0 * pi);
@@ -140,62 +157,67 @@ vec3 calculate_dir_light(Dir_light_s light, vec3 normal, vec3 view_dir)
     float spec = energy_conservation * pow(max(dot(view_dir, reflect_dir), 0.0), shininess);
 
     // Combine results
-//    vec3 ambient  = light.ambient * vec3(texture(Ubo_material.diffuse, vout_vertex_data.vout_tex_coords));
-//    vec3 diffuse  = light.diffuse  * diff * vec3(texture(Ubo_material.diffuse, vout_vertex_data.vout_tex_coords));
-//    vec3 specular = light.specular * spec * vec3(texture(Ubo_material.specular, vout_vertex_data.vout_tex_coords));
-
-    vec3 ambient  = light.ambient;
-    vec3 diffuse  = light.diffuse  * diff;
-    vec3 specular = light.specular * spec;
-    return (ambient + diffuse + specular);
+    vec3 ambient  = 0.05 * light_color;
+    vec3 diffuse  = diff * light_color;
+    vec3 specular = 0.5 * spec * light_color;
+    return (ambient + (1.0 - shadow) * (diffuse + specular));
 }
 
 vec3 calculate_point_light(Point_light_s light, vec3 normal, vec3 view_dir)
 {
-    if (!light.enabled) return vec3(0.0);
+    vec3 to_light = light.position.xyz - vout_frag_pos;
+    float distance = length(to_light);
+    float range = light.position.w;
+    if (distance >= range) return vec3(0.0);
+
+    vec3 light_dir = to_light / max(distance, 0.0001);
+    vec3 light_color = light.color.rgb * light.color.a;
 
-    vec3 light_dir = normalize(light.position - vout_frag_pos);
     float diff = max(dot(normal, light_dir), 0.0);  // Diffuse shading.
 
     // Specular shading.
     vec3 reflect_dir = reflect(-light_dir, normal);
     float spec = pow(max(dot(view_dir, reflect_dir), 0.0), 16.0);
 
-    // Attenuation
-    float distance    = length(light.position - vout_frag_pos);
-    float attenuation = 1.0 / (light.constant + light.linear * distance + light.quadratic * (distance * distance));
+    // Attenuation, fading smoothly to nothing at the light's range.
+    float attenuation = pow(clamp(1.0 - (distance / range), 0.0, 1.0), 2.0);
 
     // Combine results.
-//    vec3 ambient  = light.ambient  * vec3(texture(material.diffuse, TexCoords));
-//    vec3 diffuse  = light.diffuse  * diff * vec3(texture(material.diffuse, TexCoords));
-//    vec3 specular = light.specular * spec * vec3(texture(material.specular, TexCoords));
-
-    vec3 ambient  = light.ambient;
-    vec3 diffuse  = light.diffuse  * diff;
-    vec3 specular = light.specular * spec;
-
-    ambient  *= attenuation;
-    diffuse  *= attenuation;
-    specular *= attenuation;
-    return (ambient + diffuse + specular);
+    vec3 ambient  = 0.05 * light_color;
+    vec3 diffuse  = diff * light_color;
+    vec3 specular = 0.5 * spec * light_color;
+    return (ambient + diffuse + specular) * attenuation;
 }
 
 void main() {
     vec4 texture_color = vec4(extract_texture(), vout_vertex_data.vout_frag_color.a);
 
     // Lighting calculations.
-    const vec3 light_color = vec3(0.75);
-    const vec3 light_dir = normalize(vec3(1920.0, 1080.0, 500.0) - vout_frag_pos);
-    const vec3 view_dir = normalize(vec3(-1.0) - vout_frag_pos);
+    vec3 view_dir = normalize(vec3(-1.0) - vout_frag_pos);
+    vec3 normal = normalize(vout_vertex_data.vout_normal);
+    vec3 result = vec3(0.0);
 
-    const float ambient_strength = 0.05;
-    vec3 ambient = ambient_strength * light_color;
+    int dir_light_count = min(Ubo_lights.counts.x, 4);
+    int point_light_count = min(Ubo_lights.counts.y, 16);
 
-    const float specular_strength = light_color != vec3(0.0) ? 0.5 : 0.0;
-    vec3 specular = specular_strength * light_color;
+    // Fallback to a default light until the scene submits its own.
+    if (dir_light_count + point_light_count == 0)
+    {
+        vec3 light_dir = normalize(vec3(1920.0, 1080.0, 500.0) - vout_frag_pos);
+        Dir_light_s dir_light = Dir_light_s(vec4(-light_dir, 0.0), vec4(vec3(0.75), 1.0));
+        result = calculate_dir_light(dir_light, normal, view_dir, 0.0);
+    }
 
-    Dir_light_s dir_light = Dir_light_s(true, light_dir, ambient, light_color, specular);
-    vec3 result = calculate_dir_light(dir_light, vout_vertex_data.vout_normal, view_dir);
+    for (int i = 0; i < dir_light_count; ++i)
+    {
+        // Only the first directional light casts shadows.
+        float shadow = (i == 0) ? calculate_shadow(normal, normalize(-Ubo_lights.directional[i].direction.xyz)) : 0.0;
+        result += calculate_dir_light(Ubo_lights.directional[i], normal, view_dir, shadow);
+    }
+    for (int i = 0; i < point_light_count; ++i)
+    {
+        result += calculate_point_light(Ubo_lights.point[i], normal, view_dir);
+    }
 
     // Fragment shader snippet
     // The procedure here is directly adapted from the example at
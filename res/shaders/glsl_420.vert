@@ -28,6 +28,7 @@ layout (location = 3) in uint in_normal;  // (32 bits): (|3 X 8 bits for floatin
 //layout (location = 3) in vec3 in_normal;
 layout (location = 4) in uint in_color;  // (32 bits): (|8 bits for alpha| + |8 bits for blue| + |8 bits for green| + |8 bits for red|).
 layout (location = 5) in vec2 in_tex_coords;
+layout (location = 6) in vec3 in_tangent;
 
 layout (location = 0) flat out uint vout_entity_ID;
 layout (location = 1) flat out int vout_texture_info;
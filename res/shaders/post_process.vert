@@ -0,0 +1,11 @@
+#version 420 core
+
+// Fullscreen triangle, generated from the vertex index alone (no vertex buffer needed).
+layout (location = 0) out vec2 vout_tex_coords;
+
+void main()
+{
+    vec2 position = vec2(float((gl_VertexID << 1) & 2), float(gl_VertexID & 2));
+    vout_tex_coords = position;
+    gl_Position = vec4(position * 2.0 - 1.0, 0.0, 1.0);
+}
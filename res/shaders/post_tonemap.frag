@@ -0,0 +1,19 @@
+#version 420 core
+
+layout (location = 0) in vec2 vout_tex_coords;
+
+// Color output of the previous pass.
+layout (binding = 0) uniform sampler2D s_source;
+
+layout (location = 0) out vec4 fout_frag_color;
+
+const float c_gamma = 2.2;
+
+void main()
+{
+    vec3 color = texture(s_source, vout_tex_coords).rgb;
+
+    // Reinhard tonemapping, then gamma correction.
+    vec3 mapped = color / (color + vec3(1.0));
+    fout_frag_color = vec4(pow(mapped, vec3(1.0 / c_gamma)), 1.0);
+}
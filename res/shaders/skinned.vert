@@ -0,0 +1,74 @@
+#version 460 core
+
+// Skinned variant of glsl_460.vert, for entities shaded as EnumPrimitiveShading::Skinned. Pair it with glsl_460.frag.
+
+// Outputs.
+struct Frag_data_s
+{
+    vec3 vout_normal;
+    vec4 vout_frag_color;
+    vec2 vout_tex_coords;
+    vec3 wireframe_distances;
+};
+
+layout (std140, binding = 0) uniform ubo_camera
+{
+    mat4 m_view;
+    mat4 m_projection;
+} Ubo_camera;
+
+layout (std430, binding = 1) buffer ssbo_models
+{
+    mat4 m_matrix[];
+} Ssbo_models;
+
+// Pose of the skeleton, see C_BONES_UBO_BINDING and C_MAX_BONES.
+layout (std140, binding = 13) uniform ubo_bones
+{
+    mat4 m_bones[128];
+} Ubo_bones;
+
+layout (location = 0) in uint in_entity_ID;
+layout (location = 1) in int in_texture_info;
+layout (location = 2) in vec3 in_position;
+layout (location = 3) in uint in_normal;
+layout (location = 4) in uint in_color;
+layout (location = 5) in vec2 in_tex_coords;
+layout (location = 6) in vec3 in_tangent;
+layout (location = 7) in uint in_bone_ids;
+layout (location = 8) in vec4 in_bone_weights;
+
+layout (location = 0) flat out uint vout_entity_ID;
+layout (location = 1) out Frag_data_s vout_vertex_data;
+layout (location = 5) out vec3 vout_frag_pos;
+
+// Inverse of Vertex::pack_normal().
+vec3 unpack_normal(uint packed_normal) {
+    vec3 magnitude = vec3((packed_normal >> 24) & 0xFFu, (packed_normal >> 16) & 0xFFu, (packed_normal >> 8) & 0xFFu) / 100.0;
+    vec3 signs = vec3((packed_normal & 0x1u) != 0u ? -1.0 : 1.0, (packed_normal & 0x2u) != 0u ? -1.0 : 1.0,
+    (packed_normal & 0x8u) != 0u ? -1.0 : 1.0);
+    return magnitude * signs;
+}
+
+void main() {
+    mat4 skin_matrix = mat4(0.0);
+    for (int influence = 0; influence < 4; influence++) {
+        uint bone_id = (in_bone_ids >> (influence * 8)) & 0xFFu;
+        skin_matrix += Ubo_bones.m_bones[min(bone_id, 127u)] * in_bone_weights[influence];
+    }
+    // Vertices not skinned to any bone stay in bind pose.
+    if (in_bone_weights == vec4(0.0)) {
+        skin_matrix = mat4(1.0);
+    }
+
+    mat4 model_matrix = Ssbo_models.m_matrix[in_entity_ID] * skin_matrix;
+    vec4 world_position = model_matrix * vec4(in_position, 1.0);
+
+    gl_Position = Ubo_camera.m_projection * Ubo_camera.m_view * world_position;
+    vout_entity_ID = in_entity_ID;
+    vout_vertex_data.vout_normal = normalize(mat3(model_matrix) * unpack_normal(in_normal));
+    vout_vertex_data.vout_tex_coords = in_tex_coords;
+    vout_vertex_data.vout_frag_color = vec4((in_color & 0x000000FFu) / 255.0, ((in_color & 0x0000FF00u) >> 8) / 255.0,
+    ((in_color & 0x00FF0000u) >> 16) / 255.0, ((in_color & 0xFF000000u) >> 24) / 255.0);
+    vout_frag_pos = vec3(world_position);
+}
@@ -0,0 +1,21 @@
+#version 430 core
+
+layout (location = 0) in vec2 vout_tex_coords;
+layout (location = 1) in vec4 vout_color;
+
+// Single channel signed distance field, 0.5 being the glyph's outline.
+layout (location = 1) uniform sampler2D u_font_atlas;
+
+layout (location = 0) out vec4 fout_frag_color;
+
+void main()
+{
+    float distance = texture(u_font_atlas, vout_tex_coords).r;
+    // Antialias over about one screen pixel, whatever the text's size.
+    float smoothing = fwidth(distance);
+    float alpha = smoothstep(0.5 - smoothing, 0.5 + smoothing, distance);
+    if (alpha <= 0.0) {
+        discard;
+    }
+    fout_frag_color = vec4(vout_color.rgb, vout_color.a * alpha);
+}
@@ -0,0 +1,18 @@
+#version 430 core
+
+layout (location = 0) in vec2 vin_position;
+layout (location = 1) in vec2 vin_tex_coords;
+layout (location = 2) in vec4 vin_color;
+
+// Orthographic projection in pixels, from the top left corner of the window.
+layout (location = 0) uniform mat4 u_projection;
+
+layout (location = 0) out vec2 vout_tex_coords;
+layout (location = 1) out vec4 vout_color;
+
+void main()
+{
+    vout_tex_coords = vin_tex_coords;
+    vout_color = vin_color;
+    gl_Position = u_projection * vec4(vin_position, 0.0, 1.0);
+}
@@ -22,13 +22,22 @@
  SOFTWARE.
 */
 
-use std::fmt::{Display, Formatter};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::{Debug, Display, Formatter};
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::sync::{Arc, mpsc};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread::JoinHandle;
 
 use assimp;
 use assimp::import::structs::PrimitiveType;
 
 #[cfg(feature = "debug")]
 use crate::Engine;
+use crate::assets::skeleton::{AnimationClip, Skeleton};
+use crate::math::{Aabb, Vec2, Vec3};
 use crate::TraitHint;
 use crate::utils::macros::logger::*;
 
@@ -51,6 +60,7 @@ pub enum EnumAssetError {
   InvalidFileExtension,
   InvalidRead,
   InvalidShapeData,
+  InvalidAnimation,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, PartialOrd, Ord, Eq, Hash)]
@@ -65,15 +75,27 @@ impl Default for EnumAssetPrimitiveMode {
   }
 }
 
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd, Ord, Eq, Hash)]
+pub enum EnumNormalMode {
+  // One normal per face, shared by all three corners of the triangle.
+  Flat,
+  // Area-weighted average of the normals of all faces sharing a vertex.
+  Smooth,
+}
+
 #[derive(Debug, Clone, PartialEq, PartialOrd, Ord, Eq, Hash)]
 pub enum EnumAssetHint {
   VertexDataIs(EnumAssetPrimitiveMode),
   SplitLargeMeshes(Option<usize>),
-  GenerateNormals(bool),
+  GenerateNormals(EnumNormalMode),
+  ForceNormals(bool),
+  GenerateTangents(bool),
   GenerateUvs(bool),
+  Cache(bool),
   Triangulate(bool),
   ReduceMeshes(bool),
   OnlyTriangles(bool),
+  FlipWinding(bool),
 }
 
 impl EnumAssetHint {
@@ -82,10 +104,14 @@ impl EnumAssetHint {
       (EnumAssetHint::VertexDataIs(_), EnumAssetHint::VertexDataIs(_)) => true,
       (EnumAssetHint::SplitLargeMeshes(_), EnumAssetHint::SplitLargeMeshes(_)) => true,
       (EnumAssetHint::GenerateNormals(_), EnumAssetHint::GenerateNormals(_)) => true,
+      (EnumAssetHint::ForceNormals(_), EnumAssetHint::ForceNormals(_)) => true,
+      (EnumAssetHint::GenerateTangents(_), EnumAssetHint::GenerateTangents(_)) => true,
       (EnumAssetHint::GenerateUvs(_), EnumAssetHint::GenerateUvs(_)) => true,
+      (EnumAssetHint::Cache(_), EnumAssetHint::Cache(_)) => true,
       (EnumAssetHint::Triangulate(_), EnumAssetHint::Triangulate(_)) => true,
       (EnumAssetHint::ReduceMeshes(_), EnumAssetHint::ReduceMeshes(_)) => true,
       (EnumAssetHint::OnlyTriangles(_), EnumAssetHint::OnlyTriangles(_)) => true,
+      (EnumAssetHint::FlipWinding(_), EnumAssetHint::FlipWinding(_)) => true,
       _ => false
     };
   }
@@ -94,11 +120,15 @@ impl EnumAssetHint {
     return match self {
       EnumAssetHint::VertexDataIs(flag) => flag,
       EnumAssetHint::SplitLargeMeshes(vertex_limit) => vertex_limit,
-      EnumAssetHint::GenerateNormals(flag) => flag,
+      EnumAssetHint::GenerateNormals(mode) => mode,
+      EnumAssetHint::ForceNormals(flag) => flag,
+      EnumAssetHint::GenerateTangents(flag) => flag,
       EnumAssetHint::GenerateUvs(flag) => flag,
+      EnumAssetHint::Cache(flag) => flag,
       EnumAssetHint::Triangulate(flag) => flag,
       EnumAssetHint::ReduceMeshes(flag) => flag,
-      EnumAssetHint::OnlyTriangles(flag) => flag
+      EnumAssetHint::OnlyTriangles(flag) => flag,
+      EnumAssetHint::FlipWinding(flag) => flag
     };
   }
 }
@@ -111,14 +141,176 @@ impl Display for EnumAssetError {
 
 impl std::error::Error for EnumAssetError {}
 
-pub struct AssetInfo<'a> {
+/// Geometry of a single mesh, copied out of the imported scene.
+pub(crate) struct AssetMesh {
+  pub(crate) m_name: String,
+  pub(crate) m_positions: Vec<Vec3<f32>>,
+  // Generated normals if requested, otherwise the file's own, if any.
+  pub(crate) m_normals: Vec<Vec3<f32>>,
+  pub(crate) m_texture_coords: Vec<Vec2<f32>>,
+  // Per-vertex tangents for normal mapping, if requested.
+  pub(crate) m_tangents: Option<Vec<Vec3<f32>>>,
+  pub(crate) m_faces: Vec<[u32; 3]>,
+  // Vertices influenced by each bone, as (bone name, [(vertex index, weight)]).
+  pub(crate) m_bone_weights: Vec<(String, Vec<(u32, f32)>)>,
+}
+
+/// Parsed asset, owning all of its data so that it can be parsed on a worker thread and handed over to the main one.
+pub struct AssetInfo {
+  // Path the asset was loaded from, as requested.
+  pub(crate) m_file_path: String,
   pub(crate) m_is_indexed: bool,
-  pub(crate) m_data: assimp::scene::Scene<'a>,
+  // Reverse triangle winding and normals when baking the entity, for assets imported with a mirrored convention.
+  pub(crate) m_flip_winding: bool,
+  pub(crate) m_meshes: Vec<AssetMesh>,
+  // Width of each texture embedded in the file.
+  pub(crate) m_embedded_texture_widths: Vec<u32>,
+  // Local space extents of all meshes combined, used for frustum culling.
+  pub(crate) m_bounds: Aabb,
+  // Bone hierarchy and animations, for rigged models only.
+  pub(crate) m_skeleton: Option<Skeleton>,
+  pub(crate) m_animation_clips: Vec<AnimationClip>,
+}
+
+/// Handle to an asset being parsed on a worker thread, see [AssetLoader::load_async].
+pub struct AssetHandle {
+  m_path: String,
+  m_receiver: mpsc::Receiver<Result<AssetInfo, EnumAssetError>>,
+  m_worker: Option<JoinHandle<()>>,
+}
+
+impl AssetHandle {
+  /// Check whether the worker thread is done parsing, without blocking. Returns the asset only once, subsequent
+  /// polls will return `None`.
+  pub fn poll(&mut self) -> Option<Result<AssetInfo, EnumAssetError>> {
+    if self.m_worker.is_none() {
+      return None;
+    }
+    
+    return match self.m_receiver.try_recv() {
+      Ok(result) => {
+        let _ = self.m_worker.take().unwrap().join();
+        Some(result)
+      }
+      Err(mpsc::TryRecvError::Empty) => None,
+      Err(mpsc::TryRecvError::Disconnected) => {
+        // Worker died before sending anything back.
+        log!(EnumLogColor::Red, "ERROR", "[AssetLoader] -->\t Worker thread for asset {0} stopped unexpectedly!",
+          self.m_path);
+        let _ = self.m_worker.take().unwrap().join();
+        Some(Err(EnumAssetError::InvalidRead))
+      }
+    };
+  }
+  
+  /// Block until the worker thread is done parsing.
+  pub fn wait(&mut self) -> Result<AssetInfo, EnumAssetError> {
+    if self.m_worker.is_none() {
+      log!(EnumLogColor::Red, "ERROR", "[AssetLoader] -->\t Asset {0} already retrieved!", self.m_path);
+      return Err(EnumAssetError::InvalidRead);
+    }
+    
+    let result = self.m_receiver.recv().unwrap_or(Err(EnumAssetError::InvalidRead));
+    let _ = self.m_worker.take().unwrap().join();
+    return result;
+  }
+  
+  pub fn get_path(&self) -> &str {
+    return &self.m_path;
+  }
+  
+  pub fn is_done(&self) -> bool {
+    return self.m_worker.is_none();
+  }
 }
 
+/// Outcome of loading a whole folder : every asset that loaded, and every file that didn't along with why.
 #[derive(Debug)]
+pub struct FolderLoadResult<T> {
+  pub m_assets: Vec<T>,
+  pub m_failures: Vec<(PathBuf, EnumAssetError)>,
+}
+
+impl<T> Default for FolderLoadResult<T> {
+  fn default() -> Self {
+    return Self {
+      m_assets: Vec::new(),
+      m_failures: Vec::new(),
+    };
+  }
+}
+
+/// Handle to a folder being loaded on a worker thread, see [AssetLoader::load_from_folder_async]. Progress can be
+/// queried at any time, for loading screens.
+pub struct FolderLoadHandle {
+  m_path: String,
+  // Files done (loaded or not) and files in total.
+  m_progress: Arc<(AtomicUsize, AtomicUsize)>,
+  m_receiver: mpsc::Receiver<Result<FolderLoadResult<AssetInfo>, EnumAssetError>>,
+  m_worker: Option<JoinHandle<()>>,
+}
+
+impl FolderLoadHandle {
+  /// Files done so far, loaded or not, out of all files in the folder. The total is 0 until the folder has been listed.
+  pub fn get_progress(&self) -> (usize, usize) {
+    return (self.m_progress.0.load(Ordering::Acquire), self.m_progress.1.load(Ordering::Acquire));
+  }
+  
+  /// See [AssetHandle::poll].
+  pub fn poll(&mut self) -> Option<Result<FolderLoadResult<AssetInfo>, EnumAssetError>> {
+    if self.m_worker.is_none() {
+      return None;
+    }
+    
+    return match self.m_receiver.try_recv() {
+      Ok(result) => {
+        let _ = self.m_worker.take().unwrap().join();
+        Some(result)
+      }
+      Err(mpsc::TryRecvError::Empty) => None,
+      Err(mpsc::TryRecvError::Disconnected) => {
+        log!(EnumLogColor::Red, "ERROR", "[AssetLoader] -->\t Worker thread for folder {0} stopped unexpectedly!",
+          self.m_path);
+        let _ = self.m_worker.take().unwrap().join();
+        Some(Err(EnumAssetError::InvalidRead))
+      }
+    };
+  }
+  
+  /// Block until the worker thread is done loading the whole folder.
+  pub fn wait(&mut self) -> Result<FolderLoadResult<AssetInfo>, EnumAssetError> {
+    if self.m_worker.is_none() {
+      log!(EnumLogColor::Red, "ERROR", "[AssetLoader] -->\t Folder {0} already retrieved!", self.m_path);
+      return Err(EnumAssetError::InvalidRead);
+    }
+    
+    let result = self.m_receiver.recv().unwrap_or(Err(EnumAssetError::InvalidRead));
+    let _ = self.m_worker.take().unwrap().join();
+    return result;
+  }
+  
+  pub fn get_path(&self) -> &str {
+    return &self.m_path;
+  }
+  
+  pub fn is_done(&self) -> bool {
+    return self.m_worker.is_none();
+  }
+}
+
 pub struct AssetLoader {
   m_hints: Vec<EnumAssetHint>,
+  // Parsed assets, keyed by their canonical path, shared between all loads of the same file.
+  m_cache: RefCell<HashMap<PathBuf, Rc<AssetInfo>>>,
+}
+
+impl Debug for AssetLoader {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    return f.debug_struct("AssetLoader")
+      .field("m_hints", &self.m_hints)
+      .field("m_cache", &self.m_cache.borrow().keys().collect::<Vec<_>>())
+      .finish();
+  }
 }
 
 impl TraitHint<EnumAssetHint> for AssetLoader {
@@ -127,24 +319,162 @@ impl TraitHint<EnumAssetHint> for AssetLoader {
       self.m_hints.remove(position);
     }
     
+    // Cached assets were parsed with the previous hints, so they are stale now.
+    if !hint.is(&EnumAssetHint::Cache(true)) {
+      self.clear_cache();
+    }
     self.m_hints.push(hint);
   }
   
   fn reset_hints(&mut self) {
     self.m_hints.clear();
+    self.clear_cache();
   }
 }
 
 impl AssetLoader {
   pub fn new() -> Self {
     return Self {
-      m_hints: Vec::with_capacity(6)
+      m_hints: Vec::with_capacity(6),
+      m_cache: RefCell::new(HashMap::new()),
+    };
+  }
+  
+  pub fn clear_cache(&mut self) {
+    self.m_cache.borrow_mut().clear();
+  }
+  
+  pub fn get_cache_size(&self) -> usize {
+    return self.m_cache.borrow().len();
+  }
+  
+  /// Load every file directly inside a folder, skipping the ones that fail to load.
+  pub fn load_from_folder(&self, folder_path_str: &str) -> Result<Vec<Rc<AssetInfo>>, EnumAssetError> {
+    return Ok(self.load_from_folder_with_progress(folder_path_str, |_loaded, _total| {})?.m_assets);
+  }
+  
+  /// Load every file directly inside a folder, calling `on_progress` with the number of files done so far and the
+  /// total after each one, whether it loaded or not. Files that failed are reported along with the loaded assets.
+  pub fn load_from_folder_with_progress(&self, folder_path_str: &str, mut on_progress: impl FnMut(usize, usize))
+    -> Result<FolderLoadResult<Rc<AssetInfo>>, EnumAssetError> {
+    let file_paths: Vec<PathBuf> = Self::list_folder(folder_path_str)?;
+    let mut result = FolderLoadResult::default();
+    
+    for (index, file_path) in file_paths.iter().enumerate() {
+      let load_result = file_path.to_str().ok_or(EnumAssetError::InvalidPath)
+        .and_then(|file_path_str| self.load(file_path_str));
+      match load_result {
+        Ok(asset) => result.m_assets.push(asset),
+        Err(err) => result.m_failures.push((file_path.clone(), err)),
+      }
+      on_progress(index + 1, file_paths.len());
+    }
+    
+    Self::log_folder_result(folder_path_str, result.m_assets.len(), &result.m_failures);
+    return Ok(result);
+  }
+  
+  /// Load every file directly inside a folder on a worker thread, see [Self::load_async]. The main thread can keep
+  /// rendering meanwhile, showing [FolderLoadHandle::get_progress] on a loading screen.
+  pub fn load_from_folder_async(&self, folder_path_str: &str) -> FolderLoadHandle {
+    let (sender, receiver) = mpsc::channel();
+    let progress: Arc<(AtomicUsize, AtomicUsize)> = Arc::new((AtomicUsize::new(0), AtomicUsize::new(0)));
+    let worker_progress = progress.clone();
+    let hints = self.m_hints.clone();
+    let path = String::from(folder_path_str);
+    
+    log!(EnumLogColor::Purple, "INFO", "[AssetLoader] -->\t Loading folder {0} asynchronously...", folder_path_str);
+    
+    let worker = std::thread::spawn(move || {
+      let loader = AssetLoader { m_hints: hints, m_cache: RefCell::new(HashMap::new()) };
+      let file_paths: Vec<PathBuf> = match Self::list_folder(&path) {
+        Ok(file_paths) => file_paths,
+        Err(err) => {
+          let _ = sender.send(Err(err));
+          return;
+        }
+      };
+      worker_progress.1.store(file_paths.len(), Ordering::Release);
+      
+      let mut result = FolderLoadResult::default();
+      for file_path in file_paths.iter() {
+        let parse_result = file_path.to_str().ok_or(EnumAssetError::InvalidPath)
+          .and_then(|file_path_str| loader.parse(file_path_str));
+        match parse_result {
+          Ok(asset) => result.m_assets.push(asset),
+          Err(err) => result.m_failures.push((file_path.clone(), err)),
+        }
+        worker_progress.0.fetch_add(1, Ordering::Release);
+      }
+      
+      Self::log_folder_result(&path, result.m_assets.len(), &result.m_failures);
+      // The main thread may have dropped the handle in the meantime, in which case the assets are simply discarded.
+      let _ = sender.send(Ok(result));
+    });
+    
+    return FolderLoadHandle {
+      m_path: String::from(folder_path_str),
+      m_progress: progress,
+      m_receiver: receiver,
+      m_worker: Some(worker),
     };
   }
   
-  pub fn load_from_folder(&self, folder_path_str: &str) -> Result<Vec<AssetInfo>, EnumAssetError> {
+  /// Parse the asset on a worker thread to avoid stalling the main loop. Only the CPU side is done off-thread, the
+  /// resulting asset still needs to be turned into an [REntity](crate::assets::r_assets::REntity) and applied on the
+  /// main thread once [AssetHandle::poll] returns it.
+  pub fn load_async(&self, file_path: &str) -> AssetHandle {
+    let (sender, receiver) = mpsc::channel();
+    let hints = self.m_hints.clone();
+    let path = String::from(file_path);
+    
+    log!(EnumLogColor::Purple, "INFO", "[AssetLoader] -->\t Loading asset {0} asynchronously...", file_path);
+    
+    let worker = std::thread::spawn(move || {
+      let loader = AssetLoader { m_hints: hints, m_cache: RefCell::new(HashMap::new()) };
+      // The main thread may have dropped the handle in the meantime, in which case the asset is simply discarded.
+      let _ = sender.send(loader.parse(&path));
+    });
+    
+    return AssetHandle {
+      m_path: String::from(file_path),
+      m_receiver: receiver,
+      m_worker: Some(worker),
+    };
+  }
+  
+  /// Load an asset, reusing the previously parsed data if the same file was already loaded, unless
+  /// [EnumAssetHint::Cache] is disabled.
+  ///
+  /// Cached assets are shared between every caller that loaded them, so they should be treated as read-only. To modify
+  /// one, load a private copy with caching turned off instead of mutating the shared one (clone-on-write).
+  pub fn load(&self, file_path: &str) -> Result<Rc<AssetInfo>, EnumAssetError> {
+    let use_cache = !self.m_hints.contains(&EnumAssetHint::Cache(false));
+    
+    if !use_cache {
+      return Ok(Rc::new(self.parse(file_path)?));
+    }
+    
+    // Canonicalize to make sure different spellings of the same path (i.e. './res/x.obj' and 'res/x.obj') match.
+    let cache_key = std::fs::canonicalize(file_path).map_err(|_| {
+      log!(EnumLogColor::Red, "ERROR", "[AssetLoader] -->\t Could not find path {0}! Make sure it \
+          exists and you have the appropriate permissions to read it.", file_path);
+      return EnumAssetError::InvalidPath;
+    })?;
+    
+    if let Some(cached_asset) = self.m_cache.borrow().get(&cache_key) {
+      log!(EnumLogColor::Blue, "INFO", "[AssetLoader] -->\t Asset {0} already loaded, reusing cached data", file_path);
+      return Ok(cached_asset.clone());
+    }
+    
+    let asset = Rc::new(self.parse(file_path)?);
+    self.m_cache.borrow_mut().insert(cache_key, asset.clone());
+    return Ok(asset);
+  }
+  
+  // Files directly inside the folder, sorted for a predictable loading order.
+  fn list_folder(folder_path_str: &str) -> Result<Vec<PathBuf>, EnumAssetError> {
     let folder_path = std::path::Path::new(folder_path_str);
-    let mut assets = Vec::with_capacity(5);
     
     if !folder_path.exists() || !folder_path.is_dir() {
       log!(EnumLogColor::Red, "ERROR", "[AssetLoader] -->\t Could not find path {0:?}! Make sure it \
@@ -152,23 +482,25 @@ impl AssetLoader {
       return Err(EnumAssetError::InvalidPath);
     }
     
-    let folder_read_result = folder_path.read_dir()?;
-    
-    for entry_result in folder_read_result {
-      if let Ok(entry) = entry_result {
-        log!(EnumLogColor::Purple, "ERROR", "[AssetLoader] -->\t Loading asset {0:?} from folder {1:?}...",
-          entry.file_name(), folder_path);
-        
-        let asset_file_name = entry.file_name();
-        if let Ok(asset) = self.load(asset_file_name.to_str().unwrap()) {
-          assets.push(asset);
-        }
-      }
+    let mut file_paths: Vec<PathBuf> = folder_path.read_dir()?
+      .filter_map(|entry_result| entry_result.ok())
+      .map(|entry| entry.path())
+      .filter(|entry_path| entry_path.is_file())
+      .collect();
+    file_paths.sort();
+    return Ok(file_paths);
+  }
+  
+  fn log_folder_result(_folder_path_str: &str, _loaded_count: usize, failures: &[(PathBuf, EnumAssetError)]) {
+    for (_file_path, _err) in failures.iter() {
+      log!(EnumLogColor::Yellow, "WARN", "[AssetLoader] -->\t Skipped {0:?} from folder {1} : {2}", _file_path,
+        _folder_path_str, _err);
     }
-    return Ok(assets);
+    log!(EnumLogColor::Green, "INFO", "[AssetLoader] -->\t Loaded {0} assets from folder {1}, {2} skipped",
+      _loaded_count, _folder_path_str, failures.len());
   }
   
-  pub fn load(&self, file_path: &str) -> Result<AssetInfo, EnumAssetError> {
+  fn parse(&self, file_path: &str) -> Result<AssetInfo, EnumAssetError> {
     let path = std::path::Path::new(file_path);
     
     if !path.exists() {
@@ -182,26 +514,39 @@ impl AssetLoader {
     // Default hints.
     let mut vertex_data_type = EnumAssetHint::VertexDataIs(Default::default());
     let mut split_large_meshes = EnumAssetHint::SplitLargeMeshes(None);
-    let mut generate_normals = EnumAssetHint::GenerateNormals(false);
+    let mut generate_normals: Option<EnumNormalMode> = None;
+    let mut force_normals = false;
+    let mut generate_tangents = false;
     let mut generate_uvs = EnumAssetHint::GenerateUvs(false);
     let mut triangulate = EnumAssetHint::Triangulate(true);
     let mut reduce_meshes = EnumAssetHint::ReduceMeshes(false);
     let mut only_triangles = EnumAssetHint::OnlyTriangles(true);
+    let mut flip_winding = EnumAssetHint::FlipWinding(false);
     
     for hint in self.m_hints.iter() {
       match hint {
         EnumAssetHint::VertexDataIs(primitive_type) => vertex_data_type = EnumAssetHint::VertexDataIs(*primitive_type),
         EnumAssetHint::SplitLargeMeshes(limit) => split_large_meshes = EnumAssetHint::SplitLargeMeshes(*limit),
-        EnumAssetHint::GenerateNormals(flag) => generate_normals = EnumAssetHint::GenerateNormals(*flag),
+        EnumAssetHint::GenerateNormals(mode) => generate_normals = Some(*mode),
+        EnumAssetHint::ForceNormals(flag) => force_normals = *flag,
+        EnumAssetHint::GenerateTangents(flag) => generate_tangents = *flag,
         EnumAssetHint::GenerateUvs(flag) => generate_uvs = EnumAssetHint::GenerateUvs(*flag),
+        EnumAssetHint::Cache(_) => {}
         EnumAssetHint::Triangulate(flag) => triangulate = EnumAssetHint::Triangulate(*flag),
         EnumAssetHint::ReduceMeshes(flag) => reduce_meshes = EnumAssetHint::ReduceMeshes(*flag),
         EnumAssetHint::OnlyTriangles(flag) => only_triangles = EnumAssetHint::OnlyTriangles(*flag),
+        EnumAssetHint::FlipWinding(flag) => flip_winding = EnumAssetHint::FlipWinding(*flag),
       }
     }
     
     self.set_options(&mut importer,
-      vec![vertex_data_type.clone(), split_large_meshes, generate_normals, generate_uvs, triangulate, reduce_meshes, only_triangles]);
+      vec![vertex_data_type.clone(), split_large_meshes, generate_uvs, triangulate, reduce_meshes,
+        only_triangles, flip_winding.clone()]);
+    
+    // Flat normals need each face to own its vertices, otherwise shared corners would end up with a single normal.
+    if generate_normals == Some(EnumNormalMode::Flat) {
+      importer.join_identical_vertices(false);
+    }
     
     importer.gen_uv_coords(true);
     importer.find_invalid_data(|invalid_data| invalid_data.enable = true);
@@ -225,14 +570,204 @@ impl AssetLoader {
         return Err(EnumAssetError::InvalidShapeData);
       }
     
+    let scene = scene.unwrap();
+    let mut meshes: Vec<AssetMesh> = Vec::with_capacity(scene.num_meshes as usize);
+    let mut bounds: Option<Aabb> = None;
+    
+    for mesh in scene.mesh_iter() {
+      let positions: Vec<Vec3<f32>> = mesh.vertex_iter()
+        .map(|vertex| Vec3::new(&[vertex.x, vertex.y, vertex.z]))
+        .collect();
+      
+      let mesh_bounds = Aabb::from_points(&positions);
+      bounds = Some(match bounds {
+        Some(previous) => Aabb::from_points(&[previous.m_min, previous.m_max, mesh_bounds.m_min, mesh_bounds.m_max]),
+        None => mesh_bounds
+      });
+      
+      let faces: Vec<[u32; 3]> = mesh.face_iter()
+        .filter(|face| face.num_indices == 3)
+        .map(|face| [face[0], face[1], face[2]])
+        .collect();
+      
+      let texture_coords: Vec<Vec2<f32>> = match mesh.texture_coords[0].is_null() {
+        true => Vec::new(),
+        false => mesh.texture_coords_iter(0)
+          .map(|uv| Vec2::new(&[uv.x, uv.y]))
+          .collect()
+      };
+      
+      // Prefer generated normals, and fall back to the file's own, if any.
+      let normals: Vec<Vec3<f32>> = match generate_normals {
+        Some(mode) if force_normals || mesh.normals.is_null() => {
+          log!(EnumLogColor::Purple, "INFO", "[AssetLoader] -->\t Generating {0:?} normals for {1} vertices in {2}...",
+            mode, positions.len(), file_path);
+          AssetLoader::compute_normals(&positions, &faces, mode)
+        }
+        _ if mesh.normals.is_null() => Vec::new(),
+        _ => mesh.normal_iter()
+          .map(|normal| Vec3::new(&[normal.x, normal.y, normal.z]))
+          .collect()
+      };
+      
+      let tangents: Option<Vec<Vec3<f32>>> = match generate_tangents {
+        true if texture_coords.is_empty() => {
+          log!(EnumLogColor::Yellow, "WARN", "[AssetLoader] -->\t Cannot generate tangents for a mesh in {0}, \
+            mesh has no texture coordinates!", file_path);
+          None
+        }
+        true => Some(AssetLoader::compute_tangents(&positions, &texture_coords, &normals, &faces)),
+        false => None,
+      };
+      
+      let bone_weights: Vec<(String, Vec<(u32, f32)>)> = mesh.bone_iter()
+        .map(|bone| (String::from(bone.name()), (0..bone.num_weights())
+          .filter_map(|weight_index| bone.get_weight(weight_index))
+          .map(|vertex_weight| (vertex_weight.vertex_id, vertex_weight.weight))
+          .collect()))
+        .collect();
+      
+      let mesh_name: String = unsafe {
+        std::ffi::CStr::from_ptr(mesh.name.data.as_ptr() as *const _).to_string_lossy().into_owned()
+      };
+      
+      meshes.push(AssetMesh {
+        m_name: mesh_name,
+        m_positions: positions,
+        m_normals: normals,
+        m_texture_coords: texture_coords,
+        m_tangents: tangents,
+        m_faces: faces,
+        m_bone_weights: bone_weights,
+      });
+    }
+    
+    let mut embedded_texture_widths: Vec<u32> = Vec::new();
+    if scene.has_materials() {
+      for material in scene.material_iter() {
+        let mut material_name: assimp_sys::AiString = assimp_sys::AiString {
+          length: 0,
+          data: [0; 1024],
+        };
+        let mut material_diffuse: [f32; 3] = [0.0; 3];
+        let result = unsafe {
+          assimp_sys::aiGetMaterialString(material.to_raw(), (**material.properties).key.data.as_ptr() as *const _,
+            0, (**material.properties).index, &mut material_name)
+        };
+        if result == assimp_sys::AiReturn::Success {
+          log!(EnumLogColor::Red, "DEBUG", "[Asset] -->\t Material name detected: {0:?}", material_name);
+        }
+        
+        let result = unsafe {
+          assimp_sys::aiGetMaterialFloatArray(material.to_raw(), (**material.properties).key.data.as_ptr() as *const _,
+            1, (**material.properties).index, material_diffuse.as_mut_ptr() as *mut _, &mut 3)
+        };
+        if result == assimp_sys::AiReturn::Success {
+          log!(EnumLogColor::Red, "DEBUG", "[Asset] -->\t Material diffuse color detected: {0:?}", material_diffuse);
+        }
+      }
+      embedded_texture_widths = scene.texture_iter().map(|texture| texture.width).collect();
+    }
+    
+    let skeleton: Option<Skeleton> = Skeleton::from_scene(&scene);
+    let animation_clips: Vec<AnimationClip> = AnimationClip::from_scene(&scene);
+    
     return Ok(AssetInfo {
+      m_file_path: String::from(file_path),
       m_is_indexed: vertex_data_type.get_value()
         .downcast_ref::<EnumAssetPrimitiveMode>()
         .is_some_and(|mode| *mode == EnumAssetPrimitiveMode::Indexed),
-      m_data: scene.unwrap(),
+      m_flip_winding: flip_winding.get_value()
+        .downcast_ref::<bool>()
+        .is_some_and(|flag| *flag),
+      m_meshes: meshes,
+      m_embedded_texture_widths: embedded_texture_widths,
+      m_bounds: bounds.unwrap_or_default(),
+      m_skeleton: skeleton,
+      m_animation_clips: animation_clips,
     });
   }
   
+  pub fn compute_normals(positions: &[Vec3<f32>], faces: &[[u32; 3]], mode: EnumNormalMode) -> Vec<Vec3<f32>> {
+    let mut normals: Vec<Vec3<f32>> = vec![Vec3::default(); positions.len()];
+    
+    for face in faces.iter() {
+      if face.iter().any(|index| *index as usize >= positions.len()) {
+        continue;
+      }
+      let edge_1 = positions[face[1] as usize] - positions[face[0] as usize];
+      let edge_2 = positions[face[2] as usize] - positions[face[0] as usize];
+      // The cross product's length is twice the triangle's area, which weights larger faces more when averaging.
+      let face_normal = edge_1.cross(edge_2);
+      
+      for index in face.iter() {
+        match mode {
+          EnumNormalMode::Flat => normals[*index as usize] = face_normal.normalize(),
+          EnumNormalMode::Smooth => normals[*index as usize] += face_normal,
+        }
+      }
+    }
+    
+    return normals.into_iter()
+      .map(|normal| normal.normalize())
+      .collect();
+  }
+  
+  pub fn compute_tangents(positions: &[Vec3<f32>], uvs: &[Vec2<f32>], normals: &[Vec3<f32>],
+                          faces: &[[u32; 3]]) -> Vec<Vec3<f32>> {
+    let mut tangents: Vec<Vec3<f32>> = vec![Vec3::default(); positions.len()];
+    
+    // Accumulate each face's tangent on its vertices (Lengyel's method).
+    for face in faces.iter() {
+      if face.iter().any(|index| *index as usize >= positions.len() || *index as usize >= uvs.len()) {
+        continue;
+      }
+      let edge_1 = positions[face[1] as usize] - positions[face[0] as usize];
+      let edge_2 = positions[face[2] as usize] - positions[face[0] as usize];
+      let delta_uv_1 = uvs[face[1] as usize] - uvs[face[0] as usize];
+      let delta_uv_2 = uvs[face[2] as usize] - uvs[face[0] as usize];
+      
+      let determinant = delta_uv_1.x * delta_uv_2.y - delta_uv_2.x * delta_uv_1.y;
+      // Degenerate UVs, let the fallback below pick a tangent instead of dividing by zero.
+      if determinant.abs() <= f32::EPSILON {
+        continue;
+      }
+      let ratio = 1.0 / determinant;
+      let face_tangent = Vec3::new(&[
+        (edge_1.x * delta_uv_2.y - edge_2.x * delta_uv_1.y) * ratio,
+        (edge_1.y * delta_uv_2.y - edge_2.y * delta_uv_1.y) * ratio,
+        (edge_1.z * delta_uv_2.y - edge_2.z * delta_uv_1.y) * ratio
+      ]);
+      
+      for index in face.iter() {
+        tangents[*index as usize] += face_tangent;
+      }
+    }
+    
+    return tangents.into_iter()
+      .enumerate()
+      .map(|(index, tangent)| {
+        let normal = normals.get(index).copied().unwrap_or(Vec3::default());
+        
+        // Gram-Schmidt orthogonalize against the normal.
+        let projection = normal.dot(tangent);
+        let orthogonal = Vec3::new(&[tangent.x - normal.x * projection, tangent.y - normal.y * projection,
+          tangent.z - normal.z * projection]);
+        if orthogonal.vec_len() > f32::EPSILON {
+          return orthogonal.normalize();
+        }
+        
+        // Fallback : any vector perpendicular to the normal, using the axis least aligned with it.
+        if normal.vec_len() <= f32::EPSILON {
+          return Vec3::new(&[1.0, 0.0, 0.0]);
+        }
+        let axis = (normal.x.abs() < 0.9).then(|| Vec3::new(&[1.0, 0.0, 0.0]))
+          .unwrap_or(Vec3::new(&[0.0, 1.0, 0.0]));
+        return normal.cross(axis).normalize();
+      })
+      .collect();
+  }
+  
   fn set_options(&self, importer: &mut assimp::Importer, hints: Vec<EnumAssetHint>) {
     for hint in hints.into_iter() {
       match hint {
@@ -257,13 +792,11 @@ impl AssetLoader {
             }
           });
         }
-        EnumAssetHint::GenerateNormals(bool) => {
-          importer.generate_normals(|gen_normals| {
-            gen_normals.enable = bool;
-            gen_normals.smooth = bool;
-          });
-        }
+        // Computed after parsing, since the importer doesn't let us choose when to override existing normals.
+        EnumAssetHint::GenerateNormals(_) | EnumAssetHint::ForceNormals(_) => {}
         EnumAssetHint::GenerateUvs(bool) => importer.gen_uv_coords(bool),
+        // Handled in load(), before parsing.
+        EnumAssetHint::Cache(_) => {}
         EnumAssetHint::Triangulate(bool) => importer.triangulate(bool),
         EnumAssetHint::ReduceMeshes(bool) => {
           importer.optimize_meshes(bool);
@@ -279,6 +812,8 @@ impl AssetLoader {
             }
           });
         }
+        // Applied when baking the entity, since normals also need to be flipped alongside the winding order.
+        EnumAssetHint::FlipWinding(_) => {}
       }
     }
   }
@@ -22,6 +22,54 @@
  SOFTWARE.
 */
 
+use std::path::{Path, PathBuf};
+
 pub mod asset_loader;
 pub mod r_assets;
+pub mod scene;
+pub mod skeleton;
+
+/// What a file can be loaded as, judging from its extension.
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd, Ord, Eq, Hash)]
+pub enum EnumAssetKind {
+  /// Loadable with [asset_loader::AssetLoader].
+  Mesh,
+  /// Loadable with [crate::utils::texture_loader::TextureLoader].
+  Texture,
+  /// Glsl source or compiled SPIR-V, for [crate::graphics::shader::Shader].
+  Shader,
+  Unknown,
+}
+
+impl EnumAssetKind {
+  const C_MESH_EXTENSIONS: [&'static str; 9] = ["obj", "fbx", "gltf", "glb", "dae", "3ds", "blend", "ply", "stl"];
+  const C_TEXTURE_EXTENSIONS: [&'static str; 7] = ["png", "jpg", "jpeg", "bmp", "tga", "hdr", "gif"];
+  const C_SHADER_EXTENSIONS: [&'static str; 7] = ["glsl", "vert", "frag", "geom", "comp", "spv", "shader"];
+  
+  pub fn from_path(file_path: &Path) -> Self {
+    let extension = match file_path.extension().and_then(|extension| extension.to_str()) {
+      Some(extension) => extension.to_lowercase(),
+      None => return EnumAssetKind::Unknown,
+    };
+    
+    if Self::C_MESH_EXTENSIONS.contains(&extension.as_str()) {
+      return EnumAssetKind::Mesh;
+    }
+    if Self::C_TEXTURE_EXTENSIONS.contains(&extension.as_str()) {
+      return EnumAssetKind::Texture;
+    }
+    if Self::C_SHADER_EXTENSIONS.contains(&extension.as_str()) {
+      return EnumAssetKind::Shader;
+    }
+    return EnumAssetKind::Unknown;
+  }
+}
+
+/// Sort out files dropped onto the window (see [crate::events::EnumEvent::DragAndDrop]) by what they can be loaded as,
+/// keeping the order they were dropped in.
+pub fn classify_dropped_files(file_paths: &[PathBuf]) -> Vec<(PathBuf, EnumAssetKind)> {
+  return file_paths.iter()
+    .map(|file_path| (file_path.clone(), EnumAssetKind::from_path(file_path)))
+    .collect();
+}
 
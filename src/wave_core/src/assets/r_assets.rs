@@ -22,6 +22,7 @@
  SOFTWARE.
 */
 
+use std::borrow::Borrow;
 use std::collections::HashSet;
 use std::fmt::{Display, Formatter};
 use std::mem::size_of;
@@ -29,12 +30,13 @@ use std::mem::size_of;
 use rand::Rng;
 
 use crate::{Engine, log, TraitFree};
-use crate::assets::asset_loader::AssetInfo;
+use crate::assets::asset_loader::{AssetInfo, EnumAssetError};
+use crate::assets::skeleton::{AnimationClip, C_MAX_BONE_INFLUENCES, Skeleton};
 use crate::graphics::color::Color;
-use crate::graphics::renderer::{EnumRendererError, EnumRendererRenderPrimitiveAs};
+use crate::graphics::renderer::{EnumRendererBlendMode, EnumRendererError, EnumRendererRenderPrimitiveAs};
 use crate::graphics::shader::Shader;
 use crate::graphics::texture::TextureArray;
-use crate::math::{Mat4, Vec2, Vec3};
+use crate::math::{Aabb, EnumEasing, Mat4, Quaternion, Vec2, Vec3};
 use crate::utils::macros::logger::*;
 
 static mut S_ENTITY_ID_COUNTER: u32 = 0;
@@ -48,16 +50,23 @@ pub enum EnumVertexMemberOffset {
   NormalOffset = (EnumVertexMemberOffset::PositionOffset as usize) + (size_of::<f32>() * 3),
   ColorOffset = (EnumVertexMemberOffset::NormalOffset as usize) + size_of::<u32>(),
   TexCoordsOffset = (EnumVertexMemberOffset::ColorOffset as usize) + size_of::<Color>(),
+  TangentOffset = (EnumVertexMemberOffset::TexCoordsOffset as usize) + (size_of::<f32>() * 2),
+  BoneIdsOffset = (EnumVertexMemberOffset::TangentOffset as usize) + (size_of::<f32>() * 3),
+  BoneWeightsOffset = (EnumVertexMemberOffset::BoneIdsOffset as usize) + size_of::<u32>(),
 }
 
 #[derive(Debug, Copy, Clone, PartialOrd, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum EnumPrimitiveShading {
   Sprite,
   Mesh(EnumMaterialShading),
   Quad,
+  // Mesh deformed by a skeleton in the vertex shader, see [REntity::play_animation].
+  Skinned,
 }
 
 #[derive(Debug, Copy, Clone, PartialOrd, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum EnumMaterialShading {
   None,
   Flat,
@@ -79,7 +88,8 @@ pub enum EnumMaterialMapMode {
   Decal,
 }
 
-#[derive(Debug, PartialEq, Hash)]
+#[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum EnumAssetMapMethod {
   OneForEach(usize, usize),
   MultipleForEach(usize, usize, usize),
@@ -144,6 +154,11 @@ pub struct Vertex {
   pub m_normal: u32,
   pub m_color: Color,
   pub m_texture_coords: Vec2<f32>,
+  // Used for normal mapping, left empty unless the asset was loaded with tangent generation.
+  pub m_tangent: Vec3<f32>,
+  // Up to 4 bones influencing the vertex, one per byte from the least significant, for skinned meshes only.
+  pub m_bone_ids: u32,
+  pub m_bone_weights: [f32; C_MAX_BONE_INFLUENCES],
 }
 
 impl Vertex {
@@ -155,6 +170,9 @@ impl Vertex {
       m_normal: 0,
       m_color: Color::default(),
       m_texture_coords: Vec2::default(),
+      m_tangent: Vec3::default(),
+      m_bone_ids: 0,
+      m_bone_weights: [0.0; C_MAX_BONE_INFLUENCES],
     };
   }
   
@@ -162,6 +180,63 @@ impl Vertex {
     return self.m_entity_id;
   }
   
+  /// Pack a unit normal into 32 bits, with each axis' magnitude on one byte (x, y, z from the most significant) and
+  /// their signs on the lowest bits, as expected by shaders.
+  pub fn pack_normal(normal: Vec3<f32>) -> u32 {
+    let x_sign = normal.x.is_sign_negative().then(|| 0x1)
+      .unwrap_or(0);
+    let y_sign = normal.y.is_sign_negative().then(|| 0x2)
+      .unwrap_or(0);
+    let z_sign = normal.z.is_sign_negative().then(|| 0x8)
+      .unwrap_or(0);
+    
+    let x_normal = ((normal.x.abs() * 100.0) as u32) << 24;
+    let y_normal = ((normal.y.abs() * 100.0) as u32) << 16;
+    let z_normal = ((normal.z.abs() * 100.0) as u32) << 8;
+    
+    return x_normal + y_normal + x_sign + y_sign + z_sign + z_normal;
+  }
+  
+  /// Inverse of [Self::pack_normal], up to its precision.
+  pub fn unpack_normal(packed_normal: u32) -> Vec3<f32> {
+    let unpack_axis = |shift: u32, sign_bit: u32| -> f32 {
+      let magnitude = ((packed_normal >> shift) & 0xFF) as f32 / 100.0;
+      return (packed_normal & sign_bit != 0).then(|| -magnitude).unwrap_or(magnitude);
+    };
+    return Vec3::new(&[unpack_axis(24, 0x1), unpack_axis(16, 0x2), unpack_axis(8, 0x8)]);
+  }
+  
+  /// Skin the vertex to a bone, keeping only the heaviest [C_MAX_BONE_INFLUENCES] influences.
+  pub fn add_bone_influence(&mut self, bone_index: usize, weight: f32) {
+    if bone_index > u8::MAX as usize || weight <= 0.0 {
+      return;
+    }
+    
+    let (lightest_slot, lightest_weight) = self.m_bone_weights.iter()
+      .copied()
+      .enumerate()
+      .min_by(|(_, first), (_, second)| first.total_cmp(second))
+      .unwrap();
+    
+    if weight > lightest_weight {
+      let shift: u32 = (lightest_slot * 8) as u32;
+      self.m_bone_ids = (self.m_bone_ids & !(0xFF << shift)) | ((bone_index as u32) << shift);
+      self.m_bone_weights[lightest_slot] = weight;
+    }
+  }
+  
+  pub fn get_bone_ids(&self) -> [usize; C_MAX_BONE_INFLUENCES] {
+    return std::array::from_fn(|slot| ((self.m_bone_ids >> (slot * 8)) & 0xFF) as usize);
+  }
+  
+  /// Rescale bone weights to add up to one, since files do not always guarantee it and dropped influences break it.
+  pub fn normalize_bone_weights(&mut self) {
+    let total_weight: f32 = self.m_bone_weights.iter().sum();
+    if total_weight > f32::EPSILON {
+      self.m_bone_weights.iter_mut().for_each(|weight| *weight /= total_weight);
+    }
+  }
+  
   pub fn register(&mut self, id: u32) {
     self.m_entity_id = id;
   }
@@ -172,6 +247,9 @@ impl Vertex {
     self.m_normal = 0;
     self.m_texture_coords = Vec2::default();
     self.m_color = Color::default();
+    self.m_tangent = Vec3::default();
+    self.m_bone_ids = 0;
+    self.m_bone_weights = [0.0; C_MAX_BONE_INFLUENCES];
   }
 }
 
@@ -249,15 +327,64 @@ impl TraitPrimitive for Mesh {
 
 pub struct REntity {
   pub(crate) m_renderer_id: u64,
-  pub(crate) m_name: &'static str,
+  pub(crate) m_name: String,
   pub(crate) m_sub_meshes: Vec<Box<dyn TraitPrimitive>>,
   pub(crate) m_type: EnumPrimitiveShading,
   pub(crate) m_primitive_mode: EnumRendererRenderPrimitiveAs,
   m_last_primitive_mode: EnumRendererRenderPrimitiveAs,
   // Transformations applied to the entity, to be eventually applied to the model matrix.
   m_transform: [Vec3<f32>; 3],
+  // Optional orientation overriding the euler rotation in the transform above, to avoid gimbal lock.
+  m_orientation: Option<Quaternion>,
   m_sent: bool,
   m_changed: bool,
+  // Shadow mapping participation.
+  m_cast_shadows: bool,
+  m_last_cast_shadows: bool,
+  m_receive_shadows: bool,
+  m_last_receive_shadows: bool,
+  // Transparent entities get drawn last, sorted back-to-front.
+  m_blend_mode: EnumRendererBlendMode,
+  m_last_blend_mode: EnumRendererBlendMode,
+  // Local space extents, for frustum culling.
+  m_bounds: Aabb,
+  // World matrix of the parent node, when part of a scene hierarchy.
+  m_parent_matrix: Option<Mat4>,
+  // What the entity was built from and how it was textured, to rebuild it when loading a saved scene.
+  m_asset_path: Option<String>,
+  m_texture_mapping: Option<EnumAssetMapMethod>,
+  // Tween in progress, advanced by update_animation().
+  m_animation: Option<TransformAnimation>,
+  // Rig and clips of skinned entities, along with the clip playing and the resulting pose.
+  m_skeleton: Option<Skeleton>,
+  m_animation_clips: Vec<AnimationClip>,
+  m_skeletal_playback: Option<SkeletalPlayback>,
+  m_bone_matrices: Vec<Mat4>,
+  m_bones_changed: bool,
+  // Per-instance model matrices, empty unless instanced, along with the range of instances changed since last upload.
+  m_instance_transforms: Vec<Mat4>,
+  m_dirty_instances: Option<(usize, usize)>,
+}
+
+/// Clip of the entity's skeleton being played, see [REntity::play_animation].
+#[derive(Debug, Copy, Clone)]
+struct SkeletalPlayback {
+  m_clip_index: usize,
+  // Seconds since the clip started.
+  m_time: f32,
+  m_looping: bool,
+}
+
+/// Translation and orientation eased from a start to an end over a duration, in seconds.
+#[derive(Debug, Copy, Clone)]
+struct TransformAnimation {
+  m_start_position: Vec3<f32>,
+  m_end_position: Vec3<f32>,
+  m_start_orientation: Quaternion,
+  m_end_orientation: Quaternion,
+  m_duration: f32,
+  m_elapsed: f32,
+  m_easing: EnumEasing,
 }
 
 impl Default for REntity {
@@ -269,6 +396,9 @@ impl Default for REntity {
       m_normal: 0,
       m_color: Color::default(),
       m_texture_coords: Vec2::default(),
+      m_tangent: Vec3::default(),
+      m_bone_ids: 0,
+      m_bone_weights: [0.0; C_MAX_BONE_INFLUENCES],
     }; 36];
     
     let positions =
@@ -292,25 +422,7 @@ impl Default for REntity {
     }
     
     for index in 0..normals.len() {
-      let x_sign = normals[index].x.is_sign_negative().then(|| 0x1)
-        .unwrap_or(0);
-      let y_sign = normals[index].y.is_sign_negative().then(|| 0x2)
-        .unwrap_or(0);
-      let z_sign = normals[index].z.is_sign_negative().then(|| 0x8)
-        .unwrap_or(0);
-      
-      let x_normal_f = normals[index].x.is_sign_negative().then(|| normals[index].x * -100.0)
-        .unwrap_or(normals[index].x * 100.0);
-      let y_normal_f = normals[index].y.is_sign_negative().then(|| normals[index].y * -100.0)
-        .unwrap_or(normals[index].y * 100.0);
-      let z_normal_f = normals[index].z.is_sign_negative().then(|| normals[index].z * -100.0)
-        .unwrap_or(normals[index].z * 100.0);
-      
-      let x_normal = (x_normal_f as u32) << 24;
-      let y_normal = (y_normal_f as u32) << 16;
-      let z_normal = (z_normal_f as u32) << 8;
-      
-      vertices[index].m_normal = x_normal + y_normal + x_sign + y_sign + z_sign + z_normal;
+      vertices[index].m_normal = Vertex::pack_normal(normals[index]);
       // vertices[index].m_normal = Vec3::new(&[normals[index].x, normals[index].y, normals[index].z]);
     }
     
@@ -348,13 +460,32 @@ impl Default for REntity {
         m_indices: Vec::from(faces),
       })],
       m_renderer_id: u64::MAX,
-      m_name: "Default Cube",
+      m_name: String::from("Default Cube"),
       m_type: EnumPrimitiveShading::default(),
       m_transform: [Vec3::default(), Vec3::default(), Vec3::new(&[1.0, 1.0, 1.0])],
+      m_orientation: None,
       m_primitive_mode: EnumRendererRenderPrimitiveAs::Filled,
       m_last_primitive_mode: EnumRendererRenderPrimitiveAs::Filled,
       m_sent: false,
       m_changed: false,
+      m_cast_shadows: true,
+      m_last_cast_shadows: true,
+      m_receive_shadows: true,
+      m_last_receive_shadows: true,
+      m_blend_mode: EnumRendererBlendMode::Opaque,
+      m_last_blend_mode: EnumRendererBlendMode::Opaque,
+      m_bounds: Aabb::new(Vec3::new(&[-0.5, -0.5, -0.5]), Vec3::new(&[0.5, 0.5, 0.5])),
+      m_parent_matrix: None,
+      m_asset_path: None,
+      m_texture_mapping: None,
+      m_animation: None,
+      m_skeleton: None,
+      m_animation_clips: Vec::new(),
+      m_skeletal_playback: None,
+      m_bone_matrices: Vec::new(),
+      m_bones_changed: false,
+      m_instance_transforms: Vec::new(),
+      m_dirty_instances: None,
     };
     
     new_entity.translate(0.0, 0.0, 10.0);
@@ -377,56 +508,49 @@ impl TraitFree<EnumRendererError> for REntity {
 }
 
 impl REntity {
-  pub fn new(asset_info: AssetInfo, data_type: EnumPrimitiveShading, name: &'static str) -> Self {
-    let mut data: Vec<Box<dyn TraitPrimitive>> = Vec::with_capacity(asset_info.m_data.num_meshes as usize);
+  pub fn new(asset: impl Borrow<AssetInfo>, data_type: EnumPrimitiveShading, name: &str) -> Self {
+    // Accept both owned and cached (shared) assets, since building an entity only reads from them.
+    let asset_info: &AssetInfo = asset.borrow();
+    let mut data: Vec<Box<dyn TraitPrimitive>> = Vec::with_capacity(asset_info.m_meshes.len());
     
     // Offset of indices to shift to the next sub-mesh indices, in order to synchronize indices between sub-meshes
     // and join all sub-mesh indices together all referencing that same primitive to avoid drawing every sub-mesh separately.
     let mut base_index: usize = 0;
     
-    for mesh in asset_info.m_data.mesh_iter() {
-      let mut vertices: Vec<Vertex> = Vec::with_capacity(mesh.num_vertices as usize);
-      vertices.resize(mesh.num_vertices as usize, Vertex::default());
-      let mut indices: Vec<u32> = Vec::with_capacity((mesh.num_faces * 3) as usize);
+    for mesh in asset_info.m_meshes.iter() {
+      let mut vertices: Vec<Vertex> = Vec::with_capacity(mesh.m_positions.len());
+      vertices.resize(mesh.m_positions.len(), Vertex::default());
+      let mut indices: Vec<u32> = Vec::with_capacity(mesh.m_faces.len() * 3);
       
       if asset_info.m_is_indexed {
-        for face in mesh.face_iter() {
+        for face in mesh.m_faces.iter() {
           indices.push(face[0] + base_index as u32);
-          indices.push(face[1] + base_index as u32);
-          indices.push(face[2] + base_index as u32);
+          // Reverse the triangle's winding order if requested, to fix mirrored assets appearing inside-out.
+          if asset_info.m_flip_winding {
+            indices.push(face[2] + base_index as u32);
+            indices.push(face[1] + base_index as u32);
+          } else {
+            indices.push(face[1] + base_index as u32);
+            indices.push(face[2] + base_index as u32);
+          }
         }
         base_index += vertices.len();
       }
       
-      for (position, vertex) in mesh.vertex_iter().enumerate() {
-        vertices[position].m_position = Vec3::new(&[vertex.x, vertex.y, vertex.z]);
+      for (position, vertex_position) in mesh.m_positions.iter().enumerate() {
+        vertices[position].m_position = *vertex_position;
         vertices[position].m_entity_id = unsafe { S_ENTITY_ID_COUNTER };
       }
       
-      for (position, normal) in mesh.normal_iter().enumerate() {
-        let x_sign = normal.x.is_sign_negative().then(|| 0x1)
-          .unwrap_or(0);
-        let y_sign = normal.y.is_sign_negative().then(|| 0x2)
-          .unwrap_or(0);
-        let z_sign = normal.z.is_sign_negative().then(|| 0x8)
-          .unwrap_or(0);
-        
-        let x_normal_f = normal.x.is_sign_negative().then(|| normal.x * -100.0)
-          .unwrap_or(normal.x * 100.0);
-        let y_normal_f = normal.y.is_sign_negative().then(|| normal.y * -100.0)
-          .unwrap_or(normal.y * 100.0);
-        let z_normal_f = normal.z.is_sign_negative().then(|| normal.z * -100.0)
-          .unwrap_or(normal.z * 100.0);
+      for (position, mesh_normal) in mesh.m_normals.iter().enumerate() {
+        let normal = asset_info.m_flip_winding.then(|| Vec3::new(&[-mesh_normal.x, -mesh_normal.y, -mesh_normal.z]))
+          .unwrap_or(*mesh_normal);
         
-        let x_normal = (x_normal_f as u32) << 24;
-        let y_normal = (y_normal_f as u32) << 16;
-        let z_normal = (z_normal_f as u32) << 8;
-        
-        vertices[position].m_normal = x_normal + y_normal + x_sign + y_sign + z_sign + z_normal;
+        vertices[position].m_normal = Vertex::pack_normal(normal);
         // vertices[position].m_normal = Vec3::new(&[normal.x, normal.y, normal.z]);
       }
       
-      for (position, texture_coord) in mesh.texture_coords_iter(0).enumerate() {
+      for (position, texture_coord) in mesh.m_texture_coords.iter().enumerate() {
         // let x_sign = texture_coord.x.is_sign_negative().then(|| 0x1)
         //   .unwrap_or(0);
         // let y_sign = texture_coord.y.is_sign_negative().then(|| 0x2)
@@ -439,26 +563,49 @@ impl REntity {
         //
         // vertices[position].m_texture_coords = x_sign + x_tex_coord + y_sign + y_tex_coord;
         
-        vertices[position].m_texture_coords = Vec2::new(&[texture_coord.x, texture_coord.y]);
+        vertices[position].m_texture_coords = *texture_coord;
       }
       
-      unsafe { S_ENTITY_ID_COUNTER += 1 };
+      if let Some(tangents) = mesh.m_tangents.as_ref() {
+        for (position, tangent) in tangents.iter().enumerate() {
+          vertices[position].m_tangent = *tangent;
+        }
+      }
       
-      let c_name = unsafe {
-        std::ffi::CStr::from_ptr(mesh.name.data.as_ptr() as *const _).to_str().unwrap()
-      };
+      if let Some(skeleton) = asset_info.m_skeleton.as_ref() {
+        for (bone_name, bone_weights) in mesh.m_bone_weights.iter() {
+          let Some(bone_index) = skeleton.find_bone(bone_name) else {
+            continue;
+          };
+          for (vertex_id, weight) in bone_weights.iter() {
+            if let Some(vertex) = vertices.get_mut(*vertex_id as usize) {
+              vertex.add_bone_influence(bone_index, *weight);
+            }
+          }
+        }
+        vertices.iter_mut().for_each(|vertex| vertex.normalize_bone_weights());
+      }
+      
+      // Without indices, vertices are laid out per triangle, so reverse the winding on the vertices themselves.
+      if !asset_info.m_is_indexed && asset_info.m_flip_winding {
+        for triangle in vertices.chunks_exact_mut(3) {
+          triangle.swap(1, 2);
+        }
+      }
+      
+      unsafe { S_ENTITY_ID_COUNTER += 1 };
       
       match data_type {
         EnumPrimitiveShading::Sprite => {
           data.push(Box::new(Sprite {
-            m_name: String::from(c_name),
+            m_name: mesh.m_name.clone(),
             m_vertices: vertices,
             m_indices: indices,
           }));
         }
-        EnumPrimitiveShading::Mesh(_) => {
+        EnumPrimitiveShading::Mesh(_) | EnumPrimitiveShading::Skinned => {
           data.push(Box::new(Mesh {
-            m_name: String::from(c_name),
+            m_name: mesh.m_name.clone(),
             m_vertices: vertices,
             m_indices: indices,
           }));
@@ -467,52 +614,104 @@ impl REntity {
       }
     }
     
-    if asset_info.m_data.has_materials() {
-      for material in asset_info.m_data.material_iter() {
-        let mut material_name: assimp_sys::AiString = assimp_sys::AiString {
-          length: 0,
-          data: [0; 1024],
-        };
-        let mut material_diffuse: [f32; 3] = [0.0; 3];
-        let result = unsafe {
-          assimp_sys::aiGetMaterialString(material.to_raw(), (**material.properties).key.data.as_ptr() as *const _,
-            0, (**material.properties).index, &mut material_name)
-        };
-        if result == assimp_sys::AiReturn::Success {
-          log!(EnumLogColor::Red, "DEBUG", "[Asset] -->\t Material name detected: {0:?}", material_name);
-        }
-        
-        let result = unsafe {
-          assimp_sys::aiGetMaterialFloatArray(material.to_raw(), (**material.properties).key.data.as_ptr() as *const _,
-            1, (**material.properties).index, material_diffuse.as_mut_ptr() as *mut _, &mut 3)
-        };
-        if result == assimp_sys::AiReturn::Success {
-          log!(EnumLogColor::Red, "DEBUG", "[Asset] -->\t Material diffuse color detected: {0:?}", material_diffuse);
-        }
-      }
-      for (index, texture) in asset_info.m_data.texture_iter().enumerate() {
-        if let Some(primitive) = data.get_mut(index) {
-          for vertex in primitive.get_vertices_mut() {
-            let shifted_texture_size: i32 = (texture.width as i32) << 16;
-            let shifted_end_depth: i32 = ((index + 1) as i32) << 8;
-            let shifted_start_depth: i32 = index as i32;
-            
-            vertex.m_texture_info = shifted_texture_size + shifted_end_depth + shifted_start_depth;
-          }
+    for (index, texture_width) in asset_info.m_embedded_texture_widths.iter().enumerate() {
+      if let Some(primitive) = data.get_mut(index) {
+        for vertex in primitive.get_vertices_mut() {
+          let shifted_texture_size: i32 = (*texture_width as i32) << 16;
+          let shifted_end_depth: i32 = ((index + 1) as i32) << 8;
+          let shifted_start_depth: i32 = index as i32;
+          
+          vertex.m_texture_info = shifted_texture_size + shifted_end_depth + shifted_start_depth;
         }
       }
     }
     
     return REntity {
       m_renderer_id: u64::MAX,
-      m_name: name,
+      m_name: String::from(name),
       m_sub_meshes: data,
       m_type: data_type,
       m_transform: [Vec3::default(), Vec3::default(), Vec3::new(&[1.0, 1.0, 1.0])],
+      m_orientation: None,
       m_primitive_mode: EnumRendererRenderPrimitiveAs::Filled,
       m_last_primitive_mode: EnumRendererRenderPrimitiveAs::Filled,
       m_sent: false,
       m_changed: false,
+      m_cast_shadows: true,
+      m_last_cast_shadows: true,
+      m_receive_shadows: true,
+      m_last_receive_shadows: true,
+      m_blend_mode: EnumRendererBlendMode::Opaque,
+      m_last_blend_mode: EnumRendererBlendMode::Opaque,
+      m_bounds: asset_info.m_bounds,
+      m_parent_matrix: None,
+      m_asset_path: Some(asset_info.m_file_path.clone()),
+      m_texture_mapping: None,
+      m_animation: None,
+      m_skeleton: asset_info.m_skeleton.clone(),
+      m_animation_clips: asset_info.m_animation_clips.clone(),
+      m_skeletal_playback: None,
+      m_bone_matrices: Vec::new(),
+      m_bones_changed: false,
+      m_instance_transforms: Vec::new(),
+      m_dirty_instances: None,
+    };
+  }
+  
+  /// Same as [Self::new], but drawn `instance_count` times in a single draw call when applied, each instance starting
+  /// at the origin until moved with [Self::set_instance_transform].
+  pub fn new_instanced(asset: impl Borrow<AssetInfo>, data_type: EnumPrimitiveShading, name: &str,
+                      instance_count: usize) -> Self {
+    let mut r_entity = REntity::new(asset, data_type, name);
+    r_entity.m_instance_transforms = vec![Mat4::new(1.0); instance_count];
+    return r_entity;
+  }
+  
+  /// Build an entity out of already merged (and transformed) geometry, under a single new entity id, as a single
+  /// primitive. Used for [crate::graphics::renderer::StaticBatch].
+  pub(crate) fn from_static_geometry(name: &str, data_type: EnumPrimitiveShading, mut vertices: Vec<Vertex>,
+                                     indices: Vec<u32>, bounds: Aabb) -> Self {
+    let entity_id = unsafe { S_ENTITY_ID_COUNTER };
+    unsafe { S_ENTITY_ID_COUNTER += 1 };
+    
+    for vertex in vertices.iter_mut() {
+      vertex.register(entity_id);
+    }
+    
+    let primitive: Box<dyn TraitPrimitive> = match data_type {
+      EnumPrimitiveShading::Sprite => Box::new(Sprite { m_name: String::from(name), m_vertices: vertices, m_indices: indices }),
+      _ => Box::new(Mesh { m_name: String::from(name), m_vertices: vertices, m_indices: indices })
+    };
+    
+    return REntity {
+      m_renderer_id: u64::MAX,
+      m_name: String::from(name),
+      m_sub_meshes: vec![primitive],
+      m_type: data_type,
+      m_transform: [Vec3::default(), Vec3::default(), Vec3::new(&[1.0, 1.0, 1.0])],
+      m_orientation: None,
+      m_primitive_mode: EnumRendererRenderPrimitiveAs::Filled,
+      m_last_primitive_mode: EnumRendererRenderPrimitiveAs::Filled,
+      m_sent: false,
+      m_changed: false,
+      m_cast_shadows: true,
+      m_last_cast_shadows: true,
+      m_receive_shadows: true,
+      m_last_receive_shadows: true,
+      m_blend_mode: EnumRendererBlendMode::Opaque,
+      m_last_blend_mode: EnumRendererBlendMode::Opaque,
+      m_bounds: bounds,
+      m_parent_matrix: None,
+      m_asset_path: None,
+      m_texture_mapping: None,
+      m_animation: None,
+      m_skeleton: None,
+      m_animation_clips: Vec::new(),
+      m_skeletal_playback: None,
+      m_bone_matrices: Vec::new(),
+      m_bones_changed: false,
+      m_instance_transforms: Vec::new(),
+      m_dirty_instances: None,
     };
   }
   
@@ -521,12 +720,12 @@ impl REntity {
       EnumPrimitiveShading::Sprite | EnumPrimitiveShading::Quad => {
         size_of::<u32>() + (size_of::<f32>() * 2) + size_of::<u32>() + (size_of::<f32>() * 2)
       }
-      EnumPrimitiveShading::Mesh(_) => size_of::<Vertex>()
+      EnumPrimitiveShading::Mesh(_) | EnumPrimitiveShading::Skinned => size_of::<Vertex>()
     };
   }
   
   pub fn get_name(&self) -> &str {
-    return self.m_name;
+    return &self.m_name;
   }
   
   pub fn get_primitive_mode(&self) -> EnumRendererRenderPrimitiveAs {
@@ -537,6 +736,10 @@ impl REntity {
     return self.m_sub_meshes.len();
   }
   
+  pub fn get_primitive(&self, primitive_index: usize) -> Option<&dyn TraitPrimitive> {
+    return self.m_sub_meshes.get(primitive_index).map(|primitive| primitive.as_ref());
+  }
+  
   pub fn get_total_vertex_count(&self) -> usize {
     let mut count = 0;
     for sub_mesh in self.m_sub_meshes.iter() {
@@ -569,18 +772,204 @@ impl REntity {
     self.m_changed = true;
   }
   
+  /// Euler rotation, in degrees. Prefer [Self::rotate_quaternion] or [Self::animate_to] to avoid gimbal lock, or to
+  /// interpolate between orientations.
   pub fn rotate(&mut self, amount_x: f32, amount_y: f32, amount_z: f32) {
     // Inverse x and y to correspond to the right orientation.
-    self.m_transform[1] += Vec3::new(&[amount_y, amount_x, -amount_z]);
+    let rotation: Vec3<f32> = Vec3::new(&[amount_y, amount_x, -amount_z]);
+    
+    // Keep the quaternion path in sync if used.
+    if let Some(orientation) = self.m_orientation {
+      self.m_orientation = Some((Quaternion::from_euler(&rotation) * orientation).normalize());
+    }
+    self.m_transform[1] += rotation;
+    self.m_changed = true;
+  }
+  
+  /// Round the position to the nearest multiple of `grid` on every axis, see [crate::math::snap].
+  pub fn snap_translation(&mut self, grid: f32) {
+    self.m_transform[0] = self.m_transform[0].snap(grid);
+    self.m_changed = true;
+  }
+  
+  /// Round the euler rotation, in degrees, to the nearest multiple of `grid_degrees` on every axis.
+  pub fn snap_rotation(&mut self, grid_degrees: f32) {
+    self.m_transform[1] = self.m_transform[1].snap(grid_degrees);
+    
+    // Keep the quaternion path in sync if used.
+    if self.m_orientation.is_some() {
+      self.m_orientation = Some(Quaternion::from_euler(&self.m_transform[1]));
+    }
+    self.m_changed = true;
+  }
+  
+  /// Apply a rotation on top of the current orientation, switching the entity to the quaternion path from then on.
+  pub fn rotate_quaternion(&mut self, rotation: Quaternion) {
+    let orientation: Quaternion = self.m_orientation.unwrap_or(Quaternion::from_euler(&self.m_transform[1]));
+    self.m_orientation = Some((rotation * orientation).normalize());
+    self.m_changed = true;
+  }
+  
+  pub fn set_orientation(&mut self, orientation: Option<Quaternion>) {
+    self.m_orientation = orientation.map(|quaternion| quaternion.normalize());
+    self.m_changed = true;
+  }
+  
+  pub fn get_orientation(&self) -> Quaternion {
+    return self.m_orientation.unwrap_or(Quaternion::from_euler(&self.m_transform[1]));
+  }
+  
+  pub fn is_instanced(&self) -> bool {
+    return !self.m_instance_transforms.is_empty();
+  }
+  
+  pub fn get_instance_count(&self) -> usize {
+    return self.m_instance_transforms.len();
+  }
+  
+  pub fn get_instance_transform(&self, instance_index: usize) -> Option<Mat4> {
+    return self.m_instance_transforms.get(instance_index).copied();
+  }
+  
+  /// Move a single instance, uploaded along with the other changed instances on the next [Self::reapply].
+  pub fn set_instance_transform(&mut self, instance_index: usize, transform: Mat4) {
+    if instance_index >= self.m_instance_transforms.len() {
+      log!(EnumLogColor::Red, "ERROR", "[Asset] -->\t Cannot set transform of instance {0}, entity {1} only has {2} \
+      instances!", instance_index, self.m_name, self.m_instance_transforms.len());
+      return;
+    }
+    
+    self.m_instance_transforms[instance_index] = transform;
+    self.m_dirty_instances = Some(match self.m_dirty_instances {
+      Some((first, last)) => (first.min(instance_index), last.max(instance_index)),
+      None => (instance_index, instance_index)
+    });
+    self.m_changed = true;
+  }
+  
+  /// Move and rotate the entity to the given position and orientation over `duration` seconds, following the `easing`
+  /// curve, replacing any animation in progress. The position is absolute, in the same axes as [Self::translate].
+  /// Nothing moves until [Self::update_animation] gets called, typically from the layer's `on_update`.
+  pub fn animate_to(&mut self, target_position: Vec3<f32>, target_orientation: Quaternion, duration: f32, easing: EnumEasing) {
+    self.m_animation = Some(TransformAnimation {
+      m_start_position: self.m_transform[0],
+      m_end_position: Vec3::new(&[target_position.x, target_position.y, -target_position.z]),
+      m_start_orientation: self.get_orientation(),
+      m_end_orientation: target_orientation.normalize(),
+      m_duration: duration.max(0.0),
+      m_elapsed: 0.0,
+      m_easing: easing,
+    });
+  }
+  
+  /// Advance the animation started with [Self::animate_to] by `time_step` seconds, marking the entity as changed to be
+  /// picked up by [Self::reapply]. Returns whether the animation is still running afterward.
+  pub fn update_animation(&mut self, time_step: f64) -> bool {
+    let Some(animation) = self.m_animation.as_mut() else {
+      return false;
+    };
+    
+    animation.m_elapsed += time_step as f32;
+    let progress: f32 = if animation.m_duration <= f32::EPSILON { 1.0 } else { animation.m_elapsed / animation.m_duration };
+    let factor: f32 = animation.m_easing.apply(progress);
+    
+    self.m_transform[0] = animation.m_start_position.lerp(&animation.m_end_position, factor);
+    self.m_orientation = Some(Quaternion::slerp(&animation.m_start_orientation, &animation.m_end_orientation, factor));
+    self.m_changed = true;
+    
+    if progress >= 1.0 {
+      self.m_animation = None;
+      return false;
+    }
+    return true;
+  }
+  
+  pub fn is_animating(&self) -> bool {
+    return self.m_animation.is_some();
+  }
+  
+  /// Stop the animation in progress, leaving the entity where it currently is.
+  pub fn stop_animation(&mut self) {
+    self.m_animation = None;
+  }
+  
+  /// Start playing the skeletal animation named, from its first frame, replacing the one in progress. Non-looping
+  /// clips hold their last frame once over. Requires an entity built from a rigged asset with
+  /// [EnumPrimitiveShading::Skinned] and [Self::update_skeleton] to be called every frame.
+  pub fn play_animation(&mut self, name: &str, looping: bool) -> Result<(), EnumAssetError> {
+    if self.m_skeleton.is_none() {
+      log!(EnumLogColor::Red, "ERROR", "[REntity] -->\t Cannot play animation {0} on entity {1}, entity has no skeleton!",
+        name, self.m_name);
+      return Err(EnumAssetError::InvalidAnimation);
+    }
+    
+    let Some(clip_index) = self.m_animation_clips.iter().position(|clip| clip.get_name() == name) else {
+      log!(EnumLogColor::Red, "ERROR", "[REntity] -->\t Cannot play animation {0} on entity {1}, no such clip!",
+        name, self.m_name);
+      return Err(EnumAssetError::InvalidAnimation);
+    };
+    
+    self.m_skeletal_playback = Some(SkeletalPlayback {
+      m_clip_index: clip_index,
+      m_time: 0.0,
+      m_looping: looping,
+    });
+    return Ok(());
+  }
+  
+  /// Advance the skeletal animation playing by `time_step` seconds and recompute the bone matrices, uploaded to the
+  /// `ubo_bones` block on the next [Self::reapply]. Without a clip playing, the skeleton is kept in bind pose.
+  pub fn update_skeleton(&mut self, time_step: f64) {
+    let Some(skeleton) = self.m_skeleton.as_ref() else {
+      return;
+    };
+    
+    let (clip, time) = match self.m_skeletal_playback.as_mut() {
+      Some(playback) => {
+        let clip: &AnimationClip = &self.m_animation_clips[playback.m_clip_index];
+        playback.m_time = clip.resolve_time(playback.m_time + time_step as f32, playback.m_looping);
+        (Some(clip), playback.m_time)
+      }
+      None => (None, 0.0),
+    };
+    
+    self.m_bone_matrices = skeleton.compute_pose(clip, time);
+    self.m_bones_changed = true;
     self.m_changed = true;
   }
   
+  pub fn stop_skeletal_animation(&mut self) {
+    self.m_skeletal_playback = None;
+  }
+  
+  /// Name and playback time of the skeletal animation playing, if any.
+  pub fn get_playing_animation(&self) -> Option<(&str, f32)> {
+    return self.m_skeletal_playback
+      .map(|playback| (self.m_animation_clips[playback.m_clip_index].get_name(), playback.m_time));
+  }
+  
+  pub fn get_animation_clips(&self) -> &[AnimationClip] {
+    return &self.m_animation_clips;
+  }
+  
+  pub fn get_bone_matrices(&self) -> &[Mat4] {
+    return &self.m_bone_matrices;
+  }
+  
+  /// Rig an entity by hand, for procedural or test content.
+  pub fn set_skeleton(&mut self, skeleton: Skeleton, animation_clips: Vec<AnimationClip>) {
+    self.m_skeleton = Some(skeleton);
+    self.m_animation_clips = animation_clips;
+    self.m_skeletal_playback = None;
+  }
+  
   pub fn scale(&mut self, amount_x: f32, amount_y: f32, amount_z: f32) {
     self.m_transform[2] += Vec3::new(&[amount_y, amount_x, amount_z]);
     self.m_changed = true;
   }
   
   pub fn map_texture(&mut self, texture_array: &TextureArray, primitive_mapping_method: EnumAssetMapMethod) {
+    self.m_texture_mapping = Some(primitive_mapping_method.clone());
     return match primitive_mapping_method {
       EnumAssetMapMethod::OneForEach(start_index, end_index) => {
         if end_index - start_index > self.m_sub_meshes.len() {
@@ -744,28 +1133,88 @@ impl REntity {
   }
   
   pub fn apply(&mut self, shader_associated: &mut Shader) -> Result<(), EnumRendererError> {
+    if self.is_instanced() {
+      let transforms: Vec<Mat4> = self.m_instance_transforms.clone();
+      return self.apply_instanced(shader_associated, &transforms);
+    }
     let renderer = Engine::get_active_renderer();
     
     renderer.enqueue(self, shader_associated)?;
     
+    self.mark_as_sent();
+    return Ok(());
+  }
+  
+  /// Render this entity once per transform in a single draw call, instead of enqueuing a copy of it per transform.
+  /// The shader associated must declare where the per-instance matrix lives with
+  /// [`EnumShaderHint::InstanceMatrixLocation`](crate::graphics::shader::EnumShaderHint::InstanceMatrixLocation).
+  /// Calling this again replaces the previous instances.
+  pub fn apply_instanced(&mut self, shader_associated: &mut Shader, transforms: &[Mat4]) -> Result<(), EnumRendererError> {
+    let renderer = Engine::get_active_renderer();
+    
+    renderer.enqueue_instanced(self, shader_associated, transforms)?;
+    self.m_instance_transforms = transforms.to_vec();
+    self.m_dirty_instances = None;
+    self.mark_as_sent();
+    return Ok(());
+  }
+  
+  pub(crate) fn mark_as_sent(&mut self) {
     self.m_sent = true;
     self.m_changed = false;
-    return Ok(());
+    // The renderer picks up the blend mode and shadow flags when enqueuing.
+    self.m_last_blend_mode = self.m_blend_mode;
+    self.m_last_cast_shadows = self.m_cast_shadows;
+    self.m_last_receive_shadows = self.m_receive_shadows;
   }
   
   pub fn reapply(&mut self) -> Result<(), EnumRendererError> {
+    // Instanced entities only upload the instances changed, their own transform isn't used.
+    if self.is_instanced() {
+      if let (true, Some((first, last))) = (self.m_sent, self.m_dirty_instances) {
+        let renderer = Engine::get_active_renderer();
+        renderer.update_instance_transforms(self.m_renderer_id, first, &self.m_instance_transforms[first..=last])?;
+        self.m_dirty_instances = None;
+      }
+      self.m_changed = false;
+      return Ok(());
+    }
+    
     if self.m_changed && self.m_sent {
       let renderer = Engine::get_active_renderer();
       let matrix = self.get_matrix();
       
       renderer.update_ubo_model(matrix, self.m_sub_meshes.first().unwrap().get_entity_id() as u64, None, self.m_sub_meshes.len())?;
       
+      if self.m_bones_changed {
+        renderer.update_ubo_bones(self.get_uuid(), &self.m_bone_matrices)?;
+        self.m_bones_changed = false;
+      }
+      
       if self.m_last_primitive_mode != self.m_primitive_mode {
-        renderer.toggle_primitive_mode(self.m_name, self.m_primitive_mode, self.m_sub_meshes.first().unwrap().get_entity_id() as u64,
+        renderer.toggle_primitive_mode(&self.m_name, self.m_primitive_mode, self.m_sub_meshes.first().unwrap().get_entity_id() as u64,
           None, self.m_sub_meshes.len())?;
         self.m_last_primitive_mode = self.m_primitive_mode;
       }
       
+      if self.m_last_blend_mode != self.m_blend_mode {
+        renderer.toggle_blend_mode(self.m_sub_meshes.first().unwrap().get_entity_id() as u64, None, self.m_sub_meshes.len(),
+          self.m_blend_mode)?;
+        self.m_last_blend_mode = self.m_blend_mode;
+      }
+      
+      if self.m_last_cast_shadows != self.m_cast_shadows {
+        renderer.toggle_shadow_casting(self.m_sub_meshes.first().unwrap().get_entity_id() as u64, None,
+          self.m_sub_meshes.len(), self.m_cast_shadows)?;
+        self.m_last_cast_shadows = self.m_cast_shadows;
+      }
+      
+      if self.m_last_receive_shadows != self.m_receive_shadows {
+        renderer.toggle_shadow_receiving(self.m_sub_meshes.first().unwrap().get_entity_id() as u64, None,
+          self.m_sub_meshes.len(), self.m_receive_shadows)?;
+        self.m_last_receive_shadows = self.m_receive_shadows;
+      }
+      
       self.m_changed = false;
     }
     return Ok(());
@@ -807,6 +1256,51 @@ impl REntity {
     }
   }
   
+  pub fn set_cast_shadows(&mut self, flag: bool) {
+    if self.m_cast_shadows != flag {
+      self.m_cast_shadows = flag;
+      self.m_changed = true;
+    }
+  }
+  
+  pub fn set_receive_shadows(&mut self, flag: bool) {
+    if self.m_receive_shadows != flag {
+      self.m_receive_shadows = flag;
+      self.m_changed = true;
+    }
+  }
+  
+  /// Shorthand for [Self::set_blend_mode], alpha blending the entity or making it opaque.
+  pub fn set_transparent(&mut self, flag: bool) {
+    if self.is_transparent() != flag {
+      self.set_blend_mode(flag.then_some(EnumRendererBlendMode::AlphaBlend).unwrap_or(EnumRendererBlendMode::Opaque));
+    }
+  }
+  
+  pub fn is_transparent(&self) -> bool {
+    return self.m_blend_mode != EnumRendererBlendMode::Opaque;
+  }
+  
+  /// Any mode other than [EnumRendererBlendMode::Opaque] draws the entity after opaque ones, sorted back-to-front.
+  pub fn set_blend_mode(&mut self, mode: EnumRendererBlendMode) {
+    if self.m_blend_mode != mode {
+      self.m_blend_mode = mode;
+      self.m_changed = true;
+    }
+  }
+  
+  pub fn get_blend_mode(&self) -> EnumRendererBlendMode {
+    return self.m_blend_mode;
+  }
+  
+  pub fn casts_shadows(&self) -> bool {
+    return self.m_cast_shadows;
+  }
+  
+  pub fn receives_shadows(&self) -> bool {
+    return self.m_receive_shadows;
+  }
+  
   pub fn is_sent(&self) -> bool {
     return self.m_sent;
   }
@@ -819,7 +1313,67 @@ impl REntity {
     return self.m_renderer_id;
   }
   
+  /// Bounding box of the entity in local space.
+  pub fn get_bounds(&self) -> Aabb {
+    return self.m_bounds;
+  }
+  
+  /// Bounding box of the entity once moved, rotated and scaled.
+  pub fn get_world_bounds(&self) -> Aabb {
+    return self.m_bounds.transform(&self.get_matrix());
+  }
+  
+  /// Distance along the ray to the entity's world bounding box, if hit. Compare distances between entities to pick the
+  /// closest one.
+  pub fn intersects_ray(&self, origin: Vec3<f32>, direction: Vec3<f32>) -> Option<f32> {
+    return self.get_world_bounds().intersects_ray(origin, direction);
+  }
+  
+  /// Attach the entity under a parent transform (see [crate::assets::scene::Scene]), or detach it with `None`.
+  pub(crate) fn set_parent_matrix(&mut self, parent_matrix: Option<Mat4>) {
+    self.m_parent_matrix = parent_matrix;
+    self.m_changed = true;
+  }
+  
+  /// File the entity was loaded from, or `None` for the built-in cube (see [REntity::default]).
+  pub fn get_asset_path(&self) -> Option<&str> {
+    return self.m_asset_path.as_deref();
+  }
+  
+  /// Last texture mapping applied through [Self::map_texture], if any.
+  pub fn get_texture_mapping(&self) -> Option<&EnumAssetMapMethod> {
+    return self.m_texture_mapping.as_ref();
+  }
+  
+  pub(crate) fn set_texture_mapping(&mut self, texture_mapping: Option<EnumAssetMapMethod>) {
+    self.m_texture_mapping = texture_mapping;
+  }
+  
+  /// Raw translation, rotation and scale, as stored (see [Self::translate]).
+  pub(crate) fn get_raw_transform(&self) -> ([Vec3<f32>; 3], Option<Quaternion>) {
+    return (self.m_transform, self.m_orientation);
+  }
+  
+  pub(crate) fn set_raw_transform(&mut self, transform: [Vec3<f32>; 3], orientation: Option<Quaternion>) {
+    self.m_transform = transform;
+    self.m_orientation = orientation;
+    self.m_changed = true;
+  }
+  
+  /// World matrix of the entity, including its parent's transform when part of a scene hierarchy.
   pub fn get_matrix(&self) -> Mat4 {
+    if let Some(parent_matrix) = self.m_parent_matrix {
+      return parent_matrix * self.get_local_matrix();
+    }
+    return self.get_local_matrix();
+  }
+  
+  /// Matrix of the entity's own translation, rotation and scale, relative to its parent if any.
+  pub fn get_local_matrix(&self) -> Mat4 {
+    if let Some(orientation) = self.m_orientation {
+      return Mat4::translation_matrix(&self.m_transform[0])
+        * (orientation.to_mat4() * Mat4::scale_matrix(&self.m_transform[2]));
+    }
     return Mat4::apply_transformations(&self.m_transform[0],
       &self.m_transform[1], &self.m_transform[2]);
   }
@@ -835,7 +1389,8 @@ impl Display for dyn TraitPrimitive {
 
 impl Display for REntity {
   fn fmt(&self, format: &mut Formatter<'_>) -> std::fmt::Result {
-    write!(format, "UUID: {3}\n{2:113}Type: {0:?}\n{2:113}Sent?: {1}\n{2:113}Data:", self.m_type, self.m_sent, "", self.get_uuid())?;
+    write!(format, "UUID: {3}\n{2:113}Type: {0:?}\n{2:113}Sent?: {1}\n{2:113}Instances: {4}\n{2:113}Data:", self.m_type,
+      self.m_sent, "", self.get_uuid(), self.m_instance_transforms.len().max(1))?;
     
     for (sub_mesh_index, sub_mesh) in self.m_sub_meshes.iter().enumerate() {
       write!(format, "\n{0:113}[{1}]:\n{0:115}{2}", "", sub_mesh_index + 1, sub_mesh)?;
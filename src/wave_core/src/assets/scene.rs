@@ -0,0 +1,315 @@
+/*
+ MIT License
+ 
+ Copyright (c) 2023 Nami Reghbati
+ 
+ Permission is hereby granted, free of charge, to any person obtaining a copy
+ of this software and associated documentation files (the "Software"), to deal
+ in the Software without restriction, including without limitation the rights
+ to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ copies of the Software, and to permit persons to whom the Software is
+ furnished to do so, subject to the following conditions:
+ 
+ The above copyright notice and this permission notice shall be included in all
+ copies or substantial portions of the Software.
+ 
+ THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ FITNESS FOR A PARTICULAR PURPOSE AND NON INFRINGEMENT. IN NO EVENT SHALL THE
+ AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ SOFTWARE.
+*/
+
+use std::fmt::{Display, Formatter};
+
+#[cfg(feature = "serde")]
+use crate::assets::asset_loader::AssetLoader;
+#[cfg(feature = "serde")]
+use crate::assets::r_assets::{EnumAssetMapMethod, EnumPrimitiveShading};
+use crate::assets::r_assets::REntity;
+use crate::graphics::renderer::EnumRendererError;
+use crate::graphics::shader::Shader;
+use crate::graphics::texture::TextureArray;
+#[cfg(feature = "serde")]
+use crate::math::Quaternion;
+use crate::math::{Aabb, Mat4, Vec3};
+use crate::utils::macros::logger::*;
+#[cfg(feature = "debug")]
+use crate::Engine;
+
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum EnumSceneError {
+  InvalidNode,
+  IoError(std::io::ErrorKind),
+  InvalidFormat,
+  AssetNotLoaded,
+}
+
+impl Display for EnumSceneError {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    write!(f, "[Scene] -->\t Error encountered with scene graph : {:?}", self)
+  }
+}
+
+// What gets saved for each node : only the data needed to rebuild the entity, no gpu handles.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SceneNodeData {
+  m_name: String,
+  m_parent: Option<usize>,
+  m_asset_path: Option<String>,
+  m_shading: EnumPrimitiveShading,
+  // Translation, rotation and scale.
+  m_transform: [[f32; 3]; 3],
+  // (w, x, y, z).
+  m_orientation: Option<[f32; 4]>,
+  m_texture_mapping: Option<EnumAssetMapMethod>,
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SceneData {
+  m_nodes: Vec<SceneNodeData>,
+}
+
+struct SceneNode {
+  m_entity: REntity,
+  m_parent: Option<usize>,
+  m_children: Vec<usize>,
+  // Cached world matrix, valid after the last call to [Scene::update_transforms].
+  m_world_matrix: Mat4,
+  // Newly added nodes have never had their world matrix computed.
+  m_dirty: bool,
+}
+
+/// Entities organized in a tree, where each child's world matrix is its parent's world matrix times its own local
+/// matrix. Nodes are referred to by the id returned when adding them.
+pub struct Scene {
+  m_nodes: Vec<SceneNode>,
+  m_roots: Vec<usize>,
+}
+
+impl Default for Scene {
+  fn default() -> Self {
+    return Scene::new();
+  }
+}
+
+impl Scene {
+  pub fn new() -> Self {
+    return Scene {
+      m_nodes: vec![],
+      m_roots: vec![],
+    };
+  }
+  
+  /// Add an entity at the root of the scene, returning its node id.
+  pub fn add_entity(&mut self, entity: REntity) -> usize {
+    let node_id = self.push_node(entity, None);
+    self.m_roots.push(node_id);
+    return node_id;
+  }
+  
+  /// Add an entity under an existing node, returning its node id. The child's transform becomes relative to its parent.
+  pub fn add_child(&mut self, parent_id: usize, entity: REntity) -> Result<usize, EnumSceneError> {
+    if parent_id >= self.m_nodes.len() {
+      log!(EnumLogColor::Red, "ERROR", "[Scene] -->\t Cannot add child to node {0}, node does not exist!", parent_id);
+      return Err(EnumSceneError::InvalidNode);
+    }
+    let node_id = self.push_node(entity, Some(parent_id));
+    self.m_nodes[parent_id].m_children.push(node_id);
+    return Ok(node_id);
+  }
+  
+  pub fn len(&self) -> usize {
+    return self.m_nodes.len();
+  }
+  
+  pub fn is_empty(&self) -> bool {
+    return self.m_nodes.is_empty();
+  }
+  
+  pub fn get_entity(&self, node_id: usize) -> Option<&REntity> {
+    return self.m_nodes.get(node_id).map(|node| &node.m_entity);
+  }
+  
+  /// Transforming the entity returned flags it, and its children, for the next [Self::update_transforms].
+  pub fn get_entity_mut(&mut self, node_id: usize) -> Option<&mut REntity> {
+    return self.m_nodes.get_mut(node_id).map(|node| &mut node.m_entity);
+  }
+  
+  pub fn get_parent(&self, node_id: usize) -> Option<usize> {
+    return self.m_nodes.get(node_id).and_then(|node| node.m_parent);
+  }
+  
+  pub fn get_children(&self, node_id: usize) -> &[usize] {
+    return self.m_nodes.get(node_id).map(|node| node.m_children.as_slice()).unwrap_or(&[]);
+  }
+  
+  /// World matrix of a node as of the last [Self::update_transforms].
+  pub fn get_world_matrix(&self, node_id: usize) -> Option<Mat4> {
+    return self.m_nodes.get(node_id).map(|node| node.m_world_matrix);
+  }
+  
+  /// Recompute world matrices of changed nodes and all of their descendants, top-down. Entities whose world matrix
+  /// changed get flagged as changed, so that [Self::reapply] uploads them to the renderer.
+  pub fn update_transforms(&mut self) {
+    // Node, parent world matrix and whether the parent was recomputed.
+    let mut stack: Vec<(usize, Option<Mat4>, bool)> = self.m_roots.iter().rev()
+      .map(|&root_id| (root_id, None, false))
+      .collect();
+    
+    while let Some((node_id, parent_matrix, parent_dirty)) = stack.pop() {
+      let node = &mut self.m_nodes[node_id];
+      let dirty = parent_dirty || node.m_dirty || node.m_entity.has_changed();
+      
+      if dirty {
+        if parent_matrix.is_some() {
+          node.m_entity.set_parent_matrix(parent_matrix);
+        }
+        node.m_world_matrix = node.m_entity.get_matrix();
+        node.m_dirty = false;
+      }
+      
+      for &child_id in node.m_children.iter().rev() {
+        stack.push((child_id, Some(node.m_world_matrix), dirty));
+      }
+    }
+  }
+  
+  /// Bounding box enclosing all entities of the scene in world space, as of the last [Self::update_transforms]. Useful
+  /// to fit a directional light's shadow map around the scene.
+  pub fn get_world_bounds(&self) -> Aabb {
+    let corners: Vec<Vec3<f32>> = self.m_nodes.iter()
+      .flat_map(|node| node.m_entity.get_bounds().transform(&node.m_world_matrix).get_corners())
+      .collect();
+    return Aabb::from_points(&corners);
+  }
+  
+  /// Flattened world transforms of all nodes, as (node id, entity, world matrix), in insertion order.
+  pub fn iter(&self) -> impl Iterator<Item=(usize, &REntity, Mat4)> {
+    return self.m_nodes.iter().enumerate().map(|(node_id, node)| (node_id, &node.m_entity, node.m_world_matrix));
+  }
+  
+  /// Send all entities in the scene to the renderer, with their world transforms.
+  pub fn apply(&mut self, shader_associated: &mut Shader) -> Result<(), EnumRendererError> {
+    self.update_transforms();
+    
+    for node in self.m_nodes.iter_mut() {
+      node.m_entity.apply(shader_associated)?;
+    }
+    return Ok(());
+  }
+  
+  /// Propagate transform changes and upload the entities affected to the renderer.
+  pub fn reapply(&mut self) -> Result<(), EnumRendererError> {
+    self.update_transforms();
+    
+    for node in self.m_nodes.iter_mut() {
+      node.m_entity.reapply()?;
+    }
+    return Ok(());
+  }
+  
+  /// Re-apply the texture mapping each entity had when its scene was saved, now that its textures are loaded.
+  pub fn map_textures(&mut self, texture_array: &TextureArray) {
+    for node in self.m_nodes.iter_mut() {
+      if let Some(texture_mapping) = node.m_entity.get_texture_mapping().cloned() {
+        node.m_entity.map_texture(texture_array, texture_mapping);
+      }
+    }
+  }
+  
+  /// Save the hierarchy as JSON : names, transforms, asset paths, shading and texture mappings of every entity.
+  #[cfg(feature = "serde")]
+  pub fn save(&self, file_path: &str) -> Result<(), EnumSceneError> {
+    let scene_data = SceneData {
+      m_nodes: self.m_nodes.iter().map(|node| {
+        let ([translation, rotation, scale], orientation) = node.m_entity.get_raw_transform();
+        return SceneNodeData {
+          m_name: String::from(node.m_entity.get_name()),
+          m_parent: node.m_parent,
+          m_asset_path: node.m_entity.get_asset_path().map(String::from),
+          m_shading: node.m_entity.m_type,
+          m_transform: [[translation.x, translation.y, translation.z], [rotation.x, rotation.y, rotation.z],
+            [scale.x, scale.y, scale.z]],
+          m_orientation: orientation.map(|quaternion| [quaternion.w, quaternion.x, quaternion.y, quaternion.z]),
+          m_texture_mapping: node.m_entity.get_texture_mapping().cloned(),
+        };
+      }).collect(),
+    };
+    
+    let json: String = serde_json::to_string_pretty(&scene_data).map_err(|_err| {
+      log!(EnumLogColor::Red, "ERROR", "[Scene] -->\t Cannot serialize scene : {0}", _err);
+      return EnumSceneError::InvalidFormat;
+    })?;
+    std::fs::write(file_path, json).map_err(|err| {
+      log!(EnumLogColor::Red, "ERROR", "[Scene] -->\t Cannot save scene to {0} : {1}", file_path, err);
+      return EnumSceneError::IoError(err.kind());
+    })?;
+    
+    log!("INFO", "[Scene] -->\t Saved {0} entities to {1}", self.m_nodes.len(), file_path);
+    return Ok(());
+  }
+  
+  /// Rebuild a scene saved with [Self::save], loading each entity's asset again through the asset loader. Entities
+  /// still need to be applied (see [Self::apply]) and their textures mapped again (see [Self::map_textures]).
+  #[cfg(feature = "serde")]
+  pub fn load(file_path: &str, asset_loader: &AssetLoader) -> Result<Self, EnumSceneError> {
+    let json: String = std::fs::read_to_string(file_path).map_err(|err| {
+      log!(EnumLogColor::Red, "ERROR", "[Scene] -->\t Cannot read scene from {0} : {1}", file_path, err);
+      return EnumSceneError::IoError(err.kind());
+    })?;
+    let scene_data: SceneData = serde_json::from_str(&json).map_err(|_err| {
+      log!(EnumLogColor::Red, "ERROR", "[Scene] -->\t Invalid scene file {0} : {1}", file_path, _err);
+      return EnumSceneError::InvalidFormat;
+    })?;
+    
+    let mut scene = Scene::new();
+    for node_data in scene_data.m_nodes.into_iter() {
+      let mut entity: REntity = match node_data.m_asset_path.as_deref() {
+        Some(asset_path) => {
+          let asset = asset_loader.load(asset_path).map_err(|_err| {
+            log!(EnumLogColor::Red, "ERROR", "[Scene] -->\t Cannot load asset {0} : {1:?}", asset_path, _err);
+            return EnumSceneError::AssetNotLoaded;
+          })?;
+          REntity::new(asset.as_ref(), node_data.m_shading, &node_data.m_name)
+        }
+        None => {
+          let mut entity = REntity::default();
+          entity.m_name = node_data.m_name;
+          entity.m_type = node_data.m_shading;
+          entity
+        }
+      };
+      
+      let [translation, rotation, scale] = node_data.m_transform.map(|vector| Vec3::new(&vector));
+      entity.set_raw_transform([translation, rotation, scale], node_data.m_orientation
+        .map(|[w, x, y, z]| Quaternion { w, x, y, z }));
+      entity.set_texture_mapping(node_data.m_texture_mapping);
+      
+      // Parents are always saved before their children.
+      match node_data.m_parent {
+        Some(parent_id) => scene.add_child(parent_id, entity).map_err(|_| EnumSceneError::InvalidFormat)?,
+        None => scene.add_entity(entity),
+      };
+    }
+    
+    scene.update_transforms();
+    log!("INFO", "[Scene] -->\t Loaded {0} entities from {1}", scene.len(), file_path);
+    return Ok(scene);
+  }
+  
+  fn push_node(&mut self, entity: REntity, parent_id: Option<usize>) -> usize {
+    self.m_nodes.push(SceneNode {
+      m_world_matrix: entity.get_matrix(),
+      m_entity: entity,
+      m_parent: parent_id,
+      m_children: vec![],
+      m_dirty: true,
+    });
+    return self.m_nodes.len() - 1;
+  }
+}
@@ -0,0 +1,346 @@
+/*
+ MIT License
+ 
+ Copyright (c) 2023 Nami Reghbati
+ 
+ Permission is hereby granted, free of charge, to any person obtaining a copy
+ of this software and associated documentation files (the "Software"), to deal
+ in the Software without restriction, including without limitation the rights
+ to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ copies of the Software, and to permit persons to whom the Software is
+ furnished to do so, subject to the following conditions:
+ 
+ The above copyright notice and this permission notice shall be included in all
+ copies or substantial portions of the Software.
+ 
+ THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ FITNESS FOR A PARTICULAR PURPOSE AND NON INFRINGEMENT. IN NO EVENT SHALL THE
+ AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ SOFTWARE.
+*/
+
+use std::collections::HashMap;
+
+#[cfg(feature = "debug")]
+use crate::Engine;
+use crate::math::{Mat4, Quaternion, Vec3};
+use crate::utils::macros::logger::*;
+
+/// Uniform block binding of the bone matrices of skinned entities, declared in shaders as
+/// `layout (std140, binding = 13) uniform ubo_bones { mat4 m_bones[128]; }`. Requires GLSL 420 or above.
+pub const C_BONES_UBO_BINDING: u32 = 13;
+/// Bones past this count are ignored when uploading the pose.
+pub const C_MAX_BONES: usize = 128;
+/// Bone influences kept per vertex, the lightest ones get dropped past that.
+pub const C_MAX_BONE_INFLUENCES: usize = 4;
+
+/// Node of the scene hierarchy, which may drive a bone.
+#[derive(Debug, Clone)]
+pub struct SkeletonNode {
+  pub m_name: String,
+  // Always precedes its children in the skeleton, so poses can be computed in a single pass.
+  pub m_parent: Option<usize>,
+  // Transform relative to the parent when not animated.
+  pub m_local_bind: Mat4,
+  // Slot in the bone palette, if a mesh is skinned to this node.
+  pub m_bone: Option<usize>,
+}
+
+/// Node hierarchy of a rigged model along with the bones its vertices are skinned to.
+#[derive(Debug, Clone)]
+pub struct Skeleton {
+  m_nodes: Vec<SkeletonNode>,
+  m_bone_names: Vec<String>,
+  // Inverse bind matrix of each bone, bringing vertices from mesh space into the bone's space.
+  m_bone_offsets: Vec<Mat4>,
+  m_global_inverse: Mat4,
+}
+
+impl Skeleton {
+  pub fn new() -> Self {
+    return Self {
+      m_nodes: Vec::new(),
+      m_bone_names: Vec::new(),
+      m_bone_offsets: Vec::new(),
+      m_global_inverse: Mat4::default(),
+    };
+  }
+  
+  /// Append a node to the hierarchy, returning its index. Parents must be added before their children.
+  pub fn add_node(&mut self, name: &str, parent: Option<usize>, local_bind: Mat4) -> usize {
+    self.m_nodes.push(SkeletonNode {
+      m_name: String::from(name),
+      m_parent: parent.filter(|parent_index| *parent_index < self.m_nodes.len()),
+      m_local_bind: local_bind,
+      m_bone: self.find_bone(name),
+    });
+    return self.m_nodes.len() - 1;
+  }
+  
+  /// Register a bone with its inverse bind matrix, returning its slot in the bone palette. Bones shared between
+  /// meshes are only registered once.
+  pub fn add_bone(&mut self, name: &str, offset_matrix: Mat4) -> usize {
+    if let Some(bone_index) = self.find_bone(name) {
+      return bone_index;
+    }
+    
+    if self.m_bone_names.len() >= C_MAX_BONES {
+      log!(EnumLogColor::Yellow, "WARN", "[Skeleton] -->\t Bone {0} exceeds the maximum of {1} bones, vertices \
+        skinned to it will not follow its pose!", name, C_MAX_BONES);
+    }
+    
+    self.m_bone_names.push(String::from(name));
+    self.m_bone_offsets.push(offset_matrix);
+    let bone_index = self.m_bone_names.len() - 1;
+    
+    if let Some(node) = self.m_nodes.iter_mut().find(|node| node.m_name == name) {
+      node.m_bone = Some(bone_index);
+    }
+    return bone_index;
+  }
+  
+  pub fn find_bone(&self, name: &str) -> Option<usize> {
+    return self.m_bone_names.iter().position(|bone_name| bone_name == name);
+  }
+  
+  pub fn set_global_inverse(&mut self, global_inverse: Mat4) {
+    self.m_global_inverse = global_inverse;
+  }
+  
+  pub fn get_nodes(&self) -> &[SkeletonNode] {
+    return &self.m_nodes;
+  }
+  
+  pub fn get_bone_count(&self) -> usize {
+    return self.m_bone_names.len();
+  }
+  
+  /// Compute the skinning matrix of every bone, at `time` seconds into the clip given, or in bind pose without one.
+  /// Nodes the clip doesn't animate keep their bind transform.
+  pub fn compute_pose(&self, clip: Option<&AnimationClip>, time: f32) -> Vec<Mat4> {
+    let mut global_transforms: Vec<Mat4> = Vec::with_capacity(self.m_nodes.len());
+    let mut bone_matrices: Vec<Mat4> = vec![Mat4::default(); self.m_bone_names.len()];
+    
+    for node in self.m_nodes.iter() {
+      let local_transform: Mat4 = clip
+        .and_then(|animation_clip| animation_clip.get_channel(&node.m_name))
+        .map(|channel| channel.sample(time))
+        .unwrap_or(node.m_local_bind);
+      
+      let global_transform: Mat4 = match node.m_parent {
+        Some(parent_index) => global_transforms[parent_index] * local_transform,
+        None => local_transform,
+      };
+      
+      if let Some(bone_index) = node.m_bone {
+        bone_matrices[bone_index] = self.m_global_inverse * global_transform * self.m_bone_offsets[bone_index];
+      }
+      global_transforms.push(global_transform);
+    }
+    return bone_matrices;
+  }
+  
+  /// Build the skeleton of an imported scene, if any of its meshes is skinned.
+  pub(crate) fn from_scene(scene: &assimp::scene::Scene) -> Option<Self> {
+    if !scene.mesh_iter().any(|mesh| mesh.num_bones() > 0) {
+      return None;
+    }
+    
+    let mut skeleton = Skeleton::new();
+    for mesh in scene.mesh_iter() {
+      for bone_index in 0..mesh.num_bones() {
+        if let Some(bone) = mesh.get_bone(bone_index) {
+          skeleton.add_bone(bone.name(), Skeleton::convert_matrix(&bone.offset_matrix()));
+        }
+      }
+    }
+    
+    let root_node = scene.root_node();
+    skeleton.add_scene_node(&root_node, None);
+    
+    let root_transform = Skeleton::convert_matrix(&root_node.transformation());
+    skeleton.set_global_inverse(root_transform.inverse().unwrap_or(Mat4::default()));
+    
+    log!(EnumLogColor::Purple, "INFO", "[Skeleton] -->\t Loaded skeleton with {0} bones over {1} nodes",
+      skeleton.get_bone_count(), skeleton.m_nodes.len());
+    return Some(skeleton);
+  }
+  
+  fn add_scene_node(&mut self, node: &assimp::scene::Node, parent: Option<usize>) {
+    let node_index = self.add_node(node.name(), parent, Skeleton::convert_matrix(&node.transformation()));
+    for child in node.child_iter() {
+      self.add_scene_node(&child, Some(node_index));
+    }
+  }
+  
+  fn convert_matrix(matrix: &assimp::math::Matrix4x4) -> Mat4 {
+    // Assimp matrices are row major as well.
+    return Mat4::from([
+      [matrix.a1, matrix.a2, matrix.a3, matrix.a4],
+      [matrix.b1, matrix.b2, matrix.b3, matrix.b4],
+      [matrix.c1, matrix.c2, matrix.c3, matrix.c4],
+      [matrix.d1, matrix.d2, matrix.d3, matrix.d4]]);
+  }
+}
+
+/// Keyframes of a single node, each track sorted by time, in seconds.
+#[derive(Debug, Clone, Default)]
+pub struct AnimationChannel {
+  pub m_node_name: String,
+  pub m_positions: Vec<(f32, Vec3<f32>)>,
+  pub m_rotations: Vec<(f32, Quaternion)>,
+  pub m_scales: Vec<(f32, Vec3<f32>)>,
+}
+
+impl AnimationChannel {
+  pub fn new(node_name: &str) -> Self {
+    return Self {
+      m_node_name: String::from(node_name),
+      m_positions: Vec::new(),
+      m_rotations: Vec::new(),
+      m_scales: Vec::new(),
+    };
+  }
+  
+  /// Local transform of the node at `time` seconds, interpolating between the surrounding keys and holding the first
+  /// and last keys outside of them.
+  pub fn sample(&self, time: f32) -> Mat4 {
+    let position: Vec3<f32> = AnimationChannel::sample_track(&self.m_positions, time, Vec3::default(),
+      |from, to, factor| from.lerp(to, factor));
+    let rotation: Quaternion = AnimationChannel::sample_track(&self.m_rotations, time, Quaternion::default(),
+      |from, to, factor| Quaternion::slerp(from, to, factor));
+    let scale: Vec3<f32> = AnimationChannel::sample_track(&self.m_scales, time, Vec3::new(&[1.0, 1.0, 1.0]),
+      |from, to, factor| from.lerp(to, factor));
+    
+    let mut translation_matrix: Mat4 = Mat4::default();
+    translation_matrix[0][3] = position.x;
+    translation_matrix[1][3] = position.y;
+    translation_matrix[2][3] = position.z;
+    
+    let mut scale_matrix: Mat4 = Mat4::default();
+    scale_matrix[0][0] = scale.x;
+    scale_matrix[1][1] = scale.y;
+    scale_matrix[2][2] = scale.z;
+    
+    return translation_matrix * rotation.to_mat4() * scale_matrix;
+  }
+  
+  fn sample_track<T: Copy>(keys: &[(f32, T)], time: f32, default: T, interpolate: impl Fn(&T, &T, f32) -> T) -> T {
+    let (Some(first_key), Some(last_key)) = (keys.first(), keys.last()) else {
+      return default;
+    };
+    
+    if time <= first_key.0 {
+      return first_key.1;
+    }
+    if time >= last_key.0 {
+      return last_key.1;
+    }
+    
+    // Index of the first key past the time requested, guaranteed to be at least 1 by the checks above.
+    let next_index: usize = keys.partition_point(|key| key.0 <= time);
+    let (previous_time, previous_value) = keys[next_index - 1];
+    let (next_time, next_value) = keys[next_index];
+    let span: f32 = next_time - previous_time;
+    let factor: f32 = if span <= f32::EPSILON { 0.0 } else { (time - previous_time) / span };
+    
+    return interpolate(&previous_value, &next_value, factor);
+  }
+}
+
+/// Named animation of a skeleton, such as a walk cycle.
+#[derive(Debug, Clone)]
+pub struct AnimationClip {
+  m_name: String,
+  // Length in seconds.
+  m_duration: f32,
+  m_channels: Vec<AnimationChannel>,
+  m_channel_lookup: HashMap<String, usize>,
+}
+
+impl AnimationClip {
+  pub fn new(name: &str, duration: f32, channels: Vec<AnimationChannel>) -> Self {
+    let channel_lookup: HashMap<String, usize> = channels.iter()
+      .enumerate()
+      .map(|(index, channel)| (channel.m_node_name.clone(), index))
+      .collect();
+    
+    return Self {
+      m_name: String::from(name),
+      m_duration: duration.max(0.0),
+      m_channels: channels,
+      m_channel_lookup: channel_lookup,
+    };
+  }
+  
+  pub fn get_name(&self) -> &str {
+    return &self.m_name;
+  }
+  
+  pub fn get_duration(&self) -> f32 {
+    return self.m_duration;
+  }
+  
+  pub fn get_channel(&self, node_name: &str) -> Option<&AnimationChannel> {
+    return self.m_channel_lookup.get(node_name).map(|index| &self.m_channels[*index]);
+  }
+  
+  /// Bring a playback time within the clip, wrapping around when looping and holding the last frame otherwise.
+  pub fn resolve_time(&self, time: f32, looping: bool) -> f32 {
+    if self.m_duration <= f32::EPSILON {
+      return 0.0;
+    }
+    if looping {
+      return time.rem_euclid(self.m_duration);
+    }
+    return time.clamp(0.0, self.m_duration);
+  }
+  
+  /// Convert all animations of an imported scene, with their keys in seconds.
+  pub(crate) fn from_scene(scene: &assimp::scene::Scene) -> Vec<Self> {
+    let mut clips: Vec<AnimationClip> = Vec::with_capacity(scene.num_animations() as usize);
+    
+    for (animation_index, animation) in scene.animation_iter().enumerate() {
+      // Files may omit the tick rate, in which case ticks are usually meant as frames at 25 fps.
+      let ticks_per_second: f64 = if animation.ticks_per_second > 0.0 { animation.ticks_per_second } else { 25.0 };
+      let to_seconds = |ticks: f64| (ticks / ticks_per_second) as f32;
+      let mut channels: Vec<AnimationChannel> = Vec::with_capacity(animation.num_channels as usize);
+      
+      for channel_index in 0..animation.num_channels as usize {
+        let Some(node_animation) = animation.get_node_anim(channel_index) else {
+          continue;
+        };
+        let mut channel = AnimationChannel::new(node_animation.node_name.as_ref());
+        
+        for key_index in 0..node_animation.num_position_keys as usize {
+          if let Some(key) = node_animation.get_position_key(key_index) {
+            channel.m_positions.push((to_seconds(key.time), Vec3::new(&[key.value.x, key.value.y, key.value.z])));
+          }
+        }
+        for key_index in 0..node_animation.num_rotation_keys as usize {
+          if let Some(key) = node_animation.get_rotation_key(key_index) {
+            channel.m_rotations.push((to_seconds(key.time),
+              Quaternion::new(key.value.w, key.value.x, key.value.y, key.value.z)));
+          }
+        }
+        for key_index in 0..node_animation.num_scaling_keys as usize {
+          if let Some(key) = node_animation.get_scaling_key(key_index) {
+            channel.m_scales.push((to_seconds(key.time), Vec3::new(&[key.value.x, key.value.y, key.value.z])));
+          }
+        }
+        channels.push(channel);
+      }
+      
+      // Unnamed clips are common in glTF files, fallback to their index to still be able to play them.
+      let clip_name: String = match animation.name.as_ref() {
+        "" => format!("animation_{0}", animation_index),
+        name => String::from(name),
+      };
+      clips.push(AnimationClip::new(&clip_name, to_seconds(animation.duration), channels));
+    }
+    return clips;
+  }
+}
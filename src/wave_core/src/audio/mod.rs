@@ -0,0 +1,361 @@
+/*
+ MIT License
+
+ Copyright (c) 2023 Nami Reghbati
+
+ Permission is hereby granted, free of charge, to any person obtaining a copy
+ of this software and associated documentation files (the "Software"), to deal
+ in the Software without restriction, including without limitation the rights
+ to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ copies of the Software, and to permit persons to whom the Software is
+ furnished to do so, subject to the following conditions:
+
+ The above copyright notice and this permission notice shall be included in all
+ copies or substantial portions of the Software.
+
+ THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ FITNESS FOR A PARTICULAR PURPOSE AND NON INFRINGEMENT. IN NO EVENT SHALL THE
+ AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ SOFTWARE.
+*/
+
+use std::cell::Cell;
+use std::fmt::{Display, Formatter};
+use std::io::BufReader;
+use std::path::Path;
+use std::rc::Rc;
+
+use rodio::{Decoder, OutputStream, OutputStreamHandle, Source, SpatialSink};
+
+#[cfg(feature = "debug")]
+use crate::Engine;
+use crate::{TraitApply, TraitFree};
+use crate::math::Vec3;
+use crate::utils::macros::logger::*;
+
+/// Half the distance between the listener's ears, in world units, which drives how much sounds pan left and right.
+const C_EAR_OFFSET: f32 = 0.1;
+
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, PartialEq, Eq, Hash)]
+pub enum EnumAudioError {
+  NoOutputDevice,
+  NotApplied,
+  InvalidPath,
+  UnsupportedFormat,
+  InvalidSound,
+}
+
+impl Display for EnumAudioError {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    write!(f, "[Audio] -->\t Error encountered with audio : {:?}", self)
+  }
+}
+
+impl std::error::Error for EnumAudioError {}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum EnumAudioState {
+  NotCreated,
+  Created,
+  Deleted,
+}
+
+/// Audio formats the engine can decode, judging from the file extension.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum EnumAudioFormat {
+  Wav,
+  Ogg,
+}
+
+impl EnumAudioFormat {
+  pub fn from_path(file_path: &Path) -> Result<Self, EnumAudioError> {
+    let extension: String = file_path.extension()
+      .and_then(|extension| extension.to_str())
+      .map(|extension| extension.to_lowercase())
+      .unwrap_or_default();
+    
+    return match extension.as_str() {
+      "wav" => Ok(EnumAudioFormat::Wav),
+      "ogg" => Ok(EnumAudioFormat::Ogg),
+      _ => {
+        log!(EnumLogColor::Red, "ERROR", "[Audio] -->\t Cannot play {0}, only wav and ogg files are supported!",
+          file_path.display());
+        Err(EnumAudioError::UnsupportedFormat)
+      }
+    };
+  }
+}
+
+/// Where sounds are heard from, usually following the active camera (see [AudioLayer]).
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct AudioListener {
+  pub m_position: Vec3<f32>,
+  // Normalized direction pointing out of the right ear.
+  pub m_right: Vec3<f32>,
+}
+
+impl Default for AudioListener {
+  fn default() -> Self {
+    return Self {
+      m_position: Vec3::default(),
+      m_right: Vec3::new(&[1.0, 0.0, 0.0]),
+    };
+  }
+}
+
+impl AudioListener {
+  pub fn get_left_ear(&self) -> Vec3<f32> {
+    return self.m_position - self.get_ear_offset();
+  }
+  
+  pub fn get_right_ear(&self) -> Vec3<f32> {
+    return self.m_position + self.get_ear_offset();
+  }
+  
+  fn get_ear_offset(&self) -> Vec3<f32> {
+    let right: Vec3<f32> = self.m_right.normalize();
+    return Vec3::new(&[right.x * C_EAR_OFFSET, right.y * C_EAR_OFFSET, right.z * C_EAR_OFFSET]);
+  }
+}
+
+struct ActiveSound {
+  m_sink: Rc<SpatialSink>,
+  // Sounds without an emitter position stay centered on the listener.
+  m_emitter: Rc<Cell<Option<Vec3<f32>>>>,
+  // Own volume multiplier, the sink plays at this times the master volume.
+  m_volume: Rc<Cell<f32>>,
+}
+
+/// Handle to a sound playing, to control it afterward. Dropping the handle leaves the sound playing.
+pub struct SoundHandle {
+  m_sink: Rc<SpatialSink>,
+  m_emitter: Rc<Cell<Option<Vec3<f32>>>>,
+  m_volume: Rc<Cell<f32>>,
+  m_master_volume: Rc<Cell<f32>>,
+}
+
+impl SoundHandle {
+  pub fn stop(&self) {
+    self.m_sink.stop();
+  }
+  
+  pub fn pause(&self) {
+    self.m_sink.pause();
+  }
+  
+  pub fn resume(&self) {
+    self.m_sink.play();
+  }
+  
+  /// Volume multiplier of this sound, on top of the engine's master volume. 1.0 plays it as is.
+  pub fn set_volume(&self, volume: f32) {
+    self.m_volume.set(volume.max(0.0));
+    self.m_sink.set_volume(self.m_volume.get() * self.m_master_volume.get());
+  }
+  
+  pub fn get_volume(&self) -> f32 {
+    return self.m_volume.get();
+  }
+  
+  /// Emit the sound from a point in the world, panned relative to the listener. `None` centers it on the listener.
+  pub fn set_position(&self, position: Option<Vec3<f32>>) {
+    self.m_emitter.set(position);
+  }
+  
+  pub fn is_playing(&self) -> bool {
+    return !self.m_sink.empty() && !self.m_sink.is_paused();
+  }
+}
+
+pub struct AudioEngine {
+  m_state: EnumAudioState,
+  // The stream must outlive every sink, playback stops as soon as it gets dropped.
+  m_stream: Option<(OutputStream, OutputStreamHandle)>,
+  m_sounds: Vec<ActiveSound>,
+  m_listener: AudioListener,
+  // Shared with every sound handle, to combine with their own volume.
+  m_master_volume: Rc<Cell<f32>>,
+}
+
+impl Default for AudioEngine {
+  fn default() -> Self {
+    return Self::new();
+  }
+}
+
+impl TraitApply<EnumAudioError> for AudioEngine {
+  fn apply(&mut self) -> Result<(), EnumAudioError> {
+    if self.m_state == EnumAudioState::Created {
+      return Ok(());
+    }
+    
+    log!(EnumLogColor::Purple, "INFO", "[Audio] -->\t Opening default audio output device...");
+    let (stream, stream_handle) = OutputStream::try_default().map_err(|_err| {
+      log!(EnumLogColor::Red, "ERROR", "[Audio] -->\t Cannot open audio output device : {0}", _err);
+      return EnumAudioError::NoOutputDevice;
+    })?;
+    
+    self.m_stream = Some((stream, stream_handle));
+    self.m_state = EnumAudioState::Created;
+    log!(EnumLogColor::Green, "INFO", "[Audio] -->\t Opened audio output device successfully");
+    return Ok(());
+  }
+}
+
+impl TraitFree<EnumAudioError> for AudioEngine {
+  fn free(&mut self) -> Result<(), EnumAudioError> {
+    for sound in self.m_sounds.iter() {
+      sound.m_sink.stop();
+    }
+    self.m_sounds.clear();
+    self.m_stream = None;
+    self.m_state = EnumAudioState::Deleted;
+    return Ok(());
+  }
+}
+
+impl AudioEngine {
+  pub fn new() -> Self {
+    return Self {
+      m_state: EnumAudioState::NotCreated,
+      m_stream: None,
+      m_sounds: Vec::new(),
+      m_listener: AudioListener::default(),
+      m_master_volume: Rc::new(Cell::new(1.0)),
+    };
+  }
+  
+  pub fn is_applied(&self) -> bool {
+    return self.m_state == EnumAudioState::Created;
+  }
+  
+  /// Play a sound once, centered on the listener.
+  pub fn play_sound(&mut self, file_path: &str) -> Result<(), EnumAudioError> {
+    self.play(file_path, false)?;
+    return Ok(());
+  }
+  
+  /// Play a sound over and over until stopped through the handle returned.
+  pub fn play_looping(&mut self, file_path: &str) -> Result<SoundHandle, EnumAudioError> {
+    return self.play(file_path, true);
+  }
+  
+  /// Play a sound once, emitted from a point in the world.
+  pub fn play_sound_at(&mut self, file_path: &str, position: Vec3<f32>) -> Result<SoundHandle, EnumAudioError> {
+    let sound_handle = self.play(file_path, false)?;
+    sound_handle.set_position(Some(position));
+    self.update_spatialization();
+    return Ok(sound_handle);
+  }
+  
+  pub fn stop_all(&mut self) {
+    for sound in self.m_sounds.iter() {
+      sound.m_sink.stop();
+    }
+    self.m_sounds.clear();
+  }
+  
+  pub fn set_master_volume(&mut self, volume: f32) {
+    self.m_master_volume.set(volume.max(0.0));
+    
+    for sound in self.m_sounds.iter() {
+      sound.m_sink.set_volume(sound.m_volume.get() * self.m_master_volume.get());
+    }
+  }
+  
+  pub fn get_master_volume(&self) -> f32 {
+    return self.m_master_volume.get();
+  }
+  
+  pub fn set_listener(&mut self, listener: AudioListener) {
+    self.m_listener = listener;
+  }
+  
+  pub fn get_listener(&self) -> AudioListener {
+    return self.m_listener;
+  }
+  
+  /// Number of sounds still playing or paused.
+  pub fn get_sound_count(&self) -> usize {
+    return self.m_sounds.len();
+  }
+  
+  /// Drop finished sounds and pan the rest relative to the listener. Called every frame by [AudioLayer].
+  pub fn on_update(&mut self) {
+    self.m_sounds.retain(|sound| !sound.m_sink.empty());
+    self.update_spatialization();
+  }
+  
+  ////////////////////////////// PRIVATE FUNCTIONS ////////////////////////////////
+  
+  fn play(&mut self, file_path: &str, looping: bool) -> Result<SoundHandle, EnumAudioError> {
+    let Some((_, stream_handle)) = self.m_stream.as_ref() else {
+      log!(EnumLogColor::Red, "ERROR", "[Audio] -->\t Cannot play {0}, audio engine not applied!", file_path);
+      return Err(EnumAudioError::NotApplied);
+    };
+    
+    let path = Path::new(file_path);
+    EnumAudioFormat::from_path(path)?;
+    
+    let file = std::fs::File::open(path).map_err(|_err| {
+      log!(EnumLogColor::Red, "ERROR", "[Audio] -->\t Cannot open {0} : {1}", file_path, _err);
+      return EnumAudioError::InvalidPath;
+    })?;
+    let decoder = Decoder::new(BufReader::new(file)).map_err(|_err| {
+      log!(EnumLogColor::Red, "ERROR", "[Audio] -->\t Cannot decode {0} : {1}", file_path, _err);
+      return EnumAudioError::InvalidSound;
+    })?;
+    
+    let left_ear: Vec3<f32> = self.m_listener.get_left_ear();
+    let right_ear: Vec3<f32> = self.m_listener.get_right_ear();
+    let sink = SpatialSink::try_new(stream_handle, AudioEngine::to_array(self.m_listener.m_position),
+      AudioEngine::to_array(left_ear), AudioEngine::to_array(right_ear))
+      .map_err(|_err| {
+        log!(EnumLogColor::Red, "ERROR", "[Audio] -->\t Cannot create sink for {0} : {1}", file_path, _err);
+        return EnumAudioError::NoOutputDevice;
+      })?;
+    
+    if looping {
+      sink.append(decoder.repeat_infinite());
+    } else {
+      sink.append(decoder);
+    }
+    sink.set_volume(self.m_master_volume.get());
+    
+    let sink = Rc::new(sink);
+    let emitter = Rc::new(Cell::new(None));
+    let volume = Rc::new(Cell::new(1.0));
+    self.m_sounds.push(ActiveSound {
+      m_sink: sink.clone(),
+      m_emitter: emitter.clone(),
+      m_volume: volume.clone(),
+    });
+    
+    log!(EnumLogColor::Blue, "INFO", "[Audio] -->\t Playing {0}{1}", file_path, looping.then(|| " (looping)").unwrap_or(""));
+    return Ok(SoundHandle {
+      m_sink: sink,
+      m_emitter: emitter,
+      m_volume: volume,
+      m_master_volume: self.m_master_volume.clone(),
+    });
+  }
+  
+  fn update_spatialization(&mut self) {
+    let left_ear: [f32; 3] = AudioEngine::to_array(self.m_listener.get_left_ear());
+    let right_ear: [f32; 3] = AudioEngine::to_array(self.m_listener.get_right_ear());
+    
+    for sound in self.m_sounds.iter() {
+      let emitter: Vec3<f32> = sound.m_emitter.get().unwrap_or(self.m_listener.m_position);
+      sound.m_sink.set_emitter_position(AudioEngine::to_array(emitter));
+      sound.m_sink.set_left_ear_position(left_ear);
+      sound.m_sink.set_right_ear_position(right_ear);
+    }
+  }
+  
+  fn to_array(vector: Vec3<f32>) -> [f32; 3] {
+    return [vector.x, vector.y, vector.z];
+  }
+}
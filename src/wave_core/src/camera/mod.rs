@@ -24,10 +24,14 @@
 
 use crate::{Engine, EnumEngineError, input};
 use crate::events::EnumEvent;
-use crate::math::Mat4;
-use crate::math::Vec3;
+use crate::math::{Aabb, Mat4};
+use crate::math::{Vec2, Vec3};
 use crate::utils::macros::logger::*;
 
+/// Uniform block binding of the camera's view and projection matrices, shared by every shader and declared as
+/// `layout (std140, binding = 0) uniform ubo_camera { mat4 m_view; mat4 m_projection; }`.
+pub const C_CAMERA_UBO_BINDING: u32 = 0;
+
 pub enum EnumError {
   InvalidDimensions,
   InvalidMatrix,
@@ -35,7 +39,7 @@ pub enum EnumError {
 
 pub enum EnumCameraType {
   Perspective(u32, f32, f32, f32),
-  Orthographic(u32, u32, f32, f32),
+  Orthographic(f32, f32, f32, f32, f32, f32),
 }
 
 pub trait TraitCamera {
@@ -48,17 +52,65 @@ pub trait TraitCamera {
   fn scale(&mut self, amount_x: f32, amount_y: f32, amount_z: f32);
   fn on_event(&mut self, event: &EnumEvent) -> Result<bool, EnumEngineError>;
   fn on_update(&mut self, time_step: f64);
+  fn get_position(&self) -> Vec3<f32>;
+  fn set_position(&mut self, position: Vec3<f32>);
+  fn get_forward(&self) -> Vec3<f32>;
+  fn set_forward(&mut self, forward: Vec3<f32>);
+  fn toggle_builtin_controls(&mut self, enabled: bool);
   fn to_string(&self) -> String;
 }
 
+/// The six clipping planes of a view-projection matrix, each stored as (a, b, c, d) with ax + by + cz + d >= 0 for
+/// points on the inner side.
+#[derive(Copy, Clone)]
+pub struct Frustum {
+  m_planes: [[f32; 4]; 6],
+}
+
+impl Frustum {
+  pub fn from_matrix(view_projection: &Mat4) -> Self {
+    let row = |index: usize| [view_projection[index][0], view_projection[index][1], view_projection[index][2],
+      view_projection[index][3]];
+    let (row_x, row_y, row_z, row_w) = (row(0), row(1), row(2), row(3));
+    let add = |a: [f32; 4], b: [f32; 4]| [a[0] + b[0], a[1] + b[1], a[2] + b[2], a[3] + b[3]];
+    let sub = |a: [f32; 4], b: [f32; 4]| [a[0] - b[0], a[1] - b[1], a[2] - b[2], a[3] - b[3]];
+    
+    // Left, right, bottom, top, near, far.
+    return Self {
+      m_planes: [add(row_w, row_x), sub(row_w, row_x), add(row_w, row_y), sub(row_w, row_y), add(row_w, row_z),
+        sub(row_w, row_z)],
+    };
+  }
+  
+  /// Whether any part of the box may be inside the frustum. Conservative : some boxes right outside a corner of the
+  /// frustum might still be reported as intersecting.
+  pub fn intersects_aabb(&self, aabb: &Aabb) -> bool {
+    for plane in self.m_planes.iter() {
+      // Test the corner the furthest along the plane's normal, if even that one is outside then the whole box is.
+      let x = (plane[0] >= 0.0).then(|| aabb.m_max.x).unwrap_or(aabb.m_min.x);
+      let y = (plane[1] >= 0.0).then(|| aabb.m_max.y).unwrap_or(aabb.m_min.y);
+      let z = (plane[2] >= 0.0).then(|| aabb.m_max.z).unwrap_or(aabb.m_min.z);
+      
+      if plane[0] * x + plane[1] * y + plane[2] * z + plane[3] < 0.0 {
+        return false;
+      }
+    }
+    return true;
+  }
+}
+
 pub struct Camera {
   m_api: Box<dyn TraitCamera>,
+  m_orbit: Option<OrbitController>,
+  m_look_at: Option<Vec3<f32>>,
 }
 
 impl Camera {
   pub fn default() -> Self {
     return Self {
       m_api: Box::new(PerspectiveCamera::default()),
+      m_orbit: None,
+      m_look_at: None,
     };
   }
   
@@ -71,15 +123,19 @@ impl Camera {
         }
         Self {
           m_api: Box::new(perspective),
+          m_orbit: None,
+          m_look_at: None,
         }
       }
-      EnumCameraType::Orthographic(width, height, z_near, z_far) => {
-        let mut orthographic = OrthographicCamera::new(width, height, z_near, z_far);
+      EnumCameraType::Orthographic(left, right, bottom, top, z_near, z_far) => {
+        let mut orthographic = OrthographicCamera::new(left, right, bottom, top, z_near, z_far);
         if apply_transform.is_some() {
           orthographic.m_transforms = apply_transform.unwrap();
         }
         Self {
           m_api: Box::new(orthographic),
+          m_orbit: None,
+          m_look_at: None,
         }
       }
     };
@@ -89,10 +145,35 @@ impl Camera {
     return self.m_api.get_projection_matrix();
   }
   pub fn get_view_matrix(&self) -> Mat4 {
+    if let Some(orbit) = self.m_orbit.as_ref() {
+      return orbit.get_view_matrix();
+    }
     return self.m_api.get_view_matrix();
   }
-  pub fn on_event(&mut self, event: &EnumEvent) -> Result<bool, EnumEngineError> { return self.m_api.on_event(event); }
+  pub fn get_frustum(&self) -> Frustum {
+    return Frustum::from_matrix(&(self.get_projection_matrix() * self.get_view_matrix()));
+  }
+  pub fn on_event(&mut self, event: &EnumEvent) -> Result<bool, EnumEngineError> {
+    if let Some(orbit) = self.m_orbit.as_mut() {
+      let previous_eye = orbit.get_eye();
+      let consumed = orbit.on_event(event);
+      if orbit.get_eye() != previous_eye {
+        self.sync_orbit();
+      }
+      if consumed {
+        return Ok(true);
+      }
+    }
+    return self.m_api.on_event(event);
+  }
   pub fn on_update(&mut self, time_step: f64) {
+    if let Some(orbit) = self.m_orbit.as_mut() {
+      if orbit.on_update() {
+        self.sync_orbit();
+      }
+    } else if let Some(target) = self.m_look_at {
+      self.aim_at(target);
+    }
     return self.m_api.on_update(time_step);
   }
   pub fn has_changed(&self) -> bool {
@@ -113,6 +194,414 @@ impl Camera {
   pub fn scale(&mut self, amount_x: f32, amount_y: f32, amount_z: f32) {
     return self.m_api.scale(amount_x, amount_y, amount_z);
   }
+  
+  /// World position of the camera.
+  pub fn get_position(&self) -> Vec3<f32> {
+    return self.m_api.get_position();
+  }
+  
+  pub fn set_position(&mut self, position: Vec3<f32>) {
+    return self.m_api.set_position(position);
+  }
+  
+  /// Normalized direction the camera is looking at, -Z by default.
+  pub fn get_forward(&self) -> Vec3<f32> {
+    return self.m_api.get_forward();
+  }
+  
+  pub fn set_forward(&mut self, forward: Vec3<f32>) {
+    return self.m_api.set_forward(forward);
+  }
+  
+  /// Keep facing a world point from wherever the camera moves, until [Self::stop_looking_at]. Ignored while orbiting,
+  /// since the orbit already faces its own target.
+  pub fn look_at(&mut self, target: Vec3<f32>) {
+    self.m_look_at = Some(target);
+    self.aim_at(target);
+  }
+  
+  pub fn stop_looking_at(&mut self) {
+    self.m_look_at = None;
+  }
+  
+  pub fn get_look_at_target(&self) -> Option<Vec3<f32>> {
+    return self.m_look_at;
+  }
+  
+  // Only touch the direction when it drifted, to avoid uploading the same view every frame.
+  fn aim_at(&mut self, target: Vec3<f32>) {
+    let direction: Vec3<f32> = (target - self.m_api.get_position()).normalize();
+    if direction.vec_len() > f32::EPSILON && (direction - self.m_api.get_forward()).vec_len() > 1e-5 {
+      self.m_api.set_forward(direction);
+    }
+  }
+  
+  /// Enable or disable the default WASD movement done on update, i.e. when driven by a controller instead.
+  pub fn toggle_builtin_controls(&mut self, enabled: bool) {
+    return self.m_api.toggle_builtin_controls(enabled);
+  }
+  
+  /// World space ray going through a point on screen (in pixels from the top left corner of the viewport), returned as
+  /// its origin on the near plane and its normalized direction.
+  pub fn screen_point_to_ray(&self, cursor: Vec2<f32>, viewport: (u32, u32)) -> (Vec3<f32>, Vec3<f32>) {
+    let inverse_view_projection = (self.get_projection_matrix() * self.get_view_matrix()).inverse();
+    
+    if inverse_view_projection.is_none() || viewport.0 == 0 || viewport.1 == 0 {
+      log!(EnumLogColor::Yellow, "WARN", "[Camera] -->\t Cannot unproject screen point, falling back to camera forward!");
+      return (self.get_position(), self.get_forward());
+    }
+    let inverse_view_projection = inverse_view_projection.unwrap();
+    
+    // To normalized device coordinates, with y going up.
+    let ndc_x = (2.0 * cursor.x / viewport.0 as f32) - 1.0;
+    let ndc_y = 1.0 - (2.0 * cursor.y / viewport.1 as f32);
+    
+    let unproject = |ndc_z: f32| {
+      let row = |index: usize| inverse_view_projection[index][0] * ndc_x + inverse_view_projection[index][1] * ndc_y
+        + inverse_view_projection[index][2] * ndc_z + inverse_view_projection[index][3];
+      let w = row(3);
+      return Vec3::new(&[row(0) / w, row(1) / w, row(2) / w]);
+    };
+    
+    let near_point = unproject(-1.0);
+    let far_point = unproject(1.0);
+    return (near_point, (far_point - near_point).normalize());
+  }
+  
+  /// Project a world space point to the screen (in pixels from the top left corner of the viewport), i.e. to hit-test
+  /// a gizmo handle against the cursor. Returns [None] for points behind the camera or an empty viewport.
+  pub fn world_to_screen(&self, point: Vec3<f32>, viewport: (u32, u32)) -> Option<Vec2<f32>> {
+    if viewport.0 == 0 || viewport.1 == 0 {
+      return None;
+    }
+    let view_projection = self.get_projection_matrix() * self.get_view_matrix();
+    let row = |index: usize| view_projection[index][0] * point.x + view_projection[index][1] * point.y
+      + view_projection[index][2] * point.z + view_projection[index][3];
+    
+    let w = row(3);
+    if w <= f32::EPSILON {
+      return None;
+    }
+    // From normalized device coordinates, with y going down.
+    return Some(Vec2::new(&[(row(0) / w + 1.0) * 0.5 * viewport.0 as f32, (1.0 - row(1) / w) * 0.5 * viewport.1 as f32]));
+  }
+  
+  /// Whether the cursor lies within `radius` pixels of a world space gizmo handle, see [Self::world_to_screen].
+  pub fn hit_test_handle(&self, handle: Vec3<f32>, cursor: Vec2<f32>, viewport: (u32, u32), radius: f32) -> bool {
+    return self.world_to_screen(handle, viewport)
+      .is_some_and(|screen_point| (screen_point - cursor).vec_len() <= radius);
+  }
+  
+  /// Orbit around a target with the mouse (drag to rotate, scroll to zoom) instead of moving freely, or go back to the
+  /// default controls with [None].
+  pub fn set_orbit_controller(&mut self, orbit: Option<OrbitController>) {
+    self.m_api.toggle_builtin_controls(orbit.is_none());
+    self.m_orbit = orbit;
+    self.sync_orbit();
+  }
+  
+  pub fn get_orbit_controller_mut(&mut self) -> Option<&mut OrbitController> {
+    return self.m_orbit.as_mut();
+  }
+  
+  /// Detach the orbit controller, going back to the default controls, i.e. to hand it over to another camera.
+  pub fn take_orbit_controller(&mut self) -> Option<OrbitController> {
+    self.m_api.toggle_builtin_controls(true);
+    return self.m_orbit.take();
+  }
+  
+  // Keep the position and direction in sync with the orbit, for anything querying them (i.e. culling).
+  fn sync_orbit(&mut self) {
+    if let Some(orbit) = self.m_orbit.as_ref() {
+      let eye = orbit.get_eye();
+      self.m_api.set_position(eye);
+      self.m_api.set_forward(orbit.get_target() - eye);
+    }
+  }
+}
+
+/// Build a view matrix from the view-space +Z axis, the world up vector and the translation applied to the world.
+fn view_matrix(direction: Vec3<f32>, up_vector: Vec3<f32>, translation: Vec3<f32>) -> Mat4 {
+  let right: Vec3<f32> = up_vector.cross(direction).normalize();
+  let up: Vec3<f32> = direction.cross(right);
+  
+  return Mat4::from(
+    [
+      [right.x, right.y, right.z, right.dot(translation)],
+      [up.x, up.y, up.z, up.dot(translation)],
+      [direction.x, direction.y, direction.z, direction.dot(translation)],
+      [0.0, 0.0, 0.0, 1.0]]
+  );
+}
+
+/*
+///////////////////////////////////   FPS Controller  ///////////////////////////////////
+///////////////////////////////////                   ///////////////////////////////////
+///////////////////////////////////                   ///////////////////////////////////
+ */
+
+/// First-person controls for the active camera : the mouse turns the view (yaw and pitch) while WASD moves along it,
+/// with space and left shift to go up and down.
+pub struct FpsController {
+  m_position: Vec3<f32>,
+  // In degrees, looking down -Z when both are zero.
+  m_yaw: f32,
+  m_pitch: f32,
+  m_move_speed: f32,
+  m_mouse_sensitivity: f32,
+}
+
+impl FpsController {
+  pub const C_MAX_PITCH: f32 = 89.0;
+  
+  pub fn default() -> Self {
+    return Self::new(Vec3::default(), 10.0, 0.1);
+  }
+  
+  pub fn new(position: Vec3<f32>, move_speed: f32, mouse_sensitivity: f32) -> Self {
+    return Self {
+      m_position: position,
+      m_yaw: 0.0,
+      m_pitch: 0.0,
+      m_move_speed: move_speed,
+      m_mouse_sensitivity: mouse_sensitivity,
+    };
+  }
+  
+  pub fn set_move_speed(&mut self, units_per_second: f32) {
+    self.m_move_speed = units_per_second;
+  }
+  
+  pub fn set_mouse_sensitivity(&mut self, degrees_per_pixel: f32) {
+    self.m_mouse_sensitivity = degrees_per_pixel;
+  }
+  
+  pub fn get_position(&self) -> Vec3<f32> {
+    return self.m_position;
+  }
+  
+  pub fn get_yaw(&self) -> f32 {
+    return self.m_yaw;
+  }
+  
+  pub fn get_pitch(&self) -> f32 {
+    return self.m_pitch;
+  }
+  
+  pub fn get_forward(&self) -> Vec3<f32> {
+    let (yaw, pitch) = (self.m_yaw.to_radians(), self.m_pitch.to_radians());
+    return Vec3::new(&[pitch.cos() * yaw.sin(), pitch.sin(), -pitch.cos() * yaw.cos()]);
+  }
+  
+  /// Horizontal right vector, so that strafing never changes altitude.
+  pub fn get_right(&self) -> Vec3<f32> {
+    let yaw = self.m_yaw.to_radians();
+    return Vec3::new(&[yaw.cos(), 0.0, yaw.sin()]);
+  }
+  
+  /// Turn by the mouse delta (in pixels) and move by `movement`, given as (right, up, forward) amounts in [-1, 1].
+  pub fn process_input(&mut self, mouse_delta: Vec2<f32>, movement: Vec3<f32>, time_step: f64) {
+    self.m_yaw = (self.m_yaw + mouse_delta.x * self.m_mouse_sensitivity) % 360.0;
+    // Moving the mouse down (positive y in window coordinates) looks down.
+    self.m_pitch = (self.m_pitch - mouse_delta.y * self.m_mouse_sensitivity)
+      .clamp(-FpsController::C_MAX_PITCH, FpsController::C_MAX_PITCH);
+    
+    let distance = self.m_move_speed * time_step as f32;
+    let forward = self.get_forward();
+    let right = self.get_right();
+    self.m_position += Vec3::new(&[
+      (right.x * movement.x + forward.x * movement.z) * distance,
+      (movement.y + forward.y * movement.z) * distance,
+      (right.z * movement.x + forward.z * movement.z) * distance
+    ]);
+  }
+  
+  pub fn apply_to(&self, camera: &mut Camera) {
+    camera.toggle_builtin_controls(false);
+    camera.set_position(self.m_position);
+    camera.set_forward(self.get_forward());
+  }
+  
+  /// Poll the mouse and keyboard, then update the active camera, if any.
+  pub fn on_update(&mut self, time_step: f64) {
+    let mouse_delta = input::Input::get_mouse_delta();
+    
+    let mut movement: Vec3<f32> = Vec3::default();
+    if Engine::is_key(input::EnumKey::W, input::EnumAction::Held) {
+      movement.z += 1.0;
+    }
+    if Engine::is_key(input::EnumKey::S, input::EnumAction::Held) {
+      movement.z -= 1.0;
+    }
+    if Engine::is_key(input::EnumKey::D, input::EnumAction::Held) {
+      movement.x += 1.0;
+    }
+    if Engine::is_key(input::EnumKey::A, input::EnumAction::Held) {
+      movement.x -= 1.0;
+    }
+    if Engine::is_key(input::EnumKey::Space, input::EnumAction::Held) {
+      movement.y += 1.0;
+    }
+    if Engine::is_key(input::EnumKey::LeftShift, input::EnumAction::Held) {
+      movement.y -= 1.0;
+    }
+    self.process_input(mouse_delta, movement, time_step);
+    
+    let renderer = Engine::get_active_renderer();
+    let matrices = renderer.get_active_camera_mut().map(|camera| {
+      self.apply_to(camera);
+      (camera.get_view_matrix(), camera.get_projection_matrix())
+    });
+    
+    // Upload right away, so that the view doesn't lag a frame behind the input.
+    if let Some((view, projection)) = matrices {
+      if let Err(_err) = renderer.update_ubo_camera(view, projection) {
+        log!(EnumLogColor::Red, "ERROR", "[Camera] -->\t Cannot update camera from fps controller : {0}", _err);
+      }
+    }
+  }
+}
+
+/*
+///////////////////////////////////   Orbit Controller  ///////////////////////////////////
+///////////////////////////////////                     ///////////////////////////////////
+///////////////////////////////////                     ///////////////////////////////////
+ */
+
+/// Arcball-like controls around a target point, for inspecting models : drag with the left mouse button to rotate
+/// around the target, and scroll to get closer or further.
+pub struct OrbitController {
+  m_target: Vec3<f32>,
+  m_radius: f32,
+  // In degrees. Both at zero puts the camera on +Z from the target, looking down -Z.
+  m_azimuth: f32,
+  m_elevation: f32,
+  m_min_radius: f32,
+  m_max_radius: f32,
+  m_rotate_sensitivity: f32,
+  m_zoom_speed: f32,
+  m_dragging: bool,
+}
+
+impl OrbitController {
+  pub const C_MAX_ELEVATION: f32 = 89.0;
+  
+  pub fn new(target: Vec3<f32>, radius: f32) -> Self {
+    return Self {
+      m_target: target,
+      m_radius: radius.max(0.1),
+      m_azimuth: 0.0,
+      m_elevation: 0.0,
+      m_min_radius: 0.1,
+      m_max_radius: 1000.0,
+      m_rotate_sensitivity: 0.25,
+      m_zoom_speed: 1.0,
+      m_dragging: false,
+    };
+  }
+  
+  pub fn set_target(&mut self, target: Vec3<f32>) {
+    self.m_target = target;
+  }
+  
+  pub fn get_target(&self) -> Vec3<f32> {
+    return self.m_target;
+  }
+  
+  pub fn get_radius(&self) -> f32 {
+    return self.m_radius;
+  }
+  
+  pub fn get_azimuth(&self) -> f32 {
+    return self.m_azimuth;
+  }
+  
+  pub fn get_elevation(&self) -> f32 {
+    return self.m_elevation;
+  }
+  
+  pub fn set_radius_limits(&mut self, min_radius: f32, max_radius: f32) {
+    self.m_min_radius = min_radius.max(0.0);
+    self.m_max_radius = max_radius.max(self.m_min_radius);
+    self.m_radius = self.m_radius.clamp(self.m_min_radius, self.m_max_radius);
+  }
+  
+  pub fn set_rotate_sensitivity(&mut self, degrees_per_pixel: f32) {
+    self.m_rotate_sensitivity = degrees_per_pixel;
+  }
+  
+  pub fn set_zoom_speed(&mut self, units_per_scroll: f32) {
+    self.m_zoom_speed = units_per_scroll;
+  }
+  
+  pub fn is_dragging(&self) -> bool {
+    return self.m_dragging;
+  }
+  
+  pub fn get_eye(&self) -> Vec3<f32> {
+    let (azimuth, elevation) = (self.m_azimuth.to_radians(), self.m_elevation.to_radians());
+    return self.m_target + Vec3::new(&[
+      self.m_radius * elevation.cos() * azimuth.sin(),
+      self.m_radius * elevation.sin(),
+      self.m_radius * elevation.cos() * azimuth.cos()
+    ]);
+  }
+  
+  pub fn get_view_matrix(&self) -> Mat4 {
+    return Mat4::apply_look_at(&self.get_eye(), &self.m_target, &Vec3::new(&[0.0, 1.0, 0.0]));
+  }
+  
+  /// Rotate around the target by a mouse delta in pixels. Dragging right moves the camera left around the target, as
+  /// if spinning the model itself.
+  pub fn rotate(&mut self, mouse_delta: Vec2<f32>) {
+    self.m_azimuth = (self.m_azimuth - mouse_delta.x * self.m_rotate_sensitivity) % 360.0;
+    self.m_elevation = (self.m_elevation + mouse_delta.y * self.m_rotate_sensitivity)
+      .clamp(-OrbitController::C_MAX_ELEVATION, OrbitController::C_MAX_ELEVATION);
+  }
+  
+  /// Get closer to the target when scrolling up, clamped within the radius limits.
+  pub fn zoom(&mut self, scroll_amount: f64) {
+    self.m_radius = (self.m_radius - scroll_amount as f32 * self.m_zoom_speed)
+      .clamp(self.m_min_radius, self.m_max_radius);
+  }
+  
+  /// Returns whether the event was consumed, which only happens while a drag is in progress, so that clicks and
+  /// scrolls still reach other handlers (i.e. picking or UI) otherwise.
+  pub fn on_event(&mut self, event: &EnumEvent) -> bool {
+    return match event {
+      EnumEvent::MouseBtnEvent(input::EnumMouseButton::LeftButton, input::EnumAction::Pressed, _) => {
+        self.m_dragging = true;
+        // Discard the cursor movement accumulated since the last drag.
+        let _ = input::Input::get_mouse_cursor_delta(Engine::get_active_window());
+        false
+      }
+      EnumEvent::MouseBtnEvent(input::EnumMouseButton::LeftButton, input::EnumAction::Released, _) => {
+        let was_dragging = self.m_dragging;
+        self.m_dragging = false;
+        was_dragging
+      }
+      EnumEvent::MouseScrollEvent(_, y_offset) => {
+        self.zoom(*y_offset);
+        self.m_dragging
+      }
+      _ => false
+    };
+  }
+  
+  /// Rotate by the cursor movement while dragging. Returns whether the orbit changed.
+  pub fn on_update(&mut self) -> bool {
+    if !self.m_dragging {
+      return false;
+    }
+    
+    let mouse_delta = input::Input::get_mouse_cursor_delta(Engine::get_active_window())
+      .unwrap_or(Vec2::default());
+    if mouse_delta.x == 0.0 && mouse_delta.y == 0.0 {
+      return false;
+    }
+    self.rotate(mouse_delta);
+    return true;
+  }
 }
 
 /*
@@ -121,82 +610,170 @@ impl Camera {
 ///////////////////////////////////                        ///////////////////////////////////
  */
 
-#[allow(unused)]
 pub struct OrthographicCamera {
-  m_width: u32,
-  m_height: u32,
-  m_z_rear: f32,
+  m_left: f32,
+  m_right: f32,
+  m_bottom: f32,
+  m_top: f32,
+  m_z_near: f32,
   m_z_far: f32,
   m_transforms: [Vec3<f32>; 3],
+  m_up_vector: Vec3<f32>,
+  // View-space +Z axis in world space, i.e. the opposite of where the camera looks.
+  m_direction: Vec3<f32>,
+  m_builtin_controls: bool,
+  m_has_changed: bool,
 }
 
 impl TraitCamera for OrthographicCamera {
   fn get_projection_matrix(&self) -> Mat4 {
-    todo!()
+    return Mat4::apply_orthographic(self.m_left, self.m_right, self.m_bottom, self.m_top, self.m_z_near, self.m_z_far);
   }
   
   fn get_view_matrix(&self) -> Mat4 {
-    todo!()
+    return view_matrix(self.m_direction, self.m_up_vector, self.m_transforms[0]);
   }
   
   fn has_changed(&self) -> bool {
-    todo!()
+    return self.m_has_changed;
   }
   
-  #[allow(unused)]
   fn set_up_vector(&mut self, to_this: Vec3<f32>) {
-    todo!()
+    self.m_up_vector = to_this;
+    self.m_has_changed = true;
   }
   
-  #[allow(unused)]
   fn translate(&mut self, amount_x: f32, amount_y: f32, amount_z: f32) {
-    todo!()
+    // Inverse z.
+    self.m_transforms[0] += Vec3::new(&[amount_x, amount_y, -amount_z]);
+    self.m_has_changed = true;
   }
   
-  #[allow(unused)]
   fn rotate(&mut self, amount_x: f32, amount_y: f32, amount_z: f32) {
-    todo!()
+    // Inverse x and y to correspond to the right orientation.
+    self.m_transforms[1] += Vec3::new(&[amount_y, amount_x, -amount_z]);
+    self.m_has_changed = true;
   }
   
-  #[allow(unused)]
   fn scale(&mut self, amount_x: f32, amount_y: f32, amount_z: f32) {
-    todo!()
+    self.m_transforms[2] += Vec3::new(&[amount_x, amount_y, amount_z]);
+    self.m_has_changed = true;
   }
   
-  fn on_event(&mut self, _event: &EnumEvent) -> Result<bool, EnumEngineError> {
-    todo!()
+  fn on_event(&mut self, event: &EnumEvent) -> Result<bool, EnumEngineError> {
+    return match event {
+      EnumEvent::FramebufferEvent(new_size_x, new_size_y) => {
+        self.m_has_changed = true;
+        if *new_size_x != 0 && *new_size_y != 0 {
+          log!(EnumLogColor::Blue, "EVENT", "[Camera] -->\t Framebuffer change detected, updating orthographic bounds...");
+          // Keep the vertical extent and center, widen or narrow horizontally to match the new aspect ratio.
+          let half_width = (self.m_top - self.m_bottom) * 0.5 * (*new_size_x as f32 / *new_size_y as f32);
+          let center_x = (self.m_left + self.m_right) * 0.5;
+          self.m_left = center_x - half_width;
+          self.m_right = center_x + half_width;
+        }
+        Ok(true)
+      }
+      _ => Ok(false)
+    };
   }
   
-  #[allow(unused)]
   fn on_update(&mut self, time_step: f64) {
-    todo!()
+    // Pan on the view plane, since there is no depth perception in orthographic views.
+    if self.m_builtin_controls {
+      if Engine::is_key(input::EnumKey::W, input::EnumAction::Held) {
+        self.translate(0.0, -10.0 * time_step as f32, 0.0);
+      }
+      if Engine::is_key(input::EnumKey::A, input::EnumAction::Held) {
+        self.translate(10.0 * time_step as f32, 0.0, 0.0);
+      }
+      if Engine::is_key(input::EnumKey::S, input::EnumAction::Held) {
+        self.translate(0.0, 10.0 * time_step as f32, 0.0);
+      }
+      if Engine::is_key(input::EnumKey::D, input::EnumAction::Held) {
+        self.translate(-10.0 * time_step as f32, 0.0, 0.0);
+      }
+    }
+    
+    if self.m_has_changed {
+      let renderer = Engine::get_active_renderer();
+      renderer.update_ubo_camera(self.get_view_matrix(), self.get_projection_matrix()).expect("Error while updating ubo camera!");
+      self.m_has_changed = false;  // Reset state.
+    }
+  }
+  
+  fn get_position(&self) -> Vec3<f32> {
+    // The translation is stored as applied to the world, opposite to the camera's own position.
+    return Vec3::new(&[-self.m_transforms[0].x, -self.m_transforms[0].y, -self.m_transforms[0].z]);
+  }
+  
+  fn set_position(&mut self, position: Vec3<f32>) {
+    self.m_transforms[0] = Vec3::new(&[-position.x, -position.y, -position.z]);
+    self.m_has_changed = true;
+  }
+  
+  fn get_forward(&self) -> Vec3<f32> {
+    return Vec3::new(&[-self.m_direction.x, -self.m_direction.y, -self.m_direction.z]);
+  }
+  
+  fn set_forward(&mut self, forward: Vec3<f32>) {
+    let forward = forward.normalize();
+    self.m_direction = Vec3::new(&[-forward.x, -forward.y, -forward.z]);
+    self.m_has_changed = true;
+  }
+  
+  fn toggle_builtin_controls(&mut self, enabled: bool) {
+    self.m_builtin_controls = enabled;
   }
   
   fn to_string(&self) -> String {
-    todo!()
+    return format!("[Orthographic Camera] --> Bounds: (left: {0}, right: {1}, bottom: {2}, top: {3}), Depth: ({4}, {5})",
+      self.m_left, self.m_right, self.m_bottom, self.m_top, self.m_z_near, self.m_z_far);
   }
 }
 
 impl OrthographicCamera {
   pub fn default() -> Self {
     return Self {
-      m_width: 640,
-      m_height: 480,
-      m_z_rear: 0.1,
+      m_left: -320.0,
+      m_right: 320.0,
+      m_bottom: -240.0,
+      m_top: 240.0,
+      m_z_near: 0.1,
       m_z_far: 10.0,
       m_transforms: [Vec3::default(), Vec3::default(), Vec3::new(&[1.0, 1.0, 1.0])],
+      m_up_vector: Vec3::new(&[0.0, 1.0, 0.0]),  // Default to Y-coordinate.
+      m_direction: Vec3::new(&[0.0, 0.0, 1.0]),
+      m_builtin_controls: true,
+      m_has_changed: true,
     };
   }
   
-  pub fn new(width: u32, height: u32, z_near: f32, z_far: f32) -> Self {
+  pub fn new(left: f32, right: f32, bottom: f32, top: f32, z_near: f32, z_far: f32) -> Self {
     return Self {
-      m_width: width,
-      m_height: height,
-      m_z_rear: z_near,
+      m_left: left,
+      m_right: right,
+      m_bottom: bottom,
+      m_top: top,
+      m_z_near: z_near,
       m_z_far: z_far,
       m_transforms: [Vec3::default(), Vec3::default(), Vec3::new(&[1.0, 1.0, 1.0])],
+      m_up_vector: Vec3::new(&[0.0, 1.0, 0.0]),  // Default to Y-coordinate.
+      m_direction: Vec3::new(&[0.0, 0.0, 1.0]),
+      m_builtin_controls: true,
+      m_has_changed: true,
     };
   }
+  
+  pub fn update_projection(&mut self, left: f32, right: f32, bottom: f32, top: f32, z_near: f32, z_far: f32) {
+    self.m_left = left;
+    self.m_right = right;
+    self.m_bottom = bottom;
+    self.m_top = top;
+    self.m_z_near = z_near;
+    self.m_z_far = z_far;
+    self.m_has_changed = true;
+  }
 }
 
 /*
@@ -212,6 +789,9 @@ pub struct PerspectiveCamera {
   m_z_far: f32,
   m_transforms: [Vec3<f32>; 3],
   m_up_vector: Vec3<f32>,
+  // View-space +Z axis in world space, i.e. the opposite of where the camera looks.
+  m_direction: Vec3<f32>,
+  m_builtin_controls: bool,
   m_has_changed: bool,
 }
 
@@ -221,20 +801,7 @@ impl TraitCamera for PerspectiveCamera {
   }
   
   fn get_view_matrix(&self) -> Mat4 {
-    let up: Vec3<f32> = self.m_up_vector;
-    let direction: Vec3<f32> = Vec3::new(&[0.0, 0.0, 1.0]);
-    let right: Vec3<f32> = up.cross(direction.clone());
-    let matrix = Mat4::apply_transformations(&self.m_transforms[0],
-      &self.m_transforms[1], &self.m_transforms[2]);
-    
-    
-    return Mat4::from(
-      [
-        [right.x, right.y, right.z, matrix[0][3]],
-        [up.x, up.y, up.z, matrix[1][3]],
-        [direction.x, direction.y, direction.z, matrix[2][3]],
-        [matrix[3][0], matrix[3][1], matrix[3][2], matrix[3][3]]]
-    );
+    return view_matrix(self.m_direction, self.m_up_vector, self.m_transforms[0]);
   }
   
   fn has_changed(&self) -> bool {
@@ -281,17 +848,19 @@ impl TraitCamera for PerspectiveCamera {
   }
   
   fn on_update(&mut self, time_step: f64) {
-    if Engine::is_key(input::EnumKey::W, input::EnumAction::Held) {
-      self.translate(0.0, 0.0, -10.0 * time_step as f32);
-    }
-    if Engine::is_key(input::EnumKey::A, input::EnumAction::Held) {
-      self.translate(-10.0 * time_step as f32, 0.0, 0.0);
-    }
-    if Engine::is_key(input::EnumKey::S, input::EnumAction::Held) {
-      self.translate(0.0, 0.0, 10.0 * time_step as f32);
-    }
-    if Engine::is_key(input::EnumKey::D, input::EnumAction::Held) {
-      self.translate(10.0 * time_step as f32, 0.0, 0.0);
+    if self.m_builtin_controls {
+      if Engine::is_key(input::EnumKey::W, input::EnumAction::Held) {
+        self.translate(0.0, 0.0, -10.0 * time_step as f32);
+      }
+      if Engine::is_key(input::EnumKey::A, input::EnumAction::Held) {
+        self.translate(-10.0 * time_step as f32, 0.0, 0.0);
+      }
+      if Engine::is_key(input::EnumKey::S, input::EnumAction::Held) {
+        self.translate(0.0, 0.0, 10.0 * time_step as f32);
+      }
+      if Engine::is_key(input::EnumKey::D, input::EnumAction::Held) {
+        self.translate(10.0 * time_step as f32, 0.0, 0.0);
+      }
     }
     
     if self.m_has_changed {
@@ -301,6 +870,30 @@ impl TraitCamera for PerspectiveCamera {
     }
   }
   
+  fn get_position(&self) -> Vec3<f32> {
+    // The translation is stored as applied to the world, opposite to the camera's own position.
+    return Vec3::new(&[-self.m_transforms[0].x, -self.m_transforms[0].y, -self.m_transforms[0].z]);
+  }
+  
+  fn set_position(&mut self, position: Vec3<f32>) {
+    self.m_transforms[0] = Vec3::new(&[-position.x, -position.y, -position.z]);
+    self.m_has_changed = true;
+  }
+  
+  fn get_forward(&self) -> Vec3<f32> {
+    return Vec3::new(&[-self.m_direction.x, -self.m_direction.y, -self.m_direction.z]);
+  }
+  
+  fn set_forward(&mut self, forward: Vec3<f32>) {
+    let forward = forward.normalize();
+    self.m_direction = Vec3::new(&[-forward.x, -forward.y, -forward.z]);
+    self.m_has_changed = true;
+  }
+  
+  fn toggle_builtin_controls(&mut self, enabled: bool) {
+    self.m_builtin_controls = enabled;
+  }
+  
   fn to_string(&self) -> String {
     todo!()
   }
@@ -315,6 +908,8 @@ impl PerspectiveCamera {
       m_z_far: 0.0,
       m_up_vector: Vec3::new(&[0.0, 1.0, 0.0]),  // Default to Y-coordinate.
       m_transforms: [Vec3::default(), Vec3::default(), Vec3::new(&[1.0, 1.0, 1.0])],
+      m_direction: Vec3::new(&[0.0, 0.0, 1.0]),
+      m_builtin_controls: true,
       m_has_changed: true,
     };
   }
@@ -327,6 +922,8 @@ impl PerspectiveCamera {
       m_z_far: z_far,
       m_up_vector: Vec3::new(&[0.0, 1.0, 0.0]),  // Default to Y-coordinate.
       m_transforms: [Vec3::default(), Vec3::default(), Vec3::new(&[1.0, 1.0, 1.0])],
+      m_direction: Vec3::new(&[0.0, 0.0, 1.0]),
+      m_builtin_controls: true,
       m_has_changed: true,
     };
   }
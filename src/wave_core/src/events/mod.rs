@@ -22,33 +22,84 @@
  SOFTWARE.
 */
 
+use std::any::Any;
 use std::fmt::{Display, Formatter};
 use std::path::PathBuf;
 
 use bitflags::bitflags;
 
+use crate::graphics::renderer::EnumRendererApi;
 use crate::input;
 use crate::utils::Time;
+use crate::window::EnumVSync;
 
 pub trait TraitEvent {
 
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug)]
 pub enum EnumEvent {
   WindowIconifyEvent(bool),
   WindowMaximizeEvent(bool),
   WindowCloseEvent(Time),
   FramebufferEvent(u32, u32),
+  /// New content scale of the window (x, y), when moved to a monitor with a different DPI or when the system's scale
+  /// setting changes.
+  ContentScaleEvent(f32, f32),
   WindowPosEvent(i32, i32),
   WindowFocusEvent(bool),
+  /// VSync mode now in effect, after [crate::window::Window::set_vsync].
+  VSyncEvent(EnumVSync),
+  /// Renderer api now in use, after [crate::Engine::switch_renderer]. Every GPU resource of the previous api is gone
+  /// by then, and must be rebuilt for the new one.
+  RendererSwitchEvent(EnumRendererApi),
   KeyEvent(input::EnumKey, input::EnumAction, Option<u32>, input::EnumModifiers),
   MouseBtnEvent(input::EnumMouseButton, input::EnumAction, input::EnumModifiers),
   MouseScrollEvent(f64, f64),
+  CharEvent(char),
   DragAndDrop(Vec<PathBuf>),
+  /// Joystick plugged in at the given id, see [input::Input::is_gamepad_connected] to know if it is a gamepad.
+  GamepadConnected(u32),
+  GamepadDisconnected(u32),
+  /// Custom event published by the app through [crate::Engine::push_event], to downcast in
+  /// [crate::layers::TraitLayer::on_async_event]. The id is chosen by the app to tell its events apart.
+  UserEvent(u64, Box<dyn Any + Send>),
   UnknownEvent,
 }
 
+impl PartialEq for EnumEvent {
+  fn eq(&self, other: &Self) -> bool {
+    return match (self, other) {
+      (EnumEvent::WindowIconifyEvent(a), EnumEvent::WindowIconifyEvent(b)) => a == b,
+      (EnumEvent::WindowMaximizeEvent(a), EnumEvent::WindowMaximizeEvent(b)) => a == b,
+      (EnumEvent::WindowCloseEvent(a), EnumEvent::WindowCloseEvent(b)) => a == b,
+      (EnumEvent::FramebufferEvent(a_x, a_y), EnumEvent::FramebufferEvent(b_x, b_y)) => a_x == b_x && a_y == b_y,
+      (EnumEvent::ContentScaleEvent(a_x, a_y), EnumEvent::ContentScaleEvent(b_x, b_y)) => a_x == b_x && a_y == b_y,
+      (EnumEvent::WindowPosEvent(a_x, a_y), EnumEvent::WindowPosEvent(b_x, b_y)) => a_x == b_x && a_y == b_y,
+      (EnumEvent::WindowFocusEvent(a), EnumEvent::WindowFocusEvent(b)) => a == b,
+      (EnumEvent::VSyncEvent(a), EnumEvent::VSyncEvent(b)) => a == b,
+      (EnumEvent::RendererSwitchEvent(a), EnumEvent::RendererSwitchEvent(b)) => a == b,
+      (EnumEvent::KeyEvent(a_key, a_action, a_repeat, a_mods), EnumEvent::KeyEvent(b_key, b_action, b_repeat, b_mods)) => {
+        a_key == b_key && a_action == b_action && a_repeat == b_repeat && a_mods == b_mods
+      }
+      (EnumEvent::MouseBtnEvent(a_btn, a_action, a_mods), EnumEvent::MouseBtnEvent(b_btn, b_action, b_mods)) => {
+        a_btn == b_btn && a_action == b_action && a_mods == b_mods
+      }
+      (EnumEvent::MouseScrollEvent(a_x, a_y), EnumEvent::MouseScrollEvent(b_x, b_y)) => a_x == b_x && a_y == b_y,
+      (EnumEvent::CharEvent(a), EnumEvent::CharEvent(b)) => a == b,
+      (EnumEvent::DragAndDrop(a), EnumEvent::DragAndDrop(b)) => a == b,
+      (EnumEvent::GamepadConnected(a), EnumEvent::GamepadConnected(b)) => a == b,
+      (EnumEvent::GamepadDisconnected(a), EnumEvent::GamepadDisconnected(b)) => a == b,
+      // Type-erased payloads can't be compared, so rely on the app's id and on the payload type instead.
+      (EnumEvent::UserEvent(a_id, a), EnumEvent::UserEvent(b_id, b)) => {
+        a_id == b_id && (**a).type_id() == (**b).type_id()
+      }
+      (EnumEvent::UnknownEvent, EnumEvent::UnknownEvent) => true,
+      _ => false
+    };
+  }
+}
+
 impl Display for EnumEvent {
   fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
     return match self {
@@ -56,12 +107,19 @@ impl Display for EnumEvent {
       EnumEvent::WindowMaximizeEvent(_) => write!(f, "WindowMaximizeEvent"),
       EnumEvent::WindowCloseEvent(_) => write!(f, "WindowCloseEvent"),
       EnumEvent::FramebufferEvent(_, _) => write!(f, "FramebufferEvent"),
+      EnumEvent::ContentScaleEvent(_, _) => write!(f, "ContentScaleEvent"),
       EnumEvent::WindowPosEvent(_, _) => write!(f, "WindowPosEvent"),
       EnumEvent::WindowFocusEvent(_) => write!(f, "WindowFocusEvent"),
+      EnumEvent::VSyncEvent(_) => write!(f, "VSyncEvent"),
+      EnumEvent::RendererSwitchEvent(_) => write!(f, "RendererSwitchEvent"),
       EnumEvent::KeyEvent(_, _, _, _) => write!(f, "KeyEvent"),
       EnumEvent::MouseBtnEvent(_, _, _) => write!(f, "MouseBtnEvent"),
       EnumEvent::MouseScrollEvent(_, _) => write!(f, "MouseScrollEvent"),
+      EnumEvent::CharEvent(_) => write!(f, "CharEvent"),
       EnumEvent::DragAndDrop(_) => write!(f, "DragAndDrop"),
+      EnumEvent::GamepadConnected(_) => write!(f, "GamepadConnected"),
+      EnumEvent::GamepadDisconnected(_) => write!(f, "GamepadDisconnected"),
+      EnumEvent::UserEvent(_, _) => write!(f, "UserEvent"),
       EnumEvent::UnknownEvent => write!(f, "UnknownEvent")
     }
   }
@@ -79,14 +137,16 @@ impl From<glfw::WindowEvent> for EnumEvent {
       glfw::WindowEvent::Iconify(bool) => EnumEvent::WindowFocusEvent(bool),
       glfw::WindowEvent::Maximize(bool) => EnumEvent::WindowFocusEvent(bool),
       glfw::WindowEvent::FramebufferSize(x_size, y_size) => EnumEvent::FramebufferEvent(x_size as u32, y_size as u32),
+      glfw::WindowEvent::ContentScale(x_scale, y_scale) => EnumEvent::ContentScaleEvent(x_scale, y_scale),
       glfw::WindowEvent::Key(key, _scancode, action, modifiers) => {
-        EnumEvent::KeyEvent(
-          input::EnumKey::from(key), input::EnumAction::from(action), input::Input::get_key_repeat(input::EnumKey::from(key)),
-          input::EnumModifiers::from(modifiers))
+        let key: input::EnumKey = input::EnumKey::from(key);
+        let action: input::EnumAction = input::EnumAction::from(action);
+        EnumEvent::KeyEvent(key, action, input::Input::track_key_repeat(key, action), input::EnumModifiers::from(modifiers))
       }
       glfw::WindowEvent::MouseButton(button, action, modifiers) => EnumEvent::MouseBtnEvent(
         input::EnumMouseButton::from(button), input::EnumAction::from(action), input::EnumModifiers::from(modifiers)),
       glfw::WindowEvent::Scroll(x_factor, y_factor) => EnumEvent::MouseScrollEvent(x_factor, y_factor),
+      glfw::WindowEvent::Char(character) => EnumEvent::CharEvent(character),
       glfw::WindowEvent::FileDrop(path_buffer) => EnumEvent::DragAndDrop(path_buffer),
       _ => EnumEvent::UnknownEvent
     };
@@ -124,15 +184,28 @@ bitflags! {
     const Input           = 0b0000000111111111;
     const DragAndDrop   = 0b0000000100000001;
     const Keyboard        = 0b0000000100000010;
+    const Char            = 0b0000000100100000;
+    const Gamepad         = 0b0000000101000000;
     
     // Mouse events.
     const Mouse           = 0b0000000100011100;
     const CursorPos      = 0b0000000100000100;
     const MouseBtn       = 0b0000000100001000;
     const MouseScroll    = 0b0000000100010000;
+    
+    // App events.
+    const User            = 0b0100000000000000;
   }
 }
 
+impl EnumEventMask {
+  /// Masks of each individual event type, without the category masks grouping them.
+  pub const C_EVENTS: [EnumEventMask; 14] = [EnumEventMask::WindowIconify, EnumEventMask::WindowMaximize,
+    EnumEventMask::WindowFocus, EnumEventMask::WindowClose, EnumEventMask::WindowSize, EnumEventMask::WindowPos,
+    EnumEventMask::DragAndDrop, EnumEventMask::Keyboard, EnumEventMask::Char, EnumEventMask::Gamepad,
+    EnumEventMask::CursorPos, EnumEventMask::MouseBtn, EnumEventMask::MouseScroll, EnumEventMask::User];
+}
+
 impl From<&EnumEvent> for EnumEventMask {
   fn from(value: &EnumEvent) -> Self {
     return match value {
@@ -140,12 +213,20 @@ impl From<&EnumEvent> for EnumEventMask {
       EnumEvent::WindowMaximizeEvent(_) => EnumEventMask::WindowMaximize,
       EnumEvent::WindowCloseEvent(_) => EnumEventMask::WindowClose,
       EnumEvent::FramebufferEvent(_, _) => EnumEventMask::WindowSize,
+      EnumEvent::ContentScaleEvent(_, _) => EnumEventMask::WindowSize,
       EnumEvent::WindowPosEvent(_, _) => EnumEventMask::WindowPos,
       EnumEvent::WindowFocusEvent(_) => EnumEventMask::WindowFocus,
+      // No dedicated flag left for window settings, so only layers polling all window events get them.
+      EnumEvent::VSyncEvent(_) => EnumEventMask::Window,
+      EnumEvent::RendererSwitchEvent(_) => EnumEventMask::Window,
       EnumEvent::KeyEvent(_, _, _, _) => EnumEventMask::Keyboard,
       EnumEvent::MouseBtnEvent(_, _, _) => EnumEventMask::MouseBtn,
       EnumEvent::MouseScrollEvent(_, _) => EnumEventMask::MouseScroll,
+      EnumEvent::CharEvent(_) => EnumEventMask::Char,
       EnumEvent::DragAndDrop(_) => EnumEventMask::DragAndDrop,
+      EnumEvent::GamepadConnected(_) => EnumEventMask::Gamepad,
+      EnumEvent::GamepadDisconnected(_) => EnumEventMask::Gamepad,
+      EnumEvent::UserEvent(_, _) => EnumEventMask::User,
       EnumEvent::UnknownEvent => EnumEventMask::empty()
     };
   }
@@ -261,6 +342,22 @@ impl Display for EnumEventMask {
         write!(f, "Keyboard ({0:016b}) ", EnumEventMask::Keyboard)?;
       }
     }
+    if self.contains(EnumEventMask::Char) {
+      mask_count += 1;
+      if mask_count > 1 {
+        write!(f, "| Char ({0:016b}) ", EnumEventMask::Char)?;
+      } else {
+        write!(f, "Char ({0:016b}) ", EnumEventMask::Char)?;
+      }
+    }
+    if self.contains(EnumEventMask::Gamepad) {
+      mask_count += 1;
+      if mask_count > 1 {
+        write!(f, "| Gamepad ({0:016b}) ", EnumEventMask::Gamepad)?;
+      } else {
+        write!(f, "Gamepad ({0:016b}) ", EnumEventMask::Gamepad)?;
+      }
+    }
     if self.contains(EnumEventMask::DragAndDrop) {
       mask_count += 1;
       if mask_count > 1 {
@@ -269,6 +366,14 @@ impl Display for EnumEventMask {
         write!(f, "Drag and drop ({0:016b})", EnumEventMask::DragAndDrop)?;
       }
     }
+    if self.contains(EnumEventMask::User) {
+      mask_count += 1;
+      if mask_count > 1 {
+        write!(f, "| User ({0:016b})", EnumEventMask::User)?;
+      } else {
+        write!(f, "User ({0:016b})", EnumEventMask::User)?;
+      }
+    }
     return Ok(());
   }
 }
\ No newline at end of file
@@ -0,0 +1,160 @@
+/*
+ MIT License
+ 
+ Copyright (c) 2024 Nami Reghbati
+ 
+ Permission is hereby granted, free of charge, to any person obtaining a copy
+ of this software and associated documentation files (the "Software"), to deal
+ in the Software without restriction, including without limitation the rights
+ to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ copies of the Software, and to permit persons to whom the Software is
+ furnished to do so, subject to the following conditions:
+ 
+ The above copyright notice and this permission notice shall be included in all
+ copies or substantial portions of the Software.
+ 
+ THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ FITNESS FOR A PARTICULAR PURPOSE AND NON INFRINGEMENT. IN NO EVENT SHALL THE
+ AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ SOFTWARE.
+*/
+
+use std::collections::HashSet;
+
+#[cfg(feature = "debug")]
+use crate::Engine;
+use crate::TraitApply;
+use crate::graphics::color::Color;
+use crate::graphics::dynamic_buffer::DynamicBuffer;
+use crate::graphics::open_gl::framebuffer::GlDebugLines;
+use crate::graphics::renderer::{EnumRendererApi, EnumRendererError};
+use crate::graphics::shader::{EnumShaderSource, EnumShaderStageType, Shader, ShaderStage};
+use crate::math::{Mat4, Vec3};
+use crate::utils::macros::logger::*;
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) struct DebugVertex {
+  m_position: [f32; 3],
+  m_color: [u8; 4],
+}
+
+/// Immediate-mode line drawing for debugging (bounding boxes, rays, normals...). Lines accumulate during the frame and
+/// are all drawn at once with `GL_LINES` after the scene, through the active camera, then cleared for the next frame.
+/// OpenGL only for now.
+pub struct DebugDraw {
+  m_vertices: Vec<DebugVertex>,
+  m_shader: Option<Shader>,
+  m_lines: Option<GlDebugLines>,
+}
+
+impl DebugDraw {
+  pub fn new() -> Self {
+    return Self {
+      m_vertices: Vec::with_capacity(256),
+      m_shader: None,
+      m_lines: None,
+    };
+  }
+  
+  pub fn draw_line(&mut self, from: Vec3<f32>, to: Vec3<f32>, color: Color) {
+    let color = color.as_u8();
+    self.m_vertices.push(DebugVertex { m_position: [from.x, from.y, from.z], m_color: color });
+    self.m_vertices.push(DebugVertex { m_position: [to.x, to.y, to.z], m_color: color });
+  }
+  
+  /// Draw the 12 edges of an axis-aligned box.
+  pub fn draw_aabb(&mut self, min: Vec3<f32>, max: Vec3<f32>, color: Color) {
+    let corner = |x: bool, y: bool, z: bool| Vec3 {
+      x: if x { max.x } else { min.x },
+      y: if y { max.y } else { min.y },
+      z: if z { max.z } else { min.z },
+    };
+    
+    for (a, b) in [(false, false), (true, false), (true, true), (false, true)] {
+      // Edges along x, y and z respectively.
+      self.draw_line(corner(false, a, b), corner(true, a, b), color);
+      self.draw_line(corner(a, false, b), corner(a, true, b), color);
+      self.draw_line(corner(a, b, false), corner(a, b, true), color);
+    }
+  }
+  
+  /// Draw a segment of `length` units from `origin` along `direction`, which does not need to be normalized.
+  pub fn draw_ray(&mut self, origin: Vec3<f32>, direction: Vec3<f32>, length: f32, color: Color) {
+    let direction = direction.normalize();
+    self.draw_line(origin, Vec3 {
+      x: origin.x + direction.x * length,
+      y: origin.y + direction.y * length,
+      z: origin.z + direction.z * length,
+    }, color);
+  }
+  
+  /// Number of line vertices accumulated this frame (two per line).
+  pub fn get_vertex_count(&self) -> usize {
+    return self.m_vertices.len();
+  }
+  
+  pub fn is_empty(&self) -> bool {
+    return self.m_vertices.is_empty();
+  }
+  
+  /// Discard all lines accumulated this frame.
+  pub fn clear(&mut self) {
+    self.m_vertices.clear();
+  }
+  
+  /// Draw all accumulated lines with the given camera matrices, streamed through `buffer`, then clear them. The built-in
+  /// shader and vertex array are only created on the first non-empty flush.
+  pub(crate) fn flush(&mut self, view: Mat4, projection: Mat4, buffer: &mut DynamicBuffer) -> Result<(), EnumRendererError> {
+    if self.m_vertices.is_empty() {
+      return Ok(());
+    }
+    
+    if self.m_shader.is_none() {
+      let mut shader = Shader::new(EnumRendererApi::OpenGL, HashSet::from([
+        ShaderStage::new(EnumShaderStageType::Vertex, EnumShaderSource::FromFile(String::from("res/shaders/debug_line.vert"))),
+        ShaderStage::new(EnumShaderStageType::Fragment, EnumShaderSource::FromFile(String::from("res/shaders/debug_line.frag")))
+      ]));
+      if let Err(_err) = shader.apply() {
+        log!(EnumLogColor::Red, "ERROR", "[DebugDraw] -->\t Cannot create debug line shader, Error => {0}", _err);
+        self.m_vertices.clear();
+        return Err(EnumRendererError::ShaderNotFound);
+      }
+      self.m_shader = Some(shader);
+      self.m_lines = Some(GlDebugLines::new()?);
+    }
+    
+    // SAFETY: DebugVertex is a plain #[repr(C)] struct of 16 bytes without padding.
+    let bytes = unsafe {
+      std::slice::from_raw_parts(self.m_vertices.as_ptr() as *const u8,
+        self.m_vertices.len() * std::mem::size_of::<DebugVertex>())
+    };
+    let program_id = self.m_shader.as_ref().unwrap().get_id();
+    let result = self.m_lines.as_mut().unwrap().draw(program_id, &(projection * view), buffer, bytes);
+    
+    self.m_vertices.clear();
+    return result;
+  }
+  
+  pub fn free(&mut self) -> Result<(), EnumRendererError> {
+    self.m_vertices.clear();
+    if let Some(mut lines) = self.m_lines.take() {
+      lines.free()?;
+    }
+    if let Some(mut shader) = self.m_shader.take() {
+      if let Err(_err) = crate::TraitFree::free(&mut shader) {
+        log!(EnumLogColor::Red, "ERROR", "[DebugDraw] -->\t Error while freeing debug line shader : {0}", _err);
+      }
+    }
+    return Ok(());
+  }
+}
+
+impl Default for DebugDraw {
+  fn default() -> Self {
+    return Self::new();
+  }
+}
@@ -0,0 +1,99 @@
+/*
+ MIT License
+ 
+ Copyright (c) 2023 Nami Reghbati
+ 
+ Permission is hereby granted, free of charge, to any person obtaining a copy
+ of this software and associated documentation files (the "Software"), to deal
+ in the Software without restriction, including without limitation the rights
+ to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ copies of the Software, and to permit persons to whom the Software is
+ furnished to do so, subject to the following conditions:
+ 
+ The above copyright notice and this permission notice shall be included in all
+ copies or substantial portions of the Software.
+ 
+ THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ FITNESS FOR A PARTICULAR PURPOSE AND NON INFRINGEMENT. IN NO EVENT SHALL THE
+ AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ SOFTWARE.
+*/
+
+#[cfg(feature = "debug")]
+use crate::Engine;
+use crate::graphics::open_gl::buffer::GlStreamBuffer;
+use crate::graphics::renderer::{EnumRendererApi, EnumRendererError, RendererCapabilities};
+use crate::utils::macros::logger::*;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum EnumDrawTopology {
+  Lines,
+  Triangles,
+}
+
+/// Vertex buffer for geometry rebuilt every frame (debug lines, text...). Each frame writes in its own region of a
+/// triple-buffered, persistently mapped buffer, so uploading never waits on the GPU still drawing previous frames.
+/// Drivers without persistent mapping (see [RendererCapabilities::m_persistent_mapping]) orphan a single region every
+/// frame instead. OpenGL only for now.
+pub struct DynamicBuffer {
+  m_buffer: GlStreamBuffer,
+}
+
+impl DynamicBuffer {
+  /// Reserve `region_size` bytes per frame in flight. Regions grow when a frame writes more than that, but doing so
+  /// waits on the GPU, so size them for a typical frame.
+  pub fn new(api: EnumRendererApi, region_size: usize, capabilities: &RendererCapabilities) -> Result<Self, EnumRendererError> {
+    if api != EnumRendererApi::OpenGL {
+      log!(EnumLogColor::Yellow, "WARN", "[DynamicBuffer] -->\t Dynamic buffers are only supported with OpenGL for now!");
+      return Err(EnumRendererError::NotImplemented);
+    }
+    if !capabilities.m_persistent_mapping {
+      log!(EnumLogColor::Yellow, "WARN", "[DynamicBuffer] -->\t Persistent buffer mapping unsupported, falling back to \
+      orphaning buffers every frame");
+    }
+    return Ok(Self {
+      m_buffer: GlStreamBuffer::new(region_size, capabilities.m_persistent_mapping)?,
+    });
+  }
+  
+  pub fn is_persistent(&self) -> bool {
+    return self.m_buffer.is_persistent();
+  }
+  
+  pub fn get_region_size(&self) -> usize {
+    return self.m_buffer.get_region_size();
+  }
+  
+  /// Copy `data` in this frame's region, returning its offset in bytes to pass to [Self::draw_range]. Offsets are only
+  /// valid until [Self::next_frame], or until a write grows the buffer.
+  pub fn write(&mut self, data: &[u8]) -> Result<usize, EnumRendererError> {
+    return Ok(self.m_buffer.write(data)?);
+  }
+  
+  /// Draw `vertex_count` vertices of `vertex_size` bytes written at `offset`, with the attribute formats of the vertex
+  /// array `vertex_array_id` (binding 0) and whichever shader is in use.
+  pub fn draw_range(&self, vertex_array_id: u32, topology: EnumDrawTopology, vertex_size: usize, offset: usize,
+                    vertex_count: usize) -> Result<(), EnumRendererError> {
+    if vertex_count == 0 {
+      return Ok(());
+    }
+    let mode = match topology {
+      EnumDrawTopology::Lines => gl::LINES,
+      EnumDrawTopology::Triangles => gl::TRIANGLES,
+    };
+    return Ok(self.m_buffer.draw_range(vertex_array_id, mode, vertex_size, offset, vertex_count)?);
+  }
+  
+  /// Hand this frame's region over to the GPU and start writing in the next one. Called by the renderer after each
+  /// frame for its own buffer.
+  pub fn next_frame(&mut self) -> Result<(), EnumRendererError> {
+    return Ok(self.m_buffer.next_frame()?);
+  }
+  
+  pub fn free(&mut self) -> Result<(), EnumRendererError> {
+    return Ok(self.m_buffer.free()?);
+  }
+}
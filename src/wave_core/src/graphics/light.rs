@@ -0,0 +1,147 @@
+/*
+ MIT License
+ 
+ Copyright (c) 2023 Nami Reghbati
+ 
+ Permission is hereby granted, free of charge, to any person obtaining a copy
+ of this software and associated documentation files (the "Software"), to deal
+ in the Software without restriction, including without limitation the rights
+ to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ copies of the Software, and to permit persons to whom the Software is
+ furnished to do so, subject to the following conditions:
+ 
+ The above copyright notice and this permission notice shall be included in all
+ copies or substantial portions of the Software.
+ 
+ THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ FITNESS FOR A PARTICULAR PURPOSE AND NON INFRINGEMENT. IN NO EVENT SHALL THE
+ AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ SOFTWARE.
+*/
+
+#[cfg(feature = "debug")]
+use crate::Engine;
+use crate::graphics::color::Color;
+use crate::math::{Aabb, Mat4, Vec3};
+use crate::utils::macros::logger::*;
+
+/// Uniform block binding of the lights, declared in shaders as
+/// `layout (std140, binding = 11) uniform ubo_lights`. Requires GLSL 420 or above.
+pub const C_LIGHTS_UBO_BINDING: u32 = 11;
+/// Lights past these counts are ignored when submitted.
+pub const C_MAX_DIRECTIONAL_LIGHTS: usize = 4;
+pub const C_MAX_POINT_LIGHTS: usize = 16;
+/// Size in bytes of the packed light block, see [EnumLight::pack] for the layout.
+pub const C_LIGHTS_UBO_SIZE: usize = 16 + (C_MAX_DIRECTIONAL_LIGHTS + C_MAX_POINT_LIGHTS) * 32;
+/// Uniform block binding of the shadow pass results, declared in shaders as
+/// `layout (std140, binding = 12) uniform ubo_shadow { mat4 light_space; ivec4 enabled; }`.
+pub const C_SHADOW_UBO_BINDING: u32 = 12;
+/// Texture unit the shadow map's depth texture is bound to, declared in shaders as
+/// `layout (binding = 15) uniform sampler2D s_shadow_map`.
+pub const C_SHADOW_MAP_TEXTURE_UNIT: u32 = 15;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum EnumLight {
+  // Infinitely far light (i.e. the sun), shining along a direction.
+  Directional {
+    m_direction: Vec3<f32>,
+    m_color: Color,
+    m_intensity: f32,
+  },
+  // Light shining in all directions from a position, fading out to nothing at its range.
+  Point {
+    m_position: Vec3<f32>,
+    m_color: Color,
+    m_intensity: f32,
+    m_range: f32,
+  },
+}
+
+impl EnumLight {
+  /// View and orthographic projection of a directional light, fitted around the bounds given so that everything inside
+  /// ends up in the shadow map. Point lights would need a cube map and aren't supported yet.
+  pub fn get_light_space_matrices(&self, scene_bounds: &Aabb) -> Option<(Mat4, Mat4)> {
+    let EnumLight::Directional { m_direction, .. } = self else {
+      return None;
+    };
+    
+    let center = scene_bounds.get_center();
+    let radius = (scene_bounds.m_max - scene_bounds.m_min).vec_len().max(f32::EPSILON) / 2.0;
+    let direction = m_direction.normalize();
+    
+    // Back away from the center along the light, far enough for the whole bounding sphere to be in front.
+    let eye = center - Vec3::new(&[direction.x * radius * 2.0, direction.y * radius * 2.0, direction.z * radius * 2.0]);
+    let view = Mat4::apply_look_at(&eye, &center, &Vec3::new(&[0.0, 1.0, 0.0]));
+    let projection = Mat4::apply_orthographic(-radius, radius, -radius, radius, radius * 0.5, radius * 3.5);
+    return Some((view, projection));
+  }
+  
+  /// Pack lights into the std140 layout of the `ubo_lights` block, which custom shaders must match:
+  ///
+  /// ```glsl
+  /// struct Dir_light_s {
+  ///     vec4 direction;  // xyz: direction the light travels in, w: unused.
+  ///     vec4 color;      // rgb: color, a: intensity.
+  /// };
+  ///
+  /// struct Point_light_s {
+  ///     vec4 position;   // xyz: world position, w: range.
+  ///     vec4 color;      // rgb: color, a: intensity.
+  /// };
+  ///
+  /// layout (std140, binding = 11) uniform ubo_lights
+  /// {
+  ///     ivec4 counts;                        // x: directional lights, y: point lights. Offset 0.
+  ///     Dir_light_s directional[4];          // Offset 16.
+  ///     Point_light_s point[16];             // Offset 144.
+  /// } Ubo_lights;
+  /// ```
+  pub fn pack(lights: &[EnumLight]) -> Vec<u8> {
+    let mut directional: Vec<[f32; 8]> = Vec::with_capacity(C_MAX_DIRECTIONAL_LIGHTS);
+    let mut point: Vec<[f32; 8]> = Vec::with_capacity(C_MAX_POINT_LIGHTS);
+    
+    for light in lights.iter() {
+      match light {
+        EnumLight::Directional { m_direction, m_color, m_intensity } => {
+          if directional.len() == C_MAX_DIRECTIONAL_LIGHTS {
+            log!(EnumLogColor::Yellow, "WARN", "[Light] -->\t Too many directional lights, max is {0}, ignoring the rest!",
+              C_MAX_DIRECTIONAL_LIGHTS);
+            continue;
+          }
+          let direction = m_direction.normalize();
+          let color = m_color.as_f32();
+          directional.push([direction.x, direction.y, direction.z, 0.0, color[0], color[1], color[2], *m_intensity]);
+        }
+        EnumLight::Point { m_position, m_color, m_intensity, m_range } => {
+          if point.len() == C_MAX_POINT_LIGHTS {
+            log!(EnumLogColor::Yellow, "WARN", "[Light] -->\t Too many point lights, max is {0}, ignoring the rest!",
+              C_MAX_POINT_LIGHTS);
+            continue;
+          }
+          let color = m_color.as_f32();
+          point.push([m_position.x, m_position.y, m_position.z, *m_range, color[0], color[1], color[2], *m_intensity]);
+        }
+      }
+    }
+    
+    let mut data: Vec<u8> = Vec::with_capacity(C_LIGHTS_UBO_SIZE);
+    for count in [directional.len() as i32, point.len() as i32, 0, 0] {
+      data.extend_from_slice(&count.to_ne_bytes());
+    }
+    // Unused slots are zeroed, the counts tell shaders where to stop.
+    for light_index in 0..C_MAX_DIRECTIONAL_LIGHTS {
+      for value in directional.get(light_index).copied().unwrap_or([0.0; 8]) {
+        data.extend_from_slice(&value.to_ne_bytes());
+      }
+    }
+    for light_index in 0..C_MAX_POINT_LIGHTS {
+      for value in point.get(light_index).copied().unwrap_or([0.0; 8]) {
+        data.extend_from_slice(&value.to_ne_bytes());
+      }
+    }
+    return data;
+  }
+}
@@ -24,8 +24,19 @@
 
 pub mod shader;
 pub mod texture;
+pub mod render_target;
+pub mod post_process;
+pub mod static_batch;
+pub mod debug_draw;
+pub mod dynamic_buffer;
+pub mod uniform_buffer;
 pub mod renderer;
 pub mod text;
 pub mod color;
+pub mod light;
 pub mod vulkan;
 pub mod open_gl;
+
+pub use render_target::RenderTarget;
+pub use dynamic_buffer::DynamicBuffer;
+pub use uniform_buffer::UniformBuffer;
@@ -37,6 +37,8 @@ pub(crate) use gl::types::{GLboolean, GLchar, GLenum, GLfloat, GLint, GLintptr,
 use crate::check_gl_call;
 #[cfg(feature = "debug")]
 use crate::Engine;
+use crate::assets::skeleton::C_MAX_BONES;
+use crate::graphics::light::C_LIGHTS_UBO_SIZE;
 use crate::graphics::open_gl::renderer::EnumOpenGLError;
 use crate::math::Mat4;
 use crate::S_ENGINE;
@@ -64,6 +66,8 @@ pub enum EnumGlBufferError {
   InvalidUboTransformInstanceCount,
   InvalidVbo,
   InvalidVao,
+  InvalidMapping,
+  InvalidFence,
   InvalidUbo,
   InvalidBlockBinding,
 }
@@ -294,6 +298,22 @@ impl GlVao {
     return Ok(());
   }
   
+  /// Source a per-instance model matrix from the given instance buffer, spanning four consecutive vec4 attribute
+  /// locations starting at `location`.
+  pub(crate) fn enable_instance_matrix(&mut self, location: u32, instance_vbo: &mut GlVbo) -> Result<(), EnumOpenGLError> {
+    self.bind()?;
+    instance_vbo.bind()?;
+    
+    let column_size = Mat4::get_size() / 4;
+    for column in 0..4u32 {
+      check_gl_call!("GlVao", gl::EnableVertexAttribArray(location + column));
+      check_gl_call!("GlVao", gl::VertexAttribPointer(location + column, 4, gl::FLOAT, gl::FALSE,
+        Mat4::get_size() as GLsizei, (column as usize * column_size) as *const GLvoid));
+      check_gl_call!("GlVao", gl::VertexAttribDivisor(location + column, 1));
+    }
+    return Ok(());
+  }
+  
   pub(crate) fn unbind(&mut self) -> Result<(), EnumOpenGLError> {
     if self.m_state == EnumBufferState::Bound {
       check_gl_call!("GlVao", gl::BindVertexArray(0));
@@ -437,6 +457,21 @@ impl GlVbo {
     return Ok(());
   }
   
+  /// Overwrite existing data in place, starting `byte_offset` bytes in, without changing the length of the buffer.
+  pub(crate) fn write_at<T>(&mut self, byte_offset: usize, data: &[T]) -> Result<(), EnumOpenGLError> {
+    let data_size: usize = size_of_val(data);
+    if data_size == 0 || byte_offset + data_size > self.m_length {
+      log!(EnumLogColor::Red, "ERROR", "[GlBuffer] -->\t Cannot overwrite data in vbo {0}, data is empty or goes past \
+      the end of the buffer!", self.m_buffer_id);
+      return Err(EnumOpenGLError::from(EnumGlBufferError::InvalidBufferSize));
+    }
+    
+    self.bind()?;
+    check_gl_call!("GlVbo", gl::BufferSubData(self.m_type, byte_offset as GLintptr, data_size as GLsizeiptr,
+      data.as_ptr() as *const GLvoid));
+    return Ok(());
+  }
+  
   #[allow(unused)]
   pub(crate) fn strip(&mut self, buffer_offset: usize, size: usize, count: usize) -> Result<(), EnumOpenGLError> {
     if size * count == 0 || size * count > self.m_length {
@@ -843,6 +878,14 @@ pub(crate) enum EnumUboType {
   ViewProjection(Mat4, Mat4),
   MVP(Mat4, Mat4, Mat4),
   Wireframe(bool, usize),
+  // Pre-packed std140 light block, see [crate::graphics::light::EnumLight::pack].
+  Lights(Vec<u8>),
+  // Light space matrix of the shadow map and whether it should be sampled.
+  Shadow(Mat4, bool),
+  // Whether the primitives about to be drawn sample the shadow map, without touching the rest of the shadow block.
+  ShadowReceiver(bool),
+  // Skinning matrices of a skeleton, see [crate::assets::skeleton::Skeleton::compute_pose].
+  Bones(Vec<Mat4>),
 }
 
 #[allow(unused)]
@@ -858,6 +901,11 @@ pub(crate) enum EnumUboTypeSize {
   Double,
   Long,
   Wireframe(usize),
+  Lights,
+  Shadow,
+  Bones,
+  // Raw, pre-packed std140 data, see [crate::graphics::UniformBuffer].
+  Bytes(usize),
 }
 
 #[allow(unused)]
@@ -894,6 +942,22 @@ impl GlUbo {
         alloc_size = 16 * count;
         data_count = count;
       }
+      EnumUboTypeSize::Lights => {
+        alloc_size = C_LIGHTS_UBO_SIZE;
+        data_count = 1;
+      }
+      EnumUboTypeSize::Shadow => {
+        alloc_size = Mat4::get_size() + 16;
+        data_count = 1;
+      }
+      EnumUboTypeSize::Bones => {
+        alloc_size = Mat4::get_size() * C_MAX_BONES;
+        data_count = C_MAX_BONES;
+      }
+      EnumUboTypeSize::Bytes(size) => {
+        alloc_size = size.max(16);
+        data_count = 1;
+      }
       _ => {
         alloc_size = 16;
         data_count = 1;
@@ -1039,10 +1103,78 @@ impl GlUbo {
         
         check_gl_call!("GlUbo", gl::BufferSubData(gl::UNIFORM_BUFFER, instance_offset as GLintptr, 4 as GLsizeiptr, c_void));
       }
+      EnumUboType::Lights(light_data) => {
+        if light_data.len() > self.m_capacity {
+          log!(EnumLogColor::Red, "ERROR", "[GlBuffer] -->\t Cannot push light data of {0} bytes, exceeds buffer capacity!",
+            light_data.len());
+          return Err(EnumOpenGLError::InvalidBufferOperation(EnumGlBufferError::InvalidBufferOffset));
+        }
+        check_gl_call!("GlUbo", gl::BufferSubData(gl::UNIFORM_BUFFER, 0, light_data.len() as GLsizeiptr,
+          light_data.as_ptr() as *const std::ffi::c_void));
+      }
+      EnumUboType::Shadow(light_space, enabled) => {
+        check_gl_call!("GlUbo", gl::BufferSubData(gl::UNIFORM_BUFFER, 0, Mat4::get_size() as GLsizeiptr,
+          light_space.transpose().as_array().as_ptr() as *const std::ffi::c_void));
+        
+        // Booleans are 4 bytes in std140, but always read as true from a bool pointer, hence the ints. Primitives
+        // receive shadows unless told otherwise.
+        let convert_to_number: [i32; 2] = [enabled.then(|| 1).unwrap_or(0), 1];
+        check_gl_call!("GlUbo", gl::BufferSubData(gl::UNIFORM_BUFFER, Mat4::get_size() as GLintptr, 8 as GLsizeiptr,
+          convert_to_number.as_ptr() as *const std::ffi::c_void));
+      }
+      EnumUboType::ShadowReceiver(receive) => {
+        let convert_to_number: i32 = receive.then(|| 1).unwrap_or(0);
+        check_gl_call!("GlUbo", gl::BufferSubData(gl::UNIFORM_BUFFER, (Mat4::get_size() + 4) as GLintptr, 4 as GLsizeiptr,
+          &convert_to_number as *const _ as *const std::ffi::c_void));
+      }
+      EnumUboType::Bones(bone_matrices) => {
+        if bone_matrices.len() > self.m_count {
+          log!(EnumLogColor::Yellow, "WARN", "[GlBuffer] -->\t Only uploading the first {0} of {1} bones, exceeds buffer \
+           capacity!", self.m_count, bone_matrices.len());
+        }
+        let bone_data: Vec<f32> = bone_matrices.iter()
+          .take(self.m_count)
+          .flat_map(|bone_matrix| bone_matrix.transpose().as_array())
+          .collect();
+        check_gl_call!("GlUbo", gl::BufferSubData(gl::UNIFORM_BUFFER, 0, size_of_val(bone_data.as_slice()) as GLsizeiptr,
+          bone_data.as_ptr() as *const std::ffi::c_void));
+      }
     }
     return Ok(());
   }
   
+  /// Attach the whole buffer to the uniform binding point `binding`, so that any block bound there reads from it.
+  pub(crate) fn bind_base(&mut self, binding: u32) -> Result<(), EnumOpenGLError> {
+    check_gl_call!("GlUbo", gl::BindBufferBase(gl::UNIFORM_BUFFER, binding, self.m_buffer_id));
+    return Ok(());
+  }
+  
+  /// Overwrite the start of the buffer with `data`, reallocating it first if it doesn't fit.
+  pub(crate) fn write_bytes(&mut self, data: &[u8]) -> Result<(), EnumOpenGLError> {
+    if data.len() > self.m_capacity {
+      check_gl_call!("GlUbo", gl::NamedBufferData(self.m_buffer_id, data.len() as GLsizeiptr, std::ptr::null(),
+        gl::DYNAMIC_DRAW));
+      self.m_capacity = data.len();
+    }
+    check_gl_call!("GlUbo", gl::NamedBufferSubData(self.m_buffer_id, 0, data.len() as GLsizeiptr,
+      data.as_ptr() as *const std::ffi::c_void));
+    self.m_length = data.len();
+    return Ok(());
+  }
+  
+  pub(crate) fn read_bytes(&self, offset: usize, size: usize) -> Result<Vec<u8>, EnumOpenGLError> {
+    if offset + size > self.m_capacity {
+      log!(EnumLogColor::Red, "ERROR", "[GlBuffer] -->\t Cannot read range [{0}, {1}) of GlUbo {2}, exceeds its \
+      capacity of {3} bytes!", offset, offset + size, self.m_buffer_id, self.m_capacity);
+      return Err(EnumOpenGLError::from(EnumGlBufferError::InvalidReadBuffer));
+    }
+    
+    let mut data: Vec<u8> = vec![0; size];
+    check_gl_call!("GlUbo", gl::GetNamedBufferSubData(self.m_buffer_id, offset as GLintptr, size as GLsizeiptr,
+      data.as_mut_ptr() as *mut std::ffi::c_void));
+    return Ok(data);
+  }
+  
   pub(crate) fn unbind(&mut self) -> Result<(), EnumOpenGLError> {
     if self.m_state == EnumBufferState::Bound {
       check_gl_call!("GlUbo", gl::BindBuffer(gl::UNIFORM_BUFFER, 0));
@@ -1069,4 +1201,194 @@ impl GlUbo {
     self.m_state = EnumBufferState::Deleted;
     return Ok(());
   }
-}
\ No newline at end of file
+}
+
+/// Streaming vertex buffer split in [Self::C_REGION_COUNT] regions written in turn, one per frame. With persistent
+/// mapping, the whole buffer stays mapped and each region is fenced once the frame using it is submitted, so the CPU
+/// only waits if it catches up to a frame the GPU hasn't finished drawing. Without it, a single region gets orphaned
+/// at the start of every frame instead.
+pub(crate) struct GlStreamBuffer {
+  m_buffer_id: GLuint,
+  m_region_size: usize,
+  m_region: usize,
+  m_cursor: usize,
+  m_mapped_ptr: *mut u8,
+  m_fences: [Option<gl::types::GLsync>; 3],
+  m_persistent: bool,
+}
+
+impl GlStreamBuffer {
+  pub(crate) const C_REGION_COUNT: usize = 3;
+  // Start of every write, to keep vertex attributes aligned whatever was written before them.
+  const C_WRITE_ALIGNMENT: usize = 16;
+  
+  pub(crate) fn new(region_size: usize, persistent: bool) -> Result<Self, EnumOpenGLError> {
+    if region_size == 0 || region_size * Self::C_REGION_COUNT > C_VBO_SIZE_LIMIT {
+      log!(EnumLogColor::Red, "ERROR", "[GlBuffer] -->\t Cannot create stream buffer of {0} bytes per region!", region_size);
+      return Err(EnumOpenGLError::from(EnumGlBufferError::InvalidCapacitySize));
+    }
+    
+    let mut stream_buffer = Self {
+      m_buffer_id: 0,
+      m_region_size: region_size,
+      m_region: 0,
+      m_cursor: 0,
+      m_mapped_ptr: std::ptr::null_mut(),
+      m_fences: [None; 3],
+      m_persistent: persistent,
+    };
+    stream_buffer.allocate()?;
+    return Ok(stream_buffer);
+  }
+  
+  #[allow(unused)]
+  pub(crate) fn get_id(&self) -> u32 {
+    return self.m_buffer_id;
+  }
+  
+  pub(crate) fn get_region_size(&self) -> usize {
+    return self.m_region_size;
+  }
+  
+  pub(crate) fn is_persistent(&self) -> bool {
+    return self.m_persistent;
+  }
+  
+  /// Copy `data` in the current frame's region, returning its offset from the start of the buffer. If the region is
+  /// full, the buffer grows, which invalidates offsets returned earlier in the frame.
+  pub(crate) fn write(&mut self, data: &[u8]) -> Result<usize, EnumOpenGLError> {
+    if data.is_empty() {
+      return Ok(self.get_region_offset() + self.m_cursor);
+    }
+    
+    let start = self.m_cursor.next_multiple_of(Self::C_WRITE_ALIGNMENT);
+    if start + data.len() > self.m_region_size {
+      let new_region_size = data.len().next_power_of_two().max(self.m_region_size * 2);
+      log!(EnumLogColor::Yellow, "WARN", "[GlBuffer] -->\t Stream buffer region full, growing it from {0} to {1} bytes",
+        self.m_region_size, new_region_size);
+      self.grow(new_region_size)?;
+      return self.write(data);
+    }
+    
+    let offset = self.get_region_offset() + start;
+    if self.m_persistent {
+      // SAFETY: The mapping covers the whole buffer, and the region was fenced before being written to again.
+      unsafe { std::ptr::copy_nonoverlapping(data.as_ptr(), self.m_mapped_ptr.add(offset), data.len()) };
+    } else {
+      check_gl_call!("GlStreamBuffer", gl::NamedBufferSubData(self.m_buffer_id, offset as GLintptr,
+        data.len() as GLsizeiptr, data.as_ptr() as *const GLvoid));
+    }
+    self.m_cursor = start + data.len();
+    return Ok(offset);
+  }
+  
+  /// Draw `vertex_count` vertices of `vertex_size` bytes, starting at `offset`, with `vao_id`'s attribute formats.
+  pub(crate) fn draw_range(&self, vao_id: GLuint, mode: GLenum, vertex_size: usize, offset: usize,
+                           vertex_count: usize) -> Result<(), EnumOpenGLError> {
+    check_gl_call!("GlStreamBuffer", gl::VertexArrayVertexBuffer(vao_id, 0, self.m_buffer_id, offset as GLintptr,
+      vertex_size as GLsizei));
+    check_gl_call!("GlStreamBuffer", gl::BindVertexArray(vao_id));
+    check_gl_call!("GlStreamBuffer", gl::DrawArrays(mode, 0, vertex_count as GLsizei));
+    check_gl_call!("GlStreamBuffer", gl::BindVertexArray(0));
+    return Ok(());
+  }
+  
+  /// Fence the region written this frame and move on to the next one, waiting for the GPU to be done reading it.
+  pub(crate) fn next_frame(&mut self) -> Result<(), EnumOpenGLError> {
+    if !self.m_persistent {
+      // Let the driver hand us fresh storage, the previous one being released once drawn.
+      check_gl_call!("GlStreamBuffer", gl::NamedBufferData(self.m_buffer_id, self.m_region_size as GLsizeiptr,
+        std::ptr::null(), gl::STREAM_DRAW));
+      self.m_cursor = 0;
+      return Ok(());
+    }
+    
+    check_gl_call!("GlStreamBuffer", let fence: gl::types::GLsync = gl::FenceSync(gl::SYNC_GPU_COMMANDS_COMPLETE, 0));
+    self.m_fences[self.m_region] = Some(fence);
+    self.m_region = (self.m_region + 1) % Self::C_REGION_COUNT;
+    self.m_cursor = 0;
+    return self.wait_for_region(self.m_region);
+  }
+  
+  pub(crate) fn free(&mut self) -> Result<(), EnumOpenGLError> {
+    if self.m_buffer_id == 0 || !gl::DeleteBuffers::is_loaded() {
+      return Ok(());
+    }
+    for fence in self.m_fences.iter_mut() {
+      if let Some(fence) = fence.take() {
+        check_gl_call!("GlStreamBuffer", gl::DeleteSync(fence));
+      }
+    }
+    if !self.m_mapped_ptr.is_null() {
+      check_gl_call!("GlStreamBuffer", gl::UnmapNamedBuffer(self.m_buffer_id));
+      self.m_mapped_ptr = std::ptr::null_mut();
+    }
+    check_gl_call!("GlStreamBuffer", gl::DeleteBuffers(1, &self.m_buffer_id));
+    self.m_buffer_id = 0;
+    return Ok(());
+  }
+  
+  fn get_region_offset(&self) -> usize {
+    return self.m_region * self.m_region_size;
+  }
+  
+  fn allocate(&mut self) -> Result<(), EnumOpenGLError> {
+    check_gl_call!("GlStreamBuffer", gl::CreateBuffers(1, &mut self.m_buffer_id));
+    
+    if !self.m_persistent {
+      check_gl_call!("GlStreamBuffer", gl::NamedBufferData(self.m_buffer_id, self.m_region_size as GLsizeiptr,
+        std::ptr::null(), gl::STREAM_DRAW));
+      return Ok(());
+    }
+    
+    let flags = gl::MAP_WRITE_BIT | gl::MAP_PERSISTENT_BIT | gl::MAP_COHERENT_BIT;
+    let size = (self.m_region_size * Self::C_REGION_COUNT) as GLsizeiptr;
+    check_gl_call!("GlStreamBuffer", gl::NamedBufferStorage(self.m_buffer_id, size, std::ptr::null(), flags));
+    check_gl_call!("GlStreamBuffer", let mapped_ptr: *mut GLvoid = gl::MapNamedBufferRange(self.m_buffer_id, 0, size, flags));
+    if mapped_ptr.is_null() {
+      log!(EnumLogColor::Red, "ERROR", "[GlBuffer] -->\t Cannot persistently map stream buffer {0}!", self.m_buffer_id);
+      return Err(EnumOpenGLError::from(EnumGlBufferError::InvalidMapping));
+    }
+    self.m_mapped_ptr = mapped_ptr as *mut u8;
+    return Ok(());
+  }
+  
+  // Buffer storage is immutable, so growing means waiting on every region and starting over with a bigger buffer.
+  fn grow(&mut self, region_size: usize) -> Result<(), EnumOpenGLError> {
+    if region_size * Self::C_REGION_COUNT > C_VBO_SIZE_LIMIT {
+      log!(EnumLogColor::Red, "ERROR", "[GlBuffer] -->\t Cannot grow stream buffer past {0} bytes!", C_VBO_SIZE_LIMIT);
+      return Err(EnumOpenGLError::from(EnumGlBufferError::InvalidCapacitySize));
+    }
+    for region in 0..Self::C_REGION_COUNT {
+      self.wait_for_region(region)?;
+    }
+    self.free()?;
+    
+    self.m_region_size = region_size;
+    self.m_region = 0;
+    self.m_cursor = 0;
+    return self.allocate();
+  }
+  
+  fn wait_for_region(&mut self, region: usize) -> Result<(), EnumOpenGLError> {
+    let Some(fence) = self.m_fences[region].take() else {
+      return Ok(());
+    };
+    
+    loop {
+      // One millisecond at a time, flushing commands so the fence can signal at all.
+      check_gl_call!("GlStreamBuffer", let status: GLenum = gl::ClientWaitSync(fence, gl::SYNC_FLUSH_COMMANDS_BIT, 1_000_000));
+      match status {
+        gl::ALREADY_SIGNALED | gl::CONDITION_SATISFIED => break,
+        gl::WAIT_FAILED => {
+          log!(EnumLogColor::Red, "ERROR", "[GlBuffer] -->\t Failed waiting on stream buffer region {0}!", region);
+          check_gl_call!("GlStreamBuffer", gl::DeleteSync(fence));
+          return Err(EnumOpenGLError::from(EnumGlBufferError::InvalidFence));
+        }
+        _ => continue,
+      }
+    }
+    check_gl_call!("GlStreamBuffer", gl::DeleteSync(fence));
+    return Ok(());
+  }
+}
@@ -0,0 +1,570 @@
+/*
+ MIT License
+
+ Copyright (c) 2023 Nami Reghbati
+ 
+ Permission is hereby granted, free of charge, to1 any person obtaining a copy
+ of this software and associated documentation files (the "Software"), to deal
+ in the Software without restriction, including without limitation the rights
+ to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ copies of the Software, and to permit persons to whom the Software is
+ furnished to do so, subject to the following conditions:
+ 
+ The above copyright notice and this permission notice shall be included in all
+ copies or substantial portions of the Software.
+ 
+ THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ FITNESS FOR A PARTICULAR PURPOSE AND NON INFRINGEMENT. IN NO EVENT SHALL THE
+ AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ SOFTWARE.
+*/
+
+/*
+///////////////////////////////////   OpenGL    ///////////////////////////////////
+///////////////////////////////////             ///////////////////////////////////
+///////////////////////////////////             ///////////////////////////////////
+ */
+
+extern crate gl;
+
+use std::mem::size_of;
+
+use gl::types::{GLboolean, GLenum, GLint, GLsizei, GLuint};
+
+use crate::check_gl_call;
+use crate::Engine;
+use crate::graphics::dynamic_buffer::{DynamicBuffer, EnumDrawTopology};
+use crate::graphics::open_gl::renderer::EnumOpenGLError;
+use crate::graphics::open_gl::texture::GlTexture;
+use crate::graphics::render_target::TraitRenderTarget;
+use crate::graphics::renderer::EnumRendererError;
+use crate::graphics::texture::{EnumTextureFormat, Texture};
+use crate::math::Mat4;
+use crate::S_ENGINE;
+use crate::utils::macros::logger::*;
+
+/// Offscreen multisampled framebuffer, resolved onto the default framebuffer at the end of each frame. Unlike the
+/// default framebuffer, its sample count can be changed at runtime without recreating the window.
+pub(crate) struct GlFramebuffer {
+  m_framebuffer_id: GLuint,
+  m_color_attachment_id: GLuint,
+  m_depth_stencil_attachment_id: GLuint,
+  m_size: (u32, u32),
+  m_samples: u32,
+}
+
+impl GlFramebuffer {
+  pub(crate) fn new(width: u32, height: u32, samples: u32) -> Result<Self, EnumOpenGLError> {
+    let mut new_framebuffer = GlFramebuffer {
+      m_framebuffer_id: 0,
+      m_color_attachment_id: 0,
+      m_depth_stencil_attachment_id: 0,
+      m_size: (width, height),
+      m_samples: samples,
+    };
+    new_framebuffer.allocate()?;
+    return Ok(new_framebuffer);
+  }
+  
+  pub(crate) fn get_max_samples() -> Result<u32, EnumOpenGLError> {
+    let mut max_samples: GLint = 0;
+    check_gl_call!("GlFramebuffer", gl::GetIntegerv(gl::MAX_SAMPLES, &mut max_samples));
+    return Ok(max_samples.max(0) as u32);
+  }
+  
+  pub(crate) fn bind(&self) -> Result<(), EnumOpenGLError> {
+    check_gl_call!("GlFramebuffer", gl::BindFramebuffer(gl::FRAMEBUFFER, self.m_framebuffer_id));
+    return Ok(());
+  }
+  
+  pub(crate) fn unbind(&self) -> Result<(), EnumOpenGLError> {
+    check_gl_call!("GlFramebuffer", gl::BindFramebuffer(gl::FRAMEBUFFER, 0));
+    return Ok(());
+  }
+  
+  /// Resolve the multisampled color attachment onto the default framebuffer.
+  pub(crate) fn resolve(&self) -> Result<(), EnumOpenGLError> {
+    check_gl_call!("GlFramebuffer", gl::BindFramebuffer(gl::READ_FRAMEBUFFER, self.m_framebuffer_id));
+    check_gl_call!("GlFramebuffer", gl::BindFramebuffer(gl::DRAW_FRAMEBUFFER, 0));
+    check_gl_call!("GlFramebuffer", gl::BlitFramebuffer(0, 0, self.m_size.0 as GLint, self.m_size.1 as GLint,
+      0, 0, self.m_size.0 as GLint, self.m_size.1 as GLint, gl::COLOR_BUFFER_BIT, gl::NEAREST));
+    return self.unbind();
+  }
+  
+  /// Read the color buffer `read_buffer` of the framebuffer `source_framebuffer_id` (0 for the window) as RGBA8,
+  /// top row first. Multisampled framebuffers cannot be read from directly, so they get resolved into a temporary
+  /// single-sampled one first.
+  pub(crate) fn read_pixels(source_framebuffer_id: GLuint, read_buffer: GLenum, width: u32, height: u32) -> Result<Vec<u8>, EnumOpenGLError> {
+    let (width, height) = (width as GLsizei, height as GLsizei);
+    let mut pixels: Vec<u8> = vec![0; width as usize * height as usize * 4];
+    
+    let mut sample_buffers: GLint = 0;
+    check_gl_call!("GlFramebuffer", gl::NamedFramebufferReadBuffer(source_framebuffer_id, read_buffer));
+    check_gl_call!("GlFramebuffer", gl::GetNamedFramebufferParameteriv(source_framebuffer_id, gl::SAMPLE_BUFFERS,
+      &mut sample_buffers));
+    
+    let mut resolve_framebuffer_id: GLuint = 0;
+    let mut resolve_color_id: GLuint = 0;
+    let mut read_framebuffer_id: GLuint = source_framebuffer_id;
+    if sample_buffers > 0 {
+      check_gl_call!("GlFramebuffer", gl::CreateFramebuffers(1, &mut resolve_framebuffer_id));
+      check_gl_call!("GlFramebuffer", gl::CreateRenderbuffers(1, &mut resolve_color_id));
+      check_gl_call!("GlFramebuffer", gl::NamedRenderbufferStorage(resolve_color_id, gl::RGBA8, width, height));
+      check_gl_call!("GlFramebuffer", gl::NamedFramebufferRenderbuffer(resolve_framebuffer_id, gl::COLOR_ATTACHMENT0,
+        gl::RENDERBUFFER, resolve_color_id));
+      
+      // Copy the stored values as is, like reading them directly would.
+      check_gl_call!("GlFramebuffer", let srgb_enabled: GLboolean = gl::IsEnabled(gl::FRAMEBUFFER_SRGB));
+      check_gl_call!("GlFramebuffer", gl::Disable(gl::FRAMEBUFFER_SRGB));
+      check_gl_call!("GlFramebuffer", gl::BlitNamedFramebuffer(source_framebuffer_id, resolve_framebuffer_id,
+        0, 0, width, height, 0, 0, width, height, gl::COLOR_BUFFER_BIT, gl::NEAREST));
+      if srgb_enabled == gl::TRUE {
+        check_gl_call!("GlFramebuffer", gl::Enable(gl::FRAMEBUFFER_SRGB));
+      }
+      read_framebuffer_id = resolve_framebuffer_id;
+    }
+    
+    check_gl_call!("GlFramebuffer", gl::BindFramebuffer(gl::READ_FRAMEBUFFER, read_framebuffer_id));
+    check_gl_call!("GlFramebuffer", gl::PixelStorei(gl::PACK_ALIGNMENT, 1));
+    check_gl_call!("GlFramebuffer", gl::ReadPixels(0, 0, width, height, gl::RGBA, gl::UNSIGNED_BYTE,
+      pixels.as_mut_ptr() as *mut std::ffi::c_void));
+    check_gl_call!("GlFramebuffer", gl::BindFramebuffer(gl::READ_FRAMEBUFFER, 0));
+    
+    if resolve_framebuffer_id != 0 {
+      check_gl_call!("GlFramebuffer", gl::DeleteRenderbuffers(1, &resolve_color_id));
+      check_gl_call!("GlFramebuffer", gl::DeleteFramebuffers(1, &resolve_framebuffer_id));
+    }
+    
+    // OpenGL reads rows from the bottom up.
+    let row_size = width as usize * 4;
+    return Ok(pixels.chunks_exact(row_size).rev().flatten().copied().collect());
+  }
+  
+  pub(crate) fn resize(&mut self, width: u32, height: u32) -> Result<(), EnumOpenGLError> {
+    if self.m_size == (width, height) || width == 0 || height == 0 {
+      return Ok(());
+    }
+    self.free()?;
+    self.m_size = (width, height);
+    return self.allocate();
+  }
+  
+  pub(crate) fn free(&mut self) -> Result<(), EnumOpenGLError> {
+    if self.m_framebuffer_id == 0 {
+      return Ok(());
+    }
+    
+    if gl::DeleteFramebuffers::is_loaded() {
+      check_gl_call!("GlFramebuffer", gl::DeleteRenderbuffers(1, &self.m_color_attachment_id));
+      check_gl_call!("GlFramebuffer", gl::DeleteRenderbuffers(1, &self.m_depth_stencil_attachment_id));
+      check_gl_call!("GlFramebuffer", gl::DeleteFramebuffers(1, &self.m_framebuffer_id));
+    }
+    self.m_framebuffer_id = 0;
+    self.m_color_attachment_id = 0;
+    self.m_depth_stencil_attachment_id = 0;
+    return Ok(());
+  }
+  
+  fn allocate(&mut self) -> Result<(), EnumOpenGLError> {
+    let (width, height) = (self.m_size.0 as GLsizei, self.m_size.1 as GLsizei);
+    
+    check_gl_call!("GlFramebuffer", gl::CreateFramebuffers(1, &mut self.m_framebuffer_id));
+    check_gl_call!("GlFramebuffer", gl::CreateRenderbuffers(1, &mut self.m_color_attachment_id));
+    check_gl_call!("GlFramebuffer", gl::CreateRenderbuffers(1, &mut self.m_depth_stencil_attachment_id));
+    
+    check_gl_call!("GlFramebuffer", gl::NamedRenderbufferStorageMultisample(self.m_color_attachment_id,
+      self.m_samples as GLsizei, gl::SRGB8_ALPHA8, width, height));
+    check_gl_call!("GlFramebuffer", gl::NamedRenderbufferStorageMultisample(self.m_depth_stencil_attachment_id,
+      self.m_samples as GLsizei, gl::DEPTH24_STENCIL8, width, height));
+    
+    check_gl_call!("GlFramebuffer", gl::NamedFramebufferRenderbuffer(self.m_framebuffer_id, gl::COLOR_ATTACHMENT0,
+      gl::RENDERBUFFER, self.m_color_attachment_id));
+    check_gl_call!("GlFramebuffer", gl::NamedFramebufferRenderbuffer(self.m_framebuffer_id, gl::DEPTH_STENCIL_ATTACHMENT,
+      gl::RENDERBUFFER, self.m_depth_stencil_attachment_id));
+    
+    let status = unsafe { gl::CheckNamedFramebufferStatus(self.m_framebuffer_id, gl::FRAMEBUFFER) };
+    if status != gl::FRAMEBUFFER_COMPLETE {
+      log!(EnumLogColor::Red, "ERROR", "[GlFramebuffer] -->\t Multisampled framebuffer (X{0}) incomplete! Status => \
+      0x{1:x}", self.m_samples, status);
+      self.free()?;
+      return Err(EnumOpenGLError::MSAAError);
+    }
+    
+    log!(EnumLogColor::Green, "INFO", "[GlFramebuffer] -->\t Created multisampled framebuffer (X{0}) of {1}x{2}",
+      self.m_samples, width, height);
+    return Ok(());
+  }
+}
+
+/// Offscreen framebuffer backed by sampleable textures, for shadow maps, picking buffers, post-processing, etc.
+pub(crate) struct GlRenderTarget {
+  m_framebuffer_id: GLuint,
+  m_color_attachments: Vec<(GLuint, EnumTextureFormat)>,
+  m_depth_attachment: Option<GLuint>,
+  m_with_depth: bool,
+  m_size: (u32, u32),
+}
+
+impl GlRenderTarget {
+  pub(crate) fn new(width: u32, height: u32, color_formats: &[EnumTextureFormat], with_depth: bool) -> Result<Self, EnumRendererError> {
+    let mut new_render_target = GlRenderTarget {
+      m_framebuffer_id: 0,
+      m_color_attachments: color_formats.iter().map(|format| (0, *format)).collect(),
+      m_depth_attachment: None,
+      m_with_depth: with_depth,
+      m_size: (width, height),
+    };
+    new_render_target.allocate()?;
+    return Ok(new_render_target);
+  }
+  
+  fn convert_format_to_internal_format(format: EnumTextureFormat) -> GLenum {
+    return match format {
+      EnumTextureFormat::Red => gl::R8,
+      EnumTextureFormat::Rg => gl::RG8,
+      EnumTextureFormat::Rgb | EnumTextureFormat::Bgr => gl::RGB8,
+      EnumTextureFormat::Rgba | EnumTextureFormat::Bgra => gl::RGBA8,
+    };
+  }
+  
+  fn create_attachment_texture(internal_format: GLenum, width: GLsizei, height: GLsizei) -> Result<GLuint, EnumOpenGLError> {
+    let mut texture_id: GLuint = 0;
+    
+    check_gl_call!("GlRenderTarget", gl::CreateTextures(gl::TEXTURE_2D, 1, &mut texture_id));
+    check_gl_call!("GlRenderTarget", gl::TextureStorage2D(texture_id, 1, internal_format, width, height));
+    check_gl_call!("GlRenderTarget", gl::TextureParameteri(texture_id, gl::TEXTURE_MIN_FILTER, gl::LINEAR as GLint));
+    check_gl_call!("GlRenderTarget", gl::TextureParameteri(texture_id, gl::TEXTURE_MAG_FILTER, gl::LINEAR as GLint));
+    check_gl_call!("GlRenderTarget", gl::TextureParameteri(texture_id, gl::TEXTURE_WRAP_S, gl::CLAMP_TO_EDGE as GLint));
+    check_gl_call!("GlRenderTarget", gl::TextureParameteri(texture_id, gl::TEXTURE_WRAP_T, gl::CLAMP_TO_EDGE as GLint));
+    return Ok(texture_id);
+  }
+  
+  fn allocate(&mut self) -> Result<(), EnumRendererError> {
+    let (width, height) = (self.m_size.0 as GLsizei, self.m_size.1 as GLsizei);
+    
+    check_gl_call!("GlRenderTarget", gl::CreateFramebuffers(1, &mut self.m_framebuffer_id));
+    
+    let mut draw_buffers: Vec<GLenum> = Vec::with_capacity(self.m_color_attachments.len());
+    for (index, (texture_id, format)) in self.m_color_attachments.iter_mut().enumerate() {
+      *texture_id = Self::create_attachment_texture(Self::convert_format_to_internal_format(*format), width, height)?;
+      check_gl_call!("GlRenderTarget", gl::NamedFramebufferTexture(self.m_framebuffer_id,
+        gl::COLOR_ATTACHMENT0 + index as GLenum, *texture_id, 0));
+      draw_buffers.push(gl::COLOR_ATTACHMENT0 + index as GLenum);
+    }
+    
+    if draw_buffers.is_empty() {
+      // Depth only (i.e. shadow maps).
+      check_gl_call!("GlRenderTarget", gl::NamedFramebufferDrawBuffer(self.m_framebuffer_id, gl::NONE));
+      check_gl_call!("GlRenderTarget", gl::NamedFramebufferReadBuffer(self.m_framebuffer_id, gl::NONE));
+    } else {
+      check_gl_call!("GlRenderTarget", gl::NamedFramebufferDrawBuffers(self.m_framebuffer_id, draw_buffers.len() as GLsizei,
+        draw_buffers.as_ptr()));
+    }
+    
+    if self.m_with_depth {
+      let depth_id = Self::create_attachment_texture(gl::DEPTH_COMPONENT32F, width, height)?;
+      check_gl_call!("GlRenderTarget", gl::NamedFramebufferTexture(self.m_framebuffer_id, gl::DEPTH_ATTACHMENT, depth_id, 0));
+      self.m_depth_attachment = Some(depth_id);
+    }
+    
+    let status = unsafe { gl::CheckNamedFramebufferStatus(self.m_framebuffer_id, gl::FRAMEBUFFER) };
+    if status != gl::FRAMEBUFFER_COMPLETE {
+      log!(EnumLogColor::Red, "ERROR", "[GlRenderTarget] -->\t Render target of {0}x{1} incomplete! Status => 0x{2:x}",
+        width, height, status);
+      self.free()?;
+      return Err(EnumRendererError::IncompleteFramebuffer);
+    }
+    
+    log!(EnumLogColor::Green, "INFO", "[GlRenderTarget] -->\t Created render target of {0}x{1} with {2} color \
+      attachment(s)", width, height, self.m_color_attachments.len());
+    return Ok(());
+  }
+}
+
+impl TraitRenderTarget for GlRenderTarget {
+  fn bind(&self) -> Result<(), EnumRendererError> {
+    check_gl_call!("GlRenderTarget", gl::BindFramebuffer(gl::FRAMEBUFFER, self.m_framebuffer_id));
+    check_gl_call!("GlRenderTarget", gl::Viewport(0, 0, self.m_size.0 as GLsizei, self.m_size.1 as GLsizei));
+    return Ok(());
+  }
+  
+  fn unbind(&self) -> Result<(), EnumRendererError> {
+    let (width, height) = Engine::get_active_window().get_framebuffer_size();
+    
+    check_gl_call!("GlRenderTarget", gl::BindFramebuffer(gl::FRAMEBUFFER, 0));
+    check_gl_call!("GlRenderTarget", gl::Viewport(0, 0, width as GLsizei, height as GLsizei));
+    return Ok(());
+  }
+  
+  fn resize(&mut self, width: u32, height: u32) -> Result<(), EnumRendererError> {
+    if self.m_size == (width, height) || width == 0 || height == 0 {
+      return Ok(());
+    }
+    self.free()?;
+    self.m_size = (width, height);
+    return self.allocate();
+  }
+  
+  fn get_size(&self) -> (u32, u32) {
+    return self.m_size;
+  }
+  
+  fn get_color_attachment_id(&self, index: usize) -> Option<u32> {
+    return self.m_color_attachments.get(index).map(|(texture_id, _)| *texture_id);
+  }
+  
+  fn read_pixels(&self, index: usize) -> Result<Vec<u8>, EnumRendererError> {
+    if index >= self.m_color_attachments.len() {
+      log!(EnumLogColor::Red, "ERROR", "[GlRenderTarget] -->\t Cannot read color attachment {0}, render target only has \
+      {1}!", index, self.m_color_attachments.len());
+      return Err(EnumRendererError::IncompleteFramebuffer);
+    }
+    return Ok(GlFramebuffer::read_pixels(self.m_framebuffer_id, gl::COLOR_ATTACHMENT0 + index as GLenum,
+      self.m_size.0, self.m_size.1)?);
+  }
+  
+  fn copy_from_window(&self) -> Result<(), EnumRendererError> {
+    let (width, height) = (self.m_size.0 as GLint, self.m_size.1 as GLint);
+    check_gl_call!("GlRenderTarget", gl::BlitNamedFramebuffer(0, self.m_framebuffer_id, 0, 0, width, height,
+      0, 0, width, height, gl::COLOR_BUFFER_BIT, gl::NEAREST));
+    return Ok(());
+  }
+  
+  fn copy_to_window(&self) -> Result<(), EnumRendererError> {
+    let (width, height) = (self.m_size.0 as GLint, self.m_size.1 as GLint);
+    check_gl_call!("GlRenderTarget", gl::BlitNamedFramebuffer(self.m_framebuffer_id, 0, 0, 0, width, height,
+      0, 0, width, height, gl::COLOR_BUFFER_BIT, gl::NEAREST));
+    return Ok(());
+  }
+  
+  fn get_color_texture(&self, index: usize) -> Option<Texture> {
+    return self.m_color_attachments.get(index).map(|(texture_id, format)| {
+      Texture::from_existing(Box::new(GlTexture::<u8>::from_existing(*texture_id, self.m_size.0, self.m_size.1, *format)))
+    });
+  }
+  
+  fn get_depth_attachment_id(&self) -> Option<u32> {
+    return self.m_depth_attachment;
+  }
+  
+  fn get_depth_texture(&self) -> Option<Texture> {
+    return self.m_depth_attachment.map(|depth_id| {
+      Texture::from_existing(Box::new(GlTexture::<u8>::from_existing(depth_id, self.m_size.0, self.m_size.1,
+        EnumTextureFormat::Red)))
+    });
+  }
+  
+  fn free(&mut self) -> Result<(), EnumRendererError> {
+    if self.m_framebuffer_id == 0 {
+      return Ok(());
+    }
+    
+    if gl::DeleteFramebuffers::is_loaded() {
+      for (texture_id, _) in self.m_color_attachments.iter() {
+        check_gl_call!("GlRenderTarget", gl::DeleteTextures(1, texture_id));
+      }
+      if let Some(depth_id) = self.m_depth_attachment.as_ref() {
+        check_gl_call!("GlRenderTarget", gl::DeleteTextures(1, depth_id));
+      }
+      check_gl_call!("GlRenderTarget", gl::DeleteFramebuffers(1, &self.m_framebuffer_id));
+    }
+    self.m_framebuffer_id = 0;
+    self.m_depth_attachment = None;
+    self.m_color_attachments.iter_mut().for_each(|(texture_id, _)| *texture_id = 0);
+    return Ok(());
+  }
+}
+
+/// Draws a single triangle covering the whole viewport, for fullscreen passes. Positions are generated in the vertex
+/// shader from the vertex index, so only an empty vertex array is needed.
+pub(crate) struct GlFullscreenPass {
+  m_vao_id: GLuint,
+}
+
+impl GlFullscreenPass {
+  pub(crate) fn new() -> Result<Self, EnumRendererError> {
+    let mut vao_id: GLuint = 0;
+    check_gl_call!("GlFullscreenPass", gl::CreateVertexArrays(1, &mut vao_id));
+    return Ok(GlFullscreenPass {
+      m_vao_id: vao_id,
+    });
+  }
+  
+  pub(crate) fn draw(&self, program_id: u32, source_texture_id: u32) -> Result<(), EnumRendererError> {
+    let depth_test_enabled = unsafe { gl::IsEnabled(gl::DEPTH_TEST) } == gl::TRUE;
+    
+    check_gl_call!("GlFullscreenPass", gl::Disable(gl::DEPTH_TEST));
+    check_gl_call!("GlFullscreenPass", gl::UseProgram(program_id));
+    check_gl_call!("GlFullscreenPass", gl::BindTextureUnit(0, source_texture_id));
+    check_gl_call!("GlFullscreenPass", gl::BindVertexArray(self.m_vao_id));
+    check_gl_call!("GlFullscreenPass", gl::DrawArrays(gl::TRIANGLES, 0, 3));
+    check_gl_call!("GlFullscreenPass", gl::BindVertexArray(0));
+    
+    if depth_test_enabled {
+      check_gl_call!("GlFullscreenPass", gl::Enable(gl::DEPTH_TEST));
+    }
+    return Ok(());
+  }
+  
+  pub(crate) fn free(&mut self) -> Result<(), EnumRendererError> {
+    if self.m_vao_id != 0 && gl::DeleteVertexArrays::is_loaded() {
+      check_gl_call!("GlFullscreenPass", gl::DeleteVertexArrays(1, &self.m_vao_id));
+    }
+    self.m_vao_id = 0;
+    return Ok(());
+  }
+}
+
+/// Streamed line list used for debug drawing : each vertex is a position (3 x f32) followed by a packed RGBA8 color,
+/// re-uploaded every frame and drawn with `GL_LINES`.
+pub(crate) struct GlDebugLines {
+  m_vao_id: GLuint,
+}
+
+impl GlDebugLines {
+  const C_VERTEX_SIZE: usize = 3 * size_of::<f32>() + size_of::<u32>();
+  
+  pub(crate) fn new() -> Result<Self, EnumRendererError> {
+    let mut vao_id: GLuint = 0;
+    check_gl_call!("GlDebugLines", gl::CreateVertexArrays(1, &mut vao_id));
+    
+    // Position.
+    check_gl_call!("GlDebugLines", gl::EnableVertexArrayAttrib(vao_id, 0));
+    check_gl_call!("GlDebugLines", gl::VertexArrayAttribFormat(vao_id, 0, 3, gl::FLOAT, gl::FALSE, 0));
+    check_gl_call!("GlDebugLines", gl::VertexArrayAttribBinding(vao_id, 0, 0));
+    // Color, normalized from its packed bytes.
+    check_gl_call!("GlDebugLines", gl::EnableVertexArrayAttrib(vao_id, 1));
+    check_gl_call!("GlDebugLines", gl::VertexArrayAttribFormat(vao_id, 1, 4, gl::UNSIGNED_BYTE, gl::TRUE,
+      (3 * size_of::<f32>()) as GLuint));
+    check_gl_call!("GlDebugLines", gl::VertexArrayAttribBinding(vao_id, 1, 0));
+    
+    return Ok(GlDebugLines {
+      m_vao_id: vao_id,
+    });
+  }
+  
+  /// Stream the packed vertices through `buffer` and draw them as lines, with `view_projection` as the only uniform
+  /// (location 0).
+  pub(crate) fn draw(&mut self, program_id: u32, view_projection: &Mat4, buffer: &mut DynamicBuffer,
+                     vertices: &[u8]) -> Result<(), EnumRendererError> {
+    let vertex_count = vertices.len() / Self::C_VERTEX_SIZE;
+    if vertex_count == 0 {
+      return Ok(());
+    }
+    let offset = buffer.write(vertices)?;
+    
+    check_gl_call!("GlDebugLines", gl::UseProgram(program_id));
+    check_gl_call!("GlDebugLines", gl::ProgramUniformMatrix4fv(program_id, 0, 1, gl::FALSE,
+      view_projection.transpose().as_array().as_ptr()));
+    return buffer.draw_range(self.m_vao_id, EnumDrawTopology::Lines, Self::C_VERTEX_SIZE, offset, vertex_count);
+  }
+  
+  pub(crate) fn free(&mut self) -> Result<(), EnumRendererError> {
+    if self.m_vao_id != 0 && gl::DeleteVertexArrays::is_loaded() {
+      check_gl_call!("GlDebugLines", gl::DeleteVertexArrays(1, &self.m_vao_id));
+    }
+    self.m_vao_id = 0;
+    return Ok(());
+  }
+}
+
+/// Vertex array and atlas textures for screen-space signed distance field text.
+pub(crate) struct GlTextQuads {
+  m_vao_id: GLuint,
+}
+
+impl GlTextQuads {
+  const C_VERTEX_SIZE: usize = 4 * size_of::<f32>() + size_of::<u32>();
+  
+  pub(crate) fn new() -> Result<Self, EnumRendererError> {
+    let mut vao_id: GLuint = 0;
+    check_gl_call!("GlTextQuads", gl::CreateVertexArrays(1, &mut vao_id));
+    
+    // Position, in pixels.
+    check_gl_call!("GlTextQuads", gl::EnableVertexArrayAttrib(vao_id, 0));
+    check_gl_call!("GlTextQuads", gl::VertexArrayAttribFormat(vao_id, 0, 2, gl::FLOAT, gl::FALSE, 0));
+    check_gl_call!("GlTextQuads", gl::VertexArrayAttribBinding(vao_id, 0, 0));
+    // Atlas coordinates.
+    check_gl_call!("GlTextQuads", gl::EnableVertexArrayAttrib(vao_id, 1));
+    check_gl_call!("GlTextQuads", gl::VertexArrayAttribFormat(vao_id, 1, 2, gl::FLOAT, gl::FALSE,
+      (2 * size_of::<f32>()) as GLuint));
+    check_gl_call!("GlTextQuads", gl::VertexArrayAttribBinding(vao_id, 1, 0));
+    // Color, normalized from its packed bytes.
+    check_gl_call!("GlTextQuads", gl::EnableVertexArrayAttrib(vao_id, 2));
+    check_gl_call!("GlTextQuads", gl::VertexArrayAttribFormat(vao_id, 2, 4, gl::UNSIGNED_BYTE, gl::TRUE,
+      (4 * size_of::<f32>()) as GLuint));
+    check_gl_call!("GlTextQuads", gl::VertexArrayAttribBinding(vao_id, 2, 0));
+    
+    return Ok(GlTextQuads {
+      m_vao_id: vao_id,
+    });
+  }
+  
+  /// Upload a single channel distance field atlas, filtered linearly so edges can be reconstructed between texels.
+  pub(crate) fn create_atlas(data: &[u8], width: usize, height: usize) -> Result<u32, EnumRendererError> {
+    let mut texture_id: GLuint = 0;
+    check_gl_call!("GlTextQuads", gl::CreateTextures(gl::TEXTURE_2D, 1, &mut texture_id));
+    check_gl_call!("GlTextQuads", gl::TextureStorage2D(texture_id, 1, gl::R8, width as GLsizei, height as GLsizei));
+    // Rows of the atlas are tightly packed single bytes.
+    check_gl_call!("GlTextQuads", gl::PixelStorei(gl::UNPACK_ALIGNMENT, 1));
+    check_gl_call!("GlTextQuads", gl::TextureSubImage2D(texture_id, 0, 0, 0, width as GLsizei, height as GLsizei,
+      gl::RED, gl::UNSIGNED_BYTE, data.as_ptr() as *const std::ffi::c_void));
+    check_gl_call!("GlTextQuads", gl::PixelStorei(gl::UNPACK_ALIGNMENT, 4));
+    check_gl_call!("GlTextQuads", gl::TextureParameteri(texture_id, gl::TEXTURE_MIN_FILTER, gl::LINEAR as GLint));
+    check_gl_call!("GlTextQuads", gl::TextureParameteri(texture_id, gl::TEXTURE_MAG_FILTER, gl::LINEAR as GLint));
+    check_gl_call!("GlTextQuads", gl::TextureParameteri(texture_id, gl::TEXTURE_WRAP_S, gl::CLAMP_TO_EDGE as GLint));
+    check_gl_call!("GlTextQuads", gl::TextureParameteri(texture_id, gl::TEXTURE_WRAP_T, gl::CLAMP_TO_EDGE as GLint));
+    return Ok(texture_id);
+  }
+  
+  pub(crate) fn delete_atlas(texture_id: u32) -> Result<(), EnumRendererError> {
+    if texture_id != 0 && gl::DeleteTextures::is_loaded() {
+      check_gl_call!("GlTextQuads", gl::DeleteTextures(1, &texture_id));
+    }
+    return Ok(());
+  }
+  
+  /// Stream the packed vertices through `buffer` and draw them as triangles sampling `atlas_id`, with `projection`
+  /// (location 0) and the atlas sampler (location 1) as uniforms. Text is alpha blended without depth testing, always
+  /// being on top.
+  pub(crate) fn draw(&mut self, program_id: u32, projection: &Mat4, atlas_id: u32, buffer: &mut DynamicBuffer,
+                     vertices: &[u8]) -> Result<(), EnumRendererError> {
+    let vertex_count = vertices.len() / Self::C_VERTEX_SIZE;
+    if vertex_count == 0 {
+      return Ok(());
+    }
+    let offset = buffer.write(vertices)?;
+    
+    let depth_test_enabled: bool = unsafe { gl::IsEnabled(gl::DEPTH_TEST) == gl::TRUE };
+    let blending_enabled: bool = unsafe { gl::IsEnabled(gl::BLEND) == gl::TRUE };
+    check_gl_call!("GlTextQuads", gl::Disable(gl::DEPTH_TEST));
+    check_gl_call!("GlTextQuads", gl::Enable(gl::BLEND));
+    check_gl_call!("GlTextQuads", gl::BlendFunc(gl::SRC_ALPHA, gl::ONE_MINUS_SRC_ALPHA));
+    
+    check_gl_call!("GlTextQuads", gl::UseProgram(program_id));
+    check_gl_call!("GlTextQuads", gl::ProgramUniformMatrix4fv(program_id, 0, 1, gl::FALSE,
+      projection.transpose().as_array().as_ptr()));
+    check_gl_call!("GlTextQuads", gl::ProgramUniform1i(program_id, 1, 0));
+    check_gl_call!("GlTextQuads", gl::BindTextureUnit(0, atlas_id));
+    let result = buffer.draw_range(self.m_vao_id, EnumDrawTopology::Triangles, Self::C_VERTEX_SIZE, offset, vertex_count);
+    
+    if depth_test_enabled {
+      check_gl_call!("GlTextQuads", gl::Enable(gl::DEPTH_TEST));
+    }
+    if !blending_enabled {
+      check_gl_call!("GlTextQuads", gl::Disable(gl::BLEND));
+    }
+    return result;
+  }
+  
+  pub(crate) fn free(&mut self) -> Result<(), EnumRendererError> {
+    if self.m_vao_id != 0 && gl::DeleteVertexArrays::is_loaded() {
+      check_gl_call!("GlTextQuads", gl::DeleteVertexArrays(1, &self.m_vao_id));
+    }
+    self.m_vao_id = 0;
+    return Ok(());
+  }
+}
@@ -23,21 +23,28 @@
 */
 
 use std::any::Any;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt::{Display, Formatter};
 use std::mem::size_of;
 
 use gl46::GlFns;
-use gl::types::{GLint, GLintptr, GLvoid};
+use gl::types::{GLboolean, GLint, GLintptr, GLvoid};
 
 use crate::{Engine, S_ENGINE};
 use crate::assets::r_assets::{EnumMaterialShading, EnumPrimitiveShading, EnumVertexMemberOffset, REntity, TraitPrimitive, Vertex};
+use crate::assets::skeleton::C_BONES_UBO_BINDING;
 use crate::events::EnumEvent;
 use crate::graphics::{open_gl, renderer};
 use crate::graphics::open_gl::buffer::{EnumAttributeType, EnumUboType, EnumUboTypeSize, GLchar, GLenum, GlIbo, GLsizei, GlUbo, GLuint, GlVao, GlVbo, GlVertexAttribute};
-use crate::graphics::renderer::{EnumRendererBlendingFactor, EnumRendererCallCheckingMode, EnumRendererCull, EnumRendererError, EnumRendererHint, EnumRendererOptimizationMode, EnumRendererRenderPrimitiveAs, EnumRendererState, TraitContext};
+use crate::graphics::light::{C_LIGHTS_UBO_BINDING, C_SHADOW_MAP_TEXTURE_UNIT, C_SHADOW_UBO_BINDING};
+use crate::graphics::open_gl::framebuffer::GlFramebuffer;
+use crate::graphics::open_gl::texture::C_GL_MAX_TEXTURE_MAX_ANISOTROPY;
+use crate::graphics::render_target::RenderTarget;
+use crate::graphics::renderer::{EnumRendererBlendMode, EnumRendererBlendingFactor, EnumRendererCallCheckingMode, EnumRendererCull, EnumRendererDepthFunc, EnumRendererError, EnumRendererHint, EnumRendererOptimizationMode, EnumRendererRenderPrimitiveAs, EnumRendererState, RendererCapabilities, RendererStats, TraitContext};
 use crate::graphics::shader::{EnumShaderLanguage, Shader};
-use crate::math::Mat4;
+use crate::graphics::uniform_buffer::UniformBuffer;
+use crate::camera::{C_CAMERA_UBO_BINDING, Frustum};
+use crate::math::{Aabb, Mat4, Vec3};
 use crate::utils::macros::logger::*;
 use crate::window::Window;
 
@@ -134,6 +141,21 @@ macro_rules! check_gl_call {
     };
 }
 
+impl From<EnumRendererDepthFunc> for GLenum {
+  fn from(value: EnumRendererDepthFunc) -> Self {
+    return match value {
+      EnumRendererDepthFunc::Never => gl::NEVER,
+      EnumRendererDepthFunc::Less => gl::LESS,
+      EnumRendererDepthFunc::Equal => gl::EQUAL,
+      EnumRendererDepthFunc::LessEqual => gl::LEQUAL,
+      EnumRendererDepthFunc::Greater => gl::GREATER,
+      EnumRendererDepthFunc::NotEqual => gl::NOTEQUAL,
+      EnumRendererDepthFunc::GreaterEqual => gl::GEQUAL,
+      EnumRendererDepthFunc::Always => gl::ALWAYS
+    };
+  }
+}
+
 impl From<EnumRendererBlendingFactor> for GLenum {
   fn from(value: EnumRendererBlendingFactor) -> Self {
     return match value {
@@ -164,6 +186,7 @@ pub enum EnumOpenGLError {
   InvalidContext,
   InvalidOperation(GLenum),
   MSAAError,
+  MissingInstanceAttribute,
   EntityUUIDNotFound,
   InvalidEntityType,
   InvalidBufferOperation(open_gl::buffer::EnumGlBufferError),
@@ -217,6 +240,8 @@ pub enum EnumGlDrawCommandFunction {
   MultiDrawElementsBaseVertex(EnumGlPrimitiveMode, *const GLsizei, EnumGlElementType, *const *const GLvoid, GLsizei, *mut GLint),
   MultiDrawArraysIndirect(EnumGlPrimitiveMode, *const GLvoid, GLsizei, GLsizei),
   MultiDrawElementsIndirect(EnumGlPrimitiveMode, EnumGlElementType, *const GLvoid, GLsizei, GLsizei),
+  DrawArraysInstanced(EnumGlPrimitiveMode, GLint, GLsizei, GLsizei),
+  DrawElementsInstanced(EnumGlPrimitiveMode, GLsizei, EnumGlElementType, *const GLvoid, GLsizei),
 }
 
 impl EnumGlDrawCommandFunction {
@@ -264,6 +289,16 @@ impl EnumGlDrawCommandFunction {
         check_gl_call!("GlContext", gl::MultiDrawElementsIndirect(*mode as GLenum, *index_type as GLenum, *indirect, *draw_count, *stride));
         Ok(())
       }
+      EnumGlDrawCommandFunction::DrawArraysInstanced(mode, base_vertex, vertex_count, instance_count) => {
+        check_gl_call!("GlContext", gl::DrawArraysInstanced(*mode as GLenum, *base_vertex, *vertex_count, *instance_count));
+        Ok(())
+      }
+      EnumGlDrawCommandFunction::DrawElementsInstanced(mode, index_count, e_type, ibo_offset,
+        instance_count) => {
+        check_gl_call!("GlContext", gl::DrawElementsInstanced(*mode as GLenum, *index_count, *e_type as GLenum, *ibo_offset,
+          *instance_count));
+        Ok(())
+      }
     };
   }
 }
@@ -306,6 +341,14 @@ impl Display for EnumGlDrawCommandFunction {
         write!(f, "glMultiDrawElementsIndirect({0:?}, {1:?}, {2:?}, {3}, {4})", mode, index_type, draw_command_array_pointer, draw_count,
           stride_between_commands)
       }
+      EnumGlDrawCommandFunction::DrawArraysInstanced(mode, vertex_offset, vertex_count, instance_count) => {
+        write!(f, "glDrawArraysInstanced({0:?}, {1}, {2}, {3})", mode, vertex_offset, vertex_count, instance_count)
+      }
+      EnumGlDrawCommandFunction::DrawElementsInstanced(mode, index_count, index_type, index_array,
+        instance_count) => {
+        write!(f, "glDrawElementsInstanced({0:?}, {1}, {2:?}, {3:?}, {4})", mode, index_count, index_type, index_array,
+          instance_count)
+      }
     };
   }
 }
@@ -341,6 +384,15 @@ struct GlPrimitiveInfo {
   m_base_index: i32,
   m_entity_offset: usize,
   m_visible: bool,  // Make primitive appear or disappear upon request from the user
+  m_polygon_mode: GLenum,  // Fill, line or point, set right before drawing the primitive
+  m_transparent: bool,  // Drawn after all opaque primitives, sorted back-to-front
+  m_blend_mode: EnumRendererBlendMode,  // Blend function used when drawing the primitive, if transparent
+  m_cast_shadows: bool,  // Drawn in the shadow pass
+  m_receive_shadows: bool,  // Samples the shadow map in the main pass
+  m_position: Vec3<f32>,  // World position of the entity, used to sort transparent primitives
+  m_local_bounds: Aabb,
+  m_world_bounds: Aabb,
+  m_culled: bool,  // Outside the camera frustum this frame
 }
 
 struct GlDrawCommandInfo {
@@ -351,6 +403,31 @@ struct GlDrawCommandInfo {
   m_primitives: Vec<GlPrimitiveInfo>,
 }
 
+struct GlInstancedCommandInfo {
+  m_uuid: u64,
+  m_linked_shader: u32,
+  m_vao: GlVao,
+  m_vbo: GlVbo,
+  m_ibo: Option<GlIbo>,
+  m_instance_vbo: GlVbo,
+  m_vertex_count: GLsizei,
+  m_index_count: GLsizei,
+  m_instance_count: GLsizei,
+  m_receive_shadows: bool,
+}
+
+impl GlInstancedCommandInfo {
+  fn free(&mut self) -> Result<(), EnumOpenGLError> {
+    self.m_vao.free()?;
+    self.m_vbo.free()?;
+    self.m_instance_vbo.free()?;
+    if let Some(ibo) = self.m_ibo.as_mut() {
+      ibo.free()?;
+    }
+    return Ok(());
+  }
+}
+
 struct GlRendererCommands {
   m_draw_commands: Vec<GlDrawCommandInfo>,
   m_draw_command_index_count_array: Vec<GLsizei>,
@@ -374,7 +451,7 @@ impl GlRendererCommands {
 }
 
 pub struct GlContext {
-  pub(crate) m_ext: HashMap<String, ()>,
+  m_capabilities: RendererCapabilities,
   pub(crate) m_state: EnumRendererState,
   pub(crate) m_version: u32,
   m_commands: GlRendererCommands,
@@ -383,24 +460,46 @@ pub struct GlContext {
   m_indirect_buffers: Vec<GlVbo>,
   m_ibo_buffers: Vec<GlIbo>,
   m_ubo_buffers: Vec<GlUbo>,
+  // View and projection of the active camera, uploaded once for every shader to read.
+  m_camera_ubo: Option<UniformBuffer>,
   m_debug_callback: gl::types::GLDEBUGPROC,
   m_batch_mode: EnumRendererOptimizationMode,
+  m_buffer_allocations: usize,
+  m_msaa_framebuffer: Option<GlFramebuffer>,
+  m_instanced_commands: Vec<GlInstancedCommandInfo>,
+  m_view_matrix: Mat4,
+  m_projection_matrix: Option<Mat4>,
+  m_frustum_culling: bool,
+  m_depth_func: EnumRendererDepthFunc,
+  m_depth_write: bool,
+  // Pose of each skinned entity (by uuid), uploaded to the bones block right before drawing the entity.
+  m_bone_poses: HashMap<u64, Vec<Mat4>>,
 }
 
 impl TraitContext for GlContext {
   fn new() -> Self {
     return Self {
       m_state: EnumRendererState::NotCreated,
-      m_ext: HashMap::new(),
+      m_capabilities: RendererCapabilities::default(),
       m_commands: GlRendererCommands::new(),
       m_vao_buffers: Vec::new(),
       m_vbo_buffers: Vec::new(),
       m_indirect_buffers: Vec::new(),
       m_ibo_buffers: Vec::new(),
       m_ubo_buffers: Vec::new(),
+      m_camera_ubo: None,
       m_debug_callback: Some(gl_error_callback),
       m_batch_mode: EnumRendererOptimizationMode::default(),
       m_version: 460,
+      m_buffer_allocations: 0,
+      m_msaa_framebuffer: None,
+      m_instanced_commands: Vec::new(),
+      m_view_matrix: Mat4::default(),
+      m_projection_matrix: None,
+      m_frustum_culling: true,
+      m_depth_func: EnumRendererDepthFunc::default(),
+      m_depth_write: true,
+      m_bone_poses: HashMap::new(),
     };
   }
   
@@ -434,31 +533,57 @@ impl TraitContext for GlContext {
   }
   
   fn check_extension(&self, desired_extension: &str) -> bool {
-    let str = String::from(desired_extension);
-    return self.m_ext.contains_key(&str);
+    return self.m_capabilities.has_extension(desired_extension);
+  }
+  
+  fn get_capabilities(&self) -> &RendererCapabilities {
+    return &self.m_capabilities;
   }
   
   fn on_event(&mut self, event: &EnumEvent) -> Result<bool, EnumRendererError> {
     return match event {
       EnumEvent::FramebufferEvent(width, height) => {
         check_gl_call!("GlContext", gl::Viewport(0, 0, *width as GLsizei, *height as GLsizei));
+        if let Some(msaa_framebuffer) = self.m_msaa_framebuffer.as_mut() {
+          msaa_framebuffer.resize(*width, *height)?;
+        }
         Ok(true)
       }
       _ => Ok(false)
     };
   }
   
-  fn on_render(&mut self) -> Result<(), EnumRendererError> {
+  fn on_render(&mut self, #[allow(unused)] stats: &mut RendererStats) -> Result<(), EnumRendererError> {
     if self.m_state == EnumRendererState::Submitted {
+      // Draws redirected to a render target (see Renderer::set_render_target) go straight to it, without MSAA.
+      let mut draw_framebuffer_id: GLint = 0;
+      check_gl_call!("GlContext", gl::GetIntegerv(gl::DRAW_FRAMEBUFFER_BINDING, &mut draw_framebuffer_id));
+      let with_msaa = draw_framebuffer_id == 0;
+      
+      if let Some(msaa_framebuffer) = self.m_msaa_framebuffer.as_ref().filter(|_| with_msaa) {
+        msaa_framebuffer.bind()?;
+      }
+      // Clearing honors the depth mask.
+      check_gl_call!("GlContext", gl::DepthMask(gl::TRUE));
       check_gl_call!("GlContext", gl::Clear(gl::COLOR_BUFFER_BIT | gl::DEPTH_BUFFER_BIT));
+      self.restore_depth_state()?;
       
       // If we are rendering the same material type, don't make unnecessary bindings.
       let mut previous_shader_id: i32 = -1;
       let mut previous_ibo: i32 = -1;
+      let mut current_polygon_mode: GLenum = gl::FILL;
+      let mut current_receive_shadows: Option<bool> = None;
+      let mut current_bone_pose: Option<u64> = None;
+      
+      self.cull_primitives(stats);
       
       for draw_command in self.m_commands.m_draw_commands.iter() {
         if draw_command.m_linked_shader != previous_shader_id as u32 {
           check_gl_call!("GlContext", gl::UseProgram(draw_command.m_linked_shader));
+          #[cfg(feature = "debug")]
+          {
+            stats.m_active_shaders += 1;
+          }
           
           self.m_vao_buffers[draw_command.m_vao_index].bind()?;
           previous_shader_id = draw_command.m_linked_shader as i32;
@@ -469,55 +594,102 @@ impl TraitContext for GlContext {
           }
         }
         
-        let new_draw: EnumGlDrawCommandFunction;
+        // Split the batch into runs of consecutive primitives sharing the same polygon mode, so that toggling a single
+        // entity to wireframe or points doesn't affect the rest of the batch.
+        #[cfg(feature = "debug")]
+        let is_indexed = !self.m_ibo_buffers.is_empty() && !self.m_ibo_buffers[draw_command.m_ibo_index].is_empty();
+        let mut run_start: usize = 0;
         
-        if self.m_ibo_buffers.is_empty() || self.m_ibo_buffers[draw_command.m_ibo_index].is_empty() {
-          if self.m_version >= 430 && self.m_batch_mode == EnumRendererOptimizationMode::MinimizeDrawCalls {
-            // Be careful to only load indirect structs from GPU instead of from client-side, since that requires compatibility profile.
-            new_draw = EnumGlDrawCommandFunction::MultiDrawArraysIndirect(EnumGlPrimitiveMode::Triangle,
-              std::ptr::null() as *const _,
-              draw_command.m_primitives.len() as GLsizei,
-              0);
-            new_draw.draw()?;
+        while run_start < draw_command.m_primitives.len() {
+          let polygon_mode = draw_command.m_primitives[run_start].m_polygon_mode;
+          let transparent = draw_command.m_primitives[run_start].m_transparent;
+          let culled = draw_command.m_primitives[run_start].m_culled;
+          let receive_shadows = draw_command.m_primitives[run_start].m_receive_shadows;
+          let bone_pose = self.get_bone_pose(&draw_command.m_primitives[run_start]);
+          let run_end = draw_command.m_primitives[run_start..].iter()
+            .position(|primitive| primitive.m_polygon_mode != polygon_mode || primitive.m_transparent != transparent ||
+              primitive.m_culled != culled || primitive.m_receive_shadows != receive_shadows ||
+              self.get_bone_pose(primitive) != bone_pose)
+            .map(|run_length| run_start + run_length)
+            .unwrap_or(draw_command.m_primitives.len());
+          
+          // Transparent primitives are drawn separately below, once all opaque ones are done.
+          if transparent || culled {
+            run_start = run_end;
             continue;
           }
-          new_draw = EnumGlDrawCommandFunction::MultiDrawArrays(EnumGlPrimitiveMode::Triangle,
-            self.m_commands.m_draw_command_vertex_count_array.as_ptr() as *const GLsizei,
-            self.m_commands.m_draw_command_vertex_offset_array.as_ptr() as *const GLsizei,
-            draw_command.m_primitives.len() as GLsizei);
-          new_draw.draw()?;
-          continue;
-        }
-        
-        match self.m_batch_mode {
-          EnumRendererOptimizationMode::MinimizeDrawCalls => {
-            if self.m_version >= 430 {
-              // Be careful to only load indirect structs from GPU instead of from client-side, since that requires compatibility profile.
-              new_draw = EnumGlDrawCommandFunction::MultiDrawElementsIndirect(EnumGlPrimitiveMode::Triangle,
-                EnumGlElementType::UnsignedInt,
-                std::ptr::null() as *const _,
-                draw_command.m_primitives.len() as GLsizei,
-                0);
-              new_draw.draw()?;
-              continue;
-            }
-            
-            new_draw = EnumGlDrawCommandFunction::DrawElements(EnumGlPrimitiveMode::Triangle,
-              self.m_ibo_buffers[draw_command.m_ibo_index].m_count as i32,
-              EnumGlElementType::UnsignedInt,
-              std::ptr::null() as *const _);
+          
+          if polygon_mode != current_polygon_mode {
+            check_gl_call!("GlContext", gl::PolygonMode(gl::FRONT_AND_BACK, polygon_mode));
+            current_polygon_mode = polygon_mode;
+          }
+          if current_receive_shadows != Some(receive_shadows) {
+            Self::push_shadow_receiver(&mut self.m_ubo_buffers, receive_shadows)?;
+            current_receive_shadows = Some(receive_shadows);
           }
-          EnumRendererOptimizationMode::NoOptimizations => {
-            new_draw = EnumGlDrawCommandFunction::MultiDrawElementsBaseVertex(EnumGlPrimitiveMode::Triangle,
-              self.m_commands.m_draw_command_index_count_array.as_ptr() as *const GLsizei,
-              EnumGlElementType::UnsignedInt,
-              self.m_commands.m_draw_command_index_offset_array.as_ptr() as *const *const GLvoid,
-              draw_command.m_primitives.len() as GLsizei,
-              self.m_commands.m_draw_command_base_indices.as_mut_ptr() as *mut GLint);
+          Self::push_bone_pose(&mut self.m_ubo_buffers, &self.m_bone_poses, bone_pose, &mut current_bone_pose)?;
+          self.draw_primitives(draw_command, run_start, run_end - run_start)?;
+          
+          // Each run results in exactly one draw call.
+          #[cfg(feature = "debug")]
+          {
+            for primitive in draw_command.m_primitives[run_start..run_end].iter() {
+              stats.m_vertices += primitive.m_vbo_count as u64;
+              stats.m_triangles += (is_indexed.then(|| primitive.m_ibo_count).unwrap_or(primitive.m_vbo_count) / 3) as u64;
+            }
+            stats.m_draw_calls += 1;
           }
+          run_start = run_end;
+        }
+      }
+      
+      self.draw_transparent_primitives(&mut current_polygon_mode, &mut current_receive_shadows, &mut current_bone_pose, stats)?;
+      
+      // Restore the default polygon mode, for instanced entities and for anything drawn after us.
+      if current_polygon_mode != gl::FILL {
+        check_gl_call!("GlContext", gl::PolygonMode(gl::FRONT_AND_BACK, gl::FILL));
+      }
+      
+      // Instanced entities, one draw call per entity no matter how many instances there are.
+      if !self.m_instanced_commands.is_empty() {
+        // Batched vaos keep track of their bound state, so make sure they get rebound next frame.
+        for vao in self.m_vao_buffers.iter_mut() {
+          vao.unbind()?;
+        }
+      }
+      for instanced_command in self.m_instanced_commands.iter_mut() {
+        check_gl_call!("GlContext", gl::UseProgram(instanced_command.m_linked_shader));
+        instanced_command.m_vao.bind()?;
+        if current_receive_shadows != Some(instanced_command.m_receive_shadows) {
+          Self::push_shadow_receiver(&mut self.m_ubo_buffers, instanced_command.m_receive_shadows)?;
+          current_receive_shadows = Some(instanced_command.m_receive_shadows);
         }
         
+        let new_draw = match instanced_command.m_ibo {
+          Some(_) => EnumGlDrawCommandFunction::DrawElementsInstanced(EnumGlPrimitiveMode::Triangle,
+            instanced_command.m_index_count, EnumGlElementType::UnsignedInt, std::ptr::null(),
+            instanced_command.m_instance_count),
+          None => EnumGlDrawCommandFunction::DrawArraysInstanced(EnumGlPrimitiveMode::Triangle, 0,
+            instanced_command.m_vertex_count, instanced_command.m_instance_count)
+        };
         new_draw.draw()?;
+        instanced_command.m_vao.unbind()?;
+        
+        #[cfg(feature = "debug")]
+        {
+          let instance_count = instanced_command.m_instance_count as u64;
+          let primitive_count = instanced_command.m_ibo.is_some()
+            .then(|| instanced_command.m_index_count)
+            .unwrap_or(instanced_command.m_vertex_count) as u64;
+          stats.m_active_shaders += 1;
+          stats.m_draw_calls += 1;
+          stats.m_vertices += instanced_command.m_vertex_count as u64 * instance_count;
+          stats.m_triangles += (primitive_count / 3) * instance_count;
+        }
+      }
+      
+      if let Some(msaa_framebuffer) = self.m_msaa_framebuffer.as_ref().filter(|_| with_msaa) {
+        msaa_framebuffer.resolve()?;
       }
     }
     return Ok(());
@@ -543,24 +715,23 @@ impl TraitContext for GlContext {
       }
     }
     let extensions = GlContext::load_extensions()?;
-    let mut hash_map = HashMap::with_capacity(extensions.len());
-    for ext in extensions.into_iter() {
-      hash_map.insert(ext, ());
-    }
     
     self.m_state = EnumRendererState::Created;
     self.m_batch_mode = EnumRendererOptimizationMode::default();
     self.m_commands = GlRendererCommands::new();
     self.m_debug_callback = Some(gl_error_callback);
-    self.m_ext = hash_map;
+    // Query limits before toggling any option, since some of them (MSAA) depend on it.
+    self.m_capabilities = GlContext::query_capabilities(extensions.into_iter().collect())?;
+    log!("INFO", "[GlContext] -->\t Capabilities :\n{0}", self.m_capabilities);
     
     // Enable or disable features AFTER context creation since we need a context to load our openGL
     // functions.
+    let [red, green, blue, alpha] = renderer::C_DEFAULT_CLEAR_COLOR;
+    check_gl_call!("GlContext", gl::ClearColor(red, green, blue, alpha));
     self.toggle_options(renderer_hints)?;
     
     let window_framebuffer_size = window.get_framebuffer_size();
     check_gl_call!("GlContext", gl::Viewport(0, 0, window_framebuffer_size.0 as i32, window_framebuffer_size.1 as i32));
-    check_gl_call!("GlContext", gl::ClearColor(0.025, 0.025, 0.025, 1.0));
     
     self.m_state = EnumRendererState::Submitted;
     return Ok(());
@@ -586,27 +757,97 @@ impl TraitContext for GlContext {
   }
   
   fn toggle_primitive_mode(&mut self, mode: EnumRendererRenderPrimitiveAs, entity_uuid: u64, instance_offset: Option<usize>, instance_count: usize) -> Result<(), EnumRendererError> {
-    match mode {
-      EnumRendererRenderPrimitiveAs::Filled => {
-        check_gl_call!("GlContext", gl::PolygonMode(gl::FRONT_AND_BACK, gl::FILL));
-        self.toggle_solid_wireframe(false, entity_uuid, instance_offset, instance_count)?
-      }
-      EnumRendererRenderPrimitiveAs::Points => {
-        check_gl_call!("GlContext", gl::PolygonMode(gl::FRONT_AND_BACK, gl::POINT));
-        self.toggle_solid_wireframe(false, entity_uuid, instance_offset, instance_count)?
-      }
-      EnumRendererRenderPrimitiveAs::Wireframe => {
-        check_gl_call!("GlContext", gl::PolygonMode(gl::FRONT_AND_BACK, gl::LINE));
-        self.toggle_solid_wireframe(false, entity_uuid, instance_offset, instance_count)?
+    let polygon_mode: GLenum = match mode {
+      EnumRendererRenderPrimitiveAs::Points => gl::POINT,
+      EnumRendererRenderPrimitiveAs::Wireframe => gl::LINE,
+      EnumRendererRenderPrimitiveAs::Filled | EnumRendererRenderPrimitiveAs::SolidWireframe => gl::FILL,
+    };
+    
+    // Only store the mode per primitive, it gets set right before the primitive is drawn and restored afterward.
+    let entity_offsets = (entity_uuid as usize + instance_offset.unwrap_or(0))..(entity_uuid as usize + instance_count);
+    let mut found = false;
+    for command in self.m_commands.m_draw_commands.iter_mut() {
+      for primitive in command.m_primitives.iter_mut()
+        .filter(|primitive| entity_offsets.contains(&primitive.m_entity_offset)) {
+        primitive.m_polygon_mode = polygon_mode;
+        found = true;
       }
-      EnumRendererRenderPrimitiveAs::SolidWireframe => {
-        check_gl_call!("GlContext", gl::PolygonMode(gl::FRONT_AND_BACK, gl::FILL));
-        self.toggle_solid_wireframe(true, entity_uuid, instance_offset, instance_count)?
+    }
+    
+    if !found {
+      log!(EnumLogColor::Red, "ERROR", "[GlContext] -->\t Cannot toggle primitive mode of entity {0}, entity not found!", entity_uuid);
+      return Err(EnumRendererError::EntityNotFound);
+    }
+    return self.toggle_solid_wireframe(mode == EnumRendererRenderPrimitiveAs::SolidWireframe, entity_uuid, instance_offset,
+      instance_count);
+  }
+  
+  fn toggle_blend_mode(&mut self, entity_uuid: u64, instance_offset: Option<usize>, instance_count: usize, mode: EnumRendererBlendMode) -> Result<(), EnumRendererError> {
+    let entity_offsets = (entity_uuid as usize + instance_offset.unwrap_or(0))..(entity_uuid as usize + instance_count);
+    let mut found = false;
+    for command in self.m_commands.m_draw_commands.iter_mut() {
+      for primitive in command.m_primitives.iter_mut()
+        .filter(|primitive| entity_offsets.contains(&primitive.m_entity_offset)) {
+        primitive.m_transparent = mode != EnumRendererBlendMode::Opaque;
+        primitive.m_blend_mode = mode;
+        found = true;
       }
     }
+    
+    if !found {
+      log!(EnumLogColor::Red, "ERROR", "[GlContext] -->\t Cannot toggle blend mode of entity {0}, entity not found!", entity_uuid);
+      return Err(EnumRendererError::EntityNotFound);
+    }
+    return Ok(());
+  }
+  
+  fn set_viewport(&mut self, viewport: (i32, i32, u32, u32), scissor: bool) -> Result<(), EnumRendererError> {
+    check_gl_call!("GlContext", gl::Viewport(viewport.0, viewport.1, viewport.2 as GLsizei, viewport.3 as GLsizei));
+    
+    // Clip clears as well when rendering to a sub-region, to leave the rest of the window untouched.
+    if scissor {
+      check_gl_call!("GlContext", gl::Scissor(viewport.0, viewport.1, viewport.2 as GLsizei, viewport.3 as GLsizei));
+      check_gl_call!("GlContext", gl::Enable(gl::SCISSOR_TEST));
+    } else {
+      check_gl_call!("GlContext", gl::Disable(gl::SCISSOR_TEST));
+    }
     return Ok(());
   }
   
+  fn get_polygon_mode(&self) -> Result<EnumRendererRenderPrimitiveAs, EnumRendererError> {
+    let mut polygon_mode: GLint = 0;
+    check_gl_call!("GlContext", gl::GetIntegerv(gl::POLYGON_MODE, &mut polygon_mode));
+    
+    return Ok(match polygon_mode as GLenum {
+      gl::POINT => EnumRendererRenderPrimitiveAs::Points,
+      gl::LINE => EnumRendererRenderPrimitiveAs::Wireframe,
+      _ => EnumRendererRenderPrimitiveAs::Filled
+    });
+  }
+  
+  fn get_blend_mode(&self) -> Result<Option<EnumRendererBlendMode>, EnumRendererError> {
+    check_gl_call!("GlContext", let blending_enabled: GLboolean = gl::IsEnabled(gl::BLEND));
+    if blending_enabled == gl::FALSE {
+      return Ok(Some(EnumRendererBlendMode::Opaque));
+    }
+    
+    let mut source: GLint = 0;
+    let mut destination: GLint = 0;
+    let mut equation: GLint = 0;
+    check_gl_call!("GlContext", gl::GetIntegerv(gl::BLEND_SRC_RGB, &mut source));
+    check_gl_call!("GlContext", gl::GetIntegerv(gl::BLEND_DST_RGB, &mut destination));
+    check_gl_call!("GlContext", gl::GetIntegerv(gl::BLEND_EQUATION_RGB, &mut equation));
+    
+    // All presets add the source and destination terms together.
+    if equation as GLenum != gl::FUNC_ADD {
+      return Ok(None);
+    }
+    return Ok([EnumRendererBlendMode::AlphaBlend, EnumRendererBlendMode::Additive, EnumRendererBlendMode::Multiply]
+      .into_iter()
+      .find(|mode| mode.get_factors()
+        .is_some_and(|(src, dst)| GLenum::from(src) == source as GLenum && GLenum::from(dst) == destination as GLenum)));
+  }
+  
   fn get_max_msaa_count(&self) -> Result<u8, EnumRendererError> {
     // let framebuffer_color_sample_count: u8 = self.m_framebuffer.max_color_sample_count;
     // let framebuffer_depth_sample_count: u8 = self.m_framebuffer.max_depth_sample_count;
@@ -685,6 +926,16 @@ impl TraitContext for GlContext {
           log!("INFO", "[GlContext] -->\t Depth test {0}",
           enabled.then(|| return "enabled").unwrap_or("disabled"));
         }
+        EnumRendererHint::DepthFunc(func) => {
+          self.m_depth_func = *func;
+          check_gl_call!("GlContext", gl::DepthFunc(GLenum::from(*func)));
+          log!("INFO", "[GlContext] -->\t Depth function set to {0}", func);
+        }
+        EnumRendererHint::DepthWrite(enabled) => {
+          self.m_depth_write = *enabled;
+          check_gl_call!("GlContext", gl::DepthMask(enabled.then_some(gl::TRUE).unwrap_or(gl::FALSE)));
+          log!("INFO", "[GlContext] -->\t Depth write {0}", enabled.then(|| return "enabled").unwrap_or("disabled"));
+        }
         EnumRendererHint::MSAA(sample_count) => {
           #[allow(unused)]
             let mut max_sample_count: u8 = 1;
@@ -713,6 +964,7 @@ impl TraitContext for GlContext {
           }
           
           if opt_factors.is_some() {
+            check_gl_call!("GlContext", gl::BlendEquation(gl::FUNC_ADD));
             check_gl_call!("GlContext", gl::BlendFunc(GLenum::from(opt_factors.unwrap().0), GLenum::from(opt_factors.unwrap().1)));
           }
           
@@ -756,6 +1008,16 @@ impl TraitContext for GlContext {
         }
         EnumRendererHint::SplitLargeVertexBuffers(_vertex_limit) => {}
         EnumRendererHint::SplitLargeIndexBuffers(_index_limit) => {}
+        // Read by the renderer when allocating the shadow map.
+        EnumRendererHint::ShadowMapResolution(_resolution) => {}
+        EnumRendererHint::ClearColor([red, green, blue, alpha]) => {
+          check_gl_call!("GlContext", gl::ClearColor(*red, *green, *blue, *alpha));
+          log!("INFO", "[GlContext] -->\t Clear color set to ({0}, {1}, {2}, {3})", red, green, blue, alpha);
+        }
+        EnumRendererHint::FrustumCulling(enabled) => {
+          self.m_frustum_culling = *enabled;
+          log!("INFO", "[GlContext] -->\t Frustum culling {0}", enabled.then(|| return "enabled").unwrap_or("disabled"));
+        }
         EnumRendererHint::ForceApiVersion(version_requested) => {
           if *version_requested <= self.get_max_shader_version_available() as u32 {
             self.m_version = *version_requested;
@@ -768,12 +1030,13 @@ impl TraitContext for GlContext {
   }
   
   fn flush(&mut self) -> Result<(), EnumRendererError> {
-    self.on_render()?;
+    self.on_render(&mut RendererStats::default())?;
     
     self.m_commands.m_draw_commands.clear();
     self.m_vao_buffers.clear();
     self.m_vbo_buffers.clear();
     self.m_ubo_buffers.clear();
+    self.m_bone_poses.clear();
     return Ok(());
   }
   
@@ -790,7 +1053,8 @@ impl TraitContext for GlContext {
     
     // Check if this is the first primitive of this type. If so, alloc new buffers for it.
     if primitive_matched_with_shader_found.is_none() {
-      self.alloc_buffers(r_asset, shader_associated)?;
+      self.alloc_buffers(r_asset, r_asset.get_size() * r_asset.get_total_vertex_count(),
+        size_of::<u32>() * r_asset.get_total_index_count(), shader_associated)?;
     }
     
     let mut ibo_index = 0;
@@ -852,6 +1116,15 @@ impl TraitContext for GlContext {
         m_base_index: base_index,
         m_entity_offset: last_primitive_offset + position,
         m_visible: false,
+        m_polygon_mode: gl::FILL,
+        m_transparent: r_asset.is_transparent(),
+        m_blend_mode: r_asset.get_blend_mode(),
+        m_cast_shadows: r_asset.casts_shadows(),
+        m_receive_shadows: r_asset.receives_shadows(),
+        m_position: Vec3::new(&[transform[0][3], transform[1][3], transform[2][3]]),
+        m_local_bounds: r_asset.get_bounds(),
+        m_world_bounds: r_asset.get_world_bounds(),
+        m_culled: false,
       };
       
       self.push_buffers(&new_primitive, vao_index, vbo_index, ibo_index, sub_mesh, transform)?;
@@ -862,17 +1135,7 @@ impl TraitContext for GlContext {
     }
     
     self.push_command(command)?;
-    
-    // If we already have a perspective camera ubo bound, skip.
-    if !self.m_ubo_buffers.iter().any(|ubo| ubo.get_name() == Some("ubo_camera")) {
-      let mut camera_ubo = GlUbo::new(Some("ubo_camera"), EnumUboTypeSize::ViewProjection, 0)?;
-      
-      // If glsl version is lower than 420, then we cannot bind blocks in shaders and have to encode them here instead.
-      if shader_associated.get_version() < 420 {
-        camera_ubo.bind_block(shader_associated.get_id(), 0)?;
-      }
-      self.m_ubo_buffers.push(camera_ubo);
-    }
+    self.alloc_camera_ubo(shader_associated)?;
     
     // let mut result = 0;
     // check_gl_call!("GLContext", gl::GetVertexAttribiv(0, gl::VERTEX_ATTRIB_ARRAY_BUFFER_BINDING, &mut result));
@@ -900,20 +1163,372 @@ impl TraitContext for GlContext {
     return Ok(());
   }
   
-  fn dequeue(&mut self, _uuid: u64) -> Result<(), EnumRendererError> {
+  fn enqueue_batch(&mut self, r_assets: &[REntity], shader_associated: &mut Shader) -> Result<(), EnumRendererError> {
+    if r_assets.is_empty() {
+      log!(EnumLogColor::Yellow, "WARN", "[GlContext] -->\t Batch is empty! Not sending it...");
+      return Ok(());
+    }
+    
+    let mut batch_vbo_size: usize = 0;
+    let mut batch_ibo_size: usize = 0;
+    for r_asset in r_assets.iter() {
+      batch_vbo_size += r_asset.get_size() * r_asset.get_total_vertex_count();
+      batch_ibo_size += size_of::<u32>() * r_asset.get_total_index_count();
+    }
+    
+    // Reserve space for the whole batch upfront, to avoid expanding (and migrating) the buffers once per entity.
+    let previous_buffers = self.m_commands.m_draw_commands.iter()
+      .rfind(|command| command.m_linked_shader == shader_associated.get_id())
+      .map(|command| (command.m_vbo_index, command.m_ibo_index));
+    
+    match previous_buffers {
+      None => self.alloc_buffers(&r_assets[0], batch_vbo_size, batch_ibo_size, shader_associated)?,
+      Some((vbo_index, ibo_index)) => self.reserve_buffers(vbo_index, ibo_index, batch_vbo_size, batch_ibo_size)?
+    }
+    
+    for r_asset in r_assets.iter() {
+      self.enqueue(r_asset, shader_associated)?;
+    }
+    
+    log!(EnumLogColor::Green, "INFO", "[GlContext] -->\t Enqueued batch of {0} entities ({1} bytes of vertices, {2} bytes of indices)",
+      r_assets.len(), batch_vbo_size, batch_ibo_size);
     return Ok(());
   }
   
-  fn update_ubo_camera(&mut self, view: Mat4, projection: Mat4) -> Result<(), EnumRendererError> {
-    let ubo_camera_index_found = self.m_ubo_buffers.iter_mut()
-      .position(|ubo| ubo.get_name() == Some("ubo_camera"));
+  fn get_buffer_allocation_count(&self) -> usize {
+    return self.m_buffer_allocations;
+  }
+  
+  fn enqueue_instanced(&mut self, r_asset: &REntity, shader_associated: &mut Shader, transforms: &[Mat4]) -> Result<(), EnumRendererError> {
+    if r_asset.is_empty() || transforms.is_empty() {
+      log!(EnumLogColor::Yellow, "WARN", "[GlContext] -->\t Entity [{0}] has no vertices or no instances! Not \
+      sending it...", r_asset);
+      return Ok(());
+    }
     
-    if ubo_camera_index_found.is_none() {
-      log!(EnumLogColor::Red, "ERROR", "[GlContext] -->\t Cannot update camera ubo, 'ubo_camera' not found in batch!");
+    let instance_matrix_location = match shader_associated.get_instance_matrix_location() {
+      Some(location) => location,
+      None => {
+        log!(EnumLogColor::Red, "ERROR", "[GlContext] -->\t Cannot enqueue instanced entity [{0}], shader {1} does \
+        not declare an instance matrix attribute location!", r_asset, shader_associated.get_id());
+        return Err(renderer::EnumRendererError::from(EnumOpenGLError::MissingInstanceAttribute));
+      }
+    };
+    
+    // Re-enqueuing the same entity replaces its previous instances.
+    if let Some(position) = self.m_instanced_commands.iter().position(|command| command.m_uuid == r_asset.get_uuid()) {
+      self.m_instanced_commands.remove(position).free()?;
+    }
+    
+    let vertex_count = r_asset.get_total_vertex_count();
+    let index_count = r_asset.get_total_index_count();
+    
+    let mut vao = GlVao::new()?;
+    let mut vbo = GlVbo::new(gl::ARRAY_BUFFER, r_asset.get_size() * vertex_count)?;
+    let mut ibo = (index_count > 0).then(|| GlIbo::new(size_of::<u32>() * index_count)).transpose()?;
+    
+    // Pack all sub meshes in a single draw, offsetting indices accordingly.
+    let mut base_vertex: u32 = 0;
+    for sub_mesh in r_asset.m_sub_meshes.iter() {
+      vbo.push(sub_mesh.get_vertices_ref())?;
+      if let Some(ibo) = ibo.as_mut() {
+        let indices_offset = sub_mesh.get_indices().iter()
+          .map(|index| *index + base_vertex)
+          .collect::<Vec<u32>>();
+        ibo.push(&indices_offset)?;
+      }
+      base_vertex += sub_mesh.get_vertices_ref().len() as u32;
+    }
+    
+    vao.bind()?;
+    vbo.bind()?;
+    Self::set_attributes(&r_asset.m_type, &mut vao)?;
+    
+    let instance_matrices = transforms.iter()
+      .map(|transform| transform.transpose().as_array())
+      .collect::<Vec<[f32; 16]>>();
+    let mut instance_vbo = GlVbo::new(gl::ARRAY_BUFFER, size_of::<Mat4>() * transforms.len())?;
+    instance_vbo.push(&instance_matrices)?;
+    vao.enable_instance_matrix(instance_matrix_location, &mut instance_vbo)?;
+    
+    if let Some(ibo) = ibo.as_mut() {
+      ibo.bind()?;
+    }
+    vao.unbind()?;
+    self.m_buffer_allocations += 2 + ibo.is_some() as usize;
+    
+    self.m_instanced_commands.push(GlInstancedCommandInfo {
+      m_uuid: r_asset.get_uuid(),
+      m_linked_shader: shader_associated.get_id(),
+      m_vao: vao,
+      m_vbo: vbo,
+      m_ibo: ibo,
+      m_instance_vbo: instance_vbo,
+      m_vertex_count: vertex_count as GLsizei,
+      m_index_count: index_count as GLsizei,
+      m_instance_count: transforms.len() as GLsizei,
+      m_receive_shadows: r_asset.receives_shadows(),
+    });
+    self.alloc_camera_ubo(shader_associated)?;
+    
+    log!(EnumLogColor::Yellow, "INFO", "[GlContext] -->\t Enqueued {0} instances of entity [{1}]", transforms.len(),
+      r_asset);
+    return Ok(());
+  }
+  
+  fn relink_shader(&mut self, old_shader_id: u32, new_shader_id: u32) {
+    for command in self.m_commands.m_draw_commands.iter_mut()
+      .filter(|command| command.m_linked_shader == old_shader_id) {
+      command.m_linked_shader = new_shader_id;
+    }
+    for instanced_command in self.m_instanced_commands.iter_mut()
+      .filter(|command| command.m_linked_shader == old_shader_id) {
+      instanced_command.m_linked_shader = new_shader_id;
+    }
+  }
+  
+  fn get_instance_buffer_size(&self, entity_uuid: u64) -> Option<usize> {
+    return self.m_instanced_commands.iter()
+      .find(|command| command.m_uuid == entity_uuid)
+      .map(|command| command.m_instance_vbo.m_length);
+  }
+  
+  fn update_instance_transforms(&mut self, entity_uuid: u64, first_instance: usize, transforms: &[Mat4]) -> Result<(), EnumRendererError> {
+    let Some(command) = self.m_instanced_commands.iter_mut().find(|command| command.m_uuid == entity_uuid) else {
+      log!(EnumLogColor::Red, "ERROR", "[GlContext] -->\t Cannot update instances of entity {0}, entity not found!",
+        entity_uuid);
+      return Err(EnumRendererError::EntityNotFound);
+    };
+    
+    // Same layout as when enqueuing, one transposed matrix per instance.
+    let instance_matrices = transforms.iter()
+      .map(|transform| transform.transpose().as_array())
+      .collect::<Vec<[f32; 16]>>();
+    command.m_instance_vbo.write_at(first_instance * size_of::<Mat4>(), &instance_matrices)?;
+    return Ok(());
+  }
+  
+  fn capture_frame(&mut self, width: u32, height: u32, presented: bool) -> Result<Vec<u8>, EnumRendererError> {
+    if width == 0 || height == 0 {
+      log!(EnumLogColor::Red, "ERROR", "[GlContext] -->\t Cannot capture frame of {0}x{1}!", width, height);
+      return Err(EnumRendererError::ScreenshotError);
+    }
+    
+    // Once presented, the frame is in the front buffer, and the back buffer's content is undefined.
+    let read_buffer = if presented { gl::FRONT } else { gl::BACK };
+    return Ok(GlFramebuffer::read_pixels(0, read_buffer, width, height)?);
+  }
+  
+  fn toggle_msaa(&mut self, sample_count: Option<u32>) -> Result<Option<u32>, EnumRendererError> {
+    // Validate before touching the current framebuffer, to keep it intact on failure.
+    let mut effective_sample_count = sample_count;
+    if let Some(samples) = sample_count {
+      let max_sample_count = self.m_capabilities.m_max_samples;
+      if samples < 2 || !samples.is_power_of_two() || max_sample_count < 2 {
+        log!(EnumLogColor::Red, "ERROR", "[GlContext] -->\t Cannot enable MSAA with X{0}! Supported sample counts \
+        are powers of two between 2 and {1}", samples, max_sample_count);
+        return Err(renderer::EnumRendererError::from(EnumOpenGLError::MSAAError));
+      }
+      
+      if samples > max_sample_count {
+        log!(EnumLogColor::Yellow, "WARN", "[GlContext] -->\t Cannot enable MSAA with X{0}! Clamping to the maximum \
+        supported (X{1})...", samples, max_sample_count);
+        effective_sample_count = Some(max_sample_count);
+      }
+    }
+    
+    if let Some(mut msaa_framebuffer) = self.m_msaa_framebuffer.take() {
+      msaa_framebuffer.free()?;
+    }
+    
+    match effective_sample_count {
+      None => {
+        check_gl_call!("GlContext", gl::Disable(gl::MULTISAMPLE));
+        log!("INFO", "[GlContext] -->\t MSAA disabled");
+      }
+      Some(samples) => {
+        let (width, height) = Engine::get_active_window().get_framebuffer_size();
+        self.m_msaa_framebuffer = Some(GlFramebuffer::new(width, height, samples)?);
+        check_gl_call!("GlContext", gl::Enable(gl::MULTISAMPLE));
+        log!("INFO", "[GlContext] -->\t MSAA enabled (X{0})", samples);
+      }
+    }
+    return Ok(effective_sample_count);
+  }
+  
+  fn dequeue(&mut self, uuid: u64) -> Result<(), EnumRendererError> {
+    // Instanced entities own their buffers, free them right away.
+    if let Some(position) = self.m_instanced_commands.iter().position(|command| command.m_uuid == uuid) {
+      self.m_instanced_commands.remove(position).free()?;
+      log!(EnumLogColor::Yellow, "INFO", "[GlContext] -->\t Dequeued instanced entity {0}", uuid);
+    }
+    self.m_bone_poses.remove(&uuid);
+    return Ok(());
+  }
+  
+  fn update_ubo_camera(&mut self, view: Mat4, projection: Mat4) -> Result<(), EnumRendererError> {
+    let Some(camera_ubo) = self.m_camera_ubo.as_mut() else {
+      log!(EnumLogColor::Red, "ERROR", "[GlContext] -->\t Cannot update camera ubo, 'ubo_camera' not allocated yet!");
       return Err(EnumRendererError::UboNotFound);
+    };
+    
+    camera_ubo.upload(&[view, projection][..])?;
+    
+    // Keep the camera around to cull primitives outside its frustum and to sort transparent ones by distance.
+    self.m_view_matrix = view;
+    self.m_projection_matrix = Some(projection);
+    return Ok(());
+  }
+  
+  fn toggle_shadow_casting(&mut self, entity_uuid: u64, instance_offset: Option<usize>, instance_count: usize, cast_shadows: bool) -> Result<(), EnumRendererError> {
+    let entity_offsets = (entity_uuid as usize + instance_offset.unwrap_or(0))..(entity_uuid as usize + instance_count);
+    let mut found = false;
+    for command in self.m_commands.m_draw_commands.iter_mut() {
+      for primitive in command.m_primitives.iter_mut()
+        .filter(|primitive| entity_offsets.contains(&primitive.m_entity_offset)) {
+        primitive.m_cast_shadows = cast_shadows;
+        found = true;
+      }
+    }
+    
+    if !found {
+      log!(EnumLogColor::Red, "ERROR", "[GlContext] -->\t Cannot toggle shadow casting of entity {0}, entity not found!", entity_uuid);
+      return Err(EnumRendererError::EntityNotFound);
     }
+    return Ok(());
+  }
+  
+  fn toggle_shadow_receiving(&mut self, entity_uuid: u64, instance_offset: Option<usize>, instance_count: usize, receive_shadows: bool) -> Result<(), EnumRendererError> {
+    let entity_offsets = (entity_uuid as usize + instance_offset.unwrap_or(0))..(entity_uuid as usize + instance_count);
+    let mut found = false;
+    for command in self.m_commands.m_draw_commands.iter_mut() {
+      for primitive in command.m_primitives.iter_mut()
+        .filter(|primitive| entity_offsets.contains(&primitive.m_entity_offset)) {
+        primitive.m_receive_shadows = receive_shadows;
+        found = true;
+      }
+    }
+    
+    if !found {
+      log!(EnumLogColor::Red, "ERROR", "[GlContext] -->\t Cannot toggle shadow receiving of entity {0}, entity not found!", entity_uuid);
+      return Err(EnumRendererError::EntityNotFound);
+    }
+    return Ok(());
+  }
+  
+  fn render_shadow_depth(&mut self, light_view: Mat4, light_projection: Mat4, shadow_map: &RenderTarget) -> Result<(), EnumRendererError> {
+    if self.m_state != EnumRendererState::Submitted {
+      return Ok(());
+    }
+    let Some(depth_texture_id) = shadow_map.get_depth_attachment_id() else {
+      log!(EnumLogColor::Red, "ERROR", "[GlContext] -->\t Cannot render shadow pass, shadow map has no depth attachment!");
+      return Err(EnumRendererError::IncompleteFramebuffer);
+    };
+    
+    // Render from the light's point of view, then put the camera back. Without any camera submitted yet, put back
+    // whatever the camera block held instead.
+    let camera_matrices = self.m_projection_matrix.map(|projection| (self.m_view_matrix, projection));
+    let previous_view = self.m_view_matrix;
+    let previous_camera_data = match camera_matrices {
+      Some(_) => None,
+      None => self.m_camera_ubo.as_ref()
+        .map(|camera_ubo| camera_ubo.read(0, Mat4::get_size() * 2))
+        .transpose()?
+    };
+    self.update_ubo_camera(light_view, light_projection)?;
+    
+    // The main program still samples the shadow map, which cannot be read while being written to.
+    check_gl_call!("GlContext", gl::BindTextureUnit(C_SHADOW_MAP_TEXTURE_UNIT, 0));
+    shadow_map.bind()?;
+    check_gl_call!("GlContext", gl::ColorMask(gl::FALSE, gl::FALSE, gl::FALSE, gl::FALSE));
+    // The shadow map is only made of depth, regardless of the depth state of the main pass.
+    check_gl_call!("GlContext", gl::DepthMask(gl::TRUE));
+    check_gl_call!("GlContext", gl::DepthFunc(gl::LESS));
+    check_gl_call!("GlContext", gl::Clear(gl::DEPTH_BUFFER_BIT));
+    // Push depth values back slightly to avoid self-shadowing artifacts (shadow acne).
+    check_gl_call!("GlContext", gl::Enable(gl::POLYGON_OFFSET_FILL));
+    check_gl_call!("GlContext", gl::PolygonOffset(2.0, 4.0));
+    
+    // Batched vaos keep track of their bound state, and the main pass expects to bind them itself.
+    for vao in self.m_vao_buffers.iter_mut() {
+      vao.unbind()?;
+    }
+    
+    let mut current_bone_pose: Option<u64> = None;
+    for draw_command in self.m_commands.m_draw_commands.iter() {
+      check_gl_call!("GlContext", gl::UseProgram(draw_command.m_linked_shader));
+      self.m_vao_buffers[draw_command.m_vao_index].bind()?;
+      if !self.m_ibo_buffers.is_empty() {
+        self.m_ibo_buffers[draw_command.m_ibo_index].bind()?;
+      }
+      
+      // Only draw runs of opaque shadow casters, transparent ones let light through.
+      let mut run_start: usize = 0;
+      while run_start < draw_command.m_primitives.len() {
+        let casts = draw_command.m_primitives[run_start].m_cast_shadows && !draw_command.m_primitives[run_start].m_transparent;
+        let bone_pose = self.get_bone_pose(&draw_command.m_primitives[run_start]);
+        let run_end = draw_command.m_primitives[run_start..].iter()
+          .position(|primitive| (primitive.m_cast_shadows && !primitive.m_transparent) != casts ||
+            self.get_bone_pose(primitive) != bone_pose)
+          .map(|run_length| run_start + run_length)
+          .unwrap_or(draw_command.m_primitives.len());
+        
+        if casts {
+          Self::push_bone_pose(&mut self.m_ubo_buffers, &self.m_bone_poses, bone_pose, &mut current_bone_pose)?;
+          self.draw_primitives(draw_command, run_start, run_end - run_start)?;
+        }
+        run_start = run_end;
+      }
+      self.m_vao_buffers[draw_command.m_vao_index].unbind()?;
+    }
+    
+    check_gl_call!("GlContext", gl::Disable(gl::POLYGON_OFFSET_FILL));
+    check_gl_call!("GlContext", gl::ColorMask(gl::TRUE, gl::TRUE, gl::TRUE, gl::TRUE));
+    self.restore_depth_state()?;
+    shadow_map.unbind()?;
     
-    self.m_ubo_buffers[ubo_camera_index_found.unwrap()].push(EnumUboType::ViewProjection(view, projection))?;
+    if let Some((camera_view, camera_projection)) = camera_matrices {
+      self.update_ubo_camera(camera_view, camera_projection)?;
+    } else {
+      self.m_view_matrix = previous_view;
+      self.m_projection_matrix = None;
+      if let (Some(camera_ubo), Some(camera_data)) = (self.m_camera_ubo.as_mut(), previous_camera_data) {
+        camera_ubo.upload_bytes(&camera_data)?;
+      }
+    }
+    
+    // Expose the result to the main pass.
+    if !self.m_ubo_buffers.iter().any(|ubo| ubo.get_name() == Some("ubo_shadow")) {
+      self.m_ubo_buffers.push(GlUbo::new(Some("ubo_shadow"), EnumUboTypeSize::Shadow, C_SHADOW_UBO_BINDING)?);
+    }
+    let ubo_shadow = self.m_ubo_buffers.iter_mut()
+      .find(|ubo| ubo.get_name() == Some("ubo_shadow"))
+      .unwrap();
+    ubo_shadow.push(EnumUboType::Shadow(light_projection * light_view, true))?;
+    check_gl_call!("GlContext", gl::BindTextureUnit(C_SHADOW_MAP_TEXTURE_UNIT, depth_texture_id));
+    return Ok(());
+  }
+  
+  fn update_ubo_lights(&mut self, light_data: &[u8]) -> Result<(), EnumRendererError> {
+    // Lights are shared by all shaders, allocate their block on first submission.
+    if !self.m_ubo_buffers.iter().any(|ubo| ubo.get_name() == Some("ubo_lights")) {
+      self.m_ubo_buffers.push(GlUbo::new(Some("ubo_lights"), EnumUboTypeSize::Lights, C_LIGHTS_UBO_BINDING)?);
+    }
+    
+    let ubo_lights = self.m_ubo_buffers.iter_mut()
+      .find(|ubo| ubo.get_name() == Some("ubo_lights"))
+      .unwrap();
+    ubo_lights.push(EnumUboType::Lights(light_data.to_vec()))?;
+    return Ok(());
+  }
+  
+  fn update_ubo_bones(&mut self, entity_uuid: u64, bone_matrices: &[Mat4]) -> Result<(), EnumRendererError> {
+    if !self.m_ubo_buffers.iter().any(|ubo| ubo.get_name() == Some("ubo_bones")) {
+      self.m_ubo_buffers.push(GlUbo::new(Some("ubo_bones"), EnumUboTypeSize::Bones, C_BONES_UBO_BINDING)?);
+    }
+    
+    // Only keep the pose around, it gets uploaded right before drawing the entity.
+    self.m_bone_poses.insert(entity_uuid, bone_matrices.to_vec());
     return Ok(());
   }
   
@@ -931,6 +1546,16 @@ impl TraitContext for GlContext {
     for instance_index in instance_offset.unwrap_or(0)..instance_count {
       ubo.push(EnumUboType::Transform(model_transform, entity_uuid as usize + instance_index))?;
     }
+    
+    let entity_offsets = (entity_uuid as usize + instance_offset.unwrap_or(0))..(entity_uuid as usize + instance_count);
+    let position = Vec3::new(&[model_transform[0][3], model_transform[1][3], model_transform[2][3]]);
+    for command in self.m_commands.m_draw_commands.iter_mut() {
+      for primitive in command.m_primitives.iter_mut()
+        .filter(|primitive| entity_offsets.contains(&primitive.m_entity_offset)) {
+        primitive.m_position = position;
+        primitive.m_world_bounds = primitive.m_local_bounds.transform(&model_transform);
+      }
+    }
     return Ok(());
   }
   
@@ -947,11 +1572,24 @@ impl TraitContext for GlContext {
       return Err(renderer::EnumRendererError::from(EnumOpenGLError::InvalidContext));
     }
     
+    if let Some(mut msaa_framebuffer) = self.m_msaa_framebuffer.take() {
+      msaa_framebuffer.free()?;
+    }
+    
     log!(EnumLogColor::Purple, "INFO", "[GlContext] -->\t Freeing buffers...");
+    // Free instanced buffers.
+    for instanced_command in self.m_instanced_commands.iter_mut() {
+      instanced_command.free()?;
+    }
+    self.m_instanced_commands.clear();
+    
     // Free ubos.
     for ubo in self.m_ubo_buffers.iter_mut() {
       ubo.free()?;
     };
+    if let Some(mut camera_ubo) = self.m_camera_ubo.take() {
+      camera_ubo.free()?;
+    }
     
     // Free vaos.
     for vao in self.m_vao_buffers.iter_mut() {
@@ -980,6 +1618,45 @@ impl TraitContext for GlContext {
 }
 
 impl GlContext {
+  fn query_capabilities(extensions: HashSet<String>) -> Result<RendererCapabilities, EnumOpenGLError> {
+    let get_string = |name: GLenum| unsafe {
+      let string_ptr = gl::GetString(name);
+      if string_ptr.is_null() {
+        return String::from("Unknown");
+      }
+      return std::ffi::CStr::from_ptr(string_ptr as *const i8).to_string_lossy().into_owned();
+    };
+    
+    let mut max_texture_units: GLint = 0;
+    let mut max_texture_size: GLint = 0;
+    check_gl_call!("GlContext", gl::GetIntegerv(gl::MAX_COMBINED_TEXTURE_IMAGE_UNITS, &mut max_texture_units));
+    check_gl_call!("GlContext", gl::GetIntegerv(gl::MAX_TEXTURE_SIZE, &mut max_texture_size));
+    
+    let mut max_anisotropy: gl::types::GLfloat = 1.0;
+    if extensions.contains("GL_ARB_texture_filter_anisotropic") || extensions.contains("GL_EXT_texture_filter_anisotropic") {
+      check_gl_call!("GlContext", gl::GetFloatv(C_GL_MAX_TEXTURE_MAX_ANISOTROPY, &mut max_anisotropy));
+    }
+    
+    let mut major_version: GLint = 0;
+    let mut minor_version: GLint = 0;
+    check_gl_call!("GlContext", gl::GetIntegerv(gl::MAJOR_VERSION, &mut major_version));
+    check_gl_call!("GlContext", gl::GetIntegerv(gl::MINOR_VERSION, &mut minor_version));
+    let persistent_mapping = (major_version, minor_version) >= (4, 4) || extensions.contains("GL_ARB_buffer_storage");
+    
+    return Ok(RendererCapabilities {
+      m_vendor: get_string(gl::VENDOR),
+      m_device_name: get_string(gl::RENDERER),
+      m_api_version: get_string(gl::VERSION),
+      m_shading_language_version: get_string(gl::SHADING_LANGUAGE_VERSION),
+      m_max_texture_units: max_texture_units.max(0) as u32,
+      m_max_samples: GlFramebuffer::get_max_samples()?,
+      m_max_texture_size: max_texture_size.max(0) as u32,
+      m_max_anisotropy: max_anisotropy.max(1.0),
+      m_persistent_mapping: persistent_mapping,
+      m_extensions: extensions,
+    });
+  }
+  
   fn load_extensions() -> Result<Vec<String>, EnumOpenGLError> {
     let mut ext_count = 0;
     unsafe { gl::GetIntegerv(gl::NUM_EXTENSIONS, &mut ext_count) };
@@ -1001,6 +1678,200 @@ impl GlContext {
     return Ok(gl_extensions_available);
   }
   
+  /// Flag primitives entirely outside the active camera's frustum, so that they get skipped this frame. Nothing gets
+  /// culled until a camera has been submitted, or while frustum culling is disabled.
+  fn cull_primitives(&mut self, stats: &mut RendererStats) {
+    let frustum = self.m_projection_matrix
+      .filter(|_| self.m_frustum_culling)
+      .map(|projection| Frustum::from_matrix(&(projection * self.m_view_matrix)));
+    
+    for command in self.m_commands.m_draw_commands.iter_mut() {
+      for primitive in command.m_primitives.iter_mut() {
+        primitive.m_culled = frustum.as_ref()
+          .is_some_and(|frustum| !frustum.intersects_aabb(&primitive.m_world_bounds));
+        
+        if primitive.m_culled {
+          stats.m_culled_primitives += 1;
+        }
+      }
+    }
+  }
+  
+  /// Draw transparent primitives one by one from the farthest to the closest to the camera, with blending enabled and
+  /// depth writes disabled, so that they blend correctly with each other and with the opaque primitives behind them.
+  fn draw_transparent_primitives(&mut self, current_polygon_mode: &mut GLenum, current_receive_shadows: &mut Option<bool>,
+                                 current_bone_pose: &mut Option<u64>, #[allow(unused)] stats: &mut RendererStats) -> Result<(), EnumRendererError> {
+    let mut transparent_primitives: Vec<(usize, usize, f32)> = Vec::new();
+    for (command_index, command) in self.m_commands.m_draw_commands.iter().enumerate() {
+      for (primitive_index, primitive) in command.m_primitives.iter().enumerate() {
+        if primitive.m_transparent && !primitive.m_culled {
+          let view_space_matrix = self.m_view_matrix * Mat4::translation_matrix(&primitive.m_position);
+          let view_space_position: Vec3<f32> = Vec3::new(&[view_space_matrix[0][3], view_space_matrix[1][3],
+            view_space_matrix[2][3]]);
+          transparent_primitives.push((command_index, primitive_index, view_space_position.vec_len()));
+        }
+      }
+    }
+    
+    if transparent_primitives.is_empty() {
+      return Ok(());
+    }
+    transparent_primitives.sort_by(|a, b| b.2.partial_cmp(&a.2).unwrap_or(std::cmp::Ordering::Equal));
+    
+    let blending_enabled = unsafe { gl::IsEnabled(gl::BLEND) } == gl::TRUE;
+    if !blending_enabled {
+      check_gl_call!("GlContext", gl::Enable(gl::BLEND));
+    }
+    check_gl_call!("GlContext", gl::DepthMask(gl::FALSE));
+    
+    // Each primitive may blend differently, so restore the global blend function once done.
+    let mut global_source: GLint = 0;
+    let mut global_destination: GLint = 0;
+    check_gl_call!("GlContext", gl::GetIntegerv(gl::BLEND_SRC_RGB, &mut global_source));
+    check_gl_call!("GlContext", gl::GetIntegerv(gl::BLEND_DST_RGB, &mut global_destination));
+    let mut current_blend_mode: Option<EnumRendererBlendMode> = None;
+    
+    // Batched vaos keep track of their bound state, and we are about to jump between them.
+    for vao in self.m_vao_buffers.iter_mut() {
+      vao.unbind()?;
+    }
+    
+    let mut previous_command_index: Option<usize> = None;
+    for (command_index, primitive_index, _distance) in transparent_primitives.into_iter() {
+      let draw_command = &self.m_commands.m_draw_commands[command_index];
+      if previous_command_index != Some(command_index) {
+        check_gl_call!("GlContext", gl::UseProgram(draw_command.m_linked_shader));
+        self.m_vao_buffers[draw_command.m_vao_index].bind()?;
+        if !self.m_ibo_buffers.is_empty() {
+          self.m_ibo_buffers[draw_command.m_ibo_index].bind()?;
+        }
+        previous_command_index = Some(command_index);
+      }
+      
+      let primitive = &draw_command.m_primitives[primitive_index];
+      if primitive.m_polygon_mode != *current_polygon_mode {
+        check_gl_call!("GlContext", gl::PolygonMode(gl::FRONT_AND_BACK, primitive.m_polygon_mode));
+        *current_polygon_mode = primitive.m_polygon_mode;
+      }
+      if current_blend_mode != Some(primitive.m_blend_mode) {
+        let (source, destination) = primitive.m_blend_mode.get_factors()
+          .unwrap_or((EnumRendererBlendingFactor::SrcAlpha, EnumRendererBlendingFactor::OneMinusSrcAlpha));
+        check_gl_call!("GlContext", gl::BlendFunc(GLenum::from(source), GLenum::from(destination)));
+        current_blend_mode = Some(primitive.m_blend_mode);
+      }
+      if *current_receive_shadows != Some(primitive.m_receive_shadows) {
+        Self::push_shadow_receiver(&mut self.m_ubo_buffers, primitive.m_receive_shadows)?;
+        *current_receive_shadows = Some(primitive.m_receive_shadows);
+      }
+      let bone_pose = self.get_bone_pose(primitive);
+      Self::push_bone_pose(&mut self.m_ubo_buffers, &self.m_bone_poses, bone_pose, current_bone_pose)?;
+      self.draw_primitives(draw_command, primitive_index, 1)?;
+      
+      #[cfg(feature = "debug")]
+      {
+        let is_indexed = !self.m_ibo_buffers.is_empty() && !self.m_ibo_buffers[draw_command.m_ibo_index].is_empty();
+        stats.m_vertices += primitive.m_vbo_count as u64;
+        stats.m_triangles += (is_indexed.then(|| primitive.m_ibo_count).unwrap_or(primitive.m_vbo_count) / 3) as u64;
+        stats.m_draw_calls += 1;
+      }
+    }
+    
+    for vao in self.m_vao_buffers.iter_mut() {
+      vao.unbind()?;
+    }
+    
+    self.restore_depth_state()?;
+    check_gl_call!("GlContext", gl::BlendFunc(global_source as GLenum, global_destination as GLenum));
+    if !blending_enabled {
+      check_gl_call!("GlContext", gl::Disable(gl::BLEND));
+    }
+    return Ok(());
+  }
+  
+  /// Tell the shader whether the next draws sample the shadow map. Nothing to do until a shadow pass has been rendered.
+  fn push_shadow_receiver(ubo_buffers: &mut Vec<GlUbo>, receive_shadows: bool) -> Result<(), EnumRendererError> {
+    if let Some(ubo_shadow) = ubo_buffers.iter_mut().find(|ubo| ubo.get_name() == Some("ubo_shadow")) {
+      ubo_shadow.push(EnumUboType::ShadowReceiver(receive_shadows))?;
+    }
+    return Ok(());
+  }
+  
+  /// Uuid of the entity whose pose `primitive` is skinned with, if any. Primitives of different skinned entities cannot
+  /// be drawn together, since the bones block only holds one pose at a time.
+  fn get_bone_pose(&self, primitive: &GlPrimitiveInfo) -> Option<u64> {
+    return self.m_bone_poses.contains_key(&primitive.m_uuid).then_some(primitive.m_uuid);
+  }
+  
+  /// Upload the pose of the skinned entity `bone_pose` to the bones block, unless it is the one already there.
+  fn push_bone_pose(ubo_buffers: &mut Vec<GlUbo>, bone_poses: &HashMap<u64, Vec<Mat4>>, bone_pose: Option<u64>,
+                    current_bone_pose: &mut Option<u64>) -> Result<(), EnumRendererError> {
+    let Some(entity_uuid) = bone_pose.filter(|uuid| *current_bone_pose != Some(*uuid)) else {
+      return Ok(());
+    };
+    if let Some(ubo_bones) = ubo_buffers.iter_mut().find(|ubo| ubo.get_name() == Some("ubo_bones")) {
+      ubo_bones.push(EnumUboType::Bones(bone_poses[&entity_uuid].clone()))?;
+      *current_bone_pose = bone_pose;
+    }
+    return Ok(());
+  }
+  
+  /// Put back the depth function and depth mask set through the renderer hints, after a pass overriding them.
+  fn restore_depth_state(&self) -> Result<(), EnumRendererError> {
+    check_gl_call!("GlContext", gl::DepthFunc(GLenum::from(self.m_depth_func)));
+    check_gl_call!("GlContext", gl::DepthMask(self.m_depth_write.then_some(gl::TRUE).unwrap_or(gl::FALSE)));
+    return Ok(());
+  }
+  
+  fn draw_primitives(&self, draw_command: &GlDrawCommandInfo, first_primitive: usize, primitive_count: usize) -> Result<(), EnumRendererError> {
+    let new_draw: EnumGlDrawCommandFunction;
+    
+    if self.m_ibo_buffers.is_empty() || self.m_ibo_buffers[draw_command.m_ibo_index].is_empty() {
+      if self.m_version >= 430 && self.m_batch_mode == EnumRendererOptimizationMode::MinimizeDrawCalls {
+        // Be careful to only load indirect structs from GPU instead of from client-side, since that requires compatibility profile.
+        new_draw = EnumGlDrawCommandFunction::MultiDrawArraysIndirect(EnumGlPrimitiveMode::Triangle,
+          (first_primitive * size_of::<GlDrawArraysIndirectCommand>()) as *const _,
+          primitive_count as GLsizei,
+          0);
+        return new_draw.draw();
+      }
+      new_draw = EnumGlDrawCommandFunction::MultiDrawArrays(EnumGlPrimitiveMode::Triangle,
+        self.m_commands.m_draw_command_vertex_count_array.as_ptr().wrapping_add(first_primitive) as *const GLsizei,
+        self.m_commands.m_draw_command_vertex_offset_array.as_ptr().wrapping_add(first_primitive) as *const GLsizei,
+        primitive_count as GLsizei);
+      return new_draw.draw();
+    }
+    
+    let is_whole_command = first_primitive == 0 && primitive_count == draw_command.m_primitives.len();
+    if self.m_batch_mode == EnumRendererOptimizationMode::MinimizeDrawCalls {
+      if self.m_version >= 430 {
+        // Be careful to only load indirect structs from GPU instead of from client-side, since that requires compatibility profile.
+        new_draw = EnumGlDrawCommandFunction::MultiDrawElementsIndirect(EnumGlPrimitiveMode::Triangle,
+          EnumGlElementType::UnsignedInt,
+          (first_primitive * size_of::<GlDrawElementsIndirectCommand>()) as *const _,
+          primitive_count as GLsizei,
+          0);
+        return new_draw.draw();
+      }
+      
+      // A single draw of the whole ibo is only possible if the entire batch shares the same polygon mode.
+      if is_whole_command {
+        new_draw = EnumGlDrawCommandFunction::DrawElements(EnumGlPrimitiveMode::Triangle,
+          self.m_ibo_buffers[draw_command.m_ibo_index].m_count as i32,
+          EnumGlElementType::UnsignedInt,
+          std::ptr::null() as *const _);
+        return new_draw.draw();
+      }
+    }
+    
+    new_draw = EnumGlDrawCommandFunction::MultiDrawElementsBaseVertex(EnumGlPrimitiveMode::Triangle,
+      self.m_commands.m_draw_command_index_count_array.as_ptr().wrapping_add(first_primitive) as *const GLsizei,
+      EnumGlElementType::UnsignedInt,
+      self.m_commands.m_draw_command_index_offset_array.as_ptr().wrapping_add(first_primitive) as *const *const GLvoid,
+      primitive_count as GLsizei,
+      self.m_commands.m_draw_command_base_indices.as_ptr().wrapping_add(first_primitive) as *mut GLint);
+    return new_draw.draw();
+  }
+  
   fn toggle_solid_wireframe(&mut self, value: bool, entity_uuid: u64, instance_offset: Option<usize>, instance_count: usize) -> Result<(), EnumRendererError> {
     // Find ubo.
     let wireframe_ubo_found = self.m_ubo_buffers.iter_mut()
@@ -1019,13 +1890,31 @@ impl GlContext {
     return Ok(());
   }
   
-  fn alloc_buffers(&mut self, sendable_entity: &REntity, shader: &mut Shader) -> Result<(), EnumOpenGLError> {
+  fn alloc_camera_ubo(&mut self, shader_associated: &Shader) -> Result<(), EnumRendererError> {
+    // Every shader shares the same camera block, skip if it's already there.
+    if self.m_camera_ubo.is_none() {
+      let mut camera_ubo = UniformBuffer::for_block("ubo_camera", C_CAMERA_UBO_BINDING)?;
+      camera_ubo.upload_bytes(&vec![0; Mat4::get_size() * 2])?;
+      
+      // If glsl version is lower than 420, then we cannot bind blocks in shaders and have to encode them here instead.
+      if shader_associated.get_version() < 420 {
+        camera_ubo.bind_block(shader_associated.get_id())?;
+      }
+      self.m_camera_ubo = Some(camera_ubo);
+    }
+    return Ok(());
+  }
+  
+  fn alloc_buffers(&mut self, sendable_entity: &REntity, vbo_capacity: usize, ibo_capacity: usize,
+                   shader: &mut Shader) -> Result<(), EnumOpenGLError> {
     let mut new_vao = GlVao::new()?;
-    let new_vbo = GlVbo::new(gl::ARRAY_BUFFER, sendable_entity.get_size() * sendable_entity.get_total_vertex_count())?;
+    let new_vbo = GlVbo::new(gl::ARRAY_BUFFER, vbo_capacity)?;
+    self.m_buffer_allocations += 1;
     
-    if sendable_entity.get_total_index_count() > 0 {
-      let new_ibo = GlIbo::new(size_of::<u32>() * sendable_entity.get_total_index_count())?;
+    if ibo_capacity > 0 {
+      let new_ibo = GlIbo::new(ibo_capacity)?;
       self.m_ibo_buffers.push(new_ibo);
+      self.m_buffer_allocations += 1;
     }
     
     Self::set_attributes(&sendable_entity.m_type, &mut new_vao)?;
@@ -1045,10 +1934,40 @@ impl GlContext {
     return Ok(());
   }
   
+  fn reserve_buffers(&mut self, vbo_index: usize, ibo_index: usize, vbo_size: usize, ibo_size: usize) -> Result<(), EnumOpenGLError> {
+    if let Some(vbo) = self.m_vbo_buffers.get_mut(vbo_index) {
+      let vbo_available = vbo.m_capacity - vbo.m_length;
+      if vbo_size > vbo_available {
+        vbo.expand(vbo_size - vbo_available)?;
+        self.m_buffer_allocations += 1;
+      }
+    }
+    
+    if let Some(ibo) = self.m_ibo_buffers.get_mut(ibo_index) {
+      let ibo_available = ibo.m_capacity - ibo.m_length;
+      if ibo_size > ibo_available {
+        ibo.expand(ibo_size - ibo_available)?;
+        self.m_buffer_allocations += 1;
+      }
+    }
+    return Ok(());
+  }
+  
   fn push_buffers(&mut self, new_primitive: &GlPrimitiveInfo, vao_index: usize, vbo_index: usize, ibo_index: usize,
                   primitive: &Box<dyn TraitPrimitive>, transform_matrix: Mat4) -> Result<(), EnumOpenGLError> {
+    let vbo_capacity = self.m_vbo_buffers.get(vbo_index).map(|vbo| vbo.m_capacity);
+    let ibo_capacity = self.m_ibo_buffers.get(ibo_index).map(|ibo| ibo.m_capacity);
+    
     self.push_data(new_primitive, vbo_index, ibo_index, primitive)?;
     
+    // Keep track of buffers expanded on the fly, since each expansion reallocates a whole new buffer.
+    if self.m_vbo_buffers.get(vbo_index).map(|vbo| vbo.m_capacity) != vbo_capacity {
+      self.m_buffer_allocations += 1;
+    }
+    if self.m_ibo_buffers.get(ibo_index).map(|ibo| ibo.m_capacity) != ibo_capacity {
+      self.m_buffer_allocations += 1;
+    }
+    
     // If we had to reallocate our vbo to append more data to it, thus migrating over to a new buffer
     // and as a result, leaving our old vbo id that linked to the vao attrib array binding behind.
     // It is important to 'rebind' the vao's attrib buffer binding by re-enabling vertex attributes.
@@ -1184,10 +2103,37 @@ impl GlContext {
   
   fn set_attributes(entity_shading_type: &EnumPrimitiveShading, vao: &mut GlVao) -> Result<(), EnumOpenGLError> {
     // Establish vao attributes.
-    let mut attributes: Vec<GlVertexAttribute> = Vec::with_capacity(5);
+    let mut attributes: Vec<GlVertexAttribute> = Vec::with_capacity(9);
     let size;
     
     match entity_shading_type {
+      EnumPrimitiveShading::Skinned => {
+        size = size_of::<Vertex>();
+        
+        // Same layout as smooth shaded meshes, see below.
+        attributes.push(GlVertexAttribute::new(EnumAttributeType::UnsignedInt(1), false,
+          EnumVertexMemberOffset::EntityIDOffset as usize, 0)?);
+        attributes.push(GlVertexAttribute::new(EnumAttributeType::Int(1), false,
+          EnumVertexMemberOffset::TextureInfoOffset as usize, 0)?);
+        attributes.push(GlVertexAttribute::new(EnumAttributeType::Vec3, false,
+          EnumVertexMemberOffset::PositionOffset as usize, 0)?);
+        attributes.push(GlVertexAttribute::new(EnumAttributeType::UnsignedInt(1), false,
+          EnumVertexMemberOffset::NormalOffset as usize, 0)?);
+        attributes.push(GlVertexAttribute::new(EnumAttributeType::UnsignedInt(1), false,
+          EnumVertexMemberOffset::ColorOffset as usize, 0)?);
+        attributes.push(GlVertexAttribute::new(EnumAttributeType::Vec2, false,
+          EnumVertexMemberOffset::TexCoordsOffset as usize, 0)?);
+        attributes.push(GlVertexAttribute::new(EnumAttributeType::Vec3, false,
+          EnumVertexMemberOffset::TangentOffset as usize, 0)?);
+        
+        // Bone ids, packed one per byte.
+        attributes.push(GlVertexAttribute::new(EnumAttributeType::UnsignedInt(1), false,
+          EnumVertexMemberOffset::BoneIdsOffset as usize, 0)?);
+        
+        // Bone weights.
+        attributes.push(GlVertexAttribute::new(EnumAttributeType::Vec4, false,
+          EnumVertexMemberOffset::BoneWeightsOffset as usize, 0)?);
+      }
       EnumPrimitiveShading::Mesh(material) => {
         size = size_of::<Vertex>();
         
@@ -1219,6 +2165,10 @@ impl GlContext {
         // Texture coordinates.
         attributes.push(GlVertexAttribute::new(EnumAttributeType::Vec2, false,
           EnumVertexMemberOffset::TexCoordsOffset as usize, 0)?);
+        
+        // Tangents.
+        attributes.push(GlVertexAttribute::new(EnumAttributeType::Vec3, false,
+          EnumVertexMemberOffset::TangentOffset as usize, 0)?);
       }
       _ => todo!()
     };
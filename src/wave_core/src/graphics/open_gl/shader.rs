@@ -25,19 +25,30 @@
 use std::any::Any;
 use std::collections::{HashMap, HashSet};
 
-use gl::types::{GLenum, GLsizei};
+use gl::types::{GLenum, GLintptr, GLsizei, GLsizeiptr};
 
+use crate::assets::skeleton::C_BONES_UBO_BINDING;
+use crate::camera::C_CAMERA_UBO_BINDING;
 use crate::check_gl_call;
 #[cfg(feature = "debug")]
 use crate::Engine;
+use crate::graphics::light::{C_LIGHTS_UBO_BINDING, C_SHADOW_UBO_BINDING};
 use crate::graphics::open_gl::buffer::{GLboolean, GLchar, GLfloat, GLint, GLuint};
 use crate::graphics::open_gl::renderer::S_GL_4_6;
 use crate::graphics::renderer::{EnumRendererApi};
-use crate::graphics::shader::{self, EnumShaderSource, EnumShaderStageType, ShaderStage, TraitShader};
+use crate::graphics::shader::{self, EnumShaderSource, EnumShaderStageType, EnumUniformType, ShaderStage, TraitShader};
 use crate::math::Mat4;
 use crate::S_ENGINE;
 use crate::utils::macros::logger::*;
 
+// Bindings of the blocks filled by the renderer itself (camera, models, wireframes, lights, shadows and bones).
+const C_RESERVED_UBO_BINDINGS: [u32; 6] = [C_CAMERA_UBO_BINDING, 1, 9, C_LIGHTS_UBO_BINDING, C_SHADOW_UBO_BINDING,
+  C_BONES_UBO_BINDING];
+
+// Buffers created with [GlShader::create_uniform_block], shared by every shader creating a block at the same binding :
+// (binding, buffer id, size, number of shaders using it).
+static mut S_UNIFORM_BLOCKS: Vec<(u32, GLuint, usize, usize)> = Vec::new();
+
 /*
 ///////////////////////////////////   OpenGL shader    ///////////////////////////////////
 ///////////////////////////////////                    ///////////////////////////////////
@@ -59,6 +70,11 @@ pub enum EnumError {
   NoBinaryFormatsError,
   UnsupportedUniformType,
   UniformNotFound,
+  UniformBlockNotFound,
+  InvalidUniformBlockRange,
+  ReservedUniformBlockBinding,
+  StorageBufferNotFound,
+  InvalidStorageBufferRange,
   OpenGLApiError,
 }
 
@@ -68,6 +84,9 @@ pub struct GlShader {
   m_shader_ids: HashMap<EnumShaderStageType, GLuint>,
   m_shader_stages: HashSet<ShaderStage>,
   m_uniform_cache: HashMap<&'static str, GLint>,
+  // Bindings of the shared uniform blocks created by this shader.
+  m_uniform_blocks: HashSet<u32>,
+  m_storage_buffers: HashMap<u32, (GLuint, usize)>,
 }
 
 impl TraitShader for GlShader {
@@ -77,6 +96,8 @@ impl TraitShader for GlShader {
       m_shader_ids: HashMap::with_capacity(shader_stages.len()),
       m_shader_stages: HashSet::from_iter(shader_stages.into_iter()),
       m_uniform_cache: Default::default(),
+      m_uniform_blocks: Default::default(),
+      m_storage_buffers: Default::default(),
     };
   }
   
@@ -146,41 +167,22 @@ impl TraitShader for GlShader {
       
       unsafe { gl::GetShaderiv(*shader_id, gl::COMPILE_STATUS, &mut compiled_successfully) };
       if compiled_successfully as GLboolean == gl::FALSE {
-        #[allow(unused)]
-          let _shader_type_str: String;
-        // For debug purposes.
-        match shader_stage.m_stage {
-          EnumShaderStageType::Vertex => {
-            _shader_type_str = "vertex shader".to_string();
-          }
-          EnumShaderStageType::Fragment => {
-            _shader_type_str = "fragment shader".to_string();
-          }
-          EnumShaderStageType::Geometry => {
-            _shader_type_str = "geometry shader".to_string();
-          }
-          EnumShaderStageType::Compute => {
-            _shader_type_str = "compute shader".to_string();
-          }
-        }
-        
         // Get info length.
         unsafe {
           gl::GetShaderiv(*shader_id, gl::INFO_LOG_LENGTH,
             &mut buffer_length as *mut _)
         };
-        let mut buffer: Vec<u8> = Vec::with_capacity(buffer_length as usize);
+        let mut buffer: Vec<u8> = vec![0; buffer_length.max(1) as usize];
         
         unsafe {
           gl::GetShaderInfoLog(*shader_id, buffer_length, &mut buffer_length, buffer.as_mut_ptr().cast())
         };
+        buffer.truncate(buffer_length.max(0) as usize);
+        let info_log = String::from_utf8_lossy(&buffer).trim_end().to_string();
         
-        log!(EnumLogColor::Red, "ERROR", "[GlShader] -->\t Error, could not compile {0}!\n \
-        Info => {1}", _shader_type_str, unsafe {
-        std::ffi::CStr::from_ptr(buffer.as_ptr().cast()).to_str()
-          .expect("[GlShader] -->\t Cannot convert shader info log to Rust str in compile!")
-      });
-        return Err(shader::EnumShaderError::from(EnumError::ShaderSyntaxError));
+        log!(EnumLogColor::Red, "ERROR", "[GlShader] -->\t Error, could not compile {0} shader {1}!\n \
+        Info => {2}", shader_stage.m_stage, shader_stage.m_source, info_log);
+        return Err(shader::EnumShaderError::CompileFailed { stage: shader_stage.m_stage, log: info_log });
       }
     }
     
@@ -302,12 +304,191 @@ impl TraitShader for GlShader {
     return self.m_program_id;
   }
   
+  fn get_active_uniforms(&self) -> Vec<(String, EnumUniformType)> {
+    if self.m_program_id == 0 {
+      return Vec::new();
+    }
+    
+    let mut uniform_count: GLint = 0;
+    let mut max_name_length: GLint = 0;
+    unsafe {
+      gl::GetProgramiv(self.m_program_id, gl::ACTIVE_UNIFORMS, &mut uniform_count);
+      gl::GetProgramiv(self.m_program_id, gl::ACTIVE_UNIFORM_MAX_LENGTH, &mut max_name_length);
+    }
+    
+    let mut uniforms = Vec::with_capacity(uniform_count.max(0) as usize);
+    let mut name_buffer: Vec<u8> = vec![0; max_name_length.max(1) as usize];
+    for uniform_index in 0..uniform_count.max(0) as GLuint {
+      // Skip members of uniform blocks, which are set through their buffer instead.
+      let mut block_index: GLint = -1;
+      unsafe { gl::GetActiveUniformsiv(self.m_program_id, 1, &uniform_index, gl::UNIFORM_BLOCK_INDEX, &mut block_index) };
+      if block_index != -1 {
+        continue;
+      }
+      
+      let mut name_length: GLsizei = 0;
+      let mut array_size: GLint = 0;
+      let mut uniform_type: GLenum = 0;
+      unsafe {
+        gl::GetActiveUniform(self.m_program_id, uniform_index, name_buffer.len() as GLsizei, &mut name_length,
+          &mut array_size, &mut uniform_type, name_buffer.as_mut_ptr().cast());
+      }
+      let name = String::from_utf8_lossy(&name_buffer[..name_length.max(0) as usize]).to_string();
+      uniforms.push((name, Self::convert_uniform_type(uniform_type)));
+    }
+    return uniforms;
+  }
+  
   fn get_api_handle(&self) -> &dyn Any {
     return self;
   }
   
+  fn bind_uniform_block(&mut self, block_name: &str, binding: u32) -> Result<(), shader::EnumShaderError> {
+    let Ok(c_str) = std::ffi::CString::new(block_name) else {
+      log!(EnumLogColor::Red, "ERROR", "[GlShader] -->\t Cannot bind uniform block '{0}', invalid block name!",
+        block_name);
+      return Err(shader::EnumShaderError::from(EnumError::UniformBlockNotFound));
+    };
+    
+    check_gl_call!("GlShader", let block_index: GLuint = gl::GetUniformBlockIndex(self.m_program_id, c_str.as_ptr()));
+    if block_index == gl::INVALID_INDEX {
+      log!(EnumLogColor::Red, "ERROR", "[GlShader] -->\t Cannot bind uniform block '{0}', block not found in \
+      program {1}!", block_name, self.m_program_id);
+      return Err(shader::EnumShaderError::from(EnumError::UniformBlockNotFound));
+    }
+    check_gl_call!("GlShader", gl::UniformBlockBinding(self.m_program_id, block_index, binding));
+    return Ok(());
+  }
+  
+  fn create_uniform_block(&mut self, block_name: &str, binding: u32, size: usize) -> Result<(), shader::EnumShaderError> {
+    if C_RESERVED_UBO_BINDINGS.contains(&binding) {
+      log!(EnumLogColor::Red, "ERROR", "[GlShader] -->\t Cannot create uniform block '{0}' at binding {1}, binding \
+      reserved by the renderer!", block_name, binding);
+      return Err(shader::EnumShaderError::from(EnumError::ReservedUniformBlockBinding));
+    }
+    self.bind_uniform_block(block_name, binding)?;
+    
+    match unsafe { S_UNIFORM_BLOCKS.iter_mut().find(|(block_binding, ..)| *block_binding == binding) } {
+      Some((_, _, block_size, _)) if *block_size != size => {
+        log!(EnumLogColor::Red, "ERROR", "[GlShader] -->\t Cannot create uniform block '{0}' of {1} bytes, block \
+        at binding {2} already holds {3} bytes!", block_name, size, binding, block_size);
+        return Err(shader::EnumShaderError::from(EnumError::InvalidUniformBlockRange));
+      }
+      Some((_, buffer_id, _, user_count)) => {
+        if self.m_uniform_blocks.insert(binding) {
+          *user_count += 1;
+        }
+        // Another buffer might have been attached at the same binding since.
+        check_gl_call!("GlShader", gl::BindBufferRange(gl::UNIFORM_BUFFER, binding, *buffer_id, 0, size as GLsizeiptr));
+        log!("INFO", "[GlShader] -->\t Sharing uniform block '{0}' ({1} bytes) at binding {2}", block_name, size,
+          binding);
+      }
+      None => {
+        let mut buffer_id: GLuint = 0;
+        check_gl_call!("GlShader", gl::CreateBuffers(1, &mut buffer_id));
+        check_gl_call!("GlShader", gl::NamedBufferData(buffer_id, size as GLsizeiptr, std::ptr::null(), gl::DYNAMIC_DRAW));
+        check_gl_call!("GlShader", gl::BindBufferRange(gl::UNIFORM_BUFFER, binding, buffer_id, 0, size as GLsizeiptr));
+        unsafe { S_UNIFORM_BLOCKS.push((binding, buffer_id, size, 1)) };
+        self.m_uniform_blocks.insert(binding);
+        
+        log!("INFO", "[GlShader] -->\t Created uniform block '{0}' ({1} bytes) at binding {2}", block_name, size,
+          binding);
+      }
+    }
+    return Ok(());
+  }
+  
+  fn update_uniform_block(&mut self, binding: u32, offset: usize, data: &[u8]) -> Result<(), shader::EnumShaderError> {
+    let shared_block = match self.m_uniform_blocks.contains(&binding) {
+      true => unsafe { S_UNIFORM_BLOCKS.iter().find(|(block_binding, ..)| *block_binding == binding) },
+      false => None,
+    };
+    let (buffer_id, size) = match shared_block {
+      Some((_, buffer_id, size, _)) => (*buffer_id, *size),
+      None => {
+        log!(EnumLogColor::Red, "ERROR", "[GlShader] -->\t Cannot update uniform block, no block created at \
+        binding {0}!", binding);
+        return Err(shader::EnumShaderError::from(EnumError::UniformBlockNotFound));
+      }
+    };
+    
+    if offset + data.len() > size {
+      log!(EnumLogColor::Red, "ERROR", "[GlShader] -->\t Cannot update uniform block at binding {0}, range \
+      [{1}, {2}) exceeds block size of {3} bytes!", binding, offset, offset + data.len(), size);
+      return Err(shader::EnumShaderError::from(EnumError::InvalidUniformBlockRange));
+    }
+    
+    check_gl_call!("GlShader", gl::NamedBufferSubData(buffer_id, offset as GLintptr, data.len() as GLsizeiptr,
+      data.as_ptr() as *const std::ffi::c_void));
+    return Ok(());
+  }
+  
+  fn create_storage_buffer(&mut self, binding: u32, size: usize) -> Result<(), shader::EnumShaderError> {
+    if size == 0 {
+      log!(EnumLogColor::Red, "ERROR", "[GlShader] -->\t Cannot create storage buffer at binding {0}, size is 0!", binding);
+      return Err(shader::EnumShaderError::from(EnumError::InvalidStorageBufferRange));
+    }
+    
+    // Replace any buffer previously bound at the same binding point.
+    if let Some((previous_buffer_id, _)) = self.m_storage_buffers.remove(&binding) {
+      check_gl_call!("GlShader", gl::DeleteBuffers(1, &previous_buffer_id));
+    }
+    
+    let mut buffer_id: GLuint = 0;
+    check_gl_call!("GlShader", gl::CreateBuffers(1, &mut buffer_id));
+    check_gl_call!("GlShader", gl::NamedBufferData(buffer_id, size as GLsizeiptr, std::ptr::null(), gl::DYNAMIC_COPY));
+    check_gl_call!("GlShader", gl::BindBufferBase(gl::SHADER_STORAGE_BUFFER, binding, buffer_id));
+    self.m_storage_buffers.insert(binding, (buffer_id, size));
+    
+    log!("INFO", "[GlShader] -->\t Created storage buffer ({0} bytes) at binding {1}", size, binding);
+    return Ok(());
+  }
+  
+  fn update_storage_buffer(&mut self, binding: u32, offset: usize, data: &[u8]) -> Result<(), shader::EnumShaderError> {
+    let buffer_id: GLuint = self.get_storage_buffer(binding, offset, data.len())?;
+    
+    check_gl_call!("GlShader", gl::NamedBufferSubData(buffer_id, offset as GLintptr, data.len() as GLsizeiptr,
+      data.as_ptr() as *const std::ffi::c_void));
+    return Ok(());
+  }
+  
+  fn read_storage_buffer(&self, binding: u32, offset: usize, size: usize) -> Result<Vec<u8>, shader::EnumShaderError> {
+    let buffer_id: GLuint = self.get_storage_buffer(binding, offset, size)?;
+    
+    let mut data: Vec<u8> = vec![0; size];
+    check_gl_call!("GlShader", gl::GetNamedBufferSubData(buffer_id, offset as GLintptr, size as GLsizeiptr,
+      data.as_mut_ptr() as *mut std::ffi::c_void));
+    return Ok(data);
+  }
+  
+  fn dispatch(&mut self, group_count_x: u32, group_count_y: u32, group_count_z: u32) -> Result<(), shader::EnumShaderError> {
+    check_gl_call!("GlShader", gl::UseProgram(self.m_program_id));
+    check_gl_call!("GlShader", gl::DispatchCompute(group_count_x, group_count_y, group_count_z));
+    return Ok(());
+  }
+  
+  fn memory_barrier(&self, barrier: shader::EnumShaderBarrier) -> Result<(), shader::EnumShaderError> {
+    let barrier_bits: GLenum = match barrier {
+      shader::EnumShaderBarrier::StorageBuffer => gl::SHADER_STORAGE_BARRIER_BIT,
+      shader::EnumShaderBarrier::VertexAttribute => gl::VERTEX_ATTRIB_ARRAY_BARRIER_BIT,
+      shader::EnumShaderBarrier::Command => gl::COMMAND_BARRIER_BIT,
+      shader::EnumShaderBarrier::BufferUpdate => gl::BUFFER_UPDATE_BARRIER_BIT,
+      shader::EnumShaderBarrier::All => gl::ALL_BARRIER_BITS,
+    };
+    check_gl_call!("GlShader", gl::MemoryBarrier(barrier_bits));
+    return Ok(());
+  }
+  
   fn free(&mut self) -> Result<(), shader::EnumShaderError> {
     if gl::UseProgram::is_loaded() {
+      for binding in self.m_uniform_blocks.drain() {
+        GlShader::release_uniform_block(binding)?;
+      }
+      for (buffer_id, _) in self.m_storage_buffers.values() {
+        check_gl_call!("GlShader", gl::DeleteBuffers(1, buffer_id));
+      }
+      self.m_storage_buffers.clear();
+      
       check_gl_call!("GlShader", gl::UseProgram(0));
       check_gl_call!("GlShader", gl::DeleteProgram(self.m_program_id));
     }
@@ -316,6 +497,62 @@ impl TraitShader for GlShader {
 }
 
 impl GlShader {
+  // Stop using the shared uniform block at `binding`, deleting its buffer once no shader uses it anymore.
+  fn release_uniform_block(binding: u32) -> Result<(), shader::EnumShaderError> {
+    let shared_blocks = unsafe { &mut S_UNIFORM_BLOCKS };
+    let Some(block_index) = shared_blocks.iter().position(|(block_binding, ..)| *block_binding == binding) else {
+      return Ok(());
+    };
+    
+    shared_blocks[block_index].3 -= 1;
+    if shared_blocks[block_index].3 == 0 {
+      let (_, buffer_id, _, _) = shared_blocks.swap_remove(block_index);
+      check_gl_call!("GlShader", gl::DeleteBuffers(1, &buffer_id));
+    }
+    return Ok(());
+  }
+  
+  // Buffer id of the storage buffer at `binding`, if the range given fits in it.
+  fn get_storage_buffer(&self, binding: u32, offset: usize, size: usize) -> Result<GLuint, shader::EnumShaderError> {
+    let (buffer_id, buffer_size) = match self.m_storage_buffers.get(&binding) {
+      Some(buffer) => *buffer,
+      None => {
+        log!(EnumLogColor::Red, "ERROR", "[GlShader] -->\t No storage buffer created at binding {0}!", binding);
+        return Err(shader::EnumShaderError::from(EnumError::StorageBufferNotFound));
+      }
+    };
+    
+    if size == 0 || offset + size > buffer_size {
+      log!(EnumLogColor::Red, "ERROR", "[GlShader] -->\t Invalid range [{1}, {2}) for storage buffer at binding {0} \
+      of {3} bytes!", binding, offset, offset + size, buffer_size);
+      return Err(shader::EnumShaderError::from(EnumError::InvalidStorageBufferRange));
+    }
+    return Ok(buffer_id);
+  }
+  
+  fn convert_uniform_type(uniform_type: GLenum) -> EnumUniformType {
+    return match uniform_type {
+      gl::BOOL => EnumUniformType::Bool,
+      gl::INT => EnumUniformType::Int,
+      gl::UNSIGNED_INT => EnumUniformType::UInt,
+      gl::FLOAT => EnumUniformType::Float,
+      gl::DOUBLE => EnumUniformType::Double,
+      gl::FLOAT_VEC2 => EnumUniformType::Vec2,
+      gl::FLOAT_VEC3 => EnumUniformType::Vec3,
+      gl::FLOAT_VEC4 => EnumUniformType::Vec4,
+      gl::INT_VEC2 => EnumUniformType::IVec2,
+      gl::INT_VEC3 => EnumUniformType::IVec3,
+      gl::INT_VEC4 => EnumUniformType::IVec4,
+      gl::FLOAT_MAT3 => EnumUniformType::Mat3,
+      gl::FLOAT_MAT4 => EnumUniformType::Mat4,
+      gl::SAMPLER_2D => EnumUniformType::Sampler2D,
+      gl::SAMPLER_2D_ARRAY => EnumUniformType::Sampler2DArray,
+      gl::SAMPLER_2D_SHADOW => EnumUniformType::Sampler2DShadow,
+      gl::SAMPLER_CUBE => EnumUniformType::SamplerCube,
+      other => EnumUniformType::Other(other),
+    };
+  }
+  
   pub fn bind(&self) -> Result<(), shader::EnumShaderError> {
     check_gl_call!("GlShader", gl::UseProgram(self.m_program_id));
     return Ok(());
@@ -23,20 +23,23 @@
 */
 
 
-use gl::types::{GLint, GLsizei};
+use gl::types::{GLenum, GLfloat, GLint, GLsizei};
 use num::Integer;
 use stb_image::image::Image;
 use crate::check_gl_call;
 use crate::graphics::open_gl::renderer::EnumOpenGLError;
-use crate::graphics::texture::{EnumTextureDataAlignment, EnumTextureFormat, EnumTextureTarget, EnumTextureInfo, TraitTexture};
+use crate::graphics::texture::{EnumTextureDataAlignment, EnumTextureFilter, EnumTextureFormat, EnumTextureTarget, EnumTextureInfo,
+  TraitTexture};
 use crate::utils::macros::logger::*;
-#[cfg(feature = "debug")]
-use crate::Engine;
-use crate::{S_ENGINE};
+use crate::{Engine, S_ENGINE};
 use crate::graphics::renderer::EnumRendererError;
 use crate::utils::texture_loader::TextureInfo;
 
 
+// From GL_ARB_texture_filter_anisotropic, not part of the 4.5 core bindings.
+const C_GL_TEXTURE_MAX_ANISOTROPY: GLenum = 0x84FE;
+pub(crate) const C_GL_MAX_TEXTURE_MAX_ANISOTROPY: GLenum = 0x84FF;
+
 #[derive(Debug, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
 pub enum EnumGlTextureError {
   InvalidInternalFormat,
@@ -121,6 +124,9 @@ impl<T> Default for GlTexture<T> {
           depth: 0,
           data: vec![],
         },
+        m_mipmaps: true,
+        m_filter: EnumTextureFilter::default(),
+        m_anisotropy: None,
       },
       m_level: 0,
       m_ms: None,
@@ -152,6 +158,23 @@ impl<T> GlTexture<T> {
     };
   }
   
+  /// Wrap a 2D texture allocated elsewhere (i.e. a framebuffer attachment), without taking ownership of it.
+  pub(crate) fn from_existing(texture_id: u32, width: u32, height: u32, format: EnumTextureFormat) -> Self {
+    let (gl_format, internal_format) = Self::convert_format_to_internal_format(format);
+    let mut texture = Self::default();
+    
+    texture.m_id = texture_id;
+    texture.m_slot = 0;
+    texture.m_internal_target = gl::TEXTURE_2D;
+    texture.m_format = gl_format;
+    texture.m_internal_format = internal_format;
+    texture.m_texture.m_type = EnumTextureInfo::Texture2D(EnumTextureTarget::Texture2D, 0, format, width, height,
+      EnumTextureDataAlignment::UnsignedByte, 0);
+    texture.m_texture.m_data.width = width as usize;
+    texture.m_texture.m_data.height = height as usize;
+    return texture;
+  }
+  
   fn convert_target_to_internal_target(target: EnumTextureTarget) -> (u32, Option<u32>) {
     return match target {
       EnumTextureTarget::Texture1D => (gl::TEXTURE_1D, None),
@@ -185,6 +208,35 @@ impl<T> GlTexture<T> {
     };
   }
   
+  fn convert_filter_to_internal_filters(filter: EnumTextureFilter, with_mipmaps: bool) -> (u32, u32) {
+    // (Min filter, mag filter), mipmaps only affect minification.
+    return match (filter, with_mipmaps) {
+      (EnumTextureFilter::Nearest, false) => (gl::NEAREST, gl::NEAREST),
+      (EnumTextureFilter::Nearest, true) => (gl::NEAREST_MIPMAP_NEAREST, gl::NEAREST),
+      (EnumTextureFilter::Linear, false) | (EnumTextureFilter::Trilinear, false) => (gl::LINEAR, gl::LINEAR),
+      (EnumTextureFilter::Linear, true) => (gl::LINEAR_MIPMAP_NEAREST, gl::LINEAR),
+      (EnumTextureFilter::Trilinear, true) => (gl::LINEAR_MIPMAP_LINEAR, gl::LINEAR),
+    };
+  }
+  
+  fn set_anisotropy(&self, anisotropy: f32) -> Result<(), EnumOpenGLError> {
+    let capabilities = Engine::get_active_renderer().get_capabilities();
+    if !capabilities.has_extension("GL_ARB_texture_filter_anisotropic") &&
+      !capabilities.has_extension("GL_EXT_texture_filter_anisotropic") {
+      log!(EnumLogColor::Yellow, "WARN", "[GlTexture] -->\t Cannot set anisotropic filtering, extension \
+        'GL_ARB_texture_filter_anisotropic' unsupported! Ignoring...");
+      return Ok(());
+    }
+    
+    let clamped_anisotropy = anisotropy.clamp(1.0, capabilities.m_max_anisotropy);
+    if clamped_anisotropy != anisotropy {
+      log!(EnumLogColor::Yellow, "WARN", "[GlTexture] -->\t Anisotropy level {0} unsupported, clamping to {1}",
+        anisotropy, clamped_anisotropy);
+    }
+    check_gl_call!("GlTexture", gl::TexParameterf(self.m_internal_target, C_GL_TEXTURE_MAX_ANISOTROPY, clamped_anisotropy));
+    return Ok(());
+  }
+  
   fn convert_type_to_internal_type(texture_type: EnumTextureDataAlignment) -> u32 {
     return match texture_type {
       EnumTextureDataAlignment::UnsignedByte => gl::UNSIGNED_BYTE,
@@ -227,6 +279,28 @@ impl<T> TraitTexture for GlTexture<T> {
     todo!()
   }
   
+  fn get_filters(&self) -> Result<(u32, u32), EnumRendererError> {
+    let mut min_filter: GLint = 0;
+    let mut mag_filter: GLint = 0;
+    
+    // Query the driver directly, to report what is actually set rather than what we requested.
+    check_gl_call!("GlTexture", gl::GetTextureParameteriv(self.m_id, gl::TEXTURE_MIN_FILTER, &mut min_filter));
+    check_gl_call!("GlTexture", gl::GetTextureParameteriv(self.m_id, gl::TEXTURE_MAG_FILTER, &mut mag_filter));
+    return Ok((min_filter as u32, mag_filter as u32));
+  }
+  
+  fn get_anisotropy(&self) -> Result<f32, EnumRendererError> {
+    let capabilities = Engine::get_active_renderer().get_capabilities();
+    if !capabilities.has_extension("GL_ARB_texture_filter_anisotropic") &&
+      !capabilities.has_extension("GL_EXT_texture_filter_anisotropic") {
+      return Ok(1.0);
+    }
+    
+    let mut anisotropy: GLfloat = 1.0;
+    check_gl_call!("GlTexture", gl::GetTextureParameterfv(self.m_id, C_GL_TEXTURE_MAX_ANISOTROPY, &mut anisotropy));
+    return Ok(anisotropy);
+  }
+  
   fn apply(&mut self) -> Result<(), EnumRendererError> {
     #[cfg(feature = "debug")]
     log!(EnumLogColor::Blue, "DEBUG", "[GlTexture] -->\t Storing {0}", self.m_texture.m_type);
@@ -234,6 +308,10 @@ impl<T> TraitTexture for GlTexture<T> {
     check_gl_call!("GlTexture", gl::GenTextures(1, &mut self.m_id));
     check_gl_call!("GlTexture", gl::ActiveTexture(gl::TEXTURE0 + self.m_slot as u32));
     check_gl_call!("GlTexture", gl::BindTexture(self.m_internal_target, self.m_id));
+    #[cfg(feature = "debug")]
+    {
+      Engine::get_active_renderer().m_stats.m_texture_binds += 1;
+    }
     
     match self.m_internal_target {
       gl::TEXTURE_2D_MULTISAMPLE | gl::TEXTURE_2D_MULTISAMPLE_ARRAY => {
@@ -245,9 +323,14 @@ impl<T> TraitTexture for GlTexture<T> {
         check_gl_call!("GlTexture", gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_WRAP_T, gl::CLAMP_TO_BORDER as GLint));
       }
       _ => {
-        check_gl_call!("GlTexture", gl::TexParameteri(self.m_internal_target, gl::TEXTURE_MAG_FILTER, gl::LINEAR as GLint));
-        check_gl_call!("GlTexture", gl::TexParameteri(self.m_internal_target, gl::TEXTURE_MIN_FILTER,
-          gl::LINEAR_MIPMAP_LINEAR as GLint));
+        let (min_filter, mag_filter) = Self::convert_filter_to_internal_filters(self.m_texture.m_filter,
+          self.m_texture.m_mipmaps);
+        check_gl_call!("GlTexture", gl::TexParameteri(self.m_internal_target, gl::TEXTURE_MAG_FILTER, mag_filter as GLint));
+        check_gl_call!("GlTexture", gl::TexParameteri(self.m_internal_target, gl::TEXTURE_MIN_FILTER, min_filter as GLint));
+        
+        if let Some(anisotropy) = self.m_texture.m_anisotropy {
+          self.set_anisotropy(anisotropy)?;
+        }
         
         check_gl_call!("GlTexture", gl::TexParameteri(self.m_internal_target, gl::TEXTURE_WRAP_S, gl::REPEAT as GLint));
         check_gl_call!("GlTexture", gl::TexParameteri(self.m_internal_target, gl::TEXTURE_WRAP_T, gl::REPEAT as GLint));
@@ -271,6 +354,11 @@ impl<T> TraitTexture for GlTexture<T> {
         check_gl_call!("GlTexture", gl::TexImage2D(self.m_internal_target, self.m_level as GLint, self.m_internal_format as GLint,
         self.m_texture.m_data.width as GLsizei, self.m_texture.m_data.height as GLsizei, 0, self.m_format, self.m_internal_type,
           self.m_texture.m_data.data.as_ptr() as *const _));
+        
+        // Multi-sampled textures cannot have mipmaps.
+        if self.m_texture.m_mipmaps && self.m_ms.is_none() && self.m_internal_target != gl::TEXTURE_2D_MULTISAMPLE {
+          check_gl_call!("GlTexture", gl::GenerateMipmap(self.m_internal_target));
+        }
       }
       gl::TEXTURE_2D_ARRAY | gl::TEXTURE_3D | gl::TEXTURE_2D_MULTISAMPLE_ARRAY => {
         // Check if texture is multi-sampled.
@@ -299,7 +387,9 @@ impl<T> TraitTexture for GlTexture<T> {
           }
           _ => {}
         }
-        check_gl_call!("GlTexture", gl::GenerateMipmap(self.m_internal_target));
+        if self.m_texture.m_mipmaps && self.m_ms.is_none() {
+          check_gl_call!("GlTexture", gl::GenerateMipmap(self.m_internal_target));
+        }
       }
       _ => todo!()
     }
@@ -309,6 +399,10 @@ impl<T> TraitTexture for GlTexture<T> {
   
   fn clear(&mut self) -> Result<(), EnumRendererError> {
     check_gl_call!("GlTexture", gl::BindTexture(self.m_internal_target, self.m_id));
+    #[cfg(feature = "debug")]
+    {
+      Engine::get_active_renderer().m_stats.m_texture_binds += 1;
+    }
     
     match self.m_internal_target {
       gl::TEXTURE_2D | gl::TEXTURE_2D_MULTISAMPLE | gl::TEXTURE_CUBE_MAP | gl::TEXTURE_RECTANGLE | gl::TEXTURE_1D_ARRAY => {
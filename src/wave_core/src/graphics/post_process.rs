@@ -0,0 +1,135 @@
+/*
+ MIT License
+ 
+ Copyright (c) 2024 Nami Reghbati
+ 
+ Permission is hereby granted, free of charge, to any person obtaining a copy
+ of this software and associated documentation files (the "Software"), to deal
+ in the Software without restriction, including without limitation the rights
+ to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ copies of the Software, and to permit persons to whom the Software is
+ furnished to do so, subject to the following conditions:
+ 
+ The above copyright notice and this permission notice shall be included in all
+ copies or substantial portions of the Software.
+ 
+ THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ FITNESS FOR A PARTICULAR PURPOSE AND NON INFRINGEMENT. IN NO EVENT SHALL THE
+ AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ SOFTWARE.
+*/
+
+use std::collections::HashSet;
+
+use crate::TraitApply;
+#[cfg(feature = "debug")]
+use crate::Engine;
+use crate::graphics::open_gl::framebuffer::GlFullscreenPass;
+use crate::graphics::render_target::RenderTarget;
+use crate::graphics::renderer::{EnumRendererApi, EnumRendererError};
+use crate::graphics::shader::{EnumShaderError, EnumShaderSource, EnumShaderStageType, Shader, ShaderStage};
+use crate::graphics::texture::EnumTextureFormat;
+use crate::utils::macros::logger::*;
+
+/// Ordered list of fullscreen passes applied on the rendered scene. Each pass samples the previous pass's color output
+/// (bound to texture unit 0) and draws into one of two render targets used in turn (ping-pong). OpenGL only for now.
+pub struct PostProcessChain {
+  m_passes: Vec<Shader>,
+  m_targets: Vec<RenderTarget>,
+  m_fullscreen_pass: Option<GlFullscreenPass>,
+}
+
+impl PostProcessChain {
+  pub fn new() -> Self {
+    return Self {
+      m_passes: Vec::with_capacity(2),
+      m_targets: Vec::with_capacity(2),
+      m_fullscreen_pass: None,
+    };
+  }
+  
+  /// Built-in pass, applying Reinhard tonemapping followed by gamma correction.
+  pub fn tonemap_pass() -> Result<Shader, EnumShaderError> {
+    let mut shader = Shader::new(EnumRendererApi::OpenGL, HashSet::from([
+      ShaderStage::new(EnumShaderStageType::Vertex, EnumShaderSource::FromFile(String::from("res/shaders/post_process.vert"))),
+      ShaderStage::new(EnumShaderStageType::Fragment, EnumShaderSource::FromFile(String::from("res/shaders/post_tonemap.frag")))
+    ]));
+    shader.apply()?;
+    return Ok(shader);
+  }
+  
+  /// Append a pass, run after all previously added ones. The shader should already be applied, and sample its input
+  /// from `layout (binding = 0) uniform sampler2D`.
+  pub fn add_pass(&mut self, shader: Shader) {
+    self.m_passes.push(shader);
+  }
+  
+  pub fn len(&self) -> usize {
+    return self.m_passes.len();
+  }
+  
+  pub fn is_empty(&self) -> bool {
+    return self.m_passes.is_empty();
+  }
+  
+  /// Run all passes on the input's first color attachment, returning the target holding the final result (or the input
+  /// itself if there are no passes).
+  pub fn apply<'a>(&'a mut self, input: &'a RenderTarget) -> Result<&'a RenderTarget, EnumRendererError> {
+    if self.m_passes.is_empty() {
+      return Ok(input);
+    }
+    
+    let (width, height) = input.get_size();
+    if self.m_targets.is_empty() {
+      log!(EnumLogColor::Purple, "INFO", "[PostProcess] -->\t Allocating ping-pong targets of {0}x{1}...", width, height);
+      self.m_targets.push(RenderTarget::new(EnumRendererApi::OpenGL, width, height, &[EnumTextureFormat::Rgba], false)?);
+      self.m_targets.push(RenderTarget::new(EnumRendererApi::OpenGL, width, height, &[EnumTextureFormat::Rgba], false)?);
+      self.m_fullscreen_pass = Some(GlFullscreenPass::new()?);
+    }
+    for target in self.m_targets.iter_mut() {
+      target.resize(width, height)?;
+    }
+    
+    let fullscreen_pass = self.m_fullscreen_pass.as_ref().unwrap();
+    let mut source_texture_id = input.get_color_attachment_id(0).ok_or(EnumRendererError::IncompleteFramebuffer)?;
+    
+    for (index, shader) in self.m_passes.iter().enumerate() {
+      let destination = &self.m_targets[index % 2];
+      destination.bind()?;
+      fullscreen_pass.draw(shader.get_id(), source_texture_id)?;
+      source_texture_id = destination.get_color_attachment_id(0).ok_or(EnumRendererError::IncompleteFramebuffer)?;
+    }
+    self.m_targets[0].unbind()?;
+    
+    return Ok(&self.m_targets[(self.m_passes.len() - 1) % 2]);
+  }
+  
+  pub fn free(&mut self) -> Result<(), EnumRendererError> {
+    for target in self.m_targets.iter_mut() {
+      crate::TraitFree::free(target)?;
+    }
+    self.m_targets.clear();
+    
+    if let Some(fullscreen_pass) = self.m_fullscreen_pass.as_mut() {
+      fullscreen_pass.free()?;
+    }
+    self.m_fullscreen_pass = None;
+    
+    // Passes are owned by the chain once added, so their shaders go with it.
+    for mut shader in self.m_passes.drain(..) {
+      if let Err(_err) = crate::TraitFree::free(&mut shader) {
+        log!(EnumLogColor::Red, "ERROR", "[PostProcess] -->\t Error while freeing pass shader : {0}", _err);
+      }
+    }
+    return Ok(());
+  }
+}
+
+impl Default for PostProcessChain {
+  fn default() -> Self {
+    return Self::new();
+  }
+}
@@ -0,0 +1,144 @@
+/*
+ MIT License
+ 
+ Copyright (c) 2024 Nami Reghbati
+ 
+ Permission is hereby granted, free of charge, to any person obtaining a copy
+ of this software and associated documentation files (the "Software"), to deal
+ in the Software without restriction, including without limitation the rights
+ to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ copies of the Software, and to permit persons to whom the Software is
+ furnished to do so, subject to the following conditions:
+ 
+ The above copyright notice and this permission notice shall be included in all
+ copies or substantial portions of the Software.
+ 
+ THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ FITNESS FOR A PARTICULAR PURPOSE AND NON INFRINGEMENT. IN NO EVENT SHALL THE
+ AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ SOFTWARE.
+*/
+
+use crate::graphics::open_gl::framebuffer::GlRenderTarget;
+use crate::graphics::renderer::{EnumRendererApi, EnumRendererError};
+use crate::graphics::texture::{EnumTextureFormat, Texture};
+use crate::{Engine, TraitFree};
+use crate::utils::macros::logger::*;
+use crate::window::EnumWindowState;
+
+pub(crate) trait TraitRenderTarget {
+  fn bind(&self) -> Result<(), EnumRendererError>;
+  fn unbind(&self) -> Result<(), EnumRendererError>;
+  fn resize(&mut self, width: u32, height: u32) -> Result<(), EnumRendererError>;
+  fn get_size(&self) -> (u32, u32);
+  fn get_color_attachment_id(&self, index: usize) -> Option<u32>;
+  fn read_pixels(&self, index: usize) -> Result<Vec<u8>, EnumRendererError>;
+  fn copy_from_window(&self) -> Result<(), EnumRendererError>;
+  fn copy_to_window(&self) -> Result<(), EnumRendererError>;
+  fn get_color_texture(&self, index: usize) -> Option<Texture>;
+  fn get_depth_attachment_id(&self) -> Option<u32>;
+  fn get_depth_texture(&self) -> Option<Texture>;
+  fn free(&mut self) -> Result<(), EnumRendererError>;
+}
+
+/// Offscreen rendering target, with any number of color attachments and an optional depth attachment, all of which
+/// can be sampled afterward as textures.
+pub struct RenderTarget {
+  m_api: Box<dyn TraitRenderTarget>,
+}
+
+impl TraitFree<EnumRendererError> for RenderTarget {
+  fn free(&mut self) -> Result<(), EnumRendererError> {
+    return self.m_api.free();
+  }
+}
+
+impl RenderTarget {
+  pub fn new(api_chosen: EnumRendererApi, width: u32, height: u32, color_attachments: &[EnumTextureFormat],
+             with_depth: bool) -> Result<Self, EnumRendererError> {
+    return match api_chosen {
+      EnumRendererApi::OpenGL => {
+        Ok(RenderTarget {
+          m_api: Box::new(GlRenderTarget::new(width, height, color_attachments, with_depth)?),
+        })
+      }
+      EnumRendererApi::Vulkan => {
+        log!(EnumLogColor::Red, "ERROR", "[RenderTarget] -->\t Render targets are not supported in Vulkan yet!");
+        Err(EnumRendererError::NotImplemented)
+      }
+    };
+  }
+  
+  /// Redirect subsequent draws to this target, with the viewport matching its size.
+  pub fn bind(&self) -> Result<(), EnumRendererError> {
+    return self.m_api.bind();
+  }
+  
+  /// Go back to drawing onto the window, restoring the viewport to the window's framebuffer size.
+  pub fn unbind(&self) -> Result<(), EnumRendererError> {
+    return self.m_api.unbind();
+  }
+  
+  /// Reallocate all attachments to the new size. Previous contents are lost.
+  pub fn resize(&mut self, width: u32, height: u32) -> Result<(), EnumRendererError> {
+    return self.m_api.resize(width, height);
+  }
+  
+  pub fn get_size(&self) -> (u32, u32) {
+    return self.m_api.get_size();
+  }
+  
+  pub(crate) fn get_color_attachment_id(&self, index: usize) -> Option<u32> {
+    return self.m_api.get_color_attachment_id(index);
+  }
+  
+  /// Read back a color attachment as RGBA8 pixels, row by row from the top left corner.
+  pub fn read_pixels(&self, index: usize) -> Result<Vec<u8>, EnumRendererError> {
+    return self.m_api.read_pixels(index);
+  }
+  
+  /// Copy the window's current color buffer into the first color attachment.
+  pub(crate) fn copy_from_window(&self) -> Result<(), EnumRendererError> {
+    return self.m_api.copy_from_window();
+  }
+  
+  /// Copy the first color attachment onto the window's color buffer.
+  pub(crate) fn copy_to_window(&self) -> Result<(), EnumRendererError> {
+    return self.m_api.copy_to_window();
+  }
+  
+  /// Get a handle to a color attachment, to sample it elsewhere. The texture remains owned by the render target, and
+  /// will be invalidated once it gets resized or freed.
+  pub fn color_texture_handle(&self, index: usize) -> Option<Texture> {
+    return self.m_api.get_color_texture(index);
+  }
+  
+  /// Get a handle to the first color attachment, see [Self::color_texture_handle]. Panics if the target was created
+  /// without any color attachment, like depth only targets.
+  pub fn get_color_texture(&self) -> Texture {
+    return self.color_texture_handle(0).expect("Render target has no color attachment!");
+  }
+  
+  pub(crate) fn get_depth_attachment_id(&self) -> Option<u32> {
+    return self.m_api.get_depth_attachment_id();
+  }
+  
+  /// Get a handle to the depth attachment (single channel), if the target was created with one. Same ownership rules as
+  /// [Self::color_texture_handle].
+  pub fn depth_texture_handle(&self) -> Option<Texture> {
+    return self.m_api.get_depth_texture();
+  }
+}
+
+impl Drop for RenderTarget {
+  fn drop(&mut self) {
+    if Engine::get_active_window().m_state != EnumWindowState::Closed {
+      if let Err(_err) = self.m_api.free() {
+        log!(EnumLogColor::Red, "ERROR", "[RenderTarget] -->\t Error while freeing render target, Error => {0}", _err);
+      }
+    }
+  }
+}
@@ -23,22 +23,40 @@
 */
 
 use std::any::Any;
+use std::collections::HashSet;
 use std::fmt::{Display, Formatter};
 
 use crate::Engine;
 use crate::utils::macros::logger::*;
 use crate::assets::asset_loader;
 use crate::assets::r_assets::{REntity};
-use crate::{events, TraitApply, TraitFree, TraitHint};
-use crate::graphics::{open_gl, texture};
+use crate::camera::Camera;
+use crate::{EnumEngineError, events, input, TraitApply, TraitFree, TraitHint};
+use crate::graphics::{light, open_gl, texture};
+use crate::graphics::light::EnumLight;
 use crate::graphics::open_gl::renderer::GlContext;
+use crate::graphics::render_target::RenderTarget;
+use crate::graphics::texture::Texture;
+pub use crate::graphics::debug_draw::DebugDraw;
+pub use crate::graphics::post_process::PostProcessChain;
+pub use crate::graphics::static_batch::StaticBatch;
+use crate::graphics::text::{Font, TextDraw};
+use crate::graphics::dynamic_buffer::DynamicBuffer;
+use crate::graphics::color::Color;
 use crate::graphics::shader::{Shader};
 #[cfg(feature = "vulkan")]
 use crate::graphics::vulkan;
 #[cfg(feature = "vulkan")]
 use crate::graphics::vulkan::renderer::VkContext;
-use crate::math::{Mat4};
+use crate::math::{Aabb, Mat4, Vec2};
 use crate::window::Window;
+#[cfg(feature = "vulkan")]
+use crate::window::S_WINDOW_CONTEXT;
+
+// Bytes per frame in flight for streamed geometry (debug lines, text), before growing.
+const C_DYNAMIC_BUFFER_REGION_SIZE: usize = 256 * 1024;
+/// Background of the window when no [EnumRendererHint::ClearColor] is given.
+pub const C_DEFAULT_CLEAR_COLOR: [f32; 4] = [0.025, 0.025, 0.025, 1.0];
 
 #[derive(Debug, Copy, Clone, PartialOrd, PartialEq, Ord, Eq, Hash)]
 pub enum EnumRendererState {
@@ -123,6 +141,25 @@ impl Default for EnumRendererBlendingFactor {
   }
 }
 
+/// Comparison between the depth of an incoming fragment and the one stored, the fragment being kept if it passes.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum EnumRendererDepthFunc {
+  Never,
+  Less,
+  Equal,
+  LessEqual,
+  Greater,
+  NotEqual,
+  GreaterEqual,
+  Always,
+}
+
+impl Default for EnumRendererDepthFunc {
+  fn default() -> Self {
+    return EnumRendererDepthFunc::Less;
+  }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum EnumRendererRenderPrimitiveAs {
   Points,
@@ -158,6 +195,59 @@ impl Display for EnumRendererBlendingFactor {
   }
 }
 
+/// Common blending presets, mapping to a pair of source and destination [EnumRendererBlendingFactor] added together.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum EnumRendererBlendMode {
+  Opaque,
+  AlphaBlend,
+  Additive,
+  Multiply,
+}
+
+impl Default for EnumRendererBlendMode {
+  fn default() -> Self {
+    return EnumRendererBlendMode::Opaque;
+  }
+}
+
+impl EnumRendererBlendMode {
+  /// Source and destination factors of the blend function, or [None] if blending should be disabled.
+  pub fn get_factors(&self) -> Option<(EnumRendererBlendingFactor, EnumRendererBlendingFactor)> {
+    return match self {
+      EnumRendererBlendMode::Opaque => None,
+      EnumRendererBlendMode::AlphaBlend => Some((EnumRendererBlendingFactor::SrcAlpha, EnumRendererBlendingFactor::OneMinusSrcAlpha)),
+      EnumRendererBlendMode::Additive => Some((EnumRendererBlendingFactor::SrcAlpha, EnumRendererBlendingFactor::One)),
+      EnumRendererBlendMode::Multiply => Some((EnumRendererBlendingFactor::DstColor, EnumRendererBlendingFactor::Zero)),
+    };
+  }
+}
+
+impl Display for EnumRendererBlendMode {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    return match self {
+      EnumRendererBlendMode::Opaque => write!(f, "Opaque"),
+      EnumRendererBlendMode::AlphaBlend => write!(f, "Alpha blend"),
+      EnumRendererBlendMode::Additive => write!(f, "Additive"),
+      EnumRendererBlendMode::Multiply => write!(f, "Multiply")
+    };
+  }
+}
+
+impl Display for EnumRendererDepthFunc {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    return match self {
+      EnumRendererDepthFunc::Never => write!(f, "Never"),
+      EnumRendererDepthFunc::Less => write!(f, "Less"),
+      EnumRendererDepthFunc::Equal => write!(f, "Equal"),
+      EnumRendererDepthFunc::LessEqual => write!(f, "Less or equal"),
+      EnumRendererDepthFunc::Greater => write!(f, "Greater"),
+      EnumRendererDepthFunc::NotEqual => write!(f, "Not equal"),
+      EnumRendererDepthFunc::GreaterEqual => write!(f, "Greater or equal"),
+      EnumRendererDepthFunc::Always => write!(f, "Always")
+    };
+  }
+}
+
 impl Display for EnumRendererCull {
   fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
     return match self {
@@ -191,7 +281,7 @@ impl Default for EnumRendererOptimizationMode {
   }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub enum EnumRendererHint {
   ForceApiVersion(u32),
   /// Combine primitives with the same material type into a single buffer if possible, both for the vbo and ibo.
@@ -253,6 +343,18 @@ pub enum EnumRendererHint {
   
   /// Enable depth testing to avoid artefacts or overlapping geometry incorrectly displayed onto the screen.
   DepthTest(bool),
+  /// Comparison used by the depth test.
+  /// ### Argument:
+  /// - [EnumRendererDepthFunc::Less] **Default**.
+  ///
+  /// - [EnumRendererDepthFunc::LessEqual]: Useful for skyboxes drawn at the far plane, or for redrawing the same
+  /// geometry in another pass.
+  DepthFunc(EnumRendererDepthFunc),
+  /// Write the depth of fragments passing the depth test to the depth buffer. Disable for overlays that should not
+  /// occlude what gets drawn after them.
+  /// ### Argument:
+  /// - *true* **Default**.
+  DepthWrite(bool),
   /// Enable culling for a specific face to avoid rendering it when unneeded save on fragment shader calls when rendering.
   /// ### Argument:
   /// Four possible values can be provided:
@@ -266,6 +368,20 @@ pub enum EnumRendererHint {
   /// - Some([EnumRendererCull::FrontAndBack]): Cull both front and back faces of primitives.
   CullFacing(Option<EnumRendererCull>),
   MSAA(Option<u8>),
+  /// Width and height, in texels, of the shadow map rendered by [Renderer::render_shadow_pass]. Higher resolutions give
+  /// sharper shadow edges at the cost of fill rate and memory.
+  /// ### Argument:
+  /// - *2048* **Default**.
+  ShadowMapResolution(u32),
+  /// Skip primitives whose world bounding box lies entirely outside the active camera's frustum. Culled primitives are
+  /// counted in [Renderer::get_culled_count].
+  /// ### Argument:
+  /// - *true* **Default**.
+  FrustumCulling(bool),
+  /// Color (RGBA) the window is cleared with before each frame.
+  /// ### Argument:
+  /// - [C_DEFAULT_CLEAR_COLOR] **Default**.
+  ClearColor([f32; 4]),
   SRGB(bool),
   Blending(Option<(EnumRendererBlendingFactor, EnumRendererBlendingFactor)>),
 }
@@ -280,8 +396,13 @@ impl EnumRendererHint {
       EnumRendererHint::Optimization(bool) => bool,
       EnumRendererHint::ApiCallChecking(mode) => mode,
       EnumRendererHint::DepthTest(bool) => bool,
+      EnumRendererHint::DepthFunc(func) => func,
+      EnumRendererHint::DepthWrite(bool) => bool,
       EnumRendererHint::CullFacing(mode) => mode,
       EnumRendererHint::MSAA(sample_count) => sample_count,
+      EnumRendererHint::ShadowMapResolution(resolution) => resolution,
+      EnumRendererHint::FrustumCulling(bool) => bool,
+      EnumRendererHint::ClearColor(rgba) => rgba,
       EnumRendererHint::SRGB(bool) => bool,
       EnumRendererHint::Blending(blend_func) => blend_func,
       EnumRendererHint::SplitLargeVertexBuffers(vertex_limit) => vertex_limit,
@@ -297,6 +418,7 @@ pub enum EnumRendererError {
   NoApi,
   NoActiveRenderer,
   InvalidApi,
+  InvalidHint,
   UnsupportedApi,
   NotImplemented,
   ContextError,
@@ -306,6 +428,8 @@ pub enum EnumRendererError {
   EntityNotFound,
   ShaderNotFound,
   UboNotFound,
+  IncompleteFramebuffer,
+  ScreenshotError,
   CError,
   #[cfg(feature = "vulkan")]
   VulkanError(vulkan::renderer::EnumVkContextError),
@@ -378,24 +502,100 @@ impl Stats {
   }
 }
 
+/// Per-frame rendering statistics, only gathered when the `debug` feature is enabled.
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct RendererStats {
+  pub m_draw_calls: u32,
+  pub m_triangles: u64,
+  pub m_vertices: u64,
+  pub m_texture_binds: u32,
+  pub m_active_shaders: u32,
+  // Primitives skipped for being outside the camera frustum, counted even without the `debug` feature.
+  pub m_culled_primitives: u32,
+}
+
+impl Display for RendererStats {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    write!(f, "Draw calls: {0}, Triangles: {1}, Vertices: {2}, Texture binds: {3}, Active shaders: {4}, Culled: {5}",
+      self.m_draw_calls, self.m_triangles, self.m_vertices, self.m_texture_binds, self.m_active_shaders,
+      self.m_culled_primitives)
+  }
+}
+
+/// What the current GPU and driver support, queried once when the renderer gets applied. Everything is zeroed or empty
+/// until then.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct RendererCapabilities {
+  pub m_vendor: String,
+  pub m_device_name: String,
+  pub m_api_version: String,
+  pub m_shading_language_version: String,
+  // Texture units usable at once, across all shader stages.
+  pub m_max_texture_units: u32,
+  pub m_max_samples: u32,
+  pub m_max_texture_size: u32,
+  // 1.0 if anisotropic filtering is unsupported.
+  pub m_max_anisotropy: f32,
+  // Buffers can stay mapped while the GPU reads from them (GL_ARB_buffer_storage or OpenGL 4.4+).
+  pub m_persistent_mapping: bool,
+  pub m_extensions: HashSet<String>,
+}
+
+impl RendererCapabilities {
+  pub fn has_extension(&self, extension: &str) -> bool {
+    return self.m_extensions.contains(extension);
+  }
+}
+
+impl Display for RendererCapabilities {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    write!(f, "Max texture units =>\t {0};\n\
+    Max texture size =>\t {1};\n\
+    Max MSAA samples =>\t {2};\n\
+    Max anisotropy =>\t {3};\n\
+    Persistent mapping =>\t {4};\n\
+    Extensions =>\t\t {5} available;",
+      self.m_max_texture_units, self.m_max_texture_size, self.m_max_samples, self.m_max_anisotropy,
+      self.m_persistent_mapping, self.m_extensions.len())
+  }
+}
+
 pub(crate) trait TraitContext {
   fn new() -> Self where Self: Sized;
   fn get_api_handle(&mut self) -> &mut dyn Any;
   fn get_api_version(&self) -> f32;
   fn get_max_shader_version_available(&self) -> u16;
   fn check_extension(&self, desired_extension: &str) -> bool;
+  fn get_capabilities(&self) -> &RendererCapabilities;
   fn on_event(&mut self, event: &events::EnumEvent) -> Result<bool, EnumRendererError>;
-  fn on_render(&mut self) -> Result<(), EnumRendererError>;
+  fn on_render(&mut self, stats: &mut RendererStats) -> Result<(), EnumRendererError>;
   fn apply(&mut self, window: &mut Window, renderer_options: &Vec<EnumRendererHint>) -> Result<(), EnumRendererError>;
   fn toggle_visibility_of(&mut self, entity_uuid: u64, sub_primitive_offset: Option<usize>, instance_count: usize, visible: bool) -> Result<(), EnumRendererError>;
   fn toggle_primitive_mode(&mut self, mode: EnumRendererRenderPrimitiveAs, entity_uuid: u64, sub_primitive_index: Option<usize>, instance_count: usize) -> Result<(), EnumRendererError>;
+  fn toggle_blend_mode(&mut self, entity_uuid: u64, instance_offset: Option<usize>, instance_count: usize, mode: EnumRendererBlendMode) -> Result<(), EnumRendererError>;
+  fn get_polygon_mode(&self) -> Result<EnumRendererRenderPrimitiveAs, EnumRendererError>;
+  fn get_blend_mode(&self) -> Result<Option<EnumRendererBlendMode>, EnumRendererError>;
+  fn set_viewport(&mut self, viewport: (i32, i32, u32, u32), scissor: bool) -> Result<(), EnumRendererError>;
   fn get_max_msaa_count(&self) -> Result<u8, EnumRendererError>;
   fn to_string(&self) -> String;
   fn toggle_options(&mut self, renderer_options: &Vec<EnumRendererHint>) -> Result<(), EnumRendererError>;
   fn flush(&mut self) -> Result<(), EnumRendererError>;
   fn enqueue(&mut self, entity: &REntity, shader_associated: &mut Shader) -> Result<(), EnumRendererError>;
+  fn enqueue_batch(&mut self, entities: &[REntity], shader_associated: &mut Shader) -> Result<(), EnumRendererError>;
+  fn get_buffer_allocation_count(&self) -> usize;
+  fn enqueue_instanced(&mut self, entity: &REntity, shader_associated: &mut Shader, transforms: &[Mat4]) -> Result<(), EnumRendererError>;
+  fn get_instance_buffer_size(&self, entity_uuid: u64) -> Option<usize>;
+  fn update_instance_transforms(&mut self, entity_uuid: u64, first_instance: usize, transforms: &[Mat4]) -> Result<(), EnumRendererError>;
+  fn relink_shader(&mut self, old_shader_id: u32, new_shader_id: u32);
+  fn toggle_msaa(&mut self, sample_count: Option<u32>) -> Result<Option<u32>, EnumRendererError>;
+  fn capture_frame(&mut self, width: u32, height: u32, presented: bool) -> Result<Vec<u8>, EnumRendererError>;
   fn dequeue(&mut self, id: u64) -> Result<(), EnumRendererError>;
   fn update_ubo_camera(&mut self, view: Mat4, projection: Mat4) -> Result<(), EnumRendererError>;
+  fn update_ubo_lights(&mut self, light_data: &[u8]) -> Result<(), EnumRendererError>;
+  fn update_ubo_bones(&mut self, entity_uuid: u64, bone_matrices: &[Mat4]) -> Result<(), EnumRendererError>;
+  fn toggle_shadow_casting(&mut self, entity_uuid: u64, instance_offset: Option<usize>, instance_count: usize, cast_shadows: bool) -> Result<(), EnumRendererError>;
+  fn toggle_shadow_receiving(&mut self, entity_uuid: u64, instance_offset: Option<usize>, instance_count: usize, receive_shadows: bool) -> Result<(), EnumRendererError>;
+  fn render_shadow_depth(&mut self, light_view: Mat4, light_projection: Mat4, shadow_map: &RenderTarget) -> Result<(), EnumRendererError>;
   fn update_ubo_model(&mut self, model_transform: Mat4, entity_uuid: u64, instance_offset: Option<usize>, instance_count: usize) -> Result<(), EnumRendererError>;
   fn free(&mut self) -> Result<(), EnumRendererError>;
 }
@@ -405,6 +605,20 @@ pub struct Renderer {
   pub(crate) m_type: EnumRendererApi,
   pub(crate) m_hints: Vec<EnumRendererHint>,
   pub(crate) m_ids: Vec<u64>,
+  pub(crate) m_stats: RendererStats,
+  m_last_frame_stats: RendererStats,
+  m_camera: Option<Camera>,
+  // Split-screen cameras, each with its region of the window (x, y, width, height) from the top left corner.
+  m_viewports: Vec<(Camera, (u32, u32, u32, u32))>,
+  m_focused_viewport: Option<usize>,
+  m_post_process: Option<PostProcessChain>,
+  m_post_process_input: Option<RenderTarget>,
+  m_shadow_map: Option<RenderTarget>,
+  m_render_target: Option<RenderTarget>,
+  m_light_space_matrix: Option<Mat4>,
+  m_debug_draw: DebugDraw,
+  m_text_draw: TextDraw,
+  m_dynamic_buffer: Option<DynamicBuffer>,
   m_api: Box<dyn TraitContext>,
 }
 
@@ -422,6 +636,19 @@ impl Default for Renderer {
       m_type: EnumRendererApi::default(),
       m_hints: hints.clone(),
       m_ids: Vec::with_capacity(10),
+      m_stats: RendererStats::default(),
+      m_last_frame_stats: RendererStats::default(),
+      m_camera: None,
+      m_viewports: vec![],
+      m_focused_viewport: None,
+      m_post_process: None,
+      m_post_process_input: None,
+      m_shadow_map: None,
+      m_render_target: None,
+      m_light_space_matrix: None,
+      m_debug_draw: DebugDraw::new(),
+      m_text_draw: TextDraw::new(),
+      m_dynamic_buffer: None,
       m_api: Box::new(GlContext::new()),
     };
   }
@@ -453,14 +680,12 @@ impl TraitApply<EnumRendererError> for Renderer {
     if self.m_hints.is_empty() {
       self.reset_hints();
     }
-    if self.m_type == EnumRendererApi::Vulkan {
-      #[cfg(not(feature = "vulkan"))]
-      {
-        log!(EnumLogColor::Red, "ERROR", "[Renderer] -->\t Cannot apply Vulkan renderer, vulkan feature not enabled!");
-        return Err(EnumRendererError::InvalidApi);
-      }
-      
-      return self.m_api.apply(window, &self.m_hints);
+    // The window already probed the api before creating its surface, follow it instead of failing to create a
+    // Vulkan context on an OpenGL window.
+    if self.m_type == EnumRendererApi::Vulkan && Self::probe_api(self.m_type) != EnumRendererApi::Vulkan {
+      log!(EnumLogColor::Yellow, "WARN", "[Renderer] -->\t Vulkan unavailable, falling back to OpenGL...");
+      self.m_type = EnumRendererApi::OpenGL;
+      self.m_api = Box::new(GlContext::new());
     }
     
     return self.m_api.apply(window, &self.m_hints);
@@ -475,6 +700,19 @@ impl TraitFree<EnumRendererError> for Renderer {
     }
     
     // Free up resources.
+    self.clear_post_process();
+    self.m_debug_draw.free()?;
+    self.m_text_draw.free()?;
+    if let Some(mut dynamic_buffer) = self.m_dynamic_buffer.take() {
+      dynamic_buffer.free()?;
+    }
+    if let Some(mut shadow_map) = self.m_shadow_map.take() {
+      shadow_map.free()?;
+    }
+    if let Some(mut render_target) = self.m_render_target.take() {
+      render_target.free()?;
+    }
+    self.m_light_space_matrix = None;
     self.m_api.free()?;
     self.m_state = EnumRendererState::Deleted;
     log!(EnumLogColor::Green, "INFO", "[Renderer] -->\t Freed resources successfully");
@@ -491,6 +729,19 @@ impl<'a> Renderer {
           m_type: EnumRendererApi::OpenGL,
           m_hints: vec![],
           m_ids: Vec::with_capacity(10),
+          m_stats: RendererStats::default(),
+          m_last_frame_stats: RendererStats::default(),
+          m_camera: None,
+          m_viewports: vec![],
+          m_focused_viewport: None,
+          m_post_process: None,
+          m_post_process_input: None,
+          m_shadow_map: None,
+          m_render_target: None,
+          m_light_space_matrix: None,
+          m_debug_draw: DebugDraw::new(),
+          m_text_draw: TextDraw::new(),
+          m_dynamic_buffer: None,
           m_api: Box::new(GlContext::new()),
         }
       }
@@ -500,12 +751,73 @@ impl<'a> Renderer {
           m_type: EnumRendererApi::Vulkan,
           m_hints: vec![],
           m_ids: Vec::with_capacity(10),
+          m_stats: RendererStats::default(),
+          m_last_frame_stats: RendererStats::default(),
+          m_camera: None,
+          m_viewports: vec![],
+          m_focused_viewport: None,
+          m_post_process: None,
+          m_post_process_input: None,
+          m_shadow_map: None,
+          m_render_target: None,
+          m_light_space_matrix: None,
+          m_debug_draw: DebugDraw::new(),
+          m_text_draw: TextDraw::new(),
+          m_dynamic_buffer: None,
           m_api: Box::new(VkContext::new()),
         }
       }
     }
   }
   
+  /// Free every GPU resource of the current api and swap in a new, unapplied, context for `api`. Hints, the camera and
+  /// viewports are kept. The new context must be applied once the window has been recreated for it.
+  pub(crate) fn reset_api(&mut self, api: EnumRendererApi) -> Result<(), EnumRendererError> {
+    self.free()?;
+    self.m_ids.clear();
+    self.m_type = api;
+    self.m_api = match api {
+      EnumRendererApi::OpenGL => Box::new(GlContext::new()),
+      EnumRendererApi::Vulkan => Box::new(VkContext::new()),
+    };
+    self.m_state = EnumRendererState::Created;
+    return Ok(());
+  }
+  
+  /// Pick the api actually usable when `preferred` is requested : OpenGL if Vulkan support was not compiled in (`vulkan`
+  /// feature), or if no Vulkan loader and driver can be found on this machine. The window context must already be
+  /// initialized, which is the case once a [Window] has been created.
+  pub fn probe_api(preferred: EnumRendererApi) -> EnumRendererApi {
+    if preferred == EnumRendererApi::OpenGL {
+      return EnumRendererApi::OpenGL;
+    }
+    
+    #[cfg(not(feature = "vulkan"))]
+    {
+      log!(EnumLogColor::Yellow, "WARN", "[Renderer] -->\t Vulkan requested, but vulkan feature not enabled!");
+      return EnumRendererApi::OpenGL;
+    }
+    
+    #[cfg(feature = "vulkan")]
+    {
+      let glfw_supported = unsafe { S_WINDOW_CONTEXT.as_ref() }
+        .map(|context| context.vulkan_supported())
+        .unwrap_or(false);
+      if !glfw_supported {
+        log!(EnumLogColor::Yellow, "WARN", "[Renderer] -->\t Vulkan requested, but no Vulkan loader or compatible \
+        driver found!");
+        return EnumRendererApi::OpenGL;
+      }
+      
+      if let Err(_err) = ash::Entry::linked().try_enumerate_instance_version() {
+        log!(EnumLogColor::Yellow, "WARN", "[Renderer] -->\t Vulkan requested, but the Vulkan loader is unusable, \
+        Error => {0}", _err);
+        return EnumRendererApi::OpenGL;
+      }
+      return EnumRendererApi::Vulkan;
+    }
+  }
+  
   pub fn hide(&mut self, entity_uuid: u64, sub_primitive_offset: Option<usize>, instance_count: usize) -> Result<(), EnumRendererError> {
     log!(EnumLogColor::Blue, "INFO", "[Renderer] -->\t Asset {0} now hidden", entity_uuid);
     return self.m_api.toggle_visibility_of(entity_uuid, sub_primitive_offset, instance_count, false);
@@ -516,7 +828,7 @@ impl<'a> Renderer {
     return self.m_api.toggle_visibility_of(entity_uuid, sub_primitive_offset, instance_count, true);
   }
   
-  pub fn toggle_primitive_mode(&mut self, name: &'static str, mode: EnumRendererRenderPrimitiveAs, entity_uuid: u64, instance_offset: Option<usize>,
+  pub fn toggle_primitive_mode(&mut self, name: &str, mode: EnumRendererRenderPrimitiveAs, entity_uuid: u64, instance_offset: Option<usize>,
                                instance_count: usize) -> Result<(), EnumRendererError> {
     self.m_api.toggle_primitive_mode(mode, entity_uuid, instance_offset, instance_count)?;
     
@@ -526,14 +838,88 @@ impl<'a> Renderer {
     return Ok(());
   }
   
-  pub fn toggle_msaa(&mut self, _sample_count: Option<u32>) -> Result<(), EnumRendererError> {
-    todo!()
+  /// Include or exclude an entity from the shadow pass.
+  pub fn toggle_shadow_casting(&mut self, entity_uuid: u64, instance_offset: Option<usize>, instance_count: usize,
+                               cast_shadows: bool) -> Result<(), EnumRendererError> {
+    return self.m_api.toggle_shadow_casting(entity_uuid, instance_offset, instance_count, cast_shadows);
+  }
+  
+  /// Whether an entity gets darkened by the shadow map or stays fully lit.
+  pub fn toggle_shadow_receiving(&mut self, entity_uuid: u64, instance_offset: Option<usize>, instance_count: usize,
+                                 receive_shadows: bool) -> Result<(), EnumRendererError> {
+    return self.m_api.toggle_shadow_receiving(entity_uuid, instance_offset, instance_count, receive_shadows);
+  }
+  
+  /// Set the blend mode of an entity. Any mode other than [EnumRendererBlendMode::Opaque] defers its draw after all
+  /// opaque entities, sorted back-to-front from the active camera with its own blend function.
+  pub fn toggle_blend_mode(&mut self, entity_uuid: u64, instance_offset: Option<usize>, instance_count: usize,
+                           mode: EnumRendererBlendMode) -> Result<(), EnumRendererError> {
+    return self.m_api.toggle_blend_mode(entity_uuid, instance_offset, instance_count, mode);
+  }
+  
+  /// Set the blend function used outside transparent entity draws, enabling or disabling blending accordingly.
+  pub fn set_blend_mode(&mut self, mode: EnumRendererBlendMode) -> Result<(), EnumRendererError> {
+    let hint = EnumRendererHint::Blending(mode.get_factors());
+    self.m_api.toggle_options(&vec![hint])?;
+    
+    self.set_hint(hint);
+    return Ok(());
+  }
+  
+  /// Query the blend state currently set in the api, outside any entity draw. Returns [None] if the blend function
+  /// does not match any [EnumRendererBlendMode].
+  pub fn get_blend_mode(&self) -> Result<Option<EnumRendererBlendMode>, EnumRendererError> {
+    return self.m_api.get_blend_mode();
+  }
+  
+  /// Query the polygon mode currently set in the api, outside any entity draw. Solid wireframe is reported as filled,
+  /// since the wireframe overlay is done in shaders.
+  pub fn get_polygon_mode(&self) -> Result<EnumRendererRenderPrimitiveAs, EnumRendererError> {
+    return self.m_api.get_polygon_mode();
+  }
+  
+  /// Enable MSAA with the given sample count, or disable it with [None], without having to recreate the window.
+  /// Sample counts above what the hardware supports are clamped to the maximum, see [Self::get_msaa_sample_count] for
+  /// the effective count. Sample counts that are not powers of two are rejected.
+  pub fn toggle_msaa(&mut self, sample_count: Option<u32>) -> Result<(), EnumRendererError> {
+    let effective_sample_count = self.m_api.toggle_msaa(sample_count)?;
+    
+    self.set_hint(EnumRendererHint::MSAA(effective_sample_count.map(|samples| samples as u8)));
+    Engine::get_active_window().m_samples = effective_sample_count.unwrap_or(1);
+    return Ok(());
+  }
+  
+  /// Enable or disable skipping primitives outside the camera frustum, without having to recreate the renderer.
+  pub fn toggle_frustum_culling(&mut self, enabled: bool) -> Result<(), EnumRendererError> {
+    self.m_api.toggle_options(&vec![EnumRendererHint::FrustumCulling(enabled)])?;
+    
+    self.set_hint(EnumRendererHint::FrustumCulling(enabled));
+    return Ok(());
+  }
+  
+  pub fn is_frustum_culling(&self) -> bool {
+    return !self.m_hints.contains(&EnumRendererHint::FrustumCulling(false));
+  }
+  
+  pub fn get_msaa_sample_count(&self) -> Option<u32> {
+    return self.m_hints.iter()
+      .find_map(|hint| match hint {
+        EnumRendererHint::MSAA(sample_count) => *sample_count,
+        _ => None
+      })
+      .map(|samples| samples as u32);
   }
   
   pub fn check_extension(&self, desired_extension: &str) -> bool {
     return self.m_api.check_extension(desired_extension);
   }
   
+  /// Limits and extensions of the current GPU, queried once when the renderer got applied. Prefer this over querying
+  /// the api again.
+  pub fn get_capabilities(&self) -> &RendererCapabilities {
+    return self.m_api.get_capabilities();
+  }
+  
   pub fn on_event(&mut self, event: &events::EnumEvent) -> Result<bool, EnumRendererError> {
     match event {
       events::EnumEvent::WindowCloseEvent(_time) => {
@@ -552,7 +938,151 @@ impl<'a> Renderer {
   }
   
   pub fn on_render(&mut self) -> Result<(), EnumRendererError> {
-    return self.m_api.on_render();
+    if let Some(render_target) = self.m_render_target.as_ref() {
+      render_target.bind()?;
+      self.m_api.on_render(&mut self.m_stats)?;
+      render_target.unbind()?;
+    } else if self.m_viewports.is_empty() {
+      self.m_api.on_render(&mut self.m_stats)?;
+    } else {
+      // Render the scene once per viewport, each only clearing and drawing its own region of the window.
+      let (framebuffer_width, framebuffer_height) = Engine::get_active_window().get_framebuffer_size();
+      for (camera, (x, y, width, height)) in self.m_viewports.iter() {
+        self.m_api.update_ubo_camera(camera.get_view_matrix(), camera.get_projection_matrix())?;
+        // OpenGL viewports start from the bottom left corner.
+        self.m_api.set_viewport((*x as i32, framebuffer_height as i32 - (*y + *height) as i32, *width, *height), true)?;
+        self.m_api.on_render(&mut self.m_stats)?;
+      }
+      self.m_api.set_viewport((0, 0, framebuffer_width, framebuffer_height), false)?;
+      
+      if let Some(camera) = self.m_camera.as_ref() {
+        self.m_api.update_ubo_camera(camera.get_view_matrix(), camera.get_projection_matrix())?;
+      }
+    }
+    
+    if self.m_type == EnumRendererApi::OpenGL {
+      if self.m_dynamic_buffer.is_none() && (!self.m_debug_draw.is_empty() || !self.m_text_draw.is_empty()) {
+        self.m_dynamic_buffer = Some(DynamicBuffer::new(self.m_type, C_DYNAMIC_BUFFER_REGION_SIZE,
+          self.m_api.get_capabilities())?);
+      }
+      
+      if let Some(dynamic_buffer) = self.m_dynamic_buffer.as_mut() {
+        // Debug lines are drawn over the whole window with the main camera, and never carried over to the next frame.
+        match self.m_camera.as_ref() {
+          Some(camera) => {
+            self.m_debug_draw.flush(camera.get_view_matrix(), camera.get_projection_matrix(), dynamic_buffer)?;
+          }
+          None => self.m_debug_draw.clear(),
+        }
+        // Text goes over everything else, debug lines included.
+        self.m_text_draw.flush(Engine::get_active_window().get_framebuffer_size(), dynamic_buffer)?;
+        dynamic_buffer.next_frame()?;
+      }
+    } else {
+      self.m_debug_draw.clear();
+      self.m_text_draw.clear();
+    }
+    
+    // Keep the completed frame around for queries, and start counting the next one from scratch.
+    self.m_last_frame_stats = std::mem::take(&mut self.m_stats);
+    return Ok(());
+  }
+  
+  /// Run `draw` with the render target bound, so that everything it draws ends up in the target instead of the window.
+  /// The window framebuffer and viewport are restored afterward, even if nothing was drawn.
+  pub fn render_to(&mut self, target: &RenderTarget, draw: impl FnOnce()) -> Result<(), EnumRendererError> {
+    target.bind()?;
+    draw();
+    return target.unbind();
+  }
+  
+  /// Draw the scene into `target` instead of the window on every rendered frame, until set back to `None`. Split-screen
+  /// viewports are ignored in the meantime, and debug lines, text and post-processing keep going to the window. Returns
+  /// the previous target, if any.
+  pub fn set_render_target(&mut self, target: Option<RenderTarget>) -> Option<RenderTarget> {
+    return std::mem::replace(&mut self.m_render_target, target);
+  }
+  
+  pub fn get_render_target(&self) -> Option<&RenderTarget> {
+    return self.m_render_target.as_ref();
+  }
+  
+  /// Lines to draw on top of the scene this frame, through the main camera. Cleared after every rendered frame.
+  pub fn get_debug_draw_mut(&mut self) -> &mut DebugDraw {
+    return &mut self.m_debug_draw;
+  }
+  
+  /// Queue `text` to be drawn this frame with `font`, `position` being its top left corner in pixels from the top left
+  /// corner of the window, and `size` the height of a line in pixels. Newlines start a new line, and characters outside
+  /// of printable ASCII show up as '?'. OpenGL only.
+  pub fn draw_text(&mut self, font: &mut Font, text: &str, position: Vec2<f32>, size: f32, color: Color) -> Result<(), EnumRendererError> {
+    if self.m_type != EnumRendererApi::OpenGL {
+      log!(EnumLogColor::Yellow, "WARN", "[Renderer] -->\t Text drawing is only supported with OpenGL for now!");
+      return Err(EnumRendererError::NotImplemented);
+    }
+    let texture_id: u32 = font.get_or_create_texture()?;
+    self.m_text_draw.queue(texture_id, font.layout(text, position, size, color));
+    return Ok(());
+  }
+  
+  /// Set the chain of fullscreen passes to run on every rendered frame, replacing any previous one. OpenGL only.
+  pub fn set_post_process(&mut self, chain: PostProcessChain) {
+    self.clear_post_process();
+    self.m_post_process = Some(chain);
+  }
+  
+  pub fn get_post_process_mut(&mut self) -> Option<&mut PostProcessChain> {
+    return self.m_post_process.as_mut();
+  }
+  
+  pub fn clear_post_process(&mut self) {
+    if let Some(mut chain) = self.m_post_process.take() {
+      if let Err(_err) = chain.free() {
+        log!(EnumLogColor::Red, "ERROR", "[Renderer] -->\t Error while freeing post-processing chain : {0}", _err);
+      }
+    }
+    self.m_post_process_input = None;
+  }
+  
+  /// Copy the rendered frame into an offscreen target, run it through the post-processing chain and write the result
+  /// back to the window, right before it gets presented.
+  pub(crate) fn apply_post_process(&mut self) -> Result<(), EnumRendererError> {
+    if self.m_type != EnumRendererApi::OpenGL || self.m_state == EnumRendererState::Deleted {
+      return Ok(());
+    }
+    let Some(chain) = self.m_post_process.as_mut() else {
+      return Ok(());
+    };
+    if chain.is_empty() {
+      return Ok(());
+    }
+    
+    let (width, height) = Engine::get_active_window().get_framebuffer_size();
+    if width == 0 || height == 0 {
+      return Ok(());
+    }
+    match self.m_post_process_input.as_mut() {
+      Some(input) => input.resize(width, height)?,
+      None => {
+        self.m_post_process_input = Some(RenderTarget::new(EnumRendererApi::OpenGL, width, height,
+          &[texture::EnumTextureFormat::Rgba], false)?);
+      }
+    }
+    
+    let input = self.m_post_process_input.as_ref().unwrap();
+    input.copy_from_window()?;
+    let output = chain.apply(input)?;
+    return output.copy_to_window();
+  }
+  
+  /// Retrieve the statistics of the last rendered frame. Always zeroed if the `debug` feature is disabled.
+  pub fn get_stats(&self) -> RendererStats {
+    return self.m_last_frame_stats;
+  }
+  
+  /// Number of primitives skipped by frustum culling in the last rendered frame, regardless of the `debug` feature.
+  pub fn get_culled_count(&self) -> u32 {
+    return self.m_last_frame_stats.m_culled_primitives;
   }
   
   // pub fn enable(&mut self, feature: EnumRendererOption) -> Result<(), EnumRendererError> {
@@ -577,6 +1107,56 @@ impl<'a> Renderer {
     return self.m_api.enqueue(r_entity, shader_associated);
   }
   
+  pub fn apply_batch(&mut self, r_entities: &mut [REntity], shader_associated: &mut Shader) -> Result<(), EnumRendererError> {
+    let mut new_id = 0;
+    for r_entity in r_entities.iter_mut() {
+      while self.m_ids.contains(&new_id) {
+        new_id += 1;
+      }
+      r_entity.m_renderer_id = new_id;
+      self.m_ids.push(new_id);
+    }
+    
+    // Let the api coalesce buffer allocations for the whole batch, instead of growing them entity per entity.
+    self.m_api.enqueue_batch(r_entities, shader_associated)?;
+    
+    for r_entity in r_entities.iter_mut() {
+      r_entity.mark_as_sent();
+    }
+    return Ok(());
+  }
+  
+  pub fn get_buffer_allocation_count(&self) -> usize {
+    return self.m_api.get_buffer_allocation_count();
+  }
+  
+  pub fn enqueue_instanced(&mut self, r_entity: &mut REntity, shader_associated: &mut Shader, transforms: &[Mat4]) -> Result<(), EnumRendererError> {
+    // Keep the same renderer id if the entity's instances are simply being updated.
+    if !self.m_ids.contains(&r_entity.m_renderer_id) {
+      let mut new_id = 0;
+      while self.m_ids.contains(&new_id) {
+        new_id += 1;
+      }
+      r_entity.m_renderer_id = new_id;
+      self.m_ids.push(new_id);
+    }
+    return self.m_api.enqueue_instanced(r_entity, shader_associated, transforms);
+  }
+  
+  pub(crate) fn relink_shader(&mut self, old_shader_id: u32, new_shader_id: u32) {
+    self.m_api.relink_shader(old_shader_id, new_shader_id);
+  }
+  
+  /// Size in bytes of the per-instance transform buffer of an instanced entity, if any.
+  pub fn get_instance_buffer_size(&self, entity_uuid: u64) -> Option<usize> {
+    return self.m_api.get_instance_buffer_size(entity_uuid);
+  }
+  
+  /// Overwrite the transforms of already enqueued instances, starting at `first_instance`, leaving the others as is.
+  pub fn update_instance_transforms(&mut self, entity_uuid: u64, first_instance: usize, transforms: &[Mat4]) -> Result<(), EnumRendererError> {
+    return self.m_api.update_instance_transforms(entity_uuid, first_instance, transforms);
+  }
+  
   pub fn dequeue(&mut self, id: u64, _primitive_index_selected: Option<usize>) -> Result<(), EnumRendererError> {
     return self.m_api.dequeue(id);
   }
@@ -585,6 +1165,248 @@ impl<'a> Renderer {
     return self.m_api.update_ubo_camera(view, projection);
   }
   
+  /// Change the color the window is cleared with, from the next frame on.
+  pub fn set_clear_color(&mut self, red: f32, green: f32, blue: f32, alpha: f32) -> Result<(), EnumRendererError> {
+    let hint = EnumRendererHint::ClearColor([red, green, blue, alpha]);
+    self.m_api.toggle_options(&vec![hint])?;
+    
+    self.set_hint(hint);
+    return Ok(());
+  }
+  
+  /// Change the comparison used by the depth test, from the next draw on.
+  pub fn set_depth_func(&mut self, func: EnumRendererDepthFunc) -> Result<(), EnumRendererError> {
+    let hint = EnumRendererHint::DepthFunc(func);
+    self.m_api.toggle_options(&vec![hint])?;
+    
+    self.set_hint(hint);
+    return Ok(());
+  }
+  
+  pub fn get_depth_func(&self) -> EnumRendererDepthFunc {
+    return self.m_hints.iter()
+      .find_map(|hint| match hint {
+        EnumRendererHint::DepthFunc(func) => Some(*func),
+        _ => None
+      })
+      .unwrap_or_default();
+  }
+  
+  /// Enable or disable writing to the depth buffer, from the next draw on. Clears and shadow passes still write depth.
+  pub fn set_depth_write(&mut self, enabled: bool) -> Result<(), EnumRendererError> {
+    let hint = EnumRendererHint::DepthWrite(enabled);
+    self.m_api.toggle_options(&vec![hint])?;
+    
+    self.set_hint(hint);
+    return Ok(());
+  }
+  
+  pub fn is_depth_write(&self) -> bool {
+    return !self.m_hints.contains(&EnumRendererHint::DepthWrite(false));
+  }
+  
+  pub fn get_clear_color(&self) -> [f32; 4] {
+    return self.m_hints.iter()
+      .find_map(|hint| match hint {
+        EnumRendererHint::ClearColor(rgba) => Some(*rgba),
+        _ => None
+      })
+      .unwrap_or(C_DEFAULT_CLEAR_COLOR);
+  }
+  
+  /// Read back the last frame presented to the window as RGBA8 pixels, row by row from the top left corner. Headless
+  /// windows never present, so the frame being rendered is read instead. OpenGL only.
+  pub fn capture_frame(&mut self) -> Result<Vec<u8>, EnumRendererError> {
+    let window = Engine::get_active_window();
+    let (width, height) = window.get_framebuffer_size();
+    return self.m_api.capture_frame(width, height, !window.is_headless());
+  }
+  
+  /// Save the frame returned by [Self::capture_frame] to a PNG file at `file_path`.
+  pub fn save_screenshot(&mut self, file_path: &str) -> Result<(), EnumRendererError> {
+    let (width, height) = Engine::get_active_window().get_framebuffer_size();
+    let pixels = self.capture_frame()?;
+    
+    if let Err(_err) = image::save_buffer(file_path, &pixels, width, height, image::ColorType::Rgba8) {
+      log!(EnumLogColor::Red, "ERROR", "[Renderer] -->\t Cannot save screenshot to {0}! Error => {1}", file_path, _err);
+      return Err(EnumRendererError::ScreenshotError);
+    }
+    log!(EnumLogColor::Green, "INFO", "[Renderer] -->\t Saved screenshot ({0}x{1}) to {2}", width, height, file_path);
+    return Ok(());
+  }
+  
+  /// Set the skinning matrices of the entity `entity_uuid`, read by skinned shaders from the `ubo_bones` block, see
+  /// [crate::assets::skeleton::C_BONES_UBO_BINDING]. Each entity keeps its own pose, uploaded to the block right
+  /// before the entity gets drawn.
+  pub fn update_ubo_bones(&mut self, entity_uuid: u64, bone_matrices: &[Mat4]) -> Result<(), EnumRendererError> {
+    return self.m_api.update_ubo_bones(entity_uuid, bone_matrices);
+  }
+  
+  /// Upload the lights of the scene, replacing the previous ones. Shaders read them from the `ubo_lights` block, whose
+  /// layout is documented in [EnumLight::pack]. Lights past [light::C_MAX_DIRECTIONAL_LIGHTS] directional and
+  /// [light::C_MAX_POINT_LIGHTS] point lights are ignored.
+  pub fn submit_lights(&mut self, lights: &[EnumLight]) -> Result<(), EnumRendererError> {
+    self.m_api.update_ubo_lights(&EnumLight::pack(lights))?;
+    
+    log!(EnumLogColor::Blue, "INFO", "[Renderer] -->\t {0} light(s) submitted", lights.len());
+    return Ok(());
+  }
+  
+  /// Render the depth of all shadow casting entities from a directional light into the shadow map, fitting the light's
+  /// orthographic view around the scene bounds given. The main shader then samples it to shade what the light can't see.
+  /// Call it again whenever the light or the scene moves. OpenGL only.
+  pub fn render_shadow_pass(&mut self, light: &EnumLight, scene_bounds: &Aabb) -> Result<(), EnumRendererError> {
+    let Some((light_view, light_projection)) = light.get_light_space_matrices(scene_bounds) else {
+      log!(EnumLogColor::Yellow, "WARN", "[Renderer] -->\t Cannot render shadow pass, only directional lights cast shadows!");
+      return Err(EnumRendererError::NotImplemented);
+    };
+    
+    let resolution = self.get_shadow_map_resolution();
+    match self.m_shadow_map.as_mut() {
+      Some(shadow_map) => shadow_map.resize(resolution, resolution)?,
+      None => self.m_shadow_map = Some(RenderTarget::new(self.m_type, resolution, resolution, &[], true)?),
+    }
+    
+    self.m_api.render_shadow_depth(light_view, light_projection, self.m_shadow_map.as_ref().unwrap())?;
+    self.m_light_space_matrix = Some(light_projection * light_view);
+    return Ok(());
+  }
+  
+  /// Depth texture of the last shadow pass, if any. The texture remains owned by the renderer.
+  pub fn get_shadow_map_texture(&self) -> Option<Texture> {
+    return self.m_shadow_map.as_ref().and_then(|shadow_map| shadow_map.depth_texture_handle());
+  }
+  
+  /// Matrix transforming world positions into the shadow map's clip space, as of the last shadow pass.
+  pub fn get_light_space_matrix(&self) -> Option<Mat4> {
+    return self.m_light_space_matrix;
+  }
+  
+  pub fn get_shadow_map_resolution(&self) -> u32 {
+    return self.m_hints.iter()
+      .find_map(|hint| match hint {
+        EnumRendererHint::ShadowMapResolution(resolution) => Some(*resolution),
+        _ => None
+      })
+      .filter(|resolution| *resolution > 0)
+      .unwrap_or(2048);
+  }
+  
+  pub fn submit_camera(&mut self, camera: Camera) -> Result<(), EnumRendererError> {
+    // Upload the new view-projection right away, to avoid rendering a frame with the previous camera.
+    self.m_api.update_ubo_camera(camera.get_view_matrix(), camera.get_projection_matrix())?;
+    self.m_camera = Some(camera);
+    
+    log!(EnumLogColor::Blue, "INFO", "[Renderer] -->\t Camera submitted, now active");
+    return Ok(());
+  }
+  
+  /// Render the scene once per camera, each in its own region of the window given as (x, y, width, height) in pixels
+  /// from the top left corner, i.e. for split-screen or multiple editor views. Input events go to the camera of the
+  /// viewport under the cursor. Replaces any previous viewports, and an empty vector goes back to rendering the whole
+  /// window with the active camera.
+  pub fn set_viewports(&mut self, viewports: Vec<(Camera, (u32, u32, u32, u32))>) -> Result<(), EnumRendererError> {
+    let was_split = !self.m_viewports.is_empty();
+    self.m_viewports = viewports;
+    self.m_focused_viewport = None;
+    
+    // Match each camera's aspect ratio to its own region rather than the whole window.
+    for (camera, (_, _, width, height)) in self.m_viewports.iter_mut() {
+      let _ = camera.on_event(&events::EnumEvent::FramebufferEvent(*width, *height));
+    }
+    
+    if was_split && self.m_viewports.is_empty() && self.m_state != EnumRendererState::Deleted {
+      let (width, height) = Engine::get_active_window().get_framebuffer_size();
+      self.m_api.set_viewport((0, 0, width, height), false)?;
+      if let Some(camera) = self.m_camera.as_ref() {
+        self.m_api.update_ubo_camera(camera.get_view_matrix(), camera.get_projection_matrix())?;
+      }
+    }
+    
+    log!(EnumLogColor::Blue, "INFO", "[Renderer] -->\t {0} viewport(s) now active", self.m_viewports.len());
+    return Ok(());
+  }
+  
+  pub fn get_viewport_count(&self) -> usize {
+    return self.m_viewports.len();
+  }
+  
+  pub fn get_viewport_camera_mut(&mut self, viewport_index: usize) -> Option<&mut Camera> {
+    return self.m_viewports.get_mut(viewport_index).map(|(camera, _)| camera);
+  }
+  
+  /// Index of the viewport containing a point in window pixels from the top left corner, if any.
+  pub fn get_viewport_at(&self, cursor: Vec2<f32>) -> Option<usize> {
+    if cursor.x < 0.0 || cursor.y < 0.0 {
+      return None;
+    }
+    return self.m_viewports.iter()
+      .position(|(_, (x, y, width, height))| {
+        cursor.x >= *x as f32 && cursor.x < (*x + *width) as f32 && cursor.y >= *y as f32 && cursor.y < (*y + *height) as f32
+      });
+  }
+  
+  /// Forward an event to the active camera, or, when split into viewports, to the camera of the viewport under the
+  /// cursor.
+  pub(crate) fn on_camera_event(&mut self, event: &events::EnumEvent) -> Result<bool, EnumEngineError> {
+    if !self.m_viewports.is_empty() {
+      match event {
+        events::EnumEvent::FramebufferEvent(_, _) => {
+          for (camera, (_, _, width, height)) in self.m_viewports.iter_mut() {
+            camera.on_event(&events::EnumEvent::FramebufferEvent(*width, *height))?;
+          }
+        }
+        // Releasing a button ends drags started in any viewport, wherever the cursor ended up.
+        events::EnumEvent::MouseBtnEvent(_, input::EnumAction::Released, _) => {
+          let mut handled = false;
+          for (camera, _) in self.m_viewports.iter_mut() {
+            handled |= camera.on_event(event)?;
+          }
+          return Ok(handled);
+        }
+        events::EnumEvent::KeyEvent(_, _, _, _) | events::EnumEvent::MouseBtnEvent(_, _, _) |
+        events::EnumEvent::MouseScrollEvent(_, _) | events::EnumEvent::CharEvent(_) => {
+          let cursor = input::Input::get_mouse_cursor_position(Engine::get_active_window()).unwrap_or(Vec2::default());
+          self.m_focused_viewport = self.get_viewport_at(cursor);
+          
+          return match self.m_focused_viewport {
+            Some(viewport_index) => self.m_viewports[viewport_index].0.on_event(event),
+            None => Ok(false)
+          };
+        }
+        _ => {}
+      }
+    }
+    
+    if let Some(camera) = self.m_camera.as_mut() {
+      return camera.on_event(event);
+    }
+    return Ok(false);
+  }
+  
+  pub fn get_active_camera(&self) -> Option<&Camera> {
+    return self.m_camera.as_ref();
+  }
+  
+  pub fn get_active_camera_mut(&mut self) -> Option<&mut Camera> {
+    return self.m_camera.as_mut();
+  }
+  
+  pub fn on_update(&mut self, time_step: f64) {
+    // Take the camera out while updating it, since it uploads its matrices through the active renderer.
+    if let Some(mut camera) = self.m_camera.take() {
+      camera.on_update(time_step);
+      self.m_camera = Some(camera);
+    }
+    
+    // Only the viewport last interacted with responds to held keys and drags.
+    if let Some(viewport_index) = self.m_focused_viewport {
+      if let Some((camera, _)) = self.m_viewports.get_mut(viewport_index) {
+        camera.on_update(time_step);
+      }
+    }
+  }
+  
   pub fn update_ubo_model(&mut self, model_transform: Mat4, entity_uuid: u64, instance_offset: Option<usize>, instance_count: usize) -> Result<(), EnumRendererError> {
     return self.m_api.update_ubo_model(model_transform, entity_uuid, instance_offset, instance_count);
   }
@@ -600,7 +1422,8 @@ impl<'a> Renderer {
 
 impl Display for Renderer {
   fn fmt(&self, format: &mut Formatter<'_>) -> std::fmt::Result {
-    write!(format, "\nState =>\t\t {0:#?};\n{1}", self.m_state, self.m_api.to_string())
+    write!(format, "\nState =>\t\t {0:#?};\n{1}\n{2}", self.m_state, self.m_api.to_string(),
+      self.m_api.get_capabilities())
   }
 }
 
@@ -621,4 +1444,140 @@ impl Drop for Renderer {
       }
     }
   }
-}
\ No newline at end of file
+}
+
+/// Collects renderer hints through chained calls on top of the default ones, validates them, and sets them all on a
+/// new, unapplied [Renderer] in one go.
+///
+/// ### Example:
+/// ```text
+/// let renderer = RendererBuilder::new(EnumRendererApi::OpenGL)
+///   .msaa(8)
+///   .optimization(EnumRendererOptimizationMode::MinimizeDrawCalls)
+///   .build()?;
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct RendererBuilder {
+  m_api: EnumRendererApi,
+  m_hints: Vec<EnumRendererHint>,
+}
+
+impl Default for RendererBuilder {
+  fn default() -> Self {
+    return Self::new(EnumRendererApi::default());
+  }
+}
+
+impl RendererBuilder {
+  pub fn new(api: EnumRendererApi) -> Self {
+    return Self {
+      m_api: api,
+      m_hints: Vec::new(),
+    };
+  }
+  
+  pub fn api(mut self, api: EnumRendererApi) -> Self {
+    self.m_api = api;
+    return self;
+  }
+  
+  /// Add any hint, replacing a previous hint of the same kind.
+  pub fn hint(mut self, hint: EnumRendererHint) -> Self {
+    self.m_hints.retain(|existing| !existing.is_equivalent(&hint));
+    self.m_hints.push(hint);
+    return self;
+  }
+  
+  pub fn msaa(self, samples: u8) -> Self {
+    return self.hint(EnumRendererHint::MSAA((samples > 1).then_some(samples)));
+  }
+  
+  pub fn depth_test(self, flag: bool) -> Self {
+    return self.hint(EnumRendererHint::DepthTest(flag));
+  }
+  
+  pub fn depth_func(self, func: EnumRendererDepthFunc) -> Self {
+    return self.hint(EnumRendererHint::DepthFunc(func));
+  }
+  
+  pub fn depth_write(self, flag: bool) -> Self {
+    return self.hint(EnumRendererHint::DepthWrite(flag));
+  }
+  
+  pub fn cull_facing(self, cull: Option<EnumRendererCull>) -> Self {
+    return self.hint(EnumRendererHint::CullFacing(cull));
+  }
+  
+  pub fn srgb(self, flag: bool) -> Self {
+    return self.hint(EnumRendererHint::SRGB(flag));
+  }
+  
+  pub fn blending(self, factors: Option<(EnumRendererBlendingFactor, EnumRendererBlendingFactor)>) -> Self {
+    return self.hint(EnumRendererHint::Blending(factors));
+  }
+  
+  pub fn optimization(self, mode: EnumRendererOptimizationMode) -> Self {
+    return self.hint(EnumRendererHint::Optimization(mode));
+  }
+  
+  pub fn api_call_checking(self, mode: EnumRendererCallCheckingMode) -> Self {
+    return self.hint(EnumRendererHint::ApiCallChecking(mode));
+  }
+  
+  pub fn frustum_culling(self, flag: bool) -> Self {
+    return self.hint(EnumRendererHint::FrustumCulling(flag));
+  }
+  
+  pub fn clear_color(self, red: f32, green: f32, blue: f32, alpha: f32) -> Self {
+    return self.hint(EnumRendererHint::ClearColor([red, green, blue, alpha]));
+  }
+  
+  pub fn shadow_map_resolution(self, resolution: u32) -> Self {
+    return self.hint(EnumRendererHint::ShadowMapResolution(resolution));
+  }
+  
+  pub fn force_api_version(self, version: u32) -> Self {
+    return self.hint(EnumRendererHint::ForceApiVersion(version));
+  }
+  
+  pub fn get_hints(&self) -> &Vec<EnumRendererHint> {
+    return &self.m_hints;
+  }
+  
+  /// Check the collected hints for values the renderer cannot honor, such as a sample count that is not a power of
+  /// two or an empty shadow map.
+  pub fn validate(&self) -> Result<(), EnumRendererError> {
+    for hint in self.m_hints.iter() {
+      match hint {
+        EnumRendererHint::MSAA(Some(samples)) if !samples.is_power_of_two() => {
+          log!(EnumLogColor::Red, "ERROR", "[Renderer] -->\t Cannot build renderer, {0} MSAA samples is not a power \
+          of two!", samples);
+          return Err(EnumRendererError::InvalidHint);
+        }
+        EnumRendererHint::ShadowMapResolution(0) => {
+          log!(EnumLogColor::Red, "ERROR", "[Renderer] -->\t Cannot build renderer, shadow map resolution is empty!");
+          return Err(EnumRendererError::InvalidHint);
+        }
+        EnumRendererHint::ClearColor(rgba) if !rgba.iter().all(|channel| (0.0..=1.0).contains(channel)) => {
+          log!(EnumLogColor::Red, "ERROR", "[Renderer] -->\t Cannot build renderer, clear color {0:?} has channels \
+          outside [0, 1]!", rgba);
+          return Err(EnumRendererError::InvalidHint);
+        }
+        _ => {}
+      }
+    }
+    return Ok(());
+  }
+  
+  /// Validate the hints and create an unapplied renderer with the default hints, overridden by the collected ones.
+  pub fn build(self) -> Result<Renderer, EnumRendererError> {
+    self.validate()?;
+    
+    let mut renderer = Renderer::new(self.m_api);
+    renderer.reset_hints();
+    for hint in self.m_hints.into_iter() {
+      renderer.set_hint(hint);
+    }
+    return Ok(renderer);
+  }
+}
@@ -24,6 +24,8 @@
 
 use std::collections::HashSet;
 use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+use std::time::SystemTime;
 
 use crate::{Engine, TraitApply, TraitFree, TraitHint};
 use crate::graphics::open_gl;
@@ -65,6 +67,11 @@ pub enum EnumShaderError {
   ShaderBinaryError,
   InvalidShaderSource,
   InvalidFileOperation,
+  /// Compute stages can only be linked on their own, without any raster stage.
+  MixedComputeStages,
+  NotComputeShader,
+  /// A stage failed to compile, with the driver's info log explaining why.
+  CompileFailed { stage: EnumShaderStageType, log: String },
   IoError(std::io::ErrorKind),
   OpenGLShaderError(open_gl::shader::EnumError),
   #[cfg(feature = "vulkan")]
@@ -94,6 +101,44 @@ pub enum EnumShaderStageType {
   Compute = gl::COMPUTE_SHADER,
 }
 
+/// Type of an active uniform, as reported by the api after linking.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum EnumUniformType {
+  Bool,
+  Int,
+  UInt,
+  Float,
+  Double,
+  Vec2,
+  Vec3,
+  Vec4,
+  IVec2,
+  IVec3,
+  IVec4,
+  Mat3,
+  Mat4,
+  Sampler2D,
+  Sampler2DArray,
+  Sampler2DShadow,
+  SamplerCube,
+  /// Any other type, with its raw api enum value.
+  Other(u32),
+}
+
+/// What needs to see the writes of a compute dispatch, see [Shader::memory_barrier].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum EnumShaderBarrier {
+  /// Storage buffers read by later shaders.
+  StorageBuffer,
+  /// Storage buffers used as vertex attributes by later draws.
+  VertexAttribute,
+  /// Storage buffers used as indirect draw or dispatch commands.
+  Command,
+  /// Storage buffers read back on the CPU, see [Shader::read_storage_buffer].
+  BufferUpdate,
+  All,
+}
+
 #[derive(Debug, Clone, PartialOrd, PartialEq, Hash)]
 pub enum EnumShaderSource {
   FromFile(String),
@@ -117,6 +162,9 @@ impl Default for EnumShaderProfile {
 pub enum EnumShaderHint {
   ForceProfile(EnumShaderProfile),
   ForceGlslVersion(u32),
+  /// First of the four consecutive vertex attribute locations holding the per-instance model matrix, used when
+  /// rendering instanced entities (i.e. `layout (location = 7) in mat4 in_instance_matrix;`).
+  InstanceMatrixLocation(u32),
 }
 
 impl EnumShaderHint {
@@ -222,7 +270,16 @@ pub trait TraitShader {
   fn apply(&mut self) -> Result<(), EnumShaderError>;
   fn to_string(&self) -> String;
   fn upload_data(&mut self, uniform_name: &'static str, uniform: &dyn std::any::Any) -> Result<(), EnumShaderError>;
+  fn bind_uniform_block(&mut self, block_name: &str, binding: u32) -> Result<(), EnumShaderError>;
+  fn create_uniform_block(&mut self, block_name: &str, binding: u32, size: usize) -> Result<(), EnumShaderError>;
+  fn update_uniform_block(&mut self, binding: u32, offset: usize, data: &[u8]) -> Result<(), EnumShaderError>;
   fn get_id(&self) -> u32;
+  fn get_active_uniforms(&self) -> Vec<(String, EnumUniformType)>;
+  fn create_storage_buffer(&mut self, binding: u32, size: usize) -> Result<(), EnumShaderError>;
+  fn update_storage_buffer(&mut self, binding: u32, offset: usize, data: &[u8]) -> Result<(), EnumShaderError>;
+  fn read_storage_buffer(&self, binding: u32, offset: usize, size: usize) -> Result<Vec<u8>, EnumShaderError>;
+  fn dispatch(&mut self, group_count_x: u32, group_count_y: u32, group_count_z: u32) -> Result<(), EnumShaderError>;
+  fn memory_barrier(&self, barrier: EnumShaderBarrier) -> Result<(), EnumShaderError>;
   fn get_api_handle(&self) -> &dyn std::any::Any;
   fn free(&mut self) -> Result<(), EnumShaderError>;
 }
@@ -297,6 +354,9 @@ pub struct Shader {
   m_api_data: Box<dyn TraitShader>,
   m_hints: Vec<EnumShaderHint>,
   m_stages: Vec<ShaderStage>,
+  m_watched_sources: Vec<(PathBuf, SystemTime)>,
+  m_uniform_blocks: Vec<(String, u32, usize)>,
+  m_block_bindings: Vec<(String, u32)>,
 }
 
 impl TraitHint<EnumShaderHint> for Shader {
@@ -321,10 +381,18 @@ impl TraitApply<EnumShaderError> for Shader {
       return Err(EnumShaderError::NoStagesProvided);
     }
     
+    if self.is_compute() && self.m_stages.len() > 1 {
+      log!(EnumLogColor::Red, "ERROR", "[Shader] -->\t Cannot create shader : Compute stages cannot be linked with \
+      other stages!");
+      return Err(EnumShaderError::MixedComputeStages);
+    }
+    
     for hint in self.m_hints.iter() {
       match hint {
         EnumShaderHint::ForceProfile(profile) => Self::load_profile(&mut self.m_stages, *profile)?,
-        EnumShaderHint::ForceGlslVersion(version) => Self::target_version(&mut self.m_stages, *version)?
+        EnumShaderHint::ForceGlslVersion(version) => Self::target_version(&mut self.m_stages, *version)?,
+        // Only relevant when enqueuing instanced entities.
+        EnumShaderHint::InstanceMatrixLocation(_) => {}
       }
     }
     
@@ -444,6 +512,9 @@ impl Default for Shader {
       m_shader_lang: EnumShaderLanguage::Glsl,
       m_api_data: Box::new(GlShader::new(vec![])),
       m_hints: Vec::with_capacity(3),
+      m_watched_sources: Vec::new(),
+      m_uniform_blocks: Vec::new(),
+      m_block_bindings: Vec::new(),
       m_stages: vec![ShaderStage::default_for(EnumShaderStageType::Vertex),
         ShaderStage::default_for(EnumShaderStageType::Fragment), ShaderStage::default_for(EnumShaderStageType::Geometry)],
     };
@@ -461,6 +532,9 @@ impl Shader {
           m_shader_lang: EnumShaderLanguage::Glsl,
           m_api_data: Box::new(GlShader::new(vec![])),
           m_hints: Vec::with_capacity(3),
+          m_watched_sources: Vec::new(),
+          m_uniform_blocks: Vec::new(),
+          m_block_bindings: Vec::new(),
           m_stages: Vec::from_iter(shader_stages_info.into_iter()),
         }
       }
@@ -472,12 +546,24 @@ impl Shader {
           m_shader_lang: EnumShaderLanguage::Glsl,
           m_api_data: Box::new(VkShader::new(vec![])),
           m_hints: Vec::with_capacity(3),
+          m_watched_sources: Vec::new(),
+          m_uniform_blocks: Vec::new(),
+          m_block_bindings: Vec::new(),
           m_stages: Vec::from_iter(shader_stages_info.into_iter()),
         }
       }
     }
   }
   
+  /// Shader made of a single compute stage, to run with [Self::dispatch] instead of being drawn with.
+  pub fn new_compute(api_chosen: EnumRendererApi, compute_stage: ShaderStage) -> Self {
+    return Shader::new(api_chosen, HashSet::from([compute_stage]));
+  }
+  
+  pub fn is_compute(&self) -> bool {
+    return self.m_stages.iter().any(|stage| stage.m_stage == EnumShaderStageType::Compute);
+  }
+  
   pub fn push_stage(&mut self, shader_stage: ShaderStage) -> Result<(), EnumShaderError> {
     if self.m_stages.contains(&shader_stage) {
       return Err(EnumShaderError::StageAlreadyProvided);
@@ -633,6 +719,13 @@ impl Shader {
     return self.m_version;
   }
   
+  pub fn get_instance_matrix_location(&self) -> Option<u32> {
+    return self.m_hints.iter().find_map(|hint| match hint {
+      EnumShaderHint::InstanceMatrixLocation(location) => Some(*location),
+      _ => None
+    });
+  }
+  
   fn check_validity(shader_stage: &ShaderStage) -> Result<(), EnumShaderError> {
     match &shader_stage.m_source {
       EnumShaderSource::FromFile(file_path_str) => {
@@ -751,6 +844,68 @@ impl Shader {
     return self.m_api_data.upload_data(uniform_name, uniform);
   }
   
+  /// Read the uniform block `block_name` from whichever buffer is attached at `binding`, i.e. a
+  /// [UniformBuffer](crate::graphics::UniformBuffer) shared with other shaders.
+  ///
+  /// Errors out if no block named `block_name` is active in the linked program.
+  pub fn bind_uniform_block(&mut self, block_name: &str, binding: u32) -> Result<(), EnumShaderError> {
+    self.m_api_data.bind_uniform_block(block_name, binding)?;
+    
+    self.m_block_bindings.retain(|(name, _)| name != block_name);
+    self.m_block_bindings.push((block_name.to_string(), binding));
+    return Ok(());
+  }
+  
+  /// Create a uniform buffer of `size` bytes for the uniform block `block_name`, and bind it at `binding`. Other
+  /// shaders creating a block at the same binding reuse the same buffer, so shared data only needs to be uploaded
+  /// once, through any of them. The buffer is deleted along with the last shader using it.
+  ///
+  /// Errors out if no block named `block_name` is active in the linked program, if `binding` is one the renderer
+  /// fills itself (i.e. [crate::camera::C_CAMERA_UBO_BINDING]), or if the block shared there has a different size.
+  pub fn create_uniform_block(&mut self, block_name: &str, binding: u32, size: usize) -> Result<(), EnumShaderError> {
+    self.m_api_data.create_uniform_block(block_name, binding, size)?;
+    
+    self.m_uniform_blocks.retain(|(_, block_binding, _)| *block_binding != binding);
+    self.m_uniform_blocks.push((block_name.to_string(), binding, size));
+    return Ok(());
+  }
+  
+  pub fn update_uniform_block(&mut self, binding: u32, offset: usize, data: &[u8]) -> Result<(), EnumShaderError> {
+    return self.m_api_data.update_uniform_block(binding, offset, data);
+  }
+  
+  /// Create a storage buffer of `size` bytes, bound at `binding` for any shader declaring a `buffer` block there
+  /// (i.e. `layout (std430, binding = 2) buffer`), so that a compute shader's output can be read by later draws.
+  /// Freed along with this shader.
+  pub fn create_storage_buffer(&mut self, binding: u32, size: usize) -> Result<(), EnumShaderError> {
+    return self.m_api_data.create_storage_buffer(binding, size);
+  }
+  
+  pub fn update_storage_buffer(&mut self, binding: u32, offset: usize, data: &[u8]) -> Result<(), EnumShaderError> {
+    return self.m_api_data.update_storage_buffer(binding, offset, data);
+  }
+  
+  /// Copy `size` bytes of a storage buffer back to the CPU, after a [`BufferUpdate`](EnumShaderBarrier::BufferUpdate)
+  /// barrier if a dispatch wrote to it.
+  pub fn read_storage_buffer(&self, binding: u32, offset: usize, size: usize) -> Result<Vec<u8>, EnumShaderError> {
+    return self.m_api_data.read_storage_buffer(binding, offset, size);
+  }
+  
+  /// Run the compute stage over the given number of work groups on each axis. Writes aren't visible to anything else
+  /// until a [Self::memory_barrier] for it.
+  pub fn dispatch(&mut self, group_count_x: u32, group_count_y: u32, group_count_z: u32) -> Result<(), EnumShaderError> {
+    if !self.is_compute() {
+      log!(EnumLogColor::Red, "ERROR", "[Shader] -->\t Cannot dispatch shader {0}, it has no compute stage!",
+        self.get_id());
+      return Err(EnumShaderError::NotComputeShader);
+    }
+    return self.m_api_data.dispatch(group_count_x, group_count_y, group_count_z);
+  }
+  
+  pub fn memory_barrier(&self, barrier: EnumShaderBarrier) -> Result<(), EnumShaderError> {
+    return self.m_api_data.memory_barrier(barrier);
+  }
+  
   pub fn get_api(&self) -> &dyn TraitShader {
     return self.m_api_data.as_ref();
   }
@@ -759,6 +914,101 @@ impl Shader {
     return self.m_api_data.get_id();
   }
   
+  /// List the uniforms the linked program actually uses (unused ones get optimized out by the driver), outside of
+  /// uniform blocks. Arrays are reported once, with their first element's name (i.e. `u_values[0]`).
+  pub fn get_active_uniforms(&self) -> Vec<(String, EnumUniformType)> {
+    return self.m_api_data.get_active_uniforms();
+  }
+  
+  /// Start watching the files of all stages loaded [`FromFile`](EnumShaderSource::FromFile), to be able to reload
+  /// them with [`reload_if_changed`](Self::reload_if_changed).
+  pub fn watch_sources(&mut self) {
+    self.m_watched_sources = self.m_stages.iter()
+      .filter_map(|stage| stage.m_source.get_file_path())
+      .filter_map(|file_path_str| {
+        let modified_time = std::fs::metadata(file_path_str).and_then(|metadata| metadata.modified());
+        return match modified_time {
+          Ok(time) => Some((PathBuf::from(file_path_str), time)),
+          Err(_err) => {
+            log!(EnumLogColor::Yellow, "WARN", "[Shader] -->\t Cannot watch shader source '{0}', Error => {1}",
+              file_path_str, _err);
+            None
+          }
+        };
+      })
+      .collect();
+    
+    log!(EnumLogColor::Blue, "INFO", "[Shader] -->\t Watching {0} shader source(s) for changes",
+      self.m_watched_sources.len());
+  }
+  
+  /// Recompile and relink the shader if one of its watched sources changed since the last check. Meant to be
+  /// called periodically, like in a layer's `on_update`.
+  ///
+  /// If the new sources fail to compile or link, the error is logged and the previous program stays in use, so
+  /// the sources can be fixed and saved again.
+  ///
+  /// ### Returns:
+  /// - `Ok(true)` if the shader was reloaded successfully.
+  /// - `Ok(false)` if nothing changed, or if the new sources failed to compile.
+  pub fn reload_if_changed(&mut self) -> Result<bool, EnumShaderError> {
+    if self.m_state != EnumShaderState::Sent {
+      return Ok(false);
+    }
+    
+    let mut has_changed = false;
+    for (file_path, last_modified_time) in self.m_watched_sources.iter_mut() {
+      // Editors often save by replacing the file, so it might briefly be missing. Try again on the next check.
+      let modified_time = match std::fs::metadata(&file_path).and_then(|metadata| metadata.modified()) {
+        Ok(time) => time,
+        Err(_) => continue
+      };
+      if modified_time != *last_modified_time {
+        *last_modified_time = modified_time;
+        has_changed = true;
+      }
+    }
+    
+    if !has_changed {
+      return Ok(false);
+    }
+    
+    log!(EnumLogColor::Purple, "INFO", "[Shader] -->\t Shader source(s) changed, reloading shader {0}...", self.get_id());
+    
+    let mut new_api_data: Box<dyn TraitShader> = match self.m_api {
+      EnumRendererApi::OpenGL => Box::new(GlShader::new(self.m_stages.clone())),
+      #[cfg(feature = "vulkan")]
+      EnumRendererApi::Vulkan => Box::new(VkShader::new(self.m_stages.clone())),
+      #[cfg(not(feature = "vulkan"))]
+      EnumRendererApi::Vulkan => return Err(EnumShaderError::InvalidApi),
+    };
+    
+    if let Err(_err) = new_api_data.apply() {
+      log!(EnumLogColor::Red, "ERROR", "[Shader] -->\t Cannot reload shader {0}, keeping previous program! \
+      Error => {1}", self.get_id(), _err);
+      new_api_data.free()?;
+      return Ok(false);
+    }
+    
+    let old_id = self.get_id();
+    self.m_api_data.free()?;
+    self.m_api_data = new_api_data;
+    
+    // Uniform blocks are recreated empty, and need to be updated again.
+    for (block_name, binding) in self.m_block_bindings.iter() {
+      self.m_api_data.bind_uniform_block(block_name, *binding)?;
+    }
+    for (block_name, binding, size) in self.m_uniform_blocks.iter() {
+      self.m_api_data.create_uniform_block(block_name, *binding, *size)?;
+    }
+    
+    // Make sure entities already enqueued with the old program are drawn with the new one.
+    Engine::get_active_renderer().relink_shader(old_id, self.get_id());
+    
+    log!(EnumLogColor::Green, "INFO", "[Shader] -->\t Reloaded shader {0} (now {1}) successfully", old_id, self.get_id());
+    return Ok(true);
+  }
+  
   pub fn get_lang(&self) -> EnumShaderLanguage {
     return self.m_shader_lang;
   }
@@ -0,0 +1,148 @@
+/*
+ MIT License
+ 
+ Copyright (c) 2024 Nami Reghbati
+ 
+ Permission is hereby granted, free of charge, to any person obtaining a copy
+ of this software and associated documentation files (the "Software"), to deal
+ in the Software without restriction, including without limitation the rights
+ to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ copies of the Software, and to permit persons to whom the Software is
+ furnished to do so, subject to the following conditions:
+ 
+ The above copyright notice and this permission notice shall be included in all
+ copies or substantial portions of the Software.
+ 
+ THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ FITNESS FOR A PARTICULAR PURPOSE AND NON INFRINGEMENT. IN NO EVENT SHALL THE
+ AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ SOFTWARE.
+*/
+
+#[cfg(feature = "debug")]
+use crate::Engine;
+use crate::TraitFree;
+use crate::assets::r_assets::{EnumAssetPrimitiveSurface, REntity, Vertex};
+use crate::graphics::renderer::EnumRendererError;
+use crate::graphics::shader::Shader;
+use crate::math::{Aabb, Mat4, Vec3};
+use crate::utils::macros::logger::*;
+
+/// Static geometry of several entities merged into a single vertex and index buffer, drawn with a single call.
+///
+/// Each entity's transform is baked into its vertices when building the batch, which makes batched entities
+/// immovable: transforming, hiding or freeing the original entities afterward has no effect on the batch, and the
+/// originals should not be applied themselves. Rebuild the batch to move anything in it.
+pub struct StaticBatch {
+  m_entity: REntity,
+  m_entity_count: usize,
+}
+
+impl TraitFree<EnumRendererError> for StaticBatch {
+  fn free(&mut self) -> Result<(), EnumRendererError> {
+    return self.m_entity.free();
+  }
+}
+
+impl StaticBatch {
+  /// Merge the entities' vertices and indices, in world space. Entities must all share the same primitive type, and
+  /// either all be indexed or none of them.
+  pub fn build(entities: &[REntity]) -> Result<Self, EnumRendererError> {
+    if entities.is_empty() {
+      log!(EnumLogColor::Red, "ERROR", "[StaticBatch] -->\t Cannot build a static batch without any entity!");
+      return Err(EnumRendererError::InvalidEntity);
+    }
+    
+    let data_type = entities[0].m_type;
+    let is_indexed = entities[0].get_total_index_count() > 0;
+    if entities.iter().any(|entity| entity.m_type != data_type || (entity.get_total_index_count() > 0) != is_indexed) {
+      log!(EnumLogColor::Red, "ERROR", "[StaticBatch] -->\t Cannot batch entities of different primitive types, or \
+      mix indexed and non-indexed entities!");
+      return Err(EnumRendererError::InvalidEntity);
+    }
+    
+    let vertex_count: usize = entities.iter().map(|entity| entity.get_total_vertex_count()).sum();
+    let index_count: usize = entities.iter().map(|entity| entity.get_total_index_count()).sum();
+    let mut vertices: Vec<Vertex> = Vec::with_capacity(vertex_count);
+    let mut indices: Vec<u32> = Vec::with_capacity(index_count);
+    let mut corners: Vec<Vec3<f32>> = Vec::with_capacity(entities.len() * 8);
+    
+    for entity in entities.iter() {
+      let matrix = entity.get_matrix();
+      // Normals need the inverse transpose, to stay perpendicular under non-uniform scaling.
+      let normal_matrix = matrix.inverse().map(|inverse| inverse.transpose()).unwrap_or(Mat4::default());
+      
+      // Indices of an entity already span all of its sub-meshes, so only offset them by the vertices merged before it.
+      let base_vertex = vertices.len() as u32;
+      
+      for sub_mesh in entity.m_sub_meshes.iter() {
+        for vertex in sub_mesh.get_vertices_ref().iter() {
+          let mut baked_vertex = *vertex;
+          baked_vertex.m_position = Self::transform_point(&matrix, vertex.m_position);
+          baked_vertex.m_normal = Vertex::pack_normal(
+            Self::transform_direction(&normal_matrix, Vertex::unpack_normal(vertex.m_normal)).normalize());
+          baked_vertex.m_tangent = Self::transform_direction(&matrix, vertex.m_tangent);
+          vertices.push(baked_vertex);
+        }
+        indices.extend(sub_mesh.get_indices().iter().map(|index| index + base_vertex));
+      }
+      corners.extend(entity.get_world_bounds().get_corners());
+    }
+    
+    let batch = StaticBatch {
+      m_entity: REntity::from_static_geometry("Static batch", data_type, vertices, indices, Aabb::from_points(&corners)),
+      m_entity_count: entities.len(),
+    };
+    
+    log!(EnumLogColor::Green, "INFO", "[StaticBatch] -->\t Merged {0} entities into {1} vertices and {2} indices",
+      entities.len(), vertex_count, index_count);
+    return Ok(batch);
+  }
+  
+  /// Send the merged geometry to the GPU, as a single entity.
+  pub fn apply(&mut self, shader_associated: &mut Shader) -> Result<(), EnumRendererError> {
+    self.m_entity.apply(shader_associated)?;
+    self.m_entity.show(EnumAssetPrimitiveSurface::Everything);
+    return Ok(());
+  }
+  
+  pub fn get_entity(&self) -> &REntity {
+    return &self.m_entity;
+  }
+  
+  pub fn get_entity_count(&self) -> usize {
+    return self.m_entity_count;
+  }
+  
+  pub fn get_vertex_count(&self) -> usize {
+    return self.m_entity.get_total_vertex_count();
+  }
+  
+  pub fn get_index_count(&self) -> usize {
+    return self.m_entity.get_total_index_count();
+  }
+  
+  /// World bounds of the whole batch.
+  pub fn get_bounds(&self) -> Aabb {
+    return self.m_entity.get_bounds();
+  }
+  
+  fn transform_point(matrix: &Mat4, point: Vec3<f32>) -> Vec3<f32> {
+    return Vec3::new(&[
+      matrix[0][0] * point.x + matrix[0][1] * point.y + matrix[0][2] * point.z + matrix[0][3],
+      matrix[1][0] * point.x + matrix[1][1] * point.y + matrix[1][2] * point.z + matrix[1][3],
+      matrix[2][0] * point.x + matrix[2][1] * point.y + matrix[2][2] * point.z + matrix[2][3]
+    ]);
+  }
+  
+  fn transform_direction(matrix: &Mat4, direction: Vec3<f32>) -> Vec3<f32> {
+    return Vec3::new(&[
+      matrix[0][0] * direction.x + matrix[0][1] * direction.y + matrix[0][2] * direction.z,
+      matrix[1][0] * direction.x + matrix[1][1] * direction.y + matrix[1][2] * direction.z,
+      matrix[2][0] * direction.x + matrix[2][1] * direction.y + matrix[2][2] * direction.z
+    ]);
+  }
+}
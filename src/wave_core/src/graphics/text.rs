@@ -0,0 +1,426 @@
+/*
+ MIT License
+
+ Copyright (c) 2023 Nami Reghbati
+ 
+ Permission is hereby granted, free of charge, to any person obtaining a copy
+ of this software and associated documentation files (the "Software"), to deal
+ in the Software without restriction, including without limitation the rights
+ to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ copies of the Software, and to permit persons to whom the Software is
+ furnished to do so, subject to the following conditions:
+ 
+ The above copyright notice and this permission notice shall be included in all
+ copies or substantial portions of the Software.
+ 
+ THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ FITNESS FOR A PARTICULAR PURPOSE AND NON INFRINGEMENT. IN NO EVENT SHALL THE
+ AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ SOFTWARE.
+*/
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+
+#[cfg(feature = "debug")]
+use crate::Engine;
+use crate::TraitApply;
+use crate::graphics::color::Color;
+use crate::graphics::dynamic_buffer::DynamicBuffer;
+use crate::graphics::open_gl::framebuffer::GlTextQuads;
+use crate::graphics::renderer::{EnumRendererApi, EnumRendererError};
+use crate::graphics::shader::{EnumShaderSource, EnumShaderStageType, Shader, ShaderStage};
+use crate::math::{Mat4, Vec2};
+use crate::utils::macros::logger::*;
+
+/// Distance, in atlas pixels, encoded on each side of a glyph's outline. Larger spreads allow for outlines and
+/// glows, at the cost of atlas space.
+pub const C_SDF_SPREAD: usize = 4;
+const C_FONT_ATLAS_WIDTH: usize = 512;
+const C_FONT_ATLAS_MAX_HEIGHT: usize = 4096;
+// Empty pixels between glyphs, to avoid sampling neighbors when filtering.
+const C_FONT_ATLAS_PADDING: usize = 1;
+// Printable ASCII characters, rasterized into every font.
+const C_FONT_CHARACTERS: std::ops::RangeInclusive<char> = ' '..='~';
+// Drawn in place of characters missing from the atlas.
+const C_FONT_FALLBACK_CHARACTER: char = '?';
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum EnumTextError {
+  InvalidPath(String),
+  InvalidFont,
+  AtlasTooLarge,
+}
+
+impl Display for EnumTextError {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    write!(f, "[Text] -->\t Error encountered with font : {:?}", self)
+  }
+}
+
+/// Turn a glyph's coverage bitmap (one byte per pixel, rows top to bottom) into a signed distance field, padded by
+/// `spread` pixels on each side (at least one). Values above 127 lie inside the outline, 127.5 being the outline itself,
+/// and distances saturate `spread` pixels away from it.
+pub fn generate_sdf(coverage: &[u8], width: usize, height: usize, spread: usize) -> Vec<u8> {
+  let spread: usize = spread.max(1);
+  let padded_width: usize = width + 2 * spread;
+  let padded_height: usize = height + 2 * spread;
+  let radius: isize = spread as isize;
+  
+  let is_inside = |x: isize, y: isize| -> bool {
+    let (x, y) = (x - radius, y - radius);
+    return x >= 0 && y >= 0 && (x as usize) < width && (y as usize) < height && coverage[y as usize * width + x as usize] >= 128;
+  };
+  
+  let mut sdf: Vec<u8> = vec![0; padded_width * padded_height];
+  for y in 0..padded_height as isize {
+    for x in 0..padded_width as isize {
+      let inside: bool = is_inside(x, y);
+      
+      // Closest pixel on the other side of the outline, within the spread.
+      let mut closest_squared: isize = (radius + 1) * (radius + 1);
+      for offset_y in -radius..=radius {
+        for offset_x in -radius..=radius {
+          let distance_squared: isize = offset_x * offset_x + offset_y * offset_y;
+          if distance_squared < closest_squared && is_inside(x + offset_x, y + offset_y) != inside {
+            closest_squared = distance_squared;
+          }
+        }
+      }
+      
+      // The outline runs halfway between two pixels on either side of it.
+      let distance: f32 = ((closest_squared as f32).sqrt() - 0.5).min(spread as f32);
+      let signed_distance: f32 = if inside { distance } else { -distance };
+      sdf[y as usize * padded_width + x as usize] =
+        ((0.5 + signed_distance / (2.0 * spread as f32)).clamp(0.0, 1.0) * 255.0).round() as u8;
+    }
+  }
+  return sdf;
+}
+
+/// Placement of a character in its font's atlas, in pixels at the font's rasterized size.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Glyph {
+  pub(crate) m_uv_min: Vec2<f32>,
+  pub(crate) m_uv_max: Vec2<f32>,
+  pub(crate) m_size: Vec2<f32>,
+  // From the pen position on the baseline to the top left corner of the glyph's quad, y going down.
+  pub(crate) m_offset: Vec2<f32>,
+  pub(crate) m_advance: f32,
+}
+
+impl Glyph {
+  pub fn get_uvs(&self) -> (Vec2<f32>, Vec2<f32>) {
+    return (self.m_uv_min, self.m_uv_max);
+  }
+  
+  pub fn get_size(&self) -> Vec2<f32> {
+    return self.m_size;
+  }
+  
+  pub fn get_advance(&self) -> f32 {
+    return self.m_advance;
+  }
+}
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) struct TextVertex {
+  m_position: [f32; 2],
+  m_uv: [f32; 2],
+  m_color: [u8; 4],
+}
+
+/// A TrueType font rasterized once, for printable ASCII characters, into a signed distance field atlas. Text drawn with
+/// it through [crate::graphics::renderer::Renderer::draw_text] stays crisp at any size. The atlas texture is only sent
+/// to the GPU on the first draw, and must be released with [Self::free] before the renderer goes away.
+#[derive(Debug)]
+pub struct Font {
+  m_pixel_size: f32,
+  m_ascent: f32,
+  m_line_height: f32,
+  m_glyphs: HashMap<char, Glyph>,
+  m_atlas: Vec<u8>,
+  m_atlas_size: (usize, usize),
+  m_texture_id: Option<u32>,
+}
+
+impl Font {
+  pub fn from_file(file_path: &str, pixel_size: f32) -> Result<Self, EnumTextError> {
+    let bytes: Vec<u8> = std::fs::read(file_path).map_err(|_err| {
+      log!(EnumLogColor::Red, "ERROR", "[Text] -->\t Cannot read font {0}, Error => {1}", file_path, _err);
+      EnumTextError::InvalidPath(String::from(file_path))
+    })?;
+    return Self::from_bytes(&bytes, pixel_size);
+  }
+  
+  /// Rasterize the font's printable ASCII characters at `pixel_size`, the size at which text looks its best. Larger
+  /// sizes use more atlas space but keep sharp corners when scaled up.
+  pub fn from_bytes(bytes: &[u8], pixel_size: f32) -> Result<Self, EnumTextError> {
+    let font = fontdue::Font::from_bytes(bytes, fontdue::FontSettings { scale: pixel_size, ..Default::default() })
+      .map_err(|_err| {
+        log!(EnumLogColor::Red, "ERROR", "[Text] -->\t Cannot parse font, Error => {0}", _err);
+        EnumTextError::InvalidFont
+      })?;
+    let line_metrics = font.horizontal_line_metrics(pixel_size).ok_or(EnumTextError::InvalidFont)?;
+    
+    // Rasterize every glyph first, to know where each one fits in the atlas.
+    let rasterized: Vec<(char, fontdue::Metrics, Vec<u8>)> = C_FONT_CHARACTERS
+      .map(|character| {
+        let (metrics, coverage) = font.rasterize(character, pixel_size);
+        let sdf: Vec<u8> = match metrics.width * metrics.height {
+          0 => Vec::new(),
+          _ => generate_sdf(&coverage, metrics.width, metrics.height, C_SDF_SPREAD),
+        };
+        (character, metrics, sdf)
+      })
+      .collect();
+    
+    let sizes: Vec<(usize, usize)> = rasterized.iter()
+      .map(|(_, metrics, sdf)| match sdf.is_empty() {
+        true => (0, 0),
+        false => (metrics.width + 2 * C_SDF_SPREAD, metrics.height + 2 * C_SDF_SPREAD),
+      })
+      .collect();
+    let (positions, atlas_height) = Self::pack(&sizes, C_FONT_ATLAS_WIDTH)?;
+    
+    let mut atlas: Vec<u8> = vec![0; C_FONT_ATLAS_WIDTH * atlas_height];
+    let mut glyphs: HashMap<char, Glyph> = HashMap::with_capacity(rasterized.len());
+    
+    for (index, (character, metrics, sdf)) in rasterized.iter().enumerate() {
+      let (x, y) = positions[index];
+      let (width, height) = sizes[index];
+      for row in 0..height {
+        atlas[(y + row) * C_FONT_ATLAS_WIDTH + x..(y + row) * C_FONT_ATLAS_WIDTH + x + width]
+          .copy_from_slice(&sdf[row * width..(row + 1) * width]);
+      }
+      
+      glyphs.insert(*character, Glyph {
+        m_uv_min: Vec2::new(&[x as f32 / C_FONT_ATLAS_WIDTH as f32, y as f32 / atlas_height as f32]),
+        m_uv_max: Vec2::new(&[(x + width) as f32 / C_FONT_ATLAS_WIDTH as f32, (y + height) as f32 / atlas_height as f32]),
+        m_size: Vec2::new(&[width as f32, height as f32]),
+        m_offset: Vec2::new(&[metrics.xmin as f32 - C_SDF_SPREAD as f32,
+          -(metrics.ymin as f32 + metrics.height as f32) - C_SDF_SPREAD as f32]),
+        m_advance: metrics.advance_width,
+      });
+    }
+    
+    log!(EnumLogColor::Green, "INFO", "[Text] -->\t Rasterized {0} glyphs at {1}px into a {2}x{3} atlas", glyphs.len(),
+      pixel_size, C_FONT_ATLAS_WIDTH, atlas_height);
+    return Ok(Self {
+      m_pixel_size: pixel_size,
+      m_ascent: line_metrics.ascent,
+      m_line_height: line_metrics.new_line_size,
+      m_glyphs: glyphs,
+      m_atlas: atlas,
+      m_atlas_size: (C_FONT_ATLAS_WIDTH, atlas_height),
+      m_texture_id: None,
+    });
+  }
+  
+  pub fn get_glyph(&self, character: char) -> Option<&Glyph> {
+    return self.m_glyphs.get(&character);
+  }
+  
+  pub fn get_pixel_size(&self) -> f32 {
+    return self.m_pixel_size;
+  }
+  
+  /// Distance between two baselines, in pixels at the font's rasterized size.
+  pub fn get_line_height(&self) -> f32 {
+    return self.m_line_height;
+  }
+  
+  pub fn get_atlas_size(&self) -> (usize, usize) {
+    return self.m_atlas_size;
+  }
+  
+  /// Width of the longest line and total height of `text` drawn at `size` pixels, without drawing it.
+  pub fn measure(&self, text: &str, size: f32) -> Vec2<f32> {
+    let scale: f32 = size / self.m_pixel_size;
+    let width: f32 = text.split('\n')
+      .map(|line| line.chars()
+        .filter_map(|character| self.get_glyph_or_fallback(character))
+        .map(|glyph| glyph.m_advance)
+        .sum::<f32>())
+      .fold(0.0, f32::max);
+    return Vec2::new(&[width * scale, text.split('\n').count() as f32 * self.m_line_height * scale]);
+  }
+  
+  /// Two triangles per visible character of `text`, with `position` being the top left corner of the text in pixels.
+  pub(crate) fn layout(&self, text: &str, position: Vec2<f32>, size: f32, color: Color) -> Vec<TextVertex> {
+    let scale: f32 = size / self.m_pixel_size;
+    let color: [u8; 4] = color.as_u8();
+    let mut vertices: Vec<TextVertex> = Vec::with_capacity(text.len() * 6);
+    let mut pen: Vec2<f32> = Vec2::new(&[position.x, position.y + self.m_ascent * scale]);
+    
+    for character in text.chars() {
+      if character == '\n' {
+        pen = Vec2::new(&[position.x, pen.y + self.m_line_height * scale]);
+        continue;
+      }
+      let Some(glyph) = self.get_glyph_or_fallback(character) else {
+        continue;
+      };
+      
+      if glyph.m_size.x > 0.0 {
+        let (x0, y0) = (pen.x + glyph.m_offset.x * scale, pen.y + glyph.m_offset.y * scale);
+        let (x1, y1) = (x0 + glyph.m_size.x * scale, y0 + glyph.m_size.y * scale);
+        let vertex = |x: f32, y: f32, u: f32, v: f32| TextVertex { m_position: [x, y], m_uv: [u, v], m_color: color };
+        
+        vertices.extend_from_slice(&[
+          vertex(x0, y0, glyph.m_uv_min.x, glyph.m_uv_min.y),
+          vertex(x1, y0, glyph.m_uv_max.x, glyph.m_uv_min.y),
+          vertex(x1, y1, glyph.m_uv_max.x, glyph.m_uv_max.y),
+          vertex(x0, y0, glyph.m_uv_min.x, glyph.m_uv_min.y),
+          vertex(x1, y1, glyph.m_uv_max.x, glyph.m_uv_max.y),
+          vertex(x0, y1, glyph.m_uv_min.x, glyph.m_uv_max.y),
+        ]);
+      }
+      pen.x += glyph.m_advance * scale;
+    }
+    return vertices;
+  }
+  
+  /// Send the atlas to the GPU if it isn't already, returning its texture handle.
+  pub(crate) fn get_or_create_texture(&mut self) -> Result<u32, EnumRendererError> {
+    if let Some(texture_id) = self.m_texture_id {
+      return Ok(texture_id);
+    }
+    let texture_id: u32 = GlTextQuads::create_atlas(&self.m_atlas, self.m_atlas_size.0, self.m_atlas_size.1)?;
+    self.m_texture_id = Some(texture_id);
+    return Ok(texture_id);
+  }
+  
+  /// Release the atlas texture, if it was sent to the GPU. The font can still be drawn afterward, re-sending it.
+  pub fn free(&mut self) -> Result<(), EnumRendererError> {
+    if let Some(texture_id) = self.m_texture_id.take() {
+      GlTextQuads::delete_atlas(texture_id)?;
+    }
+    return Ok(());
+  }
+  
+  fn get_glyph_or_fallback(&self, character: char) -> Option<&Glyph> {
+    return self.m_glyphs.get(&character).or(self.m_glyphs.get(&C_FONT_FALLBACK_CHARACTER));
+  }
+  
+  // Shelf packing : glyphs go left to right in rows as tall as their tallest glyph. Returns each glyph's top left
+  // corner and the atlas height, rounded up to a power of two.
+  fn pack(sizes: &[(usize, usize)], atlas_width: usize) -> Result<(Vec<(usize, usize)>, usize), EnumTextError> {
+    let mut positions: Vec<(usize, usize)> = Vec::with_capacity(sizes.len());
+    let (mut x, mut y, mut shelf_height) = (C_FONT_ATLAS_PADDING, C_FONT_ATLAS_PADDING, 0);
+    
+    for (width, height) in sizes.iter() {
+      if *width + 2 * C_FONT_ATLAS_PADDING > atlas_width {
+        return Err(EnumTextError::AtlasTooLarge);
+      }
+      if x + *width + C_FONT_ATLAS_PADDING > atlas_width {
+        x = C_FONT_ATLAS_PADDING;
+        y += shelf_height + C_FONT_ATLAS_PADDING;
+        shelf_height = 0;
+      }
+      positions.push((x, y));
+      x += *width + C_FONT_ATLAS_PADDING;
+      shelf_height = shelf_height.max(*height);
+    }
+    
+    let atlas_height: usize = (y + shelf_height + C_FONT_ATLAS_PADDING).next_power_of_two();
+    if atlas_height > C_FONT_ATLAS_MAX_HEIGHT {
+      log!(EnumLogColor::Red, "ERROR", "[Text] -->\t Cannot fit glyphs in a font atlas taller than {0}px, try a smaller \
+      pixel size!", C_FONT_ATLAS_MAX_HEIGHT);
+      return Err(EnumTextError::AtlasTooLarge);
+    }
+    return Ok((positions, atlas_height));
+  }
+}
+
+/// Text queued during the frame, drawn at once in screen space over everything else after the scene, then cleared for
+/// the next frame. OpenGL only for now.
+pub(crate) struct TextDraw {
+  // Vertices grouped by the font atlas they sample.
+  m_batches: Vec<(u32, Vec<TextVertex>)>,
+  m_shader: Option<Shader>,
+  m_quads: Option<GlTextQuads>,
+}
+
+impl TextDraw {
+  pub(crate) fn new() -> Self {
+    return Self {
+      m_batches: Vec::new(),
+      m_shader: None,
+      m_quads: None,
+    };
+  }
+  
+  pub(crate) fn queue(&mut self, texture_id: u32, mut vertices: Vec<TextVertex>) {
+    match self.m_batches.iter_mut().find(|(batch_texture_id, _)| *batch_texture_id == texture_id) {
+      Some((_, batch)) => batch.append(&mut vertices),
+      None => self.m_batches.push((texture_id, vertices)),
+    }
+  }
+  
+  pub(crate) fn is_empty(&self) -> bool {
+    return self.m_batches.is_empty();
+  }
+  
+  pub(crate) fn clear(&mut self) {
+    self.m_batches.clear();
+  }
+  
+  /// Draw all queued text over a `viewport` sized in pixels, streamed through `buffer`, then clear it. The built-in
+  /// shader and vertex array are only created on the first non-empty flush.
+  pub(crate) fn flush(&mut self, viewport: (u32, u32), buffer: &mut DynamicBuffer) -> Result<(), EnumRendererError> {
+    if self.m_batches.is_empty() {
+      return Ok(());
+    }
+    
+    if self.m_shader.is_none() {
+      let mut shader = Shader::new(EnumRendererApi::OpenGL, HashSet::from([
+        ShaderStage::new(EnumShaderStageType::Vertex, EnumShaderSource::FromFile(String::from("res/shaders/text_sdf.vert"))),
+        ShaderStage::new(EnumShaderStageType::Fragment, EnumShaderSource::FromFile(String::from("res/shaders/text_sdf.frag")))
+      ]));
+      if let Err(_err) = shader.apply() {
+        log!(EnumLogColor::Red, "ERROR", "[Text] -->\t Cannot create text shader, Error => {0}", _err);
+        self.m_batches.clear();
+        return Err(EnumRendererError::ShaderNotFound);
+      }
+      self.m_shader = Some(shader);
+      self.m_quads = Some(GlTextQuads::new()?);
+    }
+    
+    // Pixels from the top left corner of the window, y going down.
+    let projection: Mat4 = Mat4::apply_orthographic(0.0, viewport.0 as f32, viewport.1 as f32, 0.0, -1.0, 1.0);
+    let program_id: u32 = self.m_shader.as_ref().unwrap().get_id();
+    
+    let mut result: Result<(), EnumRendererError> = Ok(());
+    for (texture_id, vertices) in self.m_batches.iter() {
+      // SAFETY: TextVertex is a plain #[repr(C)] struct of 20 bytes without padding.
+      let bytes = unsafe {
+        std::slice::from_raw_parts(vertices.as_ptr() as *const u8, vertices.len() * std::mem::size_of::<TextVertex>())
+      };
+      result = self.m_quads.as_mut().unwrap().draw(program_id, &projection, *texture_id, buffer, bytes);
+      if result.is_err() {
+        break;
+      }
+    }
+    
+    self.m_batches.clear();
+    return result;
+  }
+  
+  pub(crate) fn free(&mut self) -> Result<(), EnumRendererError> {
+    self.m_batches.clear();
+    if let Some(mut quads) = self.m_quads.take() {
+      quads.free()?;
+    }
+    if let Some(mut shader) = self.m_shader.take() {
+      if let Err(_err) = crate::TraitFree::free(&mut shader) {
+        log!(EnumLogColor::Red, "ERROR", "[Text] -->\t Error while freeing text shader : {0}", _err);
+      }
+    }
+    return Ok(());
+  }
+}
@@ -87,6 +87,22 @@ impl Default for EnumTextureFormat {
   }
 }
 
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum EnumTextureFilter {
+  // Sharp texels, mostly for pixel art.
+  Nearest,
+  // Bilinear filtering, picking the nearest mipmap level if any.
+  Linear,
+  // Bilinear filtering, blending between the two nearest mipmap levels if any.
+  Trilinear,
+}
+
+impl Default for EnumTextureFilter {
+  fn default() -> Self {
+    return EnumTextureFilter::Trilinear;
+  }
+}
+
 #[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
 pub enum EnumCubeMapFace {
   Left,
@@ -309,6 +325,10 @@ pub enum EnumTextureError {
   FileError(String),
   InvalidMipMap,
   InvalidFormat,
+  /// A texture's (width, height) differs from the other layers of its texture array.
+  SizeMismatch { expected: (usize, usize), got: (usize, usize) },
+  /// A texture's format, and so its channel count, differs from the other layers of its texture array.
+  FormatMismatch { expected: EnumTextureFormat, got: EnumTextureFormat },
   OpenGLError(EnumGlTextureError),
   #[cfg(feature = "vulkan")]
   VulkanError(EnumVkTextureError),
@@ -328,6 +348,8 @@ pub(crate) enum EnumTextureState {
   Created,
   Sent,
   Deleted,
+  // Handle to a texture owned by something else (i.e. a render target attachment), never applied nor freed.
+  Borrowed,
 }
 
 //////////////////////////// DISPLAY /////////////////////////////////////
@@ -466,6 +488,8 @@ pub(crate) trait TraitTexture {
   fn get_size(&self) -> (usize, usize);
   fn set_depth(&mut self, depth: u16);
   fn convert_to(&mut self, format: EnumTextureFormat) -> Result<(), EnumRendererError>;
+  fn get_filters(&self) -> Result<(u32, u32), EnumRendererError>;
+  fn get_anisotropy(&self) -> Result<f32, EnumRendererError>;
   fn apply(&mut self) -> Result<(), EnumRendererError>;
   fn clear(&mut self) -> Result<(), EnumRendererError>;
   fn free(&mut self) -> Result<(), EnumRendererError>;
@@ -537,6 +561,18 @@ impl Texture {
     };
   }
   
+  pub(crate) fn from_existing(api: Box<dyn TraitTexture>) -> Self {
+    let new_uuid = unsafe { S_TEXTURE_ID_COUNTER };
+    unsafe { S_TEXTURE_ID_COUNTER += 1 };
+    
+    return Self {
+      m_uuid: new_uuid,
+      m_state: EnumTextureState::Borrowed,
+      m_api: api,
+      m_hints: vec![],
+    };
+  }
+  
   #[allow(unused)]
   pub(crate) fn get_depth(&self) -> u16 {
     return self.m_api.get_depth();
@@ -551,6 +587,16 @@ impl Texture {
   pub(crate) fn set_depth(&mut self, depth: u16) {
     self.m_api.set_depth(depth);
   }
+  
+  /// Get the minification and magnification filters currently set on the texture, as api-specific values.
+  pub fn get_filters(&self) -> Result<(u32, u32), EnumRendererError> {
+    return self.m_api.get_filters();
+  }
+  
+  /// Get the max anisotropy level currently set on the texture, 1.0 if anisotropic filtering is unsupported.
+  pub fn get_anisotropy(&self) -> Result<f32, EnumRendererError> {
+    return self.m_api.get_anisotropy();
+  }
 }
 
 impl Default for Texture {
@@ -594,26 +640,16 @@ pub struct TextureArray {
 }
 
 impl TextureArray {
-  pub fn new(api_chosen: EnumRendererApi, textures_info: Vec<TextureInfo<u8>>) -> Self {
-    let mut to_texture_array: Vec<TextureInfo<u8>> = Vec::with_capacity(textures_info.len());
-    let mut depth_counter: u16 = 0;
-    
-    for texture_info in textures_info.into_iter() {
-      let new_texture_info = TextureInfo {
-        m_type: EnumTextureInfo::Texture3D(texture_info.m_type.get_target(), texture_info.m_type.get_mipmap_level(),
-          texture_info.m_type.get_format(), texture_info.m_type.get_width() as u32, texture_info.m_type.get_height() as u32,
-          depth_counter as u32, texture_info.m_type.get_data_type(), texture_info.m_type.get_slot()),
-        m_data: texture_info.m_data,
-      };
-      to_texture_array.push(new_texture_info);
-      
-      depth_counter += 1;
-    }
-    return Self {
-      m_textures: to_texture_array,
-      m_max_depth: depth_counter,
+  /// Gather textures as layers of a single array texture. They must all share the same size and format, see
+  /// [Self::append].
+  pub fn new(api_chosen: EnumRendererApi, textures_info: Vec<TextureInfo<u8>>) -> Result<Self, EnumTextureError> {
+    let mut texture_array = Self {
+      m_textures: Vec::with_capacity(textures_info.len()),
+      m_max_depth: 0,
       m_api: api_chosen
     };
+    texture_array.append(textures_info)?;
+    return Ok(texture_array);
   }
   
   pub fn len(&self) -> usize {
@@ -624,7 +660,15 @@ impl TextureArray {
     return self.m_max_depth;
   }
   
-  pub fn append(&mut self, textures_info: Vec<TextureInfo<u8>>) {
+  /// Add textures as new layers. Nothing gets added if any of them differs in size or format from the layers already
+  /// in the array (or from the first texture given, for an empty array), since every layer shares the same storage.
+  pub fn append(&mut self, textures_info: Vec<TextureInfo<u8>>) -> Result<(), EnumTextureError> {
+    if let Some(reference) = self.m_textures.first().or(textures_info.first()) {
+      for texture_info in textures_info.iter() {
+        Self::validate_layer(reference, texture_info)?;
+      }
+    }
+    
     let mut to_texture_array: Vec<TextureInfo<u8>> = Vec::with_capacity(textures_info.len());
     let mut depth_counter: u16 = self.m_max_depth;
     
@@ -634,6 +678,9 @@ impl TextureArray {
           texture_info.m_type.get_format(), texture_info.m_type.get_width() as u32, texture_info.m_type.get_height() as u32,
           depth_counter as u32, texture_info.m_type.get_data_type(), texture_info.m_type.get_slot()),
         m_data: texture_info.m_data,
+        m_mipmaps: texture_info.m_mipmaps,
+        m_filter: texture_info.m_filter,
+        m_anisotropy: texture_info.m_anisotropy,
       };
       to_texture_array.push(new_texture_info);
       
@@ -641,6 +688,27 @@ impl TextureArray {
     }
     self.m_textures.append(&mut to_texture_array);
     self.m_max_depth = depth_counter;
+    return Ok(());
+  }
+  
+  fn validate_layer(reference: &TextureInfo<u8>, texture_info: &TextureInfo<u8>) -> Result<(), EnumTextureError> {
+    let expected_size: (usize, usize) = (reference.m_type.get_width(), reference.m_type.get_height());
+    let size: (usize, usize) = (texture_info.m_type.get_width(), texture_info.m_type.get_height());
+    
+    if size != expected_size {
+      log!(EnumLogColor::Red, "ERROR", "[Texture] -->\t Cannot add {0}x{1} texture to a {2}x{3} texture array!",
+        size.0, size.1, expected_size.0, expected_size.1);
+      return Err(EnumTextureError::SizeMismatch { expected: expected_size, got: size });
+    }
+    
+    let expected_format: EnumTextureFormat = reference.m_type.get_format();
+    let format: EnumTextureFormat = texture_info.m_type.get_format();
+    if format != expected_format {
+      log!(EnumLogColor::Red, "ERROR", "[Texture] -->\t Cannot add {0:?} texture to a {1:?} texture array!", format,
+        expected_format);
+      return Err(EnumTextureError::FormatMismatch { expected: expected_format, got: format });
+    }
+    return Ok(());
   }
   
   pub fn get_texture_handle(&self) -> Texture {
@@ -659,7 +727,11 @@ impl TextureArray {
         height: texture_height,
         depth: self.m_max_depth as usize,
         data: vec![],
-      }
+      },
+      // The whole array shares a single sampler, so use the first texture's settings.
+      m_mipmaps: self.m_textures[0].m_mipmaps,
+      m_filter: self.m_textures[0].m_filter,
+      m_anisotropy: self.m_textures[0].m_anisotropy,
     };
     
     return Texture::new(self.m_api, texture_info);
@@ -0,0 +1,154 @@
+/*
+ MIT License
+ 
+ Copyright (c) 2023 Nami Reghbati
+ 
+ Permission is hereby granted, free of charge, to any person obtaining a copy
+ of this software and associated documentation files (the "Software"), to deal
+ in the Software without restriction, including without limitation the rights
+ to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ copies of the Software, and to permit persons to whom the Software is
+ furnished to do so, subject to the following conditions:
+ 
+ The above copyright notice and this permission notice shall be included in all
+ copies or substantial portions of the Software.
+ 
+ THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ FITNESS FOR A PARTICULAR PURPOSE AND NON INFRINGEMENT. IN NO EVENT SHALL THE
+ AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ SOFTWARE.
+*/
+
+#[cfg(feature = "debug")]
+use crate::Engine;
+use crate::graphics::open_gl::buffer::{EnumUboTypeSize, GlUbo};
+use crate::graphics::renderer::EnumRendererError;
+use crate::math::Mat4;
+use crate::utils::macros::logger::*;
+
+/// Values that can be uploaded to a [UniformBuffer], packed following the std140 layout rules.
+pub trait TraitUniformData {
+  fn to_std140(&self) -> Vec<u8>;
+}
+
+impl TraitUniformData for Mat4 {
+  fn to_std140(&self) -> Vec<u8> {
+    // GLSL matrices are column-major.
+    return self.transpose().as_array().iter().flat_map(|value| value.to_ne_bytes()).collect();
+  }
+}
+
+impl TraitUniformData for f32 {
+  fn to_std140(&self) -> Vec<u8> {
+    return self.to_ne_bytes().to_vec();
+  }
+}
+
+impl TraitUniformData for i32 {
+  fn to_std140(&self) -> Vec<u8> {
+    return self.to_ne_bytes().to_vec();
+  }
+}
+
+impl TraitUniformData for u32 {
+  fn to_std140(&self) -> Vec<u8> {
+    return self.to_ne_bytes().to_vec();
+  }
+}
+
+impl TraitUniformData for bool {
+  fn to_std140(&self) -> Vec<u8> {
+    return (*self as u32).to_ne_bytes().to_vec();
+  }
+}
+
+impl<T: TraitUniformData> TraitUniformData for [T] {
+  fn to_std140(&self) -> Vec<u8> {
+    // Array elements are padded to a multiple of a vec4.
+    let mut bytes: Vec<u8> = Vec::new();
+    for element in self.iter() {
+      let mut element_bytes = element.to_std140();
+      element_bytes.resize(element_bytes.len().next_multiple_of(16), 0);
+      bytes.extend(element_bytes);
+    }
+    return bytes;
+  }
+}
+
+/// Uniform buffer attached to a binding point, shared by every shader declaring a block at that binding (see
+/// [crate::graphics::shader::Shader::bind_uniform_block]). Data uploaded here is seen by all of them, so it only needs
+/// to be uploaded once per frame. OpenGL only for now.
+pub struct UniformBuffer {
+  m_ubo: GlUbo,
+  m_binding: u32,
+}
+
+impl UniformBuffer {
+  pub fn new(binding_point: u32) -> Result<Self, EnumRendererError> {
+    let ubo = GlUbo::new(None, EnumUboTypeSize::Bytes(16), binding_point)?;
+    
+    log!("INFO", "[UniformBuffer] -->\t Created uniform buffer {0} at binding {1}", ubo.get_id(), binding_point);
+    return Ok(Self {
+      m_ubo: ubo,
+      m_binding: binding_point,
+    });
+  }
+  
+  /// Same as [Self::new], for the uniform block `block_name`, to bind it explicitly in shaders too old to declare
+  /// their own bindings (see [Self::bind_block]).
+  pub(crate) fn for_block(block_name: &'static str, binding_point: u32) -> Result<Self, EnumRendererError> {
+    let ubo = GlUbo::new(Some(block_name), EnumUboTypeSize::Bytes(16), binding_point)?;
+    
+    log!("INFO", "[UniformBuffer] -->\t Created uniform buffer {0} for block '{1}' at binding {2}", ubo.get_id(),
+      block_name, binding_point);
+    return Ok(Self {
+      m_ubo: ubo,
+      m_binding: binding_point,
+    });
+  }
+  
+  /// Point the block of the same name in the program `shader_id` to this buffer's binding, for GLSL below 420.
+  pub(crate) fn bind_block(&mut self, shader_id: u32) -> Result<(), EnumRendererError> {
+    return Ok(self.m_ubo.bind_block(shader_id, self.m_binding)?);
+  }
+  
+  pub fn get_binding_point(&self) -> u32 {
+    return self.m_binding;
+  }
+  
+  /// Size in bytes of the last upload.
+  pub fn len(&self) -> usize {
+    return self.m_ubo.len();
+  }
+  
+  /// Replace the buffer's content with `data`, growing it if needed.
+  ///
+  /// Bound on [TraitUniformData] rather than `bytemuck::Pod`, since copying the raw bytes isn't enough for std140 :
+  /// [Mat4] is stored row-major and has to be transposed, and array elements have to be padded to a vec4. Plain data
+  /// already laid out for std140 can go through [Self::upload_bytes] instead.
+  pub fn upload<T: TraitUniformData + ?Sized>(&mut self, data: &T) -> Result<(), EnumRendererError> {
+    return self.upload_bytes(&data.to_std140());
+  }
+  
+  /// Same as [Self::upload], for data already packed in std140.
+  pub fn upload_bytes(&mut self, data: &[u8]) -> Result<(), EnumRendererError> {
+    return Ok(self.m_ubo.write_bytes(data)?);
+  }
+  
+  /// Attach this buffer back to its binding point, if another buffer got bound there since.
+  pub fn bind(&mut self) -> Result<(), EnumRendererError> {
+    return Ok(self.m_ubo.bind_base(self.m_binding)?);
+  }
+  
+  /// Copy `size` bytes of the buffer back to the CPU.
+  pub fn read(&self, offset: usize, size: usize) -> Result<Vec<u8>, EnumRendererError> {
+    return Ok(self.m_ubo.read_bytes(offset, size)?);
+  }
+  
+  pub fn free(&mut self) -> Result<(), EnumRendererError> {
+    return Ok(self.m_ubo.free()?);
+  }
+}
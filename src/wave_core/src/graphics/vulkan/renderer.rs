@@ -49,8 +49,10 @@ use crate::{Engine, events};
 #[cfg(feature = "vulkan")]
 use crate::graphics::{renderer, vulkan};
 #[cfg(feature = "vulkan")]
-use crate::graphics::renderer::{EnumRendererCallCheckingMode, EnumRendererHint, EnumRendererState, TraitContext};
-use crate::graphics::renderer::{ EnumRendererError, EnumRendererRenderPrimitiveAs};
+use crate::graphics::renderer::{EnumRendererCallCheckingMode, EnumRendererHint, EnumRendererState, RendererCapabilities, TraitContext};
+use crate::graphics::renderer::{EnumRendererBlendMode, EnumRendererError, EnumRendererRenderPrimitiveAs};
+#[cfg(feature = "vulkan")]
+use crate::graphics::render_target::RenderTarget;
 #[cfg(feature = "vulkan")]
 use crate::graphics::shader::Shader;
 #[cfg(feature = "vulkan")]
@@ -212,6 +214,7 @@ pub struct VkContext {
   m_swap_chain_image_views: Vec<vk::ImageView>,
   m_dynamic_states: Vec<vk::DynamicState>,
   m_vbo_array: Vec<VkVbo>,
+  m_capabilities: RendererCapabilities,
   m_debug_report_callback: Option<(ext::DebugUtils, vk::DebugUtilsMessengerEXT)>
 }
 
@@ -343,6 +346,7 @@ impl VkContext {
       VkVertexAttribute::new(0, 2, vk::Format::R32G32B32_SFLOAT, EnumVertexMemberOffset::NormalOffset as u32)?,
       VkVertexAttribute::new(0, 3, vk::Format::R32G32B32A32_SFLOAT, EnumVertexMemberOffset::ColorOffset as u32)?,
       VkVertexAttribute::new(0, 4, vk::Format::R32G32_SFLOAT, EnumVertexMemberOffset::TexCoordsOffset as u32)?,
+      VkVertexAttribute::new(0, 5, vk::Format::R32G32B32_SFLOAT, EnumVertexMemberOffset::TangentOffset as u32)?,
     ];
     
     // Setup vertex input.
@@ -444,6 +448,45 @@ impl VkContext {
     return Ok(c_layers);
   }
   
+  /// Query the limits and device extensions of the picked physical device.
+  ///
+  /// ### Returns:
+  ///   * `Result<RendererCapabilities, renderer::EnumError>`: The device's capabilities if successful,
+  /// otherwise an [renderer::EnumError::VulkanError(EnumVulkanErrors::ExtensionError)] if the device extensions
+  /// cannot be enumerated.
+  ///
+  fn query_capabilities(&self) -> Result<RendererCapabilities, renderer::EnumRendererError> {
+    let instance = self.m_instance.as_ref().unwrap();
+    let device_properties = unsafe { instance.get_physical_device_properties(self.m_physical_device) };
+    let device_extensions = unsafe { instance.enumerate_device_extension_properties(self.m_physical_device) }
+      .map_err(|_err| {
+        log!(EnumLogColor::Red, "ERROR", "[VkContext] -->\t Cannot enumerate device extensions : \
+        Vulkan returned with : {:?}", _err);
+        return renderer::EnumRendererError::from(EnumVkContextError::ExtensionError);
+      })?;
+    
+    let api_version = device_properties.api_version;
+    return Ok(RendererCapabilities {
+      m_vendor: format!("0x{0:x}", device_properties.vendor_id),
+      m_device_name: unsafe { std::ffi::CStr::from_ptr(device_properties.device_name.as_ptr()) }
+        .to_string_lossy().into_owned(),
+      m_api_version: format!("{0}.{1}.{2}", vk::api_version_major(api_version), vk::api_version_minor(api_version),
+        vk::api_version_patch(api_version)),
+      // SPIR-V only, no shading language to report.
+      m_shading_language_version: String::from("SPIR-V"),
+      m_max_texture_units: device_properties.limits.max_per_stage_descriptor_samplers,
+      m_max_samples: self.get_max_msaa_count()? as u32,
+      m_max_texture_size: device_properties.limits.max_image_dimension2_d,
+      m_max_anisotropy: device_properties.limits.max_sampler_anisotropy.max(1.0),
+      // Host visible memory can always stay mapped.
+      m_persistent_mapping: true,
+      m_extensions: device_extensions.iter()
+        .map(|extension| unsafe { std::ffi::CStr::from_ptr(extension.extension_name.as_ptr()) }
+          .to_string_lossy().into_owned())
+        .collect(),
+    });
+  }
+  
   /// Check if the requested Vulkan instance extensions are supported.
   ///
   /// ### Arguments:
@@ -948,6 +991,7 @@ impl TraitContext for VkContext {
       m_swap_chain_image_views: vec![],
       m_dynamic_states: vec![],
       m_vbo_array: vec![],
+      m_capabilities: RendererCapabilities::default(),
       m_debug_report_callback: None
     }
   }
@@ -957,6 +1001,43 @@ impl TraitContext for VkContext {
     return Ok(());
   }
   
+  fn toggle_blend_mode(&mut self, _entity_uuid: u64, _instance_offset: Option<usize>, _instance_count: usize,
+                       _mode: EnumRendererBlendMode) -> Result<(), EnumRendererError> {
+    return Ok(());
+  }
+  
+  fn get_polygon_mode(&self) -> Result<EnumRendererRenderPrimitiveAs, EnumRendererError> {
+    return Err(EnumRendererError::NotImplemented);
+  }
+  
+  fn get_blend_mode(&self) -> Result<Option<EnumRendererBlendMode>, EnumRendererError> {
+    return Err(EnumRendererError::NotImplemented);
+  }
+  
+  fn set_viewport(&mut self, _viewport: (i32, i32, u32, u32), _scissor: bool) -> Result<(), EnumRendererError> {
+    return Err(EnumRendererError::NotImplemented);
+  }
+  
+  fn update_ubo_lights(&mut self, _light_data: &[u8]) -> Result<(), EnumRendererError> {
+    return Err(EnumRendererError::NotImplemented);
+  }
+  
+  fn update_ubo_bones(&mut self, _entity_uuid: u64, _bone_matrices: &[Mat4]) -> Result<(), EnumRendererError> {
+    return Err(EnumRendererError::NotImplemented);
+  }
+  
+  fn toggle_shadow_casting(&mut self, _entity_uuid: u64, _instance_offset: Option<usize>, _instance_count: usize, _cast_shadows: bool) -> Result<(), EnumRendererError> {
+    return Ok(());
+  }
+  
+  fn toggle_shadow_receiving(&mut self, _entity_uuid: u64, _instance_offset: Option<usize>, _instance_count: usize, _receive_shadows: bool) -> Result<(), EnumRendererError> {
+    return Ok(());
+  }
+  
+  fn render_shadow_depth(&mut self, _light_view: Mat4, _light_projection: Mat4, _shadow_map: &RenderTarget) -> Result<(), EnumRendererError> {
+    return Err(EnumRendererError::NotImplemented);
+  }
+  
   fn toggle_visibility_of(&mut self, _entity_uuid: u64, _sub_primitive_offset: Option<usize>, _instance_count: usize, _visible: bool) -> Result<(), EnumRendererError> {
     return Ok(());
   }
@@ -992,15 +1073,19 @@ impl TraitContext for VkContext {
     return (to_float * 10.0) as u16;
   }
   
-  fn check_extension(&self, _desired_extension: &str) -> bool {
-    todo!()
+  fn check_extension(&self, desired_extension: &str) -> bool {
+    return self.m_capabilities.has_extension(desired_extension);
+  }
+  
+  fn get_capabilities(&self) -> &RendererCapabilities {
+    return &self.m_capabilities;
   }
   
   fn on_event(&mut self, _event: &events::EnumEvent) -> Result<bool, renderer::EnumRendererError> {
     return Ok(false);
   }
   
-  fn on_render(&mut self) -> Result<(), EnumRendererError> {
+  fn on_render(&mut self, _stats: &mut renderer::RendererStats) -> Result<(), EnumRendererError> {
     return Ok(());
   }
   
@@ -1032,12 +1117,13 @@ impl TraitContext for VkContext {
     self.m_physical_device = vk_physical_device;
     self.m_queue_family_indices = queue_family_indices;
     self.m_logical_device = Some(ash_logical_device);
+    self.m_capabilities = self.query_capabilities()?;
     
     // Toggle features.
     self.toggle_options(renderer_hints)?;
     
     // Create swap chain.
-    self.create_swap_chain(window.m_vsync)?;
+    self.create_swap_chain(window.is_vsync_enabled())?;
     
     let swap_chain_images = unsafe {
       if self.m_swap_chain.is_none() {
@@ -1190,6 +1276,49 @@ impl TraitContext for VkContext {
     return Ok(());
   }
   
+  fn enqueue_batch(&mut self, sendable_entities: &[REntity], shader_associated: &mut Shader) -> Result<(), renderer::EnumRendererError> {
+    for sendable_entity in sendable_entities.iter() {
+      self.enqueue(sendable_entity, shader_associated)?;
+    }
+    return Ok(());
+  }
+  
+  fn get_buffer_allocation_count(&self) -> usize {
+    // Vertex data is not uploaded to Vulkan buffers yet.
+    return 0;
+  }
+  
+  fn enqueue_instanced(&mut self, r_entity: &REntity, shader_associated: &mut Shader, _transforms: &[Mat4]) -> Result<(), renderer::EnumRendererError> {
+    // No instance buffers yet, fallback to a single regular copy of the entity.
+    log!(EnumLogColor::Yellow, "WARN", "[VkContext] -->\t Instanced rendering not supported with Vulkan, \
+    enqueuing a single instance instead...");
+    return self.enqueue(r_entity, shader_associated);
+  }
+  
+  fn get_instance_buffer_size(&self, _entity_uuid: u64) -> Option<usize> {
+    return None;
+  }
+  
+  fn update_instance_transforms(&mut self, _entity_uuid: u64, _first_instance: usize, _transforms: &[Mat4]) -> Result<(), renderer::EnumRendererError> {
+    // Nothing to update, instanced entities are enqueued as a single regular copy with Vulkan.
+    return Ok(());
+  }
+  
+  fn relink_shader(&mut self, _old_shader_id: u32, _new_shader_id: u32) {
+    // Nothing to relink, draw commands are not tracked per shader with Vulkan yet.
+  }
+  
+  fn capture_frame(&mut self, _width: u32, _height: u32, _presented: bool) -> Result<Vec<u8>, renderer::EnumRendererError> {
+    log!(EnumLogColor::Red, "ERROR", "[VkContext] -->\t Cannot capture frame, not supported with Vulkan yet!");
+    return Err(renderer::EnumRendererError::NotImplemented);
+  }
+  
+  fn toggle_msaa(&mut self, _sample_count: Option<u32>) -> Result<Option<u32>, renderer::EnumRendererError> {
+    // Changing the sample count requires recreating the swapchain and render pass, which is not supported yet.
+    log!(EnumLogColor::Red, "ERROR", "[VkContext] -->\t Cannot toggle MSAA at runtime, not supported with Vulkan!");
+    return Err(renderer::EnumRendererError::NotImplemented);
+  }
+  
   fn dequeue(&mut self, _id: u64) -> Result<(), renderer::EnumRendererError> {
     todo!()
   }
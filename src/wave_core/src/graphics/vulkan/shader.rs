@@ -183,10 +183,55 @@ impl TraitShader for VkShader {
     return Ok(());
   }
   
+  fn bind_uniform_block(&mut self, _block_name: &str, _binding: u32) -> Result<(), shader::EnumShaderError> {
+    log!(EnumLogColor::Red, "ERROR", "[VkShader] -->\t Uniform blocks not supported with Vulkan yet!");
+    return Err(shader::EnumShaderError::UnsupportedApiFunction);
+  }
+  
+  fn create_uniform_block(&mut self, _block_name: &str, _binding: u32, _size: usize) -> Result<(), shader::EnumShaderError> {
+    log!(EnumLogColor::Red, "ERROR", "[VkShader] -->\t Uniform blocks not supported with Vulkan yet!");
+    return Err(shader::EnumShaderError::UnsupportedApiFunction);
+  }
+  
+  fn update_uniform_block(&mut self, _binding: u32, _offset: usize, _data: &[u8]) -> Result<(), shader::EnumShaderError> {
+    log!(EnumLogColor::Red, "ERROR", "[VkShader] -->\t Uniform blocks not supported with Vulkan yet!");
+    return Err(shader::EnumShaderError::UnsupportedApiFunction);
+  }
+  
   fn get_id(&self) -> u32 {
     return self.m_id;
   }
   
+  fn get_active_uniforms(&self) -> Vec<(String, shader::EnumUniformType)> {
+    log!(EnumLogColor::Yellow, "WARN", "[VkShader] -->\t Uniform reflection not supported with Vulkan yet!");
+    return Vec::new();
+  }
+  
+  fn create_storage_buffer(&mut self, _binding: u32, _size: usize) -> Result<(), shader::EnumShaderError> {
+    log!(EnumLogColor::Red, "ERROR", "[VkShader] -->\t Storage buffers not supported with Vulkan yet!");
+    return Err(shader::EnumShaderError::UnsupportedApiFunction);
+  }
+  
+  fn update_storage_buffer(&mut self, _binding: u32, _offset: usize, _data: &[u8]) -> Result<(), shader::EnumShaderError> {
+    log!(EnumLogColor::Red, "ERROR", "[VkShader] -->\t Storage buffers not supported with Vulkan yet!");
+    return Err(shader::EnumShaderError::UnsupportedApiFunction);
+  }
+  
+  fn read_storage_buffer(&self, _binding: u32, _offset: usize, _size: usize) -> Result<Vec<u8>, shader::EnumShaderError> {
+    log!(EnumLogColor::Red, "ERROR", "[VkShader] -->\t Storage buffers not supported with Vulkan yet!");
+    return Err(shader::EnumShaderError::UnsupportedApiFunction);
+  }
+  
+  fn dispatch(&mut self, _group_count_x: u32, _group_count_y: u32, _group_count_z: u32) -> Result<(), shader::EnumShaderError> {
+    log!(EnumLogColor::Red, "ERROR", "[VkShader] -->\t Compute dispatches not supported with Vulkan yet!");
+    return Err(shader::EnumShaderError::UnsupportedApiFunction);
+  }
+  
+  fn memory_barrier(&self, _barrier: shader::EnumShaderBarrier) -> Result<(), shader::EnumShaderError> {
+    log!(EnumLogColor::Red, "ERROR", "[VkShader] -->\t Compute dispatches not supported with Vulkan yet!");
+    return Err(shader::EnumShaderError::UnsupportedApiFunction);
+  }
+  
   fn get_api_handle(&self) -> &dyn Any {
     return self;
   }
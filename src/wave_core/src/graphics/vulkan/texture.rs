@@ -81,6 +81,14 @@ impl<T> TraitTexture for VkTexture<T> {
     todo!()
   }
   
+  fn get_filters(&self) -> Result<(u32, u32), EnumRendererError> {
+    return Err(EnumRendererError::NotImplemented);
+  }
+  
+  fn get_anisotropy(&self) -> Result<f32, EnumRendererError> {
+    return Err(EnumRendererError::NotImplemented);
+  }
+  
   fn apply(&mut self) -> Result<(), EnumRendererError> {
     todo!()
   }
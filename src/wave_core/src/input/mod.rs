@@ -22,28 +22,78 @@
  SOFTWARE.
 */
 
+use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
 
 use crate::utils::macros::logger::*;
-#[cfg(feature = "debug")]
-use crate::Engine;
+use crate::{Engine, S_ENGINE};
 use crate::math::Vec2;
-use crate::window::{Window};
+use crate::window::{EnumCursorMode, Window};
 
 // Highest glfw key index (348). Glfw key indices => [0, 348], however we don't care about the first 32 indices.
-const C_NUM_KEYS: usize = glfw::ffi::KEY_LAST as usize;
+const C_NUM_KEYS: usize = glfw::ffi::KEY_LAST as usize + 1;
 
-// Highest glfw key index (348). Glfw key indices => [0, 7].
-const C_NUM_MOUSE_BUTTONS: usize = glfw::ffi::MOUSE_BUTTON_LAST as usize;
+// Glfw mouse button indices => [0, 7].
+const C_NUM_MOUSE_BUTTONS: usize = glfw::ffi::MOUSE_BUTTON_LAST as usize + 1;
 
 static mut S_KEY_STATES: [(EnumAction, Option<u32>); C_NUM_KEYS] = [(EnumAction::Released, None); C_NUM_KEYS];
 static mut S_MOUSE_BUTTON_STATES: [EnumAction; C_NUM_MOUSE_BUTTONS] = [EnumAction::Released; C_NUM_MOUSE_BUTTONS];
+// Glfw joystick indices => [0, 15], gamepad button indices => [0, 14].
+const C_NUM_GAMEPADS: usize = glfw::ffi::JOYSTICK_LAST as usize + 1;
+const C_NUM_GAMEPAD_BUTTONS: usize = glfw::ffi::GAMEPAD_BUTTON_LAST as usize + 1;
+
+static mut S_GAMEPAD_BUTTON_STATES: [[EnumAction; C_NUM_GAMEPAD_BUTTONS]; C_NUM_GAMEPADS] =
+  [[EnumAction::Released; C_NUM_GAMEPAD_BUTTONS]; C_NUM_GAMEPADS];
+// Last cursor position queried for deltas, in screen coordinates.
+static mut S_MOUSE_CURSOR_POSITION: Option<(f32, f32)> = None;
+// Cursor position at the start of the current frame, and how much it moved since the previous one.
+static mut S_MOUSE_FRAME_POSITION: Option<(f32, f32)> = None;
+static mut S_MOUSE_FRAME_DELTA: (f32, f32) = (0.0, 0.0);
+// Frame being replayed, read instead of the window for current input states.
+static mut S_REPLAYED_FRAME: Option<InputFrame> = None;
+
+// All known keys, used to resolve key names when loading action maps.
+const C_KEYS: [EnumKey; 120] = [
+  EnumKey::Space, EnumKey::Apostrophe, EnumKey::Comma, EnumKey::Minus, EnumKey::Period, EnumKey::Slash, EnumKey::Num0,
+  EnumKey::Num1, EnumKey::Num2, EnumKey::Num3, EnumKey::Num4, EnumKey::Num5, EnumKey::Num6, EnumKey::Num7,
+  EnumKey::Num8, EnumKey::Num9, EnumKey::Semicolon, EnumKey::Equal, EnumKey::A, EnumKey::B, EnumKey::C, EnumKey::D,
+  EnumKey::E, EnumKey::F, EnumKey::G, EnumKey::H, EnumKey::I, EnumKey::J, EnumKey::K, EnumKey::L, EnumKey::M,
+  EnumKey::N, EnumKey::O, EnumKey::P, EnumKey::Q, EnumKey::R, EnumKey::S, EnumKey::T, EnumKey::U, EnumKey::V,
+  EnumKey::W, EnumKey::X, EnumKey::Y, EnumKey::Z, EnumKey::LeftBracket, EnumKey::Backslash, EnumKey::RightBracket,
+  EnumKey::GraveAccent, EnumKey::World1, EnumKey::World2, EnumKey::Escape, EnumKey::Enter, EnumKey::Tab,
+  EnumKey::Backspace, EnumKey::Insert, EnumKey::Delete, EnumKey::Right, EnumKey::Left, EnumKey::Down, EnumKey::Up,
+  EnumKey::PageUp, EnumKey::PageDown, EnumKey::Home, EnumKey::End, EnumKey::CapsLock, EnumKey::ScrollLock,
+  EnumKey::NumLock, EnumKey::PrintScreen, EnumKey::Pause, EnumKey::F1, EnumKey::F2, EnumKey::F3, EnumKey::F4,
+  EnumKey::F5, EnumKey::F6, EnumKey::F7, EnumKey::F8, EnumKey::F9, EnumKey::F10, EnumKey::F11, EnumKey::F12,
+  EnumKey::F13, EnumKey::F14, EnumKey::F15, EnumKey::F16, EnumKey::F17, EnumKey::F18, EnumKey::F19, EnumKey::F20,
+  EnumKey::F21, EnumKey::F22, EnumKey::F23, EnumKey::F24, EnumKey::F25, EnumKey::Kp0, EnumKey::Kp1, EnumKey::Kp2,
+  EnumKey::Kp3, EnumKey::Kp4, EnumKey::Kp5, EnumKey::Kp6, EnumKey::Kp7, EnumKey::Kp8, EnumKey::Kp9, EnumKey::KpDecimal,
+  EnumKey::KpDivide, EnumKey::KpMultiply, EnumKey::KpSubtract, EnumKey::KpAdd, EnumKey::KpEnter, EnumKey::KpEqual,
+  EnumKey::LeftShift, EnumKey::LeftControl, EnumKey::LeftAlt, EnumKey::LeftSuper, EnumKey::RightShift,
+  EnumKey::RightControl, EnumKey::RightAlt, EnumKey::RightSuper, EnumKey::Menu
+];
+
+const C_MOUSE_BUTTONS: [EnumMouseButton; 8] = [
+  EnumMouseButton::LeftButton, EnumMouseButton::RightButton, EnumMouseButton::MiddleButton, EnumMouseButton::Button4,
+  EnumMouseButton::Button5, EnumMouseButton::Button6, EnumMouseButton::Button7, EnumMouseButton::Button8
+];
+
+const C_GAMEPAD_BUTTONS: [EnumGamepadButton; 15] = [
+  EnumGamepadButton::A, EnumGamepadButton::B, EnumGamepadButton::X, EnumGamepadButton::Y,
+  EnumGamepadButton::LeftBumper, EnumGamepadButton::RightBumper, EnumGamepadButton::Back, EnumGamepadButton::Start,
+  EnumGamepadButton::Guide, EnumGamepadButton::LeftThumb, EnumGamepadButton::RightThumb, EnumGamepadButton::DpadUp,
+  EnumGamepadButton::DpadRight, EnumGamepadButton::DpadDown, EnumGamepadButton::DpadLeft
+];
 
 #[derive(Debug, Eq, PartialEq)]
 pub enum EnumInputError {
   InvalidWindowContext,
   InvalidKey,
   InvalidMouseButton,
+  InvalidGamepadButton,
+  InvalidBinding,
+  InvalidActionMapFile,
+  InvalidSnapshot,
 }
 
 #[repr(i32)]
@@ -210,9 +260,55 @@ impl From<EnumMouseButton> for glfw::MouseButton {
   }
 }
 
+/// Buttons of a gamepad, named after the Xbox controller layout.
+#[repr(i32)]
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub enum EnumGamepadButton {
+  A = glfw::ffi::GAMEPAD_BUTTON_A,
+  B = glfw::ffi::GAMEPAD_BUTTON_B,
+  X = glfw::ffi::GAMEPAD_BUTTON_X,
+  Y = glfw::ffi::GAMEPAD_BUTTON_Y,
+  LeftBumper = glfw::ffi::GAMEPAD_BUTTON_LEFT_BUMPER,
+  RightBumper = glfw::ffi::GAMEPAD_BUTTON_RIGHT_BUMPER,
+  Back = glfw::ffi::GAMEPAD_BUTTON_BACK,
+  Start = glfw::ffi::GAMEPAD_BUTTON_START,
+  Guide = glfw::ffi::GAMEPAD_BUTTON_GUIDE,
+  LeftThumb = glfw::ffi::GAMEPAD_BUTTON_LEFT_THUMB,
+  RightThumb = glfw::ffi::GAMEPAD_BUTTON_RIGHT_THUMB,
+  DpadUp = glfw::ffi::GAMEPAD_BUTTON_DPAD_UP,
+  DpadRight = glfw::ffi::GAMEPAD_BUTTON_DPAD_RIGHT,
+  DpadDown = glfw::ffi::GAMEPAD_BUTTON_DPAD_DOWN,
+  DpadLeft = glfw::ffi::GAMEPAD_BUTTON_DPAD_LEFT,
+}
+
+impl From<EnumGamepadButton> for glfw::GamepadButton {
+  fn from(value: EnumGamepadButton) -> Self {
+    return convert_gamepad_btn_to_api_gamepad_btn(value);
+  }
+}
+
+/// Axes of a gamepad, ranging from -1 to 1. Triggers rest at -1.
+#[repr(i32)]
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub enum EnumGamepadAxis {
+  LeftX = glfw::ffi::GAMEPAD_AXIS_LEFT_X,
+  LeftY = glfw::ffi::GAMEPAD_AXIS_LEFT_Y,
+  RightX = glfw::ffi::GAMEPAD_AXIS_RIGHT_X,
+  RightY = glfw::ffi::GAMEPAD_AXIS_RIGHT_Y,
+  LeftTrigger = glfw::ffi::GAMEPAD_AXIS_LEFT_TRIGGER,
+  RightTrigger = glfw::ffi::GAMEPAD_AXIS_RIGHT_TRIGGER,
+}
+
+impl From<EnumGamepadAxis> for glfw::GamepadAxis {
+  fn from(value: EnumGamepadAxis) -> Self {
+    return convert_gamepad_axis_to_api_gamepad_axis(value);
+  }
+}
+
 #[repr(i32)]
 #[doc = "Key events for each key input."]
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum EnumAction {
   Released = glfw::ffi::RELEASE,
   Pressed = glfw::ffi::PRESS,
@@ -538,6 +634,37 @@ fn convert_api_mouse_btn_to_mouse_btn(api_mouse_button: glfw::MouseButton) -> En
   };
 }
 
+fn convert_gamepad_btn_to_api_gamepad_btn(enum_gamepad_button: EnumGamepadButton) -> glfw::GamepadButton {
+  return match enum_gamepad_button {
+    EnumGamepadButton::A => glfw::GamepadButton::ButtonA,
+    EnumGamepadButton::B => glfw::GamepadButton::ButtonB,
+    EnumGamepadButton::X => glfw::GamepadButton::ButtonX,
+    EnumGamepadButton::Y => glfw::GamepadButton::ButtonY,
+    EnumGamepadButton::LeftBumper => glfw::GamepadButton::ButtonLeftBumper,
+    EnumGamepadButton::RightBumper => glfw::GamepadButton::ButtonRightBumper,
+    EnumGamepadButton::Back => glfw::GamepadButton::ButtonBack,
+    EnumGamepadButton::Start => glfw::GamepadButton::ButtonStart,
+    EnumGamepadButton::Guide => glfw::GamepadButton::ButtonGuide,
+    EnumGamepadButton::LeftThumb => glfw::GamepadButton::ButtonLeftThumb,
+    EnumGamepadButton::RightThumb => glfw::GamepadButton::ButtonRightThumb,
+    EnumGamepadButton::DpadUp => glfw::GamepadButton::ButtonDpadUp,
+    EnumGamepadButton::DpadRight => glfw::GamepadButton::ButtonDpadRight,
+    EnumGamepadButton::DpadDown => glfw::GamepadButton::ButtonDpadDown,
+    EnumGamepadButton::DpadLeft => glfw::GamepadButton::ButtonDpadLeft
+  };
+}
+
+fn convert_gamepad_axis_to_api_gamepad_axis(enum_gamepad_axis: EnumGamepadAxis) -> glfw::GamepadAxis {
+  return match enum_gamepad_axis {
+    EnumGamepadAxis::LeftX => glfw::GamepadAxis::AxisLeftX,
+    EnumGamepadAxis::LeftY => glfw::GamepadAxis::AxisLeftY,
+    EnumGamepadAxis::RightX => glfw::GamepadAxis::AxisRightX,
+    EnumGamepadAxis::RightY => glfw::GamepadAxis::AxisRightY,
+    EnumGamepadAxis::LeftTrigger => glfw::GamepadAxis::AxisLeftTrigger,
+    EnumGamepadAxis::RightTrigger => glfw::GamepadAxis::AxisRightTrigger
+  };
+}
+
 fn convert_action_to_api_action(enum_action: EnumAction) -> glfw::Action {
   match enum_action {
     EnumAction::Released => glfw::Action::Release,
@@ -574,6 +701,37 @@ impl Input {
       for mouse_button in 0..S_MOUSE_BUTTON_STATES.len() {
         S_MOUSE_BUTTON_STATES[mouse_button] = EnumAction::Released;
       }
+      
+      for gamepad in 0..S_GAMEPAD_BUTTON_STATES.len() {
+        S_GAMEPAD_BUTTON_STATES[gamepad] = [EnumAction::Released; C_NUM_GAMEPAD_BUTTONS];
+      }
+      
+      S_MOUSE_CURSOR_POSITION = None;
+      S_MOUSE_FRAME_POSITION = None;
+      S_MOUSE_FRAME_DELTA = (0.0, 0.0);
+      S_REPLAYED_FRAME = None;
+    }
+  }
+  
+  /// Track per-frame input state, such as the mouse delta (see [Self::get_mouse_delta]). Called by the engine once
+  /// per frame, right after polling window events. Reads the frame being replayed if any, otherwise the active
+  /// engine's window.
+  pub fn on_update() {
+    let window: Option<&Window> = unsafe { S_ENGINE.map(|engine| &(*engine).m_window) };
+    let new_position: Option<(f32, f32)> = match unsafe { S_REPLAYED_FRAME.as_ref() } {
+      Some(replayed_frame) => Some(replayed_frame.m_cursor_position),
+      None => window.and_then(|window| window.m_api_window.as_ref())
+        .map(|api_window| api_window.get_cursor_pos())
+        .map(|(x_position, y_position)| (x_position as f32, y_position as f32)),
+    };
+    
+    unsafe {
+      // No movement on the first frame, or while no window is there to move in.
+      S_MOUSE_FRAME_DELTA = match (S_MOUSE_FRAME_POSITION, new_position) {
+        (Some((old_x, old_y)), Some((new_x, new_y))) => (new_x - old_x, new_y - old_y),
+        _ => (0.0, 0.0),
+      };
+      S_MOUSE_FRAME_POSITION = new_position;
     }
   }
   
@@ -584,25 +742,40 @@ impl Input {
       S_KEY_STATES[api_key as usize].0
     };
     
-    let new_state = window.m_api_window.as_ref().unwrap().get_key(api_key);
-    let old_repeat_count: Option<u32> = unsafe { S_KEY_STATES[api_key as usize].1 };
-    unsafe { S_KEY_STATES[api_key as usize] = (EnumAction::from(new_state), old_repeat_count) };
+    let new_state: EnumAction = Input::poll_key(Some(window), key_code, old_state);
+    // Keep counting repeats while the key stays down, the count only comes from key events.
+    let repeat_count: Option<u32> = match new_state {
+      EnumAction::Released => None,
+      _ => unsafe { S_KEY_STATES[api_key as usize].1 },
+    };
+    unsafe { S_KEY_STATES[api_key as usize] = (new_state, repeat_count) };
     
     return match key_action {
       EnumAction::Released => {
-        old_state == EnumAction::Pressed && new_state == glfw::Action::Release
+        old_state == EnumAction::Pressed && new_state == EnumAction::Released
       }
       EnumAction::Pressed => {
-        old_state == EnumAction::Released &&
-          (new_state == glfw::Action::Press && new_state != glfw::Action::Repeat)
+        old_state == EnumAction::Released && new_state == EnumAction::Pressed
       }
       EnumAction::Held => {
         (old_state == EnumAction::Pressed || old_state == EnumAction::Held) &&
-          (new_state == glfw::Action::Press || new_state == glfw::Action::Repeat)
+          (new_state == EnumAction::Pressed || new_state == EnumAction::Held)
       }
     }
   }
   
+  // Current state of a key, from the replayed frame if any, otherwise from the window. Without either, the key is
+  // considered unchanged.
+  fn poll_key(window: Option<&Window>, key: EnumKey, old_state: EnumAction) -> EnumAction {
+    if let Some(frame) = unsafe { S_REPLAYED_FRAME.as_ref() } {
+      return frame.get_key(key).map_or(old_state, |(_previous, current)| current);
+    }
+    return match window.and_then(|window| window.m_api_window.as_ref()) {
+      Some(api_window) => EnumAction::from(api_window.get_key(convert_key_to_api_key(key))),
+      None => old_state,
+    };
+  }
+  
   #[allow(unused)]
   pub(crate) fn get_key_name(key_code: EnumKey) -> Result<String, EnumInputError> {
     let api_key = convert_key_to_api_key(key_code);
@@ -614,21 +787,42 @@ impl Input {
     return Err(EnumInputError::InvalidKey);
   }
   
-  #[allow(unused)]
-  pub(crate) fn get_key_repeat(key: EnumKey) -> Option<u32> {
-    unsafe {
-      let (_, repeat_count) = S_KEY_STATES[key as usize];
-      return repeat_count;
-    }
+  /// How many times in a row a key has been reported as held since it was pressed, or `None` when it is up or was
+  /// only just pressed. Useful to accelerate repeated actions, like erasing text while holding backspace.
+  pub fn get_key_repeat(key: EnumKey) -> Option<u32> {
+    let Some(key_index) = Input::get_key_index(key) else {
+      return None;
+    };
+    return unsafe { S_KEY_STATES[key_index].1 };
   }
   
   #[allow(unused)]
   pub(crate) fn set_key_repeat(key: EnumKey, count: u32) {
-    unsafe {
-      S_KEY_STATES[key as usize].1 = Some(count);
+    if let Some(key_index) = Input::get_key_index(key) {
+      unsafe { S_KEY_STATES[key_index].1 = Some(count) };
     }
   }
   
+  /// Record a key event from the window, returning the key's repeat count afterward (see [Self::get_key_repeat]).
+  pub(crate) fn track_key_repeat(key: EnumKey, action: EnumAction) -> Option<u32> {
+    let Some(key_index) = Input::get_key_index(key) else {
+      return None;
+    };
+    
+    let repeat_count: Option<u32> = match action {
+      EnumAction::Pressed | EnumAction::Released => None,
+      EnumAction::Held => Some(unsafe { S_KEY_STATES[key_index].1 }.unwrap_or(0) + 1),
+    };
+    // Leave the state itself to polling, which needs the previous one to detect presses and releases.
+    unsafe { S_KEY_STATES[key_index].1 = repeat_count };
+    return repeat_count;
+  }
+  
+  fn get_key_index(key: EnumKey) -> Option<usize> {
+    // Unknown keys have a negative code.
+    return usize::try_from(key as i32).ok().filter(|key_index| *key_index < C_NUM_KEYS);
+  }
+  
   #[allow(unused)]
   pub fn get_modifier_key_combo(window: &Window, first_key: EnumKey, second_key: EnumModifiers) -> bool {
     if second_key.contains(EnumModifiers::Shift) {
@@ -703,49 +897,506 @@ impl Input {
   
   // MOUSE BUTTON QUERY FUNCTIONS.
   pub fn get_mouse_button_state(window: &Window, mouse_button: EnumMouseButton, mouse_button_action: EnumAction) -> bool {
-    let api_mouse_button = convert_mouse_btn_to_api_mouse_btn(mouse_button);
+    let Some(button_index) = Input::get_mouse_button_index(mouse_button) else {
+      return false;
+    };
     
     let old_state = unsafe {
-      S_MOUSE_BUTTON_STATES[api_mouse_button as usize]
+      S_MOUSE_BUTTON_STATES[button_index]
     };
-    let new_state = window.m_api_window.as_ref().unwrap().get_mouse_button(api_mouse_button);
+    let new_state: EnumAction = Input::poll_mouse_button(Some(window), mouse_button, old_state);
     
-    unsafe { S_MOUSE_BUTTON_STATES[api_mouse_button as usize] = EnumAction::from(new_state) };
+    unsafe { S_MOUSE_BUTTON_STATES[button_index] = new_state };
     
     return match mouse_button_action {
       EnumAction::Released => {
-        old_state == EnumAction::Pressed &&
-          new_state == glfw::Action::from(EnumAction::Released)
+        old_state == EnumAction::Pressed && new_state == EnumAction::Released
       }
       EnumAction::Pressed => {
-        old_state == EnumAction::Released &&
-          new_state == glfw::Action::from(EnumAction::Pressed)
+        old_state == EnumAction::Released && new_state == EnumAction::Pressed
       }
       EnumAction::Held => {
-        old_state == EnumAction::Pressed &&
-          new_state == glfw::Action::from(EnumAction::Pressed)
+        old_state == EnumAction::Pressed && new_state == EnumAction::Pressed
       }
     };
   }
   
+  // See [Self::poll_key].
+  fn poll_mouse_button(window: Option<&Window>, mouse_button: EnumMouseButton, old_state: EnumAction) -> EnumAction {
+    if let Some(frame) = unsafe { S_REPLAYED_FRAME.as_ref() } {
+      return frame.get_mouse_button(mouse_button).map_or(old_state, |(_previous, current)| current);
+    }
+    return match window.and_then(|window| window.m_api_window.as_ref()) {
+      Some(api_window) => EnumAction::from(api_window.get_mouse_button(convert_mouse_btn_to_api_mouse_btn(mouse_button))),
+      None => old_state,
+    };
+  }
+  
+  // GAMEPAD QUERY FUNCTIONS.
+  
+  /// Whether a joystick is plugged in at `gamepad_id` (0 to 15), with a known gamepad mapping.
+  pub fn is_gamepad_connected(window: &Window, gamepad_id: u32) -> bool {
+    return Input::poll_gamepad(window, gamepad_id).is_some();
+  }
+  
+  pub fn get_gamepad_button_state(window: &Window, gamepad_id: u32, gamepad_button: EnumGamepadButton,
+                                  gamepad_button_action: EnumAction) -> bool {
+    let Some(gamepad_index) = Input::get_gamepad_index(gamepad_id) else {
+      return false;
+    };
+    let button_index = gamepad_button as usize;
+    
+    let old_state = unsafe {
+      S_GAMEPAD_BUTTON_STATES[gamepad_index][button_index]
+    };
+    let new_state: EnumAction = Input::poll_gamepad_button(Some(window), gamepad_id, gamepad_button);
+    
+    unsafe { S_GAMEPAD_BUTTON_STATES[gamepad_index][button_index] = new_state };
+    
+    return match gamepad_button_action {
+      EnumAction::Released => {
+        old_state == EnumAction::Pressed && new_state == EnumAction::Released
+      }
+      EnumAction::Pressed => {
+        old_state == EnumAction::Released && new_state == EnumAction::Pressed
+      }
+      EnumAction::Held => {
+        old_state == EnumAction::Pressed && new_state == EnumAction::Pressed
+      }
+    };
+  }
+  
+  /// Current position of a gamepad axis, with values within `deadzone` of the rest position ignored (see
+  /// [Self::apply_deadzone]). Disconnected gamepads report 0.
+  pub fn get_gamepad_axis(window: &Window, gamepad_id: u32, gamepad_axis: EnumGamepadAxis, deadzone: f32) -> f32 {
+    let value: f32 = Input::poll_gamepad(window, gamepad_id)
+      .map_or(0.0, |gamepad_state| gamepad_state.get_axis(convert_gamepad_axis_to_api_gamepad_axis(gamepad_axis)));
+    return Input::apply_deadzone(value, deadzone);
+  }
+  
+  /// Snap axis values within `deadzone` of 0 to 0, and rescale the rest so that the output still covers [-1, 1]
+  /// smoothly, without jumping at the edge of the deadzone.
+  pub fn apply_deadzone(value: f32, deadzone: f32) -> f32 {
+    let value: f32 = value.clamp(-1.0, 1.0);
+    let deadzone: f32 = deadzone.clamp(0.0, 1.0);
+    
+    if value.abs() <= deadzone {
+      return 0.0;
+    }
+    return value.signum() * (value.abs() - deadzone) / (1.0 - deadzone);
+  }
+  
+  // Current state of a gamepad button, from the replayed frame if any, otherwise from the window. Buttons of a
+  // disconnected gamepad are all released.
+  fn poll_gamepad_button(window: Option<&Window>, gamepad_id: u32, gamepad_button: EnumGamepadButton) -> EnumAction {
+    if let Some(frame) = unsafe { S_REPLAYED_FRAME.as_ref() } {
+      return frame.get_gamepad_button(gamepad_id, gamepad_button)
+        .map_or(EnumAction::Released, |(_previous, current)| current);
+    }
+    return window.and_then(|window| Input::poll_gamepad(window, gamepad_id))
+      .map_or(EnumAction::Released, |gamepad_state| EnumAction::from(gamepad_state.get_button_state(
+        convert_gamepad_btn_to_api_gamepad_btn(gamepad_button))));
+  }
+  
+  fn poll_gamepad(window: &Window, gamepad_id: u32) -> Option<glfw::GamepadState> {
+    let joystick_id = glfw::JoystickId::from_i32(i32::try_from(gamepad_id).ok()?)?;
+    let joystick = window.m_api_window.as_ref()?.glfw.get_joystick(joystick_id);
+    
+    if !joystick.is_gamepad() {
+      return None;
+    }
+    return joystick.get_gamepad_state();
+  }
+  
   // MOUSE MOVEMENT STATIC FUNCTIONS.
-  #[allow(unused)]
-  pub(crate) fn get_mouse_cursor_position() {
-    todo!()
+  pub fn get_mouse_cursor_position(window: &Window) -> Result<Vec2<f32>, EnumInputError> {
+    if let Some(frame) = unsafe { S_REPLAYED_FRAME.as_ref() } {
+      return Ok(Vec2::new(&[frame.m_cursor_position.0, frame.m_cursor_position.1]));
+    }
+    if window.m_api_window.is_none() {
+      log!(EnumLogColor::Red, "ERROR", "[Input] -->\t Cannot retrieve cursor position : No active window!");
+      return Err(EnumInputError::InvalidWindowContext);
+    }
+    
+    let (x_position, y_position) = window.m_api_window.as_ref().unwrap().get_cursor_pos();
+    return Ok(Vec2::new(&[x_position as f32, y_position as f32]));
   }
   
-  #[allow(unused)]
-  pub(crate) fn get_mouse_cursor_attribute(window: &Window) -> Result<glfw::CursorMode, EnumInputError> {
-    return Ok(window.m_api_window.as_ref().unwrap().get_cursor_mode());
+  pub fn get_mouse_cursor_delta(window: &Window) -> Result<Vec2<f32>, EnumInputError> {
+    let new_position = Input::get_mouse_cursor_position(window)?;
+    
+    // No movement on the first query, since we have nothing to compare against.
+    let (old_x, old_y) = unsafe {
+      S_MOUSE_CURSOR_POSITION.unwrap_or((new_position.x, new_position.y))
+    };
+    unsafe { S_MOUSE_CURSOR_POSITION = Some((new_position.x, new_position.y)) };
+    
+    return Ok(Vec2::new(&[new_position.x - old_x, new_position.y - old_y]));
   }
   
-  #[allow(unused)]
-  pub(crate) fn set_mouse_cursor_attribute(window: &mut Window, cursor_mode: glfw::CursorMode) {
-    return window.m_api_window.as_mut().unwrap().set_cursor_mode(cursor_mode);
+  /// How much the cursor moved between the start of the previous frame and the start of this one, in screen
+  /// coordinates. Unlike [Self::get_mouse_cursor_delta], every query in a frame returns the same delta. Lock the
+  /// cursor with [EnumCursorMode::Disabled] to keep it flowing past the edges of the window, for mouse-look.
+  pub fn get_mouse_delta() -> Vec2<f32> {
+    let (x_delta, y_delta) = unsafe { S_MOUSE_FRAME_DELTA };
+    return Vec2::new(&[x_delta, y_delta]);
+  }
+  
+  pub fn get_mouse_cursor_attribute(window: &Window) -> Result<EnumCursorMode, EnumInputError> {
+    return window.get_cursor_mode().map_err(|_| EnumInputError::InvalidWindowContext);
+  }
+  
+  pub fn set_mouse_cursor_attribute(window: &mut Window, cursor_mode: EnumCursorMode) -> Result<(), EnumInputError> {
+    return window.set_cursor_mode(cursor_mode).map_err(|_| EnumInputError::InvalidWindowContext);
   }
   
   #[allow(unused)]
   pub(crate) fn set_mouse_cursor_position(window: &mut Window, cursor_position: Vec2<f32>) {
     return window.m_api_window.as_mut().unwrap().set_cursor_pos(cursor_position.x as f64, cursor_position.y as f64);
   }
-}
\ No newline at end of file
+  
+  // REPLAY FUNCTIONS.
+  
+  /// Capture the whole input state as queries would see it right now : previous and current state of every key, mouse
+  /// button and gamepad button, key repeat counts and cursor positions. Current states come from the frame being replayed if any,
+  /// otherwise from the active engine's window. Take snapshots at the same point of every frame (e.g. at the start of
+  /// [crate::layers::TraitLayer::on_update]) to replay them faithfully.
+  pub fn snapshot() -> InputFrame {
+    let window: Option<&Window> = unsafe { S_ENGINE.map(|engine| &(*engine).m_window) };
+    let mut frame = InputFrame::default();
+    
+    for key in C_KEYS.iter() {
+      let Some(key_index) = Input::get_key_index(*key) else {
+        continue;
+      };
+      let (previous_state, repeat_count) = unsafe { S_KEY_STATES[key_index] };
+      frame.m_key_states[key_index] = (previous_state, Input::poll_key(window, *key, previous_state));
+      frame.m_key_repeats[key_index] = repeat_count;
+    }
+    
+    for mouse_button in C_MOUSE_BUTTONS.iter() {
+      let Some(button_index) = Input::get_mouse_button_index(*mouse_button) else {
+        continue;
+      };
+      let previous_state = unsafe { S_MOUSE_BUTTON_STATES[button_index] };
+      frame.m_mouse_button_states[button_index] = (previous_state, Input::poll_mouse_button(window, *mouse_button,
+        previous_state));
+    }
+    
+    for gamepad_id in 0..C_NUM_GAMEPADS as u32 {
+      for gamepad_button in C_GAMEPAD_BUTTONS.iter() {
+        let Some(state_index) = Input::get_gamepad_button_index(gamepad_id, *gamepad_button) else {
+          continue;
+        };
+        let previous_state = unsafe { S_GAMEPAD_BUTTON_STATES[gamepad_id as usize][*gamepad_button as usize] };
+        frame.m_gamepad_button_states[state_index] = (previous_state, Input::poll_gamepad_button(window, gamepad_id,
+          *gamepad_button));
+      }
+    }
+    
+    frame.m_last_cursor_position = unsafe { S_MOUSE_CURSOR_POSITION };
+    frame.m_cursor_position = match unsafe { S_REPLAYED_FRAME.as_ref() } {
+      Some(replayed_frame) => replayed_frame.m_cursor_position,
+      None => window.and_then(|window| Input::get_mouse_cursor_position(window).ok())
+        .map(|position| (position.x, position.y))
+        .or(frame.m_last_cursor_position)
+        .unwrap_or((0.0, 0.0)),
+    };
+    return frame;
+  }
+  
+  /// Force the input state to a recorded frame, so that key, mouse button, gamepad button and cursor queries (including presses and
+  /// releases) read it back instead of the window, until the next snapshot is applied or [Self::stop_replay] is called.
+  pub fn apply_snapshot(frame: &InputFrame) -> Result<(), EnumInputError> {
+    if frame.m_key_states.len() != C_NUM_KEYS || frame.m_key_repeats.len() != C_NUM_KEYS ||
+      frame.m_mouse_button_states.len() != C_NUM_MOUSE_BUTTONS ||
+      frame.m_gamepad_button_states.len() != C_NUM_GAMEPADS * C_NUM_GAMEPAD_BUTTONS {
+      log!(EnumLogColor::Red, "ERROR", "[Input] -->\t Cannot apply input snapshot : Expected {0} keys, {1} mouse \
+      buttons and {2} gamepad buttons, got {3}, {4} and {5}!", C_NUM_KEYS, C_NUM_MOUSE_BUTTONS,
+        C_NUM_GAMEPADS * C_NUM_GAMEPAD_BUTTONS, frame.m_key_states.len(), frame.m_mouse_button_states.len(),
+        frame.m_gamepad_button_states.len());
+      return Err(EnumInputError::InvalidSnapshot);
+    }
+    
+    unsafe {
+      for key_index in 0..C_NUM_KEYS {
+        S_KEY_STATES[key_index] = (frame.m_key_states[key_index].0, frame.m_key_repeats[key_index]);
+      }
+      for button_index in 0..C_NUM_MOUSE_BUTTONS {
+        S_MOUSE_BUTTON_STATES[button_index] = frame.m_mouse_button_states[button_index].0;
+      }
+      for gamepad_index in 0..C_NUM_GAMEPADS {
+        for button_index in 0..C_NUM_GAMEPAD_BUTTONS {
+          S_GAMEPAD_BUTTON_STATES[gamepad_index][button_index] =
+            frame.m_gamepad_button_states[gamepad_index * C_NUM_GAMEPAD_BUTTONS + button_index].0;
+        }
+      }
+      S_MOUSE_CURSOR_POSITION = frame.m_last_cursor_position;
+      S_REPLAYED_FRAME = Some(frame.clone());
+    }
+    return Ok(());
+  }
+  
+  /// Go back to reading input from the window, keeping the last replayed frame's state as the previous one.
+  pub fn stop_replay() {
+    unsafe { S_REPLAYED_FRAME = None };
+  }
+  
+  pub fn is_replaying() -> bool {
+    return unsafe { S_REPLAYED_FRAME.is_some() };
+  }
+  
+  fn get_mouse_button_index(mouse_button: EnumMouseButton) -> Option<usize> {
+    return usize::try_from(mouse_button as i32).ok().filter(|button_index| *button_index < C_NUM_MOUSE_BUTTONS);
+  }
+  
+  fn get_gamepad_index(gamepad_id: u32) -> Option<usize> {
+    return usize::try_from(gamepad_id).ok().filter(|gamepad_index| *gamepad_index < C_NUM_GAMEPADS);
+  }
+  
+  // Index of a gamepad button in the flattened state of all gamepads.
+  fn get_gamepad_button_index(gamepad_id: u32, gamepad_button: EnumGamepadButton) -> Option<usize> {
+    return Input::get_gamepad_index(gamepad_id)
+      .map(|gamepad_index| gamepad_index * C_NUM_GAMEPAD_BUTTONS + gamepad_button as usize);
+  }
+}
+
+/// Input state of a single frame, captured with [Input::snapshot] and replayed with [Input::apply_snapshot]. Keys and
+/// mouse buttons keep both their previous and current state, for presses and releases to be detected again on replay.
+/// Gamepad buttons are kept the same way for every gamepad slot, gamepad axes aren't part of it.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InputFrame {
+  // (previous, current), indexed by key code.
+  m_key_states: Vec<(EnumAction, EnumAction)>,
+  m_key_repeats: Vec<Option<u32>>,
+  // (previous, current), indexed by mouse button.
+  m_mouse_button_states: Vec<(EnumAction, EnumAction)>,
+  // (previous, current), indexed by gamepad id then gamepad button.
+  m_gamepad_button_states: Vec<(EnumAction, EnumAction)>,
+  m_cursor_position: (f32, f32),
+  // Last position queried for cursor deltas.
+  m_last_cursor_position: Option<(f32, f32)>,
+}
+
+impl Default for InputFrame {
+  fn default() -> Self {
+    return Self {
+      m_key_states: vec![(EnumAction::Released, EnumAction::Released); C_NUM_KEYS],
+      m_key_repeats: vec![None; C_NUM_KEYS],
+      m_mouse_button_states: vec![(EnumAction::Released, EnumAction::Released); C_NUM_MOUSE_BUTTONS],
+      m_gamepad_button_states: vec![(EnumAction::Released, EnumAction::Released); C_NUM_GAMEPADS * C_NUM_GAMEPAD_BUTTONS],
+      m_cursor_position: (0.0, 0.0),
+      m_last_cursor_position: None,
+    };
+  }
+}
+
+impl InputFrame {
+  /// Previous and current state of `key`, or `None` for unknown keys.
+  pub fn get_key(&self, key: EnumKey) -> Option<(EnumAction, EnumAction)> {
+    return Input::get_key_index(key).and_then(|key_index| self.m_key_states.get(key_index).copied());
+  }
+  
+  pub fn set_key(&mut self, key: EnumKey, previous_state: EnumAction, current_state: EnumAction) {
+    if let Some(key_state) = Input::get_key_index(key).and_then(|key_index| self.m_key_states.get_mut(key_index)) {
+      *key_state = (previous_state, current_state);
+    }
+  }
+  
+  pub fn get_key_repeat(&self, key: EnumKey) -> Option<u32> {
+    return Input::get_key_index(key).and_then(|key_index| self.m_key_repeats.get(key_index).copied().flatten());
+  }
+  
+  /// Previous and current state of `mouse_button`, or `None` for buttons the engine doesn't track.
+  pub fn get_mouse_button(&self, mouse_button: EnumMouseButton) -> Option<(EnumAction, EnumAction)> {
+    return Input::get_mouse_button_index(mouse_button)
+      .and_then(|button_index| self.m_mouse_button_states.get(button_index).copied());
+  }
+  
+  pub fn set_mouse_button(&mut self, mouse_button: EnumMouseButton, previous_state: EnumAction, current_state: EnumAction) {
+    if let Some(button_state) = Input::get_mouse_button_index(mouse_button)
+      .and_then(|button_index| self.m_mouse_button_states.get_mut(button_index)) {
+      *button_state = (previous_state, current_state);
+    }
+  }
+  
+  /// Previous and current state of `gamepad_button` on gamepad `gamepad_id`, or `None` for gamepads past the last one.
+  pub fn get_gamepad_button(&self, gamepad_id: u32, gamepad_button: EnumGamepadButton) -> Option<(EnumAction, EnumAction)> {
+    return Input::get_gamepad_button_index(gamepad_id, gamepad_button)
+      .and_then(|state_index| self.m_gamepad_button_states.get(state_index).copied());
+  }
+  
+  pub fn set_gamepad_button(&mut self, gamepad_id: u32, gamepad_button: EnumGamepadButton, previous_state: EnumAction,
+                            current_state: EnumAction) {
+    if let Some(button_state) = Input::get_gamepad_button_index(gamepad_id, gamepad_button)
+      .and_then(|state_index| self.m_gamepad_button_states.get_mut(state_index)) {
+      *button_state = (previous_state, current_state);
+    }
+  }
+  
+  pub fn get_cursor_position(&self) -> Vec2<f32> {
+    return Vec2::new(&[self.m_cursor_position.0, self.m_cursor_position.1]);
+  }
+  
+  pub fn set_cursor_position(&mut self, cursor_position: Vec2<f32>) {
+    self.m_cursor_position = (cursor_position.x, cursor_position.y);
+  }
+}
+
+/*
+///////////////////////////////////   Action Map  ///////////////////////////////////
+///////////////////////////////////               ///////////////////////////////////
+///////////////////////////////////               ///////////////////////////////////
+ */
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum EnumInputBinding {
+  Key(EnumKey),
+  MouseButton(EnumMouseButton),
+  GamepadButton(u32, EnumGamepadButton),
+}
+
+impl Display for EnumInputBinding {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    return match self {
+      EnumInputBinding::Key(key) => write!(f, "Key:{0:?}", key),
+      EnumInputBinding::MouseButton(mouse_button) => write!(f, "MouseButton:{0:?}", mouse_button),
+      EnumInputBinding::GamepadButton(gamepad_id, gamepad_button) => {
+        write!(f, "GamepadButton:{0}:{1:?}", gamepad_id, gamepad_button)
+      }
+    };
+  }
+}
+
+impl std::str::FromStr for EnumInputBinding {
+  type Err = EnumInputError;
+  
+  fn from_str(binding_str: &str) -> Result<Self, Self::Err> {
+    return match binding_str.trim().split_once(':') {
+      Some(("Key", key_name)) => {
+        C_KEYS.iter().find(|key| format!("{0:?}", key) == key_name)
+          .map(|key| EnumInputBinding::Key(*key))
+          .ok_or(EnumInputError::InvalidKey)
+      }
+      Some(("MouseButton", button_name)) => {
+        C_MOUSE_BUTTONS.iter().find(|mouse_button| format!("{0:?}", mouse_button) == button_name)
+          .map(|mouse_button| EnumInputBinding::MouseButton(*mouse_button))
+          .ok_or(EnumInputError::InvalidMouseButton)
+      }
+      Some(("GamepadButton", gamepad_str)) => {
+        let (gamepad_id, button_name) = gamepad_str.split_once(':').ok_or(EnumInputError::InvalidBinding)?;
+        let gamepad_id = gamepad_id.parse::<u32>().map_err(|_| EnumInputError::InvalidBinding)?;
+        
+        C_GAMEPAD_BUTTONS.iter().find(|gamepad_button| format!("{0:?}", gamepad_button) == button_name)
+          .map(|gamepad_button| EnumInputBinding::GamepadButton(gamepad_id, *gamepad_button))
+          .ok_or(EnumInputError::InvalidGamepadButton)
+      }
+      _ => Err(EnumInputError::InvalidBinding)
+    };
+  }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActionMap {
+  m_actions: HashMap<String, Vec<EnumInputBinding>>,
+}
+
+impl ActionMap {
+  pub fn new() -> Self {
+    return Self {
+      m_actions: HashMap::new(),
+    };
+  }
+  
+  pub fn bind(&mut self, action_name: &str, binding: EnumInputBinding) {
+    let bindings = self.m_actions.entry(String::from(action_name)).or_default();
+    
+    if !bindings.contains(&binding) {
+      bindings.push(binding);
+    }
+  }
+  
+  pub fn unbind(&mut self, action_name: &str, binding: EnumInputBinding) {
+    if let Some(bindings) = self.m_actions.get_mut(action_name) {
+      bindings.retain(|existing_binding| *existing_binding != binding);
+    }
+  }
+  
+  pub fn clear(&mut self, action_name: &str) {
+    self.m_actions.remove(action_name);
+  }
+  
+  pub fn get_bindings(&self, action_name: &str) -> Option<&Vec<EnumInputBinding>> {
+    return self.m_actions.get(action_name);
+  }
+  
+  // Any of the bindings matching the requested state triggers the action.
+  pub fn is_action_active(&self, action_name: &str, state: EnumAction) -> bool {
+    return match self.m_actions.get(action_name) {
+      // Poll every binding, even once one is active, to keep the stored state of each one up to date. Otherwise, a
+      // skipped binding would report its press or release again on the next query.
+      Some(bindings) => bindings.iter()
+        .map(|binding| match binding {
+          EnumInputBinding::Key(key) => Engine::is_key(*key, state),
+          EnumInputBinding::MouseButton(mouse_button) => Engine::is_mouse_btn_from(*mouse_button, state),
+          EnumInputBinding::GamepadButton(gamepad_id, gamepad_button) => {
+            Engine::is_gamepad_btn_from(*gamepad_id, *gamepad_button, state)
+          }
+        })
+        .fold(false, |is_active, is_binding_active| is_active || is_binding_active),
+      None => false
+    };
+  }
+  
+  // Format : one action per line, as 'action_name=Key:W,MouseButton:LeftButton,GamepadButton:0:A'. Lines starting with '#' are ignored.
+  pub fn parse(action_map_str: &str) -> Result<Self, EnumInputError> {
+    let mut action_map = ActionMap::new();
+    
+    for line in action_map_str.lines().map(|line| line.trim()) {
+      if line.is_empty() || line.starts_with('#') {
+        continue;
+      }
+      
+      let (action_name, bindings) = line.split_once('=').ok_or(EnumInputError::InvalidActionMapFile)?;
+      for binding in bindings.split(',').filter(|binding| !binding.trim().is_empty()) {
+        action_map.bind(action_name.trim(), binding.parse::<EnumInputBinding>()?);
+      }
+    }
+    return Ok(action_map);
+  }
+  
+  pub fn load(file_path: &str) -> Result<Self, EnumInputError> {
+    return match std::fs::read_to_string(file_path) {
+      Ok(file_contents) => ActionMap::parse(&file_contents),
+      Err(_err) => {
+        log!(EnumLogColor::Red, "ERROR", "[Input] -->\t Cannot load action map from {0}, Error => {1}", file_path, _err);
+        Err(EnumInputError::InvalidActionMapFile)
+      }
+    };
+  }
+  
+  pub fn save(&self, file_path: &str) -> Result<(), EnumInputError> {
+    if let Err(_err) = std::fs::write(file_path, self.to_string()) {
+      log!(EnumLogColor::Red, "ERROR", "[Input] -->\t Cannot save action map to {0}, Error => {1}", file_path, _err);
+      return Err(EnumInputError::InvalidActionMapFile);
+    }
+    return Ok(());
+  }
+}
+
+impl Display for ActionMap {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    // Sort actions to keep saved files stable between runs.
+    let mut action_names: Vec<&String> = self.m_actions.keys().collect();
+    action_names.sort();
+    
+    for action_name in action_names {
+      let bindings: Vec<String> = self.m_actions[action_name].iter()
+        .map(|binding| binding.to_string())
+        .collect();
+      writeln!(f, "{0}={1}", action_name, bindings.join(","))?;
+    }
+    return Ok(());
+  }
+}
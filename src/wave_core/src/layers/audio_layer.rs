@@ -0,0 +1,110 @@
+/*
+ MIT License
+ 
+ Copyright (c) 2023 Nami Reghbati
+ 
+ Permission is hereby granted, free of charge, to any person obtaining a copy
+ of this software and associated documentation files (the "Software"), to deal
+ in the Software without restriction, including without limitation the rights
+ to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ copies of the Software, and to permit persons to whom the Software is
+ furnished to do so, subject to the following conditions:
+ 
+ The above copyright notice and this permission notice shall be included in all
+ copies or substantial portions of the Software.
+ 
+ THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ FITNESS FOR A PARTICULAR PURPOSE AND NON INFRINGEMENT. IN NO EVENT SHALL THE
+ AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ SOFTWARE.
+*/
+
+use crate::utils::macros::logger::*;
+use crate::{Engine, EnumEngineError, events, TraitApply, TraitFree};
+use crate::audio::{AudioEngine, AudioListener, EnumAudioError};
+use crate::layers::{EnumLayerType, TraitLayer};
+use crate::math::Vec3;
+
+pub struct AudioLayer {
+  pub(crate) m_context: *mut AudioEngine,
+}
+
+impl AudioLayer {
+  pub fn new(audio_context: &mut AudioEngine) -> Self {
+    return Self {
+      m_context: audio_context,
+    };
+  }
+}
+
+impl TraitLayer for AudioLayer {
+  fn get_type(&self) -> EnumLayerType {
+    return EnumLayerType::Audio;
+  }
+  
+  fn on_apply(&mut self) -> Result<(), EnumEngineError> {
+    log!(EnumLogColor::Purple, "INFO", "[Engine] -->\t Setting up audio...");
+    
+    // Machines without any output device (i.e. CI) can still run the engine, silently.
+    return match unsafe { (*self.m_context).apply() } {
+      Ok(()) => {
+        log!(EnumLogColor::Green, "INFO", "[Engine] -->\t Setup audio successfully");
+        Ok(())
+      }
+      Err(EnumAudioError::NoOutputDevice) => {
+        log!(EnumLogColor::Yellow, "WARN", "[Engine] -->\t No audio output device found, audio disabled!");
+        Ok(())
+      }
+      Err(err) => Err(EnumEngineError::from(err)),
+    };
+  }
+  
+  fn on_sync_event(&mut self) -> Result<(), EnumEngineError> {
+    return Ok(());
+  }
+  
+  fn on_async_event(&mut self, _event: &events::EnumEvent) -> Result<bool, EnumEngineError> {
+    return Ok(false);
+  }
+  
+  fn on_update(&mut self, _time_step: f64) -> Result<(), EnumEngineError> {
+    let audio_context = unsafe { &mut *self.m_context };
+    if !audio_context.is_applied() {
+      return Ok(());
+    }
+    
+    // Hear the world from the active camera.
+    if let Some(camera) = Engine::get_active_camera() {
+      let forward: Vec3<f32> = camera.get_forward();
+      audio_context.set_listener(AudioListener {
+        m_position: camera.get_position(),
+        m_right: forward.cross(Vec3::new(&[0.0, 1.0, 0.0])).normalize(),
+      });
+    }
+    audio_context.on_update();
+    return Ok(());
+  }
+  
+  fn on_render(&mut self) -> Result<(), EnumEngineError> {
+    return Ok(());
+  }
+  
+  fn free(&mut self) -> Result<(), EnumEngineError> {
+    return unsafe {
+      (*self.m_context).free().map_err(|err| EnumEngineError::from(err))
+    };
+  }
+  
+  fn to_string(&self) -> String {
+    unsafe {
+      return format!("\n{0:115}Applied?: {1}\n{0:115}Sounds playing: {2}\n{0:115}Master volume: {3}",
+        "",
+        (*self.m_context).is_applied(),
+        (*self.m_context).get_sound_count(),
+        (*self.m_context).get_master_volume());
+    }
+  }
+}
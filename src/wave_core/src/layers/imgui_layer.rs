@@ -36,6 +36,10 @@ impl ImguiLayer {
       m_ui: imgui
     }
   }
+  
+  pub fn get_ui_mut(&mut self) -> &mut Imgui {
+    return &mut self.m_ui;
+  }
 }
 
 impl TraitLayer for ImguiLayer {
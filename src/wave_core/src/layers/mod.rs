@@ -35,12 +35,14 @@ use crate::events::{self, EnumEvent, EnumEventMask};
 pub mod window_layer;
 pub mod renderer_layer;
 pub mod imgui_layer;
+pub mod audio_layer;
 
 #[derive(Debug, Copy, Clone, Ord, PartialOrd, PartialEq, Eq, Hash)]
 pub enum EnumLayerError {
   SyncDisabled,
   InvalidCallback,
-  InvalidEventMask
+  InvalidEventMask,
+  LayerNotFound,
 }
 
 impl Display for EnumLayerError {
@@ -57,6 +59,7 @@ pub enum EnumLayerType {
   Renderer = 2,
   Editor = 3,
   App = 4,
+  Audio = 5,
 }
 
 #[derive(Debug, Copy, Clone, Ord, PartialOrd, PartialEq, Eq, Hash)]
@@ -65,12 +68,22 @@ pub enum EnumSyncInterval {
   Every(u32)
 }
 
+/// Whether a layer gets updated once per frame with the frame delta, or a fixed number of times per second with a
+/// constant delta (see [crate::utils::FixedTimeStep]).
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, PartialEq, Eq, Hash)]
+pub enum EnumUpdateMode {
+  Variable,
+  Fixed,
+}
+
 pub struct Layer {
   pub m_uuid: u64,
   pub m_name: &'static str,
-  m_priority: u32,
+  m_priority: i32,
+  m_enabled: bool,
   m_sync_polling_enabled: bool,
   m_sync_interval: EnumSyncInterval,
+  m_update_mode: EnumUpdateMode,
   m_poll_mask: EnumEventMask,
   pub(crate) m_data: Box<dyn TraitLayer>,
 }
@@ -79,13 +92,15 @@ impl Eq for Layer {}
 
 impl PartialEq<Self> for Layer {
   fn eq(&self, other: &Self) -> bool {
-    return self.m_data.get_type() == other.m_data.get_type();
+    return self.m_priority == other.m_priority && self.m_data.get_type() == other.m_data.get_type();
   }
 }
 
 impl PartialOrd<Self> for Layer {
   fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-    return Option::from(self.m_priority.cmp(&other.m_priority));
+    // Explicit priority first, then the layer type for layers sharing the same priority.
+    return Option::from(self.m_priority.cmp(&other.m_priority)
+      .then((self.m_data.get_type() as u32).cmp(&(other.m_data.get_type() as u32))));
   }
 }
 
@@ -99,6 +114,8 @@ pub trait TraitLayer {
   fn get_type(&self) -> EnumLayerType;
   fn on_apply(&mut self) -> Result<(), EnumEngineError>;
   fn on_sync_event(&mut self) -> Result<(), EnumEngineError>;
+  /// Handle an event this layer polls for. Returning `Ok(true)` marks the event as consumed and stops it from reaching
+  /// lower priority layers, except for window close and resize events which every polling layer receives.
   fn on_async_event(&mut self, event: &EnumEvent) -> Result<bool, EnumEngineError>;
   fn on_update(&mut self, time_step: f64) -> Result<(), EnumEngineError>;
   fn on_render(&mut self) -> Result<(), EnumEngineError>;
@@ -111,9 +128,11 @@ impl Layer {
     return Self {
       m_uuid: 0,
       m_name: name,
-      m_priority: data.get_type() as u32,
+      m_priority: 0,
+      m_enabled: true,
       m_sync_polling_enabled: false,
       m_sync_interval: EnumSyncInterval::EveryFrame,
+      m_update_mode: EnumUpdateMode::Variable,
       m_poll_mask: EnumEventMask::None,
       m_data: Box::new(data),
     };
@@ -142,6 +161,26 @@ impl Layer {
     return self.m_name == name;
   }
   
+  /// Layers with a higher priority sit above the others: they receive events first and get updated and rendered
+  /// first. Layers sharing a priority are ordered by [EnumLayerType]. Takes effect once the layer gets pushed.
+  pub fn set_priority(&mut self, priority: i32) {
+    self.m_priority = priority;
+  }
+  
+  pub fn get_priority(&self) -> i32 {
+    return self.m_priority;
+  }
+  
+  /// Disabled layers stay in the engine but get skipped when dispatching events, updating and rendering. To disable a
+  /// layer already pushed, go through [crate::Engine::set_layer_enabled] so window polling gets updated as well.
+  pub fn set_enabled(&mut self, enabled: bool) {
+    self.m_enabled = enabled;
+  }
+  
+  pub fn is_enabled(&self) -> bool {
+    return self.m_enabled;
+  }
+  
   pub fn enable_async_polling_for(&mut self, event_mask: EnumEventMask) {
     self.m_poll_mask = event_mask;
   }
@@ -182,13 +221,23 @@ impl Layer {
     };
   }
   
+  /// Opt into fixed updates, where [TraitLayer::on_update] receives the engine's fixed step instead of the frame
+  /// delta and may be called zero or several times per frame.
+  pub fn set_update_mode(&mut self, update_mode: EnumUpdateMode) {
+    self.m_update_mode = update_mode;
+  }
+  
+  pub fn get_update_mode(&self) -> EnumUpdateMode {
+    return self.m_update_mode;
+  }
+  
   pub(crate) fn poll_includes(&self, poll_mask: EnumEventMask) -> bool {
-    return self.m_poll_mask.contains(poll_mask);
+    return self.m_enabled && self.m_poll_mask.contains(poll_mask);
   }
   
   pub(crate) fn polls(&self, event: &EnumEvent) -> bool {
     let cast = events::EnumEventMask::from(event);
-    return self.m_poll_mask.contains(cast);
+    return self.m_enabled && self.m_poll_mask.contains(cast);
   }
   
   pub fn try_cast<T: TraitLayer + 'static>(&self) -> Option<&T> {
@@ -220,6 +269,27 @@ impl Layer {
   }
 }
 
+/// Dispatch an event to the layers polling for it, from the highest priority layer down, until one consumes it (see
+/// [TraitLayer::on_async_event]). Layers must be sorted in ascending order, as the engine keeps them.
+///
+/// ### Returns:
+/// - *Result<bool, [EnumEngineError]>*: Whether a layer consumed the event, or the first error returned by a layer.
+pub fn dispatch_event(layers: &mut [Layer], event: &EnumEvent) -> Result<bool, EnumEngineError> {
+  for layer in layers.iter_mut().rev().filter(|layer| layer.polls(event)) {
+    let handled: bool = layer.on_async_event(event)?;
+    
+    // Mandatory event handling, ignoring if the event has been processed or not.
+    if matches!(event, EnumEvent::WindowCloseEvent(_) | EnumEvent::FramebufferEvent(_, _) |
+      EnumEvent::ContentScaleEvent(_, _)) {
+      continue;
+    }
+    if handled {
+      return Ok(true);
+    }
+  }
+  return Ok(false);
+}
+
 impl Display for Layer {
   fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
     let log_color: &str = color_to_str(EnumLogColor::Purple);
@@ -76,16 +76,21 @@ impl TraitLayer for RendererLayer {
         }
         _ => {}
       }
+    
+    // Keep the active camera, or viewport cameras, in sync with window changes (i.e. aspect ratio) and input.
+    unsafe { (*self.m_context).on_camera_event(event)? };
     return unsafe { (*self.m_context).on_event(event).map_err(|err| EnumEngineError::from(err)) };
   }
   
-  fn on_update(&mut self, _time_step: f64) -> Result<(), EnumEngineError> {
+  fn on_update(&mut self, time_step: f64) -> Result<(), EnumEngineError> {
+    unsafe { (*self.m_context).on_update(time_step) };
     return Ok(());
   }
   
   fn on_render(&mut self) -> Result<(), EnumEngineError> {
     return unsafe {
-      (*self.m_context).on_render().map_err(|err| EnumEngineError::from(err))
+      (*self.m_context).on_render().map_err(|err| EnumEngineError::from(err))?;
+      (*self.m_context).apply_post_process().map_err(|err| EnumEngineError::from(err))
     }
   }
   
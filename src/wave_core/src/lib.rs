@@ -22,16 +22,21 @@
  SOFTWARE.
 */
 
+use camera::Camera;
+use std::collections::VecDeque;
+
 use events::{EnumEvent};
-use graphics::renderer::{self, Renderer};
+use audio::AudioEngine;
+use graphics::renderer::{self, EnumRendererApi, Renderer};
 use graphics::shader::{self};
-use input::{EnumAction, EnumKey, EnumMouseButton, Input};
-use layers::{EnumLayerType, Layer, TraitLayer};
+use input::{EnumAction, EnumGamepadButton, EnumKey, EnumMouseButton, Input};
+use layers::{EnumLayerError, EnumLayerType, EnumUpdateMode, Layer, TraitLayer};
+use layers::audio_layer::AudioLayer;
 use layers::renderer_layer::RendererLayer;
 use layers::window_layer::WindowLayer;
 #[cfg(feature = "debug")]
 use utils::macros::logger::{color_to_str, EnumLogColor};
-use utils::Time;
+use utils::{FixedTimeStep, Time};
 use window::Window;
 use crate::events::EnumEventMask;
 
@@ -46,9 +51,9 @@ pub mod camera;
 pub mod input;
 pub mod events;
 pub mod layers;
+pub mod audio;
 
 static mut S_ENGINE: Option<*mut Engine> = None;
-pub(crate) static mut S_LOG_FILE_PTR: Option<std::fs::File> = None;
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 enum EnumEngineState {
@@ -76,6 +81,10 @@ pub enum EnumEngineError {
   InputError(input::EnumInputError),
   UiError(ui::EnumUIError),
   EventError(events::EnumEventError),
+  SceneError(assets::scene::EnumSceneError),
+  ConfigError(utils::config::EnumConfigError),
+  AudioError(audio::EnumAudioError),
+  TextError(graphics::text::EnumTextError),
 }
 
 macro_rules! impl_enum_error {
@@ -89,6 +98,41 @@ macro_rules! impl_enum_error {
   }
 }
 
+impl EnumEngineError {
+  /// Errors the engine cannot recover from, regardless of the error policy in place.
+  pub fn is_fatal(&self) -> bool {
+    return match self {
+      EnumEngineError::NoActiveEngine => true,
+      EnumEngineError::RendererError(renderer::EnumRendererError::Init) |
+      EnumEngineError::RendererError(renderer::EnumRendererError::NoApi) |
+      EnumEngineError::RendererError(renderer::EnumRendererError::NoActiveRenderer) |
+      EnumEngineError::RendererError(renderer::EnumRendererError::InvalidApi) |
+      EnumEngineError::RendererError(renderer::EnumRendererError::UnsupportedApi) |
+      EnumEngineError::RendererError(renderer::EnumRendererError::ContextError) => true,
+      EnumEngineError::WindowError(window::EnumWindowError::NoContext) |
+      EnumEngineError::WindowError(window::EnumWindowError::InitError) |
+      EnumEngineError::WindowError(window::EnumWindowError::ApiError) => true,
+      _ => false
+    };
+  }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum EnumErrorPolicy {
+  // Stop the engine loop on the first error.
+  Abort,
+  // Log non-fatal errors and keep going.
+  LogAndContinue,
+  // Let the user decide, returning true to keep going.
+  Callback(fn(&EnumEngineError) -> bool),
+}
+
+impl Default for EnumErrorPolicy {
+  fn default() -> Self {
+    return EnumErrorPolicy::Abort;
+  }
+}
+
 impl From<std::io::Error> for EnumEngineError {
   fn from(value: std::io::Error) -> Self {
     log!(EnumLogColor::Red, "ERROR", "[Editor] -->\t Error occurred when performing I/O operations, Error => {:?}", value);
@@ -123,6 +167,18 @@ impl_enum_error!(graphics::texture::EnumTextureLoaderError, EnumEngineError::Tex
 // Convert event errors to wave_core::EnumError
 impl_enum_error!(events::EnumEventError, EnumEngineError::EventError);
 
+// Convert scene errors to wave_core::EnumError
+impl_enum_error!(assets::scene::EnumSceneError, EnumEngineError::SceneError);
+
+// Convert config errors to wave_core::EnumError
+impl_enum_error!(utils::config::EnumConfigError, EnumEngineError::ConfigError);
+
+// Convert audio errors to wave_core::EnumError
+impl_enum_error!(audio::EnumAudioError, EnumEngineError::AudioError);
+
+// Convert text errors to wave_core::EnumError
+impl_enum_error!(graphics::text::EnumTextError, EnumEngineError::TextError);
+
 pub trait TraitHint<T: 'static + PartialEq> {
   fn set_hint(&mut self, hint: T);
   fn reset_hints(&mut self);
@@ -140,34 +196,55 @@ pub struct Engine {
   m_layers: Vec<Layer>,
   m_window: Window,
   m_renderer: Renderer,
+  m_audio: AudioEngine,
   m_time_step: f64,
+  m_uptime: f64,
+  m_frame_count: u64,
   m_tick_rate: f32,
+  m_fixed_time_step: FixedTimeStep,
+  m_paused: bool,
+  m_event_queue: VecDeque<EnumEvent>,
   m_state: EnumEngineState,
+  m_error_policy: EnumErrorPolicy,
 }
 
 impl<'a> Engine {
   #[allow(unused)]
   pub fn default() -> Self {
-    unsafe { S_LOG_FILE_PTR = Some(utils::macros::logger::init().unwrap()) };
+    utils::macros::logger::set_log_file(utils::macros::logger::init().unwrap());
     return Engine {
       m_layers: vec![],
       m_window: Window::default(),
       m_renderer: Renderer::default(),
+      m_audio: AudioEngine::default(),
       m_time_step: 0.0,
+      m_uptime: 0.0,
+      m_frame_count: 0,
       m_tick_rate: 0.0,
+      m_fixed_time_step: FixedTimeStep::default(),
+      m_paused: false,
+      m_event_queue: VecDeque::new(),
       m_state: EnumEngineState::NotStarted,
+      m_error_policy: EnumErrorPolicy::default(),
     };
   }
   
   pub fn new(window: Window, renderer: Renderer, app_layers: Vec<Layer>) -> Self {
-    unsafe { S_LOG_FILE_PTR = Some(utils::macros::logger::init().unwrap()) };
+    utils::macros::logger::set_log_file(utils::macros::logger::init().unwrap());
     return Engine {
       m_layers: app_layers,
       m_window: window,
       m_renderer: renderer,
+      m_audio: AudioEngine::default(),
       m_time_step: 0.0,
+      m_uptime: 0.0,
+      m_frame_count: 0,
       m_tick_rate: 0.0,
+      m_fixed_time_step: FixedTimeStep::default(),
+      m_paused: false,
+      m_event_queue: VecDeque::new(),
       m_state: EnumEngineState::NotStarted,
+      m_error_policy: EnumErrorPolicy::default(),
     };
   }
   
@@ -181,17 +258,19 @@ impl<'a> Engine {
     self.m_state = EnumEngineState::Starting;
     let mut window_layer = Layer::new("Window Layer", WindowLayer::new(&mut self.m_window));
     let mut renderer_layer = Layer::new("Renderer Layer", RendererLayer::new(&mut self.m_renderer));
+    let audio_layer = Layer::new("Audio Layer", AudioLayer::new(&mut self.m_audio));
     
     window_layer.enable_async_polling_for(EnumEventMask::WindowClose | EnumEventMask::WindowSize
       | EnumEventMask::Keyboard);
     renderer_layer.enable_async_polling_for(EnumEventMask::WindowClose | EnumEventMask::WindowSize
-      | EnumEventMask::Keyboard);
+      | EnumEventMask::Keyboard | EnumEventMask::MouseBtn | EnumEventMask::MouseScroll);
     
     // Setup window context for polling to ba available when pushing subsequent layers.
     self.m_window.apply()?;
     
     self.m_layers.push(window_layer);
     self.m_layers.push(renderer_layer);
+    self.m_layers.push(audio_layer);
     self.m_layers.sort_unstable();
     
     Engine::set_singleton(self);
@@ -216,8 +295,11 @@ impl<'a> Engine {
     
     self.m_state = EnumEngineState::Running;
     
-    // For time step.
+    // For time step and uptime.
     let mut frame_start: Time = Time::from(chrono::Utc::now());
+    let run_start: Time = frame_start;
+    self.m_uptime = 0.0;
+    self.m_frame_count = 0;
     
     // For uptime and fps.
     let mut frame_counter: u32 = 0;
@@ -232,14 +314,23 @@ impl<'a> Engine {
     while !self.m_window.is_closed() {
       self.m_time_step = Time::get_delta(frame_start, Time::from(chrono::Utc::now())).to_secs();
       frame_start = Time::from(chrono::Utc::now());
+      self.m_uptime = Time::get_delta(run_start, frame_start).to_secs();
       
       self.m_window.poll_events();
+      Input::on_update();
+      
+      // Dispatch events queued by window callbacks and the app, before updating. Events pushed while dispatching wait
+      // for the next frame.
+      for _ in 0..self.m_event_queue.len() {
+        let event: EnumEvent = self.m_event_queue.pop_front().unwrap();
+        self.dispatch_event(&event);
+      }
       
       // Sync event polling.
       let mut result: Result<(), EnumEngineError> = Ok(());
       self.m_layers.iter_mut().rev()
         .filter(|layer| {
-          if !layer.is_sync_enabled() {
+          if !layer.is_enabled() || !layer.is_sync_enabled() {
             return false;
           }
           layer.get_sync_interval() == 0 || frame_counter % layer.get_sync_interval() == 0
@@ -249,36 +340,60 @@ impl<'a> Engine {
           return result.is_ok();
         });
       
-      // Exit function if an error occurred.
-      result?;
+      // Exit function if an error occurred, unless the error policy allows us to recover from it.
+      if let Err(err) = result {
+        Self::recover_from(&self.m_error_policy, err)?;
+      }
       
-      // Update layers.
-      for layer in self.m_layers.iter_mut().rev() {
-        layer.on_update(self.m_time_step)?;
+      // Update layers, unless paused. Events and rendering keep going regardless.
+      if !self.m_paused {
+        for layer in self.m_layers.iter_mut().rev()
+          .filter(|layer| layer.is_enabled() && layer.get_update_mode() == EnumUpdateMode::Variable) {
+          if let Err(err) = layer.on_update(self.m_time_step) {
+            Self::recover_from(&self.m_error_policy, err)?;
+          }
+        }
+        
+        let fixed_step: f64 = self.m_fixed_time_step.get_step();
+        for _ in 0..self.m_fixed_time_step.accumulate(self.m_time_step) {
+          for layer in self.m_layers.iter_mut().rev()
+            .filter(|layer| layer.is_enabled() && layer.get_update_mode() == EnumUpdateMode::Fixed) {
+            if let Err(err) = layer.on_update(fixed_step) {
+              Self::recover_from(&self.m_error_policy, err)?;
+            }
+          }
+        }
       }
       
       // Render layers.
-      for layer in self.m_layers.iter_mut().rev() {
-        layer.on_render()?;
+      for layer in self.m_layers.iter_mut().rev().filter(|layer| layer.is_enabled()) {
+        if let Err(err) = layer.on_render() {
+          Self::recover_from(&self.m_error_policy, err)?;
+        }
       }
       
       // Sync to engine tick rate.
-      let time_elapsed = Time::now().to_secs() - self.m_time_step;
+      let time_elapsed = Time::get_delta(frame_start, Time::now()).to_secs();
       if time_elapsed < self.m_tick_rate as f64 {
-        Time::wait_for(time_elapsed - self.m_tick_rate as f64);
+        Time::wait_for(self.m_tick_rate as f64 - time_elapsed);
       }
       frame_counter += 1;
+      self.m_frame_count += 1;
       
       // If a second passed, display fps counter and reset it.
       if Time::get_delta(runtime, Time::from(chrono::Utc::now())).to_secs() >= 1.0 {
         if same_frame_counter != frame_counter {
           // Only display differing framerate to avoid output clutter for logging and displaying the
           // same fps several times.
-          self.m_window.set_title(&format!("{0} | {1} FPS", title_cache, &frame_counter));
+          self.m_window.set_title(&format!("{0} | {1} FPS | {2} culled", title_cache, &frame_counter,
+            self.m_renderer.get_culled_count()));
           #[cfg(feature = "debug")]
           log!(EnumLogColor::White, "INFO", "Framerate : {0}", &frame_counter);
         }
         
+        // Long sessions can grow the log past its limit, reopen it to rotate it.
+        utils::macros::logger::rotate_log_file();
+        
         same_frame_counter = frame_counter;
         frame_counter = 0;
         runtime = Time::from(chrono::Utc::now());
@@ -303,6 +418,51 @@ impl<'a> Engine {
     return &mut self.m_renderer;
   }
   
+  pub fn get_audio_ref(&self) -> &AudioEngine {
+    return &self.m_audio;
+  }
+  
+  /// Play sounds through the engine, see [AudioEngine::play_sound]. Audio is silently disabled on machines without
+  /// any output device.
+  pub fn get_audio_mut(&mut self) -> &mut AudioEngine {
+    return &mut self.m_audio;
+  }
+  
+  /// Switch the renderer to another api while running, recreating the window for it. The current renderer is left
+  /// untouched if `api` is unsupported on this machine (see [Renderer::probe_api]).
+  ///
+  /// Kept across the switch : window hints, size and position, renderer hints, the camera and viewports, as well as
+  /// layers and the CPU-side data they hold. Everything living on the GPU is gone however, and must be rebuilt by the
+  /// app once it receives [EnumEvent::RendererSwitchEvent] (polled with [EnumEventMask::Window]) :
+  ///   * Shaders are tied to the api they were created with, new ones must be created and applied.
+  ///   * Entities must be applied again with those new shaders, from the vertex data they kept.
+  ///   * Textures, render targets, the post-processing chain and ImGui layers must be recreated.
+  pub fn switch_renderer(&mut self, api: EnumRendererApi) -> Result<(), EnumEngineError> {
+    let current_api = self.m_renderer.get_type();
+    if current_api == api {
+      return Ok(());
+    }
+    if Renderer::probe_api(api) != api {
+      log!(EnumLogColor::Red, "ERROR", "[Engine] -->\t Cannot switch renderer to {0:?}, api unsupported on this \
+      machine! Keeping {1:?}...", api, current_api);
+      return Err(EnumEngineError::RendererError(renderer::EnumRendererError::UnsupportedApi));
+    }
+    
+    log!(EnumLogColor::Purple, "INFO", "[Engine] -->\t Switching renderer from {0:?} to {1:?}...", current_api, api);
+    self.m_renderer.reset_api(api)?;
+    self.m_window.recreate(api)?;
+    
+    // Callbacks were registered on the previous window.
+    for layer in self.m_layers.iter_mut() {
+      Self::enable_async_polling_for(layer);
+    }
+    self.m_renderer.apply()?;
+    
+    Engine::on_async_event(EnumEvent::RendererSwitchEvent(api));
+    log!(EnumLogColor::Green, "INFO", "[Engine] -->\t Switched renderer to {0:?} successfully", api);
+    return Ok(());
+  }
+  
   pub fn free(&mut self) -> Result<(), EnumEngineError> {
     self.m_state = EnumEngineState::Deleting;
     
@@ -359,10 +519,112 @@ impl<'a> Engine {
     return Ok(layer_popped);
   }
   
+  /// Temporarily disable a layer without popping it, or re-enable it. Disabled layers are skipped when dispatching
+  /// events, updating and rendering, and the window stops polling events no enabled layer needs anymore.
+  pub fn set_layer_enabled(&mut self, name: &str, enabled: bool) -> Result<(), EnumEngineError> {
+    let Some(layer) = self.m_layers.iter_mut().find(|layer| layer.is_named(name)) else {
+      log!(EnumLogColor::Red, "ERROR", "[Engine] -->\t Cannot toggle layer '{0}', layer not found!", name);
+      return Err(EnumEngineError::LayerError(EnumLayerError::LayerNotFound));
+    };
+    
+    if layer.is_enabled() == enabled {
+      return Ok(());
+    }
+    layer.set_enabled(enabled);
+    let poll_mask: EnumEventMask = layer.get_poll_mask();
+    log!("INFO", "[Engine] -->\t Layer '{0}' {1}", name, if enabled { "enabled" } else { "disabled" });
+    
+    if !self.m_window.is_applied() {
+      return Ok(());
+    }
+    
+    if enabled {
+      self.m_window.enable_polling_for(poll_mask);
+      self.m_window.enable_callback_for(poll_mask);
+      return Ok(());
+    }
+    
+    // Only stop polling the events no other enabled layer is interested in.
+    for event_mask in EnumEventMask::C_EVENTS.iter()
+      .filter(|event_mask| poll_mask.contains(**event_mask)) {
+      if self.m_layers.iter().all(|layer| !layer.poll_includes(*event_mask)) {
+        self.m_window.disable_polling(*event_mask);
+      }
+    }
+    return Ok(());
+  }
+  
+  pub fn set_error_policy(&mut self, error_policy: EnumErrorPolicy) {
+    self.m_error_policy = error_policy;
+  }
+  
+  pub fn get_error_policy(&self) -> EnumErrorPolicy {
+    return self.m_error_policy;
+  }
+  
   pub fn get_time_step(&self) -> f64 {
     return self.m_time_step;
   }
   
+  /// Seconds elapsed since [Self::run] started, as of the start of the current frame.
+  pub fn get_uptime(&self) -> f64 {
+    return self.m_uptime;
+  }
+  
+  /// Number of frames fully processed since [Self::run] started, i.e. the index of the current frame.
+  pub fn get_frame_count(&self) -> u64 {
+    return self.m_frame_count;
+  }
+  
+  /// Cap the framerate to save power, or uncap it with `None`. Note that vsync, if enabled, caps it as well.
+  pub fn set_target_fps(&mut self, fps: Option<u32>) {
+    self.m_tick_rate = match fps {
+      Some(fps) if fps > 0 => 1.0 / fps as f32,
+      _ => 0.0,
+    };
+  }
+  
+  pub fn get_target_fps(&self) -> Option<u32> {
+    if self.m_tick_rate <= 0.0 {
+      return None;
+    }
+    return Some((1.0 / self.m_tick_rate).round() as u32);
+  }
+  
+  /// Pause or resume updating layers. Paused engines still poll events and render, so the app stays responsive.
+  pub fn set_paused(&mut self, paused: bool) {
+    if paused == self.m_paused {
+      return;
+    }
+    // Don't let the time spent paused spill into fixed updates once resumed.
+    self.m_fixed_time_step.reset();
+    self.m_paused = paused;
+    log!("INFO", "[Engine] -->\t Engine {0}", if paused { "paused" } else { "resumed" });
+  }
+  
+  pub fn is_paused(&self) -> bool {
+    return self.m_paused;
+  }
+  
+  /// Set how many times per second layers in [EnumUpdateMode::Fixed] get updated, and the maximum number of fixed
+  /// updates allowed in a single frame to catch up after a hitch (see [FixedTimeStep]).
+  pub fn set_fixed_update_rate(&mut self, steps_per_second: u32, max_steps_per_frame: u32) {
+    self.m_fixed_time_step.set_steps_per_second(steps_per_second);
+    self.m_fixed_time_step.set_max_steps(max_steps_per_frame);
+  }
+  
+  pub fn get_fixed_time_step(&self) -> f64 {
+    return self.m_fixed_time_step.get_step();
+  }
+  
+  /// Queue an event (typically an [EnumEvent::UserEvent]) to be dispatched to layers polling for it at the start of
+  /// the next frame, just like window events.
+  pub fn push_event(event: EnumEvent) -> Result<(), EnumEngineError> {
+    let engine = unsafe { &mut *S_ENGINE.ok_or(EnumEngineError::NoActiveEngine)? };
+    engine.m_event_queue.push_back(event);
+    return Ok(());
+  }
+  
   pub fn is_key(key: EnumKey, state: EnumAction) -> bool {
     let engine = unsafe { &mut *S_ENGINE.expect("Cannot retrieve active engine!") };
     return Input::get_key_state(&engine.m_window, key, state);
@@ -373,8 +635,31 @@ impl<'a> Engine {
     return Input::get_mouse_button_state(&engine.m_window, button, state);
   }
   
-  pub fn get_log_file() -> &'a std::fs::File {
-    return unsafe { S_LOG_FILE_PTR.as_ref().unwrap() };
+  pub fn is_gamepad_btn_from(gamepad_id: u32, button: EnumGamepadButton, state: EnumAction) -> bool {
+    let engine = unsafe { &mut *S_ENGINE.expect("Cannot retrieve active engine!") };
+    return Input::get_gamepad_button_state(&engine.m_window, gamepad_id, button, state);
+  }
+  
+  pub fn get_active_camera() -> Option<&'a Camera> {
+    let engine = unsafe { &*S_ENGINE? };
+    return engine.m_renderer.get_active_camera();
+  }
+  
+  /// See [Self::get_uptime], for the active engine.
+  pub fn get_active_uptime() -> Option<f64> {
+    let engine = unsafe { &*S_ENGINE? };
+    return Some(engine.m_uptime);
+  }
+  
+  /// See [Self::get_frame_count], for the active engine.
+  pub fn get_active_frame_count() -> Option<u64> {
+    let engine = unsafe { &*S_ENGINE? };
+    return Some(engine.m_frame_count);
+  }
+  
+  /// Log file shared by every thread, only to be written to while locked.
+  pub fn get_log_file() -> &'static std::sync::Mutex<Option<std::fs::File>> {
+    return utils::macros::logger::get_log_file();
   }
   
   ////////////////////////////// PRIVATE FUNCTIONS ////////////////////////////////
@@ -412,29 +697,18 @@ impl<'a> Engine {
     }
   }
   
-  pub(crate) fn on_async_event(event: &EnumEvent) {
-    let engine = unsafe { &mut *S_ENGINE.expect("Cannot push layer, engine not active!") };
-    
-    // Async event polling.
-    let mut each_result: Result<bool, EnumEngineError> = Ok(false);
-    let _result = engine.m_layers.iter_mut().rev()
-      .filter(|layer| layer.polls(&event))
-      .all(|matching_layer| {
-        // Mandatory event handling, ignoring if the event has been processed or not.
-        match event {
-          EnumEvent::WindowCloseEvent(_) | EnumEvent::FramebufferEvent(_, _) => {
-            each_result = matching_layer.on_async_event(event);
-            return each_result.is_ok();
-          }
-          _ => {}
-        }
-        each_result = matching_layer.on_async_event(&event);
-        // Short circuit if an error occurred or if the event has been processed.
-        return each_result.is_ok() && !each_result.as_ref().unwrap();
-      });
-    
-    if each_result.is_err() {
-      log!(EnumLogColor::Red, "ERROR", "[Engine] -->\t Error while processing async event: {0:?}", each_result.err().unwrap());
+  /// Queue events coming from window callbacks, to be dispatched once per frame in [Self::run]. Callbacks fire while
+  /// polling, so handling them right away would alias the engine and its layers.
+  pub(crate) fn on_async_event(event: EnumEvent) {
+    let Some(engine) = (unsafe { S_ENGINE }) else {
+      return;
+    };
+    unsafe { (*engine).m_event_queue.push_back(event) };
+  }
+  
+  fn dispatch_event(&mut self, event: &EnumEvent) {
+    if let Err(_err) = layers::dispatch_event(&mut self.m_layers, event) {
+      log!(EnumLogColor::Red, "ERROR", "[Engine] -->\t Error while processing async event: {0:?}", _err);
     }
   }
   
@@ -448,6 +722,27 @@ impl<'a> Engine {
     return &mut engine.m_window;
   }
   
+  fn recover_from(error_policy: &EnumErrorPolicy, error: EnumEngineError) -> Result<(), EnumEngineError> {
+    if error.is_fatal() {
+      log!(EnumLogColor::Red, "ERROR", "[Engine] -->\t Fatal error encountered : {0:?}, aborting...", error);
+      return Err(error);
+    }
+    
+    return match error_policy {
+      EnumErrorPolicy::Abort => Err(error),
+      EnumErrorPolicy::LogAndContinue => {
+        log!(EnumLogColor::Yellow, "WARN", "[Engine] -->\t Error encountered : {0:?}, ignoring it...", error);
+        Ok(())
+      }
+      EnumErrorPolicy::Callback(callback) => {
+        if callback(&error) {
+          return Ok(());
+        }
+        Err(error)
+      }
+    };
+  }
+  
   fn set_singleton(engine: &mut Engine) -> () {
     unsafe { S_ENGINE = Some(engine) };
   }
@@ -80,13 +80,58 @@ impl Vec3<f32> {
   }
   
   pub fn dot(&self, other: Self) -> f32 {
-    return (self.x * other.x) + (self.y * other.y) + (self.x * other.z);
+    return (self.x * other.x) + (self.y * other.y) + (self.z * other.z);
   }
   
   pub fn vec_len(&self) -> f32 {
     return (self.x.powi(2) + self.y.powi(2) + self.z.powi(2))
       .sqrt();  // Return NaN or the distance.
   }
+  
+  pub fn normalize(&self) -> Self {
+    let length: f32 = self.vec_len();
+    
+    // Cannot normalize a null vector, leave it as is.
+    if length <= f32::EPSILON {
+      return *self;
+    }
+    return Vec3 {
+      x: self.x / length,
+      y: self.y / length,
+      z: self.z / length,
+    };
+  }
+  
+  pub fn lerp(&self, other: &Vec3<f32>, factor: f32) -> Self {
+    return self.lerp_unclamped(other, factor.clamp(0.0, 1.0));
+  }
+  
+  pub fn lerp_unclamped(&self, other: &Vec3<f32>, factor: f32) -> Self {
+    return Vec3 {
+      x: self.x + (other.x - self.x) * factor,
+      y: self.y + (other.y - self.y) * factor,
+      z: self.z + (other.z - self.z) * factor,
+    };
+  }
+  
+  /// Round each component to the nearest multiple of `grid`, see [snap].
+  pub fn snap(&self, grid: f32) -> Self {
+    return Vec3 {
+      x: snap(self.x, grid),
+      y: snap(self.y, grid),
+      z: snap(self.z, grid),
+    };
+  }
+  
+  // Normal is expected to be of unit length.
+  pub fn reflect(&self, normal: &Vec3<f32>) -> Self {
+    let projection: f32 = 2.0 * self.dot(*normal);
+    return Vec3 {
+      x: self.x - projection * normal.x,
+      y: self.y - projection * normal.y,
+      z: self.z - projection * normal.z,
+    };
+  }
 }
 
 ///////////////////// INDEXING ////////////////////////
@@ -344,6 +389,123 @@ impl Mat4 {
     
     return result;
   }
+  
+  pub fn apply_orthographic(left: f32, right: f32, bottom: f32, top: f32, z_near: f32, z_far: f32) -> Self {
+    let mut result = Mat4::default();
+    
+    result[0][0] = 2.0 / (right - left);
+    result[1][1] = 2.0 / (top - bottom);
+    result[2][2] = -2.0 / (z_far - z_near);
+    result[0][3] = -(right + left) / (right - left);
+    result[1][3] = -(top + bottom) / (top - bottom);
+    result[2][3] = -(z_far + z_near) / (z_far - z_near);
+    
+    return result;
+  }
+  
+  pub fn apply_look_at(eye: &Vec3<f32>, target: &Vec3<f32>, up: &Vec3<f32>) -> Self {
+    let forward: Vec3<f32> = (*target - *eye).normalize();
+    let mut right: Vec3<f32> = forward.cross(*up);
+    
+    // Forward is parallel to up, fallback to another up axis to still get a valid basis.
+    if right.vec_len() <= f32::EPSILON {
+      let alternate_up: Vec3<f32> = (forward.z.abs() < 0.999)
+        .then(|| Vec3::new(&[0.0, 0.0, 1.0]))
+        .unwrap_or(Vec3::new(&[1.0, 0.0, 0.0]));
+      right = forward.cross(alternate_up);
+    }
+    right = right.normalize();
+    let true_up: Vec3<f32> = right.cross(forward);
+    
+    return Mat4::from(
+      [
+        [right.x, right.y, right.z, -right.dot(*eye)],
+        [true_up.x, true_up.y, true_up.z, -true_up.dot(*eye)],
+        [-forward.x, -forward.y, -forward.z, forward.dot(*eye)],
+        [0.0, 0.0, 0.0, 1.0]]
+    );
+  }
+  
+  #[deprecated(note = "Renamed to `apply_look_at`, to match the other view and projection constructors")]
+  pub fn look_at(eye: &Vec3<f32>, target: &Vec3<f32>, up: &Vec3<f32>) -> Self {
+    return Mat4::apply_look_at(eye, target, up);
+  }
+  
+  pub fn determinant(&self) -> f32 {
+    let (sub_factors, cofactors) = self.get_sub_determinants();
+    
+    return sub_factors[0] * cofactors[5] - sub_factors[1] * cofactors[4] + sub_factors[2] * cofactors[3]
+      + sub_factors[3] * cofactors[2] - sub_factors[4] * cofactors[1] + sub_factors[5] * cofactors[0];
+  }
+  
+  pub fn inverse(&self) -> Option<Mat4> {
+    let (s, c) = self.get_sub_determinants();
+    let determinant: f32 = s[0] * c[5] - s[1] * c[4] + s[2] * c[3] + s[3] * c[2] - s[4] * c[1] + s[5] * c[0];
+    
+    // Singular (or close enough to it) matrix, no inverse possible. Relative to the magnitude of the rows (the largest
+    // determinant they could have), since i.e. orthographic projections over wide areas are tiny but still invertible.
+    let row_magnitudes: f32 = (0..4usize)
+      .map(|row| (0..4usize).map(|col| self[row][col] * self[row][col]).sum::<f32>().sqrt())
+      .product();
+    if determinant.abs() <= f32::EPSILON * row_magnitudes {
+      return None;
+    }
+    
+    let inverse_determinant: f32 = 1.0 / determinant;
+    let m = self;
+    let mut result: Mat4 = Mat4::new(0.0);
+    
+    // Adjugate matrix (transposed cofactors), expanded from the 2x2 sub determinants above.
+    result[0][0] = m[1][1] * c[5] - m[1][2] * c[4] + m[1][3] * c[3];
+    result[0][1] = -m[0][1] * c[5] + m[0][2] * c[4] - m[0][3] * c[3];
+    result[0][2] = m[3][1] * s[5] - m[3][2] * s[4] + m[3][3] * s[3];
+    result[0][3] = -m[2][1] * s[5] + m[2][2] * s[4] - m[2][3] * s[3];
+    
+    result[1][0] = -m[1][0] * c[5] + m[1][2] * c[2] - m[1][3] * c[1];
+    result[1][1] = m[0][0] * c[5] - m[0][2] * c[2] + m[0][3] * c[1];
+    result[1][2] = -m[3][0] * s[5] + m[3][2] * s[2] - m[3][3] * s[1];
+    result[1][3] = m[2][0] * s[5] - m[2][2] * s[2] + m[2][3] * s[1];
+    
+    result[2][0] = m[1][0] * c[4] - m[1][1] * c[2] + m[1][3] * c[0];
+    result[2][1] = -m[0][0] * c[4] + m[0][1] * c[2] - m[0][3] * c[0];
+    result[2][2] = m[3][0] * s[4] - m[3][1] * s[2] + m[3][3] * s[0];
+    result[2][3] = -m[2][0] * s[4] + m[2][1] * s[2] - m[2][3] * s[0];
+    
+    result[3][0] = -m[1][0] * c[3] + m[1][1] * c[1] - m[1][2] * c[0];
+    result[3][1] = m[0][0] * c[3] - m[0][1] * c[1] + m[0][2] * c[0];
+    result[3][2] = -m[3][0] * s[3] + m[3][1] * s[1] - m[3][2] * s[0];
+    result[3][3] = m[2][0] * s[3] - m[2][1] * s[1] + m[2][2] * s[0];
+    
+    for row in 0..4usize {
+      for col in 0..4usize {
+        result[row][col] *= inverse_determinant;
+      }
+    }
+    return Some(result);
+  }
+  
+  /// Compute the 2x2 sub determinants of the top two rows and bottom two rows respectively, used
+  /// for both the determinant and the adjugate matrix (Laplace expansion).
+  fn get_sub_determinants(&self) -> ([f32; 6], [f32; 6]) {
+    let m = self;
+    let sub_factors: [f32; 6] = [
+      m[0][0] * m[1][1] - m[1][0] * m[0][1],
+      m[0][0] * m[1][2] - m[1][0] * m[0][2],
+      m[0][0] * m[1][3] - m[1][0] * m[0][3],
+      m[0][1] * m[1][2] - m[1][1] * m[0][2],
+      m[0][1] * m[1][3] - m[1][1] * m[0][3],
+      m[0][2] * m[1][3] - m[1][2] * m[0][3],
+    ];
+    let cofactors: [f32; 6] = [
+      m[2][0] * m[3][1] - m[3][0] * m[2][1],
+      m[2][0] * m[3][2] - m[3][0] * m[2][2],
+      m[2][0] * m[3][3] - m[3][0] * m[2][3],
+      m[2][1] * m[3][2] - m[3][1] * m[2][2],
+      m[2][1] * m[3][3] - m[3][1] * m[2][3],
+      m[2][2] * m[3][3] - m[3][2] * m[2][3],
+    ];
+    return (sub_factors, cofactors);
+  }
 }
 
 ///////////////////// DISPLAY ////////////////////////
@@ -448,3 +610,320 @@ impl std::ops::Mul for Mat4 {
     return default_matrix;
   }
 }
+
+/*
+///////////////////////////////////   QUATERNIONS  ///////////////////////////////////
+///////////////////////////////////                ///////////////////////////////////
+///////////////////////////////////                ///////////////////////////////////
+ */
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Quaternion {
+  pub w: f32,
+  pub x: f32,
+  pub y: f32,
+  pub z: f32,
+}
+
+impl Quaternion {
+  pub fn default() -> Self {
+    return Self {
+      w: 1.0,
+      x: 0.0,
+      y: 0.0,
+      z: 0.0,
+    };
+  }
+  
+  pub fn new(w: f32, x: f32, y: f32, z: f32) -> Self {
+    return Self {
+      w,
+      x,
+      y,
+      z,
+    };
+  }
+  
+  pub fn from_axis_angle(axis: &Vec3<f32>, angle: f32) -> Self {
+    let normalized_axis: Vec3<f32> = axis.normalize();
+    let half_angle: f32 = angle.to_radians() / 2.0;
+    let sin_half_angle: f32 = half_angle.sin();
+    
+    return Self {
+      w: half_angle.cos(),
+      x: normalized_axis.x * sin_half_angle,
+      y: normalized_axis.y * sin_half_angle,
+      z: normalized_axis.z * sin_half_angle,
+    };
+  }
+  
+  pub fn from_euler(rotation_vec: &Vec3<f32>) -> Self {
+    let rotation_x = Quaternion::from_axis_angle(&Vec3::new(&[1.0, 0.0, 0.0]), rotation_vec.x);
+    // Inverse y to match the orientation of Mat4::rotation_matrix().
+    let rotation_y = Quaternion::from_axis_angle(&Vec3::new(&[0.0, 1.0, 0.0]), -rotation_vec.y);
+    let rotation_z = Quaternion::from_axis_angle(&Vec3::new(&[0.0, 0.0, 1.0]), rotation_vec.z);
+    
+    // Same order as Mat4::rotation_matrix() : x, then y, then z.
+    return rotation_z * (rotation_y * rotation_x);
+  }
+  
+  pub fn len(&self) -> f32 {
+    return (self.w.powi(2) + self.x.powi(2) + self.y.powi(2) + self.z.powi(2)).sqrt();
+  }
+  
+  pub fn dot(&self, other: &Self) -> f32 {
+    return (self.w * other.w) + (self.x * other.x) + (self.y * other.y) + (self.z * other.z);
+  }
+  
+  pub fn normalize(&self) -> Self {
+    let length: f32 = self.len();
+    
+    // Cannot normalize a null quaternion, fallback to identity.
+    if length <= f32::EPSILON {
+      return Quaternion::default();
+    }
+    return Self {
+      w: self.w / length,
+      x: self.x / length,
+      y: self.y / length,
+      z: self.z / length,
+    };
+  }
+  
+  pub fn conjugate(&self) -> Self {
+    return Self {
+      w: self.w,
+      x: -self.x,
+      y: -self.y,
+      z: -self.z,
+    };
+  }
+  
+  pub fn slerp(from: &Quaternion, to: &Quaternion, factor: f32) -> Self {
+    let start: Quaternion = from.normalize();
+    let mut end: Quaternion = to.normalize();
+    let mut cos_theta: f32 = start.dot(&end);
+    
+    // Take the shortest arc.
+    if cos_theta < 0.0 {
+      end = Quaternion::new(-end.w, -end.x, -end.y, -end.z);
+      cos_theta = -cos_theta;
+    }
+    
+    // Quaternions are too close, fallback to a linear interpolation to avoid dividing by sin(~0).
+    if cos_theta > 0.9995 {
+      return Quaternion::new(start.w + (end.w - start.w) * factor, start.x + (end.x - start.x) * factor,
+        start.y + (end.y - start.y) * factor, start.z + (end.z - start.z) * factor).normalize();
+    }
+    
+    let theta: f32 = cos_theta.acos();
+    let sin_theta: f32 = theta.sin();
+    let start_weight: f32 = ((1.0 - factor) * theta).sin() / sin_theta;
+    let end_weight: f32 = (factor * theta).sin() / sin_theta;
+    
+    return Quaternion::new(start.w * start_weight + end.w * end_weight, start.x * start_weight + end.x * end_weight,
+      start.y * start_weight + end.y * end_weight, start.z * start_weight + end.z * end_weight);
+  }
+  
+  pub fn to_mat4(&self) -> Mat4 {
+    let q: Quaternion = self.normalize();
+    
+    return Mat4::from(
+      [
+        [1.0 - 2.0 * (q.y * q.y + q.z * q.z), 2.0 * (q.x * q.y - q.w * q.z), 2.0 * (q.x * q.z + q.w * q.y), 0.0],
+        [2.0 * (q.x * q.y + q.w * q.z), 1.0 - 2.0 * (q.x * q.x + q.z * q.z), 2.0 * (q.y * q.z - q.w * q.x), 0.0],
+        [2.0 * (q.x * q.z - q.w * q.y), 2.0 * (q.y * q.z + q.w * q.x), 1.0 - 2.0 * (q.x * q.x + q.y * q.y), 0.0],
+        [0.0, 0.0, 0.0, 1.0]]
+    );
+  }
+}
+
+///////////////////// DISPLAY ////////////////////////
+
+impl std::fmt::Display for Quaternion {
+  fn fmt(&self, format: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(format, "[Quaternion] --> w: {0:.3}, x: {1:.3}, y: {2:.3}, z: {3:.3}", self.w, self.x, self.y, self.z)
+  }
+}
+
+///////////////////// ARITHMETIC ////////////////////////
+
+impl std::ops::Mul for Quaternion {
+  type Output = Quaternion;
+  
+  fn mul(self, other: Self) -> Quaternion {
+    return Quaternion {
+      w: self.w * other.w - self.x * other.x - self.y * other.y - self.z * other.z,
+      x: self.w * other.x + self.x * other.w + self.y * other.z - self.z * other.y,
+      y: self.w * other.y - self.x * other.z + self.y * other.w + self.z * other.x,
+      z: self.w * other.z + self.x * other.y - self.y * other.x + self.z * other.w,
+    };
+  }
+}
+
+/*
+///////////////////////////////////   BOUNDING BOXES  ///////////////////////////////////
+///////////////////////////////////                   ///////////////////////////////////
+///////////////////////////////////                   ///////////////////////////////////
+ */
+
+/// Axis-aligned bounding box.
+#[derive(Copy, Clone)]
+pub struct Aabb {
+  pub m_min: Vec3<f32>,
+  pub m_max: Vec3<f32>,
+}
+
+impl Aabb {
+  pub fn new(min: Vec3<f32>, max: Vec3<f32>) -> Self {
+    return Self {
+      m_min: min,
+      m_max: max,
+    };
+  }
+  
+  /// Smallest box enclosing all the given points, or an empty box at the origin if there are none.
+  pub fn from_points(points: &[Vec3<f32>]) -> Self {
+    if points.is_empty() {
+      return Self::new(Vec3::default(), Vec3::default());
+    }
+    
+    let mut min = points[0];
+    let mut max = points[0];
+    for point in points.iter().skip(1) {
+      min = Vec3::new(&[min.x.min(point.x), min.y.min(point.y), min.z.min(point.z)]);
+      max = Vec3::new(&[max.x.max(point.x), max.y.max(point.y), max.z.max(point.z)]);
+    }
+    return Self::new(min, max);
+  }
+  
+  pub fn get_center(&self) -> Vec3<f32> {
+    return Vec3::new(&[(self.m_min.x + self.m_max.x) / 2.0, (self.m_min.y + self.m_max.y) / 2.0,
+      (self.m_min.z + self.m_max.z) / 2.0]);
+  }
+  
+  pub fn get_corners(&self) -> [Vec3<f32>; 8] {
+    return [
+      Vec3::new(&[self.m_min.x, self.m_min.y, self.m_min.z]), Vec3::new(&[self.m_max.x, self.m_min.y, self.m_min.z]),
+      Vec3::new(&[self.m_min.x, self.m_max.y, self.m_min.z]), Vec3::new(&[self.m_max.x, self.m_max.y, self.m_min.z]),
+      Vec3::new(&[self.m_min.x, self.m_min.y, self.m_max.z]), Vec3::new(&[self.m_max.x, self.m_min.y, self.m_max.z]),
+      Vec3::new(&[self.m_min.x, self.m_max.y, self.m_max.z]), Vec3::new(&[self.m_max.x, self.m_max.y, self.m_max.z])
+    ];
+  }
+  
+  /// Box enclosing this one once transformed by the given matrix, i.e. a model matrix to go from local to world space.
+  pub fn transform(&self, matrix: &Mat4) -> Self {
+    let corners = self.get_corners().map(|corner| Vec3::new(&[
+      matrix[0][0] * corner.x + matrix[0][1] * corner.y + matrix[0][2] * corner.z + matrix[0][3],
+      matrix[1][0] * corner.x + matrix[1][1] * corner.y + matrix[1][2] * corner.z + matrix[1][3],
+      matrix[2][0] * corner.x + matrix[2][1] * corner.y + matrix[2][2] * corner.z + matrix[2][3]
+    ]));
+    return Self::from_points(&corners);
+  }
+  
+  /// Distance along the ray to the closest intersection with the box (slab method), if any. Rays starting inside the box
+  /// hit it right away, at 0.
+  pub fn intersects_ray(&self, origin: Vec3<f32>, direction: Vec3<f32>) -> Option<f32> {
+    let mut t_min: f32 = 0.0;
+    let mut t_max: f32 = f32::MAX;
+    
+    for axis in 0..3usize {
+      let (origin_axis, direction_axis, min_axis, max_axis) = match axis {
+        0 => (origin.x, direction.x, self.m_min.x, self.m_max.x),
+        1 => (origin.y, direction.y, self.m_min.y, self.m_max.y),
+        _ => (origin.z, direction.z, self.m_min.z, self.m_max.z),
+      };
+      
+      // Parallel to the slab, either always inside it or never.
+      if direction_axis.abs() <= f32::EPSILON {
+        if origin_axis < min_axis || origin_axis > max_axis {
+          return None;
+        }
+        continue;
+      }
+      
+      let mut t_near = (min_axis - origin_axis) / direction_axis;
+      let mut t_far = (max_axis - origin_axis) / direction_axis;
+      if t_near > t_far {
+        std::mem::swap(&mut t_near, &mut t_far);
+      }
+      t_min = t_min.max(t_near);
+      t_max = t_max.min(t_far);
+      if t_min > t_max {
+        return None;
+      }
+    }
+    return Some(t_min);
+  }
+}
+
+impl Default for Aabb {
+  fn default() -> Self {
+    return Self::new(Vec3::default(), Vec3::default());
+  }
+}
+
+/*
+///////////////////////////////////   EASING  ///////////////////////////////////
+///////////////////////////////////           ///////////////////////////////////
+///////////////////////////////////           ///////////////////////////////////
+ */
+
+/// Curve remapping the linear progress of an animation, from 0 to 1.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum EnumEasing {
+  Linear,
+  // Slow start and end (smoothstep).
+  EaseInOut,
+  // Bouncing off the end, like a dropped ball.
+  Bounce,
+}
+
+impl Default for EnumEasing {
+  fn default() -> Self {
+    return EnumEasing::Linear;
+  }
+}
+
+impl EnumEasing {
+  pub fn apply(&self, progress: f32) -> f32 {
+    let t: f32 = progress.clamp(0.0, 1.0);
+    return match self {
+      EnumEasing::Linear => t,
+      EnumEasing::EaseInOut => t * t * (3.0 - 2.0 * t),
+      EnumEasing::Bounce => {
+        const C_N: f32 = 7.5625;
+        const C_D: f32 = 2.75;
+        
+        if t < 1.0 / C_D {
+          C_N * t * t
+        } else if t < 2.0 / C_D {
+          let t = t - 1.5 / C_D;
+          C_N * t * t + 0.75
+        } else if t < 2.5 / C_D {
+          let t = t - 2.25 / C_D;
+          C_N * t * t + 0.9375
+        } else {
+          let t = t - 2.625 / C_D;
+          C_N * t * t + 0.984375
+        }
+      }
+    };
+  }
+}
+
+/*
+///////////////////////////////////   UTILS  ///////////////////////////////////
+///////////////////////////////////          ///////////////////////////////////
+///////////////////////////////////          ///////////////////////////////////
+ */
+
+/// Round `value` to the nearest multiple of `grid`, halfway values going away from zero on either side of it. A grid
+/// of zero or less leaves the value as is.
+pub fn snap(value: f32, grid: f32) -> f32 {
+  if grid <= 0.0 {
+    return value;
+  }
+  // Adding zero turns a negative zero (i.e. -0.1 snapped to 1) into a positive one.
+  return (value / grid).round() * grid + 0.0;
+}
@@ -24,6 +24,8 @@
 
 use std::fmt::{Display, Formatter};
 
+pub use crate::graphics::text::Font;
+
 #[derive(Debug, Copy, Clone, PartialOrd, PartialEq, Ord, Eq, Hash)]
 pub enum EnumUIError {
   InvalidContext,
@@ -39,10 +41,17 @@ impl Display for EnumUIError {
 
 #[derive(Debug, Copy, Clone, PartialOrd, PartialEq, Ord, Eq, Hash)]
 pub enum EnumUiType {
+  /// Panels can be docked into each other and onto a dockspace covering the main window.
   Docked,
   Floating,
 }
 
+impl Default for EnumUiType {
+  fn default() -> Self {
+    return EnumUiType::Floating;
+  }
+}
+
 pub mod ui_imgui {
   use std::ffi::CStr;
   use std::os::raw::c_void;
@@ -53,7 +62,7 @@ pub mod ui_imgui {
   pub use glfw;
   use glfw::{Context, Key, StandardCursor};
   use glfw::ffi::GLFWwindow;
-  use imgui::{Condition, ConfigFlags, Key as ImGuiKey, MouseCursor};
+  use imgui::{ConfigFlags, Key as ImGuiKey, MouseCursor};
   use imgui_opengl_renderer::Renderer;
   
   #[allow(unused)]
@@ -61,7 +70,7 @@ pub mod ui_imgui {
   use crate::events::EnumEvent;
   use crate::graphics::renderer::EnumRendererApi;
   use crate::input::{EnumAction, EnumModifiers, EnumMouseButton};
-  use crate::ui::EnumUIError;
+  use crate::ui::{EnumUIError, EnumUiType};
   use crate::utils::Time;
   use crate::window::Window;
   
@@ -81,7 +90,13 @@ pub mod ui_imgui {
     }
   }
   
+  /// Callback drawing the app's own panels, called once per frame in between imgui's new frame and render.
+  pub type UiFn = Box<dyn FnMut(&imgui::Ui)>;
+  
   trait TraitUi {
+    fn set_ui_fn(&mut self, ui_fn: Option<UiFn>);
+    fn set_ui_type(&mut self, ui_type: EnumUiType);
+    fn get_ui_type(&self) -> EnumUiType;
     fn on_event(&mut self, event: &EnumEvent) -> bool;
     fn on_update(&mut self);
     fn on_render(&mut self);
@@ -105,6 +120,25 @@ pub mod ui_imgui {
       };
     }
     
+    /// Set what to draw every frame, replacing the previous callback. Nothing gets drawn without one.
+    pub fn set_ui_fn(&mut self, ui_fn: UiFn) {
+      self.m_api.set_ui_fn(Some(ui_fn));
+    }
+    
+    pub fn clear_ui_fn(&mut self) {
+      self.m_api.set_ui_fn(None);
+    }
+    
+    /// Toggle docking. Multiple viewports (panels dragged outside the main window) stay disabled, since the OpenGL
+    /// backend does not create platform windows.
+    pub fn set_ui_type(&mut self, ui_type: EnumUiType) {
+      self.m_api.set_ui_type(ui_type);
+    }
+    
+    pub fn get_ui_type(&self) -> EnumUiType {
+      return self.m_api.get_ui_type();
+    }
+    
     pub fn on_event(&mut self, event: &EnumEvent) -> bool {
       return self.m_api.on_event(event);
     }
@@ -137,9 +171,29 @@ pub mod ui_imgui {
     m_ui_handle: *mut imgui::Ui,
     m_window_handle: *mut Window,
     m_renderer: Renderer,
+    m_ui_fn: Option<UiFn>,
+    m_ui_type: EnumUiType,
   }
   
   impl TraitUi for GlImgui {
+    fn set_ui_fn(&mut self, ui_fn: Option<UiFn>) {
+      self.m_ui_fn = ui_fn;
+    }
+    
+    fn set_ui_type(&mut self, ui_type: EnumUiType) {
+      self.m_ui_type = ui_type;
+      
+      let io = self.m_imgui_handle.io_mut();
+      match ui_type {
+        EnumUiType::Docked => io.config_flags.insert(ConfigFlags::DOCKING_ENABLE),
+        EnumUiType::Floating => io.config_flags.remove(ConfigFlags::DOCKING_ENABLE)
+      }
+    }
+    
+    fn get_ui_type(&self) -> EnumUiType {
+      return self.m_ui_type;
+    }
+    
     fn on_event(&mut self, event: &EnumEvent) -> bool {
       return match event {
         EnumEvent::MouseBtnEvent(mouse_btn, action, _modifiers) => {
@@ -165,10 +219,10 @@ pub mod ui_imgui {
           self.m_imgui_handle.io_mut().mouse_wheel = *y as f32;
           true
         }
-        // WindowEvent::Char(character) => {
-        //   self.m_imgui_handle.io_mut().add_input_character(character);
-        //   true
-        // }
+        EnumEvent::CharEvent(character) => {
+          self.m_imgui_handle.io_mut().add_input_character(*character);
+          true
+        }
         EnumEvent::KeyEvent(key, action, _repeat_count, modifier) => {
           // GLFW modifiers.
           self.m_imgui_handle.io_mut().key_ctrl = modifier.intersects(EnumModifiers::Control);
@@ -176,11 +230,12 @@ pub mod ui_imgui {
           self.m_imgui_handle.io_mut().key_shift = modifier.intersects(EnumModifiers::Shift);
           self.m_imgui_handle.io_mut().key_super = modifier.intersects(EnumModifiers::Super);
           
-          self.m_imgui_handle.io_mut().keys_down[*key as usize] = action == &EnumAction::Pressed;
+          // Keep the key down while repeating, imgui applies its own repeat rate to text navigation from there.
+          self.m_imgui_handle.io_mut().keys_down[*key as usize] = action != &EnumAction::Released;
           false
         }
-        EnumEvent::FramebufferEvent(x_size, y_size) => {
-          self.m_imgui_handle.io_mut().display_size = [*x_size as f32, *y_size as f32];
+        EnumEvent::FramebufferEvent(_, _) | EnumEvent::ContentScaleEvent(_, _) => {
+          unsafe { Self::sync_display_metrics(self.m_imgui_handle.io_mut(), &*self.m_window_handle) };
           true
         }
         _ => false
@@ -197,22 +252,14 @@ pub mod ui_imgui {
       
       self.m_ui_handle = self.m_imgui_handle.new_frame();
       
-      // unsafe {
-      //   (*self.m_ui_handle).dockspace_over_main_viewport();
-      // }
-      
       unsafe {
-        (*self.m_ui_handle).window("Example Ui")
-          .bg_alpha(0.0)
-          .menu_bar(true)
-          .resizable(true)
-          .mouse_inputs(true)
-          .size(self.m_imgui_handle.io_mut().display_size, Condition::FirstUseEver)
-          .position([(*self.m_window_handle).m_window_pos.0 as f32,
-            (*self.m_window_handle).m_window_pos.1 as f32], Condition::FirstUseEver)
-          .build(|| {
-            (*self.m_ui_handle).text_colored([1.0, 0.0, 0.0, 1.0], "Example text");
-          });
+        if self.m_ui_type == EnumUiType::Docked {
+          (*self.m_ui_handle).dockspace_over_main_viewport();
+        }
+        
+        if let Some(ui_fn) = self.m_ui_fn.as_mut() {
+          ui_fn(&*self.m_ui_handle);
+        }
       }
     }
     
@@ -266,6 +313,7 @@ pub mod ui_imgui {
       
       let io_mut = context.io_mut();
       Self::glfw_to_imgui(io_mut);
+      unsafe { Self::sync_display_metrics(io_mut, &*window) };
       context.set_renderer_name(String::from("OpenGL"));
       
       let renderer = Renderer::new(&mut context, |s| unsafe {
@@ -281,7 +329,28 @@ pub mod ui_imgui {
         m_ui_handle: std::ptr::null_mut(),
         m_window_handle: window,
         m_renderer: renderer,
+        m_ui_fn: None,
+        m_ui_type: EnumUiType::default(),
+      }
+    }
+    
+    /// Imgui works in logical units and scales them up to the framebuffer itself, so the framebuffer scale is the ratio
+    /// between the framebuffer and the window size. The content scale (DPI) only drives the font size, for the part
+    /// the framebuffer scale doesn't already cover.
+    fn sync_display_metrics(io: &mut imgui::Io, window: &Window) {
+      let ((width, height), (fb_width, fb_height)) = match window.m_api_window.as_ref() {
+        Some(api_window) => (api_window.get_size(), api_window.get_framebuffer_size()),
+        None => return,
+      };
+      // Minimized windows report a null size, keep the previous metrics until they get restored.
+      if width <= 0 || height <= 0 {
+        return;
       }
+      let (x_scale, _y_scale) = window.get_content_scale();
+      
+      io.display_size = [width as f32, height as f32];
+      io.display_framebuffer_scale = [fb_width as f32 / width as f32, fb_height as f32 / height as f32];
+      io.font_global_scale = x_scale / io.display_framebuffer_scale[0];
     }
     
     fn glfw_to_imgui(imgui: &mut imgui::Io) {
@@ -0,0 +1,189 @@
+/*
+ MIT License
+ 
+ Copyright (c) 2024 Nami Reghbati
+ 
+ Permission is hereby granted, free of charge, to any person obtaining a copy
+ of this software and associated documentation files (the "Software"), to deal
+ in the Software without restriction, including without limitation the rights
+ to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ copies of the Software, and to permit persons to whom the Software is
+ furnished to do so, subject to the following conditions:
+ 
+ The above copyright notice and this permission notice shall be included in all
+ copies or substantial portions of the Software.
+ 
+ THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ FITNESS FOR A PARTICULAR PURPOSE AND NON INFRINGEMENT. IN NO EVENT SHALL THE
+ AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ SOFTWARE.
+*/
+
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+
+#[cfg(feature = "debug")]
+use crate::Engine;
+use crate::graphics::renderer::{EnumRendererApi, EnumRendererHint, Renderer};
+use crate::TraitHint;
+use crate::utils::macros::logger::*;
+use crate::window::{EnumWindowHint, EnumWindowMode, Window};
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum EnumConfigError {
+  IoError(std::io::ErrorKind),
+  InvalidSyntax(usize),
+  InvalidValue(usize),
+}
+
+impl Display for EnumConfigError {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    write!(f, "[Config] -->\t Error encountered while loading config : {:?}", self)
+  }
+}
+
+/// Window and renderer settings loaded at startup, so they can be tweaked without recompiling. Config files use a
+/// flat subset of TOML, where any missing field falls back to its default :
+///
+/// ```toml
+/// # Renderer api, either "opengl" or "vulkan".
+/// api = "opengl"
+/// # Omit to let the window pick the monitor's resolution.
+/// resolution = [1920, 1080]
+/// # Either "windowed", "borderless", "fullscreen" or "headless".
+/// window_mode = "windowed"
+/// vsync = true
+/// # Sample count, 0 to disable MSAA.
+/// msaa = 4
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Config {
+  /// Api to create the window and renderer with, see [Window::new] and [Renderer::new].
+  pub m_renderer_api: EnumRendererApi,
+  pub m_resolution: Option<(u32, u32)>,
+  pub m_window_mode: EnumWindowMode,
+  pub m_vsync: bool,
+  pub m_msaa: Option<u8>,
+}
+
+impl Default for Config {
+  fn default() -> Self {
+    return Config {
+      m_renderer_api: EnumRendererApi::default(),
+      m_resolution: None,
+      m_window_mode: EnumWindowMode::default(),
+      m_vsync: true,
+      m_msaa: None,
+    };
+  }
+}
+
+impl Config {
+  pub fn from_file(file_path: &str) -> Result<Self, EnumConfigError> {
+    let contents: String = std::fs::read_to_string(Path::new(file_path)).map_err(|err| {
+      log!(EnumLogColor::Red, "ERROR", "[Config] -->\t Cannot read config file {0} : {1}", file_path, err);
+      return EnumConfigError::IoError(err.kind());
+    })?;
+    
+    let config = Config::parse(&contents)?;
+    log!("INFO", "[Config] -->\t Loaded config from {0} : {1:?}", file_path, config);
+    return Ok(config);
+  }
+  
+  /// Parse a config from the contents of a config file. Unknown keys and sections are ignored with a warning.
+  pub fn parse(contents: &str) -> Result<Self, EnumConfigError> {
+    let mut config = Config::default();
+    
+    for (line_index, line) in contents.lines().enumerate() {
+      let line_number: usize = line_index + 1;
+      let line: &str = Config::strip_comment(line).trim();
+      if line.is_empty() || line.starts_with('[') {
+        continue;
+      }
+      
+      let Some((key, value)) = line.split_once('=') else {
+        log!(EnumLogColor::Red, "ERROR", "[Config] -->\t Invalid syntax on line {0}, expected 'key = value'!",
+          line_number);
+        return Err(EnumConfigError::InvalidSyntax(line_number));
+      };
+      let (key, value) = (key.trim(), value.trim());
+      let unquoted: String = value.trim_matches('"').to_lowercase();
+      
+      match key {
+        "api" => {
+          config.m_renderer_api = match unquoted.as_str() {
+            "opengl" => EnumRendererApi::OpenGL,
+            "vulkan" => EnumRendererApi::Vulkan,
+            _ => return Err(Config::invalid_value(key, value, line_number)),
+          };
+        }
+        "resolution" => {
+          let dimensions: Vec<Result<u32, _>> = value.trim_start_matches('[').trim_end_matches(']')
+            .split(',')
+            .map(|dimension| dimension.trim().parse::<u32>())
+            .collect();
+          config.m_resolution = match dimensions.as_slice() {
+            [Ok(width), Ok(height)] => Some((*width, *height)),
+            _ => return Err(Config::invalid_value(key, value, line_number)),
+          };
+        }
+        "window_mode" => {
+          config.m_window_mode = match unquoted.as_str() {
+            "windowed" => EnumWindowMode::Windowed,
+            "borderless" => EnumWindowMode::Borderless,
+            "fullscreen" => EnumWindowMode::Fullscreen,
+            "headless" => EnumWindowMode::Headless,
+            _ => return Err(Config::invalid_value(key, value, line_number)),
+          };
+        }
+        "vsync" => {
+          config.m_vsync = value.parse::<bool>().map_err(|_| Config::invalid_value(key, value, line_number))?;
+        }
+        "msaa" => {
+          let samples: u8 = value.parse::<u8>().map_err(|_| Config::invalid_value(key, value, line_number))?;
+          config.m_msaa = (samples > 1).then_some(samples);
+        }
+        _ => {
+          log!(EnumLogColor::Yellow, "WARN", "[Config] -->\t Unknown key '{0}' on line {1}, ignoring it...", key,
+            line_number);
+        }
+      }
+    }
+    return Ok(config);
+  }
+  
+  /// Set the matching window and renderer hints, before applying them. The api can't be changed through hints, use
+  /// [Self::m_renderer_api] when creating the window and renderer.
+  pub fn apply_to(&self, window: &mut Window, renderer: &mut Renderer) {
+    window.set_hint(EnumWindowHint::WindowMode(self.m_window_mode));
+    if let Some((width, height)) = self.m_resolution {
+      window.set_hint(EnumWindowHint::Resolution(width, height));
+    }
+    window.set_hint(EnumWindowHint::VSync(self.m_vsync));
+    window.set_hint(EnumWindowHint::MSAA(self.m_msaa.map(|samples| samples as u32)));
+    renderer.set_hint(EnumRendererHint::MSAA(self.m_msaa));
+  }
+  
+  // Everything before the first '#' that isn't inside a quoted string.
+  fn strip_comment(line: &str) -> &str {
+    let mut in_quotes: bool = false;
+    
+    for (char_index, character) in line.char_indices() {
+      match character {
+        '"' => in_quotes = !in_quotes,
+        '#' if !in_quotes => return &line[..char_index],
+        _ => {}
+      }
+    }
+    return line;
+  }
+  
+  fn invalid_value(_key: &str, _value: &str, line_number: usize) -> EnumConfigError {
+    log!(EnumLogColor::Red, "ERROR", "[Config] -->\t Invalid value '{0}' for '{1}' on line {2}!", _value, _key,
+      line_number);
+    return EnumConfigError::InvalidValue(line_number);
+  }
+}
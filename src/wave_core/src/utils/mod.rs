@@ -23,6 +23,7 @@
 */
 
 pub mod texture_loader;
+pub mod config;
 
 pub mod macros {
   ///
@@ -208,6 +209,10 @@ pub mod macros {
   
   pub mod logger {
     use std::fs::File;
+    use std::io::Write;
+    use std::path::Path;
+    use std::sync::atomic::{AtomicU64, AtomicU8, AtomicUsize, Ordering};
+    use std::sync::Mutex;
     
     pub enum EnumLogColor {
       White,
@@ -218,6 +223,156 @@ pub mod macros {
       Purple,
     }
     
+    /// Severity of a log message, inferred from its log type (i.e. "INFO"). Messages below the level set with
+    /// [set_log_level] are skipped before being formatted.
+    #[repr(u8)]
+    #[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+    pub enum EnumLogLevel {
+      Trace = 0,
+      Info = 1,
+      Warn = 2,
+      Error = 3,
+    }
+    
+    impl From<&str> for EnumLogLevel {
+      fn from(log_type: &str) -> Self {
+        return match log_type {
+          "ERROR" => EnumLogLevel::Error,
+          "WARN" => EnumLogLevel::Warn,
+          "INFO" => EnumLogLevel::Info,
+          // Debug, event and timer messages.
+          _ => EnumLogLevel::Trace
+        };
+      }
+    }
+    
+    static S_LOG_LEVEL: AtomicU8 = AtomicU8::new(EnumLogLevel::Trace as u8);
+    
+    pub fn set_log_level(log_level: EnumLogLevel) {
+      S_LOG_LEVEL.store(log_level as u8, Ordering::Relaxed);
+    }
+    
+    pub fn get_log_level() -> EnumLogLevel {
+      return match S_LOG_LEVEL.load(Ordering::Relaxed) {
+        0 => EnumLogLevel::Trace,
+        1 => EnumLogLevel::Info,
+        2 => EnumLogLevel::Warn,
+        _ => EnumLogLevel::Error
+      };
+    }
+    
+    #[inline(always)]
+    pub fn is_log_enabled(log_level: EnumLogLevel) -> bool {
+      return log_level as u8 >= S_LOG_LEVEL.load(Ordering::Relaxed);
+    }
+    
+    pub const C_LOG_FILE_NAME: &str = "wave-engine.log";
+    const C_LOG_DEFAULT_MAX_SIZE: u64 = 50 * 1024 * 1024;
+    const C_LOG_DEFAULT_MAX_ROTATED_FILES: usize = 3;
+    
+    static S_LOG_MAX_SIZE: AtomicU64 = AtomicU64::new(C_LOG_DEFAULT_MAX_SIZE);
+    static S_LOG_MAX_ROTATED_FILES: AtomicUsize = AtomicUsize::new(C_LOG_DEFAULT_MAX_ROTATED_FILES);
+    
+    /// Set the size, in bytes, past which the log file gets rotated, and how many rotated log files to keep around
+    /// (`wave-engine.1.log` being the most recent). Defaults to 50 MiB and 3 files.
+    pub fn set_log_rotation(max_size: u64, max_rotated_files: usize) {
+      S_LOG_MAX_SIZE.store(max_size, Ordering::Relaxed);
+      S_LOG_MAX_ROTATED_FILES.store(max_rotated_files, Ordering::Relaxed);
+    }
+    
+    // Log file written to by [log!], only ever accessed while locked, so that messages logged concurrently from several
+    // threads never interleave, and so that rotating it never pulls the file from under another thread.
+    static S_LOG_FILE: Mutex<Option<File>> = Mutex::new(None);
+    
+    pub fn get_log_file() -> &'static Mutex<Option<File>> {
+      return &S_LOG_FILE;
+    }
+    
+    /// Log to `log_file` from now on, replacing the previous log file, if any.
+    pub fn set_log_file(log_file: File) {
+      *lock_log_file(&S_LOG_FILE) = Some(log_file);
+    }
+    
+    fn lock_log_file(log_file: &Mutex<Option<File>>) -> std::sync::MutexGuard<'_, Option<File>> {
+      // A thread panicking mid-write leaves nothing to recover, keep logging.
+      return log_file.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    }
+    
+    /// Write a whole log line, followed by a color reset and a newline, to `log_file` (if open) and to stdout.
+    /// Concurrent calls are serialized, so each line comes out in one piece.
+    pub fn write_log_line(log_file: &Mutex<Option<File>>, line: &str) {
+      let line: String = format!("{0}\x1b[0m\n", line);
+      let mut log_file = lock_log_file(log_file);
+      
+      if let Some(log_file) = log_file.as_mut() {
+        let _ = log_file.write_all(line.as_bytes());
+      }
+      let mut stdout = std::io::stdout().lock();
+      let _ = stdout.write_all(line.as_bytes());
+      let _ = stdout.flush();
+    }
+    
+    /// Whether the current log file grew past the size limit set with [set_log_rotation].
+    pub fn needs_rotation() -> bool {
+      return std::fs::metadata(C_LOG_FILE_NAME)
+        .is_ok_and(|metadata| metadata.len() > S_LOG_MAX_SIZE.load(Ordering::Relaxed));
+    }
+    
+    /// Rotate the log file if it grew past the size limit set with [set_log_rotation], and reopen it. Done while
+    /// holding the log file lock, so that no other thread can be writing to it in the meantime.
+    ///
+    /// ### Returns:
+    /// - *bool*: Whether the log file got rotated.
+    pub fn rotate_log_file() -> bool {
+      let mut log_file = lock_log_file(&S_LOG_FILE);
+      if !needs_rotation() {
+        return false;
+      }
+      
+      // Close the current file before renaming it, then start a fresh one.
+      log_file.take();
+      *log_file = init();
+      return true;
+    }
+    
+    /// Rotate the log file at `path` if it is larger than `max_size` bytes : `name.log` becomes `name.1.log`,
+    /// `name.1.log` becomes `name.2.log` and so on, dropping the oldest past `max_rotated_files`.
+    ///
+    /// ### Returns:
+    /// - *std::io::Result<bool>*: Whether the file got rotated, or the I/O error encountered while renaming.
+    pub fn rotate_if_larger_than(path: &str, max_size: u64, max_rotated_files: usize) -> std::io::Result<bool> {
+      let Ok(metadata) = std::fs::metadata(path) else {
+        return Ok(false);
+      };
+      if metadata.len() <= max_size {
+        return Ok(false);
+      }
+      
+      if max_rotated_files == 0 {
+        std::fs::remove_file(path)?;
+        return Ok(true);
+      }
+      
+      let rotated_path = |index: usize| -> String {
+        let file_path = Path::new(path);
+        let stem = file_path.file_stem().and_then(|stem| stem.to_str()).unwrap_or(path);
+        let rotated_name = match file_path.extension().and_then(|extension| extension.to_str()) {
+          Some(extension) => format!("{0}.{1}.{2}", stem, index, extension),
+          None => format!("{0}.{1}", stem, index),
+        };
+        return file_path.with_file_name(rotated_name).to_string_lossy().to_string();
+      };
+      
+      // Shift older logs first, overwriting the oldest one.
+      for index in (1..max_rotated_files).rev() {
+        if Path::new(&rotated_path(index)).exists() {
+          std::fs::rename(rotated_path(index), rotated_path(index + 1))?;
+        }
+      }
+      std::fs::rename(path, rotated_path(1))?;
+      return Ok(true);
+    }
+    
     #[cfg(not(feature = "debug"))]
     #[macro_export]
     macro_rules! trace {
@@ -227,21 +382,28 @@ pub mod macros {
     #[cfg(not(feature = "profiler"))]
     #[macro_export]
     macro_rules! profile {
+    ($label: literal, $block: block) => { $block };
     ($expression: expr) => { $expression; };
 }
     
+    ///
+    /// Time a labeled block (or a single expression) with a [crate::utils::ScopedTimer], aggregating results in
+    /// [crate::utils::profiler_report]. Only enabled with the `profiler` feature, otherwise the block simply runs.
+    ///
+    /// ```text
+    /// let visible = profile!("Culling", { renderer.cull(&camera) });
+    /// ```
     #[cfg(feature = "profiler")]
     #[macro_export]
     macro_rules! profile {
-  ($expression: expr) => {
-    use crate::wave::Time;
-    
-    let current_time = Time::from(chrono::Utc::now());
+  ($label: literal, $block: block) => {{
+    let _scoped_timer = $crate::utils::ScopedTimer::new($label);
+    $block
+  }};
+  ($expression: expr) => {{
+    let _scoped_timer = $crate::utils::ScopedTimer::new(stringify!($expression));
     $expression;
-    let end_time = Time::from(chrono::Utc::now());
-    log!(EnumLogColor::Blue, "TIMER", "Instruction took {0} milliseconds",
-      Time::get_delta(&current_time, &end_time).to_millis());
-  }
+  }};
 }
     
     ///
@@ -405,10 +567,14 @@ pub mod macros {
     
     #[inline(always)]
     pub fn init() -> Option<File> {
+      // Start fresh if previous sessions grew the log past its limit.
+      let _ = rotate_if_larger_than(C_LOG_FILE_NAME, S_LOG_MAX_SIZE.load(Ordering::Relaxed),
+        S_LOG_MAX_ROTATED_FILES.load(Ordering::Relaxed));
+      
       let file = std::fs::OpenOptions::new()
         .append(true)
         .create(true)
-        .open("wave-engine.log");
+        .open(C_LOG_FILE_NAME);
       
       return file.ok();
     }
@@ -429,6 +595,9 @@ pub mod macros {
     /// log!(log_type, format + arguments)
     /// log!(log_color, log_type, format + arguments)
     /// ```
+    ///
+    /// Messages whose log type maps to an [EnumLogLevel] below the one set with [set_log_level] are skipped
+    /// before anything gets formatted.
     /// \
     /// \
     /// ### Returns: Nothing
@@ -468,42 +637,38 @@ pub mod macros {
     };
 
     ($log_type: literal, $($format_and_arguments:tt)*) => {{
-      use std::io::Write;
       use self::Engine;
-      use self::{trace, function_name, file_name};
+      use self::{trace, function_name, file_name, is_log_enabled, write_log_line, EnumLogLevel};
       use chrono;
       
-      let current_time = chrono::Local::now();
-
-      let format_string: String = format!("\x1b[0m[{0}]\t[{1:19}] {2:<60}\t",
-                                           $log_type, &current_time.to_string()[0..19], trace!());
-
-      let log_message: String = format!($($format_and_arguments)*);
-      let mut log_file_ptr = Engine::get_log_file();
-      
-      let _ = writeln!(log_file_ptr, "{0}\x1b[0m", format_string.clone() + &log_message);
-      let _ = std::io::stdout().flush();
-      let _ = writeln!(std::io::stdout(), "{0}\x1b[0m", format_string + &log_message);
+      if is_log_enabled(EnumLogLevel::from($log_type)) {
+        let current_time = chrono::Local::now();
+        
+        let format_string: String = format!("\x1b[0m[{0}]\t[{1:19}] {2:<60}\t",
+                                             $log_type, &current_time.to_string()[0..19], trace!());
+        
+        let log_message: String = format!($($format_and_arguments)*);
+        write_log_line(Engine::get_log_file(), &(format_string + &log_message));
+      }
     }};
 
     ($log_color: expr, $log_type: literal, $($format_and_arguments:tt)*) =>{{
-      use std::io::Write;
       use self::Engine;
-      use self::{trace, function_name, file_name, color_to_str, EnumLogColor};
+      use self::{trace, function_name, file_name, color_to_str, EnumLogColor, is_log_enabled, write_log_line,
+        EnumLogLevel};
       use chrono;
       
-      let current_time = chrono::Local::now();
-
-      let log_color: &str = color_to_str($log_color);
-      let format_string: String = format!("{0}[{1}]\t[{2:19}] {3:<60}\t",
-                                          log_color, $log_type, &current_time.to_string()[0..19],
-                                          trace!());
-
-      let log_message: String = format!($($format_and_arguments)*);
-      let mut log_file_ptr = Engine::get_log_file();
-      let _ = writeln!(log_file_ptr, "{0}\x1b[0m", format_string.clone() + &log_message);
-      let _ = std::io::stdout().flush();
-      let _ = writeln!(std::io::stdout(), "{0}\x1b[0m", format_string + &log_message);
+      if is_log_enabled(EnumLogLevel::from($log_type)) {
+        let current_time = chrono::Local::now();
+        
+        let log_color: &str = color_to_str($log_color);
+        let format_string: String = format!("{0}[{1}]\t[{2:19}] {3:<60}\t",
+                                            log_color, $log_type, &current_time.to_string()[0..19],
+                                            trace!());
+        
+        let log_message: String = format!($($format_and_arguments)*);
+        write_log_line(Engine::get_log_file(), &(format_string + &log_message));
+      }
     }};
   }
     
@@ -521,7 +686,7 @@ pub mod macros {
     
     #[inline(always)]
     pub fn show_logs() -> String {
-      let logs: String = std::fs::read_to_string("wave-engine.log")
+      let logs: String = std::fs::read_to_string(C_LOG_FILE_NAME)
         .expect("[Logger] --> Unable to show logs, due to error opening file!");
       println!(
         "-----------------Start of Logs------------------------\n{}\
@@ -536,7 +701,7 @@ pub mod macros {
       std::fs::OpenOptions::new()
         .write(true)
         .truncate(true)
-        .open("wave-engine.log")
+        .open(C_LOG_FILE_NAME)
         .expect("[Logger] --> Could not reset file, due to error opening file!");
     }
     pub use log;
@@ -550,13 +715,19 @@ pub mod macros {
 ///////////////////////////////////           ///////////////////////////////////
  */
 
+use std::fmt::{Display, Formatter};
+use std::sync::Mutex;
+
 #[cfg(feature = "debug")]
 use crate::Engine;
 use self::macros::logger::*;
 
 const CONST_TIME_NANO: f64 = 1000000000.0;
-const CONST_TIME_MICRO: f64 = 1000000.0;
-const CONST_TIME_MILLI: f64 = 1000.0;
+// Nano seconds in a milli second and in a micro second.
+const CONST_TIME_MILLI: f64 = 1000000.0;
+const CONST_TIME_MICRO: f64 = 1000.0;
+// Time left to spin for, in seconds, when waiting, to make up for sleep imprecision.
+const CONST_TIME_SPIN_MARGIN: f64 = 0.002;
 
 impl From<chrono::DateTime<chrono::Utc>> for Time {
   fn from(local_time: chrono::DateTime<chrono::Utc>) -> Self {
@@ -589,7 +760,7 @@ impl Time {
   
   pub fn from_milli_u64(milli_seconds: u64) -> Self {
     return Time {
-      m_nano_seconds: milli_seconds as f64 * 1000.0,
+      m_nano_seconds: milli_seconds as f64 * CONST_TIME_MILLI,
     };
   }
   
@@ -601,13 +772,13 @@ impl Time {
       };
     }
     return Time {
-      m_nano_seconds: milli_seconds * 1000.0,
+      m_nano_seconds: milli_seconds * CONST_TIME_MILLI,
     };
   }
   
   pub fn from_micro_u64(micro_seconds: u64) -> Self {
     return Time {
-      m_nano_seconds: micro_seconds as f64 * 1000_000.0,
+      m_nano_seconds: micro_seconds as f64 * CONST_TIME_MICRO,
     };
   }
   
@@ -619,7 +790,7 @@ impl Time {
       };
     }
     return Time {
-      m_nano_seconds: micro_seconds * 1000_000.0,
+      m_nano_seconds: micro_seconds * CONST_TIME_MICRO,
     };
   }
   
@@ -654,11 +825,20 @@ impl Time {
     };
   }
   
+  /// Block the current thread for the given number of seconds. Sleep for the bulk of the interval and only spin
+  /// for the last stretch, since the OS scheduler may oversleep by a millisecond or so.
   pub fn wait_for(seconds: f64) -> () {
     if seconds <= 0.0 {
       return;
     }
-    std::thread::sleep(std::time::Duration::from_secs_f64(seconds));
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs_f64(seconds);
+    
+    if seconds > CONST_TIME_SPIN_MARGIN {
+      std::thread::sleep(std::time::Duration::from_secs_f64(seconds - CONST_TIME_SPIN_MARGIN));
+    }
+    while std::time::Instant::now() < deadline {
+      std::hint::spin_loop();
+    }
   }
   
   pub fn reset(&mut self) {
@@ -670,11 +850,11 @@ impl Time {
   }
   
   pub fn to_micros(&self) -> f64 {
-    return self.m_nano_seconds / CONST_TIME_MILLI;
+    return self.m_nano_seconds / CONST_TIME_MICRO;
   }
   
   pub fn to_millis(&self) -> f64 {
-    return self.m_nano_seconds / CONST_TIME_MICRO;
+    return self.m_nano_seconds / CONST_TIME_MILLI;
   }
 }
 
@@ -685,7 +865,7 @@ impl std::ops::Add for Time {
   
   fn add(self, rhs: Self) -> Time {
     return Time {
-      m_nano_seconds: self.m_nano_seconds - rhs.m_nano_seconds,
+      m_nano_seconds: self.m_nano_seconds + rhs.m_nano_seconds,
     };
   }
 }
@@ -699,3 +879,175 @@ impl std::ops::Sub for Time {
     };
   }
 }
+
+///////////////////////////////////   FIXED TIME STEP    ///////////////////////////////////
+
+/// Accumulates variable frame deltas and hands them back as a whole number of fixed-size steps, so that fixed
+/// updates (physics, gameplay ticks) run a deterministic number of times per second regardless of the framerate.
+///
+/// The number of steps returned for a single frame is clamped to [Self::m_max_steps]. Without this clamp, a long
+/// hitch (i.e. dragging the window, a breakpoint) would queue up more fixed steps than a frame can process, making
+/// the next frame even longer, and so on (the 'spiral of death'). Any time left over past the clamp is dropped,
+/// which makes the simulation run slower than real time during hitches instead of stalling.
+#[derive(Debug, PartialEq, PartialOrd, Copy, Clone)]
+pub struct FixedTimeStep {
+  m_step: f64,
+  m_accumulator: f64,
+  m_max_steps: u32,
+}
+
+impl Default for FixedTimeStep {
+  fn default() -> Self {
+    return FixedTimeStep::new(60, 5);
+  }
+}
+
+impl FixedTimeStep {
+  pub fn new(steps_per_second: u32, max_steps: u32) -> Self {
+    return FixedTimeStep {
+      m_step: 1.0 / steps_per_second.max(1) as f64,
+      m_accumulator: 0.0,
+      m_max_steps: max_steps.max(1),
+    };
+  }
+  
+  pub fn set_steps_per_second(&mut self, steps_per_second: u32) {
+    self.m_step = 1.0 / steps_per_second.max(1) as f64;
+  }
+  
+  pub fn set_max_steps(&mut self, max_steps: u32) {
+    self.m_max_steps = max_steps.max(1);
+  }
+  
+  /// Fixed delta, in seconds, to pass to each fixed update.
+  pub fn get_step(&self) -> f64 {
+    return self.m_step;
+  }
+  
+  pub fn get_max_steps(&self) -> u32 {
+    return self.m_max_steps;
+  }
+  
+  /// Fraction of a step left in the accumulator, useful to interpolate rendering between two fixed updates.
+  pub fn get_alpha(&self) -> f64 {
+    return self.m_accumulator / self.m_step;
+  }
+  
+  /// Add the frame delta (in seconds) to the accumulator and return how many fixed steps should run this frame.
+  pub fn accumulate(&mut self, time_step: f64) -> u32 {
+    if time_step.is_sign_negative() {
+      return 0;
+    }
+    self.m_accumulator += time_step;
+    
+    let mut step_count: u32 = 0;
+    while self.m_accumulator >= self.m_step && step_count < self.m_max_steps {
+      self.m_accumulator -= self.m_step;
+      step_count += 1;
+    }
+    
+    // Clamped, drop the backlog instead of carrying it over to the next frames.
+    if self.m_accumulator >= self.m_step {
+      log!(EnumLogColor::Yellow, "WARN", "[Time] -->\t Fixed update falling behind, dropping {0:.3} secs!",
+        self.m_accumulator - (self.m_accumulator % self.m_step));
+      self.m_accumulator %= self.m_step;
+    }
+    return step_count;
+  }
+  
+  pub fn reset(&mut self) {
+    self.m_accumulator = 0.0;
+  }
+}
+
+///////////////////////////////////   PROFILER    ///////////////////////////////////
+
+static S_PROFILER_ENTRIES: Mutex<Vec<ProfilerEntry>> = Mutex::new(Vec::new());
+
+/// Aggregated timings, in milliseconds, of every [ScopedTimer] sharing the same label over the session.
+#[derive(Debug, PartialEq, PartialOrd, Copy, Clone)]
+pub struct ProfilerEntry {
+  pub m_label: &'static str,
+  pub m_calls: u64,
+  pub m_min: f64,
+  pub m_max: f64,
+  pub m_total: f64,
+}
+
+impl ProfilerEntry {
+  pub fn get_average(&self) -> f64 {
+    if self.m_calls == 0 {
+      return 0.0;
+    }
+    return self.m_total / self.m_calls as f64;
+  }
+}
+
+impl Display for ProfilerEntry {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{0:<30} calls: {1:<8} min: {2:.3} ms, max: {3:.3} ms, avg: {4:.3} ms, total: {5:.3} ms", self.m_label,
+      self.m_calls, self.m_min, self.m_max, self.get_average(), self.m_total)
+  }
+}
+
+/// Measures the time elapsed between its creation and its drop, logging it and adding it to the session's
+/// [profiler_report] under its label.
+pub struct ScopedTimer {
+  m_label: &'static str,
+  m_start: Time,
+}
+
+impl ScopedTimer {
+  pub fn new(label: &'static str) -> Self {
+    return ScopedTimer {
+      m_label: label,
+      m_start: Time::from(chrono::Utc::now()),
+    };
+  }
+  
+  /// Time elapsed since the timer started, in milliseconds.
+  pub fn get_elapsed(&self) -> f64 {
+    return Time::get_delta(self.m_start, Time::from(chrono::Utc::now())).to_millis();
+  }
+}
+
+impl Drop for ScopedTimer {
+  fn drop(&mut self) {
+    let elapsed: f64 = self.get_elapsed();
+    log!(EnumLogColor::Blue, "TIMER", "[Profiler] -->\t {0} took {1:.3} ms", self.m_label, elapsed);
+    
+    let Ok(mut entries) = S_PROFILER_ENTRIES.lock() else {
+      return;
+    };
+    match entries.iter_mut().find(|entry| entry.m_label == self.m_label) {
+      Some(entry) => {
+        entry.m_calls += 1;
+        entry.m_min = entry.m_min.min(elapsed);
+        entry.m_max = entry.m_max.max(elapsed);
+        entry.m_total += elapsed;
+      }
+      None => entries.push(ProfilerEntry {
+        m_label: self.m_label,
+        m_calls: 1,
+        m_min: elapsed,
+        m_max: elapsed,
+        m_total: elapsed,
+      }),
+    }
+  }
+}
+
+/// Timings aggregated per label since the start of the session (or the last [reset_profiler]), slowest in total first.
+pub fn profiler_report() -> Vec<ProfilerEntry> {
+  let mut report: Vec<ProfilerEntry> = S_PROFILER_ENTRIES.lock()
+    .map(|entries| entries.clone())
+    .unwrap_or_default();
+  report.sort_by(|a, b| b.m_total.total_cmp(&a.m_total));
+  return report;
+}
+
+pub fn reset_profiler() {
+  if let Ok(mut entries) = S_PROFILER_ENTRIES.lock() {
+    entries.clear();
+  }
+}
@@ -26,11 +26,12 @@ use std::any::Any;
 
 #[cfg(feature = "debug")]
 use crate::Engine;
-use crate::graphics::texture::{EnumTextureDataAlignment, EnumTextureFormat, EnumTextureInfo, EnumTextureLoaderError, EnumTextureTarget};
+use crate::graphics::texture::{EnumTextureDataAlignment, EnumTextureFilter, EnumTextureFormat, EnumTextureInfo, EnumTextureLoaderError,
+  EnumTextureTarget};
 use crate::TraitHint;
 use crate::utils::macros::logger::*;
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub enum EnumTextureLoaderHint {
   TextureType(EnumTextureTarget),
   MaxDimensions((u32, u32, u32)),
@@ -40,6 +41,10 @@ pub enum EnumTextureLoaderHint {
   DataEncodedWith(EnumTextureDataAlignment),
   FlipUvs(bool),
   BindLess(bool),
+  GenerateMipmaps(bool),
+  FilterMode(EnumTextureFilter),
+  // Max anisotropy level, clamped to what the driver supports.
+  Anisotropy(f32),
 }
 
 impl EnumTextureLoaderHint {
@@ -53,7 +58,10 @@ impl EnumTextureLoaderHint {
       EnumTextureLoaderHint::IsHdr(value) => result = value,
       EnumTextureLoaderHint::DataEncodedWith(value) => result = value,
       EnumTextureLoaderHint::FlipUvs(bool) => result = bool,
-      EnumTextureLoaderHint::BindLess(bool) => result = bool
+      EnumTextureLoaderHint::BindLess(bool) => result = bool,
+      EnumTextureLoaderHint::GenerateMipmaps(bool) => result = bool,
+      EnumTextureLoaderHint::FilterMode(value) => result = value,
+      EnumTextureLoaderHint::Anisotropy(value) => result = value
     };
     return result;
   }
@@ -66,6 +74,9 @@ impl EnumTextureLoaderHint {
 pub struct TextureInfo<T> {
   pub(crate) m_type: EnumTextureInfo,
   pub(crate) m_data: stb_image::image::Image<T>,
+  pub(crate) m_mipmaps: bool,
+  pub(crate) m_filter: EnumTextureFilter,
+  pub(crate) m_anisotropy: Option<f32>,
 }
 
 impl<T: Clone> Clone for TextureInfo<T> {
@@ -78,6 +89,9 @@ impl<T: Clone> Clone for TextureInfo<T> {
         depth: self.m_data.depth,
         data: self.m_data.data.clone(),
       },
+      m_mipmaps: self.m_mipmaps,
+      m_filter: self.m_filter,
+      m_anisotropy: self.m_anisotropy,
     }
   }
 }
@@ -166,7 +180,11 @@ impl TextureLoader {
     let mut texture_mipmap = 0;
     let mut texture_data_type = EnumTextureDataAlignment::default();
     let mut texture_format = EnumTextureFormat::default();
+    let mut target_format: Option<EnumTextureFormat> = None;
     let mut texture_hdr = false;
+    let mut texture_mipmaps = true;
+    let mut texture_filter = EnumTextureFilter::default();
+    let mut texture_anisotropy = None;
     
     // Toggle all provided hints before sending it off to api.
     for hint in self.m_hints.iter() {
@@ -174,9 +192,12 @@ impl TextureLoader {
         EnumTextureLoaderHint::TextureType(target) => texture_target = target,
         EnumTextureLoaderHint::MaxDimensions(dimensions) => texture_dimensions = dimensions,
         EnumTextureLoaderHint::MaxMipMapLevel(mipmap) => texture_mipmap = mipmap,
-        EnumTextureLoaderHint::TargetFormat(format) => texture_format = format,
+        EnumTextureLoaderHint::TargetFormat(format) => target_format = Some(format),
         EnumTextureLoaderHint::DataEncodedWith(data_type) => texture_data_type = data_type,
         EnumTextureLoaderHint::IsHdr(bool) => texture_hdr = bool,
+        EnumTextureLoaderHint::GenerateMipmaps(bool) => texture_mipmaps = bool,
+        EnumTextureLoaderHint::FilterMode(filter) => texture_filter = filter,
+        EnumTextureLoaderHint::Anisotropy(level) => texture_anisotropy = Some(level),
         _ => {}
       }
     }
@@ -186,7 +207,7 @@ impl TextureLoader {
         log!(EnumLogColor::Red, "ERROR", "[Texture] -->\t Cannot load texture from file {0}, Error => {1}", file_path, message);
         return Err(EnumTextureLoaderError::FileError(message));
       }
-      stb_image::image::LoadResult::ImageU8(data) => {
+      stb_image::image::LoadResult::ImageU8(mut data) => {
         match data.depth {
           1 => texture_format = EnumTextureFormat::Red,
           2 => texture_format = EnumTextureFormat::Rg,
@@ -195,6 +216,16 @@ impl TextureLoader {
           _ => {}
         }
         
+        // Repack the pixels if the caller asked for a different format than what the file holds.
+        if let Some(format) = target_format {
+          if format != texture_format {
+            log!(EnumLogColor::Yellow, "WARN", "[TexLoader] -->\t Converting texture {0} from {1:?} to {2:?}...",
+              file_path, texture_format, format);
+            data = TextureLoader::convert_format(data, texture_format, format);
+            texture_format = format;
+          }
+        }
+        
         // Check if our specified dimensions are correct, if so take them, otherwise use figure out using data.
         if texture_dimensions > (data.width as u32, data.height as u32, data.depth as u32) || texture_dimensions == (0, 0, 0) {
           texture_dimensions = (data.width as u32, data.height as u32, data.depth as u32);
@@ -250,6 +281,48 @@ impl TextureLoader {
     return Ok(TextureInfo {
       m_type: texture_info.0,
       m_data: texture_info.1,
+      m_mipmaps: texture_mipmaps,
+      m_filter: texture_filter,
+      m_anisotropy: texture_anisotropy,
     });
   }
+  
+  /// Reorder, drop or fill in the 8-bit channels of every pixel to go from the `from` format to the `to` format.
+  /// Missing color channels are copied from the red channel for single channel (grayscale) images and left at zero
+  /// otherwise, while a missing alpha channel is fully opaque.
+  fn convert_format(image: stb_image::image::Image<u8>, from: EnumTextureFormat,
+                    to: EnumTextureFormat) -> stb_image::image::Image<u8> {
+    // Indices into an RGBA pixel, in the order each format stores its channels.
+    let layout = |format: EnumTextureFormat| -> &'static [usize] {
+      return match format {
+        EnumTextureFormat::Red => &[0],
+        EnumTextureFormat::Rg => &[0, 1],
+        EnumTextureFormat::Rgb => &[0, 1, 2],
+        EnumTextureFormat::Bgr => &[2, 1, 0],
+        EnumTextureFormat::Rgba => &[0, 1, 2, 3],
+        EnumTextureFormat::Bgra => &[2, 1, 0, 3],
+      };
+    };
+    let (from_layout, to_layout) = (layout(from), layout(to));
+    let mut data: Vec<u8> = Vec::with_capacity(image.width * image.height * to_layout.len());
+    
+    for pixel in image.data.chunks_exact(from_layout.len()) {
+      let mut rgba: [u8; 4] = [0, 0, 0, 255];
+      for (channel, &component) in pixel.iter().zip(from_layout) {
+        rgba[component] = *channel;
+      }
+      if from == EnumTextureFormat::Red {
+        rgba[1] = rgba[0];
+        rgba[2] = rgba[0];
+      }
+      data.extend(to_layout.iter().map(|&component| rgba[component]));
+    }
+    
+    return stb_image::image::Image {
+      width: image.width,
+      height: image.height,
+      depth: to_layout.len(),
+      data,
+    };
+  }
 }
@@ -22,6 +22,7 @@
  SOFTWARE.
 */
 
+use std::ffi::CStr;
 use std::fmt::{Display, Formatter};
 use std::path::PathBuf;
 
@@ -31,15 +32,19 @@ use glfw::Context;
 use crate::utils::macros::logger::*;
 use crate::{Engine, TraitApply, TraitFree, TraitHint};
 use crate::events::{EnumEvent, EnumEventMask};
-use crate::graphics::renderer::EnumRendererApi;
-use crate::input::{self, EnumAction, EnumKey, EnumModifiers, EnumMouseButton};
+use crate::graphics::renderer::{EnumRendererApi, Renderer};
+use crate::input::{self, EnumAction, EnumKey, EnumModifiers, EnumMouseButton, Input};
 use crate::utils::Time;
+use crate::utils::texture_loader::TextureLoader;
 
 pub(crate) static mut S_WINDOW_CONTEXT: Option<glfw::Glfw> = None;
 
 pub(crate) static mut S_PREVIOUS_WIDTH: u32 = 640;
 pub(crate) static mut S_PREVIOUS_HEIGHT: u32 = 480;
 
+/// Aspect ratio assumed while the window has no visible area (i.e. minimized), to keep projections valid.
+pub const C_DEFAULT_ASPECT_RATIO: f32 = 16.0 / 9.0;
+
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum EnumWindowState {
   ContextReady,
@@ -63,6 +68,124 @@ pub enum EnumWindowHint {
   MSAA(Option<u32>),
   DebugApi(bool),
   RefreshRate(Option<u32>),
+  GlVersion(u32, u32),
+  GlProfile(EnumGlProfile),
+  Monitor(usize),
+  RawMouseMotion(bool),
+  /// Smallest size (width, height) the window can be resized to, in screen coordinates.
+  MinSize(u32, u32),
+  /// Largest size (width, height) the window can be resized to, in screen coordinates.
+  MaxSize(u32, u32),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonitorInfo {
+  pub m_index: usize,
+  pub m_name: String,
+  pub m_resolution: (u32, u32),
+  pub m_refresh_rate: u32,
+  pub m_position: (i32, i32),
+}
+
+impl Display for MonitorInfo {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    write!(f, "[{0}] {1} : {2}x{3} @ {4}Hz, at ({5}, {6})", self.m_index, self.m_name, self.m_resolution.0,
+      self.m_resolution.1, self.m_refresh_rate, self.m_position.0, self.m_position.1)
+  }
+}
+
+/// A video mode supported by a monitor, usable in fullscreen.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct VideoMode {
+  pub m_resolution: (u32, u32),
+  pub m_refresh_rate: u32,
+  /// Bits per (red, green, blue) channel.
+  pub m_bit_depth: (u32, u32, u32),
+}
+
+impl From<glfw::VidMode> for VideoMode {
+  fn from(vid_mode: glfw::VidMode) -> Self {
+    return VideoMode {
+      m_resolution: (vid_mode.width, vid_mode.height),
+      m_refresh_rate: vid_mode.refresh_rate,
+      m_bit_depth: (vid_mode.red_bits, vid_mode.green_bits, vid_mode.blue_bits),
+    };
+  }
+}
+
+impl Display for VideoMode {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{0}x{1} @ {2}Hz ({3}/{4}/{5} bits)", self.m_resolution.0, self.m_resolution.1, self.m_refresh_rate,
+      self.m_bit_depth.0, self.m_bit_depth.1, self.m_bit_depth.2)
+  }
+}
+
+/// Buffer swap synchronization with the monitor's refresh rate.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum EnumVSync {
+  Off,
+  On,
+  /// Sync when running faster than the refresh rate, but swap right away when a frame is late instead of waiting for the
+  /// next refresh. Falls back to [EnumVSync::On] when the driver does not support it.
+  Adaptive,
+}
+
+impl Default for EnumVSync {
+  fn default() -> Self {
+    return EnumVSync::On;
+  }
+}
+
+impl Display for EnumVSync {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match self {
+      EnumVSync::Off => { write!(f, "Off") }
+      EnumVSync::On => { write!(f, "On") }
+      EnumVSync::Adaptive => { write!(f, "Adaptive") }
+    }
+  }
+}
+
+/// How the mouse cursor behaves over the window.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum EnumCursorMode {
+  Normal,
+  /// Invisible while over the window, but otherwise behaves normally.
+  Hidden,
+  /// Hidden and locked to the window, for unbounded mouse movement (i.e. FPS mouse-look).
+  Disabled,
+}
+
+impl Default for EnumCursorMode {
+  fn default() -> Self {
+    return EnumCursorMode::Normal;
+  }
+}
+
+impl From<glfw::CursorMode> for EnumCursorMode {
+  fn from(cursor_mode: glfw::CursorMode) -> Self {
+    return match cursor_mode {
+      glfw::CursorMode::Normal => EnumCursorMode::Normal,
+      glfw::CursorMode::Hidden => EnumCursorMode::Hidden,
+      glfw::CursorMode::Disabled => EnumCursorMode::Disabled
+    };
+  }
+}
+
+impl From<EnumCursorMode> for glfw::CursorMode {
+  fn from(cursor_mode: EnumCursorMode) -> Self {
+    return match cursor_mode {
+      EnumCursorMode::Normal => glfw::CursorMode::Normal,
+      EnumCursorMode::Hidden => glfw::CursorMode::Hidden,
+      EnumCursorMode::Disabled => glfw::CursorMode::Disabled
+    };
+  }
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum EnumGlProfile {
+  Core,
+  Compat,
 }
 
 #[derive(Debug, PartialEq, Copy, Clone)]
@@ -70,6 +193,9 @@ pub enum EnumWindowMode {
   Windowed,
   Borderless,
   Fullscreen,
+  /// Invisible window, only kept around for its context. Nothing gets presented, so rendering should be done
+  /// into a [RenderTarget](crate::graphics::RenderTarget) instead.
+  Headless,
 }
 
 impl Default for EnumWindowMode {
@@ -84,6 +210,7 @@ impl Display for EnumWindowMode {
       EnumWindowMode::Windowed => { write!(f, "Windowed") }
       EnumWindowMode::Borderless => { write!(f, "Borderless Window") }
       EnumWindowMode::Fullscreen => { write!(f, "Fullscreen") }
+      EnumWindowMode::Headless => { write!(f, "Headless") }
     }
   }
 }
@@ -100,6 +227,9 @@ pub enum EnumWindowError {
   VulkanIncompatibleError,
   VulkanSurfaceCreationError,
   WindowInputError,
+  UnsupportedContextVersion,
+  UnsupportedVideoMode,
+  MonitorNotFound,
 }
 
 impl Display for EnumWindowError {
@@ -126,12 +256,22 @@ pub struct Window {
   pub(crate) m_state: EnumWindowState,
   pub(crate) m_api_window_events: Option<glfw::GlfwReceiver<(f64, glfw::WindowEvent)>>,
   pub(crate) m_api_window: Option<glfw::PWindow>,
-  pub(crate) m_vsync: bool,
+  pub(crate) m_vsync: EnumVSync,
   pub(crate) m_refresh_count_desired: Option<u32>,
   pub(crate) m_samples: u32,
   pub(crate) m_window_resolution: Option<(u32, u32)>,
   pub(crate) m_window_pos: (i32, i32),
   pub(crate) m_is_windowed: bool,
+  pub(crate) m_gl_version: Option<(u32, u32)>,
+  pub(crate) m_gl_profile: Option<EnumGlProfile>,
+  pub(crate) m_monitor_index: Option<usize>,
+  pub(crate) m_icon_paths: Vec<PathBuf>,
+  pub(crate) m_raw_mouse_motion: bool,
+  // Minimum and maximum size, if any, and the aspect ratio (numerator, denominator) resizing should stick to.
+  pub(crate) m_size_limits: (Option<(u32, u32)>, Option<(u32, u32)>),
+  pub(crate) m_aspect_ratio_lock: Option<(u32, u32)>,
+  // Resolution requested through hints, kept apart from the live resolution which follows framebuffer resizes.
+  pub(crate) m_requested_resolution: Option<(u32, u32)>,
   m_window_mode: EnumWindowMode,
   m_render_api: EnumRendererApi,
 }
@@ -168,12 +308,20 @@ impl Default for Window {
     return Self {
       m_api_window_events: None,
       m_api_window: None,
-      m_vsync: true,
+      m_vsync: EnumVSync::default(),
       m_refresh_count_desired: None,
       m_samples: 1,
       m_window_resolution: None,
       m_window_pos: (0, 0),
       m_is_windowed: true,
+      m_gl_version: None,
+      m_gl_profile: None,
+      m_monitor_index: None,
+      m_icon_paths: Vec::new(),
+      m_raw_mouse_motion: false,
+      m_size_limits: (None, None),
+      m_aspect_ratio_lock: None,
+      m_requested_resolution: None,
       m_window_mode: EnumWindowMode::default(),  // Default to Fullscreen.
       m_render_api: EnumRendererApi::default(),
       m_state: EnumWindowState::ContextReady,
@@ -187,12 +335,17 @@ impl TraitHint<EnumWindowHint> for Window {
       EnumWindowHint::WindowMode(window_mode) => {
         self.m_window_mode = window_mode;
         self.m_is_windowed = window_mode == EnumWindowMode::Windowed;
+        
+        if window_mode == EnumWindowMode::Headless {
+          unsafe { (*S_WINDOW_CONTEXT.as_mut().unwrap()).window_hint(glfw::WindowHint::Visible(false)) };
+        }
       }
       EnumWindowHint::Resolution(x_res, y_res) => {
         self.m_window_resolution = Some((x_res, y_res));
+        self.m_requested_resolution = Some((x_res, y_res));
       }
       EnumWindowHint::Visible(flag) => unsafe {
-        (*S_WINDOW_CONTEXT.as_mut().unwrap()).window_hint(glfw::WindowHint::Visible(flag));
+        (*S_WINDOW_CONTEXT.as_mut().unwrap()).window_hint(glfw::WindowHint::Visible(flag && !self.is_headless()));
       }
       EnumWindowHint::Resizable(flag) => unsafe {
         if self.m_window_mode == EnumWindowMode::Borderless {
@@ -217,7 +370,7 @@ impl TraitHint<EnumWindowHint> for Window {
         (*S_WINDOW_CONTEXT.as_mut().unwrap()).window_hint(glfw::WindowHint::Decorated(flag));
       }
       EnumWindowHint::VSync(flag) => {
-        self.m_vsync = flag;
+        self.m_vsync = flag.then(|| EnumVSync::On).unwrap_or(EnumVSync::Off);
       }
       EnumWindowHint::MSAA(sample_rate_desired) => unsafe {
         (*S_WINDOW_CONTEXT.as_mut().unwrap()).window_hint(glfw::WindowHint::Samples(sample_rate_desired));
@@ -233,6 +386,29 @@ impl TraitHint<EnumWindowHint> for Window {
         (*S_WINDOW_CONTEXT.as_mut().unwrap()).window_hint(glfw::WindowHint::RefreshRate(refresh_count_desired));
         self.m_refresh_count_desired = refresh_count_desired;
       }
+      EnumWindowHint::GlVersion(major, minor) => unsafe {
+        (*S_WINDOW_CONTEXT.as_mut().unwrap()).window_hint(glfw::WindowHint::ContextVersion(major, minor));
+        self.m_gl_version = Some((major, minor));
+      }
+      EnumWindowHint::GlProfile(profile) => unsafe {
+        (*S_WINDOW_CONTEXT.as_mut().unwrap()).window_hint(glfw::WindowHint::OpenGlProfile(match profile {
+          EnumGlProfile::Core => glfw::OpenGlProfileHint::Core,
+          EnumGlProfile::Compat => glfw::OpenGlProfileHint::Compat,
+        }));
+        self.m_gl_profile = Some(profile);
+      }
+      EnumWindowHint::Monitor(monitor_index) => {
+        self.m_monitor_index = Some(monitor_index);
+      }
+      EnumWindowHint::RawMouseMotion(flag) => {
+        self.m_raw_mouse_motion = flag;
+      }
+      EnumWindowHint::MinSize(width, height) => {
+        self.m_size_limits.0 = Some((width, height));
+      }
+      EnumWindowHint::MaxSize(width, height) => {
+        self.m_size_limits.1 = Some((width, height));
+      }
     }
   }
   
@@ -247,33 +423,70 @@ impl TraitHint<EnumWindowHint> for Window {
     context_ref.window_hint(glfw::WindowHint::RefreshRate(None));
     context_ref.window_hint(glfw::WindowHint::ClientApi(glfw::ClientApiHint::OpenGl));
     context_ref.window_hint(glfw::WindowHint::OpenGlDebugContext(false));
+    context_ref.window_hint(glfw::WindowHint::ContextVersion(1, 0));
+    context_ref.window_hint(glfw::WindowHint::OpenGlProfile(glfw::OpenGlProfileHint::Any));
     
-    self.m_vsync = true;
+    self.m_vsync = EnumVSync::default();
     self.m_render_api = EnumRendererApi::default();
     self.m_window_resolution = None;
     self.m_window_mode = EnumWindowMode::default();
     self.m_refresh_count_desired = None;
     self.m_window_pos = (0, 0);
     self.m_is_windowed = false;
+    self.m_gl_version = None;
+    self.m_gl_profile = None;
+    self.m_monitor_index = None;
+    self.m_raw_mouse_motion = false;
+    self.m_size_limits = (None, None);
+    self.m_aspect_ratio_lock = None;
+    self.m_requested_resolution = None;
   }
 }
 
 impl TraitApply<EnumWindowError> for Window {
   fn apply(&mut self) -> Result<(), EnumWindowError> {
+    // Vulkan surfaces must be created without any OpenGL context, and Vulkan might not even be available, in which case
+    // the renderer falls back to OpenGL as well.
+    self.m_render_api = Renderer::probe_api(self.m_render_api);
     unsafe {
-      (*S_WINDOW_CONTEXT.as_mut().unwrap()).with_primary_monitor(|_, monitor| -> Result<(), EnumWindowError> {
-        let primary_monitor = monitor.expect("Cannot apply window context, cannot retrieve primary monitor!");
-        let vid_mode = primary_monitor.get_video_mode()
-          .expect("Cannot apply window context, cannot retrieve video mode of primary monitor!");
+      (*S_WINDOW_CONTEXT.as_mut().unwrap()).window_hint(glfw::WindowHint::ClientApi(match self.m_render_api {
+        EnumRendererApi::OpenGL => glfw::ClientApiHint::OpenGl,
+        EnumRendererApi::Vulkan => glfw::ClientApiHint::NoApi,
+      }));
+    }
+    
+    unsafe {
+      Self::with_monitor(self.m_monitor_index, |monitor| -> Result<(), EnumWindowError> {
+        let (width, height) = match self.m_window_mode {
+          EnumWindowMode::Fullscreen | EnumWindowMode::Borderless => {
+            let Some(vid_mode) = monitor.and_then(|selected_monitor| selected_monitor.get_video_mode()) else {
+              log!(EnumLogColor::Red, "ERROR", "[Window] -->\t Cannot create {0:?} window, no monitor found!",
+                self.m_window_mode);
+              return Err(EnumWindowError::MonitorNotFound);
+            };
+            (vid_mode.width, vid_mode.height)
+          }
+          // Headless windows are never shown and windowed ones start maximized, so neither need a monitor, allowing
+          // them on machines without any (i.e. CI or servers).
+          EnumWindowMode::Windowed | EnumWindowMode::Headless => {
+            self.m_window_resolution.unwrap_or(Self::C_HEADLESS_DEFAULT_RESOLUTION)
+          }
+        };
         
-        match (*S_WINDOW_CONTEXT.as_mut().unwrap()).create_window(vid_mode.width, vid_mode.height,
+        match (*S_WINDOW_CONTEXT.as_mut().unwrap()).create_window(width, height,
           "Wave Engine (Rust)",
           match self.m_window_mode {
-            EnumWindowMode::Fullscreen => glfw::WindowMode::FullScreen(&primary_monitor),
+            EnumWindowMode::Fullscreen => glfw::WindowMode::FullScreen(monitor.unwrap()),
             EnumWindowMode::Windowed => glfw::WindowMode::Windowed,
-            EnumWindowMode::Borderless => glfw::WindowMode::Windowed
+            EnumWindowMode::Borderless => glfw::WindowMode::Windowed,
+            EnumWindowMode::Headless => glfw::WindowMode::Windowed
           }) {
           None => {
+            if self.m_gl_version.is_some() {
+              log!(EnumLogColor::Red, "ERROR", "[Window] -->\t Unable to create GLFW window, requested OpenGL {0:?} \
+              context ({1:?} profile) might not be supported!", self.m_gl_version.unwrap(), self.m_gl_profile);
+              return Err(EnumWindowError::UnsupportedContextVersion);
+            }
             log!(EnumLogColor::Red, "ERROR", "[Window] -->\t Unable to create GLFW window");
             return Err(EnumWindowError::InitError);
           }
@@ -291,38 +504,59 @@ impl TraitApply<EnumWindowError> for Window {
             self.m_state = EnumWindowState::Created;
             self.m_window_pos = window.get_pos();
             self.m_is_windowed = self.m_window_mode != EnumWindowMode::Fullscreen;
+            // On HiDPI monitors the framebuffer is larger than the window's logical size, and we render in pixels.
+            let framebuffer_bounds = window.get_framebuffer_size();
             self.m_api_window = Some(window);
             self.m_api_window_events = Some(events);
-            self.m_window_resolution = Some((bounds.0 as u32, bounds.1 as u32));
+            self.m_window_resolution = Some((framebuffer_bounds.0 as u32, framebuffer_bounds.1 as u32));
           }
         };
         return Ok(());
-      }).map_err(|err| return err)?
+      })?;
     }
+    
+    // Set the window icon if requested.
+    if !self.m_icon_paths.is_empty() {
+      let icon_paths = self.m_icon_paths.clone();
+      self.apply_icon(&icon_paths)?;
+    }
+    
+    if self.m_raw_mouse_motion {
+      self.set_raw_mouse_motion(true);
+    }
+    
+    // Bound resizing if requested.
+    if self.m_size_limits != (None, None) {
+      self.set_size_limits(self.m_size_limits.0, self.m_size_limits.1)?;
+    }
+    if self.m_aspect_ratio_lock.is_some() {
+      self.set_aspect_ratio_lock(self.m_aspect_ratio_lock)?;
+    }
+    
     // Toggle on fullscreen if requested.
-    if self.m_window_mode != EnumWindowMode::Windowed {
-      unsafe {
-        (*S_WINDOW_CONTEXT.as_mut().unwrap()).with_primary_monitor(|_, monitor| {
-          if monitor.is_none() {
-            log!(EnumLogColor::Red, "ERROR", "[Window] -->\t Cannot identify primary monitor!");
-            return;
+    if self.m_window_mode != EnumWindowMode::Windowed && !self.is_headless() {
+      Self::with_monitor(self.m_monitor_index, |monitor| -> Result<(), EnumWindowError> {
+        if monitor.is_none() {
+          log!(EnumLogColor::Red, "ERROR", "[Window] -->\t Cannot identify selected monitor!");
+          return Err(EnumWindowError::MonitorNotFound);
+        }
+        
+        let mode: VideoMode = self.select_video_mode(monitor.unwrap())?;
+        let (monitor_x, monitor_y) = monitor.as_ref().unwrap().get_pos();
+        
+        match self.m_window_mode {
+          EnumWindowMode::Windowed | EnumWindowMode::Headless => {}
+          EnumWindowMode::Borderless => {
+            self.m_api_window.as_mut().unwrap().set_monitor(glfw::WindowMode::Windowed,
+              monitor_x, monitor_y, mode.m_resolution.0, mode.m_resolution.1, Some(mode.m_refresh_rate));
           }
-          
-          let mode: glfw::VidMode = monitor.as_ref().unwrap().get_video_mode().unwrap();
-          
-          match self.m_window_mode {
-            EnumWindowMode::Windowed => {}
-            EnumWindowMode::Borderless => {
-              self.m_api_window.as_mut().unwrap().set_monitor(glfw::WindowMode::Windowed,
-                self.m_window_pos.0, self.m_window_pos.1, mode.width, mode.height, self.m_refresh_count_desired);
-            }
-            EnumWindowMode::Fullscreen => {
-              self.m_api_window.as_mut().unwrap().set_monitor(glfw::WindowMode::FullScreen(monitor.unwrap()),
-                self.m_window_pos.0, self.m_window_pos.1, mode.width, mode.height, Some(mode.refresh_rate));
-            }
+          EnumWindowMode::Fullscreen => {
+            self.m_api_window.as_mut().unwrap().set_monitor(glfw::WindowMode::FullScreen(monitor.unwrap()),
+              monitor_x, monitor_y, mode.m_resolution.0, mode.m_resolution.1, Some(mode.m_refresh_rate));
           }
-        });
-      }
+        }
+        return Ok(());
+      })?;
     }
     return Ok(());
   }
@@ -340,6 +574,10 @@ impl TraitFree<EnumWindowError> for Window {
 }
 
 impl<'a> Window {
+  /// Size of the framebuffer for headless windows (and of windowed ones before being maximized), when no resolution
+  /// was hinted.
+  pub const C_HEADLESS_DEFAULT_RESOLUTION: (u32, u32) = (1280, 720);
+  
   pub fn new(context_api_chosen: EnumRendererApi) -> Self {
     let result = glfw::init(glfw::fail_on_errors);
     
@@ -368,12 +606,20 @@ impl<'a> Window {
     return Self {
       m_api_window_events: None,
       m_api_window: None,
-      m_vsync: true,
+      m_vsync: EnumVSync::default(),
       m_refresh_count_desired: None,
       m_samples: 1,
       m_window_resolution: None,
       m_window_pos: (0, 0),
       m_is_windowed: true,
+      m_gl_version: None,
+      m_gl_profile: None,
+      m_monitor_index: None,
+      m_icon_paths: Vec::new(),
+      m_raw_mouse_motion: false,
+      m_size_limits: (None, None),
+      m_aspect_ratio_lock: None,
+      m_requested_resolution: None,
       m_window_mode: EnumWindowMode::default(),
       m_render_api: context_api_chosen,
       m_state: EnumWindowState::ContextReady,
@@ -384,7 +630,40 @@ impl<'a> Window {
     return self.m_api_window.is_some();
   }
   
+  pub fn new_headless(context_api_chosen: EnumRendererApi) -> Self {
+    let mut window = Self::new(context_api_chosen);
+    window.set_hint(EnumWindowHint::WindowMode(EnumWindowMode::Headless));
+    return window;
+  }
+  
+  /// Destroy the window and create it again for `api`, since the client api of a window (an OpenGL context, or none
+  /// for Vulkan) cannot change once created. Hints, position and resolution are kept, and the new window is shown if
+  /// the previous one was visible. Polling must be enabled again afterward.
+  pub(crate) fn recreate(&mut self, api: EnumRendererApi) -> Result<(), EnumWindowError> {
+    let was_visible = self.m_state == EnumWindowState::Visible;
+    
+    // Dropping the glfw window destroys it, along with its OpenGL context if any.
+    self.m_api_window_events = None;
+    self.m_api_window = None;
+    self.m_render_api = api;
+    self.m_state = EnumWindowState::ContextReady;
+    self.apply()?;
+    
+    if was_visible {
+      self.show();
+    }
+    return Ok(());
+  }
+  
+  pub fn is_headless(&self) -> bool {
+    return self.m_window_mode == EnumWindowMode::Headless;
+  }
+  
   pub fn show(&mut self) {
+    if self.is_headless() {
+      log!(EnumLogColor::Yellow, "WARN", "[Window] -->\t Cannot show a headless window! Ignoring...");
+      return;
+    }
     self.m_api_window.as_mut().unwrap().show();
     self.m_state = EnumWindowState::Visible;
   }
@@ -399,9 +678,28 @@ impl<'a> Window {
     self.m_api_window.as_mut().unwrap().make_current();
     
     // Set v-sync.
-    self.m_api_window.as_mut().unwrap().glfw.set_swap_interval(glfw::SwapInterval::Sync(self.m_vsync
-      .then(|| { return 1; })
-      .unwrap_or(0)));
+    self.m_vsync = self.apply_swap_interval(self.m_vsync);
+  }
+  
+  // Set the swap interval of the current OpenGL context, returning the mode actually applied.
+  fn apply_swap_interval(&mut self, vsync: EnumVSync) -> EnumVSync {
+    let glfw = &mut self.m_api_window.as_mut().unwrap().glfw;
+    
+    let applied_vsync = match vsync {
+      EnumVSync::Adaptive if !glfw.extension_supported("WGL_EXT_swap_control_tear") &&
+        !glfw.extension_supported("GLX_EXT_swap_control_tear") => {
+        log!(EnumLogColor::Yellow, "WARN", "[Window] -->\t Adaptive VSync not supported, falling back to regular VSync...");
+        EnumVSync::On
+      }
+      _ => vsync
+    };
+    
+    glfw.set_swap_interval(match applied_vsync {
+      EnumVSync::Off => glfw::SwapInterval::None,
+      EnumVSync::On => glfw::SwapInterval::Sync(1),
+      EnumVSync::Adaptive => glfw::SwapInterval::Adaptive
+    });
+    return applied_vsync;
   }
   
   #[cfg(feature = "vulkan")]
@@ -433,7 +731,10 @@ impl<'a> Window {
             true
           }
           (EnumKey::Enter, EnumAction::Pressed, &EnumModifiers::Alt) => {
-            self.toggle_fullscreen();
+            if let Err(_err) = self.toggle_fullscreen() {
+              log!(EnumLogColor::Yellow, "WARN", "[Window] -->\t Cannot toggle fullscreen : {0:?}, staying in current \
+              mode...", _err);
+            }
             true
           }
           (EnumKey::V, EnumAction::Pressed, &EnumModifiers::Alt) => {
@@ -452,6 +753,10 @@ impl<'a> Window {
         self.m_window_resolution = Some((*width, *height));
         true
       }
+      EnumEvent::ContentScaleEvent(_x_scale, _y_scale) => {
+        log!(EnumLogColor::Blue, "EVENT", "[Window] -->\t Content scale changed to: ({0}, {1})", _x_scale, _y_scale);
+        true
+      }
       EnumEvent::WindowCloseEvent(_time) => {
         match self.free() {
           Err(_err) => {
@@ -479,12 +784,14 @@ impl<'a> Window {
       self.m_api_window.as_mut().unwrap().set_maximize_polling(true);
       self.m_api_window.as_mut().unwrap().set_focus_polling(true);
       self.m_api_window.as_mut().unwrap().set_framebuffer_size_polling(true);
+      self.m_api_window.as_mut().unwrap().set_content_scale_polling(true);
       self.m_api_window.as_mut().unwrap().set_pos_polling(true);
     }
     if event_mask.contains(EnumEventMask::Input) {
       self.m_api_window.as_mut().unwrap().set_key_polling(true);
       self.m_api_window.as_mut().unwrap().set_mouse_button_polling(true);
       self.m_api_window.as_mut().unwrap().set_scroll_polling(true);
+      self.m_api_window.as_mut().unwrap().set_char_polling(true);
       self.m_api_window.as_mut().unwrap().set_drag_and_drop_polling(true);
     }
     if event_mask.contains(EnumEventMask::WindowClose) {
@@ -501,6 +808,7 @@ impl<'a> Window {
     }
     if event_mask.contains(EnumEventMask::WindowSize) {
       self.m_api_window.as_mut().unwrap().set_framebuffer_size_polling(true);
+      self.m_api_window.as_mut().unwrap().set_content_scale_polling(true);
     }
     if event_mask.contains(EnumEventMask::WindowPos) {
       self.m_api_window.as_mut().unwrap().set_pos_polling(true);
@@ -518,6 +826,9 @@ impl<'a> Window {
     if event_mask.contains(EnumEventMask::MouseScroll) {
       self.m_api_window.as_mut().unwrap().set_scroll_polling(true);
     }
+    if event_mask.contains(EnumEventMask::Char) {
+      self.m_api_window.as_mut().unwrap().set_char_polling(true);
+    }
     if event_mask.contains(EnumEventMask::DragAndDrop) {
       self.m_api_window.as_mut().unwrap().set_drag_and_drop_polling(true);
     }
@@ -535,6 +846,8 @@ impl<'a> Window {
       self.m_api_window.as_mut().unwrap().set_focus_polling(false);
       self.m_api_window.as_mut().unwrap().unset_framebuffer_size_callback();
       self.m_api_window.as_mut().unwrap().set_framebuffer_size_polling(false);
+      self.m_api_window.as_mut().unwrap().unset_content_scale_callback();
+      self.m_api_window.as_mut().unwrap().set_content_scale_polling(false);
       self.m_api_window.as_mut().unwrap().unset_pos_callback();
       self.m_api_window.as_mut().unwrap().set_pos_polling(false);
     }
@@ -545,6 +858,8 @@ impl<'a> Window {
       self.m_api_window.as_mut().unwrap().set_mouse_button_polling(false);
       self.m_api_window.as_mut().unwrap().unset_scroll_callback();
       self.m_api_window.as_mut().unwrap().set_scroll_polling(false);
+      self.m_api_window.as_mut().unwrap().unset_char_callback();
+      self.m_api_window.as_mut().unwrap().set_char_polling(false);
       self.m_api_window.as_mut().unwrap().unset_drag_and_drop_callback();
       self.m_api_window.as_mut().unwrap().set_drag_and_drop_polling(false);
     }
@@ -567,6 +882,8 @@ impl<'a> Window {
     if event_mask.contains(EnumEventMask::WindowSize) {
       self.m_api_window.as_mut().unwrap().unset_framebuffer_size_callback();
       self.m_api_window.as_mut().unwrap().set_framebuffer_size_polling(false);
+      self.m_api_window.as_mut().unwrap().unset_content_scale_callback();
+      self.m_api_window.as_mut().unwrap().set_content_scale_polling(false);
     }
     if event_mask.contains(EnumEventMask::WindowPos) {
       self.m_api_window.as_mut().unwrap().unset_pos_callback();
@@ -590,10 +907,18 @@ impl<'a> Window {
       self.m_api_window.as_mut().unwrap().unset_scroll_callback();
       self.m_api_window.as_mut().unwrap().set_scroll_polling(false);
     }
+    if event_mask.contains(EnumEventMask::Char) {
+      self.m_api_window.as_mut().unwrap().unset_char_callback();
+      self.m_api_window.as_mut().unwrap().set_char_polling(false);
+    }
     if event_mask.contains(EnumEventMask::DragAndDrop) {
       self.m_api_window.as_mut().unwrap().unset_drag_and_drop_callback();
       self.m_api_window.as_mut().unwrap().set_drag_and_drop_polling(false);
     }
+    // Joysticks can only be listened to through a callback.
+    if event_mask.contains(EnumEventMask::Gamepad) {
+      self.get_api_mut().unset_joystick_callback();
+    }
   }
   
   pub fn enable_callback_for(&mut self, event_mask: EnumEventMask) {
@@ -603,12 +928,14 @@ impl<'a> Window {
       self.m_api_window.as_mut().unwrap().set_maximize_callback(Self::window_maximize_callback);
       self.m_api_window.as_mut().unwrap().set_focus_callback(Self::window_focus_callback);
       self.m_api_window.as_mut().unwrap().set_framebuffer_size_callback(Self::window_size_callback);
+      self.m_api_window.as_mut().unwrap().set_content_scale_callback(Self::window_content_scale_callback);
       self.m_api_window.as_mut().unwrap().set_pos_callback(Self::window_pos_callback);
     }
     if event_mask.contains(EnumEventMask::Input) {
       self.m_api_window.as_mut().unwrap().set_key_callback(Self::key_callback);
       self.m_api_window.as_mut().unwrap().set_mouse_button_callback(Self::mouse_btn_callback);
       self.m_api_window.as_mut().unwrap().set_scroll_callback(Self::scroll_callback);
+      self.m_api_window.as_mut().unwrap().set_char_callback(Self::char_callback);
       self.m_api_window.as_mut().unwrap().set_drag_and_drop_callback(Self::drag_and_drop_callback);
     }
     if event_mask.contains(EnumEventMask::WindowClose) {
@@ -625,6 +952,7 @@ impl<'a> Window {
     }
     if event_mask.contains(EnumEventMask::WindowSize) {
       self.m_api_window.as_mut().unwrap().set_framebuffer_size_callback(Self::window_size_callback);
+      self.m_api_window.as_mut().unwrap().set_content_scale_callback(Self::window_content_scale_callback);
     }
     if event_mask.contains(EnumEventMask::WindowPos) {
       self.m_api_window.as_mut().unwrap().set_pos_callback(Self::window_pos_callback);
@@ -642,9 +970,15 @@ impl<'a> Window {
     if event_mask.contains(EnumEventMask::MouseScroll) {
       self.m_api_window.as_mut().unwrap().set_scroll_callback(Self::scroll_callback);
     }
+    if event_mask.contains(EnumEventMask::Char) {
+      self.m_api_window.as_mut().unwrap().set_char_callback(Self::char_callback);
+    }
     if event_mask.contains(EnumEventMask::DragAndDrop) {
       self.m_api_window.as_mut().unwrap().set_drag_and_drop_callback(Self::drag_and_drop_callback);
     }
+    if event_mask.contains(EnumEventMask::Gamepad) {
+      self.get_api_mut().set_joystick_callback(Self::joystick_callback);
+    }
   }
   
   pub fn disable_callback_for(&mut self, event_mask: EnumEventMask) {
@@ -654,12 +988,14 @@ impl<'a> Window {
       self.m_api_window.as_mut().unwrap().unset_maximize_callback();
       self.m_api_window.as_mut().unwrap().unset_focus_callback();
       self.m_api_window.as_mut().unwrap().unset_framebuffer_size_callback();
+      self.m_api_window.as_mut().unwrap().unset_content_scale_callback();
       self.m_api_window.as_mut().unwrap().unset_pos_callback();
     }
     if event_mask.contains(EnumEventMask::Input) {
       self.m_api_window.as_mut().unwrap().unset_key_callback();
       self.m_api_window.as_mut().unwrap().unset_mouse_button_callback();
       self.m_api_window.as_mut().unwrap().unset_scroll_callback();
+      self.m_api_window.as_mut().unwrap().unset_char_callback();
       self.m_api_window.as_mut().unwrap().unset_drag_and_drop_callback();
     }
     if event_mask.contains(EnumEventMask::WindowClose) {
@@ -676,6 +1012,7 @@ impl<'a> Window {
     }
     if event_mask.contains(EnumEventMask::WindowSize) {
       self.m_api_window.as_mut().unwrap().unset_framebuffer_size_callback();
+      self.m_api_window.as_mut().unwrap().unset_content_scale_callback();
     }
     if event_mask.contains(EnumEventMask::WindowPos) {
       self.m_api_window.as_mut().unwrap().unset_pos_callback();
@@ -693,12 +1030,22 @@ impl<'a> Window {
     if event_mask.contains(EnumEventMask::MouseScroll) {
       self.m_api_window.as_mut().unwrap().unset_scroll_callback();
     }
+    if event_mask.contains(EnumEventMask::Char) {
+      self.m_api_window.as_mut().unwrap().unset_char_callback();
+    }
     if event_mask.contains(EnumEventMask::DragAndDrop) {
       self.m_api_window.as_mut().unwrap().unset_drag_and_drop_callback();
     }
+    if event_mask.contains(EnumEventMask::Gamepad) {
+      self.get_api_mut().unset_joystick_callback();
+    }
   }
   
   pub fn refresh(&mut self) {
+    // No swapchain to present to.
+    if self.is_headless() {
+      return;
+    }
     if self.m_render_api == EnumRendererApi::OpenGL {
       self.m_api_window.as_mut().unwrap().swap_buffers();
     }
@@ -718,7 +1065,99 @@ impl<'a> Window {
   }
   
   pub fn get_aspect_ratio(&self) -> f32 {
-    return self.m_window_resolution.unwrap().0 as f32 / self.m_window_resolution.unwrap().1 as f32;
+    if let Some(window) = self.m_api_window.as_ref() {
+      let (width, height) = window.get_framebuffer_size();
+      if width > 0 && height > 0 {
+        return width as f32 / height as f32;
+      }
+    }
+    return self.m_window_resolution
+      .map(|(width, height)| Window::compute_aspect_ratio(width, height))
+      .unwrap_or(C_DEFAULT_ASPECT_RATIO);
+  }
+  
+  /// Width over height, falling back to [C_DEFAULT_ASPECT_RATIO] for empty sizes, which windows report while minimized.
+  pub fn compute_aspect_ratio(width: u32, height: u32) -> f32 {
+    if width == 0 || height == 0 {
+      return C_DEFAULT_ASPECT_RATIO;
+    }
+    return width as f32 / height as f32;
+  }
+  
+  /// Bound the size (width, height) the window can be resized to, in screen coordinates, `None` leaving that side
+  /// unbounded. The minimum is at least 1x1. Can be set before the window is applied.
+  pub fn set_size_limits(&mut self, min_size: Option<(u32, u32)>, max_size: Option<(u32, u32)>) -> Result<(), EnumWindowError> {
+    let min_size: Option<(u32, u32)> = min_size.map(|(width, height)| (width.max(1), height.max(1)));
+    
+    if let (Some(min), Some(max)) = (min_size, max_size) {
+      if min.0 > max.0 || min.1 > max.1 {
+        log!(EnumLogColor::Red, "ERROR", "[Window] -->\t Cannot limit window size, minimum {0:?} exceeds maximum {1:?}!",
+          min, max);
+        return Err(EnumWindowError::InvalidWindowOption);
+      }
+    }
+    self.m_size_limits = (min_size, max_size);
+    
+    if let Some(api_window) = self.m_api_window.as_mut() {
+      api_window.set_size_limits(min_size.map(|size| size.0), min_size.map(|size| size.1),
+        max_size.map(|size| size.0), max_size.map(|size| size.1));
+      log!(EnumLogColor::Blue, "EVENT", "[Window] -->\t Size limits : {0:?} to {1:?}", min_size, max_size);
+    }
+    return Ok(());
+  }
+  
+  pub fn get_size_limits(&self) -> (Option<(u32, u32)>, Option<(u32, u32)>) {
+    return self.m_size_limits;
+  }
+  
+  /// Keep the window's aspect ratio at `numerator`:`denominator` while the user resizes it, or let it resize freely
+  /// with `None`. Can be set before the window is applied.
+  pub fn set_aspect_ratio_lock(&mut self, ratio: Option<(u32, u32)>) -> Result<(), EnumWindowError> {
+    if ratio.is_some_and(|(numerator, denominator)| numerator == 0 || denominator == 0) {
+      log!(EnumLogColor::Red, "ERROR", "[Window] -->\t Cannot lock aspect ratio to {0:?}, both terms must be positive!",
+        ratio);
+      return Err(EnumWindowError::InvalidWindowOption);
+    }
+    self.m_aspect_ratio_lock = ratio;
+    
+    if let Some(api_window) = self.m_api_window.as_mut() {
+      match ratio {
+        Some((numerator, denominator)) => api_window.set_aspect_ratio(numerator, denominator),
+        None => api_window.set_aspect_ratio(glfw::ffi::DONT_CARE as u32, glfw::ffi::DONT_CARE as u32),
+      }
+      log!(EnumLogColor::Blue, "EVENT", "[Window] -->\t Aspect ratio lock : {0:?}", ratio);
+    }
+    return Ok(());
+  }
+  
+  pub fn get_aspect_ratio_lock(&self) -> Option<(u32, u32)> {
+    return self.m_aspect_ratio_lock;
+  }
+  
+  /// Get the DPI scale the system applies to the window's content, for each axis. This is (1.0, 1.0) at 96 DPI, and
+  /// typically (2.0, 2.0) on HiDPI monitors or when the user raises the system's scale setting.
+  ///
+  /// This is not the ratio between the framebuffer and the window size, which may stay at 1.0 on platforms that don't
+  /// scale the framebuffer themselves (use [Window::get_framebuffer_size] for pixel work). Use it to size UI elements
+  /// and fonts so they stay readable, and listen for [EnumEvent::ContentScaleEvent] to know when it changes.
+  pub fn get_content_scale(&self) -> (f32, f32) {
+    return match self.m_api_window.as_ref() {
+      Some(window) => window.get_content_scale(),
+      None => (1.0, 1.0)
+    };
+  }
+  
+  pub fn get_gl_version(&self) -> Option<(u32, u32)> {
+    return self.m_gl_version;
+  }
+  
+  pub fn get_gl_profile(&self) -> Option<EnumGlProfile> {
+    return self.m_gl_profile;
+  }
+  
+  pub fn get_context_version(&self) -> Option<(u32, u32)> {
+    return self.m_api_window.as_ref()
+      .map(|window| (window.get_context_version().major as u32, window.get_context_version().minor as u32));
   }
   
   pub fn get_state(&self) -> EnumWindowState {
@@ -738,12 +1177,41 @@ impl<'a> Window {
   }
   
   pub fn toggle_vsync(&mut self) {
-    self.m_vsync = !self.m_vsync;
-    self.m_api_window.as_mut().unwrap().glfw.set_swap_interval(glfw::SwapInterval::Sync(self.m_vsync as u32));
-    log!(EnumLogColor::Blue, "EVENT", "[Window] -->\t VSync {0}", self.m_vsync);
+    self.set_vsync(self.is_vsync_enabled().then(|| EnumVSync::Off).unwrap_or(EnumVSync::On));
+  }
+  
+  /// Change the swap interval at runtime, without recreating the window. Once applied, layers polling window events get
+  /// an [EnumEvent::VSyncEvent] with the mode in effect whenever it changes. Only affects OpenGL contexts, Vulkan picks
+  /// its present mode when creating its swap chain.
+  pub fn set_vsync(&mut self, vsync: EnumVSync) {
+    if self.m_api_window.is_none() || self.m_render_api != EnumRendererApi::OpenGL {
+      self.m_vsync = vsync;
+      return;
+    }
+    
+    let applied_vsync = self.apply_swap_interval(vsync);
+    if applied_vsync != self.m_vsync {
+      self.m_vsync = applied_vsync;
+      log!(EnumLogColor::Blue, "EVENT", "[Window] -->\t VSync {0}", self.m_vsync);
+      Engine::on_async_event(EnumEvent::VSyncEvent(self.m_vsync));
+    }
+  }
+  
+  pub fn get_vsync(&self) -> EnumVSync {
+    return self.m_vsync;
+  }
+  
+  pub fn is_vsync_enabled(&self) -> bool {
+    return self.m_vsync != EnumVSync::Off;
   }
   
-  pub fn toggle_fullscreen(&mut self) {
+  /// Switch between windowed mode and the window's fullscreen mode. When a resolution was hinted, fullscreen uses
+  /// the monitor's video mode matching it, failing with [EnumWindowError::UnsupportedVideoMode] if there is none.
+  pub fn toggle_fullscreen(&mut self) -> Result<(), EnumWindowError> {
+    if self.is_headless() {
+      log!(EnumLogColor::Yellow, "WARN", "[Window] -->\t Cannot toggle fullscreen on a headless window! Ignoring...");
+      return Ok(());
+    }
     unsafe {
       if S_WINDOW_CONTEXT.is_none() {
         log!(EnumLogColor::Red, "ERROR", "[Window] -->\t Cannot toggle fullscreen : \
@@ -751,8 +1219,8 @@ impl<'a> Window {
         panic!("[Window] -->\t Cannot toggle fullscreen : No active window context!");
       };
       
-      (*S_WINDOW_CONTEXT.as_mut().unwrap()).with_primary_monitor(|_, monitor| {
-        let mode: glfw::VidMode = monitor.as_ref().unwrap().get_video_mode().unwrap();
+      return Self::with_monitor(self.m_monitor_index, |monitor| -> Result<(), EnumWindowError> {
+        let (monitor_x, monitor_y) = monitor.as_ref().unwrap().get_pos();
         
         if !self.m_is_windowed {
           self.m_api_window.as_mut().unwrap().set_resizable(true);
@@ -768,75 +1236,338 @@ impl<'a> Window {
           }
           log!(EnumLogColor::Blue, "EVENT", "[Window] -->\t Window mode : Windowed");
         } else {
+          let mode: VideoMode = self.select_video_mode(monitor.unwrap())?;
+          
           match self.m_window_mode {
             EnumWindowMode::Borderless => {
               self.m_api_window.as_mut().unwrap().set_decorated(false);
-              self.m_api_window.as_mut().unwrap().set_size(mode.width as i32, mode.height as i32);
+              self.m_api_window.as_mut().unwrap().set_pos(monitor_x, monitor_y);
+              self.m_api_window.as_mut().unwrap().set_size(mode.m_resolution.0 as i32, mode.m_resolution.1 as i32);
               log!(EnumLogColor::Blue, "EVENT", "[Window] -->\t Window mode : Borderless");
             }
             _ => {
               self.m_api_window.as_mut().unwrap().set_resizable(false);
               self.m_api_window.as_mut().unwrap().set_monitor(glfw::WindowMode::FullScreen(monitor.unwrap()),
-                self.m_window_pos.0, self.m_window_pos.1, mode.width, mode.height,
-                Some(mode.refresh_rate));
-              log!(EnumLogColor::Blue, "EVENT", "[Window] -->\t Window mode : Fullscreen");
+                monitor_x, monitor_y, mode.m_resolution.0, mode.m_resolution.1, Some(mode.m_refresh_rate));
+              log!(EnumLogColor::Blue, "EVENT", "[Window] -->\t Window mode : Fullscreen ({0})", mode);
             }
           }
         }
         self.m_is_windowed = !self.m_is_windowed;
+        return Ok(());
       });
     }
   }
   
-  pub fn get_framebuffer_size(&mut self) -> (u32, u32) {
-    if self.m_window_mode != EnumWindowMode::Windowed {
-      return self.m_api_window.as_mut().unwrap().glfw.with_primary_monitor(|_, primary_monitor| {
-        let mode: glfw::VidMode = primary_monitor.as_ref().unwrap().get_video_mode().unwrap();
-        return (mode.width, mode.height);
+  pub fn get_monitors() -> Vec<MonitorInfo> {
+    if unsafe { S_WINDOW_CONTEXT.is_none() } {
+      log!(EnumLogColor::Red, "ERROR", "[Window] -->\t Cannot enumerate monitors : No active window context!");
+      return Vec::new();
+    }
+    
+    return unsafe {
+      (*S_WINDOW_CONTEXT.as_mut().unwrap()).with_connected_monitors(|_, monitors| {
+        monitors.iter().enumerate()
+          .map(|(index, monitor)| {
+            let vid_mode = monitor.get_video_mode();
+            MonitorInfo {
+              m_index: index,
+              m_name: monitor.get_name().unwrap_or(String::from("Unknown")),
+              m_resolution: vid_mode.map(|mode| (mode.width, mode.height)).unwrap_or((0, 0)),
+              m_refresh_rate: vid_mode.map(|mode| mode.refresh_rate).unwrap_or(0),
+              m_position: monitor.get_pos(),
+            }
+          })
+          .collect()
+      })
+    };
+  }
+  
+  /// List the video modes supported by the monitor at `monitor_index`, sorted by resolution then refresh rate.
+  /// Falls back to the primary monitor if there is no monitor at that index.
+  pub fn get_video_modes(monitor_index: usize) -> Vec<VideoMode> {
+    if unsafe { S_WINDOW_CONTEXT.is_none() } {
+      log!(EnumLogColor::Red, "ERROR", "[Window] -->\t Cannot enumerate video modes : No active window context!");
+      return Vec::new();
+    }
+    
+    return Self::with_monitor(Some(monitor_index), |monitor| {
+      monitor.map(|monitor| monitor.get_video_modes().into_iter().map(VideoMode::from).collect())
+        .unwrap_or_default()
+    });
+  }
+  
+  // Pick the fullscreen video mode : the monitor's current one unless a resolution was hinted, in which case the mode
+  // with that exact resolution and the refresh rate closest to the desired one (or the current one) is chosen.
+  fn select_video_mode(&self, monitor: &glfw::Monitor) -> Result<VideoMode, EnumWindowError> {
+    let current_mode: Option<VideoMode> = monitor.get_video_mode().map(VideoMode::from);
+    
+    let requested_resolution = match self.m_requested_resolution {
+      Some(resolution) => resolution,
+      None => return current_mode.ok_or(EnumWindowError::ApiError),
+    };
+    let desired_refresh_rate: u32 = self.m_refresh_count_desired
+      .or(current_mode.map(|mode| mode.m_refresh_rate))
+      .unwrap_or(u32::MAX);
+    
+    return monitor.get_video_modes().into_iter()
+      .map(VideoMode::from)
+      .filter(|mode| mode.m_resolution == requested_resolution)
+      .min_by_key(|mode| mode.m_refresh_rate.abs_diff(desired_refresh_rate))
+      .ok_or_else(|| {
+        log!(EnumLogColor::Red, "ERROR", "[Window] -->\t Requested resolution {0}x{1} is not supported by monitor \
+        '{2}'!", requested_resolution.0, requested_resolution.1, monitor.get_name().unwrap_or(String::from("Unknown")));
+        EnumWindowError::UnsupportedVideoMode
       });
+  }
+  
+  /// Retrieve the current clipboard content, if any. Returns `None` if the window has not been created yet, or if
+  /// the clipboard is empty or does not contain valid UTF-8 text.
+  pub fn get_clipboard_string(&self) -> Option<String> {
+    let api_window = self.m_api_window.as_ref()?;
+    let char_ptr = unsafe { glfw::ffi::glfwGetClipboardString(api_window.window_ptr()) };
+    
+    if char_ptr.is_null() {
+      return None;
+    }
+    
+    let c_str = unsafe { CStr::from_ptr(char_ptr) };
+    return c_str.to_str().ok()
+      .filter(|text| !text.is_empty())
+      .map(String::from);
+  }
+  
+  pub fn set_clipboard_string(&mut self, text: &str) {
+    match self.m_api_window.as_mut() {
+      Some(api_window) => api_window.set_clipboard_string(text),
+      None => {
+        log!(EnumLogColor::Yellow, "WARN", "[Window] -->\t Cannot set clipboard string, window has not been created!");
+      }
+    }
+  }
+  
+  pub fn get_monitor_index(&self) -> Option<usize> {
+    return self.m_monitor_index;
+  }
+  
+  /// Set the window icon from one or more image files. Providing multiple sizes (i.e. 16x16, 32x32, 48x48) lets
+  /// the platform pick the one best suited for the current DPI. If the window has not been applied yet, the icon
+  /// is deferred until [`apply`](TraitApply::apply) is called.
+  pub fn set_icon(&mut self, image_paths: &[&str]) -> Result<(), EnumWindowError> {
+    let icon_paths: Vec<PathBuf> = image_paths.iter().map(PathBuf::from).collect();
+    
+    if self.m_api_window.is_none() {
+      self.m_icon_paths = icon_paths;
+      return Ok(());
+    }
+    
+    self.apply_icon(&icon_paths)?;
+    self.m_icon_paths = icon_paths;
+    return Ok(());
+  }
+  
+  fn apply_icon(&mut self, icon_paths: &[PathBuf]) -> Result<(), EnumWindowError> {
+    let texture_loader = TextureLoader::new();
+    let mut icon_images: Vec<glfw::PixelImage> = Vec::with_capacity(icon_paths.len());
+    
+    for icon_path in icon_paths.iter() {
+      icon_images.push(Self::load_pixel_image(&texture_loader, icon_path.to_str().unwrap_or_default())?);
+    }
+    
+    let api_window = self.m_api_window.as_mut().ok_or_else(|| {
+      log!(EnumLogColor::Red, "ERROR", "[Window] -->\t Cannot set window icon, window has not been created!");
+      return EnumWindowError::ApiError;
+    })?;
+    
+    // Largest first, GLFW will pick the closest match to the platform's desired icon size.
+    icon_images.sort_by(|lhs, rhs| (rhs.width * rhs.height).cmp(&(lhs.width * lhs.height)));
+    api_window.set_icon_from_pixels(icon_images);
+    
+    log!(EnumLogColor::Green, "INFO", "[Window] -->\t Window icon set from {0} image(s)", icon_paths.len());
+    return Ok(());
+  }
+  
+  // Decode an image file into RGBA8, in the byte order GLFW expects for icons and cursors.
+  fn load_pixel_image(texture_loader: &TextureLoader, image_path: &str) -> Result<glfw::PixelImage, EnumWindowError> {
+    let texture_info = texture_loader.load(image_path).map_err(|_err| {
+      log!(EnumLogColor::Red, "ERROR", "[Window] -->\t Cannot decode image {0}, Error => {1:?}", image_path, _err);
+      return EnumWindowError::ApiError;
+    })?;
+    
+    let image = &texture_info.m_data;
+    if image.width == 0 || image.height == 0 || image.depth == 0 {
+      log!(EnumLogColor::Red, "ERROR", "[Window] -->\t Cannot decode image {0}, image is empty!", image_path);
+      return Err(EnumWindowError::ApiError);
+    }
+    
+    let pixels: Vec<u32> = image.data.chunks_exact(image.depth)
+      .map(|texel| {
+        let rgba: [u8; 4] = match image.depth {
+          1 => [texel[0], texel[0], texel[0], 255],
+          2 => [texel[0], texel[0], texel[0], texel[1]],
+          3 => [texel[0], texel[1], texel[2], 255],
+          _ => [texel[0], texel[1], texel[2], texel[3]],
+        };
+        return u32::from_ne_bytes(rgba);
+      })
+      .collect();
+    
+    return Ok(glfw::PixelImage { width: image.width as u32, height: image.height as u32, pixels });
+  }
+  
+  pub fn get_cursor_mode(&self) -> Result<EnumCursorMode, EnumWindowError> {
+    let api_window = self.m_api_window.as_ref().ok_or_else(|| {
+      log!(EnumLogColor::Red, "ERROR", "[Window] -->\t Cannot get cursor mode, window has not been created!");
+      return EnumWindowError::NoContext;
+    })?;
+    return Ok(EnumCursorMode::from(api_window.get_cursor_mode()));
+  }
+  
+  /// Change how the cursor behaves over the window. For mouse-look, pair [EnumCursorMode::Disabled] with
+  /// [Self::set_raw_mouse_motion].
+  pub fn set_cursor_mode(&mut self, cursor_mode: EnumCursorMode) -> Result<(), EnumWindowError> {
+    let api_window = self.m_api_window.as_mut().ok_or_else(|| {
+      log!(EnumLogColor::Red, "ERROR", "[Window] -->\t Cannot set cursor mode, window has not been created!");
+      return EnumWindowError::NoContext;
+    })?;
+    
+    api_window.set_cursor_mode(glfw::CursorMode::from(cursor_mode));
+    log!(EnumLogColor::Blue, "EVENT", "[Window] -->\t Cursor mode : {0:?}", cursor_mode);
+    return Ok(());
+  }
+  
+  /// Toggle raw (unaccelerated and unscaled) mouse motion, bypassing the OS' pointer acceleration for precise aiming.
+  /// Only has an effect while the cursor is [EnumCursorMode::Disabled], and is ignored if the platform does not support
+  /// it. Can be set before the window is applied.
+  pub fn set_raw_mouse_motion(&mut self, flag: bool) {
+    self.m_raw_mouse_motion = flag;
+    
+    let Some(api_window) = self.m_api_window.as_mut() else {
+      return;
+    };
+    if !api_window.glfw.supports_raw_motion() {
+      log!(EnumLogColor::Yellow, "WARN", "[Window] -->\t Raw mouse motion is not supported on this platform! Ignoring...");
+      return;
+    }
+    api_window.set_raw_mouse_motion(flag);
+    log!(EnumLogColor::Blue, "EVENT", "[Window] -->\t Raw mouse motion {0}", flag);
+  }
+  
+  pub fn is_raw_mouse_motion(&self) -> bool {
+    return self.m_raw_mouse_motion;
+  }
+  
+  /// Replace the cursor image while over the window. The hotspot is the pixel of the image, from its top left corner,
+  /// that points at the cursor position.
+  pub fn set_custom_cursor(&mut self, image_path: &str, hotspot: (u32, u32)) -> Result<(), EnumWindowError> {
+    if self.m_api_window.is_none() {
+      log!(EnumLogColor::Red, "ERROR", "[Window] -->\t Cannot set custom cursor, window has not been created!");
+      return Err(EnumWindowError::NoContext);
+    }
+    
+    let image = Self::load_pixel_image(&TextureLoader::new(), image_path)?;
+    if hotspot.0 >= image.width || hotspot.1 >= image.height {
+      log!(EnumLogColor::Red, "ERROR", "[Window] -->\t Cannot set custom cursor, hotspot ({0}, {1}) is outside of \
+      the {2}x{3} image!", hotspot.0, hotspot.1, image.width, image.height);
+      return Err(EnumWindowError::InvalidWindowOption);
+    }
+    
+    self.m_api_window.as_mut().unwrap().set_cursor(Some(glfw::Cursor::create_from_pixels(image, hotspot.0, hotspot.1)));
+    log!(EnumLogColor::Green, "INFO", "[Window] -->\t Custom cursor set from {0}", image_path);
+    return Ok(());
+  }
+  
+  /// Go back to the platform's default arrow cursor.
+  pub fn reset_cursor(&mut self) -> Result<(), EnumWindowError> {
+    let api_window = self.m_api_window.as_mut().ok_or_else(|| {
+      log!(EnumLogColor::Red, "ERROR", "[Window] -->\t Cannot reset cursor, window has not been created!");
+      return EnumWindowError::NoContext;
+    })?;
+    api_window.set_cursor(None);
+    return Ok(());
+  }
+  
+  /// Size in pixels of the window's framebuffer, whichever monitor and video mode it ended up on. Falls back to the
+  /// requested resolution until the window is applied.
+  pub fn get_framebuffer_size(&mut self) -> (u32, u32) {
+    if let Some(api_window) = self.m_api_window.as_ref() {
+      let (width, height) = api_window.get_framebuffer_size();
+      return (width as u32, height as u32);
     }
-    return (self.m_window_resolution.unwrap().0, self.m_window_resolution.unwrap().1);
+    return self.m_window_resolution.unwrap_or((0, 0));
   }
   
   pub fn window_close_callback(_window: &mut glfw::Window) {
-    Engine::on_async_event(&EnumEvent::WindowCloseEvent(Time::now()));
+    Engine::on_async_event(EnumEvent::WindowCloseEvent(Time::now()));
   }
   
   pub fn window_iconify_callback(_window: &mut glfw::Window, flag: bool) {
-    Engine::on_async_event(&EnumEvent::WindowIconifyEvent(flag));
+    Engine::on_async_event(EnumEvent::WindowIconifyEvent(flag));
   }
   
   pub fn window_focus_callback(_window: &mut glfw::Window, flag: bool) {
-    Engine::on_async_event(&EnumEvent::WindowFocusEvent(flag));
+    Engine::on_async_event(EnumEvent::WindowFocusEvent(flag));
   }
   
   pub fn window_maximize_callback(_window: &mut glfw::Window, flag: bool) {
-    Engine::on_async_event(&EnumEvent::WindowMaximizeEvent(flag));
+    Engine::on_async_event(EnumEvent::WindowMaximizeEvent(flag));
   }
   
   pub fn window_pos_callback(_window: &mut glfw::Window, pos_x: i32, pos_y: i32) {
-    Engine::on_async_event(&EnumEvent::WindowPosEvent(pos_x, pos_y));
+    Engine::on_async_event(EnumEvent::WindowPosEvent(pos_x, pos_y));
   }
   
   pub fn window_size_callback(_window: &mut glfw::Window, size_x: i32, size_y: i32) {
-    Engine::on_async_event(&EnumEvent::FramebufferEvent(size_x as u32, size_y as u32));
+    Engine::on_async_event(EnumEvent::FramebufferEvent(size_x as u32, size_y as u32));
+  }
+  
+  pub fn window_content_scale_callback(_window: &mut glfw::Window, scale_x: f32, scale_y: f32) {
+    Engine::on_async_event(EnumEvent::ContentScaleEvent(scale_x, scale_y));
   }
   
   pub fn key_callback(_window: &mut glfw::Window, key: glfw::Key, _scancode: glfw::Scancode, action: glfw::Action,
                       modifiers: glfw::Modifiers) {
-    Engine::on_async_event(&EnumEvent::KeyEvent(EnumKey::from(key), EnumAction::from(action), None, EnumModifiers::from(modifiers)));
+    let key: EnumKey = EnumKey::from(key);
+    let action: EnumAction = EnumAction::from(action);
+    Engine::on_async_event(EnumEvent::KeyEvent(key, action, Input::track_key_repeat(key, action), EnumModifiers::from(modifiers)));
   }
   
   pub fn mouse_btn_callback(_window: &mut glfw::Window, mouse_btn: glfw::MouseButton, action: glfw::Action, modifiers: glfw::Modifiers) {
-    Engine::on_async_event(&EnumEvent::MouseBtnEvent(EnumMouseButton::from(mouse_btn), EnumAction::from(action), EnumModifiers::from(modifiers)));
+    Engine::on_async_event(EnumEvent::MouseBtnEvent(EnumMouseButton::from(mouse_btn), EnumAction::from(action), EnumModifiers::from(modifiers)));
   }
   
   pub fn scroll_callback(_window: &mut glfw::Window, delta_x: f64, delta_y: f64) {
-    Engine::on_async_event(&EnumEvent::MouseScrollEvent(delta_x, delta_y));
+    Engine::on_async_event(EnumEvent::MouseScrollEvent(delta_x, delta_y));
+  }
+  
+  // Glfw always lists the primary monitor first, so fall back to it when the requested index is out of range.
+  fn with_monitor<T>(monitor_index: Option<usize>, callback: impl FnOnce(Option<&glfw::Monitor>) -> T) -> T {
+    return unsafe {
+      (*S_WINDOW_CONTEXT.as_mut().unwrap()).with_connected_monitors(|_, monitors| {
+        let selected_index = monitor_index.filter(|index| *index < monitors.len()).unwrap_or(0);
+        
+        if monitor_index.is_some_and(|index| index != selected_index) {
+          log!(EnumLogColor::Yellow, "WARN", "[Window] -->\t Monitor {0} not found, falling back to primary monitor...",
+            monitor_index.unwrap());
+        }
+        callback(monitors.get(selected_index))
+      })
+    };
+  }
+  
+  pub fn char_callback(_window: &mut glfw::Window, character: char) {
+    Engine::on_async_event(EnumEvent::CharEvent(character));
   }
   
   pub fn drag_and_drop_callback(_window: &mut glfw::Window, path: Vec<PathBuf>) {
-    Engine::on_async_event(&EnumEvent::DragAndDrop(path));
+    Engine::on_async_event(EnumEvent::DragAndDrop(path));
+  }
+  
+  pub fn joystick_callback(joystick_id: glfw::JoystickId, event: glfw::JoystickEvent) {
+    Engine::on_async_event(match event {
+      glfw::JoystickEvent::Connected => EnumEvent::GamepadConnected(joystick_id as u32),
+      glfw::JoystickEvent::Disconnected => EnumEvent::GamepadDisconnected(joystick_id as u32),
+    });
   }
 }
 
@@ -860,4 +1591,171 @@ impl Drop for Window {
       S_WINDOW_CONTEXT = None;
     }
   }
-}
\ No newline at end of file
+}
+
+/// Collects window hints through chained calls, validates them against each other, and sets them all on a new,
+/// unapplied [Window] in one go, so that no hint ends up set after the window got created.
+///
+/// ### Example:
+/// ```text
+/// let window = WindowBuilder::new(EnumRendererApi::OpenGL)
+///   .resolution(1920, 1080)
+///   .msaa(8)
+///   .vsync(true)
+///   .build()?;
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowBuilder {
+  m_api: EnumRendererApi,
+  m_hints: Vec<EnumWindowHint>,
+  m_icon_paths: Vec<PathBuf>,
+}
+
+impl Default for WindowBuilder {
+  fn default() -> Self {
+    return Self::new(EnumRendererApi::default());
+  }
+}
+
+impl WindowBuilder {
+  pub fn new(api: EnumRendererApi) -> Self {
+    return Self {
+      m_api: api,
+      m_hints: Vec::new(),
+      m_icon_paths: Vec::new(),
+    };
+  }
+  
+  pub fn api(mut self, api: EnumRendererApi) -> Self {
+    self.m_api = api;
+    return self;
+  }
+  
+  /// Add any hint, replacing a previous hint of the same kind.
+  pub fn hint(mut self, hint: EnumWindowHint) -> Self {
+    self.m_hints.retain(|existing| std::mem::discriminant(existing) != std::mem::discriminant(&hint));
+    self.m_hints.push(hint);
+    return self;
+  }
+  
+  pub fn resolution(self, width: u32, height: u32) -> Self {
+    return self.hint(EnumWindowHint::Resolution(width, height));
+  }
+  
+  pub fn window_mode(self, window_mode: EnumWindowMode) -> Self {
+    return self.hint(EnumWindowHint::WindowMode(window_mode));
+  }
+  
+  pub fn vsync(self, flag: bool) -> Self {
+    return self.hint(EnumWindowHint::VSync(flag));
+  }
+  
+  pub fn msaa(self, samples: u32) -> Self {
+    return self.hint(EnumWindowHint::MSAA((samples > 1).then_some(samples)));
+  }
+  
+  pub fn refresh_rate(self, refresh_rate: u32) -> Self {
+    return self.hint(EnumWindowHint::RefreshRate(Some(refresh_rate)));
+  }
+  
+  pub fn monitor(self, monitor_index: usize) -> Self {
+    return self.hint(EnumWindowHint::Monitor(monitor_index));
+  }
+  
+  pub fn resizable(self, flag: bool) -> Self {
+    return self.hint(EnumWindowHint::Resizable(flag));
+  }
+  
+  pub fn decorated(self, flag: bool) -> Self {
+    return self.hint(EnumWindowHint::Decorated(flag));
+  }
+  
+  pub fn gl_version(self, major: u32, minor: u32) -> Self {
+    return self.hint(EnumWindowHint::GlVersion(major, minor));
+  }
+  
+  pub fn gl_profile(self, profile: EnumGlProfile) -> Self {
+    return self.hint(EnumWindowHint::GlProfile(profile));
+  }
+  
+  /// See [Window::set_icon].
+  pub fn icon(mut self, icon_paths: Vec<PathBuf>) -> Self {
+    self.m_icon_paths = icon_paths;
+    return self;
+  }
+  
+  pub fn raw_mouse_motion(self, flag: bool) -> Self {
+    return self.hint(EnumWindowHint::RawMouseMotion(flag));
+  }
+  
+  pub fn size_limits(mut self, min_size: Option<(u32, u32)>, max_size: Option<(u32, u32)>) -> Self {
+    self.m_hints.retain(|hint| !matches!(hint, EnumWindowHint::MinSize(..) | EnumWindowHint::MaxSize(..)));
+    if let Some((width, height)) = min_size {
+      self = self.hint(EnumWindowHint::MinSize(width, height));
+    }
+    if let Some((width, height)) = max_size {
+      self = self.hint(EnumWindowHint::MaxSize(width, height));
+    }
+    return self;
+  }
+  
+  pub fn get_hints(&self) -> &Vec<EnumWindowHint> {
+    return &self.m_hints;
+  }
+  
+  /// Check the collected hints for values that are invalid or that contradict each other, such as multisampling
+  /// a headless window or OpenGL context hints on a Vulkan window.
+  pub fn validate(&self) -> Result<(), EnumWindowError> {
+    let is_headless = self.m_hints.contains(&EnumWindowHint::WindowMode(EnumWindowMode::Headless));
+    let mut min_size: Option<(u32, u32)> = None;
+    let mut max_size: Option<(u32, u32)> = None;
+    
+    for hint in self.m_hints.iter() {
+      match hint {
+        EnumWindowHint::Resolution(0, _) | EnumWindowHint::Resolution(_, 0) => {
+          log!(EnumLogColor::Red, "ERROR", "[Window] -->\t Cannot build window, resolution {0:?} is empty!", hint);
+          return Err(EnumWindowError::InvalidWindowOption);
+        }
+        EnumWindowHint::MSAA(Some(_)) if is_headless => {
+          log!(EnumLogColor::Red, "ERROR", "[Window] -->\t Cannot build window, headless windows have no default \
+          framebuffer to multisample, multisample a render target instead!");
+          return Err(EnumWindowError::InvalidWindowOption);
+        }
+        EnumWindowHint::GlVersion(..) | EnumWindowHint::GlProfile(..) if self.m_api == EnumRendererApi::Vulkan => {
+          log!(EnumLogColor::Red, "ERROR", "[Window] -->\t Cannot build window, {0:?} only applies to OpenGL \
+          windows!", hint);
+          return Err(EnumWindowError::InvalidWindowOption);
+        }
+        EnumWindowHint::MinSize(width, height) => min_size = Some((*width, *height)),
+        EnumWindowHint::MaxSize(width, height) => max_size = Some((*width, *height)),
+        _ => {}
+      }
+    }
+    
+    if let (Some(min), Some(max)) = (min_size, max_size) {
+      if min.0 > max.0 || min.1 > max.1 {
+        log!(EnumLogColor::Red, "ERROR", "[Window] -->\t Cannot build window, minimum size {0:?} exceeds maximum size \
+        {1:?}!", min, max);
+        return Err(EnumWindowError::InvalidWindowOption);
+      }
+    }
+    return Ok(());
+  }
+  
+  /// Validate the hints and create an unapplied window with all of them set, ready to be applied by the engine.
+  pub fn build(self) -> Result<Window, EnumWindowError> {
+    self.validate()?;
+    
+    let mut window = Window::new(self.m_api);
+    // The window mode decides how some other hints apply (i.e. resizable for borderless windows), set it first.
+    let (window_modes, other_hints): (Vec<EnumWindowHint>, Vec<EnumWindowHint>) = self.m_hints.into_iter()
+      .partition(|hint| matches!(hint, EnumWindowHint::WindowMode(_)));
+    
+    for hint in window_modes.into_iter().chain(other_hints) {
+      window.set_hint(hint);
+    }
+    // Deferred until the window gets applied.
+    window.m_icon_paths = self.m_icon_paths;
+    return Ok(window);
+  }
+}
@@ -25,17 +25,20 @@
 pub extern crate wave_core;
 
 use std::collections::HashMap;
+use std::path::PathBuf;
 
 use wave_core::{camera, Engine, EnumEngineError, input, layers, TraitApply, TraitFree, TraitHint};
+use wave_core::assets::{classify_dropped_files, EnumAssetKind};
 use wave_core::assets::asset_loader::{AssetLoader};
 use wave_core::assets::r_assets::{EnumAssetMapMethod, EnumAssetPrimitiveSurface, EnumPrimitiveShading, REntity};
 #[allow(unused)]
 use wave_core::dependencies::chrono;
 use wave_core::events::{EnumEvent, EnumEventMask};
-use wave_core::graphics::renderer::{Renderer, EnumRendererRenderPrimitiveAs, EnumRendererHint, EnumRendererOptimizationMode, EnumRendererApi, EnumRendererCallCheckingMode};
+use wave_core::graphics::renderer::{C_DEFAULT_CLEAR_COLOR, Renderer, RendererBuilder, EnumRendererError, EnumRendererRenderPrimitiveAs, EnumRendererOptimizationMode, EnumRendererApi, EnumRendererCallCheckingMode};
 use wave_core::graphics::{shader};
 use wave_core::graphics::shader::EnumShaderHint;
 use wave_core::graphics::texture::{Texture, TextureArray};
+use wave_core::utils::config::Config;
 use wave_core::utils::texture_loader::{EnumTextureLoaderHint, TextureLoader};
 use wave_core::layers::{EnumLayerType, EnumSyncInterval, Layer, TraitLayer};
 #[allow(unused)]
@@ -43,7 +46,13 @@ use wave_core::layers::imgui_layer::ImguiLayer;
 #[allow(unused)]
 use wave_core::ui::ui_imgui::Imgui;
 use wave_core::utils::macros::logger::*;
-use wave_core::window::{EnumWindowHint, Window};
+use wave_core::window::{Window, WindowBuilder};
+
+// Rotation step, in degrees, while snapping to the grid.
+const C_ROTATION_SNAP_DEGREES: f32 = 15.0;
+// Half of the vertical extent, in world units, shown by the main camera in 2D.
+const C_ORTHOGRAPHIC_HALF_HEIGHT: f32 = 20.0;
+const C_LIGHT_BACKGROUND: [f32; 4] = [1.0, 1.0, 1.0, 1.0];
 
 static mut S_EDITOR: Option<*mut Editor> = None;
 
@@ -118,30 +127,37 @@ impl TraitLayer for EditorLayer {
 pub struct Editor {
   m_engine: Engine,
   m_r_assets: HashMap<&'static str, (shader::Shader, Vec<REntity>)>,
-  m_cameras: Vec<camera::Camera>,
   m_textures: Vec<Texture>,
+  m_action_map: input::ActionMap,
+  m_view_2d: bool,
+  // Asset group and index of the entity last clicked on, if any.
+  m_selected: Option<(&'static str, usize)>,
 }
 
 impl Default for Editor {
   fn default() -> Self {
-    let mut window = Window::default();  // Apply default window hints.
-    let mut renderer = Renderer::default();  // Apply default renderer hints.
-    
-    // window.set_hint(EnumWindowHint::WindowApi(EnumRendererApi::Vulkan));  // Select Vulkan client api.
-    window.set_hint(EnumWindowHint::MSAA(None));  // Enable MSAA.
+    // Api, resolution, window mode, vsync and MSAA, falling back to defaults without a config file.
+    let config: Config = Config::from_file("res/config.toml").unwrap_or_default();
     
+    let mut window = WindowBuilder::new(config.m_renderer_api)  // Apply default window hints.
+      .build()
+      .expect("Cannot build editor window!");
     // Enable all optimizations.
-    // renderer.set_hint(EnumRendererHint::ForceApiVersion(420));
-    renderer.set_hint(EnumRendererHint::ApiCallChecking(EnumRendererCallCheckingMode::SyncAndAsync));
-    renderer.set_hint(EnumRendererHint::Optimization(EnumRendererOptimizationMode::MinimizeDrawCalls));
-    renderer.set_hint(EnumRendererHint::MSAA(None));  // Enable MSAA.
-    // renderer.set_hint(EnumRendererHint::ContextApi(EnumRendererApi::Vulkan));  // Select Vulkan context api.
+    let mut renderer = RendererBuilder::new(config.m_renderer_api)  // Apply default renderer hints.
+      // .force_api_version(420)
+      .api_call_checking(EnumRendererCallCheckingMode::SyncAndAsync)
+      .optimization(EnumRendererOptimizationMode::MinimizeDrawCalls)
+      .build()
+      .expect("Cannot build editor renderer!");
+    config.apply_to(&mut window, &mut renderer);
     
     return Editor {
       m_engine: Engine::new(window, renderer, vec![]),
       m_r_assets: HashMap::with_capacity(5),
-      m_cameras: Vec::with_capacity(1),
       m_textures: Vec::with_capacity(5),
+      m_action_map: Editor::default_action_map(),
+      m_view_2d: false,
+      m_selected: None,
     };
   }
 }
@@ -151,9 +167,168 @@ impl Editor {
     return Editor {
       m_engine: Engine::new(window, renderer, app_layers),
       m_r_assets: HashMap::new(),
-      m_cameras: Vec::new(),
       m_textures: Vec::new(),
+      m_action_map: Editor::default_action_map(),
+      m_view_2d: false,
+      m_selected: None,
+    };
+  }
+  
+  /// Orthographic bounds keep the window's aspect ratio, and get updated on resize by the camera itself.
+  pub fn main_camera_type(view_2d: bool, aspect_ratio: f32) -> camera::EnumCameraType {
+    if view_2d {
+      let half_width: f32 = C_ORTHOGRAPHIC_HALF_HEIGHT * aspect_ratio;
+      return camera::EnumCameraType::Orthographic(-half_width, half_width, -C_ORTHOGRAPHIC_HALF_HEIGHT,
+        C_ORTHOGRAPHIC_HALF_HEIGHT, 0.01, 1000.0);
+    }
+    return camera::EnumCameraType::Perspective(75, aspect_ratio, 0.01, 1000.0);
+  }
+  
+  /// Swap the main camera between a perspective (3D) and orthographic (2D) projection, keeping its orbit around the
+  /// scene.
+  pub fn toggle_view_2d(&mut self) -> Result<(), EnumEngineError> {
+    self.m_view_2d = !self.m_view_2d;
+    let aspect_ratio: f32 = self.m_engine.get_window_ref().get_aspect_ratio();
+    let mut main_camera = camera::Camera::new(Editor::main_camera_type(self.m_view_2d, aspect_ratio), None);
+    
+    let renderer = self.m_engine.get_renderer_mut();
+    let orbit = renderer.get_active_camera_mut().and_then(|previous| previous.take_orbit_controller());
+    main_camera.set_orbit_controller(orbit);
+    renderer.submit_camera(main_camera)?;
+    
+    log!(EnumLogColor::Blue, "INFO", "[App] -->\t Switched main camera to {0} view", self.m_view_2d.then(|| "2D").unwrap_or("3D"));
+    return Ok(());
+  }
+  
+  /// Swap the background between the dark default and a white one.
+  pub fn toggle_light_background(&mut self) -> Result<(), EnumEngineError> {
+    let renderer = self.m_engine.get_renderer_mut();
+    let [red, green, blue, alpha] = (renderer.get_clear_color() == C_LIGHT_BACKGROUND)
+      .then_some(C_DEFAULT_CLEAR_COLOR)
+      .unwrap_or(C_LIGHT_BACKGROUND);
+    renderer.set_clear_color(red, green, blue, alpha)?;
+    return Ok(());
+  }
+  
+  /// Select the closest entity under the cursor, or clear the selection when clicking on nothing.
+  pub fn pick_entity(&mut self) -> Result<Option<(&'static str, usize)>, EnumEngineError> {
+    let window = self.m_engine.get_window_mut();
+    let viewport: (u32, u32) = window.get_framebuffer_size();
+    let cursor = input::Input::get_mouse_cursor_position(window)?;
+    
+    let Some(camera) = self.m_engine.get_renderer_mut().get_active_camera() else {
+      return Ok(None);
     };
+    let (origin, direction) = camera.screen_point_to_ray(cursor, viewport);
+    
+    let mut closest: Option<(&'static str, usize, f32)> = None;
+    for (group, (_, r_entities)) in self.m_r_assets.iter() {
+      for (index, r_entity) in r_entities.iter().enumerate() {
+        match r_entity.intersects_ray(origin, direction) {
+          Some(distance) if closest.map_or(true, |(_, _, closest_distance)| distance < closest_distance) => {
+            closest = Some((*group, index, distance));
+          }
+          _ => {}
+        }
+      }
+    }
+    
+    self.m_selected = closest.map(|(group, index, _)| (group, index));
+    match self.m_selected {
+      Some((_group, _index)) => {
+        log!(EnumLogColor::Blue, "INFO", "[App] -->\t Selected {0}", self.m_r_assets[_group].1[_index].get_name());
+      }
+      None => log!(EnumLogColor::Blue, "INFO", "[App] -->\t Selection cleared"),
+    }
+    return Ok(self.m_selected);
+  }
+  
+  pub fn get_selected_entity(&self) -> Option<&REntity> {
+    return self.m_selected.and_then(|(group, index)| self.m_r_assets.get(group)?.1.get(index));
+  }
+  
+  pub fn get_action_map_mut(&mut self) -> &mut input::ActionMap {
+    return &mut self.m_action_map;
+  }
+  
+  fn default_action_map() -> input::ActionMap {
+    let mut action_map = input::ActionMap::new();
+    action_map.bind("rotate_up", input::EnumInputBinding::Key(input::EnumKey::Up));
+    action_map.bind("rotate_left", input::EnumInputBinding::Key(input::EnumKey::Left));
+    action_map.bind("rotate_down", input::EnumInputBinding::Key(input::EnumKey::Down));
+    action_map.bind("rotate_right", input::EnumInputBinding::Key(input::EnumKey::Right));
+    action_map.bind("snap", input::EnumInputBinding::Key(input::EnumKey::LeftShift));
+    return action_map;
+  }
+  
+  // Load dropped meshes at the origin and send dropped textures to the GPU. Files that fail to load are only reported,
+  // since a bad drop shouldn't bring the editor down.
+  fn on_files_dropped(&mut self, file_paths: &[PathBuf]) -> Result<bool, EnumEngineError> {
+    let asset_loader = AssetLoader::new();
+    let texture_loader = TextureLoader::new();
+    let mut handled = false;
+    
+    for (file_path, asset_kind) in classify_dropped_files(file_paths) {
+      let file_path_str = file_path.to_str().unwrap_or_default();
+      
+      match asset_kind {
+        EnumAssetKind::Mesh => {
+          let asset = match asset_loader.load(file_path_str) {
+            Ok(asset) => asset,
+            Err(_err) => {
+              log!(EnumLogColor::Red, "ERROR", "[App] -->\t Cannot load dropped mesh {0}, Error => {1}", file_path_str, _err);
+              continue;
+            }
+          };
+          
+          let name: String = file_path.file_stem().unwrap_or_default().to_string_lossy().into_owned();
+          let mut r_entity = REntity::new(asset, EnumPrimitiveShading::default(), &name);
+          
+          let (shader, r_entities) = match self.m_r_assets.get_mut(&"Smooth assets") {
+            Some(smooth_assets) => smooth_assets,
+            None => {
+              log!(EnumLogColor::Red, "ERROR", "[App] -->\t Cannot place dropped mesh {0}, no shader to send it with!",
+                file_path_str);
+              continue;
+            }
+          };
+          r_entity.apply(shader)?;
+          r_entity.show(EnumAssetPrimitiveSurface::Everything);
+          r_entities.push(r_entity);
+          
+          log!(EnumLogColor::Green, "INFO", "[App] -->\t Dropped mesh {0} placed at the origin", file_path_str);
+          handled = true;
+        }
+        EnumAssetKind::Texture => {
+          let texture_info = match texture_loader.load(file_path_str) {
+            Ok(texture_info) => texture_info,
+            Err(_err) => {
+              log!(EnumLogColor::Red, "ERROR", "[App] -->\t Cannot load dropped texture {0}, Error => {1:?}",
+                file_path_str, _err);
+              continue;
+            }
+          };
+          
+          let mut texture = match TextureArray::new(self.m_engine.get_renderer_mut().get_type(), vec![texture_info]) {
+            Ok(texture_array) => texture_array.get_texture_handle(),
+            Err(_err) => {
+              log!(EnumLogColor::Red, "ERROR", "[App] -->\t Cannot create texture from dropped {0}, Error => {1:?}",
+                file_path_str, _err);
+              continue;
+            }
+          };
+          texture.apply()?;
+          self.m_textures.push(texture);
+          
+          log!(EnumLogColor::Green, "INFO", "[App] -->\t Dropped texture {0} sent to GPU", file_path_str);
+          handled = true;
+        }
+        EnumAssetKind::Shader | EnumAssetKind::Unknown => {
+          log!(EnumLogColor::Yellow, "WARN", "[App] -->\t Ignoring dropped file {0} ({1:?})", file_path_str, asset_kind);
+        }
+      }
+    }
+    return Ok(handled);
   }
   
   pub fn run(&mut self) -> Result<(), EnumEditorError> {
@@ -182,6 +357,14 @@ impl TraitLayer for Editor {
     let window = self.m_engine.get_window_mut();
     let aspect_ratio: f32 = window.get_aspect_ratio();
     
+    // Start parsing assets on worker threads right away, while we compile shaders and upload textures.
+    let asset_loader = AssetLoader::new();
+    // asset_loader.set_hint(EnumAssetHint::VertexDataIs(EnumAssetPrimitiveMode::Plain));
+    
+    let mut awp_handle = asset_loader.load_async("res/assets/awp/awp.obj");
+    let mut mario_handle = asset_loader.load_async("res/assets/mario/mario.obj");
+    let mut logo_handle = asset_loader.load_async("res/assets/n64_logo/n64_logo.obj");
+    
     log!(EnumLogColor::Purple, "INFO", "[App] -->\t Loading shaders...");
     
     let mut shader = shader::Shader::default();  // Get default smooth shader with 3 stages (vertex, geometry, and fragment).
@@ -190,6 +373,7 @@ impl TraitLayer for Editor {
     
     // Source and compile the shader program.
     shader.apply()?;
+    shader.watch_sources();  // Hot-reload the shader when its sources get modified.
     
     log!(EnumLogColor::Green, "INFO", "[App] -->\t Loaded shaders successfully");
     log!(EnumLogColor::Purple, "INFO", "[App] -->\t Sending textures to GPU...");
@@ -205,10 +389,12 @@ impl TraitLayer for Editor {
     let n64_logo_textures_info = texture_preset.load_from_folder("res/textures/n64_logo")?;
     
     // Batch all textures from assets that share the same size to fit them in an appropriate 'texture array bucket' in the shader.
-    let mut texture_1024_array = TextureArray::new(EnumRendererApi::OpenGL, vec![awp_texture_info]);
-    texture_1024_array.append(mario_textures_info);
+    let mut texture_1024_array = TextureArray::new(EnumRendererApi::OpenGL, vec![awp_texture_info])
+      .map_err(EnumRendererError::from)?;
+    texture_1024_array.append(mario_textures_info).map_err(EnumRendererError::from)?;
     
-    let texture_64_array = TextureArray::new(EnumRendererApi::OpenGL, n64_logo_textures_info);
+    let texture_64_array = TextureArray::new(EnumRendererApi::OpenGL, n64_logo_textures_info)
+      .map_err(EnumRendererError::from)?;
     
     let mut texture_1024_handle = texture_1024_array.get_texture_handle();
     let mut textures_64_handle = texture_64_array.get_texture_handle();
@@ -222,12 +408,9 @@ impl TraitLayer for Editor {
     log!(EnumLogColor::Green, "INFO", "[App] -->\t Textures sent to GPU...");
     log!(EnumLogColor::Purple, "INFO", "[App] -->\t Sending assets to GPU...");
     
-    let asset_loader = AssetLoader::new();
-    // asset_loader.set_hint(EnumAssetHint::VertexDataIs(EnumAssetPrimitiveMode::Plain));
-    
-    let awp_asset = asset_loader.load("res/assets/awp/awp.obj")?;
-    let mario_asset = asset_loader.load("res/assets/mario/mario.obj")?;
-    let logo_asset = asset_loader.load("res/assets/n64_logo/n64_logo.obj")?;
+    let awp_asset = awp_handle.wait()?;
+    let mario_asset = mario_handle.wait()?;
+    let logo_asset = logo_handle.wait()?;
     
     let mut awp = REntity::new(awp_asset, EnumPrimitiveShading::default(), "Awp Sniper");
     
@@ -237,6 +420,7 @@ impl TraitLayer for Editor {
     awp.rotate(90.0, -90.0, 0.0);
     awp.apply(&mut shader)?;  // Bake and send the asset.
     awp.show(EnumAssetPrimitiveSurface::Everything);
+    let awp_center = awp.get_world_bounds().get_center();
     
     let mut mario = REntity::new(mario_asset, EnumPrimitiveShading::default(), "Mario");
     
@@ -258,12 +442,19 @@ impl TraitLayer for Editor {
     
     log!(EnumLogColor::Green, "INFO", "[App] -->\t Asset sent to GPU successfully");
     
-    let mut main_camera = camera::Camera::new(camera::EnumCameraType::Perspective(75, aspect_ratio, 0.01, 1000.0), None);
-    main_camera.on_update(self.m_engine.get_time_step());
-    self.m_cameras.push(main_camera);
+    // Make the renderer own the main camera, for it to be updated each frame and reachable by other systems.
+    let mut main_camera = camera::Camera::new(Editor::main_camera_type(self.m_view_2d, aspect_ratio), None);
     
-    // let mut imgui_layer: Layer = Layer::new("Imgui",
-    //   ImguiLayer::new(Imgui::new(self.m_engine.get_renderer_mut().get_type(), self.m_engine.get_window_mut())));
+    // Inspect the awp by dragging the mouse around it, and scrolling to zoom.
+    let mut orbit = camera::OrbitController::new(awp_center, 30.0);
+    orbit.set_radius_limits(5.0, 200.0);
+    main_camera.set_orbit_controller(Some(orbit));
+    self.m_engine.get_renderer_mut().submit_camera(main_camera)?;
+    
+    // let mut imgui = Imgui::new(self.m_engine.get_renderer_mut().get_type(), self.m_engine.get_window_mut());
+    // imgui.set_ui_type(EnumUiType::Docked);
+    // imgui.set_ui_fn(Box::new(|ui| { ui.window("Scene").build(|| ui.text("Entities")); }));
+    // let mut imgui_layer: Layer = Layer::new("Imgui", ImguiLayer::new(imgui));
     // imgui_layer.enable_async_polling_for(EnumEventMask::Input | EnumEventMask::Window);
     // self.m_engine.push_layer(imgui_layer, true)?;
     
@@ -277,34 +468,34 @@ impl TraitLayer for Editor {
     // Process synchronous events.
     let time_step = self.m_engine.get_time_step();
     
-    if Engine::is_key(input::EnumKey::Up, input::EnumAction::Held) {
-      for asset in self.m_r_assets.values_mut() {
-        for primitive in asset.1.iter_mut() {
-          primitive.rotate(0.0, 25.0 * time_step as f32, 0.0);
-          primitive.reapply()?;
-        }
-      }
+    // Holding the snap modifier turns rotations into discrete steps, landing on the grid.
+    let snapping: bool = self.m_action_map.is_action_active("snap", input::EnumAction::Held);
+    let (step, trigger) = match snapping {
+      true => (C_ROTATION_SNAP_DEGREES, input::EnumAction::Pressed),
+      false => (25.0 * time_step as f32, input::EnumAction::Held),
+    };
+    
+    let mut rotation: (f32, f32) = (0.0, 0.0);
+    if self.m_action_map.is_action_active("rotate_up", trigger) {
+      rotation.1 += step;
     }
-    if Engine::is_key(input::EnumKey::Left, input::EnumAction::Held) {
-      for asset in self.m_r_assets.values_mut() {
-        for primitive in asset.1.iter_mut() {
-          primitive.rotate(-25.0 * time_step as f32, 0.0, 0.0);
-          primitive.reapply()?;
-        }
-      }
+    if self.m_action_map.is_action_active("rotate_left", trigger) {
+      rotation.0 -= step;
     }
-    if Engine::is_key(input::EnumKey::Down, input::EnumAction::Held) {
-      for asset in self.m_r_assets.values_mut() {
-        for primitive in asset.1.iter_mut() {
-          primitive.rotate(0.0, -25.0 * time_step as f32, 0.0);
-          primitive.reapply()?;
-        }
-      }
+    if self.m_action_map.is_action_active("rotate_down", trigger) {
+      rotation.1 -= step;
+    }
+    if self.m_action_map.is_action_active("rotate_right", trigger) {
+      rotation.0 += step;
     }
-    if Engine::is_key(input::EnumKey::Right, input::EnumAction::Held) {
+    
+    if rotation != (0.0, 0.0) {
       for asset in self.m_r_assets.values_mut() {
         for primitive in asset.1.iter_mut() {
-          primitive.rotate(25.0 * time_step as f32, 0.0, 0.0);
+          primitive.rotate(rotation.0, rotation.1, 0.0);
+          if snapping {
+            primitive.snap_rotation(C_ROTATION_SNAP_DEGREES);
+          }
           primitive.reapply()?;
         }
       }
@@ -314,8 +505,6 @@ impl TraitLayer for Editor {
   
   fn on_async_event(&mut self, event: &EnumEvent) -> Result<bool, EnumEngineError> {
     // Process asynchronous events.
-    self.m_cameras[0].on_event(event)?;
-    
     return match event {
       EnumEvent::KeyEvent(key, action, repeat_count, modifiers) => {
         match (key, action, repeat_count, modifiers) {
@@ -355,7 +544,25 @@ impl TraitLayer for Editor {
             Ok(true)
           }
           (input::EnumKey::Num2, input::EnumAction::Pressed, _, &input::EnumModifiers::Alt) => {
-            // renderer.toggle_msaa(Some(4))?;
+            let renderer = self.m_engine.get_renderer_mut();
+            let sample_count = renderer.get_msaa_sample_count().is_none().then(|| 4);
+            renderer.toggle_msaa(sample_count)?;
+            Ok(true)
+          }
+          (input::EnumKey::Kp5, input::EnumAction::Pressed, _, _) => {
+            self.toggle_view_2d()?;
+            Ok(true)
+          }
+          (input::EnumKey::B, input::EnumAction::Pressed, _, &input::EnumModifiers::Control) => {
+            self.toggle_light_background()?;
+            Ok(true)
+          }
+          (input::EnumKey::F12, input::EnumAction::Pressed, _, _) => {
+            let file_path = format!("screenshot_{0}.png", chrono::Local::now().format("%Y%m%d_%H%M%S"));
+            // A failed screenshot shouldn't stop the editor.
+            if let Err(_err) = self.m_engine.get_renderer_mut().save_screenshot(&file_path) {
+              log!(EnumLogColor::Red, "ERROR", "[App] -->\t Cannot save screenshot to {0}, Error => {1:?}", file_path, _err);
+            }
             Ok(true)
           }
           (input::EnumKey::Delete, input::EnumAction::Pressed, _, &input::EnumModifiers::Control) => {
@@ -369,6 +576,12 @@ impl TraitLayer for Editor {
           _ => Ok(false)
         }
       }
+      EnumEvent::MouseBtnEvent(input::EnumMouseButton::LeftButton, input::EnumAction::Pressed, _) => {
+        self.pick_entity()?;
+        // Let the orbit controls start dragging from the same click.
+        Ok(false)
+      }
+      EnumEvent::DragAndDrop(file_paths) => self.on_files_dropped(file_paths),
       EnumEvent::WindowCloseEvent(_time) => {
         self.free()?;
         Ok(true)
@@ -377,8 +590,10 @@ impl TraitLayer for Editor {
     };
   }
   
-  fn on_update(&mut self, time_step: f64) -> Result<(), EnumEngineError> {
-    self.m_cameras[0].on_update(time_step);
+  fn on_update(&mut self, _time_step: f64) -> Result<(), EnumEngineError> {
+    for (shader, _) in self.m_r_assets.values_mut() {
+      shader.reload_if_changed()?;
+    }
     return Ok(());
   }
   
@@ -0,0 +1,397 @@
+/*
+ MIT License
+
+ Copyright (c) 2023 Nami Reghbati
+
+ Permission is hereby granted, free of charge, to any person obtaining a copy
+ of this software and associated documentation files (the "Software"), to deal
+ in the Software without restriction, including without limitation the rights
+ to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ copies of the Software, and to permit persons to whom the Software is
+ furnished to do so, subject to the following conditions:
+
+ The above copyright notice and this permission notice shall be included in all
+ copies or substantial portions of the Software.
+
+ THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ FITNESS FOR A PARTICULAR PURPOSE AND NON INFRINGEMENT. IN NO EVENT SHALL THE
+ AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ SOFTWARE.
+*/
+
+use std::path::PathBuf;
+use std::rc::Rc;
+
+use wave_editor::wave_core::assets::{classify_dropped_files, EnumAssetKind};
+use wave_editor::wave_core::assets::asset_loader::{AssetLoader, EnumAssetError, EnumAssetHint, FolderLoadResult};
+use wave_editor::wave_core::assets::r_assets::{EnumPrimitiveShading, REntity, Vertex};
+use wave_editor::wave_core::assets::scene::{EnumSceneError, Scene};
+use wave_editor::wave_core::assets::skeleton::{AnimationChannel, AnimationClip, Skeleton};
+use wave_editor::wave_core::graphics::renderer::{EnumRendererBlendMode, EnumRendererBlendingFactor};
+use wave_editor::wave_core::math::{EnumEasing, Mat4, Quaternion, Vec3};
+use wave_editor::wave_core::TraitHint;
+
+#[test]
+fn test_shadow_flags() {
+  let mut cube = REntity::default();
+  
+  // Entities participate in shadow mapping by default.
+  assert!(cube.casts_shadows());
+  assert!(cube.receives_shadows());
+  
+  cube.set_cast_shadows(false);
+  assert!(!cube.casts_shadows());
+  assert!(cube.receives_shadows());
+  assert!(cube.has_changed());
+  
+  cube.set_receive_shadows(false);
+  assert!(!cube.receives_shadows());
+}
+
+#[test]
+fn test_transparency_flag() {
+  let mut glass = REntity::default();
+  
+  // Entities are opaque by default, keeping the unsorted draw path.
+  assert!(!glass.is_transparent());
+  
+  glass.set_transparent(true);
+  assert!(glass.is_transparent());
+  assert!(glass.has_changed());
+  
+  glass.set_transparent(false);
+  assert!(!glass.is_transparent());
+}
+
+#[test]
+fn test_blend_mode() {
+  let mut glass = REntity::default();
+  assert_eq!(glass.get_blend_mode(), EnumRendererBlendMode::Opaque);
+  
+  // Any blend mode other than opaque is drawn as transparent.
+  glass.set_blend_mode(EnumRendererBlendMode::Additive);
+  assert_eq!(glass.get_blend_mode(), EnumRendererBlendMode::Additive);
+  assert!(glass.is_transparent());
+  assert!(glass.has_changed());
+  
+  glass.set_blend_mode(EnumRendererBlendMode::Multiply);
+  assert!(glass.is_transparent());
+  
+  // Already transparent, the blend mode is kept.
+  glass.set_transparent(true);
+  assert_eq!(glass.get_blend_mode(), EnumRendererBlendMode::Multiply);
+  
+  glass.set_transparent(false);
+  assert_eq!(glass.get_blend_mode(), EnumRendererBlendMode::Opaque);
+  
+  glass.set_transparent(true);
+  assert_eq!(glass.get_blend_mode(), EnumRendererBlendMode::AlphaBlend);
+  
+  assert_eq!(EnumRendererBlendMode::Opaque.get_factors(), None);
+  assert_eq!(EnumRendererBlendMode::Additive.get_factors(),
+    Some((EnumRendererBlendingFactor::SrcAlpha, EnumRendererBlendingFactor::One)));
+}
+
+#[test]
+fn test_scene_hierarchy() -> Result<(), EnumSceneError> {
+  let mut scene = Scene::new();
+  
+  // Bring both default cubes back to the origin on Z, to only compare the offsets below.
+  let mut parent = REntity::default();
+  parent.translate(5.0, 0.0, -10.0);
+  let parent_id = scene.add_entity(parent);
+  
+  let mut child = REntity::default();
+  child.translate(0.0, 2.0, -10.0);
+  let child_id = scene.add_child(parent_id, child)?;
+  assert_eq!(scene.get_parent(child_id), Some(parent_id));
+  assert_eq!(scene.get_children(parent_id), &[child_id]);
+  
+  // Child inherits its parent's translation on top of its own.
+  scene.update_transforms();
+  let child_world = scene.get_world_matrix(child_id).unwrap();
+  assert_eq!((child_world[0][3], child_world[1][3], child_world[2][3]), (5.0, 2.0, 0.0));
+  
+  // Moving the parent carries the child along.
+  scene.get_entity_mut(parent_id).unwrap().translate(1.0, 0.0, 0.0);
+  scene.update_transforms();
+  let child_world = scene.get_world_matrix(child_id).unwrap();
+  assert_eq!((child_world[0][3], child_world[1][3], child_world[2][3]), (6.0, 2.0, 0.0));
+  assert_eq!(scene.get_entity(child_id).unwrap().get_matrix()[0][3], 6.0);
+  
+  assert_eq!(scene.add_child(42, REntity::default()), Err(EnumSceneError::InvalidNode));
+  return Ok(());
+}
+
+#[test]
+fn test_quaternion_orientation() {
+  let mut euler_cube = REntity::default();
+  let mut quaternion_cube = REntity::default();
+  
+  euler_cube.rotate(0.0, 45.0, 0.0);
+  quaternion_cube.rotate_quaternion(Quaternion::from_euler(&Vec3::new(&[45.0, 0.0, 0.0])));
+  
+  let euler_matrix = euler_cube.get_matrix();
+  let quaternion_matrix = quaternion_cube.get_matrix();
+  
+  for row in 0..4usize {
+    for col in 0..4usize {
+      assert!((euler_matrix[row][col] - quaternion_matrix[row][col]).abs() < 1e-5);
+    }
+  }
+}
+
+#[test]
+fn test_flip_winding() -> Result<(), EnumAssetError> {
+  // Single triangle facing +Z.
+  let file_path = std::env::temp_dir().join("wave_test_flip_winding.obj");
+  std::fs::write(&file_path, "v 0.0 0.0 0.0\nv 1.0 0.0 0.0\nv 0.0 1.0 0.0\nvn 0.0 0.0 1.0\nf 1//1 2//1 3//1\n")?;
+  
+  let mut asset_loader = AssetLoader::new();
+  let triangle = REntity::new(asset_loader.load(file_path.to_str().unwrap())?, EnumPrimitiveShading::default(), "Triangle");
+  
+  asset_loader.set_hint(EnumAssetHint::FlipWinding(true));
+  let flipped_triangle = REntity::new(asset_loader.load(file_path.to_str().unwrap())?, EnumPrimitiveShading::default(),
+    "Flipped triangle");
+  
+  let primitive = triangle.get_primitive(0).unwrap();
+  let flipped_primitive = flipped_triangle.get_primitive(0).unwrap();
+  
+  let indices = primitive.get_indices();
+  let flipped_indices = flipped_primitive.get_indices();
+  assert_eq!(flipped_indices, &vec![indices[0], indices[2], indices[1]]);
+  
+  // Packed normal : z magnitude in bits 8..16, z sign in bit 3.
+  let normal = primitive.get_vertices_ref()[0].m_normal;
+  let flipped_normal = flipped_primitive.get_vertices_ref()[0].m_normal;
+  assert_eq!(normal & 0xFF00, flipped_normal & 0xFF00);
+  assert_eq!(normal & 0x8, 0);
+  assert_eq!(flipped_normal & 0x8, 0x8);
+  return Ok(());
+}
+
+#[test]
+fn test_asset_bounds() -> Result<(), EnumAssetError> {
+  // Two triangles spanning (-1, -2, -3) to (4, 5, 6).
+  let file_path = std::env::temp_dir().join("wave_test_asset_bounds.obj");
+  std::fs::write(&file_path, "v -1.0 0.0 0.0\nv 4.0 -2.0 1.0\nv 0.0 5.0 -3.0\nv 2.0 1.0 6.0\nf 1 2 3\nf 2 3 4\n")?;
+  
+  let mut asset_loader = AssetLoader::new();
+  let mut shape = REntity::new(asset_loader.load(file_path.to_str().unwrap())?, EnumPrimitiveShading::default(), "Shape");
+  
+  let bounds = shape.get_bounds();
+  assert_eq!(bounds.m_min, Vec3::new(&[-1.0, -2.0, -3.0]));
+  assert_eq!(bounds.m_max, Vec3::new(&[4.0, 5.0, 6.0]));
+  
+  // World bounds follow the entity around, while local bounds stay put.
+  shape.translate(10.0, -10.0, 0.0);
+  let world_bounds = shape.get_world_bounds();
+  assert!((world_bounds.m_min - Vec3::new(&[9.0, -12.0, -3.0])).vec_len() < 1e-4);
+  assert!((world_bounds.m_max - Vec3::new(&[14.0, -5.0, 6.0])).vec_len() < 1e-4);
+  assert_eq!(shape.get_bounds().m_min, bounds.m_min);
+  return Ok(());
+}
+
+#[test]
+fn test_instance_transforms() -> Result<(), EnumAssetError> {
+  let asset_loader = AssetLoader::new();
+  let mut logos = REntity::new_instanced(asset_loader.load("res/assets/n64_logo/n64_logo.obj")?,
+    EnumPrimitiveShading::default(), "Logos", 5);
+  
+  assert!(logos.is_instanced());
+  assert_eq!(logos.get_instance_count(), 5);
+  assert!(!REntity::default().is_instanced());
+  
+  // Moving one instance leaves the others where they were.
+  let moved: Mat4 = Mat4::translation_matrix(&Vec3::new(&[3.0, 0.0, -2.0]));
+  logos.set_instance_transform(2, moved);
+  assert!(logos.has_changed());
+  assert_eq!(logos.get_instance_transform(2), Some(moved));
+  for index in [0usize, 1, 3, 4] {
+    assert_eq!(logos.get_instance_transform(index), Some(Mat4::new(1.0)));
+  }
+  
+  // Out of range instances are ignored.
+  logos.set_instance_transform(5, moved);
+  assert_eq!(logos.get_instance_count(), 5);
+  assert!(logos.get_instance_transform(5).is_none());
+  return Ok(());
+}
+
+#[test]
+fn test_asset_cache() -> Result<(), EnumAssetError> {
+  let mut asset_loader = AssetLoader::new();
+  let asset = asset_loader.load("res/assets/n64_logo/n64_logo.obj")?;
+  let same_asset = asset_loader.load("./res/assets/n64_logo/n64_logo.obj")?;
+  
+  // Both spellings of the path should resolve to the same cached data.
+  assert!(Rc::ptr_eq(&asset, &same_asset));
+  assert_eq!(asset_loader.get_cache_size(), 1);
+  
+  let mut uncached_loader = AssetLoader::new();
+  uncached_loader.set_hint(EnumAssetHint::Cache(false));
+  let first_copy = uncached_loader.load("res/assets/n64_logo/n64_logo.obj")?;
+  let second_copy = uncached_loader.load("res/assets/n64_logo/n64_logo.obj")?;
+  assert!(!Rc::ptr_eq(&first_copy, &second_copy));
+  assert_eq!(uncached_loader.get_cache_size(), 0);
+  
+  asset_loader.clear_cache();
+  assert_eq!(asset_loader.get_cache_size(), 0);
+  return Ok(());
+}
+
+#[test]
+fn test_folder_loading_progress() -> Result<(), EnumAssetError> {
+  let asset_loader = AssetLoader::new();
+  let mut progress: Vec<(usize, usize)> = Vec::new();
+  let result = asset_loader.load_from_folder_with_progress("res/assets/n64_logo",
+    |loaded, total| progress.push((loaded, total)))?;
+  
+  // The material file isn't a model : it still counts toward progress, and is reported as skipped.
+  assert_eq!(progress, vec![(1, 2), (2, 2)]);
+  assert_eq!(result.m_assets.len(), 1);
+  assert_eq!(result.m_failures.len(), 1);
+  assert_eq!(result.m_failures[0].0.extension().and_then(|extension| extension.to_str()), Some("mtl"));
+  assert_eq!(asset_loader.load_from_folder("res/assets/n64_logo")?.len(), 1);
+  
+  // Same outcome from a worker thread.
+  let mut handle = asset_loader.load_from_folder_async("res/assets/n64_logo");
+  let async_result: FolderLoadResult<_> = handle.wait()?;
+  assert_eq!(handle.get_progress(), (2, 2));
+  assert!(handle.is_done());
+  assert_eq!((async_result.m_assets.len(), async_result.m_failures.len()), (1, 1));
+  
+  assert_eq!(asset_loader.load_from_folder_with_progress("res/assets/missing", |_, _| {}).err(),
+    Some(EnumAssetError::InvalidPath));
+  assert!(matches!(asset_loader.load_from_folder_async("res/assets/missing").wait(), Err(EnumAssetError::InvalidPath)));
+  return Ok(());
+}
+
+#[test]
+fn test_scene_save_load() -> Result<(), EnumSceneError> {
+  let mut scene = Scene::new();
+  
+  let mut parent = REntity::default();
+  parent.translate(5.0, 1.0, -2.0);
+  parent.scale(1.0, 0.5, 0.0);
+  let parent_id = scene.add_entity(parent);
+  
+  let mut child = REntity::default();
+  child.rotate(0.0, 45.0, 0.0);
+  child.rotate_quaternion(Quaternion::from_axis_angle(&Vec3::new(&[0.0, 0.0, 1.0]), 30.0));
+  scene.add_child(parent_id, child)?;
+  scene.update_transforms();
+  
+  let file_path = std::env::temp_dir().join("wave-engine-test-scene.json");
+  let file_path = file_path.to_str().unwrap();
+  scene.save(file_path)?;
+  let loaded_scene = Scene::load(file_path, &AssetLoader::new())?;
+  let _ = std::fs::remove_file(file_path);
+  
+  // Same hierarchy, same transforms.
+  assert_eq!(loaded_scene.len(), scene.len());
+  assert_eq!(loaded_scene.get_children(parent_id), scene.get_children(parent_id));
+  for ((node_id, entity, world_matrix), (_, loaded_entity, loaded_world_matrix)) in scene.iter().zip(loaded_scene.iter()) {
+    assert_eq!(loaded_entity.get_name(), entity.get_name());
+    assert_eq!(loaded_entity.get_asset_path(), None);
+    assert_eq!(loaded_scene.get_parent(node_id), scene.get_parent(node_id));
+    assert_eq!(loaded_world_matrix, world_matrix);
+  }
+  
+  assert!(matches!(Scene::load("missing-scene.json", &AssetLoader::new()), Err(EnumSceneError::IoError(_))));
+  return Ok(());
+}
+
+#[test]
+fn test_classify_dropped_files() {
+  let dropped = vec![PathBuf::from("res/assets/awp/awp.obj"), PathBuf::from("res/textures/default.PNG"),
+    PathBuf::from("res/shaders/glsl_420.frag"), PathBuf::from("README.md"), PathBuf::from("res/assets/no_extension")];
+  
+  let classified = classify_dropped_files(&dropped);
+  
+  // Drop order is kept, and extensions are case-insensitive.
+  assert_eq!(classified, vec![
+    (dropped[0].clone(), EnumAssetKind::Mesh),
+    (dropped[1].clone(), EnumAssetKind::Texture),
+    (dropped[2].clone(), EnumAssetKind::Shader),
+    (dropped[3].clone(), EnumAssetKind::Unknown),
+    (dropped[4].clone(), EnumAssetKind::Unknown),
+  ]);
+}
+
+#[test]
+fn test_transform_animation() {
+  let mut animated_cube = REntity::default();
+  let target_orientation = Quaternion::from_axis_angle(&Vec3::new(&[0.0, 1.0, 0.0]), 90.0);
+  animated_cube.animate_to(Vec3::new(&[10.0, 0.0, 10.0]), target_orientation, 2.0, EnumEasing::Linear);
+  assert!(animated_cube.is_animating());
+  
+  // Halfway through a linear tween lands on the midpoint.
+  assert!(animated_cube.update_animation(1.0));
+  let mut expected_cube = REntity::default();
+  expected_cube.translate(5.0, 0.0, 0.0);
+  expected_cube.set_orientation(Some(Quaternion::slerp(&Quaternion::default(), &target_orientation, 0.5)));
+  
+  let animated_matrix = animated_cube.get_matrix();
+  let expected_matrix = expected_cube.get_matrix();
+  for row in 0..4usize {
+    for col in 0..4usize {
+      assert!((animated_matrix[row][col] - expected_matrix[row][col]).abs() < 1e-5);
+    }
+  }
+  assert!(animated_cube.has_changed());
+  
+  // Overshooting the duration snaps to the target and ends the animation.
+  assert!(!animated_cube.update_animation(5.0));
+  assert!(!animated_cube.is_animating());
+  assert!((animated_cube.get_matrix()[0][3] - 10.0).abs() < 1e-5);
+  
+  for easing in [EnumEasing::Linear, EnumEasing::EaseInOut, EnumEasing::Bounce] {
+    assert_eq!(easing.apply(0.0), 0.0);
+    assert!((easing.apply(1.0) - 1.0).abs() < 1e-5);
+  }
+}
+
+#[test]
+fn test_skeletal_animation() -> Result<(), EnumAssetError> {
+  let mut skeleton = Skeleton::new();
+  let root = skeleton.add_node("root", None, Mat4::default());
+  skeleton.add_node("arm", Some(root), Mat4::default());
+  let arm_bone = skeleton.add_bone("arm", Mat4::default());
+  
+  let mut arm_channel = AnimationChannel::new("arm");
+  arm_channel.m_positions = vec![(0.0, Vec3::default()), (1.0, Vec3::new(&[2.0, 0.0, 0.0]))];
+  let wave_clip = AnimationClip::new("wave", 1.0, vec![arm_channel]);
+  
+  // Keys get interpolated in between, and held past the last one.
+  assert!((skeleton.compute_pose(Some(&wave_clip), 0.5)[arm_bone][0][3] - 1.0).abs() < 1e-5);
+  assert!((skeleton.compute_pose(Some(&wave_clip), 3.0)[arm_bone][0][3] - 2.0).abs() < 1e-5);
+  assert_eq!(skeleton.compute_pose(None, 0.5)[arm_bone][0][3], 0.0);
+  assert!((wave_clip.resolve_time(1.25, true) - 0.25).abs() < 1e-5);
+  assert_eq!(wave_clip.resolve_time(1.25, false), 1.0);
+  
+  let mut rigged_entity = REntity::default();
+  assert_eq!(rigged_entity.play_animation("wave", false), Err(EnumAssetError::InvalidAnimation));
+  rigged_entity.set_skeleton(skeleton, vec![wave_clip]);
+  assert_eq!(rigged_entity.play_animation("jump", false), Err(EnumAssetError::InvalidAnimation));
+  
+  // Non-looping clips clamp to their last frame.
+  rigged_entity.play_animation("wave", false)?;
+  rigged_entity.update_skeleton(1.5);
+  assert_eq!(rigged_entity.get_playing_animation(), Some(("wave", 1.0)));
+  assert!((rigged_entity.get_bone_matrices()[arm_bone][0][3] - 2.0).abs() < 1e-5);
+  
+  // Only the heaviest influences are kept, then normalized.
+  let mut vertex = Vertex::default();
+  for (bone_index, weight) in [(1, 0.1), (2, 0.4), (3, 0.2), (4, 0.2), (5, 0.1)] {
+    vertex.add_bone_influence(bone_index, weight);
+  }
+  vertex.normalize_bone_weights();
+  assert!(!vertex.get_bone_ids().contains(&1) || !vertex.get_bone_ids().contains(&5));
+  assert!(vertex.get_bone_ids().contains(&2));
+  assert!((vertex.m_bone_weights.iter().sum::<f32>() - 1.0).abs() < 1e-5);
+  return Ok(());
+}
@@ -0,0 +1,60 @@
+/*
+ MIT License
+
+ Copyright (c) 2023 Nami Reghbati
+
+ Permission is hereby granted, free of charge, to any person obtaining a copy
+ of this software and associated documentation files (the "Software"), to deal
+ in the Software without restriction, including without limitation the rights
+ to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ copies of the Software, and to permit persons to whom the Software is
+ furnished to do so, subject to the following conditions:
+
+ The above copyright notice and this permission notice shall be included in all
+ copies or substantial portions of the Software.
+
+ THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ FITNESS FOR A PARTICULAR PURPOSE AND NON INFRINGEMENT. IN NO EVENT SHALL THE
+ AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ SOFTWARE.
+*/
+
+use std::path::Path;
+
+use wave_editor::wave_core::audio::{AudioEngine, AudioListener, EnumAudioError, EnumAudioFormat};
+use wave_editor::wave_core::math::Vec3;
+
+#[test]
+fn test_audio_formats() {
+  assert_eq!(EnumAudioFormat::from_path(Path::new("res/sounds/jump.wav")), Ok(EnumAudioFormat::Wav));
+  assert_eq!(EnumAudioFormat::from_path(Path::new("res/sounds/theme.OGG")), Ok(EnumAudioFormat::Ogg));
+  assert_eq!(EnumAudioFormat::from_path(Path::new("res/sounds/theme.mp3")), Err(EnumAudioError::UnsupportedFormat));
+  assert_eq!(EnumAudioFormat::from_path(Path::new("res/sounds/theme")), Err(EnumAudioError::UnsupportedFormat));
+}
+
+#[test]
+fn test_audio_without_device() {
+  // Nothing can play until an output device has been opened.
+  let mut audio_engine = AudioEngine::new();
+  assert!(!audio_engine.is_applied());
+  assert_eq!(audio_engine.play_sound("res/sounds/jump.wav"), Err(EnumAudioError::NotApplied));
+  assert!(audio_engine.play_looping("res/sounds/theme.ogg").is_err());
+  assert_eq!(audio_engine.get_sound_count(), 0);
+  
+  // Muting and restoring the master volume doesn't need any sound playing.
+  audio_engine.set_master_volume(-1.0);
+  assert_eq!(audio_engine.get_master_volume(), 0.0);
+  audio_engine.set_master_volume(0.5);
+  assert_eq!(audio_engine.get_master_volume(), 0.5);
+  
+  let listener = AudioListener {
+    m_position: Vec3::new(&[0.0, 1.0, 0.0]),
+    m_right: Vec3::new(&[2.0, 0.0, 0.0]),
+  };
+  audio_engine.set_listener(listener);
+  assert_eq!(audio_engine.get_listener().get_left_ear().x, -0.1);
+  assert_eq!(audio_engine.get_listener().get_right_ear().x, 0.1);
+}
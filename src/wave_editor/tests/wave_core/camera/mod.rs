@@ -0,0 +1,329 @@
+/*
+ MIT License
+
+ Copyright (c) 2023 Nami Reghbati
+
+ Permission is hereby granted, free of charge, to any person obtaining a copy
+ of this software and associated documentation files (the "Software"), to deal
+ in the Software without restriction, including without limitation the rights
+ to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ copies of the Software, and to permit persons to whom the Software is
+ furnished to do so, subject to the following conditions:
+
+ The above copyright notice and this permission notice shall be included in all
+ copies or substantial portions of the Software.
+
+ THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ FITNESS FOR A PARTICULAR PURPOSE AND NON INFRINGEMENT. IN NO EVENT SHALL THE
+ AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ SOFTWARE.
+*/
+
+use wave_editor::wave_core::{EmptyApp, Engine, EnumEngineError, TraitApply};
+use wave_editor::wave_core::assets::r_assets::REntity;
+use wave_editor::wave_core::camera::{C_CAMERA_UBO_BINDING, Camera, EnumCameraType, FpsController, OrbitController};
+use wave_editor::wave_core::dependencies::gl;
+use wave_editor::wave_core::events::EnumEvent;
+use wave_editor::wave_core::graphics::renderer::{EnumRendererApi, Renderer};
+use wave_editor::wave_core::graphics::shader::{EnumShaderSource, EnumShaderStageType, Shader, ShaderStage};
+use wave_editor::wave_core::graphics::uniform_buffer::TraitUniformData;
+use wave_editor::wave_core::layers::Layer;
+use wave_editor::wave_core::math::{Aabb, Mat4, Vec2, Vec3};
+use wave_editor::wave_core::window::Window;
+
+#[ignore]
+#[test]
+fn test_submit_camera() -> Result<(), EnumEngineError> {
+  let layer = Layer::new("Camera submit", EmptyApp::default());
+  let window = Window::new(EnumRendererApi::OpenGL);
+  let renderer = Renderer::new(EnumRendererApi::OpenGL);
+  let mut engine = Engine::new(window, renderer, vec![layer]);
+  engine.apply()?;
+  
+  assert!(Engine::get_active_camera().is_none());
+  
+  // The camera block only gets allocated once a shader using it is applied.
+  let vertex_shader = ShaderStage::new(EnumShaderStageType::Vertex,
+    EnumShaderSource::FromFile(String::from("res/shaders/glsl_460.vert")));
+  let fragment_shader = ShaderStage::new(EnumShaderStageType::Fragment,
+    EnumShaderSource::FromFile(String::from("res/shaders/glsl_460.frag")));
+  let mut shader = Shader::default();
+  shader.push_stage(vertex_shader)?;
+  shader.push_stage(fragment_shader)?;
+  shader.apply()?;
+  let mut cube = REntity::default();
+  cube.apply(&mut shader)?;
+  
+  let camera = Camera::new(EnumCameraType::Perspective(75, 16.0 / 9.0, 0.01, 1000.0),
+    Some([Vec3::new(&[0.0, 2.0, -5.0]), Vec3::default(), Vec3::new(&[1.0, 1.0, 1.0])]));
+  let view_projection = camera.get_projection_matrix() * camera.get_view_matrix();
+  let matrices = [camera.get_view_matrix(), camera.get_projection_matrix()];
+  
+  engine.get_renderer_mut().submit_camera(camera)?;
+  
+  let active_camera = Engine::get_active_camera();
+  assert!(active_camera.is_some());
+  assert_eq!(active_camera.unwrap().get_projection_matrix() * active_camera.unwrap().get_view_matrix(), view_projection);
+  
+  // The view and projection are uploaded right away, without waiting for the next frame.
+  let mut buffer_id: i32 = 0;
+  let mut camera_data: Vec<u8> = vec![0; 2 * Mat4::get_size()];
+  unsafe {
+    gl::GetIntegeri_v(gl::UNIFORM_BUFFER_BINDING, C_CAMERA_UBO_BINDING, &mut buffer_id);
+    gl::GetNamedBufferSubData(buffer_id as u32, 0, camera_data.len() as isize,
+      camera_data.as_mut_ptr() as *mut std::ffi::c_void);
+  }
+  assert_ne!(buffer_id, 0);
+  assert_eq!(camera_data, matrices.to_std140());
+  
+  return engine.free();
+}
+
+#[ignore]
+#[test]
+fn test_split_viewports() -> Result<(), EnumEngineError> {
+  let layer = Layer::new("Split viewports", EmptyApp::default());
+  let window = Window::new(EnumRendererApi::OpenGL);
+  let renderer = Renderer::new(EnumRendererApi::OpenGL);
+  let mut engine = Engine::new(window, renderer, vec![layer]);
+  engine.apply()?;
+  
+  let left = Camera::new(EnumCameraType::Perspective(75, 1.0, 0.1, 100.0), None);
+  let right = Camera::new(EnumCameraType::Perspective(75, 1.0, 0.1, 100.0),
+    Some([Vec3::new(&[0.0, 2.0, -5.0]), Vec3::default(), Vec3::new(&[1.0, 1.0, 1.0])]));
+  
+  let renderer = engine.get_renderer_mut();
+  renderer.set_viewports(vec![(left, (0, 0, 400, 600)), (right, (400, 0, 400, 600))])?;
+  assert_eq!(renderer.get_viewport_count(), 2);
+  
+  // Cursor picks the viewport it's over.
+  assert_eq!(renderer.get_viewport_at(Vec2::new(&[100.0, 300.0])), Some(0));
+  assert_eq!(renderer.get_viewport_at(Vec2::new(&[650.0, 10.0])), Some(1));
+  assert_eq!(renderer.get_viewport_at(Vec2::new(&[900.0, 10.0])), None);
+  
+  // Each camera takes the aspect ratio of its own region.
+  let expected_projection = Camera::new(EnumCameraType::Perspective(75, 400.0 / 600.0, 0.1, 100.0), None)
+    .get_projection_matrix();
+  assert_eq!(renderer.get_viewport_camera_mut(1).unwrap().get_projection_matrix(), expected_projection);
+  
+  renderer.on_render()?;
+  
+  renderer.set_viewports(vec![])?;
+  assert_eq!(renderer.get_viewport_count(), 0);
+  return Ok(());
+}
+
+#[test]
+fn test_frustum_culling() {
+  // Default view, looking down -Z from the origin.
+  let camera = Camera::new(EnumCameraType::Perspective(75, 16.0 / 9.0, 0.1, 100.0), None);
+  let frustum = camera.get_frustum();
+  
+  let in_front = Aabb::new(Vec3::new(&[-0.5, -0.5, -5.5]), Vec3::new(&[0.5, 0.5, -4.5]));
+  let behind = Aabb::new(Vec3::new(&[-0.5, -0.5, 4.5]), Vec3::new(&[0.5, 0.5, 5.5]));
+  let too_far = Aabb::new(Vec3::new(&[-0.5, -0.5, -200.5]), Vec3::new(&[0.5, 0.5, -199.5]));
+  
+  assert!(frustum.intersects_aabb(&in_front));
+  assert!(!frustum.intersects_aabb(&behind));
+  assert!(!frustum.intersects_aabb(&too_far));
+}
+
+#[ignore]
+#[test]
+fn test_renderer_frustum_culling() -> Result<(), EnumEngineError> {
+  let layer = Layer::new("Frustum culling", EmptyApp::default());
+  let window = Window::new(EnumRendererApi::OpenGL);
+  let renderer = Renderer::new(EnumRendererApi::OpenGL);
+  let mut engine = Engine::new(window, renderer, vec![layer]);
+  engine.apply()?;
+  
+  let vertex_shader = ShaderStage::new(EnumShaderStageType::Vertex,
+    EnumShaderSource::FromFile(String::from("res/shaders/glsl_460.vert")));
+  let fragment_shader = ShaderStage::new(EnumShaderStageType::Fragment,
+    EnumShaderSource::FromFile(String::from("res/shaders/glsl_460.frag")));
+  let mut shader = Shader::default();
+  shader.push_stage(vertex_shader)?;
+  shader.push_stage(fragment_shader)?;
+  shader.apply()?;
+  
+  // Default view looks down -Z from the origin, while the default cube sits 10 units in front of it. Move it 10 units
+  // behind the camera instead.
+  let mut behind = REntity::default();
+  behind.translate(0.0, 0.0, -20.0);
+  behind.apply(&mut shader)?;
+  
+  let renderer = engine.get_renderer_mut();
+  renderer.submit_camera(Camera::new(EnumCameraType::Perspective(75, 16.0 / 9.0, 0.1, 100.0), None))?;
+  assert!(renderer.is_frustum_culling());
+  renderer.on_render()?;
+  assert_eq!(renderer.get_culled_count(), 1);
+  
+  renderer.toggle_frustum_culling(false)?;
+  assert!(!renderer.is_frustum_culling());
+  renderer.on_render()?;
+  assert_eq!(renderer.get_culled_count(), 0);
+  return Ok(());
+}
+
+#[test]
+fn test_fps_controller() {
+  let mut controller = FpsController::new(Vec3::default(), 10.0, 0.1);
+  
+  // Looking up past the limit clamps to the maximum pitch.
+  controller.process_input(Vec2::new(&[0.0, -5000.0]), Vec3::default(), 0.0);
+  assert_eq!(controller.get_pitch(), FpsController::C_MAX_PITCH);
+  controller.process_input(Vec2::new(&[0.0, 5000.0]), Vec3::default(), 0.0);
+  assert_eq!(controller.get_pitch(), -FpsController::C_MAX_PITCH);
+  
+  // Level out and turn right by 90 degrees, then walk forward for one second.
+  controller.process_input(Vec2::new(&[900.0, -890.0]), Vec3::default(), 0.0);
+  assert!(controller.get_pitch().abs() < 1e-4);
+  controller.process_input(Vec2::default(), Vec3::new(&[0.0, 0.0, 1.0]), 1.0);
+  
+  let position = controller.get_position();
+  assert!((position.x - 10.0).abs() < 1e-3);
+  assert!(position.y.abs() < 1e-3 && position.z.abs() < 1e-3);
+  
+  let mut camera = Camera::new(EnumCameraType::Perspective(75, 16.0 / 9.0, 0.1, 100.0), None);
+  controller.apply_to(&mut camera);
+  let camera_position = camera.get_position();
+  let camera_forward = camera.get_forward();
+  assert!((camera_position.x - 10.0).abs() < 1e-3);
+  assert!((camera_forward.x - 1.0).abs() < 1e-3);
+  
+  // Something right in front of the camera should now be visible, and what was in front before should not.
+  let ahead = Aabb::new(Vec3::new(&[14.5, -0.5, -0.5]), Vec3::new(&[15.5, 0.5, 0.5]));
+  let behind = Aabb::new(Vec3::new(&[4.5, -0.5, -0.5]), Vec3::new(&[5.5, 0.5, 0.5]));
+  assert!(camera.get_frustum().intersects_aabb(&ahead));
+  assert!(!camera.get_frustum().intersects_aabb(&behind));
+}
+
+#[test]
+fn test_orbit_controller() {
+  let target = Vec3::new(&[1.0, 2.0, 3.0]);
+  let mut orbit = OrbitController::new(target, 10.0);
+  orbit.set_radius_limits(2.0, 20.0);
+  
+  // Starts right in front of the target, on +Z.
+  let eye = orbit.get_eye();
+  assert!((eye.x - 1.0).abs() < 1e-4 && (eye.y - 2.0).abs() < 1e-4 && (eye.z - 13.0).abs() < 1e-4);
+  assert_eq!(orbit.get_view_matrix(), Mat4::apply_look_at(&eye, &target, &Vec3::new(&[0.0, 1.0, 0.0])));
+  
+  // Scrolling zooms within the radius limits, without consuming the event when not dragging.
+  assert!(!orbit.on_event(&EnumEvent::MouseScrollEvent(0.0, 5.0)));
+  assert_eq!(orbit.get_radius(), 5.0);
+  orbit.zoom(100.0);
+  assert_eq!(orbit.get_radius(), 2.0);
+  orbit.zoom(-100.0);
+  assert_eq!(orbit.get_radius(), 20.0);
+  
+  // Dragging never flips over the poles.
+  orbit.rotate(Vec2::new(&[0.0, 10000.0]));
+  assert_eq!(orbit.get_elevation(), OrbitController::C_MAX_ELEVATION);
+  
+  let mut camera = Camera::new(EnumCameraType::Perspective(75, 16.0 / 9.0, 0.1, 100.0), None);
+  camera.set_orbit_controller(Some(orbit));
+  let orbit_eye = camera.get_orbit_controller_mut().unwrap().get_eye();
+  let camera_position = camera.get_position();
+  assert!((camera_position.x - orbit_eye.x).abs() < 1e-4);
+  assert!((camera_position.y - orbit_eye.y).abs() < 1e-4);
+  assert!((camera_position.z - orbit_eye.z).abs() < 1e-4);
+  
+  // The target stays in view from anywhere on the orbit.
+  let target_box = Aabb::new(Vec3::new(&[0.5, 1.5, 2.5]), Vec3::new(&[1.5, 2.5, 3.5]));
+  assert!(camera.get_frustum().intersects_aabb(&target_box));
+}
+
+#[test]
+fn test_screen_point_to_ray() {
+  let camera = Camera::new(EnumCameraType::Perspective(75, 4.0 / 3.0, 0.1, 100.0), None);
+  
+  // The center of the screen goes straight ahead, down -Z.
+  let (origin, direction) = camera.screen_point_to_ray(Vec2::new(&[400.0, 300.0]), (800, 600));
+  assert!(origin.x.abs() < 1e-3 && origin.y.abs() < 1e-3);
+  assert!(direction.x.abs() < 1e-3 && direction.y.abs() < 1e-3 && (direction.z + 1.0).abs() < 1e-3);
+  
+  // Picking should return the closest of two cubes in line. The default cube already sits 10 units ahead.
+  let mut near_cube = REntity::default();
+  near_cube.translate(0.0, 0.0, -5.0);
+  let far_cube = REntity::default();
+  
+  let near_distance = near_cube.intersects_ray(origin, direction).unwrap();
+  let far_distance = far_cube.intersects_ray(origin, direction).unwrap();
+  assert!(near_distance < far_distance);
+  assert!((near_distance - (4.5 - 0.1)).abs() < 1e-2);
+  
+  // Top left corner points away from both.
+  let (origin, direction) = camera.screen_point_to_ray(Vec2::new(&[0.0, 0.0]), (800, 600));
+  assert!(near_cube.intersects_ray(origin, direction).is_none());
+}
+
+#[test]
+fn test_world_to_screen() {
+  let camera = Camera::new(EnumCameraType::Perspective(75, 4.0 / 3.0, 0.1, 100.0), None);
+  
+  // Straight ahead lands in the center of the screen, and round trips through the picking ray.
+  let center = camera.world_to_screen(Vec3::new(&[0.0, 0.0, -5.0]), (800, 600)).unwrap();
+  assert!((center.x - 400.0).abs() < 1e-2 && (center.y - 300.0).abs() < 1e-2);
+  
+  let (origin, direction) = camera.screen_point_to_ray(Vec2::new(&[100.0, 50.0]), (800, 600));
+  let handle: Vec3<f32> = origin + direction * Vec3::new(&[10.0, 10.0, 10.0]);
+  let projected = camera.world_to_screen(handle, (800, 600)).unwrap();
+  assert!((projected.x - 100.0).abs() < 1e-1 && (projected.y - 50.0).abs() < 1e-1);
+  
+  assert!(camera.hit_test_handle(handle, Vec2::new(&[104.0, 53.0]), (800, 600), 6.0));
+  assert!(!camera.hit_test_handle(handle, Vec2::new(&[120.0, 50.0]), (800, 600), 6.0));
+  
+  // Behind the camera or without a viewport, nothing can be hit.
+  assert!(camera.world_to_screen(Vec3::new(&[0.0, 0.0, 5.0]), (800, 600)).is_none());
+  assert!(camera.world_to_screen(Vec3::new(&[0.0, 0.0, -5.0]), (0, 0)).is_none());
+}
+
+#[test]
+fn test_camera_look_at() {
+  let mut camera = Camera::new(EnumCameraType::Perspective(75, 16.0 / 9.0, 0.1, 100.0), None);
+  let eye: Vec3<f32> = Vec3::new(&[4.0, 3.0, 8.0]);
+  let target: Vec3<f32> = Vec3::new(&[-1.0, 0.5, -2.0]);
+  camera.set_position(eye);
+  camera.look_at(target);
+  
+  assert_eq!(camera.get_look_at_target(), Some(target));
+  let view_matrix: Mat4 = camera.get_view_matrix();
+  let expected: Mat4 = Mat4::apply_look_at(&eye, &target, &Vec3::new(&[0.0, 1.0, 0.0]));
+  for row in 0..4usize {
+    for column in 0..4usize {
+      assert!((view_matrix[row][column] - expected[row][column]).abs() < 1e-4);
+    }
+  }
+  
+  // The target lands right in the middle of the screen.
+  let center = camera.world_to_screen(target, (1280, 720)).unwrap();
+  assert!((center.x - 640.0).abs() < 1e-2 && (center.y - 360.0).abs() < 1e-2);
+  
+  camera.stop_looking_at();
+  assert!(camera.get_look_at_target().is_none());
+}
+
+#[test]
+fn test_orthographic_projection() -> Result<(), EnumEngineError> {
+  let mut camera = Camera::new(EnumCameraType::Orthographic(-4.0, 4.0, -3.0, 3.0, 0.1, 100.0), None);
+  
+  // No perspective divide, the depth of the point doesn't move it on screen.
+  let near = camera.world_to_screen(Vec3::new(&[2.0, 1.5, -1.0]), (800, 600)).unwrap();
+  let far = camera.world_to_screen(Vec3::new(&[2.0, 1.5, -50.0]), (800, 600)).unwrap();
+  assert!((near.x - 600.0).abs() < 1e-2 && (near.y - 150.0).abs() < 1e-2);
+  assert_eq!(near, far);
+  
+  // Doubling the width keeps the vertical extent, and widens the horizontal one to preserve the aspect ratio.
+  assert!(camera.on_event(&EnumEvent::FramebufferEvent(1600, 600))?);
+  let resized = camera.world_to_screen(Vec3::new(&[2.0, 1.5, -1.0]), (1600, 600)).unwrap();
+  assert!((resized.x - 1000.0).abs() < 1e-2 && (resized.y - 150.0).abs() < 1e-2);
+  
+  // Minimizing the window leaves the bounds untouched.
+  camera.on_event(&EnumEvent::FramebufferEvent(0, 0))?;
+  assert_eq!(camera.world_to_screen(Vec3::new(&[2.0, 1.5, -1.0]), (1600, 600)).unwrap(), resized);
+  return Ok(());
+}
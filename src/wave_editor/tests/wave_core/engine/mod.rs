@@ -0,0 +1,184 @@
+/*
+ MIT License
+
+ Copyright (c) 2023 Nami Reghbati
+
+ Permission is hereby granted, free of charge, to any person obtaining a copy
+ of this software and associated documentation files (the "Software"), to deal
+ in the Software without restriction, including without limitation the rights
+ to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ copies of the Software, and to permit persons to whom the Software is
+ furnished to do so, subject to the following conditions:
+
+ The above copyright notice and this permission notice shall be included in all
+ copies or substantial portions of the Software.
+
+ THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ FITNESS FOR A PARTICULAR PURPOSE AND NON INFRINGEMENT. IN NO EVENT SHALL THE
+ AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ SOFTWARE.
+*/
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use wave_editor::wave_core::{Engine, EnumEngineError, EnumErrorPolicy};
+use wave_editor::wave_core::events::EnumEvent;
+use wave_editor::wave_core::graphics::renderer::{EnumRendererApi, EnumRendererError, Renderer};
+use wave_editor::wave_core::layers::{EnumLayerType, Layer, TraitLayer};
+use wave_editor::wave_core::window::Window;
+
+// Fails every update with a recoverable error, then with a fatal one after a few frames to end the loop.
+struct FaultyApp {
+  m_update_count: Rc<Cell<u32>>,
+}
+
+impl TraitLayer for FaultyApp {
+  fn get_type(&self) -> EnumLayerType {
+    return EnumLayerType::App;
+  }
+  
+  fn on_apply(&mut self) -> Result<(), EnumEngineError> {
+    return Ok(());
+  }
+  
+  fn on_sync_event(&mut self) -> Result<(), EnumEngineError> {
+    return Ok(());
+  }
+  
+  fn on_async_event(&mut self, _event: &EnumEvent) -> Result<bool, EnumEngineError> {
+    return Ok(false);
+  }
+  
+  fn on_update(&mut self, _time_step: f64) -> Result<(), EnumEngineError> {
+    self.m_update_count.set(self.m_update_count.get() + 1);
+    
+    if self.m_update_count.get() >= 5 {
+      return Err(EnumEngineError::RendererError(EnumRendererError::ContextError));
+    }
+    return Err(EnumEngineError::RendererError(EnumRendererError::EntityNotFound));
+  }
+  
+  fn on_render(&mut self) -> Result<(), EnumEngineError> {
+    return Ok(());
+  }
+  
+  fn free(&mut self) -> Result<(), EnumEngineError> {
+    return Ok(());
+  }
+  
+  fn to_string(&self) -> String {
+    return String::from("[Faulty App]");
+  }
+}
+
+#[ignore]
+#[test]
+fn test_error_policy_log_and_continue() {
+  let update_count = Rc::new(Cell::new(0));
+  let layer = Layer::new("Faulty app", FaultyApp { m_update_count: update_count.clone() });
+  let window = Window::new(EnumRendererApi::OpenGL);
+  let renderer = Renderer::new(EnumRendererApi::OpenGL);
+  let mut engine = Engine::new(window, renderer, vec![layer]);
+  
+  engine.set_error_policy(EnumErrorPolicy::LogAndContinue);
+  let result = engine.run();
+  
+  // Recoverable errors kept the loop going until the fatal one.
+  assert!(result.is_err_and(|err| err.is_fatal()));
+  assert_eq!(update_count.get(), 5);
+}
+
+#[ignore]
+#[test]
+fn test_target_fps() {
+  let window = Window::new(EnumRendererApi::OpenGL);
+  let renderer = Renderer::new(EnumRendererApi::OpenGL);
+  let mut engine = Engine::new(window, renderer, vec![]);
+  
+  // Uncapped by default.
+  assert_eq!(engine.get_target_fps(), None);
+  
+  engine.set_target_fps(Some(60));
+  assert_eq!(engine.get_target_fps(), Some(60));
+  
+  engine.set_target_fps(Some(0));
+  assert_eq!(engine.get_target_fps(), None);
+  
+  engine.set_target_fps(Some(144));
+  engine.set_target_fps(None);
+  assert_eq!(engine.get_target_fps(), None);
+}
+
+// Records the engine's frame count and uptime on every update, then stops the loop after a few frames.
+struct ClockApp {
+  m_samples: Rc<std::cell::RefCell<Vec<(u64, f64)>>>,
+}
+
+impl TraitLayer for ClockApp {
+  fn get_type(&self) -> EnumLayerType {
+    return EnumLayerType::App;
+  }
+  
+  fn on_apply(&mut self) -> Result<(), EnumEngineError> {
+    return Ok(());
+  }
+  
+  fn on_sync_event(&mut self) -> Result<(), EnumEngineError> {
+    return Ok(());
+  }
+  
+  fn on_async_event(&mut self, _event: &EnumEvent) -> Result<bool, EnumEngineError> {
+    return Ok(false);
+  }
+  
+  fn on_update(&mut self, _time_step: f64) -> Result<(), EnumEngineError> {
+    let frame_count = Engine::get_active_frame_count().ok_or(EnumEngineError::NoActiveEngine)?;
+    let uptime = Engine::get_active_uptime().ok_or(EnumEngineError::NoActiveEngine)?;
+    self.m_samples.borrow_mut().push((frame_count, uptime));
+    
+    if frame_count >= 4 {
+      return Err(EnumEngineError::RendererError(EnumRendererError::ContextError));
+    }
+    return Ok(());
+  }
+  
+  fn on_render(&mut self) -> Result<(), EnumEngineError> {
+    return Ok(());
+  }
+  
+  fn free(&mut self) -> Result<(), EnumEngineError> {
+    return Ok(());
+  }
+  
+  fn to_string(&self) -> String {
+    return String::from("[Clock App]");
+  }
+}
+
+#[ignore]
+#[test]
+fn test_uptime_and_frame_count() {
+  let samples = Rc::new(std::cell::RefCell::new(Vec::new()));
+  let layer = Layer::new("Clock app", ClockApp { m_samples: samples.clone() });
+  let window = Window::new(EnumRendererApi::OpenGL);
+  let renderer = Renderer::new(EnumRendererApi::OpenGL);
+  let mut engine = Engine::new(window, renderer, vec![layer]);
+  
+  // Nothing ran yet.
+  assert_eq!(engine.get_frame_count(), 0);
+  assert_eq!(engine.get_uptime(), 0.0);
+  
+  engine.set_target_fps(Some(60));
+  assert!(engine.run().is_err_and(|err| err.is_fatal()));
+  
+  // One update per frame, counted from zero, with the uptime never going backward.
+  let samples = samples.borrow();
+  assert_eq!(samples.iter().map(|sample| sample.0).collect::<Vec<u64>>(), vec![0, 1, 2, 3, 4]);
+  assert!(samples.windows(2).all(|pair| pair[1].1 >= pair[0].1));
+  assert!(samples.last().unwrap().1 > 0.0);
+  assert_eq!(engine.get_frame_count(), 4);
+}
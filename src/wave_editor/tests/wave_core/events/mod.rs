@@ -22,7 +22,9 @@
  SOFTWARE.
 */
 
-use wave_editor::wave_core::events::EnumEventMask;
+use wave_editor::wave_core::dependencies::glfw;
+use wave_editor::wave_core::events::{EnumEvent, EnumEventMask};
+use wave_editor::wave_core::window::EnumVSync;
 
 #[test]
 fn test_event_masking() {
@@ -38,4 +40,78 @@ fn test_event_masking() {
   assert_eq!(inputs & !keys, inputs.difference(keys));
   
   assert_ne!(keys.union(inputs), window);
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_char_event_masking() {
+  let char_event = EnumEvent::CharEvent('w');
+  
+  assert_eq!(EnumEventMask::from(&char_event), EnumEventMask::Char);
+  assert!(EnumEventMask::Input.contains(EnumEventMask::Char));
+  assert!(!EnumEventMask::Keyboard.contains(EnumEventMask::Char));
+  assert!(!EnumEventMask::Mouse.contains(EnumEventMask::Char));
+}
+
+#[test]
+fn test_char_event_from_api() {
+  // Characters are forwarded as-is, including non-ASCII codepoints typed through an IME or dead keys.
+  assert_eq!(EnumEvent::from(glfw::WindowEvent::Char('a')), EnumEvent::CharEvent('a'));
+  assert_eq!(EnumEvent::from(glfw::WindowEvent::Char('é')), EnumEvent::CharEvent('é'));
+  assert_eq!(EnumEventMask::from(&EnumEvent::from(glfw::WindowEvent::Char('é'))), EnumEventMask::Char);
+}
+
+#[test]
+fn test_user_event_masking() {
+  struct PlayerDied {
+    m_player_id: u32,
+  }
+  let user_event = EnumEvent::UserEvent(1, Box::new(PlayerDied { m_player_id: 7 }));
+  
+  assert_eq!(EnumEventMask::from(&user_event), EnumEventMask::User);
+  assert!(!EnumEventMask::Input.intersects(EnumEventMask::User));
+  assert!(!EnumEventMask::Window.intersects(EnumEventMask::User));
+  assert_eq!(user_event, user_event);
+  // Payloads can't be compared, events only match on their id and payload type.
+  assert_eq!(user_event, EnumEvent::UserEvent(1, Box::new(PlayerDied { m_player_id: 8 })));
+  assert_ne!(user_event, EnumEvent::UserEvent(2, Box::new(PlayerDied { m_player_id: 7 })));
+  assert_ne!(user_event, EnumEvent::UserEvent(1, Box::new(7u32)));
+  
+  let EnumEvent::UserEvent(_id, payload) = &user_event else { unreachable!() };
+  assert!(payload.downcast_ref::<u32>().is_none());
+  assert_eq!(payload.downcast_ref::<PlayerDied>().unwrap().m_player_id, 7);
+}
+
+#[test]
+fn test_content_scale_event_masking() {
+  let scale_event = EnumEvent::ContentScaleEvent(2.0, 2.0);
+  
+  // Follows framebuffer resizes, since both change the size of what we render to.
+  assert_eq!(EnumEventMask::from(&scale_event), EnumEventMask::WindowSize);
+  assert!(EnumEventMask::Window.contains(EnumEventMask::from(&scale_event)));
+  assert_eq!(scale_event, EnumEvent::ContentScaleEvent(2.0, 2.0));
+  assert_ne!(scale_event, EnumEvent::ContentScaleEvent(1.0, 1.0));
+}
+
+#[test]
+fn test_vsync_event_masking() {
+  let vsync_event = EnumEvent::VSyncEvent(EnumVSync::Adaptive);
+  
+  // Only reaches layers polling every window event.
+  assert_eq!(EnumEventMask::from(&vsync_event), EnumEventMask::Window);
+  assert!(!EnumEventMask::WindowSize.contains(EnumEventMask::from(&vsync_event)));
+  assert_ne!(vsync_event, EnumEvent::VSyncEvent(EnumVSync::On));
+}
+
+#[test]
+fn test_gamepad_event_masking() {
+  let connected_event = EnumEvent::GamepadConnected(0);
+  let disconnected_event = EnumEvent::GamepadDisconnected(0);
+  
+  assert_eq!(EnumEventMask::from(&connected_event), EnumEventMask::Gamepad);
+  assert_eq!(EnumEventMask::from(&disconnected_event), EnumEventMask::Gamepad);
+  assert!(EnumEventMask::Input.contains(EnumEventMask::Gamepad));
+  assert!(!EnumEventMask::Keyboard.contains(EnumEventMask::Gamepad));
+  assert!(!EnumEventMask::Mouse.contains(EnumEventMask::Gamepad));
+  assert_ne!(connected_event, disconnected_event);
+  assert_ne!(connected_event, EnumEvent::GamepadConnected(1));
+}
@@ -25,4 +25,22 @@
 pub mod test_shader;
 pub mod test_vulkan;
 pub mod test_color;
-mod test_normal;
\ No newline at end of file
+pub mod test_batch;
+pub mod test_msaa;
+pub mod test_capabilities;
+pub mod test_stats;
+pub mod test_instancing;
+pub mod test_texture;
+pub mod test_render_target;
+pub mod test_post_process;
+pub mod test_debug_draw;
+pub mod test_primitive_mode;
+pub mod test_light;
+mod test_normal;
+pub mod test_builder;
+pub mod test_text;
+pub mod test_dynamic_buffer;
+pub mod test_uniform_buffer;
+pub mod test_capture;
+pub mod test_blending;
+pub mod test_depth;
\ No newline at end of file
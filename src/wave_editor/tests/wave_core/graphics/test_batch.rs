@@ -0,0 +1,113 @@
+/*
+ MIT License
+ 
+ Copyright (c) 2023 Nami Reghbati
+ 
+ Permission is hereby granted, free of charge, to any person obtaining a copy
+ of this software and associated documentation files (the "Software"), to deal
+ in the Software without restriction, including without limitation the rights
+ to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ copies of the Software, and to permit persons to whom the Software is
+ furnished to do so, subject to the following conditions:
+ 
+ The above copyright notice and this permission notice shall be included in all
+ copies or substantial portions of the Software.
+ 
+ THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ FITNESS FOR A PARTICULAR PURPOSE AND NON INFRINGEMENT. IN NO EVENT SHALL THE
+ AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ SOFTWARE.
+*/
+
+use wave_editor::wave_core::{EmptyApp, Engine, EnumEngineError, TraitApply};
+use wave_editor::wave_core::assets::asset_loader::AssetLoader;
+use wave_editor::wave_core::assets::r_assets::{EnumPrimitiveShading, REntity, Vertex};
+use wave_editor::wave_core::graphics::renderer::{EnumRendererApi, EnumRendererError, Renderer, StaticBatch};
+use wave_editor::wave_core::graphics::shader::{EnumShaderSource, EnumShaderStageType, Shader, ShaderStage};
+use wave_editor::wave_core::layers::Layer;
+use wave_editor::wave_core::math::{Mat4, Vec3};
+use wave_editor::wave_core::window::Window;
+
+fn create_shader() -> Result<Shader, EnumEngineError> {
+  let vertex_shader = ShaderStage::new(EnumShaderStageType::Vertex,
+    EnumShaderSource::FromFile(String::from("res/shaders/test.vert")));
+  let fragment_shader = ShaderStage::new(EnumShaderStageType::Fragment,
+    EnumShaderSource::FromFile(String::from("res/shaders/test.frag")));
+  
+  let mut shader = Shader::default();
+  shader.push_stage(vertex_shader)?;
+  shader.push_stage(fragment_shader)?;
+  shader.apply()?;
+  return Ok(shader);
+}
+
+#[ignore]
+#[test]
+fn test_apply_batch() -> Result<(), EnumEngineError> {
+  let layer = Layer::new("Batch apply", EmptyApp::default());
+  let window = Window::new(EnumRendererApi::OpenGL);
+  let renderer = Renderer::new(EnumRendererApi::OpenGL);
+  let mut engine = Engine::new(window, renderer, vec![layer]);
+  engine.apply()?;
+  
+  let asset_loader = AssetLoader::new();
+  
+  // Apply 50 entities one by one.
+  let mut individual_shader = create_shader()?;
+  let mut individual_entities: Vec<REntity> = Vec::with_capacity(50);
+  for _ in 0..50 {
+    let asset = asset_loader.load("res/assets/n64_logo/n64_logo.obj")?;
+    individual_entities.push(REntity::new(asset, EnumPrimitiveShading::default(), "Individual"));
+  }
+  
+  let allocations_before = engine.get_renderer_mut().get_buffer_allocation_count();
+  for entity in individual_entities.iter_mut() {
+    engine.get_renderer_mut().enqueue(entity, &mut individual_shader)?;
+  }
+  let individual_allocations = engine.get_renderer_mut().get_buffer_allocation_count() - allocations_before;
+  
+  // Apply the same 50 entities in a single batch.
+  let mut batch_shader = create_shader()?;
+  let mut batch_entities: Vec<REntity> = Vec::with_capacity(50);
+  for _ in 0..50 {
+    let asset = asset_loader.load("res/assets/n64_logo/n64_logo.obj")?;
+    batch_entities.push(REntity::new(asset, EnumPrimitiveShading::default(), "Batched"));
+  }
+  
+  let allocations_before = engine.get_renderer_mut().get_buffer_allocation_count();
+  engine.get_renderer_mut().apply_batch(&mut batch_entities, &mut batch_shader)?;
+  let batch_allocations = engine.get_renderer_mut().get_buffer_allocation_count() - allocations_before;
+  
+  assert!(batch_entities.iter().all(|entity| entity.is_sent()));
+  assert!(batch_allocations < individual_allocations);
+  return Ok(());
+}
+
+#[test]
+fn test_static_batch() -> Result<(), EnumRendererError> {
+  assert!(StaticBatch::build(&[]).is_err());
+  
+  let mut cubes: Vec<REntity> = (0..3).map(|_| REntity::default()).collect();
+  cubes[1].translate(5.0, 0.0, 0.0);
+  cubes[2].scale(2.0, 2.0, 2.0);
+  
+  let batch = StaticBatch::build(&cubes)?;
+  assert_eq!(batch.get_entity_count(), 3);
+  assert_eq!(batch.get_index_count(), cubes.iter().map(|cube| cube.get_total_index_count()).sum::<usize>());
+  assert_eq!(batch.get_vertex_count(), cubes.iter().map(|cube| cube.get_total_vertex_count()).sum::<usize>());
+  
+  // Everything ends up in a single primitive, with transforms baked in.
+  assert_eq!(batch.get_entity().get_primitive_count(), 1);
+  assert_eq!(batch.get_entity().get_matrix(), Mat4::default());
+  let bounds = batch.get_bounds();
+  assert!(bounds.m_max.x >= cubes[1].get_world_bounds().m_max.x);
+  assert!(bounds.m_min.y <= cubes[2].get_world_bounds().m_min.y);
+  
+  // Normals survive being unpacked and repacked.
+  let normal = Vec3::new(&[0.0, -1.0, 0.0]);
+  assert_eq!(Vertex::unpack_normal(Vertex::pack_normal(normal)), normal);
+  return Ok(());
+}
@@ -0,0 +1,160 @@
+/*
+ MIT License
+ 
+ Copyright (c) 2023 Nami Reghbati
+ 
+ Permission is hereby granted, free of charge, to any person obtaining a copy
+ of this software and associated documentation files (the "Software"), to deal
+ in the Software without restriction, including without limitation the rights
+ to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ copies of the Software, and to permit persons to whom the Software is
+ furnished to do so, subject to the following conditions:
+ 
+ The above copyright notice and this permission notice shall be included in all
+ copies or substantial portions of the Software.
+ 
+ THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ FITNESS FOR A PARTICULAR PURPOSE AND NON INFRINGEMENT. IN NO EVENT SHALL THE
+ AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ SOFTWARE.
+*/
+
+use wave_editor::wave_core::{EmptyApp, Engine, EnumEngineError, TraitApply};
+use wave_editor::wave_core::assets::asset_loader::AssetLoader;
+use wave_editor::wave_core::assets::r_assets::{EnumPrimitiveShading, REntity};
+use wave_editor::wave_core::camera::{Camera, EnumCameraType};
+use wave_editor::wave_core::graphics::renderer::{EnumRendererApi, EnumRendererBlendMode, Renderer};
+use wave_editor::wave_core::graphics::shader::{EnumShaderSource, EnumShaderStageType, Shader, ShaderStage};
+use wave_editor::wave_core::layers::Layer;
+use wave_editor::wave_core::window::Window;
+
+#[ignore]
+#[test]
+fn test_blend_mode() -> Result<(), EnumEngineError> {
+  let layer = Layer::new("Blend mode", EmptyApp::default());
+  let window = Window::new(EnumRendererApi::OpenGL);
+  let renderer = Renderer::new(EnumRendererApi::OpenGL);
+  let mut engine = Engine::new(window, renderer, vec![layer]);
+  engine.apply()?;
+  
+  // The default renderer hints alpha blend.
+  assert_eq!(engine.get_renderer_mut().get_blend_mode()?, Some(EnumRendererBlendMode::AlphaBlend));
+  
+  for mode in [EnumRendererBlendMode::Opaque, EnumRendererBlendMode::AlphaBlend, EnumRendererBlendMode::Additive,
+    EnumRendererBlendMode::Multiply] {
+    engine.get_renderer_mut().set_blend_mode(mode)?;
+    assert_eq!(engine.get_renderer_mut().get_blend_mode()?, Some(mode));
+  }
+  
+  return engine.free();
+}
+
+#[ignore]
+#[test]
+fn test_blend_mode_per_entity() -> Result<(), EnumEngineError> {
+  let layer = Layer::new("Blend mode per entity", EmptyApp::default());
+  let window = Window::new(EnumRendererApi::OpenGL);
+  let renderer = Renderer::new(EnumRendererApi::OpenGL);
+  let mut engine = Engine::new(window, renderer, vec![layer]);
+  engine.apply()?;
+  
+  let mut shader = Shader::default();
+  shader.push_stage(ShaderStage::new(EnumShaderStageType::Vertex,
+    EnumShaderSource::FromFile(String::from("res/shaders/glsl_460.vert"))))?;
+  shader.push_stage(ShaderStage::new(EnumShaderStageType::Fragment,
+    EnumShaderSource::FromStr(String::from("#version 460 core\n\
+    layout (location = 0) out vec4 fout_color;\n\
+    void main() { fout_color = vec4(0.25, 0.25, 0.25, 1.0); }\n"))))?;
+  shader.apply()?;
+  
+  // A dark gray cube over a lighter gray background, so that each blend function leaves a distinct color behind.
+  let asset_loader = AssetLoader::new();
+  let mut cube = REntity::new(asset_loader.load("res/assets/cube/cube.obj")?, EnumPrimitiveShading::default(), "Cube");
+  cube.translate(0.0, 0.0, 5.0);
+  cube.apply(&mut shader)?;
+  assert!(cube.is_sent());
+  
+  let (width, height) = engine.get_window_mut().get_framebuffer_size();
+  let center = (((height / 2) * width + width / 2) * 4) as usize;
+  engine.get_renderer_mut().set_clear_color(0.5, 0.5, 0.5, 1.0)?;
+  engine.get_renderer_mut().submit_camera(Camera::new(EnumCameraType::Perspective(75, width as f32 / height as f32, 0.1,
+    100.0), None))?;
+  engine.get_renderer_mut().set_blend_mode(EnumRendererBlendMode::Opaque)?;
+  
+  for (mode, expected_channel) in [(EnumRendererBlendMode::Additive, 191u8), (EnumRendererBlendMode::Multiply, 32u8),
+    (EnumRendererBlendMode::Opaque, 64u8)] {
+    cube.set_blend_mode(mode);
+    cube.reapply()?;
+    engine.get_renderer_mut().on_render()?;
+    engine.get_window_mut().refresh();
+    
+    let pixels = engine.get_renderer_mut().capture_frame()?;
+    assert!(pixels[center..center + 3].iter().all(|channel| channel.abs_diff(expected_channel) <= 1),
+      "{0} : {1:?}", mode, &pixels[center..center + 4]);
+    
+    // The global blend state is restored once translucent entities are drawn.
+    assert_eq!(engine.get_renderer_mut().get_blend_mode()?, Some(EnumRendererBlendMode::Opaque));
+  }
+  
+  return engine.free();
+}
+
+#[ignore]
+#[test]
+fn test_transparency_sort() -> Result<(), EnumEngineError> {
+  let layer = Layer::new("Transparency sort", EmptyApp::default());
+  let window = Window::new(EnumRendererApi::OpenGL);
+  let renderer = Renderer::new(EnumRendererApi::OpenGL);
+  let mut engine = Engine::new(window, renderer, vec![layer]);
+  engine.apply()?;
+  
+  let mut red_shader = Shader::default();
+  red_shader.push_stage(ShaderStage::new(EnumShaderStageType::Vertex,
+    EnumShaderSource::FromFile(String::from("res/shaders/glsl_460.vert"))))?;
+  red_shader.push_stage(ShaderStage::new(EnumShaderStageType::Fragment,
+    EnumShaderSource::FromStr(String::from("#version 460 core\n\
+    layout (location = 0) out vec4 fout_color;\n\
+    void main() { fout_color = vec4(1.0, 0.0, 0.0, 0.5); }\n"))))?;
+  red_shader.apply()?;
+  
+  let mut blue_shader = Shader::default();
+  blue_shader.push_stage(ShaderStage::new(EnumShaderStageType::Vertex,
+    EnumShaderSource::FromFile(String::from("res/shaders/glsl_460.vert"))))?;
+  blue_shader.push_stage(ShaderStage::new(EnumShaderStageType::Fragment,
+    EnumShaderSource::FromStr(String::from("#version 460 core\n\
+    layout (location = 0) out vec4 fout_color;\n\
+    void main() { fout_color = vec4(0.0, 0.0, 1.0, 0.5); }\n"))))?;
+  blue_shader.apply()?;
+  
+  // Enqueue the closest cube first, so that drawing in insertion order would blend them the wrong way around.
+  let asset_loader = AssetLoader::new();
+  let mut near_cube = REntity::new(asset_loader.load("res/assets/cube/cube.obj")?, EnumPrimitiveShading::default(),
+    "Near cube");
+  near_cube.translate(0.0, 0.0, 5.0);
+  near_cube.set_transparent(true);
+  near_cube.apply(&mut red_shader)?;
+  
+  let mut far_cube = REntity::new(asset_loader.load("res/assets/cube/cube.obj")?, EnumPrimitiveShading::default(),
+    "Far cube");
+  far_cube.translate(0.0, 0.0, 8.0);
+  far_cube.set_transparent(true);
+  far_cube.apply(&mut blue_shader)?;
+  
+  let (width, height) = engine.get_window_mut().get_framebuffer_size();
+  let center = (((height / 2) * width + width / 2) * 4) as usize;
+  engine.get_renderer_mut().set_clear_color(0.0, 0.0, 0.0, 1.0)?;
+  engine.get_renderer_mut().submit_camera(Camera::new(EnumCameraType::Perspective(75, width as f32 / height as f32, 0.1,
+    100.0), None))?;
+  engine.get_renderer_mut().on_render()?;
+  engine.get_window_mut().refresh();
+  
+  // Back-to-front : the blue cube is drawn first and half covered by the red one, leaving more red than blue.
+  let pixels = engine.get_renderer_mut().capture_frame()?;
+  assert!(pixels[center].abs_diff(128) <= 1 && pixels[center + 2].abs_diff(64) <= 1, "{0:?}",
+    &pixels[center..center + 4]);
+  
+  return engine.free();
+}
@@ -0,0 +1,71 @@
+/*
+ MIT License
+ 
+ Copyright (c) 2023 Nami Reghbati
+ 
+ Permission is hereby granted, free of charge, to any person obtaining a copy
+ of this software and associated documentation files (the "Software"), to deal
+ in the Software without restriction, including without limitation the rights
+ to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ copies of the Software, and to permit persons to whom the Software is
+ furnished to do so, subject to the following conditions:
+ 
+ The above copyright notice and this permission notice shall be included in all
+ copies or substantial portions of the Software.
+ 
+ THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ FITNESS FOR A PARTICULAR PURPOSE AND NON INFRINGEMENT. IN NO EVENT SHALL THE
+ AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ SOFTWARE.
+*/
+
+use wave_editor::wave_core::graphics::renderer::{EnumRendererApi, EnumRendererError, EnumRendererHint,
+  EnumRendererOptimizationMode, RendererBuilder};
+
+#[test]
+fn test_renderer_builder_hints() {
+  let builder = RendererBuilder::new(EnumRendererApi::OpenGL)
+    .msaa(4)
+    .depth_test(false)
+    .optimization(EnumRendererOptimizationMode::MinimizeDrawCalls)
+    .msaa(8);
+  
+  // Chaining the same kind of hint twice keeps the last one only.
+  assert_eq!(builder.get_hints().len(), 3);
+  assert!(builder.get_hints().contains(&EnumRendererHint::MSAA(Some(8))));
+  assert!(builder.get_hints().contains(&EnumRendererHint::DepthTest(false)));
+  assert_eq!(builder.validate(), Ok(()));
+  
+  // A single sample means no multisampling.
+  assert!(RendererBuilder::default().msaa(1).get_hints().contains(&EnumRendererHint::MSAA(None)));
+}
+
+#[test]
+fn test_renderer_builder_frustum_culling() {
+  let builder = RendererBuilder::default().frustum_culling(false);
+  assert!(builder.get_hints().contains(&EnumRendererHint::FrustumCulling(false)));
+  
+  let builder = builder.frustum_culling(true);
+  assert!(builder.get_hints().contains(&EnumRendererHint::FrustumCulling(true)));
+  assert!(!builder.get_hints().contains(&EnumRendererHint::FrustumCulling(false)));
+}
+
+#[test]
+fn test_renderer_builder_clear_color() {
+  let builder = RendererBuilder::default().clear_color(1.0, 1.0, 1.0, 1.0);
+  assert!(builder.get_hints().contains(&EnumRendererHint::ClearColor([1.0, 1.0, 1.0, 1.0])));
+  
+  let builder = builder.clear_color(0.5, 0.25, 0.0, 1.0);
+  assert_eq!(builder.get_hints(), &vec![EnumRendererHint::ClearColor([0.5, 0.25, 0.0, 1.0])]);
+}
+
+#[test]
+fn test_renderer_builder_validation() {
+  assert_eq!(RendererBuilder::default().msaa(6).validate(), Err(EnumRendererError::InvalidHint));
+  assert_eq!(RendererBuilder::default().shadow_map_resolution(0).validate(), Err(EnumRendererError::InvalidHint));
+  assert_eq!(RendererBuilder::default().shadow_map_resolution(0).build().err(), Some(EnumRendererError::InvalidHint));
+  assert_eq!(RendererBuilder::default().clear_color(1.5, 0.0, 0.0, 1.0).validate(), Err(EnumRendererError::InvalidHint));
+}
@@ -0,0 +1,50 @@
+/*
+ MIT License
+ 
+ Copyright (c) 2023 Nami Reghbati
+ 
+ Permission is hereby granted, free of charge, to any person obtaining a copy
+ of this software and associated documentation files (the "Software"), to deal
+ in the Software without restriction, including without limitation the rights
+ to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ copies of the Software, and to permit persons to whom the Software is
+ furnished to do so, subject to the following conditions:
+ 
+ The above copyright notice and this permission notice shall be included in all
+ copies or substantial portions of the Software.
+ 
+ THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ FITNESS FOR A PARTICULAR PURPOSE AND NON INFRINGEMENT. IN NO EVENT SHALL THE
+ AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ SOFTWARE.
+*/
+
+use wave_editor::wave_core::{EmptyApp, Engine, EnumEngineError, TraitApply};
+use wave_editor::wave_core::graphics::renderer::{EnumRendererApi, Renderer};
+use wave_editor::wave_core::layers::Layer;
+use wave_editor::wave_core::window::Window;
+
+#[ignore]
+#[test]
+fn test_renderer_capabilities() -> Result<(), EnumEngineError> {
+  let layer = Layer::new("Capabilities", EmptyApp::default());
+  let window = Window::new(EnumRendererApi::OpenGL);
+  let renderer = Renderer::new(EnumRendererApi::OpenGL);
+  let mut engine = Engine::new(window, renderer, vec![layer]);
+  
+  // Nothing queried before the renderer is applied.
+  assert_eq!(engine.get_renderer_mut().get_capabilities().m_max_texture_size, 0);
+  engine.apply()?;
+  
+  let capabilities = engine.get_renderer_mut().get_capabilities().clone();
+  assert!(!capabilities.m_vendor.is_empty());
+  assert!(capabilities.m_max_texture_units > 0);
+  assert!(capabilities.m_max_texture_size >= 1024);
+  assert!(capabilities.m_max_anisotropy >= 1.0);
+  // Extension checks go through the same cached set.
+  assert!(capabilities.m_extensions.iter().all(|extension| engine.get_renderer_mut().check_extension(extension)));
+  return engine.free();
+}
@@ -0,0 +1,62 @@
+/*
+ MIT License
+ 
+ Copyright (c) 2023 Nami Reghbati
+ 
+ Permission is hereby granted, free of charge, to any person obtaining a copy
+ of this software and associated documentation files (the "Software"), to deal
+ in the Software without restriction, including without limitation the rights
+ to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ copies of the Software, and to permit persons to whom the Software is
+ furnished to do so, subject to the following conditions:
+ 
+ The above copyright notice and this permission notice shall be included in all
+ copies or substantial portions of the Software.
+ 
+ THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ FITNESS FOR A PARTICULAR PURPOSE AND NON INFRINGEMENT. IN NO EVENT SHALL THE
+ AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ SOFTWARE.
+*/
+
+use wave_editor::wave_core::{EmptyApp, Engine, EnumEngineError, TraitApply};
+use wave_editor::wave_core::graphics::renderer::{C_DEFAULT_CLEAR_COLOR, EnumRendererApi, Renderer};
+use wave_editor::wave_core::layers::Layer;
+use wave_editor::wave_core::window::Window;
+
+#[ignore]
+#[test]
+fn test_capture_frame() -> Result<(), EnumEngineError> {
+  let layer = Layer::new("Frame capture", EmptyApp::default());
+  let window = Window::new(EnumRendererApi::OpenGL);
+  let renderer = Renderer::new(EnumRendererApi::OpenGL);
+  let mut engine = Engine::new(window, renderer, vec![layer]);
+  engine.apply()?;
+  
+  let (width, height) = engine.get_window_mut().get_framebuffer_size();
+  assert_eq!(engine.get_renderer_mut().get_clear_color(), C_DEFAULT_CLEAR_COLOR);
+  // Pure channels stay the same whether the framebuffer is sRGB or not.
+  engine.get_renderer_mut().set_clear_color(1.0, 0.0, 0.0, 1.0)?;
+  assert_eq!(engine.get_renderer_mut().get_clear_color(), [1.0, 0.0, 0.0, 1.0]);
+  
+  // Multisampled frames get resolved before being read.
+  for sample_count in [None, Some(4)] {
+    engine.get_renderer_mut().toggle_msaa(sample_count)?;
+    engine.get_renderer_mut().on_render()?;
+    engine.get_window_mut().refresh();
+    
+    let pixels = engine.get_renderer_mut().capture_frame()?;
+    assert_eq!(pixels.len(), (width * height * 4) as usize);
+    assert!(pixels.chunks_exact(4).all(|pixel| pixel == [255, 0, 0, 255]));
+  }
+  
+  let file_path = std::env::temp_dir().join("wave_engine_screenshot.png");
+  engine.get_renderer_mut().save_screenshot(file_path.to_str().unwrap())?;
+  let png_bytes = std::fs::read(&file_path).expect("Cannot read screenshot saved!");
+  assert!(png_bytes.starts_with(&[0x89, b'P', b'N', b'G']));
+  std::fs::remove_file(&file_path).expect("Cannot remove screenshot saved!");
+  return engine.free();
+}
@@ -0,0 +1,47 @@
+/*
+ MIT License
+ 
+ Copyright (c) 2023 Nami Reghbati
+ 
+ Permission is hereby granted, free of charge, to any person obtaining a copy
+ of this software and associated documentation files (the "Software"), to deal
+ in the Software without restriction, including without limitation the rights
+ to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ copies of the Software, and to permit persons to whom the Software is
+ furnished to do so, subject to the following conditions:
+ 
+ The above copyright notice and this permission notice shall be included in all
+ copies or substantial portions of the Software.
+ 
+ THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ FITNESS FOR A PARTICULAR PURPOSE AND NON INFRINGEMENT. IN NO EVENT SHALL THE
+ AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ SOFTWARE.
+*/
+
+use wave_editor::wave_core::graphics::color::Color;
+use wave_editor::wave_core::graphics::renderer::DebugDraw;
+use wave_editor::wave_core::math::Vec3;
+
+#[test]
+fn test_debug_draw_accumulation() {
+  let mut debug_draw = DebugDraw::new();
+  assert!(debug_draw.is_empty());
+  
+  let red = Color::from([1.0, 0.0, 0.0, 1.0]);
+  debug_draw.draw_line(Vec3 { x: 0.0, y: 0.0, z: 0.0 }, Vec3 { x: 1.0, y: 0.0, z: 0.0 }, red);
+  assert_eq!(debug_draw.get_vertex_count(), 2);
+  
+  // 12 edges.
+  debug_draw.draw_aabb(Vec3 { x: -1.0, y: -1.0, z: -1.0 }, Vec3 { x: 1.0, y: 1.0, z: 1.0 }, red);
+  assert_eq!(debug_draw.get_vertex_count(), 26);
+  
+  debug_draw.draw_ray(Vec3 { x: 0.0, y: 0.0, z: 0.0 }, Vec3 { x: 0.0, y: 2.0, z: 0.0 }, 5.0, red);
+  assert_eq!(debug_draw.get_vertex_count(), 28);
+  
+  debug_draw.clear();
+  assert!(debug_draw.is_empty());
+}
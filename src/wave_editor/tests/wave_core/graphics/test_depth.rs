@@ -0,0 +1,108 @@
+/*
+ MIT License
+ 
+ Copyright (c) 2023 Nami Reghbati
+ 
+ Permission is hereby granted, free of charge, to any person obtaining a copy
+ of this software and associated documentation files (the "Software"), to deal
+ in the Software without restriction, including without limitation the rights
+ to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ copies of the Software, and to permit persons to whom the Software is
+ furnished to do so, subject to the following conditions:
+ 
+ The above copyright notice and this permission notice shall be included in all
+ copies or substantial portions of the Software.
+ 
+ THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ FITNESS FOR A PARTICULAR PURPOSE AND NON INFRINGEMENT. IN NO EVENT SHALL THE
+ AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ SOFTWARE.
+*/
+
+use wave_editor::wave_core::{EmptyApp, Engine, EnumEngineError, TraitApply};
+use wave_editor::wave_core::assets::asset_loader::AssetLoader;
+use wave_editor::wave_core::assets::r_assets::{EnumPrimitiveShading, REntity};
+use wave_editor::wave_core::camera::{Camera, EnumCameraType};
+use wave_editor::wave_core::graphics::renderer::{EnumRendererApi, EnumRendererDepthFunc, EnumRendererHint, Renderer,
+  RendererBuilder};
+use wave_editor::wave_core::graphics::shader::{EnumShaderError, EnumShaderSource, EnumShaderStageType, Shader, ShaderStage};
+use wave_editor::wave_core::layers::Layer;
+use wave_editor::wave_core::window::Window;
+
+fn solid_color_shader(color: &str) -> Result<Shader, EnumShaderError> {
+  let mut shader = Shader::default();
+  shader.push_stage(ShaderStage::new(EnumShaderStageType::Vertex,
+    EnumShaderSource::FromFile(String::from("res/shaders/glsl_460.vert"))))?;
+  shader.push_stage(ShaderStage::new(EnumShaderStageType::Fragment,
+    EnumShaderSource::FromStr(format!("#version 460 core\n\
+    layout (location = 0) out vec4 fout_color;\n\
+    void main() {{ fout_color = vec4({0}, 1.0); }}\n", color))))?;
+  shader.apply()?;
+  return Ok(shader);
+}
+
+#[test]
+fn test_renderer_builder_depth() {
+  let builder = RendererBuilder::default()
+    .depth_func(EnumRendererDepthFunc::LessEqual)
+    .depth_write(false);
+  assert!(builder.get_hints().contains(&EnumRendererHint::DepthFunc(EnumRendererDepthFunc::LessEqual)));
+  assert!(builder.get_hints().contains(&EnumRendererHint::DepthWrite(false)));
+  assert_eq!(EnumRendererDepthFunc::default(), EnumRendererDepthFunc::Less);
+}
+
+#[ignore]
+#[test]
+fn test_depth_func() -> Result<(), EnumEngineError> {
+  let layer = Layer::new("Depth function", EmptyApp::default());
+  let window = Window::new(EnumRendererApi::OpenGL);
+  let renderer = Renderer::new(EnumRendererApi::OpenGL);
+  let mut engine = Engine::new(window, renderer, vec![layer]);
+  engine.apply()?;
+  
+  let mut red_shader = solid_color_shader("1.0, 0.0, 0.0")?;
+  let mut green_shader = solid_color_shader("0.0, 1.0, 0.0")?;
+  
+  // Two identical cubes at the same spot, the red one drawn first.
+  let asset_loader = AssetLoader::new();
+  let mut first = REntity::new(asset_loader.load("res/assets/cube/cube.obj")?, EnumPrimitiveShading::default(), "First");
+  let mut second = REntity::new(asset_loader.load("res/assets/cube/cube.obj")?, EnumPrimitiveShading::default(), "Second");
+  first.translate(0.0, 0.0, 5.0);
+  second.translate(0.0, 0.0, 5.0);
+  engine.get_renderer_mut().enqueue(&mut first, &mut red_shader)?;
+  engine.get_renderer_mut().enqueue(&mut second, &mut green_shader)?;
+  
+  let (width, height) = engine.get_window_mut().get_framebuffer_size();
+  let center = (((height / 2) * width + width / 2) * 4) as usize;
+  engine.get_renderer_mut().submit_camera(Camera::new(EnumCameraType::Perspective(75, width as f32 / height as f32, 0.1,
+    100.0), None))?;
+  
+  assert_eq!(engine.get_renderer_mut().get_depth_func(), EnumRendererDepthFunc::Less);
+  // With a strict comparison, the second cube fails the depth test against the first one, and passes it otherwise.
+  for (func, expected_pixel) in [(EnumRendererDepthFunc::Less, [255, 0, 0, 255]),
+    (EnumRendererDepthFunc::LessEqual, [0, 255, 0, 255])] {
+    engine.get_renderer_mut().set_depth_func(func)?;
+    assert_eq!(engine.get_renderer_mut().get_depth_func(), func);
+    engine.get_renderer_mut().on_render()?;
+    engine.get_window_mut().refresh();
+    
+    let pixels = engine.get_renderer_mut().capture_frame()?;
+    assert_eq!(pixels[center..center + 4], expected_pixel);
+  }
+  
+  // Without depth writes, nothing occludes the second cube, and the next frame still clears depth properly.
+  engine.get_renderer_mut().set_depth_func(EnumRendererDepthFunc::Less)?;
+  engine.get_renderer_mut().set_depth_write(false)?;
+  assert!(!engine.get_renderer_mut().is_depth_write());
+  for _ in 0..2 {
+    engine.get_renderer_mut().on_render()?;
+    engine.get_window_mut().refresh();
+    
+    let pixels = engine.get_renderer_mut().capture_frame()?;
+    assert_eq!(pixels[center..center + 4], [0, 255, 0, 255]);
+  }
+  return engine.free();
+}
@@ -0,0 +1,111 @@
+/*
+ MIT License
+ 
+ Copyright (c) 2023 Nami Reghbati
+ 
+ Permission is hereby granted, free of charge, to any person obtaining a copy
+ of this software and associated documentation files (the "Software"), to deal
+ in the Software without restriction, including without limitation the rights
+ to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ copies of the Software, and to permit persons to whom the Software is
+ furnished to do so, subject to the following conditions:
+ 
+ The above copyright notice and this permission notice shall be included in all
+ copies or substantial portions of the Software.
+ 
+ THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ FITNESS FOR A PARTICULAR PURPOSE AND NON INFRINGEMENT. IN NO EVENT SHALL THE
+ AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ SOFTWARE.
+*/
+
+use std::time::Instant;
+
+use wave_editor::wave_core::{EmptyApp, Engine, EnumEngineError, TraitApply};
+#[cfg(feature = "debug")]
+use wave_editor::wave_core::dependencies::chrono;
+use wave_editor::wave_core::graphics::DynamicBuffer;
+use wave_editor::wave_core::graphics::renderer::{EnumRendererApi, EnumRendererError, Renderer, RendererCapabilities};
+use wave_editor::wave_core::layers::Layer;
+use wave_editor::wave_core::utils::macros::logger::{*};
+use wave_editor::wave_core::window::Window;
+
+#[test]
+fn test_dynamic_buffer_api() {
+  let capabilities = RendererCapabilities::default();
+  assert!(matches!(DynamicBuffer::new(EnumRendererApi::Vulkan, 1024, &capabilities),
+    Err(EnumRendererError::NotImplemented)));
+}
+
+#[ignore]
+#[test]
+fn test_dynamic_buffer_streaming() -> Result<(), EnumEngineError> {
+  let layer = Layer::new("Dynamic buffer", EmptyApp::default());
+  let window = Window::new(EnumRendererApi::OpenGL);
+  let renderer = Renderer::new(EnumRendererApi::OpenGL);
+  let mut engine = Engine::new(window, renderer, vec![layer]);
+  engine.apply()?;
+  
+  let mut capabilities = engine.get_renderer_mut().get_capabilities().clone();
+  for persistent in [capabilities.m_persistent_mapping, false] {
+    capabilities.m_persistent_mapping = persistent;
+    let mut buffer = DynamicBuffer::new(EnumRendererApi::OpenGL, 1024, &capabilities)?;
+    assert_eq!(buffer.is_persistent(), persistent);
+    
+    // Writes are aligned and packed one after the other.
+    assert_eq!(buffer.write(&[1; 10])?, 0);
+    assert_eq!(buffer.write(&[2; 10])?, 16);
+    buffer.next_frame()?;
+    
+    // Persistent buffers move on to the next region every frame, others reuse their only one.
+    let expected_offset = if persistent { 1024 } else { 0 };
+    assert_eq!(buffer.write(&[3; 10])?, expected_offset);
+    
+    // Writing past the region grows it.
+    buffer.write(&[4; 2048])?;
+    assert!(buffer.get_region_size() >= 2048);
+    buffer.free()?;
+  }
+  return engine.free();
+}
+
+#[ignore]
+#[test]
+fn bench_dynamic_buffer_streaming() -> Result<(), EnumEngineError> {
+  const C_FRAME_COUNT: usize = 1000;
+  const C_WRITES_PER_FRAME: usize = 8;
+  
+  let layer = Layer::new("Dynamic buffer benchmark", EmptyApp::default());
+  let window = Window::new(EnumRendererApi::OpenGL);
+  let renderer = Renderer::new(EnumRendererApi::OpenGL);
+  let mut engine = Engine::new(window, renderer, vec![layer]);
+  engine.apply()?;
+  
+  // About what a busy frame of debug lines and text uploads.
+  let vertices: Vec<u8> = vec![0xff; 16 * 1024];
+  let mut capabilities = engine.get_renderer_mut().get_capabilities().clone();
+  
+  for persistent in [capabilities.m_persistent_mapping, false] {
+    capabilities.m_persistent_mapping = persistent;
+    let mut buffer = DynamicBuffer::new(EnumRendererApi::OpenGL, vertices.len() * C_WRITES_PER_FRAME, &capabilities)?;
+    
+    let start = Instant::now();
+    for _ in 0..C_FRAME_COUNT {
+      for _ in 0..C_WRITES_PER_FRAME {
+        buffer.write(&vertices)?;
+      }
+      buffer.next_frame()?;
+    }
+    let _elapsed = start.elapsed();
+    
+    log!("INFO", "[DynamicBuffer] -->\t Persistent mapping : {0} => {1} frames of {2} KiB in {3:?} ({4:?} per frame)",
+      persistent, C_FRAME_COUNT, vertices.len() * C_WRITES_PER_FRAME / 1024, _elapsed, _elapsed / C_FRAME_COUNT as u32);
+    // Never grown, the region fitting a whole frame.
+    assert_eq!(buffer.get_region_size(), vertices.len() * C_WRITES_PER_FRAME);
+    buffer.free()?;
+  }
+  return engine.free();
+}
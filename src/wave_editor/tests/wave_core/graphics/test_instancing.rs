@@ -0,0 +1,113 @@
+/*
+ MIT License
+ 
+ Copyright (c) 2023 Nami Reghbati
+ 
+ Permission is hereby granted, free of charge, to any person obtaining a copy
+ of this software and associated documentation files (the "Software"), to deal
+ in the Software without restriction, including without limitation the rights
+ to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ copies of the Software, and to permit persons to whom the Software is
+ furnished to do so, subject to the following conditions:
+ 
+ The above copyright notice and this permission notice shall be included in all
+ copies or substantial portions of the Software.
+ 
+ THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ FITNESS FOR A PARTICULAR PURPOSE AND NON INFRINGEMENT. IN NO EVENT SHALL THE
+ AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ SOFTWARE.
+*/
+
+use std::mem::size_of;
+
+use wave_editor::wave_core::{EmptyApp, Engine, EnumEngineError, TraitApply, TraitFree, TraitHint};
+use wave_editor::wave_core::assets::asset_loader::AssetLoader;
+use wave_editor::wave_core::assets::r_assets::{EnumPrimitiveShading, REntity};
+use wave_editor::wave_core::graphics::renderer::{EnumRendererApi, Renderer};
+use wave_editor::wave_core::graphics::shader::{EnumShaderHint, EnumShaderSource, EnumShaderStageType, Shader, ShaderStage};
+use wave_editor::wave_core::layers::Layer;
+use wave_editor::wave_core::math::{Mat4, Vec3};
+use wave_editor::wave_core::window::Window;
+
+#[ignore]
+#[test]
+fn test_apply_instanced() -> Result<(), EnumEngineError> {
+  let layer = Layer::new("Instanced apply", EmptyApp::default());
+  let window = Window::new(EnumRendererApi::OpenGL);
+  let renderer = Renderer::new(EnumRendererApi::OpenGL);
+  let mut engine = Engine::new(window, renderer, vec![layer]);
+  engine.apply()?;
+  
+  let vertex_shader = ShaderStage::new(EnumShaderStageType::Vertex,
+    EnumShaderSource::FromFile(String::from("res/shaders/test.vert")));
+  let fragment_shader = ShaderStage::new(EnumShaderStageType::Fragment,
+    EnumShaderSource::FromFile(String::from("res/shaders/test.frag")));
+  
+  let mut shader = Shader::default();
+  shader.set_hint(EnumShaderHint::InstanceMatrixLocation(7));
+  shader.push_stage(vertex_shader)?;
+  shader.push_stage(fragment_shader)?;
+  shader.apply()?;
+  assert_eq!(shader.get_instance_matrix_location(), Some(7));
+  
+  let asset_loader = AssetLoader::new();
+  let asset = asset_loader.load("res/assets/n64_logo/n64_logo.obj")?;
+  let mut entity = REntity::new(asset, EnumPrimitiveShading::default(), "Instanced");
+  
+  let transforms: Vec<Mat4> = (0..1000)
+    .map(|index| Mat4::translation_matrix(&Vec3::new(&[index as f32, 0.0, 0.0])))
+    .collect();
+  entity.apply_instanced(&mut shader, &transforms)?;
+  
+  let renderer = engine.get_renderer_mut();
+  assert_eq!(renderer.get_instance_buffer_size(entity.get_uuid()), Some(transforms.len() * size_of::<Mat4>()));
+  
+  // Re-applying replaces the previous instances.
+  entity.apply_instanced(&mut shader, &transforms[0..10])?;
+  assert_eq!(renderer.get_instance_buffer_size(entity.get_uuid()), Some(10 * size_of::<Mat4>()));
+  
+  // Freeing the entity removes its instances from the renderer.
+  entity.free()?;
+  assert_eq!(renderer.get_instance_buffer_size(entity.get_uuid()), None);
+  return Ok(());
+}
+
+#[ignore]
+#[test]
+fn test_update_instance_transform() -> Result<(), EnumEngineError> {
+  let layer = Layer::new("Instance update", EmptyApp::default());
+  let window = Window::new(EnumRendererApi::OpenGL);
+  let renderer = Renderer::new(EnumRendererApi::OpenGL);
+  let mut engine = Engine::new(window, renderer, vec![layer]);
+  engine.apply()?;
+  
+  let vertex_shader = ShaderStage::new(EnumShaderStageType::Vertex,
+    EnumShaderSource::FromFile(String::from("res/shaders/test.vert")));
+  let fragment_shader = ShaderStage::new(EnumShaderStageType::Fragment,
+    EnumShaderSource::FromFile(String::from("res/shaders/test.frag")));
+  
+  let mut shader = Shader::default();
+  shader.set_hint(EnumShaderHint::InstanceMatrixLocation(7));
+  shader.push_stage(vertex_shader)?;
+  shader.push_stage(fragment_shader)?;
+  shader.apply()?;
+  
+  let asset_loader = AssetLoader::new();
+  let mut entity = REntity::new_instanced(asset_loader.load("res/assets/n64_logo/n64_logo.obj")?,
+    EnumPrimitiveShading::default(), "Instanced", 100);
+  entity.apply(&mut shader)?;
+  assert_eq!(engine.get_renderer_mut().get_instance_buffer_size(entity.get_uuid()), Some(100 * size_of::<Mat4>()));
+  
+  // Updating a few instances overwrites them in place, without reallocating the buffer.
+  entity.set_instance_transform(10, Mat4::translation_matrix(&Vec3::new(&[5.0, 0.0, 0.0])));
+  entity.set_instance_transform(42, Mat4::translation_matrix(&Vec3::new(&[0.0, 5.0, 0.0])));
+  entity.reapply()?;
+  assert!(!entity.has_changed());
+  assert_eq!(engine.get_renderer_mut().get_instance_buffer_size(entity.get_uuid()), Some(100 * size_of::<Mat4>()));
+  assert_eq!(entity.get_instance_transform(0), Some(Mat4::new(1.0)));
+  return Ok(());
+}
@@ -0,0 +1,119 @@
+/*
+ MIT License
+ 
+ Copyright (c) 2024 Nami Reghbati
+ 
+ Permission is hereby granted, free of charge, to any person obtaining a copy
+ of this software and associated documentation files (the "Software"), to deal
+ in the Software without restriction, including without limitation the rights
+ to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ copies of the Software, and to permit persons to whom the Software is
+ furnished to do so, subject to the following conditions:
+ 
+ The above copyright notice and this permission notice shall be included in all
+ copies or substantial portions of the Software.
+ 
+ THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ FITNESS FOR A PARTICULAR PURPOSE AND NON INFRINGEMENT. IN NO EVENT SHALL THE
+ AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ SOFTWARE.
+*/
+
+use wave_editor::wave_core::graphics::color::Color;
+use wave_editor::wave_core::graphics::light::{C_LIGHTS_UBO_SIZE, C_MAX_POINT_LIGHTS, EnumLight};
+use wave_editor::wave_core::math::{Aabb, Mat4, Vec3};
+
+fn read_f32(data: &[u8], offset: usize) -> f32 {
+  return f32::from_ne_bytes(data[offset..offset + 4].try_into().unwrap());
+}
+
+fn project(matrix: &Mat4, point: &Vec3<f32>) -> [f32; 3] {
+  let row = |i: usize| matrix[i][0] * point.x + matrix[i][1] * point.y + matrix[i][2] * point.z + matrix[i][3];
+  let w = row(3);
+  return [row(0) / w, row(1) / w, row(2) / w];
+}
+
+fn read_i32(data: &[u8], offset: usize) -> i32 {
+  return i32::from_ne_bytes(data[offset..offset + 4].try_into().unwrap());
+}
+
+#[test]
+fn test_pack_lights() {
+  let sun = EnumLight::Directional {
+    m_direction: Vec3::new(&[0.0, -2.0, 0.0]),
+    m_color: Color::from([255, 255, 255, 255]),
+    m_intensity: 0.5,
+  };
+  let lamp = EnumLight::Point {
+    m_position: Vec3::new(&[1.0, 2.0, 3.0]),
+    m_color: Color::from([255, 0, 0, 255]),
+    m_intensity: 2.0,
+    m_range: 10.0,
+  };
+  
+  let data = EnumLight::pack(&[lamp, sun]);
+  assert_eq!(data.len(), C_LIGHTS_UBO_SIZE);
+  
+  // Counts.
+  assert_eq!(read_i32(&data, 0), 1);
+  assert_eq!(read_i32(&data, 4), 1);
+  
+  // First directional light at offset 16, with a normalized direction.
+  assert_eq!(read_f32(&data, 16 + 4), -1.0);
+  assert_eq!(read_f32(&data, 16 + 16), 1.0);
+  assert_eq!(read_f32(&data, 16 + 28), 0.5);
+  
+  // First point light at offset 144, range in position.w and intensity in color.a.
+  assert_eq!((read_f32(&data, 144), read_f32(&data, 148), read_f32(&data, 152)), (1.0, 2.0, 3.0));
+  assert_eq!(read_f32(&data, 144 + 12), 10.0);
+  assert_eq!(read_f32(&data, 144 + 16), 1.0);
+  assert_eq!(read_f32(&data, 144 + 20), 0.0);
+  assert_eq!(read_f32(&data, 144 + 28), 2.0);
+}
+
+#[test]
+fn test_pack_lights_cap() {
+  let lamps = vec![EnumLight::Point {
+    m_position: Vec3::default(),
+    m_color: Color::default(),
+    m_intensity: 1.0,
+    m_range: 5.0,
+  }; C_MAX_POINT_LIGHTS + 4];
+  
+  let data = EnumLight::pack(&lamps);
+  assert_eq!(data.len(), C_LIGHTS_UBO_SIZE);
+  assert_eq!(read_i32(&data, 4), C_MAX_POINT_LIGHTS as i32);
+}
+
+#[test]
+fn test_light_space_matrices() {
+  let sun = EnumLight::Directional {
+    m_direction: Vec3::new(&[-1.0, -1.0, 0.0]),
+    m_color: Color::default(),
+    m_intensity: 1.0,
+  };
+  let bounds = Aabb::new(Vec3::new(&[-5.0, 0.0, -5.0]), Vec3::new(&[5.0, 2.0, 5.0]));
+  
+  let (view, projection) = sun.get_light_space_matrices(&bounds).unwrap();
+  let light_space = projection * view;
+  
+  // The center of the scene lands in the middle of the shadow map, and every corner fits inside it.
+  let center = project(&light_space, &bounds.get_center());
+  assert!(center[0].abs() < 1e-4 && center[1].abs() < 1e-4);
+  for corner in bounds.get_corners().iter() {
+    let ndc = project(&light_space, corner);
+    assert!(ndc.iter().all(|coordinate| (-1.0..=1.0).contains(coordinate)), "{0:?} outside of the shadow map", ndc);
+  }
+  
+  // Point lights don't cast shadows.
+  let lamp = EnumLight::Point {
+    m_position: Vec3::default(),
+    m_color: Color::default(),
+    m_intensity: 1.0,
+    m_range: 5.0,
+  };
+  assert!(lamp.get_light_space_matrices(&bounds).is_none());
+}
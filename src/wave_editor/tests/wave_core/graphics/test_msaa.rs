@@ -0,0 +1,55 @@
+/*
+ MIT License
+ 
+ Copyright (c) 2023 Nami Reghbati
+ 
+ Permission is hereby granted, free of charge, to any person obtaining a copy
+ of this software and associated documentation files (the "Software"), to deal
+ in the Software without restriction, including without limitation the rights
+ to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ copies of the Software, and to permit persons to whom the Software is
+ furnished to do so, subject to the following conditions:
+ 
+ The above copyright notice and this permission notice shall be included in all
+ copies or substantial portions of the Software.
+ 
+ THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ FITNESS FOR A PARTICULAR PURPOSE AND NON INFRINGEMENT. IN NO EVENT SHALL THE
+ AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ SOFTWARE.
+*/
+
+use wave_editor::wave_core::{EmptyApp, Engine, EnumEngineError, TraitApply};
+use wave_editor::wave_core::graphics::renderer::{EnumRendererApi, Renderer};
+use wave_editor::wave_core::layers::Layer;
+use wave_editor::wave_core::window::Window;
+
+#[ignore]
+#[test]
+fn test_toggle_msaa() -> Result<(), EnumEngineError> {
+  let layer = Layer::new("MSAA toggle", EmptyApp::default());
+  let window = Window::new(EnumRendererApi::OpenGL);
+  let renderer = Renderer::new(EnumRendererApi::OpenGL);
+  let mut engine = Engine::new(window, renderer, vec![layer]);
+  engine.apply()?;
+  
+  let renderer = engine.get_renderer_mut();
+  renderer.toggle_msaa(Some(4))?;
+  assert_eq!(renderer.get_msaa_sample_count(), Some(4));
+  
+  // Sample counts that are not powers of two are rejected, leaving the current one in place.
+  assert!(renderer.toggle_msaa(Some(3)).is_err());
+  assert_eq!(renderer.get_msaa_sample_count(), Some(4));
+  
+  // Sample counts above what the hardware supports are clamped to GL_MAX_SAMPLES.
+  let max_samples = renderer.get_capabilities().m_max_samples;
+  renderer.toggle_msaa(Some(1024))?;
+  assert_eq!(renderer.get_msaa_sample_count(), Some(max_samples));
+  
+  renderer.toggle_msaa(None)?;
+  assert_eq!(renderer.get_msaa_sample_count(), None);
+  return engine.free();
+}
@@ -0,0 +1,72 @@
+/*
+ MIT License
+ 
+ Copyright (c) 2023 Nami Reghbati
+ 
+ Permission is hereby granted, free of charge, to any person obtaining a copy
+ of this software and associated documentation files (the "Software"), to deal
+ in the Software without restriction, including without limitation the rights
+ to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ copies of the Software, and to permit persons to whom the Software is
+ furnished to do so, subject to the following conditions:
+ 
+ The above copyright notice and this permission notice shall be included in all
+ copies or substantial portions of the Software.
+ 
+ THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ FITNESS FOR A PARTICULAR PURPOSE AND NON INFRINGEMENT. IN NO EVENT SHALL THE
+ AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ SOFTWARE.
+*/
+
+use wave_editor::wave_core::{EmptyApp, Engine, EnumEngineError, TraitApply};
+use wave_editor::wave_core::dependencies::gl;
+use wave_editor::wave_core::graphics::RenderTarget;
+use wave_editor::wave_core::graphics::renderer::{EnumRendererApi, PostProcessChain, Renderer};
+use wave_editor::wave_core::graphics::texture::EnumTextureFormat;
+use wave_editor::wave_core::layers::Layer;
+use wave_editor::wave_core::window::Window;
+
+#[ignore]
+#[test]
+fn test_post_process_chain() -> Result<(), EnumEngineError> {
+  let layer = Layer::new("Post process", EmptyApp::default());
+  let window = Window::new(EnumRendererApi::OpenGL);
+  let renderer = Renderer::new(EnumRendererApi::OpenGL);
+  let mut engine = Engine::new(window, renderer, vec![layer]);
+  engine.apply()?;
+  
+  let input = RenderTarget::new(EnumRendererApi::OpenGL, 320, 240, &[EnumTextureFormat::Rgba], false)?;
+  let mut chain = PostProcessChain::new();
+  
+  // No passes : the input is returned as is.
+  assert!(std::ptr::eq(chain.apply(&input)?, &input));
+  
+  chain.add_pass(PostProcessChain::tonemap_pass()?);
+  chain.add_pass(PostProcessChain::tonemap_pass()?);
+  assert_eq!(chain.len(), 2);
+  
+  let output = chain.apply(&input)?;
+  assert!(!std::ptr::eq(output, &input));
+  assert_eq!(output.get_size(), (320, 240));
+  
+  engine.get_renderer_mut().set_post_process(chain);
+  assert_eq!(engine.get_renderer_mut().get_post_process_mut().map(|chain| chain.len()), Some(2));
+  engine.get_renderer_mut().clear_post_process();
+  assert!(engine.get_renderer_mut().get_post_process_mut().is_none());
+  
+  // Freeing the chain also frees the shaders of its passes.
+  let mut owned_chain = PostProcessChain::new();
+  let pass = PostProcessChain::tonemap_pass()?;
+  let pass_id = pass.get_id();
+  owned_chain.add_pass(pass);
+  assert_eq!(unsafe { gl::IsProgram(pass_id) }, gl::TRUE);
+  owned_chain.free()?;
+  assert!(owned_chain.is_empty());
+  assert_eq!(unsafe { gl::IsProgram(pass_id) }, gl::FALSE);
+  
+  return engine.free();
+}
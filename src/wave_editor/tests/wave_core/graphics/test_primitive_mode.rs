@@ -0,0 +1,68 @@
+/*
+ MIT License
+ 
+ Copyright (c) 2023 Nami Reghbati
+ 
+ Permission is hereby granted, free of charge, to any person obtaining a copy
+ of this software and associated documentation files (the "Software"), to deal
+ in the Software without restriction, including without limitation the rights
+ to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ copies of the Software, and to permit persons to whom the Software is
+ furnished to do so, subject to the following conditions:
+ 
+ The above copyright notice and this permission notice shall be included in all
+ copies or substantial portions of the Software.
+ 
+ THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ FITNESS FOR A PARTICULAR PURPOSE AND NON INFRINGEMENT. IN NO EVENT SHALL THE
+ AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ SOFTWARE.
+*/
+
+use wave_editor::wave_core::{EmptyApp, Engine, EnumEngineError, TraitApply};
+use wave_editor::wave_core::assets::asset_loader::AssetLoader;
+use wave_editor::wave_core::assets::r_assets::{EnumPrimitiveShading, REntity};
+use wave_editor::wave_core::graphics::renderer::{EnumRendererApi, EnumRendererRenderPrimitiveAs, Renderer};
+use wave_editor::wave_core::graphics::shader::{EnumShaderSource, EnumShaderStageType, Shader, ShaderStage};
+use wave_editor::wave_core::layers::Layer;
+use wave_editor::wave_core::window::Window;
+
+#[ignore]
+#[test]
+fn test_primitive_mode_per_entity() -> Result<(), EnumEngineError> {
+  let layer = Layer::new("Primitive mode", EmptyApp::default());
+  let window = Window::new(EnumRendererApi::OpenGL);
+  let renderer = Renderer::new(EnumRendererApi::OpenGL);
+  let mut engine = Engine::new(window, renderer, vec![layer]);
+  engine.apply()?;
+  
+  let mut shader = Shader::default();
+  shader.push_stage(ShaderStage::new(EnumShaderStageType::Vertex,
+    EnumShaderSource::FromFile(String::from("res/shaders/test.vert"))))?;
+  shader.push_stage(ShaderStage::new(EnumShaderStageType::Fragment,
+    EnumShaderSource::FromFile(String::from("res/shaders/test.frag"))))?;
+  shader.apply()?;
+  
+  let asset_loader = AssetLoader::new();
+  let mut filled = REntity::new(asset_loader.load("res/assets/n64_logo/n64_logo.obj")?, EnumPrimitiveShading::default(), "Filled");
+  let mut wireframe = REntity::new(asset_loader.load("res/assets/n64_logo/n64_logo.obj")?, EnumPrimitiveShading::default(),
+    "Wireframe");
+  engine.get_renderer_mut().enqueue(&mut filled, &mut shader)?;
+  engine.get_renderer_mut().enqueue(&mut wireframe, &mut shader)?;
+  
+  filled.toggle_primitive_mode(EnumRendererRenderPrimitiveAs::Filled);
+  wireframe.toggle_primitive_mode(EnumRendererRenderPrimitiveAs::Wireframe);
+  filled.reapply()?;
+  wireframe.reapply()?;
+  
+  // Both entities get drawn in the same frame, and the api state goes back to filled afterward.
+  engine.get_renderer_mut().on_render()?;
+  assert_eq!(filled.get_primitive_mode(), EnumRendererRenderPrimitiveAs::Filled);
+  assert_eq!(wireframe.get_primitive_mode(), EnumRendererRenderPrimitiveAs::Wireframe);
+  assert_eq!(engine.get_renderer_mut().get_polygon_mode()?, EnumRendererRenderPrimitiveAs::Filled);
+  
+  return engine.free();
+}
@@ -0,0 +1,84 @@
+/*
+ MIT License
+ 
+ Copyright (c) 2023 Nami Reghbati
+ 
+ Permission is hereby granted, free of charge, to any person obtaining a copy
+ of this software and associated documentation files (the "Software"), to deal
+ in the Software without restriction, including without limitation the rights
+ to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ copies of the Software, and to permit persons to whom the Software is
+ furnished to do so, subject to the following conditions:
+ 
+ The above copyright notice and this permission notice shall be included in all
+ copies or substantial portions of the Software.
+ 
+ THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ FITNESS FOR A PARTICULAR PURPOSE AND NON INFRINGEMENT. IN NO EVENT SHALL THE
+ AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ SOFTWARE.
+*/
+
+use wave_editor::wave_core::{EmptyApp, Engine, EnumEngineError, TraitApply};
+use wave_editor::wave_core::graphics::RenderTarget;
+use wave_editor::wave_core::graphics::renderer::{EnumRendererApi, Renderer};
+use wave_editor::wave_core::graphics::texture::EnumTextureFormat;
+use wave_editor::wave_core::layers::Layer;
+use wave_editor::wave_core::window::Window;
+
+#[ignore]
+#[test]
+fn test_render_target() -> Result<(), EnumEngineError> {
+  let layer = Layer::new("Render target", EmptyApp::default());
+  let window = Window::new(EnumRendererApi::OpenGL);
+  let renderer = Renderer::new(EnumRendererApi::OpenGL);
+  let mut engine = Engine::new(window, renderer, vec![layer]);
+  engine.apply()?;
+  
+  let mut target = RenderTarget::new(EnumRendererApi::OpenGL, 256, 256, &[EnumTextureFormat::Rgba], true)?;
+  assert_eq!(target.get_size(), (256, 256));
+  assert!(target.color_texture_handle(0).is_some());
+  assert!(target.color_texture_handle(1).is_none());
+  let _color_texture = target.get_color_texture();
+  
+  let mut has_drawn = false;
+  engine.get_renderer_mut().render_to(&target, || has_drawn = true)?;
+  assert!(has_drawn);
+  
+  target.resize(512, 128)?;
+  assert_eq!(target.get_size(), (512, 128));
+  
+  // Depth only, like shadow maps.
+  let depth_target = RenderTarget::new(EnumRendererApi::OpenGL, 1024, 1024, &[], true)?;
+  assert!(depth_target.color_texture_handle(0).is_none());
+  
+  return engine.free();
+}
+
+#[ignore]
+#[test]
+fn test_render_to_target() -> Result<(), EnumEngineError> {
+  let layer = Layer::new("Render to target", EmptyApp::default());
+  let window = Window::new(EnumRendererApi::OpenGL);
+  let renderer = Renderer::new(EnumRendererApi::OpenGL);
+  let mut engine = Engine::new(window, renderer, vec![layer]);
+  engine.apply()?;
+  
+  let target = RenderTarget::new(EnumRendererApi::OpenGL, 64, 64, &[EnumTextureFormat::Rgba], true)?;
+  let renderer = engine.get_renderer_mut();
+  renderer.set_clear_color(1.0, 0.0, 0.0, 1.0)?;
+  assert!(renderer.set_render_target(Some(target)).is_none());
+  renderer.on_render()?;
+  
+  let target = renderer.set_render_target(None).expect("Render target should still be set!");
+  let pixels = target.read_pixels(0)?;
+  assert_eq!(pixels.len(), 64 * 64 * 4);
+  assert!(pixels.chunks_exact(4).all(|pixel| pixel == [255, 0, 0, 255]));
+  
+  // Only existing color attachments can be read.
+  assert!(target.read_pixels(1).is_err());
+  return engine.free();
+}
@@ -22,10 +22,15 @@
  SOFTWARE.
 */
 
+use std::collections::HashSet;
+use std::mem::size_of;
+
 use wave_core::graphics::shader;
-use wave_core::{TraitApply};
+use wave_core::{TraitApply, TraitFree};
 use wave_core::graphics::renderer::EnumRendererApi;
 use wave_editor::wave_core::{EmptyApp, Engine, EnumEngineError};
+use wave_editor::wave_core::camera::C_CAMERA_UBO_BINDING;
+use wave_editor::wave_core::dependencies::gl;
 use wave_editor::wave_core::graphics::renderer::{Renderer};
 use wave_editor::wave_core::graphics::shader::{EnumShaderSource, EnumShaderStageType, ShaderStage};
 use wave_editor::wave_core::layers::Layer;
@@ -88,4 +93,178 @@ fn test_load_uniforms() -> Result<(), EnumEngineError> {
     Ok(_) => { Ok(()) }
     Err(err) => { Err(EnumEngineError::from(err)) }
   };
-}
\ No newline at end of file
+}
+
+#[ignore]
+#[test]
+fn test_shader_hot_reload() -> Result<(), EnumEngineError> {
+  let layer = Layer::new("Shader hot reload", EmptyApp::default());
+  let window = Window::new(EnumRendererApi::OpenGL);
+  let renderer = Renderer::new(EnumRendererApi::OpenGL);
+  let mut engine = Engine::new(window, renderer, vec![layer]);
+  engine.apply()?;
+  
+  let temp_dir = std::env::temp_dir().join("wave_engine_shader_hot_reload");
+  std::fs::create_dir_all(&temp_dir)?;
+  let vertex_path = temp_dir.join("hot_reload.vert");
+  let fragment_path = temp_dir.join("hot_reload.frag");
+  std::fs::copy("res/shaders/test.vert", &vertex_path)?;
+  std::fs::copy("res/shaders/test.frag", &fragment_path)?;
+  
+  let mut shader = shader::Shader::default();
+  shader.push_stage(ShaderStage::new(EnumShaderStageType::Vertex,
+    EnumShaderSource::FromFile(vertex_path.to_str().unwrap().to_string())))?;
+  shader.push_stage(ShaderStage::new(EnumShaderStageType::Fragment,
+    EnumShaderSource::FromFile(fragment_path.to_str().unwrap().to_string())))?;
+  shader.apply()?;
+  shader.watch_sources();
+  
+  // Nothing changed yet.
+  assert!(!shader.reload_if_changed()?);
+  let previous_id = shader.get_id();
+  
+  // A broken shader is logged and the previous program is kept.
+  let valid_fragment_source = std::fs::read_to_string(&fragment_path)?;
+  std::fs::write(&fragment_path, "#version 460 core\nvoid main() { this does not compile }")?;
+  let file = std::fs::File::options().write(true).open(&fragment_path)?;
+  file.set_modified(std::time::SystemTime::now() + std::time::Duration::from_secs(1))?;
+  assert!(!shader.reload_if_changed()?);
+  assert_eq!(shader.get_id(), previous_id);
+  
+  // Fixing it reloads the shader.
+  std::fs::write(&fragment_path, valid_fragment_source)?;
+  let file = std::fs::File::options().write(true).open(&fragment_path)?;
+  file.set_modified(std::time::SystemTime::now() + std::time::Duration::from_secs(2))?;
+  assert!(shader.reload_if_changed()?);
+  return Ok(());
+}
+
+#[ignore]
+#[test]
+fn test_uniform_blocks() -> Result<(), EnumEngineError> {
+  let layer = Layer::new("Shader uniform blocks", EmptyApp::default());
+  let window = Window::new(EnumRendererApi::OpenGL);
+  let renderer = Renderer::new(EnumRendererApi::OpenGL);
+  let mut engine = Engine::new(window, renderer, vec![layer]);
+  engine.apply()?;
+  
+  let mut shaders: Vec<shader::Shader> = Vec::with_capacity(2);
+  for _ in 0..2 {
+    let mut shader = shader::Shader::new(EnumRendererApi::OpenGL, HashSet::from([
+      ShaderStage::new(EnumShaderStageType::Vertex, EnumShaderSource::FromFile(String::from("res/shaders/glsl_460.vert"))),
+      ShaderStage::new(EnumShaderStageType::Fragment, EnumShaderSource::FromStr(String::from("#version 460 core\n\
+      layout (std140) uniform ubo_tint { vec4 m_tint; } Ubo_tint;\n\
+      layout (location = 0) out vec4 fout_color;\n\
+      void main() { fout_color = Ubo_tint.m_tint; }\n")))
+    ]));
+    shader.apply()?;
+    shaders.push(shader);
+  }
+  
+  // Typos in block names should be caught, and bindings filled by the renderer left alone.
+  assert!(shaders[0].create_uniform_block("ubo_tnt", 2, 4 * size_of::<f32>()).is_err());
+  assert!(shaders[0].create_uniform_block("ubo\0tint", 2, 4 * size_of::<f32>()).is_err());
+  assert!(shaders[0].create_uniform_block("ubo_tint", C_CAMERA_UBO_BINDING, 4 * size_of::<f32>()).is_err());
+  
+  for shader in shaders.iter_mut() {
+    shader.create_uniform_block("ubo_tint", 2, 4 * size_of::<f32>())?;
+  }
+  // Shaders sharing a block must agree on its size.
+  assert!(shaders[1].create_uniform_block("ubo_tint", 2, 8 * size_of::<f32>()).is_err());
+  
+  // Both shaders read from the same buffer, whichever one uploads to it.
+  let tint: Vec<u8> = [1.0f32, 0.5, 0.25, 1.0].iter().flat_map(|channel| channel.to_ne_bytes()).collect();
+  shaders[1].update_uniform_block(2, 0, &tint)?;
+  let mut buffer_id: i32 = 0;
+  let mut block_data: Vec<u8> = vec![0; tint.len()];
+  unsafe {
+    gl::GetIntegeri_v(gl::UNIFORM_BUFFER_BINDING, 2, &mut buffer_id);
+    gl::GetNamedBufferSubData(buffer_id as u32, 0, block_data.len() as isize, block_data.as_mut_ptr() as *mut std::ffi::c_void);
+  }
+  assert_eq!(block_data, tint);
+  
+  // Out of range updates and unknown bindings are rejected.
+  assert!(shaders[0].update_uniform_block(2, 1, &tint).is_err());
+  assert!(shaders[0].update_uniform_block(5, 0, &tint).is_err());
+  
+  // The buffer outlives the first shader freed.
+  shaders[0].free()?;
+  assert_eq!(unsafe { gl::IsBuffer(buffer_id as u32) }, gl::TRUE);
+  shaders[1].update_uniform_block(2, 0, &tint)?;
+  shaders[1].free()?;
+  assert_eq!(unsafe { gl::IsBuffer(buffer_id as u32) }, gl::FALSE);
+  return engine.free();
+}
+
+#[ignore]
+#[test]
+fn test_compile_log_and_reflection() -> Result<(), EnumEngineError> {
+  let layer = Layer::new("Shader reflection", EmptyApp::default());
+  let window = Window::new(EnumRendererApi::OpenGL);
+  let renderer = Renderer::new(EnumRendererApi::OpenGL);
+  let mut engine = Engine::new(window, renderer, vec![layer]);
+  engine.apply()?;
+  
+  // The driver's log comes back with the failing stage.
+  let mut broken_shader = shader::Shader::new(EnumRendererApi::OpenGL, HashSet::from([
+    ShaderStage::new(EnumShaderStageType::Vertex, EnumShaderSource::FromFile(String::from("res/shaders/debug_line.vert"))),
+    ShaderStage::new(EnumShaderStageType::Fragment,
+      EnumShaderSource::FromStr(String::from("#version 430 core\nvoid main() { this does not compile }")))
+  ]));
+  match broken_shader.apply() {
+    Err(shader::EnumShaderError::CompileFailed { stage, log }) => {
+      assert_eq!(stage, EnumShaderStageType::Fragment);
+      assert!(!log.is_empty());
+    }
+    _ => panic!("Expected a compile error on the fragment stage!"),
+  }
+  
+  let mut shader = shader::Shader::new(EnumRendererApi::OpenGL, HashSet::from([
+    ShaderStage::new(EnumShaderStageType::Vertex, EnumShaderSource::FromFile(String::from("res/shaders/debug_line.vert"))),
+    ShaderStage::new(EnumShaderStageType::Fragment, EnumShaderSource::FromFile(String::from("res/shaders/debug_line.frag")))
+  ]));
+  shader.apply()?;
+  assert_eq!(shader.get_active_uniforms(), vec![(String::from("u_view_projection"), shader::EnumUniformType::Mat4)]);
+  return engine.free();
+}
+
+#[ignore]
+#[test]
+fn test_compute_dispatch() -> Result<(), EnumEngineError> {
+  let layer = Layer::new("Shader compute dispatch", EmptyApp::default());
+  let window = Window::new(EnumRendererApi::OpenGL);
+  let renderer = Renderer::new(EnumRendererApi::OpenGL);
+  let mut engine = Engine::new(window, renderer, vec![layer]);
+  engine.apply()?;
+  
+  let compute_source = String::from("#version 430 core\n\
+  layout (local_size_x = 1) in;\n\
+  layout (std430, binding = 3) buffer ssbo_output { uint values[]; };\n\
+  void main() { values[gl_GlobalInvocationID.x] = 42u; }");
+  
+  // Compute stages cannot be linked with raster stages.
+  let mut mixed_shader = shader::Shader::new(EnumRendererApi::OpenGL, HashSet::from([
+    ShaderStage::new(EnumShaderStageType::Vertex, EnumShaderSource::FromFile(String::from("res/shaders/debug_line.vert"))),
+    ShaderStage::new(EnumShaderStageType::Compute, EnumShaderSource::FromStr(compute_source.clone()))
+  ]));
+  assert!(matches!(mixed_shader.apply(), Err(shader::EnumShaderError::MixedComputeStages)));
+  
+  let mut compute_shader = shader::Shader::new_compute(EnumRendererApi::OpenGL,
+    ShaderStage::new(EnumShaderStageType::Compute, EnumShaderSource::FromStr(compute_source)));
+  compute_shader.apply()?;
+  assert!(compute_shader.is_compute());
+  
+  compute_shader.create_storage_buffer(3, 4 * size_of::<u32>())?;
+  compute_shader.dispatch(4, 1, 1)?;
+  compute_shader.memory_barrier(shader::EnumShaderBarrier::BufferUpdate)?;
+  
+  let bytes = compute_shader.read_storage_buffer(3, 0, 4 * size_of::<u32>())?;
+  let values: Vec<u32> = bytes.chunks_exact(size_of::<u32>())
+    .map(|chunk| u32::from_ne_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+    .collect();
+  assert_eq!(values, vec![42; 4]);
+  
+  // Reading past the end of the buffer is rejected.
+  assert!(compute_shader.read_storage_buffer(3, 4, 4 * size_of::<u32>()).is_err());
+  return engine.free();
+}
@@ -0,0 +1,99 @@
+/*
+ MIT License
+ 
+ Copyright (c) 2023 Nami Reghbati
+ 
+ Permission is hereby granted, free of charge, to any person obtaining a copy
+ of this software and associated documentation files (the "Software"), to deal
+ in the Software without restriction, including without limitation the rights
+ to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ copies of the Software, and to permit persons to whom the Software is
+ furnished to do so, subject to the following conditions:
+ 
+ The above copyright notice and this permission notice shall be included in all
+ copies or substantial portions of the Software.
+ 
+ THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ FITNESS FOR A PARTICULAR PURPOSE AND NON INFRINGEMENT. IN NO EVENT SHALL THE
+ AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ SOFTWARE.
+*/
+
+use wave_editor::wave_core::{EmptyApp, Engine, EnumEngineError, TraitApply};
+use wave_editor::wave_core::graphics::color::Color;
+use wave_editor::wave_core::graphics::renderer::{EnumRendererApi, Renderer};
+use wave_editor::wave_core::graphics::text::{EnumTextError, generate_sdf};
+use wave_editor::wave_core::layers::Layer;
+use wave_editor::wave_core::math::Vec2;
+use wave_editor::wave_core::ui::Font;
+use wave_editor::wave_core::window::Window;
+
+#[test]
+fn test_sdf_generation() {
+  // A filled 4x4 square.
+  let coverage: Vec<u8> = vec![255; 16];
+  let sdf = generate_sdf(&coverage, 4, 4, 2);
+  assert_eq!(sdf.len(), 8 * 8);
+  
+  // Inside the square, right next to the outline, then farther away outside of it.
+  assert!(sdf[3 * 8 + 3] > 127);
+  assert!(sdf[3 * 8 + 1] < 128);
+  assert_eq!(sdf[0], 0);
+  // Distances grow toward the center.
+  assert!(sdf[3 * 8 + 3] > sdf[3 * 8 + 2]);
+  
+  // Empty coverage is all outside.
+  assert!(generate_sdf(&[0; 16], 4, 4, 2).iter().all(|value| *value == 0));
+}
+
+#[test]
+fn test_font_loading() -> Result<(), EnumEngineError> {
+  let font = Font::from_file("res/fonts/DejaVuSansMono.ttf", 32.0)?;
+  assert_eq!(font.get_pixel_size(), 32.0);
+  assert!(font.get_line_height() > 0.0);
+  assert!(font.get_glyph('A').is_some());
+  assert!(font.get_glyph('é').is_none());
+  
+  let (atlas_width, atlas_height) = font.get_atlas_size();
+  assert_eq!(atlas_width, 512);
+  assert!(atlas_height.is_power_of_two());
+  
+  // Monospaced : every character advances the same, and sizes scale linearly.
+  let advance = font.get_glyph('i').unwrap().get_advance();
+  assert_eq!(font.get_glyph('W').unwrap().get_advance(), advance);
+  let size = font.measure("Hello", 32.0);
+  assert!((size.x - 5.0 * advance).abs() < 1e-3);
+  assert!((font.measure("Hello", 16.0).x - size.x / 2.0).abs() < 1e-3);
+  
+  // Lines stack up, the longest one setting the width.
+  let two_lines = font.measure("Hi\nHello", 32.0);
+  assert!((two_lines.x - size.x).abs() < 1e-3);
+  assert!((two_lines.y - 2.0 * font.get_line_height()).abs() < 1e-3);
+  
+  assert_eq!(Font::from_file("res/fonts/missing.ttf", 32.0).unwrap_err(),
+    EnumTextError::InvalidPath(String::from("res/fonts/missing.ttf")));
+  assert_eq!(Font::from_bytes(&[0, 1, 2, 3], 32.0).unwrap_err(), EnumTextError::InvalidFont);
+  return Ok(());
+}
+
+#[ignore]
+#[test]
+fn test_draw_text() -> Result<(), EnumEngineError> {
+  let layer = Layer::new("Text", EmptyApp::default());
+  let window = Window::new(EnumRendererApi::OpenGL);
+  let renderer = Renderer::new(EnumRendererApi::OpenGL);
+  let mut engine = Engine::new(window, renderer, vec![layer]);
+  engine.apply()?;
+  
+  let mut font = Font::from_file("res/fonts/DejaVuSansMono.ttf", 48.0)?;
+  engine.get_renderer_mut().draw_text(&mut font, "Wave Engine\nSDF text", Vec2::new(&[16.0, 16.0]), 24.0,
+    Color::default())?;
+  engine.get_renderer_mut().draw_text(&mut font, "Big", Vec2::new(&[16.0, 96.0]), 96.0, Color::default())?;
+  engine.get_renderer_mut().on_render()?;
+  
+  font.free()?;
+  return engine.free();
+}
@@ -0,0 +1,134 @@
+/*
+ MIT License
+ 
+ Copyright (c) 2023 Nami Reghbati
+ 
+ Permission is hereby granted, free of charge, to any person obtaining a copy
+ of this software and associated documentation files (the "Software"), to deal
+ in the Software without restriction, including without limitation the rights
+ to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ copies of the Software, and to permit persons to whom the Software is
+ furnished to do so, subject to the following conditions:
+ 
+ The above copyright notice and this permission notice shall be included in all
+ copies or substantial portions of the Software.
+ 
+ THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ FITNESS FOR A PARTICULAR PURPOSE AND NON INFRINGEMENT. IN NO EVENT SHALL THE
+ AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ SOFTWARE.
+*/
+
+use wave_editor::wave_core::{EmptyApp, Engine, EnumEngineError, TraitApply, TraitHint};
+use wave_editor::wave_core::graphics::renderer::{EnumRendererApi, Renderer};
+use wave_editor::wave_core::graphics::texture::{EnumTextureError, EnumTextureFilter, EnumTextureFormat, Texture, TextureArray};
+use wave_editor::wave_core::layers::Layer;
+use wave_editor::wave_core::utils::texture_loader::{EnumTextureLoaderHint, TextureLoader};
+use wave_editor::wave_core::window::Window;
+
+// OpenGL filter values.
+const C_NEAREST: u32 = 0x2600;
+const C_LINEAR: u32 = 0x2601;
+const C_LINEAR_MIPMAP_LINEAR: u32 = 0x2703;
+
+#[ignore]
+#[test]
+fn test_texture_filters() -> Result<(), EnumEngineError> {
+  let layer = Layer::new("Texture filters", EmptyApp::default());
+  let window = Window::new(EnumRendererApi::OpenGL);
+  let renderer = Renderer::new(EnumRendererApi::OpenGL);
+  let mut engine = Engine::new(window, renderer, vec![layer]);
+  engine.apply()?;
+  
+  // Defaults to trilinear filtering with mipmaps.
+  let mut texture_loader = TextureLoader::new();
+  let mut texture = Texture::new(EnumRendererApi::OpenGL, texture_loader.load("res/textures/n64_logo/n64_submesh_0.png")?);
+  texture.apply()?;
+  assert_eq!(texture.get_filters()?, (C_LINEAR_MIPMAP_LINEAR, C_LINEAR));
+  
+  // Pixel art preset.
+  texture_loader.set_hint(EnumTextureLoaderHint::GenerateMipmaps(false));
+  texture_loader.set_hint(EnumTextureLoaderHint::FilterMode(EnumTextureFilter::Nearest));
+  let mut pixel_texture = Texture::new(EnumRendererApi::OpenGL,
+    texture_loader.load("res/textures/n64_logo/n64_submesh_0.png")?);
+  pixel_texture.apply()?;
+  assert_eq!(pixel_texture.get_filters()?, (C_NEAREST, C_NEAREST));
+  
+  return engine.free();
+}
+
+#[ignore]
+#[test]
+fn test_texture_anisotropy() -> Result<(), EnumEngineError> {
+  let layer = Layer::new("Texture anisotropy", EmptyApp::default());
+  let window = Window::new(EnumRendererApi::OpenGL);
+  let renderer = Renderer::new(EnumRendererApi::OpenGL);
+  let mut engine = Engine::new(window, renderer, vec![layer]);
+  engine.apply()?;
+  
+  // 1.0 if the extension is unsupported, in which case the level is ignored.
+  let max_anisotropy = engine.get_renderer_mut().get_capabilities().m_max_anisotropy;
+  
+  // Out of range levels get clamped to what the driver supports, never fail.
+  let mut texture_loader = TextureLoader::new();
+  texture_loader.set_hint(EnumTextureLoaderHint::Anisotropy(1024.0));
+  let mut texture = Texture::new(EnumRendererApi::OpenGL, texture_loader.load("res/textures/n64_logo/n64_submesh_0.png")?);
+  texture.apply()?;
+  assert_eq!(texture.get_filters()?, (C_LINEAR_MIPMAP_LINEAR, C_LINEAR));
+  assert_eq!(texture.get_anisotropy()?, max_anisotropy);
+  
+  texture_loader.set_hint(EnumTextureLoaderHint::Anisotropy(0.0));
+  let mut low_texture = Texture::new(EnumRendererApi::OpenGL,
+    texture_loader.load("res/textures/n64_logo/n64_submesh_0.png")?);
+  low_texture.apply()?;
+  assert_eq!(low_texture.get_anisotropy()?, 1.0);
+  
+  // In range levels are kept as is.
+  texture_loader.set_hint(EnumTextureLoaderHint::Anisotropy(2.0));
+  let mut valid_texture = Texture::new(EnumRendererApi::OpenGL,
+    texture_loader.load("res/textures/n64_logo/n64_submesh_0.png")?);
+  valid_texture.apply()?;
+  assert_eq!(valid_texture.get_anisotropy()?, 2.0_f32.min(max_anisotropy));
+  
+  return engine.free();
+}
+
+#[test]
+fn test_texture_array_size_mismatch() -> Result<(), EnumEngineError> {
+  let texture_loader = TextureLoader::new();
+  let textures_1024 = texture_loader.load_from_folder("res/textures/mario")?;
+  let texture_64 = texture_loader.load("res/textures/n64_logo/n64_submesh_0.png")?;
+  
+  let mut texture_array = TextureArray::new(EnumRendererApi::OpenGL, textures_1024)
+    .expect("Textures of the same size should fit in a single array");
+  assert_eq!(texture_array.get_current_depth(), 8);
+  
+  // Mismatched layers are rejected as a whole, leaving the array as it was.
+  let textures_mixed = vec![texture_loader.load("res/textures/mario/mario_submesh_0.png")?, texture_64.clone()];
+  assert_eq!(texture_array.append(textures_mixed),
+    Err(EnumTextureError::SizeMismatch { expected: (1024, 1024), got: (64, 64) }));
+  assert_eq!(texture_array.get_current_depth(), 8);
+  
+  assert_eq!(TextureArray::new(EnumRendererApi::OpenGL, vec![texture_64.clone(), texture_loader
+    .load("res/textures/normal_maps/diffuse_smooth.png")?]).err(),
+    Some(EnumTextureError::SizeMismatch { expected: (64, 64), got: (512, 512) }));
+  
+  // Nothing to compare against, nothing to reject.
+  assert!(TextureArray::new(EnumRendererApi::OpenGL, vec![]).is_ok_and(|texture_array| texture_array.len() == 0));
+  return Ok(());
+}
+
+#[test]
+fn test_texture_array_format_mismatch() -> Result<(), EnumEngineError> {
+  let mut texture_loader = TextureLoader::new();
+  let rgb_texture = texture_loader.load("res/textures/mario/mario_submesh_0.png")?;
+  texture_loader.set_hint(EnumTextureLoaderHint::TargetFormat(EnumTextureFormat::Rgba));
+  let rgba_texture = texture_loader.load("res/textures/mario/mario_submesh_1.png")?;
+  
+  assert_eq!(TextureArray::new(EnumRendererApi::OpenGL, vec![rgb_texture, rgba_texture]).err(),
+    Some(EnumTextureError::FormatMismatch { expected: EnumTextureFormat::Rgb, got: EnumTextureFormat::Rgba }));
+  return Ok(());
+}
@@ -0,0 +1,133 @@
+/*
+ MIT License
+ 
+ Copyright (c) 2023 Nami Reghbati
+ 
+ Permission is hereby granted, free of charge, to any person obtaining a copy
+ of this software and associated documentation files (the "Software"), to deal
+ in the Software without restriction, including without limitation the rights
+ to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ copies of the Software, and to permit persons to whom the Software is
+ furnished to do so, subject to the following conditions:
+ 
+ The above copyright notice and this permission notice shall be included in all
+ copies or substantial portions of the Software.
+ 
+ THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ FITNESS FOR A PARTICULAR PURPOSE AND NON INFRINGEMENT. IN NO EVENT SHALL THE
+ AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ SOFTWARE.
+*/
+
+use std::collections::HashSet;
+
+use wave_editor::wave_core::{EmptyApp, Engine, EnumEngineError, TraitApply};
+use wave_editor::wave_core::assets::r_assets::REntity;
+use wave_editor::wave_core::camera::{C_CAMERA_UBO_BINDING, Camera, EnumCameraType};
+use wave_editor::wave_core::dependencies::gl;
+use wave_editor::wave_core::graphics::renderer::{EnumRendererApi, Renderer};
+use wave_editor::wave_core::graphics::shader::{EnumShaderSource, EnumShaderStageType, Shader, ShaderStage};
+use wave_editor::wave_core::graphics::uniform_buffer::TraitUniformData;
+use wave_editor::wave_core::graphics::UniformBuffer;
+use wave_editor::wave_core::layers::Layer;
+use wave_editor::wave_core::math::{Mat4, Vec3};
+use wave_editor::wave_core::window::Window;
+
+#[test]
+fn test_uniform_data_std140() {
+  // Matrices are uploaded column-major, so the translation ends up in the last column.
+  let translation = Mat4::translation_matrix(&Vec3::new(&[1.0, 2.0, 3.0]));
+  let floats: Vec<f32> = translation.to_std140().chunks_exact(4)
+    .map(|chunk| f32::from_ne_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+    .collect();
+  assert_eq!(floats.len(), 16);
+  assert_eq!(&floats[12..15], &[1.0, 2.0, 3.0]);
+  
+  // Scalars in arrays are padded to a vec4.
+  assert_eq!(true.to_std140(), 1u32.to_ne_bytes().to_vec());
+  assert_eq!([1.0f32, 2.0].to_std140().len(), 32);
+  assert_eq!([Mat4::new(1.0), Mat4::new(1.0)].to_std140().len(), 2 * Mat4::get_size());
+}
+
+#[ignore]
+#[test]
+fn test_uniform_buffer_upload() -> Result<(), EnumEngineError> {
+  let layer = Layer::new("Uniform buffer", EmptyApp::default());
+  let window = Window::new(EnumRendererApi::OpenGL);
+  let renderer = Renderer::new(EnumRendererApi::OpenGL);
+  let mut engine = Engine::new(window, renderer, vec![layer]);
+  engine.apply()?;
+  
+  let mut uniform_buffer = UniformBuffer::new(5)?;
+  let view_projection = Mat4::translation_matrix(&Vec3::new(&[4.0, -2.0, 8.0]));
+  uniform_buffer.upload(&view_projection)?;
+  uniform_buffer.bind()?;
+  
+  // What the GPU sees is exactly what was packed.
+  assert_eq!(uniform_buffer.len(), Mat4::get_size());
+  assert_eq!(uniform_buffer.read(0, Mat4::get_size())?, view_projection.to_std140());
+  
+  // Bigger uploads grow the buffer.
+  let matrices = [Mat4::new(1.0), view_projection];
+  uniform_buffer.upload(&matrices[..])?;
+  assert_eq!(uniform_buffer.read(0, 2 * Mat4::get_size())?, matrices.to_std140());
+  assert!(uniform_buffer.read(0, 3 * Mat4::get_size()).is_err());
+  
+  let mut shader = Shader::new(EnumRendererApi::OpenGL, HashSet::from([
+    ShaderStage::new(EnumShaderStageType::Vertex, EnumShaderSource::FromFile(String::from("res/shaders/glsl_460.vert"))),
+    ShaderStage::new(EnumShaderStageType::Fragment, EnumShaderSource::FromFile(String::from("res/shaders/glsl_460.frag")))
+  ]));
+  shader.apply()?;
+  shader.bind_uniform_block("ubo_camera", uniform_buffer.get_binding_point())?;
+  assert!(shader.bind_uniform_block("ubo_camra", uniform_buffer.get_binding_point()).is_err());
+  
+  uniform_buffer.free()?;
+  return engine.free();
+}
+
+#[ignore]
+#[test]
+fn test_shared_camera_block() -> Result<(), EnumEngineError> {
+  let layer = Layer::new("Shared camera block", EmptyApp::default());
+  let window = Window::new(EnumRendererApi::OpenGL);
+  let renderer = Renderer::new(EnumRendererApi::OpenGL);
+  let mut engine = Engine::new(window, renderer, vec![layer]);
+  engine.apply()?;
+  
+  // Two batches, each with their own shader.
+  let mut first_shader = Shader::new(EnumRendererApi::OpenGL, HashSet::from([
+    ShaderStage::new(EnumShaderStageType::Vertex, EnumShaderSource::FromFile(String::from("res/shaders/glsl_460.vert"))),
+    ShaderStage::new(EnumShaderStageType::Fragment, EnumShaderSource::FromFile(String::from("res/shaders/glsl_460.frag")))
+  ]));
+  let mut second_shader = Shader::new(EnumRendererApi::OpenGL, HashSet::from([
+    ShaderStage::new(EnumShaderStageType::Vertex, EnumShaderSource::FromFile(String::from("res/shaders/glsl_460.vert"))),
+    ShaderStage::new(EnumShaderStageType::Fragment, EnumShaderSource::FromFile(String::from("res/shaders/glsl_460.frag")))
+  ]));
+  first_shader.apply()?;
+  second_shader.apply()?;
+  
+  let mut first_cube = REntity::default();
+  let mut second_cube = REntity::default();
+  first_cube.apply(&mut first_shader)?;
+  second_cube.apply(&mut second_shader)?;
+  
+  let camera = Camera::new(EnumCameraType::Perspective(75, 16.0 / 9.0, 0.1, 100.0), None);
+  let matrices = [camera.get_view_matrix(), camera.get_projection_matrix()];
+  engine.get_renderer_mut().submit_camera(camera)?;
+  
+  // A single buffer at the camera binding holds the view and projection read by both shaders.
+  let mut buffer_id: i32 = 0;
+  let mut camera_data: Vec<u8> = vec![0; 2 * Mat4::get_size()];
+  unsafe {
+    gl::GetIntegeri_v(gl::UNIFORM_BUFFER_BINDING, C_CAMERA_UBO_BINDING, &mut buffer_id);
+    gl::GetNamedBufferSubData(buffer_id as u32, 0, camera_data.len() as isize,
+      camera_data.as_mut_ptr() as *mut std::ffi::c_void);
+  }
+  assert_ne!(buffer_id, 0);
+  assert_eq!(camera_data, matrices.to_std140());
+  
+  return engine.free();
+}
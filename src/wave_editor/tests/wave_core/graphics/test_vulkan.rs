@@ -24,6 +24,10 @@
 
 #[cfg(feature = "vulkan")]
 use wave_engine::wave_core::graphics::vulkan::renderer::{ash::vk, VkContext};
+use wave_editor::wave_core::{EmptyApp, Engine, EnumEngineError, TraitApply};
+use wave_editor::wave_core::graphics::renderer::{EnumRendererApi, EnumRendererError, Renderer};
+use wave_editor::wave_core::layers::Layer;
+use wave_editor::wave_core::window::Window;
 
 #[cfg(feature = "vulkan")]
 #[ignore]
@@ -261,4 +265,44 @@ fn test_instance_creation() {
       assert!(false);
     }
   }
-}
\ No newline at end of file
+}
+
+#[ignore]
+#[test]
+fn test_vulkan_fallback() -> Result<(), EnumEngineError> {
+  let window = Window::new(EnumRendererApi::Vulkan);
+  assert_eq!(Renderer::probe_api(EnumRendererApi::OpenGL), EnumRendererApi::OpenGL);
+  let expected_api = Renderer::probe_api(EnumRendererApi::Vulkan);
+  
+  // Requesting Vulkan without support ends up with a working OpenGL renderer, not a panic.
+  let renderer = Renderer::new(EnumRendererApi::Vulkan);
+  let mut engine = Engine::new(window, renderer, vec![Layer::new("Vulkan fallback", EmptyApp::default())]);
+  engine.apply()?;
+  assert_eq!(engine.get_renderer_mut().get_type(), expected_api);
+  return engine.free();
+}
+
+#[ignore]
+#[test]
+fn test_switch_renderer() -> Result<(), EnumEngineError> {
+  let window = Window::new(EnumRendererApi::OpenGL);
+  let renderer = Renderer::new(EnumRendererApi::OpenGL);
+  let mut engine = Engine::new(window, renderer, vec![Layer::new("Renderer switch", EmptyApp::default())]);
+  engine.apply()?;
+  
+  // Switching to the api in use does nothing.
+  engine.switch_renderer(EnumRendererApi::OpenGL)?;
+  assert_eq!(engine.get_renderer_mut().get_type(), EnumRendererApi::OpenGL);
+  
+  if Renderer::probe_api(EnumRendererApi::Vulkan) == EnumRendererApi::Vulkan {
+    engine.switch_renderer(EnumRendererApi::Vulkan)?;
+    assert_eq!(engine.get_renderer_mut().get_type(), EnumRendererApi::Vulkan);
+    engine.switch_renderer(EnumRendererApi::OpenGL)?;
+  } else {
+    // Unsupported apis are rejected, keeping the current renderer.
+    assert!(matches!(engine.switch_renderer(EnumRendererApi::Vulkan),
+      Err(EnumEngineError::RendererError(EnumRendererError::UnsupportedApi))));
+  }
+  assert_eq!(engine.get_renderer_mut().get_type(), EnumRendererApi::OpenGL);
+  return engine.free();
+}
@@ -23,13 +23,21 @@
 */
 
 use std::collections::HashMap;
+use std::sync::Mutex;
+
 use wave_core::{TraitApply, TraitHint};
 use wave_core::graphics::renderer::EnumRendererApi;
 
+use wave_editor::wave_core::dependencies::glfw;
 use wave_editor::wave_core::EnumEngineError;
-use wave_editor::wave_core::input::{EnumAction, EnumKey, EnumModifiers, EnumMouseButton, Input};
+use wave_editor::wave_core::events::EnumEvent;
+use wave_editor::wave_core::input::{ActionMap, EnumAction, EnumGamepadAxis, EnumGamepadButton, EnumInputBinding, EnumInputError, EnumKey, EnumModifiers, EnumMouseButton, Input, InputFrame};
+use wave_editor::wave_core::math::Vec2;
 use wave_editor::wave_core::window::{EnumWindowMode, EnumWindowHint, Window};
 
+// Input states are global, tests changing them can't run alongside each other.
+static S_INPUT_STATE_LOCK: Mutex<()> = Mutex::new(());
+
 fn synchronous_key_inputs_loop(window: &mut Window, keys: &mut HashMap<EnumKey, bool>, action_required: EnumAction,
                                modifier: EnumModifiers) -> Result<(), EnumEngineError> {
   let copy = keys.clone();
@@ -215,4 +223,229 @@ fn test_synchronous_mouse_button_inputs() -> Result<(), EnumEngineError> {
   }
   
   return Ok(());
-}
\ No newline at end of file
+}
+
+#[ignore]
+#[test]
+fn test_mouse_cursor_position() -> Result<(), EnumEngineError> {
+  let mut window = Window::new(EnumRendererApi::OpenGL);
+  
+  // No api window until the window is applied.
+  assert_eq!(Input::get_mouse_cursor_position(&window).err(), Some(EnumInputError::InvalidWindowContext));
+  
+  window.apply()?;
+  window.get_api_mut().poll_events();
+  
+  assert!(Input::get_mouse_cursor_position(&window).is_ok());
+  
+  // First delta has nothing to compare against.
+  assert_eq!(Input::get_mouse_cursor_delta(&window)?, Vec2::new(&[0.0, 0.0]));
+  return Ok(());
+}
+
+#[test]
+fn test_gamepad_deadzone() {
+  // Readouts within the deadzone are snapped to rest.
+  assert_eq!(Input::apply_deadzone(0.05, 0.1), 0.0);
+  assert_eq!(Input::apply_deadzone(-0.1, 0.1), 0.0);
+  
+  // The rest is rescaled to still cover the whole range, in both directions.
+  assert!((Input::apply_deadzone(0.55, 0.1) - 0.5).abs() < 1e-6);
+  assert!((Input::apply_deadzone(-0.55, 0.1) + 0.5).abs() < 1e-6);
+  assert_eq!(Input::apply_deadzone(1.0, 0.1), 1.0);
+  assert_eq!(Input::apply_deadzone(-1.0, 0.1), -1.0);
+  
+  // Out of range readouts and deadzones are clamped.
+  assert_eq!(Input::apply_deadzone(1.5, 0.1), 1.0);
+  assert_eq!(Input::apply_deadzone(0.5, -1.0), 0.5);
+  assert_eq!(Input::apply_deadzone(0.99, 2.0), 0.0);
+}
+
+#[ignore]
+#[test]
+fn test_gamepad_disconnected() -> Result<(), EnumEngineError> {
+  let mut window = Window::new(EnumRendererApi::OpenGL);
+  window.apply()?;
+  
+  // Nothing plugged in at the last joystick slot, nor past it.
+  for gamepad_id in [15, 16] {
+    assert!(!Input::is_gamepad_connected(&window, gamepad_id));
+    assert!(!Input::get_gamepad_button_state(&window, gamepad_id, EnumGamepadButton::A, EnumAction::Pressed));
+    assert_eq!(Input::get_gamepad_axis(&window, gamepad_id, EnumGamepadAxis::LeftX, 0.1), 0.0);
+  }
+  return Ok(());
+}
+
+#[test]
+fn test_action_map() -> Result<(), EnumInputError> {
+  let mut action_map = ActionMap::new();
+  action_map.bind("move_forward", EnumInputBinding::Key(EnumKey::W));
+  action_map.bind("move_forward", EnumInputBinding::Key(EnumKey::Up));
+  action_map.bind("move_forward", EnumInputBinding::Key(EnumKey::W));
+  action_map.bind("shoot", EnumInputBinding::MouseButton(EnumMouseButton::LeftButton));
+  action_map.bind("shoot", EnumInputBinding::GamepadButton(0, EnumGamepadButton::RightBumper));
+  
+  // Duplicate bindings are ignored.
+  assert_eq!(action_map.get_bindings("move_forward"),
+    Some(&vec![EnumInputBinding::Key(EnumKey::W), EnumInputBinding::Key(EnumKey::Up)]));
+  assert_eq!(action_map.to_string(), "move_forward=Key:W,Key:Up\nshoot=MouseButton:LeftButton,GamepadButton:0:RightBumper\n");
+  
+  // Round trip through a file.
+  let file_path = std::env::temp_dir().join("wave_test_action_map.cfg");
+  action_map.save(file_path.to_str().unwrap())?;
+  assert_eq!(ActionMap::load(file_path.to_str().unwrap())?, action_map);
+  
+  action_map.unbind("move_forward", EnumInputBinding::Key(EnumKey::W));
+  assert_eq!(action_map.get_bindings("move_forward"), Some(&vec![EnumInputBinding::Key(EnumKey::Up)]));
+  
+  assert_eq!(ActionMap::parse("jump=Key:NotAKey").err(), Some(EnumInputError::InvalidKey));
+  assert_eq!(ActionMap::parse("jump").err(), Some(EnumInputError::InvalidActionMapFile));
+  assert_eq!(ActionMap::parse("jump=GamepadButton:1:NotAButton").err(), Some(EnumInputError::InvalidGamepadButton));
+  assert_eq!(ActionMap::parse("jump=GamepadButton:A").err(), Some(EnumInputError::InvalidBinding));
+  return Ok(());
+}
+
+#[test]
+fn test_key_repeat_count() {
+  let _lock = S_INPUT_STATE_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+  let key_event = |action: glfw::Action| -> EnumEvent {
+    return EnumEvent::from(glfw::WindowEvent::Key(glfw::Key::Backspace, 0, action, glfw::Modifiers::empty()));
+  };
+  
+  assert_eq!(key_event(glfw::Action::Press), EnumEvent::KeyEvent(EnumKey::Backspace, EnumAction::Pressed, None,
+    EnumModifiers::empty()));
+  assert_eq!(Input::get_key_repeat(EnumKey::Backspace), None);
+  
+  // Holding the key across frames keeps counting up.
+  for expected_count in 1..=5u32 {
+    assert_eq!(key_event(glfw::Action::Repeat), EnumEvent::KeyEvent(EnumKey::Backspace, EnumAction::Held,
+      Some(expected_count), EnumModifiers::empty()));
+    assert_eq!(Input::get_key_repeat(EnumKey::Backspace), Some(expected_count));
+  }
+  
+  // Releasing resets the count for the next press.
+  key_event(glfw::Action::Release);
+  assert_eq!(Input::get_key_repeat(EnumKey::Backspace), None);
+  key_event(glfw::Action::Press);
+  assert_eq!(key_event(glfw::Action::Repeat), EnumEvent::KeyEvent(EnumKey::Backspace, EnumAction::Held, Some(1),
+    EnumModifiers::empty()));
+  assert_eq!(Input::get_key_repeat(EnumKey::Unknown), None);
+}
+
+fn recorded_frame() -> InputFrame {
+  let mut frame = InputFrame::default();
+  frame.set_key(EnumKey::A, EnumAction::Released, EnumAction::Pressed);
+  frame.set_key(EnumKey::B, EnumAction::Pressed, EnumAction::Held);
+  frame.set_key(EnumKey::C, EnumAction::Pressed, EnumAction::Released);
+  frame.set_mouse_button(EnumMouseButton::LeftButton, EnumAction::Released, EnumAction::Pressed);
+  frame.set_gamepad_button(1, EnumGamepadButton::A, EnumAction::Released, EnumAction::Pressed);
+  frame.set_gamepad_button(1, EnumGamepadButton::Start, EnumAction::Pressed, EnumAction::Released);
+  frame.set_cursor_position(Vec2::new(&[320.0, 240.0]));
+  return frame;
+}
+
+#[test]
+fn test_input_snapshot_round_trip() -> Result<(), EnumInputError> {
+  let _lock = S_INPUT_STATE_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+  let frame = recorded_frame();
+  assert_eq!(frame.get_key(EnumKey::A), Some((EnumAction::Released, EnumAction::Pressed)));
+  assert_eq!(frame.get_key(EnumKey::Unknown), None);
+  assert_eq!(frame.get_mouse_button(EnumMouseButton::LeftButton), Some((EnumAction::Released, EnumAction::Pressed)));
+  assert_eq!(frame.get_gamepad_button(1, EnumGamepadButton::A), Some((EnumAction::Released, EnumAction::Pressed)));
+  assert_eq!(frame.get_gamepad_button(0, EnumGamepadButton::A), Some((EnumAction::Released, EnumAction::Released)));
+  assert_eq!(frame.get_gamepad_button(16, EnumGamepadButton::A), None);
+  
+  // Reading the state back while replaying gives the same frame, previous states included.
+  Input::apply_snapshot(&frame)?;
+  assert!(Input::is_replaying());
+  assert_eq!(Input::snapshot(), frame);
+  
+  // Replaying a recording frame by frame.
+  let recording: Vec<InputFrame> = vec![InputFrame::default(), frame.clone(), InputFrame::default()];
+  for recorded in recording.iter() {
+    Input::apply_snapshot(recorded)?;
+    assert_eq!(&Input::snapshot(), recorded);
+  }
+  
+  Input::stop_replay();
+  assert!(!Input::is_replaying());
+  return Ok(());
+}
+
+#[ignore]
+#[test]
+fn test_input_snapshot_replay() -> Result<(), EnumEngineError> {
+  let _lock = S_INPUT_STATE_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+  let mut window = Window::new(EnumRendererApi::OpenGL);
+  window.set_hint(EnumWindowHint::WindowMode(EnumWindowMode::Windowed));
+  window.apply()?;
+  
+  Input::apply_snapshot(&recorded_frame())?;
+  // Presses, holds and releases are detected from the recorded states, not the window's.
+  assert!(Input::get_key_state(&window, EnumKey::A, EnumAction::Pressed));
+  assert!(Input::get_key_state(&window, EnumKey::B, EnumAction::Held));
+  assert!(Input::get_key_state(&window, EnumKey::C, EnumAction::Released));
+  assert!(Input::get_mouse_button_state(&window, EnumMouseButton::LeftButton, EnumAction::Pressed));
+  assert!(Input::get_gamepad_button_state(&window, 1, EnumGamepadButton::A, EnumAction::Pressed));
+  assert!(Input::get_gamepad_button_state(&window, 1, EnumGamepadButton::Start, EnumAction::Released));
+  assert_eq!(Input::get_mouse_cursor_position(&window)?, Vec2::new(&[320.0, 240.0]));
+  
+  // Edges only show up once, like live input.
+  assert!(!Input::get_key_state(&window, EnumKey::A, EnumAction::Pressed));
+  assert!(Input::get_key_state(&window, EnumKey::A, EnumAction::Held));
+  
+  Input::stop_replay();
+  return Ok(());
+}
+
+#[test]
+fn test_mouse_frame_delta() -> Result<(), EnumInputError> {
+  let _lock = S_INPUT_STATE_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+  let mut frame = InputFrame::default();
+  
+  frame.set_cursor_position(Vec2::new(&[100.0, 50.0]));
+  Input::apply_snapshot(&frame)?;
+  Input::on_update();
+  
+  frame.set_cursor_position(Vec2::new(&[110.0, 45.0]));
+  Input::apply_snapshot(&frame)?;
+  Input::on_update();
+  assert_eq!(Input::get_mouse_delta(), Vec2::new(&[10.0, -5.0]));
+  // Same delta for every query until the next frame.
+  assert_eq!(Input::get_mouse_delta(), Vec2::new(&[10.0, -5.0]));
+  
+  Input::on_update();
+  assert_eq!(Input::get_mouse_delta(), Vec2::new(&[0.0, 0.0]));
+  
+  // Without a window nor a replay, the cursor doesn't move.
+  Input::stop_replay();
+  Input::on_update();
+  assert_eq!(Input::get_mouse_delta(), Vec2::new(&[0.0, 0.0]));
+  return Ok(());
+}
+
+#[test]
+fn test_key_repeat_count_per_key() {
+  let _lock = S_INPUT_STATE_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+  let key_event = |key: glfw::Key, action: glfw::Action| -> EnumEvent {
+    return EnumEvent::from(glfw::WindowEvent::Key(key, 0, action, glfw::Modifiers::empty()));
+  };
+  
+  // Press -> hold -> hold on two keys at once, each counting on its own.
+  key_event(glfw::Key::Backspace, glfw::Action::Press);
+  key_event(glfw::Key::Delete, glfw::Action::Press);
+  key_event(glfw::Key::Backspace, glfw::Action::Repeat);
+  key_event(glfw::Key::Backspace, glfw::Action::Repeat);
+  key_event(glfw::Key::Delete, glfw::Action::Repeat);
+  assert_eq!(Input::get_key_repeat(EnumKey::Backspace), Some(2));
+  assert_eq!(Input::get_key_repeat(EnumKey::Delete), Some(1));
+  
+  // Releasing one key only resets its own count.
+  assert_eq!(key_event(glfw::Key::Backspace, glfw::Action::Release), EnumEvent::KeyEvent(EnumKey::Backspace,
+    EnumAction::Released, None, EnumModifiers::empty()));
+  assert_eq!(Input::get_key_repeat(EnumKey::Backspace), None);
+  assert_eq!(Input::get_key_repeat(EnumKey::Delete), Some(1));
+  
+  key_event(glfw::Key::Delete, glfw::Action::Release);
+  assert_eq!(Input::get_key_repeat(EnumKey::Delete), None);
+}
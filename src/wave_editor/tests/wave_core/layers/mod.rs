@@ -0,0 +1,138 @@
+/*
+ MIT License
+
+ Copyright (c) 2023 Nami Reghbati
+
+ Permission is hereby granted, free of charge, to any person obtaining a copy
+ of this software and associated documentation files (the "Software"), to deal
+ in the Software without restriction, including without limitation the rights
+ to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ copies of the Software, and to permit persons to whom the Software is
+ furnished to do so, subject to the following conditions:
+
+ The above copyright notice and this permission notice shall be included in all
+ copies or substantial portions of the Software.
+
+ THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ FITNESS FOR A PARTICULAR PURPOSE AND NON INFRINGEMENT. IN NO EVENT SHALL THE
+ AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ SOFTWARE.
+*/
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use wave_editor::wave_core::EnumEngineError;
+use wave_editor::wave_core::events::{EnumEvent, EnumEventMask};
+use wave_editor::wave_core::layers::{dispatch_event, EnumLayerType, Layer, TraitLayer};
+
+// Records which layers received events, optionally consuming them.
+struct RecordingLayer {
+  m_name: &'static str,
+  m_type: EnumLayerType,
+  m_consumes: bool,
+  m_received: Rc<RefCell<Vec<&'static str>>>,
+}
+
+impl TraitLayer for RecordingLayer {
+  fn get_type(&self) -> EnumLayerType {
+    return self.m_type;
+  }
+  
+  fn on_apply(&mut self) -> Result<(), EnumEngineError> {
+    return Ok(());
+  }
+  
+  fn on_sync_event(&mut self) -> Result<(), EnumEngineError> {
+    return Ok(());
+  }
+  
+  fn on_async_event(&mut self, _event: &EnumEvent) -> Result<bool, EnumEngineError> {
+    self.m_received.borrow_mut().push(self.m_name);
+    return Ok(self.m_consumes);
+  }
+  
+  fn on_update(&mut self, _time_step: f64) -> Result<(), EnumEngineError> {
+    return Ok(());
+  }
+  
+  fn on_render(&mut self) -> Result<(), EnumEngineError> {
+    return Ok(());
+  }
+  
+  fn free(&mut self) -> Result<(), EnumEngineError> {
+    return Ok(());
+  }
+  
+  fn to_string(&self) -> String {
+    return format!("[{0}]", self.m_name);
+  }
+}
+
+fn new_layer(name: &'static str, layer_type: EnumLayerType, priority: i32, consumes: bool,
+             received: &Rc<RefCell<Vec<&'static str>>>) -> Layer {
+  let mut layer = Layer::new(name, RecordingLayer {
+    m_name: name,
+    m_type: layer_type,
+    m_consumes: consumes,
+    m_received: received.clone(),
+  });
+  layer.set_priority(priority);
+  layer.enable_async_polling_for(EnumEventMask::User | EnumEventMask::WindowSize);
+  return layer;
+}
+
+#[test]
+fn test_layer_dispatch_order() -> Result<(), EnumEngineError> {
+  let received = Rc::new(RefCell::new(Vec::new()));
+  
+  // The overlay only sits above the game thanks to its priority, App layers otherwise come first.
+  let mut layers = vec![
+    new_layer("Game", EnumLayerType::App, 0, false, &received),
+    new_layer("Overlay", EnumLayerType::Overlay, 10, false, &received),
+    new_layer("Editor", EnumLayerType::Editor, 0, false, &received),
+  ];
+  layers.sort_unstable();
+  
+  assert!(!dispatch_event(&mut layers, &EnumEvent::UserEvent(0, Box::new(0u32)))?);
+  assert_eq!(*received.borrow(), vec!["Overlay", "Game", "Editor"]);
+  
+  // Once the overlay consumes events, lower layers no longer see them.
+  received.borrow_mut().clear();
+  layers.push(new_layer("Consuming overlay", EnumLayerType::Overlay, 20, true, &received));
+  layers.sort_unstable();
+  
+  assert!(dispatch_event(&mut layers, &EnumEvent::UserEvent(0, Box::new(0u32)))?);
+  assert_eq!(*received.borrow(), vec!["Consuming overlay"]);
+  
+  // Except for resizes, which every layer needs to know about.
+  received.borrow_mut().clear();
+  dispatch_event(&mut layers, &EnumEvent::FramebufferEvent(800, 600))?;
+  assert_eq!(received.borrow().len(), 4);
+  return Ok(());
+}
+
+#[test]
+fn test_disabled_layer_skipped() -> Result<(), EnumEngineError> {
+  let received = Rc::new(RefCell::new(Vec::new()));
+  let mut layers = vec![
+    new_layer("Game", EnumLayerType::App, 0, false, &received),
+    new_layer("Overlay", EnumLayerType::Overlay, 10, true, &received),
+  ];
+  layers.sort_unstable();
+  
+  // A disabled overlay neither receives nor consumes events, but stays around.
+  layers[1].set_enabled(false);
+  assert!(!layers[1].is_enabled());
+  assert!(!dispatch_event(&mut layers, &EnumEvent::UserEvent(0, Box::new(0u32)))?);
+  assert_eq!(*received.borrow(), vec!["Game"]);
+  
+  received.borrow_mut().clear();
+  layers[1].set_enabled(true);
+  assert!(dispatch_event(&mut layers, &EnumEvent::UserEvent(0, Box::new(0u32)))?);
+  assert_eq!(*received.borrow(), vec!["Overlay"]);
+  return Ok(());
+}
@@ -140,6 +140,49 @@ fn test_vec3_eq() {
   assert_eq!(vec2_left + vec2_right, Vec2::default());
 }
 
+#[test]
+fn test_vec3_lerp() {
+  let vec3_from: Vec3<f32> = Vec3::new(&[0.0, 2.0, -4.0]);
+  let vec3_to: Vec3<f32> = Vec3::new(&[10.0, 4.0, 4.0]);
+  
+  assert_eq!(vec3_from.lerp(&vec3_to, 0.0), vec3_from);
+  assert_eq!(vec3_from.lerp(&vec3_to, 0.5), Vec3::new(&[5.0, 3.0, 0.0]));
+  assert_eq!(vec3_from.lerp(&vec3_to, 1.0), vec3_to);
+  
+  // Clamped vs unclamped.
+  assert_eq!(vec3_from.lerp(&vec3_to, 2.0), vec3_to);
+  assert_eq!(vec3_from.lerp_unclamped(&vec3_to, 2.0), Vec3::new(&[20.0, 6.0, 12.0]));
+}
+
+#[test]
+fn test_vec3_reflect() {
+  let vec3_incoming: Vec3<f32> = Vec3::new(&[1.0, -1.0, 0.5]);
+  let plane_normal: Vec3<f32> = Vec3::new(&[0.0, 1.0, 0.0]);
+  
+  assert_eq!(vec3_incoming.reflect(&plane_normal), Vec3::new(&[1.0, 1.0, 0.5]));
+}
+
+#[test]
+fn test_snap() {
+  assert_eq!(snap(1.4, 1.0), 1.0);
+  assert_eq!(snap(1.6, 1.0), 2.0);
+  assert_eq!(snap(0.3, 0.25), 0.25);
+  
+  // Negative values round to the nearest grid line as well, not towards zero.
+  assert_eq!(snap(-1.4, 1.0), -1.0);
+  assert_eq!(snap(-1.6, 1.0), -2.0);
+  assert_eq!(snap(-0.3, 0.25), -0.25);
+  assert_eq!(snap(-22.0, 15.0), -15.0);
+  assert_eq!(snap(-23.0, 15.0), -30.0);
+  assert!(snap(-0.1, 1.0).is_sign_positive());
+  
+  // No grid, no snapping.
+  assert_eq!(snap(-1.4, 0.0), -1.4);
+  
+  let vec3_snapped: Vec3<f32> = Vec3::new(&[0.6, -0.6, -2.2]).snap(0.5);
+  assert_eq!(vec3_snapped, Vec3::new(&[0.5, -0.5, -2.0]));
+}
+
 /*
 ///////////////////////////////////   VEC4  ///////////////////////////////////
 ///////////////////////////////////         ///////////////////////////////////
@@ -278,3 +321,167 @@ fn test_matrix_mul() {
                      10.000, 5.000, 2.500, 1.000\n"
   );
 }
+
+#[test]
+fn test_matrix_determinant() {
+  let mut matrix: Mat4 = Mat4::new(2.0);
+  assert_eq!(matrix.determinant(), 16.0);
+  
+  matrix[3][3] = 0.0;
+  assert_eq!(matrix.determinant(), 0.0);
+  assert!(matrix.inverse().is_none());
+}
+
+#[test]
+fn test_matrix_inverse() {
+  let matrix: Mat4 = Mat4::translation_matrix(&Vec3::new(&[10.0, -5.0, 2.5]))
+    * Mat4::rotation_matrix(&Vec3::new(&[45.0, 30.0, -90.0]));
+  
+  let inverse_matrix: Option<Mat4> = matrix.inverse();
+  assert!(inverse_matrix.is_some());
+  
+  let result: Mat4 = matrix * inverse_matrix.unwrap();
+  let identity: Mat4 = Mat4::new(1.0);
+  
+  for row in 0..4usize {
+    for col in 0..4usize {
+      assert!((result[row][col] - identity[row][col]).abs() < 1e-5,
+        "Mismatch at [{0}][{1}] : {2} != {3}", row, col, result[row][col], identity[row][col]);
+    }
+  }
+}
+
+#[test]
+fn test_matrix_inverse_scale() {
+  // Wide orthographic views have a tiny determinant, yet should still be invertible for picking.
+  let matrix: Mat4 = Mat4::apply_orthographic(-2000.0, 2000.0, -1000.0, 1000.0, 0.01, 1000.0);
+  assert!(matrix.determinant().abs() < f32::EPSILON);
+  
+  let result: Mat4 = matrix * matrix.inverse().expect("Wide orthographic projection should be invertible!");
+  let identity: Mat4 = Mat4::new(1.0);
+  for row in 0..4usize {
+    for col in 0..4usize {
+      assert!((result[row][col] - identity[row][col]).abs() < 1e-4);
+    }
+  }
+  
+  // Linearly dependent rows, no matter how large, are still singular.
+  let mut singular: Mat4 = Mat4::translation_matrix(&Vec3::new(&[100.0, 200.0, 300.0]));
+  for col in 0..4usize {
+    singular[2][col] = singular[0][col] * 3.0;
+  }
+  assert!(singular.inverse().is_none());
+}
+
+#[test]
+fn test_matrix_look_at() {
+  let eye: Vec3<f32> = Vec3::new(&[3.0, 4.0, -5.0]);
+  let view_matrix: Mat4 = Mat4::apply_look_at(&eye, &Vec3::new(&[0.0, 1.0, 0.0]), &Vec3::new(&[0.0, 1.0, 0.0]));
+  
+  // Project the eye position (w = 1) through the view matrix, which should land at the origin.
+  let mut eye_projected: Vec4<f32> = Vec4::default();
+  for row in 0..4usize {
+    eye_projected[row] = view_matrix[row][0] * eye.x + view_matrix[row][1] * eye.y + view_matrix[row][2] * eye.z
+      + view_matrix[row][3];
+  }
+  
+  for index in 0..3usize {
+    assert!(eye_projected[index].abs() < 1e-5);
+  }
+  assert_eq!(eye_projected.w, 1.0);
+  
+  // Looking straight up (forward parallel to up) should still yield a valid matrix.
+  let degenerate_matrix: Mat4 = Mat4::apply_look_at(&Vec3::default(), &Vec3::new(&[0.0, 10.0, 0.0]),
+    &Vec3::new(&[0.0, 1.0, 0.0]));
+  assert!((degenerate_matrix.determinant() - 1.0).abs() < 1e-5);
+  
+  // The previous name still works, until removed.
+  #[allow(deprecated)]
+  let legacy_matrix: Mat4 = Mat4::look_at(&eye, &Vec3::new(&[0.0, 1.0, 0.0]), &Vec3::new(&[0.0, 1.0, 0.0]));
+  assert_eq!(legacy_matrix, view_matrix);
+}
+
+#[test]
+fn test_matrix_look_at_basis() {
+  let view_matrix: Mat4 = Mat4::apply_look_at(&Vec3::new(&[-2.0, 7.0, 4.0]), &Vec3::new(&[5.0, -1.0, 3.0]),
+    &Vec3::new(&[0.0, 1.0, 0.0]));
+  
+  // The rotation rows are unit length and perpendicular to one another.
+  let rows: Vec<Vec3<f32>> = (0..3usize)
+    .map(|row| Vec3::new(&[view_matrix[row][0], view_matrix[row][1], view_matrix[row][2]]))
+    .collect();
+  for (index, row) in rows.iter().enumerate() {
+    assert!((row.vec_len() - 1.0).abs() < 1e-5);
+    assert!(row.dot(rows[(index + 1) % 3]).abs() < 1e-5);
+  }
+  
+  // From the origin looking down -Z with Y up, the view is already aligned with the world.
+  let aligned: Mat4 = Mat4::apply_look_at(&Vec3::default(), &Vec3::new(&[0.0, 0.0, -1.0]), &Vec3::new(&[0.0, 1.0, 0.0]));
+  for row in 0..4usize {
+    for column in 0..4usize {
+      let expected: f32 = (row == column).then(|| 1.0).unwrap_or(0.0);
+      assert!((aligned[row][column] - expected).abs() < 1e-6);
+    }
+  }
+}
+
+#[test]
+fn test_matrix_orthographic() {
+  let ortho_matrix: Mat4 = Mat4::apply_orthographic(-4.0, 6.0, -3.0, 5.0, 0.1, 100.0);
+  
+  // Right/top bounds on the near plane should map to +1 in clip space and left/bottom to -1.
+  let project = |x: f32, y: f32, z: f32| -> [f32; 3] {
+    let mut result: [f32; 3] = [0.0; 3];
+    for row in 0..3usize {
+      result[row] = ortho_matrix[row][0] * x + ortho_matrix[row][1] * y + ortho_matrix[row][2] * z + ortho_matrix[row][3];
+    }
+    return result;
+  };
+  
+  let top_right: [f32; 3] = project(6.0, 5.0, -0.1);
+  let bottom_left: [f32; 3] = project(-4.0, -3.0, -100.0);
+  
+  assert!((top_right[0] - 1.0).abs() < 1e-5 && (top_right[1] - 1.0).abs() < 1e-5 && (top_right[2] + 1.0).abs() < 1e-5);
+  assert!((bottom_left[0] + 1.0).abs() < 1e-5 && (bottom_left[1] + 1.0).abs() < 1e-5 && (bottom_left[2] - 1.0).abs() < 1e-5);
+  assert_eq!(ortho_matrix[3][3], 1.0);
+}
+
+/*
+///////////////////////////////////   QUATERNION  ///////////////////////////////////
+///////////////////////////////////               ///////////////////////////////////
+///////////////////////////////////               ///////////////////////////////////
+ */
+
+fn assert_matrix_near(left: &Mat4, right: &Mat4) {
+  for row in 0..4usize {
+    for col in 0..4usize {
+      assert!((left[row][col] - right[row][col]).abs() < 1e-5,
+        "Mismatch at [{0}][{1}] : {2} != {3}", row, col, left[row][col], right[row][col]);
+    }
+  }
+}
+
+#[test]
+fn test_quaternion_from_euler() {
+  let rotation: Vec3<f32> = Vec3::new(&[0.0, 90.0, 0.0]);
+  assert_matrix_near(&Quaternion::from_euler(&rotation).to_mat4(), &Mat4::rotation_matrix(&rotation));
+  
+  let rotation: Vec3<f32> = Vec3::new(&[30.0, -45.0, 60.0]);
+  assert_matrix_near(&Quaternion::from_euler(&rotation).to_mat4(), &Mat4::rotation_matrix(&rotation));
+}
+
+#[test]
+fn test_quaternion_slerp() {
+  let start: Quaternion = Quaternion::default();
+  let end: Quaternion = Quaternion::from_axis_angle(&Vec3::new(&[0.0, 1.0, 0.0]), 90.0);
+  
+  assert_matrix_near(&Quaternion::slerp(&start, &end, 0.0).to_mat4(), &start.to_mat4());
+  assert_matrix_near(&Quaternion::slerp(&start, &end, 1.0).to_mat4(), &end.to_mat4());
+  assert_matrix_near(&Quaternion::slerp(&start, &end, 0.5).to_mat4(),
+    &Quaternion::from_axis_angle(&Vec3::new(&[0.0, 1.0, 0.0]), 45.0).to_mat4());
+  
+  // Shortest arc : interpolating towards the negated (equivalent) quaternion should give the same result.
+  let negated_end: Quaternion = Quaternion::new(-end.w, -end.x, -end.y, -end.z);
+  assert_matrix_near(&Quaternion::slerp(&start, &negated_end, 0.5).to_mat4(),
+    &Quaternion::slerp(&start, &end, 0.5).to_mat4());
+}
@@ -23,7 +23,13 @@
 */
 
 pub mod math;
+pub mod camera;
+pub mod assets;
 pub mod utils;
 pub mod graphics;
 pub mod input;
 pub mod events;
+pub mod window;
+pub mod engine;
+pub mod layers;
+pub mod audio;
@@ -24,4 +24,5 @@
 
 pub mod test_logger;
 pub mod test_time;
-pub mod test_asset_loader;
\ No newline at end of file
+pub mod test_asset_loader;
+pub mod test_config;
@@ -22,10 +22,82 @@
  SOFTWARE.
 */
 
-use wave_editor::wave_core::assets::r_assets::REntity;
+use wave_editor::wave_core::assets::asset_loader::{AssetLoader, EnumAssetError, EnumNormalMode};
+use wave_editor::wave_core::assets::r_assets::{EnumPrimitiveShading, REntity};
+use wave_editor::wave_core::math::{Vec2, Vec3};
 
 #[test]
 fn test_obj_loader() {
   let cube = REntity::default();
   assert!(!cube.is_empty());
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_generated_normals() {
+  // Single quad in the XY plane, wound counter-clockwise.
+  let positions = [
+    Vec3::new(&[0.0, 0.0, 0.0]),
+    Vec3::new(&[1.0, 0.0, 0.0]),
+    Vec3::new(&[1.0, 1.0, 0.0]),
+    Vec3::new(&[0.0, 1.0, 0.0])
+  ];
+  let faces = [[0, 1, 2], [0, 2, 3]];
+  
+  let smooth_normals = AssetLoader::compute_normals(&positions, &faces, EnumNormalMode::Smooth);
+  assert_eq!(smooth_normals.len(), positions.len());
+  for normal in smooth_normals.iter() {
+    assert!(normal.x.abs() < f32::EPSILON && normal.y.abs() < f32::EPSILON);
+    assert!((normal.z - 1.0).abs() < f32::EPSILON);
+  }
+  
+  let flat_normals = AssetLoader::compute_normals(&positions, &faces, EnumNormalMode::Flat);
+  assert!(flat_normals.iter().all(|normal| (normal.z - 1.0).abs() < f32::EPSILON));
+}
+
+#[test]
+fn test_generated_tangents() {
+  // Single textured triangle in the XY plane, with U running along +X.
+  let positions = [Vec3::new(&[0.0, 0.0, 0.0]), Vec3::new(&[1.0, 0.0, 0.0]), Vec3::new(&[0.0, 1.0, 0.0])];
+  let uvs = [Vec2::new(&[0.0, 0.0]), Vec2::new(&[1.0, 0.0]), Vec2::new(&[0.0, 1.0])];
+  let normals = [Vec3::new(&[0.0, 0.0, 1.0]); 3];
+  let faces = [[0, 1, 2]];
+  
+  let tangents = AssetLoader::compute_tangents(&positions, &uvs, &normals, &faces);
+  assert_eq!(tangents.len(), positions.len());
+  for tangent in tangents.iter() {
+    assert!((tangent.x - 1.0).abs() < f32::EPSILON);
+    assert!(tangent.y.abs() < f32::EPSILON && tangent.z.abs() < f32::EPSILON);
+  }
+  
+  // Degenerate UVs should still yield a unit tangent orthogonal to the normal.
+  let degenerate_uvs = [Vec2::new(&[0.5, 0.5]); 3];
+  let fallback_tangents = AssetLoader::compute_tangents(&positions, &degenerate_uvs, &normals, &faces);
+  for (tangent, normal) in fallback_tangents.iter().zip(normals.iter()) {
+    assert!(!tangent.x.is_nan() && !tangent.y.is_nan() && !tangent.z.is_nan());
+    assert!((tangent.vec_len() - 1.0).abs() < 1e-5);
+    assert!(tangent.dot(*normal).abs() < 1e-5);
+  }
+}
+
+#[test]
+fn test_async_loader() {
+  let asset_loader = AssetLoader::new();
+  let mut handle = asset_loader.load_async("res/assets/n64_logo/n64_logo.obj");
+  assert_eq!(handle.get_path(), "res/assets/n64_logo/n64_logo.obj");
+  
+  // Poll like we would in on_update(), until the worker is done.
+  let mut result = None;
+  while result.is_none() {
+    result = handle.poll();
+    std::thread::sleep(std::time::Duration::from_millis(1));
+  }
+  assert!(handle.is_done());
+  assert!(handle.poll().is_none());
+  
+  let logo = REntity::new(result.unwrap().unwrap(), EnumPrimitiveShading::default(), "N64 Logo");
+  assert!(!logo.is_empty());
+  
+  // Errors should be reported back from the worker as well.
+  let mut invalid_handle = asset_loader.load_async("res/assets/does_not_exist.obj");
+  assert_eq!(invalid_handle.wait().err(), Some(EnumAssetError::InvalidPath));
+}
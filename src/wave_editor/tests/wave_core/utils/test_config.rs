@@ -0,0 +1,62 @@
+/*
+ MIT License
+ 
+ Copyright (c) 2023 Nami Reghbati
+ 
+ Permission is hereby granted, free of charge, to any person obtaining a copy
+ of this software and associated documentation files (the "Software"), to deal
+ in the Software without restriction, including without limitation the rights
+ to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ copies of the Software, and to permit persons to whom the Software is
+ furnished to do so, subject to the following conditions:
+ 
+ The above copyright notice and this permission notice shall be included in all
+ copies or substantial portions of the Software.
+ 
+ THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ FITNESS FOR A PARTICULAR PURPOSE AND NON INFRINGEMENT. IN NO EVENT SHALL THE
+ AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ SOFTWARE.
+*/
+
+use wave_editor::wave_core::graphics::renderer::EnumRendererApi;
+use wave_editor::wave_core::utils::config::{Config, EnumConfigError};
+use wave_editor::wave_core::window::EnumWindowMode;
+
+#[test]
+fn test_parse_config() {
+  let config = Config::parse("# Editor settings.\n\
+    [window]\n\
+    api = \"vulkan\"\n\
+    resolution = [1280, 720]  # 720p\n\
+    window_mode = \"borderless\"\n\
+    vsync = false\n\
+    msaa = 4\n\
+    unknown_key = 3\n").unwrap();
+  
+  assert_eq!(config.m_renderer_api, EnumRendererApi::Vulkan);
+  assert_eq!(config.m_resolution, Some((1280, 720)));
+  assert_eq!(config.m_window_mode, EnumWindowMode::Borderless);
+  assert!(!config.m_vsync);
+  assert_eq!(config.m_msaa, Some(4));
+  
+  // A '#' inside a quoted value doesn't start a comment.
+  assert_eq!(Config::parse("window_mode = \"windowed # not a comment\"").unwrap_err(), EnumConfigError::InvalidValue(1));
+  assert_eq!(Config::parse("window_mode = \"headless\" # \"windowed\"").unwrap().m_window_mode,
+    EnumWindowMode::Headless);
+}
+
+#[test]
+fn test_parse_config_defaults() {
+  // Missing fields keep their defaults.
+  let config = Config::parse("vsync = false").unwrap();
+  assert_eq!(config, Config { m_vsync: false, ..Config::default() });
+  assert_eq!(Config::parse("msaa = 0").unwrap().m_msaa, None);
+  
+  assert_eq!(Config::parse("\nresolution = [1280]").unwrap_err(), EnumConfigError::InvalidValue(2));
+  assert_eq!(Config::parse("vsync").unwrap_err(), EnumConfigError::InvalidSyntax(1));
+  assert!(matches!(Config::from_file("missing.toml"), Err(EnumConfigError::IoError(_))));
+}
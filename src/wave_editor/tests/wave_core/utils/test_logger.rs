@@ -79,3 +79,80 @@ fn test_function_name_length() {
   assert_eq!(function_without_namespace.len(), 23);
   assert_eq!(function_without_namespace, String::from("long_function_name_f..."));
 }
+
+#[test]
+fn test_log_level() {
+  assert_eq!(EnumLogLevel::from("ERROR"), EnumLogLevel::Error);
+  assert_eq!(EnumLogLevel::from("WARN"), EnumLogLevel::Warn);
+  assert_eq!(EnumLogLevel::from("INFO"), EnumLogLevel::Info);
+  assert_eq!(EnumLogLevel::from("DEBUG"), EnumLogLevel::Trace);
+  
+  let previous_level = get_log_level();
+  set_log_level(EnumLogLevel::Warn);
+  assert_eq!(get_log_level(), EnumLogLevel::Warn);
+  assert!(is_log_enabled(EnumLogLevel::Error));
+  assert!(is_log_enabled(EnumLogLevel::Warn));
+  assert!(!is_log_enabled(EnumLogLevel::Info));
+  assert!(!is_log_enabled(EnumLogLevel::Trace));
+  set_log_level(previous_level);
+}
+
+#[test]
+fn test_log_rotation() {
+  let directory = std::env::temp_dir().join("wave-engine-test-log-rotation");
+  let _ = std::fs::remove_dir_all(&directory);
+  std::fs::create_dir_all(&directory).unwrap();
+  let log_path = directory.join("test.log");
+  let log_path_str = log_path.to_str().unwrap();
+  
+  // Under the threshold, nothing happens.
+  std::fs::write(&log_path, "small").unwrap();
+  assert!(!rotate_if_larger_than(log_path_str, 64, 2).unwrap());
+  
+  // Past it, the log gets rotated, keeping at most two older logs.
+  for session in 0..3 {
+    std::fs::write(&log_path, format!("session {0}: {1}", session, "x".repeat(128))).unwrap();
+    assert!(rotate_if_larger_than(log_path_str, 64, 2).unwrap());
+    assert!(!log_path.exists());
+  }
+  assert!(std::fs::read_to_string(directory.join("test.1.log")).unwrap().starts_with("session 2"));
+  assert!(std::fs::read_to_string(directory.join("test.2.log")).unwrap().starts_with("session 1"));
+  assert!(!directory.join("test.3.log").exists());
+  
+  let _ = std::fs::remove_dir_all(&directory);
+}
+
+#[test]
+fn test_concurrent_log_lines() {
+  let log_path = std::env::temp_dir().join("wave-engine-test-concurrent-log.log");
+  let _ = std::fs::remove_file(&log_path);
+  let log_file = std::sync::Arc::new(std::sync::Mutex::new(std::fs::OpenOptions::new()
+    .append(true)
+    .create(true)
+    .open(&log_path)
+    .ok()));
+  
+  let threads: Vec<std::thread::JoinHandle<()>> = (0..8)
+    .map(|thread_index| {
+      let log_file = log_file.clone();
+      std::thread::spawn(move || {
+        for line_index in 0..100 {
+          write_log_line(&log_file, &format!("[Thread {0}] line {1} {2}", thread_index, line_index, "x".repeat(256)));
+        }
+      })
+    })
+    .collect();
+  threads.into_iter().for_each(|thread| thread.join().unwrap());
+  
+  // Every line must come out whole, never mixed with another thread's.
+  let logs: String = std::fs::read_to_string(&log_path).unwrap();
+  let lines: Vec<&str> = logs.lines().collect();
+  assert_eq!(lines.len(), 800);
+  for line in lines {
+    assert!(line.starts_with("[Thread "));
+    assert!(line.ends_with(&format!("{0}\x1b[0m", "x".repeat(256))));
+    assert_eq!(line.matches("[Thread ").count(), 1);
+  }
+  
+  let _ = std::fs::remove_file(&log_path);
+}
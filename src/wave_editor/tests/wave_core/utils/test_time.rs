@@ -23,7 +23,7 @@
 */
 
 use wave_editor::wave_core::dependencies::chrono;
-use wave_editor::wave_core::utils::Time;
+use wave_editor::wave_core::utils::{FixedTimeStep, profiler_report, ScopedTimer, Time};
 
 #[test]
 fn test_delta_time() {
@@ -60,4 +60,63 @@ fn test_wait_for() {
   Time::wait_for(-1.0);  // When we supply an invalid argument.
   
   assert_eq!(Time::get_delta(Time::from(chrono::Utc::now()), start_time).to_secs() as i64, 1);
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_fixed_time_step() {
+  let mut fixed_time_step = FixedTimeStep::new(50, 4);
+  assert_eq!(fixed_time_step.get_step(), 0.02);
+  
+  // Not enough for a single step yet, carry it over.
+  assert_eq!(fixed_time_step.accumulate(0.015), 0);
+  assert_eq!(fixed_time_step.accumulate(0.015), 1);
+  assert!((fixed_time_step.get_alpha() - 0.5).abs() < 1e-6);
+  
+  // Several steps in one frame.
+  fixed_time_step.reset();
+  assert_eq!(fixed_time_step.accumulate(0.061), 3);
+  
+  // A long hitch gets clamped, and the backlog dropped.
+  fixed_time_step.reset();
+  assert_eq!(fixed_time_step.accumulate(1.0), 4);
+  assert!(fixed_time_step.get_alpha() < 1.0);
+  assert_eq!(fixed_time_step.accumulate(0.0), 0);
+  
+  assert_eq!(fixed_time_step.accumulate(-1.0), 0);
+}
+
+#[test]
+fn test_time_arithmetic() {
+  let five_secs = Time::from(5.0);
+  let three_secs = Time::from(3.0);
+  
+  assert_eq!((five_secs + three_secs).to_secs(), 8.0);
+  assert_eq!((five_secs - three_secs).to_secs(), 2.0);
+}
+
+#[test]
+fn test_time_conversions() {
+  let one_and_a_half_secs = Time::from(1.5);
+  assert_eq!(one_and_a_half_secs.to_millis(), 1500.0);
+  assert_eq!(one_and_a_half_secs.to_micros(), 1500000.0);
+  
+  assert_eq!(Time::from_milli_u64(250).to_secs(), 0.25);
+  assert_eq!(Time::from_milli_f64(2.0).to_micros(), 2000.0);
+  assert_eq!(Time::from_micro_u64(500).to_millis(), 0.5);
+  assert_eq!(Time::from_micro_f64(1000.0).to_millis(), 1.0);
+}
+
+#[test]
+fn test_scoped_timer() {
+  for sleep_millis in [2, 6] {
+    let _timer = ScopedTimer::new("test_scoped_timer");
+    std::thread::sleep(std::time::Duration::from_millis(sleep_millis));
+  }
+  
+  let report = profiler_report();
+  let entry = report.iter().find(|entry| entry.m_label == "test_scoped_timer").unwrap();
+  assert_eq!(entry.m_calls, 2);
+  assert!(entry.m_min >= 2.0 && entry.m_max >= 6.0);
+  assert!(entry.m_min <= entry.get_average() && entry.get_average() <= entry.m_max);
+  assert!((entry.m_total - (entry.m_min + entry.m_max)).abs() < 1e-6);
+}
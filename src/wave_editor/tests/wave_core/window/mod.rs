@@ -0,0 +1,260 @@
+/*
+ MIT License
+
+ Copyright (c) 2023 Nami Reghbati
+
+ Permission is hereby granted, free of charge, to any person obtaining a copy
+ of this software and associated documentation files (the "Software"), to deal
+ in the Software without restriction, including without limitation the rights
+ to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ copies of the Software, and to permit persons to whom the Software is
+ furnished to do so, subject to the following conditions:
+
+ The above copyright notice and this permission notice shall be included in all
+ copies or substantial portions of the Software.
+
+ THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ FITNESS FOR A PARTICULAR PURPOSE AND NON INFRINGEMENT. IN NO EVENT SHALL THE
+ AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ SOFTWARE.
+*/
+
+
+use wave_core::{TraitApply, TraitHint};
+
+use wave_editor::wave_core::{EmptyApp, Engine, EnumEngineError};
+use wave_editor::wave_core::graphics::RenderTarget;
+use wave_editor::wave_core::graphics::renderer::{EnumRendererApi, Renderer};
+use wave_editor::wave_core::graphics::texture::EnumTextureFormat;
+use wave_editor::wave_core::layers::Layer;
+use wave_editor::wave_core::window::{C_DEFAULT_ASPECT_RATIO, EnumCursorMode, EnumGlProfile, EnumWindowError, EnumWindowHint, EnumWindowMode, EnumVSync, Window, WindowBuilder};
+
+#[ignore]
+#[test]
+fn test_gl_context_version() -> Result<(), EnumEngineError> {
+  let mut window = Window::default();
+  window.set_hint(EnumWindowHint::GlVersion(4, 3));
+  window.set_hint(EnumWindowHint::GlProfile(EnumGlProfile::Core));
+  
+  assert_eq!(window.get_gl_version(), Some((4, 3)));
+  assert_eq!(window.get_gl_profile(), Some(EnumGlProfile::Core));
+  
+  window.apply()?;
+  
+  // The driver is free to give us a newer compatible version, but never an older one.
+  let context_version = window.get_context_version();
+  assert!(context_version.is_some());
+  assert!(context_version.unwrap() >= (4, 3));
+  return Ok(());
+}
+
+#[ignore]
+#[test]
+fn test_monitor_selection() -> Result<(), EnumEngineError> {
+  let mut window = Window::default();
+  
+  let monitors = Window::get_monitors();
+  assert!(!monitors.is_empty());
+  assert!(monitors.iter().enumerate().all(|(index, monitor)| monitor.m_index == index));
+  
+  // Out of range monitor indices fall back to the primary monitor.
+  window.set_hint(EnumWindowHint::Monitor(monitors.len()));
+  window.set_hint(EnumWindowHint::WindowMode(EnumWindowMode::Borderless));
+  assert_eq!(window.get_monitor_index(), Some(monitors.len()));
+  
+  window.apply()?;
+  assert!(window.is_applied());
+  return Ok(());
+}
+
+#[ignore]
+#[test]
+fn test_window_icon() -> Result<(), EnumEngineError> {
+  let mut window = Window::default();
+  window.set_hint(EnumWindowHint::WindowMode(EnumWindowMode::Windowed));
+  // Deferred until the window is applied.
+  window.set_icon(&["res/textures/normal_maps/diffuse_smooth.png"])?;
+  window.apply()?;
+  
+  // Decode failures should be reported, not panic.
+  assert_eq!(window.set_icon(&["res/textures/does_not_exist.png"]), Err(EnumWindowError::ApiError));
+  assert!(window.set_icon(&["res/textures/normal_maps/diffuse_smooth.png", "res/textures/normal_maps/specular_smooth.png"]).is_ok());
+  return Ok(());
+}
+
+#[ignore]
+#[test]
+fn test_clipboard() -> Result<(), EnumEngineError> {
+  let mut window = Window::default();
+  
+  // No window, no clipboard.
+  assert_eq!(window.get_clipboard_string(), None);
+  
+  window.set_hint(EnumWindowHint::WindowMode(EnumWindowMode::Windowed));
+  window.apply()?;
+  
+  window.set_clipboard_string("Position: (1.0, 2.0, 3.0)");
+  assert_eq!(window.get_clipboard_string(), Some(String::from("Position: (1.0, 2.0, 3.0)")));
+  return Ok(());
+}
+
+#[ignore]
+#[test]
+fn test_headless_window() -> Result<(), EnumEngineError> {
+  let layer = Layer::new("Headless", EmptyApp::default());
+  let window = Window::new_headless(EnumRendererApi::OpenGL);
+  assert!(window.is_headless());
+  
+  let renderer = Renderer::new(EnumRendererApi::OpenGL);
+  let mut engine = Engine::new(window, renderer, vec![layer]);
+  engine.apply()?;
+  
+  // Never shown, and refreshing doesn't try to present anything.
+  let window = engine.get_window_mut();
+  window.show();
+  window.refresh();
+  assert_eq!(window.get_framebuffer_size(), Window::C_HEADLESS_DEFAULT_RESOLUTION);
+  
+  let target = RenderTarget::new(EnumRendererApi::OpenGL, 320, 240, &[EnumTextureFormat::Rgba], true)?;
+  let mut has_drawn = false;
+  engine.get_renderer_mut().render_to(&target, || has_drawn = true)?;
+  assert!(has_drawn);
+  
+  return engine.free();
+}
+
+#[ignore]
+#[test]
+fn test_cursor_mode() -> Result<(), EnumEngineError> {
+  let mut window = Window::default();
+  
+  // No window to grab the cursor of yet, but raw motion can be hinted beforehand.
+  assert_eq!(window.set_cursor_mode(EnumCursorMode::Disabled), Err(EnumWindowError::NoContext));
+  window.set_hint(EnumWindowHint::RawMouseMotion(false));
+  assert!(!window.is_raw_mouse_motion());
+  
+  window.set_hint(EnumWindowHint::WindowMode(EnumWindowMode::Windowed));
+  window.apply()?;
+  assert_eq!(window.get_cursor_mode(), Ok(EnumCursorMode::Normal));
+  
+  window.set_cursor_mode(EnumCursorMode::Disabled)?;
+  assert_eq!(window.get_cursor_mode(), Ok(EnumCursorMode::Disabled));
+  window.set_raw_mouse_motion(true);
+  assert!(window.is_raw_mouse_motion());
+  
+  // The hotspot must land on the image.
+  assert!(window.set_custom_cursor("res/textures/normal_maps/diffuse_smooth.png", (0, 0)).is_ok());
+  assert_eq!(window.set_custom_cursor("res/textures/normal_maps/diffuse_smooth.png", (u32::MAX, 0)),
+    Err(EnumWindowError::InvalidWindowOption));
+  window.reset_cursor()?;
+  return Ok(());
+}
+
+#[ignore]
+#[test]
+fn test_vsync_modes() -> Result<(), EnumEngineError> {
+  let mut window = Window::default();
+  window.set_hint(EnumWindowHint::VSync(false));
+  assert!(!window.is_vsync_enabled());
+  
+  window.set_hint(EnumWindowHint::WindowMode(EnumWindowMode::Windowed));
+  window.apply()?;
+  window.init_opengl_surface();
+  
+  window.set_vsync(EnumVSync::On);
+  assert_eq!(window.get_vsync(), EnumVSync::On);
+  
+  // Either supported, or falls back to regular vsync.
+  window.set_vsync(EnumVSync::Adaptive);
+  assert!(window.is_vsync_enabled());
+  
+  window.toggle_vsync();
+  assert_eq!(window.get_vsync(), EnumVSync::Off);
+  return Ok(());
+}
+
+#[test]
+fn test_aspect_ratio_at_zero_size() {
+  // Minimized windows report an empty framebuffer, which must not end up dividing by zero.
+  assert_eq!(Window::compute_aspect_ratio(1920, 0), C_DEFAULT_ASPECT_RATIO);
+  assert_eq!(Window::compute_aspect_ratio(0, 0), C_DEFAULT_ASPECT_RATIO);
+  assert_eq!(Window::compute_aspect_ratio(800, 400), 2.0);
+}
+
+#[ignore]
+#[test]
+fn test_size_limits() -> Result<(), EnumEngineError> {
+  let mut window = Window::default();
+  window.set_hint(EnumWindowHint::MinSize(0, 0));
+  window.set_hint(EnumWindowHint::MaxSize(1920, 1080));
+  window.set_hint(EnumWindowHint::WindowMode(EnumWindowMode::Windowed));
+  window.apply()?;
+  
+  // The minimum never goes below a single pixel.
+  assert_eq!(window.get_size_limits(), (Some((1, 1)), Some((1920, 1080))));
+  assert_eq!(window.set_size_limits(Some((800, 600)), Some((640, 480))), Err(EnumWindowError::InvalidWindowOption));
+  
+  window.set_aspect_ratio_lock(Some((16, 9)))?;
+  assert_eq!(window.get_aspect_ratio_lock(), Some((16, 9)));
+  assert_eq!(window.set_aspect_ratio_lock(Some((16, 0))), Err(EnumWindowError::InvalidWindowOption));
+  window.set_aspect_ratio_lock(None)?;
+  assert!(window.get_aspect_ratio() > 0.0);
+  return Ok(());
+}
+
+#[ignore]
+#[test]
+fn test_fullscreen_video_modes() -> Result<(), EnumEngineError> {
+  let mut window = Window::default();
+  window.set_hint(EnumWindowHint::WindowMode(EnumWindowMode::Windowed));
+  window.set_hint(EnumWindowHint::Resolution(1, 1));
+  window.apply()?;
+  
+  let video_modes = Window::get_video_modes(0);
+  assert!(!video_modes.is_empty());
+  assert!(video_modes.iter().all(|mode| mode.m_resolution.0 > 0 && mode.m_resolution.1 > 0));
+  
+  // No monitor supports a 1x1 video mode, which must be reported rather than falling back to the desktop mode.
+  assert_eq!(window.toggle_fullscreen(), Err(EnumWindowError::UnsupportedVideoMode));
+  return Ok(());
+}
+
+#[test]
+fn test_window_builder_validation() {
+  let builder = WindowBuilder::new(EnumRendererApi::OpenGL)
+    .resolution(1280, 720)
+    .msaa(8)
+    .vsync(true)
+    .resolution(1920, 1080);
+  assert_eq!(builder.get_hints(), &vec![EnumWindowHint::MSAA(Some(8)), EnumWindowHint::VSync(true),
+    EnumWindowHint::Resolution(1920, 1080)]);
+  assert_eq!(builder.validate(), Ok(()));
+  
+  // Conflicting hints are caught before any window gets created.
+  assert_eq!(WindowBuilder::default().window_mode(EnumWindowMode::Headless).msaa(4).validate(),
+    Err(EnumWindowError::InvalidWindowOption));
+  assert_eq!(WindowBuilder::default().resolution(0, 720).validate(), Err(EnumWindowError::InvalidWindowOption));
+  assert_eq!(WindowBuilder::default().size_limits(Some((800, 600)), Some((640, 480))).validate(),
+    Err(EnumWindowError::InvalidWindowOption));
+  assert_eq!(WindowBuilder::new(EnumRendererApi::Vulkan).gl_version(4, 6).validate(),
+    Err(EnumWindowError::InvalidWindowOption));
+}
+
+#[ignore]
+#[test]
+fn test_window_builder() -> Result<(), EnumEngineError> {
+  let mut window = WindowBuilder::new(EnumRendererApi::OpenGL)
+    .window_mode(EnumWindowMode::Windowed)
+    .size_limits(Some((640, 480)), None)
+    .vsync(false)
+    .build()?;
+  window.apply()?;
+  
+  assert!(window.is_applied());
+  assert_eq!(window.get_size_limits(), (Some((640, 480)), None));
+  assert!(!window.is_vsync_enabled());
+  return Ok(());
+}